@@ -0,0 +1,5 @@
+ALTER TABLE notification_preferences
+ADD COLUMN IF NOT EXISTS muted_all BOOLEAN NOT NULL DEFAULT FALSE,
+ADD COLUMN IF NOT EXISTS muted_types TEXT[] NOT NULL DEFAULT '{}',
+ADD COLUMN IF NOT EXISTS quiet_hours_start SMALLINT,
+ADD COLUMN IF NOT EXISTS quiet_hours_end SMALLINT;
@@ -0,0 +1,5 @@
+ALTER TABLE notification_preferences
+ADD COLUMN IF NOT EXISTS email_address VARCHAR(254),
+ADD COLUMN IF NOT EXISTS telegram_chat_id VARCHAR(32),
+ADD COLUMN IF NOT EXISTS telegram_link_code VARCHAR(16),
+ADD COLUMN IF NOT EXISTS telegram_link_expires_at TIMESTAMPTZ;
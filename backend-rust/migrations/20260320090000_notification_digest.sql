@@ -0,0 +1,15 @@
+ALTER TABLE notification_preferences
+ADD COLUMN IF NOT EXISTS digest_frequency VARCHAR(16);
+
+CREATE TABLE IF NOT EXISTS notification_digest_items (
+    id BIGSERIAL PRIMARY KEY,
+    user_address VARCHAR(66) NOT NULL,
+    notif_type VARCHAR(50) NOT NULL,
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    data JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_digest_items_user
+    ON notification_digest_items(user_address, created_at);
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS price_alerts (
+    id BIGSERIAL PRIMARY KEY,
+    user_address VARCHAR(66) NOT NULL,
+    token VARCHAR(20) NOT NULL,
+    direction VARCHAR(8) NOT NULL,
+    threshold DECIMAL(30, 10) NOT NULL,
+    recurring BOOLEAN NOT NULL DEFAULT FALSE,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    last_price DECIMAL(30, 10),
+    triggered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_price_alerts_user ON price_alerts(user_address);
+CREATE INDEX IF NOT EXISTS idx_price_alerts_active_token ON price_alerts(token) WHERE active;
@@ -0,0 +1,3 @@
+ALTER TABLE limit_orders
+    ADD COLUMN IF NOT EXISTS order_type VARCHAR(16) NOT NULL DEFAULT 'limit',
+    ADD COLUMN IF NOT EXISTS trigger_price DECIMAL(30, 18);
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS fee_ledger (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash VARCHAR(66) UNIQUE NOT NULL,
+    user_address VARCHAR(66) NOT NULL,
+    tx_type VARCHAR(20) NOT NULL,
+    token VARCHAR(66) NOT NULL,
+    fee_amount DECIMAL(30, 18) NOT NULL,
+    fee_usd DECIMAL(30, 10) NOT NULL DEFAULT 0,
+    fee_recipient VARCHAR(66),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_fee_ledger_token_time ON fee_ledger(token, created_at);
@@ -0,0 +1,17 @@
+-- Per-user record of private BTC swaps relayed by the backend.
+-- Only the user's own commitment and relayed tx hashes are kept: no ciphertext,
+-- recipient, nullifier or BTC-side data, so rows add no linkage beyond what the user holds.
+CREATE TABLE IF NOT EXISTS private_btc_swaps (
+    id BIGSERIAL PRIMARY KEY,
+    user_address VARCHAR(66) NOT NULL,
+    commitment VARCHAR(66) NOT NULL UNIQUE,
+    swap_id BIGINT,
+    status VARCHAR(16) NOT NULL DEFAULT 'initiated'
+        CHECK (status IN ('initiated', 'finalized', 'refunded')),
+    initiate_tx_hash VARCHAR(66) NOT NULL,
+    finalize_tx_hash VARCHAR(66),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_private_btc_swaps_user ON private_btc_swaps(user_address, created_at DESC);
@@ -0,0 +1,10 @@
+-- HTLC refund tracking for private BTC swaps.
+ALTER TABLE private_btc_swaps
+    ADD COLUMN IF NOT EXISTS timelock_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS refund_preimage_available BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS refund_notified_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS refund_tx_hash VARCHAR(66);
+
+CREATE INDEX IF NOT EXISTS idx_private_btc_swaps_timelock
+    ON private_btc_swaps(timelock_at)
+    WHERE status = 'initiated' AND timelock_at IS NOT NULL;
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS stake_points_accruals (
+    user_address VARCHAR(66) NOT NULL,
+    pool VARCHAR(66) NOT NULL,
+    last_accrued_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (user_address, pool)
+);
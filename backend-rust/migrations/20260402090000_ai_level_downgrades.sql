@@ -0,0 +1,18 @@
+-- Allow AI level downgrades in the audit trail (user-initiated or admin corrections)
+
+ALTER TABLE ai_level_upgrades
+    DROP CONSTRAINT IF EXISTS ai_level_upgrades_target_level_check;
+ALTER TABLE ai_level_upgrades
+    ADD CONSTRAINT ai_level_upgrades_target_level_check CHECK (target_level BETWEEN 1 AND 3);
+
+ALTER TABLE ai_level_upgrades
+    ADD COLUMN IF NOT EXISTS direction VARCHAR(16) NOT NULL DEFAULT 'upgrade'
+        CHECK (direction IN ('upgrade', 'downgrade')),
+    ADD COLUMN IF NOT EXISTS actor VARCHAR(16) NOT NULL DEFAULT 'user'
+        CHECK (actor IN ('user', 'admin')),
+    ADD COLUMN IF NOT EXISTS refund_carel DECIMAL(30, 18) NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS reason TEXT;
+
+-- Downgrades carry no on-chain payment.
+ALTER TABLE ai_level_upgrades ALTER COLUMN onchain_tx_hash DROP NOT NULL;
+ALTER TABLE ai_level_upgrades ALTER COLUMN block_number DROP NOT NULL;
@@ -0,0 +1,14 @@
+-- Per-user achievement categories recomputed from transaction stats
+
+CREATE TABLE IF NOT EXISTS user_achievements (
+    user_address VARCHAR(66) NOT NULL,
+    achievement_key VARCHAR(64) NOT NULL,
+    progress DOUBLE PRECISION NOT NULL DEFAULT 0,
+    threshold DOUBLE PRECISION NOT NULL,
+    earned_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_address, achievement_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_achievements_user_lower
+    ON user_achievements (LOWER(user_address));
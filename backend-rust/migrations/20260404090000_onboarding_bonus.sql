@@ -0,0 +1,4 @@
+-- One-time onboarding bonus marker; set together with the points grant
+
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS onboarding_bonus_granted_at TIMESTAMPTZ;
@@ -0,0 +1,15 @@
+-- On-chain referral bonus settlements (ReferralSystem BonusClaimed events)
+
+CREATE TABLE IF NOT EXISTS referral_payouts (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash VARCHAR(66) NOT NULL,
+    referrer VARCHAR(66) NOT NULL,
+    epoch BIGINT NOT NULL,
+    amount_carel DECIMAL(38, 18) NOT NULL,
+    block_number BIGINT NOT NULL,
+    settled_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, referrer, epoch)
+);
+
+CREATE INDEX IF NOT EXISTS idx_referral_payouts_referrer_lower
+    ON referral_payouts (LOWER(referrer));
@@ -0,0 +1,4 @@
+-- Covering index for windowed faucet stats; the "all" window is served by an index-only scan
+
+CREATE INDEX IF NOT EXISTS idx_faucet_claims_claimed_at
+    ON faucet_claims (claimed_at) INCLUDE (token, user_address, amount);
@@ -0,0 +1,8 @@
+-- Salted client fingerprint per faucet claim for sybil clustering
+
+ALTER TABLE faucet_claims
+    ADD COLUMN IF NOT EXISTS client_fingerprint VARCHAR(66);
+
+CREATE INDEX IF NOT EXISTS idx_faucet_claims_fingerprint
+    ON faucet_claims (client_fingerprint, token, claimed_at)
+    WHERE client_fingerprint IS NOT NULL;
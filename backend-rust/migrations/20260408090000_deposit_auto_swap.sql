@@ -0,0 +1,14 @@
+-- Optional auto-swap of settled fiat deposits into a target token
+
+ALTER TABLE deposits
+    ADD COLUMN IF NOT EXISTS target_token VARCHAR(16),
+    ADD COLUMN IF NOT EXISTS settled_amount DECIMAL(36, 18),
+    ADD COLUMN IF NOT EXISTS swap_status VARCHAR(20),
+    ADD COLUMN IF NOT EXISTS swap_tx_hash VARCHAR(66),
+    ADD COLUMN IF NOT EXISTS swap_amount_out DECIMAL(36, 18),
+    ADD COLUMN IF NOT EXISTS swap_error TEXT,
+    ADD COLUMN IF NOT EXISTS swap_updated_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_deposits_swap_pending
+    ON deposits (completed_at)
+    WHERE status = 'completed' AND swap_status = 'pending';
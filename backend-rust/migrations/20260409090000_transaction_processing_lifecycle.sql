@@ -0,0 +1,11 @@
+-- Points-processing lifecycle for transactions: attempts, last error, completion time
+
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS processing_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS last_processing_error TEXT,
+    ADD COLUMN IF NOT EXISTS last_processing_attempt_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS processed_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_unprocessed
+    ON transactions (timestamp)
+    WHERE processed = false;
@@ -0,0 +1,13 @@
+-- Webhook delivery logs: creation time, upstream response, and a per-webhook time index
+
+ALTER TABLE webhook_logs
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS response_status_code INTEGER,
+    ADD COLUMN IF NOT EXISTS response_body TEXT;
+
+UPDATE webhook_logs
+SET created_at = delivered_at
+WHERE delivered_at IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_webhook_logs_webhook_created
+    ON webhook_logs (webhook_id, created_at DESC);
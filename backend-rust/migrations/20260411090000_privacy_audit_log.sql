@@ -0,0 +1,17 @@
+-- Privacy pipeline audit log: operational metadata only (no proofs, nullifiers, recipients or users)
+
+CREATE TABLE IF NOT EXISTS privacy_audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    flow VARCHAR(32) NOT NULL,
+    verifier VARCHAR(32) NOT NULL,
+    executor_kind VARCHAR(64) NOT NULL,
+    pool_version VARCHAR(16),
+    tx_hash VARCHAR(66),
+    success BOOLEAN NOT NULL,
+    error_code VARCHAR(64),
+    duration_ms BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_privacy_audit_log_created
+    ON privacy_audit_log (created_at DESC);
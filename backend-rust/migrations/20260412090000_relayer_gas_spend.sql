@@ -0,0 +1,14 @@
+-- Per-user gas paid by the relayer pool on behalf of user-triggered submissions
+
+CREATE TABLE IF NOT EXISTS relayer_gas_spend (
+    id BIGSERIAL PRIMARY KEY,
+    user_address VARCHAR(66) NOT NULL,
+    flow VARCHAR(32) NOT NULL,
+    tx_hash VARCHAR(66) NOT NULL,
+    fee_amount NUMERIC(78, 0) NOT NULL,
+    fee_unit VARCHAR(8) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_relayer_gas_spend_user_created
+    ON relayer_gas_spend (user_address, created_at DESC);
@@ -0,0 +1,22 @@
+-- On-chain reconciliation of saved transactions: chain status and detected discrepancies
+
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS chain_status VARCHAR(16),
+    ADD COLUMN IF NOT EXISTS reconciled_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_unreconciled
+    ON transactions (timestamp)
+    WHERE chain_status IS NULL;
+
+CREATE TABLE IF NOT EXISTS transaction_reconciliation_issues (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash VARCHAR(66) NOT NULL,
+    issue VARCHAR(32) NOT NULL,
+    db_block_number BIGINT NOT NULL,
+    chain_block_number BIGINT,
+    detail TEXT,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_reconciliation_issues_detected
+    ON transaction_reconciliation_issues (detected_at DESC);
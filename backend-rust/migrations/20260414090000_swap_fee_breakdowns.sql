@@ -0,0 +1,16 @@
+-- Fee breakdown captured when a swap is executed, served by the swap receipt endpoint
+
+CREATE TABLE IF NOT EXISTS swap_fee_breakdowns (
+    tx_hash VARCHAR(66) PRIMARY KEY,
+    mode VARCHAR(16) NOT NULL,
+    base_fee DECIMAL(30, 18) NOT NULL,
+    mev_fee DECIMAL(30, 18) NOT NULL,
+    fee_before_discount DECIMAL(30, 18) NOT NULL,
+    nft_discount_percent DECIMAL(10, 4) NOT NULL DEFAULT 0,
+    stake_discount_percent DECIMAL(10, 4) NOT NULL DEFAULT 0,
+    fee_discount_percent DECIMAL(10, 4) NOT NULL DEFAULT 0,
+    nft_discount_saved DECIMAL(30, 18) NOT NULL DEFAULT 0,
+    stake_discount_saved DECIMAL(30, 18) NOT NULL DEFAULT 0,
+    net_fee DECIMAL(30, 18) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,12 @@
+-- Periodic snapshots of Garden app earnings for the earnings history endpoint
+
+CREATE TABLE IF NOT EXISTS garden_app_earnings_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    total_earnings DOUBLE PRECISION,
+    chains JSONB NOT NULL DEFAULT '{}'::jsonb,
+    payload JSONB NOT NULL,
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_garden_app_earnings_snapshots_captured
+    ON garden_app_earnings_snapshots (captured_at DESC);
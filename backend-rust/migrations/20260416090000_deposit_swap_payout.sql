@@ -0,0 +1,8 @@
+-- Track the payout leg of deposit auto-swaps so in-flight transactions are reconciled, not resent
+
+ALTER TABLE deposits
+    ADD COLUMN IF NOT EXISTS swap_payout_tx_hash VARCHAR(66);
+
+CREATE INDEX IF NOT EXISTS idx_deposits_swap_in_flight
+    ON deposits (swap_updated_at)
+    WHERE swap_status IN ('submitting', 'paying');
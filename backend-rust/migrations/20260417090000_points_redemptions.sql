@@ -0,0 +1,5 @@
+-- Points spent from finalized epochs and per-epoch reward claims
+
+ALTER TABLE points
+    ADD COLUMN IF NOT EXISTS redeemed_points DECIMAL(30, 2) DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS rewards_claimed BOOLEAN DEFAULT FALSE;
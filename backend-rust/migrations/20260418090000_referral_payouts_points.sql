@@ -0,0 +1,5 @@
+-- BonusClaimed.amount is referral points, not 18-decimal CAREL: store raw points
+
+ALTER TABLE referral_payouts RENAME COLUMN amount_carel TO amount_points;
+
+UPDATE referral_payouts SET amount_points = amount_points * 1000000000000000000;
@@ -0,0 +1,4 @@
+-- The finalize tx is sent for the recipient; keeping its hash next to the initiator's
+-- address links both sides of the swap. Finalization is recorded by status only.
+
+ALTER TABLE private_btc_swaps DROP COLUMN IF EXISTS finalize_tx_hash;
@@ -0,0 +1,7 @@
+-- Swaps whose initiate transaction reverted are marked failed so the HTLC monitor stops
+-- re-checking them.
+
+ALTER TABLE private_btc_swaps DROP CONSTRAINT IF EXISTS private_btc_swaps_status_check;
+ALTER TABLE private_btc_swaps
+    ADD CONSTRAINT private_btc_swaps_status_check
+    CHECK (status IN ('initiated', 'finalized', 'refunded', 'failed'));
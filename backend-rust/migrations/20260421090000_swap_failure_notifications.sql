@@ -0,0 +1,8 @@
+-- One row per reverted swap tx a swap.failed notification was sent for, so the
+-- verify path and the transaction reconciler never notify the same revert twice.
+
+CREATE TABLE IF NOT EXISTS swap_failure_notifications (
+    tx_hash VARCHAR(66) PRIMARY KEY,
+    user_address VARCHAR(66) NOT NULL,
+    notified_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
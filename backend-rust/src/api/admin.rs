@@ -1,16 +1,32 @@
-use super::AppState;
+use super::{
+    extract::{Pagination, TimeRange},
+    maintenance::{self, MaintenanceState},
+    relayer_access::{self, RelayerSpendSummary},
+    AppState,
+};
 use crate::{
+    db::{AiLevelDowngrade, StuckTransaction},
     error::{AppError, Result},
-    models::ApiResponse,
+    logging,
+    models::{ApiResponse, PaginatedResponse},
+    services::deposit_service::DepositInfo,
+    services::privacy_audit::{self, PrivacyAuditEntry, PrivacyAuditFilter},
+    services::tx_reconciler::{self, ReconciliationIssue},
+    services::DepositService,
 };
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::{HeaderMap, HeaderName},
     Json,
 };
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 const ADMIN_KEY_HEADER: &str = "x-admin-key";
+const STUCK_TRANSACTIONS_DEFAULT_LIMIT: i64 = 100;
+const STUCK_TRANSACTIONS_MAX_LIMIT: i64 = 1000;
+const LOG_FILTER_MAX_TTL_SECS: u64 = 24 * 3600;
 
 #[derive(Debug, Deserialize)]
 pub struct ResetPointsRequest {
@@ -26,8 +42,176 @@ pub struct ResetPointsResponse {
     pub transactions_rows_deleted: i64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AdminAiDowngradeRequest {
+    pub user_address: String,
+    pub target_level: u8,
+    pub reason: String,
+    pub refund_carel: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminAiDowngradeResponse {
+    pub user_address: String,
+    pub previous_level: u8,
+    pub current_level: u8,
+    pub refund_carel: Decimal,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeeSummaryQuery {
+    pub period: Option<String>, // day/week/month
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct FeeSummaryRow {
+    pub period_start: DateTime<Utc>,
+    pub token: String,
+    pub fee_amount: Decimal,
+    pub fee_usd: Decimal,
+    pub tx_count: i64,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct FeeTokenTotal {
+    pub token: String,
+    pub fee_amount: Decimal,
+    pub fee_usd: Decimal,
+    pub tx_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeeSummaryResponse {
+    pub period: String,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub total_fee_usd: Decimal,
+    pub by_token: Vec<FeeTokenTotal>,
+    pub by_period: Vec<FeeSummaryRow>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StuckTransactionsQuery {
+    pub older_than_secs: Option<u64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StuckTransactionsResponse {
+    pub older_than_secs: u64,
+    pub total: i64,
+    pub items: Vec<StuckTransaction>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PrivacyAuditQuery {
+    pub flow: Option<String>,
+    pub success: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RelayerSpendQuery {
+    pub user_address: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReconciliationIssuesQuery {
+    pub issue: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RetryTransactionsRequest {
+    pub tx_hashes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RetryTransactionsResponse {
+    pub requeued: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SettleDepositRequest {
+    pub deposit_id: String,
+    /// Base stablecoin credited to the backend account; defaults to the amount for USD deposits.
+    pub settled_amount: Option<Decimal>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetMaintenanceRequest {
+    pub enabled: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetLogLevelRequest {
+    /// `EnvFilter` directives, e.g. `carel_backend=debug`; omit to restore the startup filter.
+    pub filter: Option<String>,
+    /// Restore the startup filter automatically after this many seconds.
+    pub ttl_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogLevelResponse {
+    pub filter: String,
+    pub startup_filter: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverts_at: Option<DateTime<Utc>>,
+}
+
+// Internal helper that parses or transforms values for `normalize_retry_tx_hashes`.
+fn normalize_retry_tx_hashes(raw: Option<Vec<String>>) -> Result<Option<Vec<String>>> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+    let mut hashes: Vec<String> = raw
+        .iter()
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .collect();
+    hashes.sort();
+    hashes.dedup();
+    if hashes.is_empty() {
+        return Err(AppError::BadRequest(
+            "tx_hashes must contain at least one hash; omit it to retry all".to_string(),
+        ));
+    }
+    Ok(Some(hashes))
+}
+
+// Internal helper that parses or transforms values for `normalize_fee_period`.
+fn normalize_fee_period(raw: Option<&str>) -> Result<&'static str> {
+    match raw
+        .map(str::trim)
+        .unwrap_or("day")
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "day" | "daily" => Ok("day"),
+        "week" | "weekly" => Ok("week"),
+        "month" | "monthly" => Ok("month"),
+        other => Err(AppError::BadRequest(format!(
+            "Invalid period '{}'. Use day, week, or month",
+            other
+        ))),
+    }
+}
+
+// Internal helper that parses or transforms values for `parse_time_or`.
+fn parse_time_or(raw: Option<&str>, fallback: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => DateTime::parse_from_rfc3339(value)
+            .map(|parsed| parsed.with_timezone(&Utc))
+            .map_err(|_| AppError::BadRequest(format!("Invalid RFC3339 timestamp: {}", value))),
+        None => Ok(fallback),
+    }
+}
+
 // Internal helper that supports `require_admin_key` operations.
-fn require_admin_key(headers: &HeaderMap, state: &AppState) -> Result<()> {
+pub(crate) fn require_admin_key(headers: &HeaderMap, state: &AppState) -> Result<()> {
     let expected = state
         .config
         .admin_manual_key
@@ -60,6 +244,56 @@ fn require_admin_key(headers: &HeaderMap, state: &AppState) -> Result<()> {
     Ok(())
 }
 
+/// POST /api/v1/admin/ai/downgrade
+///
+/// # Notes
+/// * `refund_carel` is only recorded in the audit trail; the refund itself is settled manually.
+pub async fn downgrade_ai_level(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<AdminAiDowngradeRequest>,
+) -> Result<Json<ApiResponse<AdminAiDowngradeResponse>>> {
+    require_admin_key(&headers, &state)?;
+
+    let user_address = req.user_address.trim();
+    if user_address.is_empty() {
+        return Err(AppError::BadRequest("user_address is required".to_string()));
+    }
+    let reason = req.reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::BadRequest(
+            "reason is required for admin downgrades".to_string(),
+        ));
+    }
+    let refund_carel = req.refund_carel.unwrap_or(Decimal::ZERO);
+
+    let (previous_level, current_level) = state
+        .db
+        .downgrade_user_ai_level(AiLevelDowngrade {
+            user_address,
+            target_level: req.target_level,
+            actor: "admin",
+            refund_carel,
+            reason: Some(reason),
+        })
+        .await?;
+    tracing::info!(
+        "Admin AI level downgrade: user={}, {} -> {}, refund_carel={}, reason={}",
+        user_address,
+        previous_level,
+        current_level,
+        refund_carel,
+        reason
+    );
+
+    Ok(Json(ApiResponse::success(AdminAiDowngradeResponse {
+        user_address: user_address.to_string(),
+        previous_level,
+        current_level,
+        refund_carel,
+    })))
+}
+
 /// POST /api/v1/admin/points/reset
 pub async fn reset_points(
     State(state): State<AppState>,
@@ -141,3 +375,369 @@ pub async fn reset_points(
     };
     Ok(Json(ApiResponse::success(response)))
 }
+
+/// GET /api/v1/admin/fees/summary
+pub async fn fee_summary(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<FeeSummaryQuery>,
+) -> Result<Json<ApiResponse<FeeSummaryResponse>>> {
+    require_admin_key(&headers, &state)?;
+
+    let period = normalize_fee_period(query.period.as_deref())?;
+    let to = parse_time_or(query.to.as_deref(), Utc::now())?;
+    let from = parse_time_or(query.from.as_deref(), to - chrono::Duration::days(30))?;
+    if from > to {
+        return Err(AppError::BadRequest("from must be before to".to_string()));
+    }
+
+    let by_period = sqlx::query_as::<_, FeeSummaryRow>(
+        "SELECT date_trunc($1, created_at) AS period_start, token,
+                COALESCE(SUM(fee_amount), 0) AS fee_amount,
+                COALESCE(SUM(fee_usd), 0) AS fee_usd,
+                COUNT(*) AS tx_count
+         FROM fee_ledger
+         WHERE created_at >= $2 AND created_at <= $3
+         GROUP BY period_start, token
+         ORDER BY period_start ASC, token ASC",
+    )
+    .bind(period)
+    .bind(from)
+    .bind(to)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let by_token = sqlx::query_as::<_, FeeTokenTotal>(
+        "SELECT token,
+                COALESCE(SUM(fee_amount), 0) AS fee_amount,
+                COALESCE(SUM(fee_usd), 0) AS fee_usd,
+                COUNT(*) AS tx_count
+         FROM fee_ledger
+         WHERE created_at >= $1 AND created_at <= $2
+         GROUP BY token
+         ORDER BY fee_usd DESC",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let total_fee_usd = by_token.iter().map(|row| row.fee_usd).sum();
+    Ok(Json(ApiResponse::success(FeeSummaryResponse {
+        period: period.to_string(),
+        from,
+        to,
+        total_fee_usd,
+        by_token,
+        by_period,
+    })))
+}
+
+/// GET /api/v1/admin/transactions/stuck
+pub async fn stuck_transactions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<StuckTransactionsQuery>,
+) -> Result<Json<ApiResponse<StuckTransactionsResponse>>> {
+    require_admin_key(&headers, &state)?;
+
+    let older_than_secs = query
+        .older_than_secs
+        .unwrap_or(state.config.transaction_stuck_after_secs);
+    let older_than = i64::try_from(older_than_secs)
+        .map_err(|_| AppError::BadRequest("older_than_secs is too large".to_string()))?;
+    let limit = query
+        .limit
+        .unwrap_or(STUCK_TRANSACTIONS_DEFAULT_LIMIT)
+        .clamp(1, STUCK_TRANSACTIONS_MAX_LIMIT);
+
+    let total = state.db.count_stuck_transactions(older_than).await?;
+    let items = state.db.list_stuck_transactions(older_than, limit).await?;
+    Ok(Json(ApiResponse::success(StuckTransactionsResponse {
+        older_than_secs,
+        total,
+        items,
+    })))
+}
+
+/// POST /api/v1/admin/transactions/retry
+///
+/// # Notes
+/// * Clears the attempt counter so the point calculator picks the transactions up on its next tick.
+pub async fn retry_transactions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<RetryTransactionsRequest>,
+) -> Result<Json<ApiResponse<RetryTransactionsResponse>>> {
+    require_admin_key(&headers, &state)?;
+
+    let tx_hashes = normalize_retry_tx_hashes(req.tx_hashes)?;
+    let requeued = state
+        .db
+        .reset_transaction_processing(tx_hashes.as_deref())
+        .await?;
+    tracing::info!(
+        "Admin transaction retry: scope={}, requeued={}",
+        tx_hashes
+            .as_ref()
+            .map(|hashes| format!("{} hashes", hashes.len()))
+            .unwrap_or_else(|| "all".to_string()),
+        requeued
+    );
+    Ok(Json(ApiResponse::success(RetryTransactionsResponse {
+        requeued,
+    })))
+}
+
+/// GET /api/v1/admin/transactions/reconciliation
+///
+/// # Notes
+/// * `issue` filters to `reverted_on_chain`, `missing_on_chain` or `block_number_mismatch`.
+pub async fn reconciliation_issues(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Pagination { page, limit }: Pagination,
+    range: TimeRange,
+    Query(query): Query<ReconciliationIssuesQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<ReconciliationIssue>>>> {
+    require_admin_key(&headers, &state)?;
+
+    let issue = query
+        .issue
+        .as_deref()
+        .map(str::trim)
+        .filter(|issue| !issue.is_empty());
+    let offset = i64::from(page - 1) * i64::from(limit);
+    let (items, total) = tx_reconciler::list_issues(
+        &state.db,
+        issue,
+        range.from,
+        range.to,
+        i64::from(limit),
+        offset,
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(PaginatedResponse::new(
+        items, page, limit, total,
+    ))))
+}
+
+/// GET /api/v1/admin/privacy/audit
+///
+/// # Notes
+/// * Entries carry metadata only; see `PrivacyAuditEntry` for the fields that are never stored.
+pub async fn privacy_audit_log(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Pagination { page, limit }: Pagination,
+    range: TimeRange,
+    Query(query): Query<PrivacyAuditQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<PrivacyAuditEntry>>>> {
+    require_admin_key(&headers, &state)?;
+
+    let flow = query
+        .flow
+        .as_deref()
+        .map(str::trim)
+        .filter(|flow| !flow.is_empty());
+    let filter = PrivacyAuditFilter {
+        flow,
+        success: query.success,
+        from: range.from,
+        to: range.to,
+    };
+    let offset = i64::from(page - 1) * i64::from(limit);
+    let (items, total) =
+        privacy_audit::list_entries(&state.db, &filter, i64::from(limit), offset).await?;
+    Ok(Json(ApiResponse::success(PaginatedResponse::new(
+        items, page, limit, total,
+    ))))
+}
+
+/// GET /api/v1/admin/relayer/spend
+///
+/// # Notes
+/// * Sums the fees the relayer pool paid per user and fee unit, highest spenders first.
+pub async fn relayer_spend(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Pagination { page, limit }: Pagination,
+    range: TimeRange,
+    Query(query): Query<RelayerSpendQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<RelayerSpendSummary>>>> {
+    require_admin_key(&headers, &state)?;
+
+    let user_address = query
+        .user_address
+        .as_deref()
+        .map(str::trim)
+        .filter(|address| !address.is_empty());
+    let offset = i64::from(page - 1) * i64::from(limit);
+    let (items, total) = relayer_access::list_spend(
+        &state,
+        user_address,
+        range.from,
+        range.to,
+        i64::from(limit),
+        offset,
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(PaginatedResponse::new(
+        items, page, limit, total,
+    ))))
+}
+
+/// GET /api/v1/admin/maintenance
+pub async fn get_maintenance(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<Option<MaintenanceState>>>> {
+    require_admin_key(&headers, &state)?;
+    Ok(Json(ApiResponse::success(
+        maintenance::current_maintenance(&state).await,
+    )))
+}
+
+/// POST /api/v1/admin/maintenance
+///
+/// # Notes
+/// * Stored in Redis, so the change applies to every replica on its next request.
+pub async fn set_maintenance(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<SetMaintenanceRequest>,
+) -> Result<Json<ApiResponse<MaintenanceState>>> {
+    require_admin_key(&headers, &state)?;
+
+    let updated = maintenance::set_maintenance(&state, req.enabled, req.message).await?;
+    tracing::warn!(
+        "Admin maintenance mode {}: message={:?}",
+        if updated.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        },
+        updated.message
+    );
+    Ok(Json(ApiResponse::success(updated)))
+}
+
+/// POST /api/v1/admin/deposits/settle
+///
+/// # Notes
+/// * Records that the payment provider settled a fiat deposit, which queues its auto-swap.
+/// * Non-USD deposits (IDR/QRIS) must pass the stablecoin actually received as `settled_amount`.
+pub async fn settle_deposit(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<SettleDepositRequest>,
+) -> Result<Json<ApiResponse<DepositInfo>>> {
+    require_admin_key(&headers, &state)?;
+
+    let deposit_id = req.deposit_id.trim();
+    if deposit_id.is_empty() {
+        return Err(AppError::BadRequest("deposit_id is required".to_string()));
+    }
+    if req
+        .settled_amount
+        .is_some_and(|amount| amount <= Decimal::ZERO)
+    {
+        return Err(AppError::BadRequest(
+            "settled_amount must be positive".to_string(),
+        ));
+    }
+    let service = DepositService::new(state.db.clone(), state.config.clone());
+    let deposit = service.mark_settled(deposit_id, req.settled_amount).await?;
+    tracing::info!(
+        "Admin settled deposit {}: settled_amount={:?}",
+        deposit_id,
+        req.settled_amount
+    );
+    Ok(Json(ApiResponse::success(deposit)))
+}
+
+/// GET /api/v1/admin/log-level
+pub async fn get_log_level(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<LogLevelResponse>>> {
+    require_admin_key(&headers, &state)?;
+    Ok(Json(ApiResponse::success(LogLevelResponse {
+        filter: logging::current_filter()?,
+        startup_filter: logging::startup_filter(),
+        previous_filter: None,
+        reverts_at: None,
+    })))
+}
+
+/// POST /api/v1/admin/log-level
+///
+/// # Notes
+/// * Applies to this replica only; the change is logged at `warn` so it survives most filters.
+pub async fn set_log_level(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<SetLogLevelRequest>,
+) -> Result<Json<ApiResponse<LogLevelResponse>>> {
+    require_admin_key(&headers, &state)?;
+
+    let ttl_secs = req.ttl_secs.filter(|ttl| *ttl > 0);
+    if ttl_secs.is_some_and(|ttl| ttl > LOG_FILTER_MAX_TTL_SECS) {
+        return Err(AppError::BadRequest(format!(
+            "ttl_secs must be <= {}",
+            LOG_FILTER_MAX_TTL_SECS
+        )));
+    }
+    let startup_filter = logging::startup_filter();
+    let target = req
+        .filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|filter| !filter.is_empty())
+        .unwrap_or(&startup_filter)
+        .to_string();
+    let previous = logging::set_filter(&target, ttl_secs.map(std::time::Duration::from_secs))?;
+    tracing::warn!(
+        "Admin log filter change: '{}' -> '{}' (revert after {:?}s)",
+        previous,
+        target,
+        ttl_secs
+    );
+
+    Ok(Json(ApiResponse::success(LogLevelResponse {
+        filter: logging::current_filter()?,
+        startup_filter,
+        previous_filter: Some(previous),
+        reverts_at: ttl_secs.map(|ttl| Utc::now() + chrono::Duration::seconds(ttl as i64)),
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that parses or transforms values for `normalize_fee_period_accepts_aliases`.
+    fn normalize_fee_period_accepts_aliases() {
+        assert_eq!(normalize_fee_period(None).unwrap(), "day");
+        assert_eq!(normalize_fee_period(Some("Weekly")).unwrap(), "week");
+        assert_eq!(normalize_fee_period(Some("month")).unwrap(), "month");
+        assert!(normalize_fee_period(Some("year")).is_err());
+    }
+
+    #[test]
+    // Internal helper that parses or transforms values for `normalize_retry_tx_hashes_dedups_and_rejects_empty`.
+    fn normalize_retry_tx_hashes_dedups_and_rejects_empty() {
+        assert_eq!(normalize_retry_tx_hashes(None).unwrap(), None);
+        assert_eq!(
+            normalize_retry_tx_hashes(Some(vec![
+                " 0xabc ".to_string(),
+                "0xabc".to_string(),
+                "0x1".to_string(),
+            ]))
+            .unwrap(),
+            Some(vec!["0x1".to_string(), "0xabc".to_string()])
+        );
+        assert!(normalize_retry_tx_hashes(Some(vec!["  ".to_string()])).is_err());
+    }
+}
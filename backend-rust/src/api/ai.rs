@@ -1,15 +1,18 @@
 use super::{require_starknet_user, require_user, AppState};
 use crate::indexer::starknet_client::StarknetClient;
 use crate::services::onchain::{
-    felt_to_u128, parse_felt, resolve_backend_account, OnchainInvoker, OnchainReader,
+    configured_carel_burn_address, felt_to_u128, parse_felt, resolve_backend_account,
+    verify_carel_burn, OnchainInvoker, OnchainReader,
 };
 use crate::services::relayer::RelayerService;
 use crate::{
+    db::{AiLevelDowngrade, AiLevelUpgradeRecord},
     error::{AppError, Result},
     models::{ApiResponse, Transaction},
     services::ai_service::{
         classify_command_scope, has_llm_provider_configured, AIGuardScope, AIResponse, AIService,
     },
+    services::discount_service::ai_level_points_bonus_percent,
 };
 use axum::extract::Query;
 use axum::{extract::State, http::HeaderMap, Json};
@@ -18,13 +21,12 @@ use redis::AsyncCommands;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use starknet_core::types::typed_data::TypedData;
-use starknet_core::types::{
-    Call, ExecutionResult, Felt as CoreFelt, FunctionCall, InvokeTransaction,
-    Transaction as StarknetTransaction, TransactionFinalityStatus,
-};
+use starknet_core::types::{Call, Felt as CoreFelt, FunctionCall};
 use starknet_core::utils::{get_selector_from_name, get_storage_var_address};
 use starknet_crypto::{poseidon_hash_many, Felt as CryptoFelt};
 use starknet_signers::SigningKey;
+use std::collections::HashMap;
+use std::sync::OnceLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::time::{sleep, Duration};
 
@@ -36,6 +38,10 @@ const AI_EXECUTOR_READY_POLL_ATTEMPTS: usize = 12;
 const AI_EXECUTOR_READY_POLL_DELAY_MS: u64 = 1_500;
 const DEFAULT_AI_EXECUTOR_TARGET_RATE_LIMIT: u128 = 1_000;
 const EXECUTOR_HASH_WINDOW_TTL_SECONDS: u64 = 4 * 60;
+const LOCAL_RATE_COUNTER_MAX_ENTRIES: usize = 50_000;
+
+static LOCAL_RATE_COUNTERS: OnceLock<std::sync::Mutex<HashMap<String, (u64, i64)>>> =
+    OnceLock::new();
 
 #[derive(Debug, Deserialize)]
 pub struct AICommandRequest {
@@ -115,6 +121,22 @@ pub struct AILevelResponse {
     pub burn_address: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct AILevelBenefits {
+    pub level: u8,
+    pub total_cost_carel: String,
+    pub points_bonus_percent: f64,
+    pub rate_limit_per_window: u32,
+    pub features: Vec<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AILevelsResponse {
+    pub rate_limit_window_seconds: u64,
+    pub global_rate_limit_per_window: u32,
+    pub levels: Vec<AILevelBenefits>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AIUpgradeLevelRequest {
     pub target_level: u8,
@@ -131,11 +153,16 @@ pub struct AIUpgradeLevelResponse {
     pub block_number: i64,
 }
 
-#[derive(Debug, Clone)]
-struct ParsedExecuteCall {
-    to: CoreFelt,
-    selector: CoreFelt,
-    calldata: Vec<CoreFelt>,
+#[derive(Debug, Deserialize)]
+pub struct AIDowngradeLevelRequest {
+    pub target_level: u8,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AIDowngradeLevelResponse {
+    pub previous_level: u8,
+    pub current_level: u8,
+    pub refund_carel: String,
 }
 
 // Internal helper that builds inputs for `build_command`.
@@ -212,10 +239,7 @@ fn normalize_onchain_tx_hash(raw: &str) -> Result<String> {
 // Internal helper that runs side-effecting logic for `ensure_ai_level_scope`.
 fn ensure_ai_level_scope(level: u8, command: &str) -> Result<()> {
     let scope = classify_command_scope(command);
-    if level >= 3
-        && matches!(scope, AIGuardScope::SwapBridge)
-        && is_bridge_command(command)
-    {
+    if level >= 3 && matches!(scope, AIGuardScope::SwapBridge) && is_bridge_command(command) {
         return Err(AppError::BadRequest(
             "Level 3 bridge is not available yet. Use Level 2 for bridge commands for now; private L3 bridge will be added later."
                 .to_string(),
@@ -265,6 +289,34 @@ fn ensure_ai_level_scope(level: u8, command: &str) -> Result<()> {
     Ok(())
 }
 
+// Internal helper that lists what each level unlocks; keep in sync with `ensure_ai_level_scope`
+// and `ensure_privacy_level_scope`.
+fn ai_level_features(level: u8) -> Vec<&'static str> {
+    match level {
+        1 => vec!["read_only"],
+        2 => vec![
+            "read_only",
+            "swap",
+            "bridge",
+            "stake",
+            "claim",
+            "limit_order",
+        ],
+        3 => vec![
+            "read_only",
+            "swap",
+            "stake",
+            "claim",
+            "limit_order",
+            "unstake",
+            "portfolio",
+            "alerts",
+            "private_execution",
+        ],
+        _ => Vec::new(),
+    }
+}
+
 // Internal helper that supports `is_bridge_command` operations.
 fn is_bridge_command(command: &str) -> bool {
     let lower = command.to_ascii_lowercase();
@@ -753,6 +805,57 @@ fn time_bucket(window_seconds: u64) -> u64 {
     now / window
 }
 
+// Internal helper that increments a rate-limit counter in Redis, or in process memory while
+// Redis is unavailable (per-replica only, but requests keep being served).
+async fn ai_rate_counter_incr(
+    state: &AppState,
+    key: &str,
+    bucket: u64,
+    window_seconds: u64,
+) -> i64 {
+    if state.redis_available() {
+        let mut conn = state.redis.clone();
+        match conn.incr::<_, _, i64>(key, 1_i64).await {
+            Ok(count) => {
+                if count == 1 {
+                    let _: std::result::Result<bool, redis::RedisError> =
+                        conn.expire(key, window_seconds as i64).await;
+                }
+                return count;
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "AI rate limiter Redis incr failed, using local counter: {}",
+                    err
+                );
+                state.set_redis_available(false);
+            }
+        }
+    }
+    local_rate_counter_incr(key, bucket)
+}
+
+// Internal helper that supports `local_rate_counter_incr` operations.
+fn local_rate_counter_incr(key: &str, bucket: u64) -> i64 {
+    let Ok(mut counters) = local_rate_counters().lock() else {
+        return 0;
+    };
+    if counters.len() > LOCAL_RATE_COUNTER_MAX_ENTRIES {
+        counters.retain(|_, (entry_bucket, _)| *entry_bucket >= bucket);
+    }
+    let entry = counters.entry(key.to_string()).or_insert((bucket, 0));
+    if entry.0 != bucket {
+        *entry = (bucket, 0);
+    }
+    entry.1 += 1;
+    entry.1
+}
+
+// Internal helper that supports `local_rate_counters` operations.
+fn local_rate_counters() -> &'static std::sync::Mutex<HashMap<String, (u64, i64)>> {
+    LOCAL_RATE_COUNTERS.get_or_init(|| std::sync::Mutex::new(HashMap::new()))
+}
+
 // Internal helper that supports `enforce_ai_rate_limit` operations.
 async fn enforce_ai_rate_limit(
     state: &AppState,
@@ -770,31 +873,8 @@ async fn enforce_ai_rate_limit(
     let level_key = format!("ai:rl:l{}:{}:{}:{}", level, mode, normalized_user, bucket);
     let global_key = format!("ai:rl:all:{}:{}", normalized_user, bucket);
 
-    let mut conn = state.redis.clone();
-
-    let level_count: i64 = match conn.incr(&level_key, 1_i64).await {
-        Ok(value) => value,
-        Err(err) => {
-            tracing::warn!("AI rate limiter skipped (level incr failed): {}", err);
-            return Ok(());
-        }
-    };
-    if level_count == 1 {
-        let _: std::result::Result<bool, redis::RedisError> =
-            conn.expire(&level_key, window_seconds as i64).await;
-    }
-
-    let global_count: i64 = match conn.incr(&global_key, 1_i64).await {
-        Ok(value) => value,
-        Err(err) => {
-            tracing::warn!("AI rate limiter skipped (global incr failed): {}", err);
-            return Ok(());
-        }
-    };
-    if global_count == 1 {
-        let _: std::result::Result<bool, redis::RedisError> =
-            conn.expire(&global_key, window_seconds as i64).await;
-    }
+    let level_count = ai_rate_counter_incr(state, &level_key, bucket, window_seconds).await;
+    let global_count = ai_rate_counter_incr(state, &global_key, bucket, window_seconds).await;
 
     if level_count > level_limit || global_count > global_limit {
         tracing::warn!(
@@ -1600,12 +1680,7 @@ pub async fn ensure_executor_ready(
 
 // Internal helper that supports `configured_ai_upgrade_payment_address` operations.
 fn configured_ai_upgrade_payment_address(config: &crate::config::Config) -> Option<String> {
-    config
-        .treasury_address
-        .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty() && !value.starts_with("0x0000"))
-        .map(str::to_string)
+    configured_carel_burn_address(config)
 }
 
 /// GET /api/v1/ai/level
@@ -1637,6 +1712,28 @@ pub async fn get_ai_level(
     })))
 }
 
+/// GET /api/v1/ai/levels
+pub async fn get_ai_levels(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<AILevelsResponse>>> {
+    let levels = (1..=3)
+        .map(|level| AILevelBenefits {
+            level,
+            total_cost_carel: wei_to_carel_string(
+                total_upgrade_cost_wei_for_level(level).unwrap_or(0),
+            ),
+            points_bonus_percent: ai_level_points_bonus_percent(level),
+            rate_limit_per_window: ai_level_limit(&state, level),
+            features: ai_level_features(level),
+        })
+        .collect();
+    Ok(Json(ApiResponse::success(AILevelsResponse {
+        rate_limit_window_seconds: state.config.ai_rate_limit_window_seconds,
+        global_rate_limit_per_window: state.config.ai_rate_limit_global_per_window,
+        levels,
+    })))
+}
+
 /// POST /api/v1/ai/upgrade
 pub async fn upgrade_ai_level(
     State(state): State<AppState>,
@@ -1644,6 +1741,12 @@ pub async fn upgrade_ai_level(
     Json(req): Json<AIUpgradeLevelRequest>,
 ) -> Result<Json<ApiResponse<AIUpgradeLevelResponse>>> {
     let auth_subject = require_user(&headers, &state).await?;
+    let tx_hash = normalize_onchain_tx_hash(&req.onchain_tx_hash)?;
+    // Re-submitting an already recorded payment returns that upgrade instead of failing.
+    if let Some(existing) = state.db.find_ai_level_upgrade_by_tx(&tx_hash).await? {
+        return existing_upgrade_response(&state, &auth_subject, existing, tx_hash).await;
+    }
+
     let previous_level = state.db.get_user_ai_level(&auth_subject).await?;
     if !(2..=3).contains(&req.target_level) {
         return Err(AppError::BadRequest(
@@ -1665,11 +1768,15 @@ pub async fn upgrade_ai_level(
         ));
     }
 
-    let tx_hash = normalize_onchain_tx_hash(&req.onchain_tx_hash)?;
-    if state.db.get_transaction(&tx_hash).await?.is_some() {
-        return Err(AppError::BadRequest(
-            "onchain_tx_hash has already been used".to_string(),
-        ));
+    if let Some(existing_tx) = state.db.get_transaction(&tx_hash).await? {
+        // A prior attempt may have saved the payment before recording the upgrade.
+        let same_pending_upgrade = existing_tx.tx_type == "ai_level_upgrade"
+            && existing_tx.user_address.eq_ignore_ascii_case(&auth_subject);
+        if !same_pending_upgrade {
+            return Err(AppError::BadRequest(
+                "onchain_tx_hash has already been used".to_string(),
+            ));
+        }
     }
     let block_number =
         verify_ai_upgrade_payment_tx_hash(&state, &auth_subject, &tx_hash, required_wei).await?;
@@ -1707,6 +1814,10 @@ pub async fn upgrade_ai_level(
         .await
     {
         if is_unique_violation(&err) {
+            // A concurrent retry recorded the same payment first.
+            if let Some(existing) = state.db.find_ai_level_upgrade_by_tx(&tx_hash).await? {
+                return existing_upgrade_response(&state, &auth_subject, existing, tx_hash).await;
+            }
             return Err(AppError::BadRequest(
                 "onchain_tx_hash has already been used for AI upgrade".to_string(),
             ));
@@ -1728,141 +1839,73 @@ pub async fn upgrade_ai_level(
     })))
 }
 
-// Internal helper that checks conditions for `is_unique_violation`.
-fn is_unique_violation(err: &AppError) -> bool {
-    match err {
-        AppError::Database(sqlx::Error::Database(db_err)) => {
-            db_err.code().as_deref() == Some("23505")
-        }
-        _ => false,
-    }
-}
-
-// Internal helper that supports `felt_to_usize` operations.
-fn felt_to_usize(value: &CoreFelt, field_name: &str) -> Result<usize> {
-    let raw = felt_to_u128(value).map_err(|_| {
-        AppError::BadRequest(format!(
-            "Invalid invoke calldata: {field_name} is not a valid number"
-        ))
-    })?;
-    usize::try_from(raw).map_err(|_| {
-        AppError::BadRequest(format!(
-            "Invalid invoke calldata: {field_name} exceeds supported size"
-        ))
-    })
+/// POST /api/v1/ai/downgrade
+///
+/// # Notes
+/// * Upgrade payments are burned on-chain, so a user-initiated downgrade never refunds CAREL.
+pub async fn downgrade_ai_level(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<AIDowngradeLevelRequest>,
+) -> Result<Json<ApiResponse<AIDowngradeLevelResponse>>> {
+    let auth_subject = require_user(&headers, &state).await?;
+    let (previous_level, current_level) = state
+        .db
+        .downgrade_user_ai_level(AiLevelDowngrade {
+            user_address: &auth_subject,
+            target_level: req.target_level,
+            actor: "user",
+            refund_carel: Decimal::ZERO,
+            reason: None,
+        })
+        .await?;
+    Ok(Json(ApiResponse::success(AIDowngradeLevelResponse {
+        previous_level,
+        current_level,
+        refund_carel: "0".to_string(),
+    })))
 }
 
-// Internal helper that parses or transforms values for `parse_execute_calls_offset`.
-fn parse_execute_calls_offset(calldata: &[CoreFelt]) -> Result<Vec<ParsedExecuteCall>> {
-    if calldata.is_empty() {
-        return Err(AppError::BadRequest(
-            "Invalid invoke calldata: empty calldata".to_string(),
-        ));
-    }
-    let calls_len = felt_to_usize(&calldata[0], "calls_len")?;
-    let header_start = 1usize;
-    let header_width = 4usize;
-    let headers_end = header_start
-        .checked_add(calls_len.checked_mul(header_width).ok_or_else(|| {
-            AppError::BadRequest("Invalid invoke calldata: calls_len overflow".to_string())
-        })?)
-        .ok_or_else(|| {
-            AppError::BadRequest("Invalid invoke calldata: malformed headers".to_string())
-        })?;
-
-    if calldata.len() <= headers_end {
-        return Err(AppError::BadRequest(
-            "Invalid invoke calldata: missing calldata length".to_string(),
-        ));
-    }
-
-    let flattened_len = felt_to_usize(&calldata[headers_end], "flattened_len")?;
-    let flattened_start = headers_end + 1;
-    let flattened_end = flattened_start.checked_add(flattened_len).ok_or_else(|| {
-        AppError::BadRequest("Invalid invoke calldata: flattened overflow".to_string())
-    })?;
-    if calldata.len() < flattened_end {
+// Internal helper that replays a recorded upgrade for an idempotent re-submission.
+async fn existing_upgrade_response(
+    state: &AppState,
+    auth_subject: &str,
+    existing: AiLevelUpgradeRecord,
+    onchain_tx_hash: String,
+) -> Result<Json<ApiResponse<AIUpgradeLevelResponse>>> {
+    if !existing.user_address.eq_ignore_ascii_case(auth_subject) {
         return Err(AppError::BadRequest(
-            "Invalid invoke calldata: flattened segment out of bounds".to_string(),
+            "onchain_tx_hash has already been used for AI upgrade".to_string(),
         ));
     }
-
-    let flattened = &calldata[flattened_start..flattened_end];
-    let mut calls = Vec::with_capacity(calls_len);
-    for idx in 0..calls_len {
-        let offset = header_start + idx * header_width;
-        let to = calldata[offset];
-        let selector = calldata[offset + 1];
-        let data_offset = felt_to_usize(&calldata[offset + 2], "data_offset")?;
-        let data_len = felt_to_usize(&calldata[offset + 3], "data_len")?;
-        let data_end = data_offset.checked_add(data_len).ok_or_else(|| {
-            AppError::BadRequest("Invalid invoke calldata: data segment overflow".to_string())
-        })?;
-        if data_end > flattened.len() {
-            return Err(AppError::BadRequest(
-                "Invalid invoke calldata: call segment out of bounds".to_string(),
-            ));
-        }
-        calls.push(ParsedExecuteCall {
-            to,
-            selector,
-            calldata: flattened[data_offset..data_end].to_vec(),
-        });
-    }
-    Ok(calls)
+    let target_level = existing.target_level.clamp(1, 3) as u8;
+    // Re-apply the paid level after a partial failure, but never undo a later downgrade.
+    let current_level = if existing.superseded_by_downgrade {
+        state.db.get_user_ai_level(auth_subject).await?
+    } else {
+        state
+            .db
+            .upsert_user_ai_level(auth_subject, target_level)
+            .await?
+    };
+    Ok(Json(ApiResponse::success(AIUpgradeLevelResponse {
+        previous_level: existing.previous_level.clamp(1, 3) as u8,
+        current_level,
+        target_level,
+        burned_carel: existing.payment_carel.normalize().to_string(),
+        onchain_tx_hash,
+        block_number: existing.block_number.unwrap_or_default(),
+    })))
 }
 
-// Internal helper that parses or transforms values for `parse_execute_calls_inline`.
-fn parse_execute_calls_inline(calldata: &[CoreFelt]) -> Result<Vec<ParsedExecuteCall>> {
-    if calldata.is_empty() {
-        return Err(AppError::BadRequest(
-            "Invalid invoke calldata: empty calldata".to_string(),
-        ));
-    }
-    let calls_len = felt_to_usize(&calldata[0], "calls_len")?;
-    let mut cursor = 1usize;
-    let mut calls = Vec::with_capacity(calls_len);
-
-    for _ in 0..calls_len {
-        let header_end = cursor.checked_add(3).ok_or_else(|| {
-            AppError::BadRequest("Invalid invoke calldata: malformed call header".to_string())
-        })?;
-        if calldata.len() < header_end {
-            return Err(AppError::BadRequest(
-                "Invalid invoke calldata: missing inline call header".to_string(),
-            ));
-        }
-
-        let to = calldata[cursor];
-        let selector = calldata[cursor + 1];
-        let data_len = felt_to_usize(&calldata[cursor + 2], "data_len")?;
-        let data_start = cursor + 3;
-        let data_end = data_start.checked_add(data_len).ok_or_else(|| {
-            AppError::BadRequest("Invalid invoke calldata: inline data overflow".to_string())
-        })?;
-        if data_end > calldata.len() {
-            return Err(AppError::BadRequest(
-                "Invalid invoke calldata: inline data out of bounds".to_string(),
-            ));
+// Internal helper that checks conditions for `is_unique_violation`.
+fn is_unique_violation(err: &AppError) -> bool {
+    match err {
+        AppError::Database(sqlx::Error::Database(db_err)) => {
+            db_err.code().as_deref() == Some("23505")
         }
-
-        calls.push(ParsedExecuteCall {
-            to,
-            selector,
-            calldata: calldata[data_start..data_end].to_vec(),
-        });
-        cursor = data_end;
-    }
-
-    Ok(calls)
-}
-
-// Internal helper that parses or transforms values for `parse_execute_calls`.
-fn parse_execute_calls(calldata: &[CoreFelt]) -> Result<Vec<ParsedExecuteCall>> {
-    if let Ok(calls) = parse_execute_calls_offset(calldata) {
-        return Ok(calls);
+        _ => false,
     }
-    parse_execute_calls_inline(calldata)
 }
 
 // Internal helper that fetches data for `resolve_allowed_starknet_senders_async`.
@@ -1896,70 +1939,6 @@ async fn resolve_allowed_starknet_senders_async(
     Ok(out)
 }
 
-// Internal helper that supports `verify_ai_upgrade_fee_invoke_payload` operations.
-fn verify_ai_upgrade_fee_invoke_payload(
-    tx: &StarknetTransaction,
-    allowed_senders: &[CoreFelt],
-    carel_token: CoreFelt,
-    payment_address: CoreFelt,
-    min_amount_wei: u128,
-) -> Result<()> {
-    let invoke = match tx {
-        StarknetTransaction::Invoke(invoke) => invoke,
-        _ => {
-            return Err(AppError::BadRequest(
-                "onchain_tx_hash must be an INVOKE transaction".to_string(),
-            ))
-        }
-    };
-
-    let transfer_selector = get_selector_from_name("transfer")
-        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
-
-    let (sender, calldata) = match invoke {
-        InvokeTransaction::V1(tx) => (tx.sender_address, tx.calldata.as_slice()),
-        InvokeTransaction::V3(tx) => (tx.sender_address, tx.calldata.as_slice()),
-        InvokeTransaction::V0(_) => {
-            return Err(AppError::BadRequest(
-                "onchain_tx_hash uses unsupported INVOKE v0".to_string(),
-            ))
-        }
-    };
-
-    if !allowed_senders.contains(&sender) {
-        return Err(AppError::BadRequest(
-            "onchain_tx_hash sender does not match authenticated Starknet user".to_string(),
-        ));
-    }
-
-    let calls = parse_execute_calls(calldata)?;
-    for call in calls {
-        if call.to != carel_token || call.selector != transfer_selector {
-            continue;
-        }
-        if call.calldata.len() < 3 {
-            continue;
-        }
-        let recipient = call.calldata[0];
-        if recipient != payment_address {
-            continue;
-        }
-        let low = felt_to_u128(&call.calldata[1]).unwrap_or(0);
-        let high = felt_to_u128(&call.calldata[2]).unwrap_or(0);
-        if high != 0 {
-            continue;
-        }
-        if low >= min_amount_wei {
-            return Ok(());
-        }
-    }
-
-    Err(AppError::BadRequest(format!(
-        "onchain_tx_hash must include CAREL transfer >= {} to configured treasury address",
-        wei_to_carel_string(min_amount_wei)
-    )))
-}
-
 // Internal helper that supports `verify_ai_upgrade_payment_tx_hash` operations.
 async fn verify_ai_upgrade_payment_tx_hash(
     state: &AppState,
@@ -1967,82 +1946,9 @@ async fn verify_ai_upgrade_payment_tx_hash(
     tx_hash: &str,
     min_amount_wei: u128,
 ) -> Result<i64> {
-    let payment_address =
-        configured_ai_upgrade_payment_address(&state.config).ok_or_else(|| {
-            AppError::BadRequest(
-                "TREASURY_ADDRESS is not configured. Cannot verify AI upgrade payment.".to_string(),
-            )
-        })?;
-    let carel_token = state.config.carel_token_address.trim();
-    if carel_token.is_empty() || carel_token.starts_with("0x0000") {
-        return Err(AppError::BadRequest(
-            "CAREL_TOKEN_ADDRESS is not configured".to_string(),
-        ));
-    }
-
     let allowed_senders = resolve_allowed_starknet_senders_async(state, auth_subject).await?;
-    let reader = OnchainReader::from_config(&state.config)?;
-    let tx_hash_felt = parse_felt(tx_hash)?;
-    let carel_token_felt = parse_felt(carel_token)?;
-    let payment_felt = parse_felt(&payment_address)?;
-    let mut last_error = String::new();
-
-    for attempt in 0..5 {
-        let tx = match reader.get_transaction(&tx_hash_felt).await {
-            Ok(value) => value,
-            Err(err) => {
-                last_error = err.to_string();
-                if attempt < 4 {
-                    sleep(Duration::from_millis(900)).await;
-                    continue;
-                }
-                break;
-            }
-        };
-
-        verify_ai_upgrade_fee_invoke_payload(
-            &tx,
-            &allowed_senders,
-            carel_token_felt,
-            payment_felt,
-            min_amount_wei,
-        )?;
-
-        match reader.get_transaction_receipt(&tx_hash_felt).await {
-            Ok(receipt) => {
-                if let ExecutionResult::Reverted { reason } = receipt.receipt.execution_result() {
-                    return Err(AppError::BadRequest(format!(
-                        "onchain_tx_hash reverted: {}",
-                        reason
-                    )));
-                }
-                if matches!(
-                    receipt.receipt.finality_status(),
-                    TransactionFinalityStatus::PreConfirmed
-                ) {
-                    last_error = "transaction still pre-confirmed".to_string();
-                    if attempt < 4 {
-                        sleep(Duration::from_millis(900)).await;
-                        continue;
-                    }
-                    break;
-                }
-                return Ok(receipt.block.block_number() as i64);
-            }
-            Err(err) => {
-                last_error = err.to_string();
-                if attempt < 4 {
-                    sleep(Duration::from_millis(900)).await;
-                    continue;
-                }
-            }
-        }
-    }
-
-    Err(AppError::BadRequest(format!(
-        "onchain_tx_hash not confirmed on Starknet RPC: {}",
-        last_error
-    )))
+    let burn = verify_carel_burn(&state.config, tx_hash, min_amount_wei, &allowed_senders).await?;
+    Ok(burn.block_number as i64)
 }
 
 // Internal helper that runs side-effecting logic for `ensure_onchain_action`.
@@ -2273,6 +2179,35 @@ mod tests {
         );
     }
 
+    #[test]
+    // Internal helper that supports `ai_level_features_match_scope_guards` operations.
+    fn ai_level_features_match_scope_guards() {
+        for level in 1..=3 {
+            let features = ai_level_features(level);
+            assert_eq!(
+                features.contains(&"swap"),
+                ensure_ai_level_scope(level, "swap 1 STRK to CAREL").is_ok()
+            );
+            assert_eq!(
+                features.contains(&"bridge"),
+                ensure_ai_level_scope(level, "bridge 1 ETH to starknet").is_ok()
+            );
+            assert_eq!(
+                features.contains(&"private_execution"),
+                ensure_privacy_level_scope(level, "private swap 10 STRK to WBTC").is_ok()
+            );
+        }
+    }
+
+    #[test]
+    // Internal helper that supports `local_rate_counter_resets_per_bucket` operations.
+    fn local_rate_counter_resets_per_bucket() {
+        let key = "ai:rl:test:local_rate_counter_resets_per_bucket";
+        assert_eq!(local_rate_counter_incr(key, 7), 1);
+        assert_eq!(local_rate_counter_incr(key, 7), 2);
+        assert_eq!(local_rate_counter_incr(key, 8), 1);
+    }
+
     #[test]
     // Internal helper that supports `normalize_onchain_tx_hash_validates_hex_format` operations.
     fn normalize_onchain_tx_hash_validates_hex_format() {
@@ -0,0 +1,140 @@
+use super::{require_user, AppState};
+use crate::{
+    error::{AppError, Result},
+    models::{ApiResponse, PriceAlert},
+};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+const MAX_ACTIVE_ALERTS_PER_USER: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePriceAlertRequest {
+    pub token: String,
+    pub direction: String,
+    pub threshold: Decimal,
+    #[serde(default)]
+    pub recurring: bool,
+}
+
+// Internal helper that parses or transforms values for `normalize_direction`.
+fn normalize_direction(raw: &str) -> Result<&'static str> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "above" | "up" => Ok("above"),
+        "below" | "down" => Ok("below"),
+        _ => Err(AppError::BadRequest(
+            "direction must be 'above' or 'below'".to_string(),
+        )),
+    }
+}
+
+/// POST /api/v1/alerts
+pub async fn create(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<CreatePriceAlertRequest>,
+) -> Result<Json<ApiResponse<PriceAlert>>> {
+    let user_address = require_user(&headers, &state).await?;
+    let token = req.token.trim().to_ascii_uppercase();
+    if !state.config.price_tokens_list().contains(&token) {
+        return Err(AppError::InvalidToken);
+    }
+    let direction = normalize_direction(&req.direction)?;
+    if req.threshold <= Decimal::ZERO {
+        return Err(AppError::BadRequest(
+            "threshold must be greater than zero".to_string(),
+        ));
+    }
+
+    let active_count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM price_alerts WHERE user_address = $1 AND active")
+            .bind(&user_address)
+            .fetch_one(state.db.pool())
+            .await?;
+    if active_count >= MAX_ACTIVE_ALERTS_PER_USER {
+        return Err(AppError::BadRequest(format!(
+            "At most {} active price alerts per user",
+            MAX_ACTIVE_ALERTS_PER_USER
+        )));
+    }
+
+    // Seed the reference price so the first tick after creation can detect a crossing.
+    let last_price: Option<Decimal> = sqlx::query_scalar(
+        "SELECT close FROM price_history WHERE token = $1 ORDER BY timestamp DESC LIMIT 1",
+    )
+    .bind(&token)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let alert = sqlx::query_as::<_, PriceAlert>(
+        "INSERT INTO price_alerts (user_address, token, direction, threshold, recurring, last_price)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         RETURNING id, user_address, token, direction, threshold, recurring, active,
+                   last_price, triggered_at, created_at",
+    )
+    .bind(&user_address)
+    .bind(&token)
+    .bind(direction)
+    .bind(req.threshold)
+    .bind(req.recurring)
+    .bind(last_price)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok(Json(ApiResponse::success(alert)))
+}
+
+/// GET /api/v1/alerts
+pub async fn list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<Vec<PriceAlert>>>> {
+    let user_address = require_user(&headers, &state).await?;
+    let alerts = sqlx::query_as::<_, PriceAlert>(
+        "SELECT id, user_address, token, direction, threshold, recurring, active,
+                last_price, triggered_at, created_at
+         FROM price_alerts WHERE user_address = $1 ORDER BY created_at DESC",
+    )
+    .bind(&user_address)
+    .fetch_all(state.db.pool())
+    .await?;
+    Ok(Json(ApiResponse::success(alerts)))
+}
+
+/// DELETE /api/v1/alerts/{id}
+pub async fn delete(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<String>>> {
+    let user_address = require_user(&headers, &state).await?;
+    let result = sqlx::query("DELETE FROM price_alerts WHERE id = $1 AND user_address = $2")
+        .bind(id)
+        .bind(&user_address)
+        .execute(state.db.pool())
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Price alert not found".to_string()));
+    }
+    Ok(Json(ApiResponse::success(
+        "Price alert deleted".to_string(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `normalize_direction_accepts_aliases` operations.
+    fn normalize_direction_accepts_aliases() {
+        assert_eq!(normalize_direction("Above").unwrap(), "above");
+        assert_eq!(normalize_direction("down").unwrap(), "below");
+        assert!(normalize_direction("sideways").is_err());
+    }
+}
@@ -3,7 +3,6 @@ use serde::Serialize;
 
 use super::{resolve_user_scope_addresses, AppState};
 use crate::{
-    constants::EPOCH_DURATION_SECONDS,
     error::Result,
     models::ApiResponse,
     services::AnalyticsService,
@@ -47,6 +46,22 @@ fn normalize_scope_addresses(user_addresses: &[String]) -> Vec<String> {
     normalized
 }
 
+// Internal helper that caps allocation rows at `max_items`, folding the smallest holdings
+// into a single `OTHER` row so percentages still sum to the full portfolio.
+fn cap_allocation(mut items: Vec<AllocationItem>, max_items: usize) -> Vec<AllocationItem> {
+    if max_items == 0 || items.len() <= max_items {
+        return items;
+    }
+    items.sort_by_key(|item| std::cmp::Reverse(item.value_usd));
+    let tail = items.split_off(max_items.saturating_sub(1));
+    items.push(AllocationItem {
+        asset: "OTHER".to_string(),
+        percentage: tail.iter().map(|item| item.percentage).sum(),
+        value_usd: tail.iter().map(|item| item.value_usd).sum(),
+    });
+    items
+}
+
 #[derive(Debug, Serialize)]
 pub struct AnalyticsResponse {
     pub portfolio: PortfolioAnalytics,
@@ -62,6 +77,9 @@ pub struct PortfolioAnalytics {
     pub pnl_30d: Decimal,
     pub pnl_all_time: Decimal,
     pub allocation: Vec<AllocationItem>,
+    /// True when `allocation` was capped to `ANALYTICS_MAX_POINTS` rows.
+    pub downsampled: bool,
+    pub original_count: usize,
 }
 
 #[derive(Debug, Serialize)]
@@ -108,7 +126,7 @@ pub async fn get_analytics(
     )?;
 
     // Current epoch (30 days window)
-    let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+    let current_epoch = state.config.current_rewards_epoch();
 
     let total_points: Decimal = if normalized_addresses.is_empty() {
         Decimal::ZERO
@@ -130,6 +148,9 @@ pub async fn get_analytics(
             value_usd: decimal_or_zero(item.value_usd),
         })
         .collect::<Vec<_>>();
+    let original_count = allocation.len();
+    let downsampled = original_count > state.config.analytics_max_points;
+    let allocation = cap_allocation(allocation, state.config.analytics_max_points);
 
     let total_epoch_points: Decimal =
         sqlx::query_scalar("SELECT COALESCE(SUM(total_points), 0) FROM points WHERE epoch = $1")
@@ -146,6 +167,8 @@ pub async fn get_analytics(
             pnl_30d: decimal_or_zero(pnl_30d.pnl),
             pnl_all_time: decimal_or_zero(pnl_all.pnl),
             allocation,
+            downsampled,
+            original_count,
         },
         trading: TradingAnalytics {
             total_trades: trading.total_trades,
@@ -199,4 +222,22 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    // Internal helper that supports `cap_allocation_folds_tail_into_other` operations.
+    fn cap_allocation_folds_tail_into_other() {
+        let items = (1..=5)
+            .map(|idx| AllocationItem {
+                asset: format!("T{}", idx),
+                percentage: idx as f64 * 100.0 / 15.0,
+                value_usd: Decimal::from(idx),
+            })
+            .collect::<Vec<_>>();
+        let capped = cap_allocation(items, 3);
+        assert_eq!(capped.len(), 3);
+        assert_eq!(capped[0].asset, "T5");
+        assert_eq!(capped[1].asset, "T4");
+        assert_eq!(capped[2].asset, "OTHER");
+        assert_eq!(capped[2].value_usd, Decimal::from(6));
+    }
 }
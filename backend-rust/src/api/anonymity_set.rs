@@ -0,0 +1,284 @@
+use super::{require_user, AppState};
+use crate::{
+    error::{AppError, Result},
+    indexer::starknet_client::{Event, StarknetClient},
+    models::ApiResponse,
+    services::{
+        hide_executor::configured_executor_addresses,
+        onchain::{parse_felt, OnchainReader},
+    },
+};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use starknet_core::types::{Felt, FunctionCall};
+use starknet_core::utils::get_selector_from_name;
+use std::{
+    collections::HashMap,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+const ANONYMITY_SET_CACHE_SECS: u64 = 60;
+
+static ANONYMITY_SET_CACHE: OnceLock<
+    tokio::sync::RwLock<HashMap<String, (Instant, AnonymitySetResponse)>>,
+> = OnceLock::new();
+
+#[derive(Debug, Deserialize)]
+pub struct AnonymitySetQuery {
+    pub token: String,
+    pub denom_id: String,
+    pub executor_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnonymitySetResponse {
+    pub executor: String,
+    pub token: String,
+    pub denom_id: String,
+    pub fixed_amount_low: String,
+    pub fixed_amount_high: String,
+    pub anonymity_set_size: u64,
+    pub min_recommended_size: u64,
+    pub scanned_from_block: u64,
+    pub scanned_to_block: u64,
+    pub scan_truncated: bool,
+    pub recommendation: String,
+    pub message: String,
+}
+
+// Internal helper that supports `anonymity_set_cache` operations.
+fn anonymity_set_cache(
+) -> &'static tokio::sync::RwLock<HashMap<String, (Instant, AnonymitySetResponse)>> {
+    ANONYMITY_SET_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()))
+}
+
+// Internal helper that counts `DepositRegisteredV3` events in the `(token, denom_id, fixed_amount)` bucket.
+// Data layout: sender, token, denom_id, amount.low, amount.high, note_commitment, timestamp.
+fn count_bucket_deposits(
+    events: &[Event],
+    deposit_selector: Felt,
+    token: Felt,
+    denom_id: Felt,
+    amount_low: Felt,
+    amount_high: Felt,
+) -> u64 {
+    let parse = |raw: Option<&String>| raw.and_then(|value| parse_felt(value).ok());
+    events
+        .iter()
+        .filter(|event| parse(event.keys.first()) == Some(deposit_selector))
+        .filter(|event| {
+            parse(event.data.get(1)) == Some(token)
+                && parse(event.data.get(2)) == Some(denom_id)
+                && parse(event.data.get(3)) == Some(amount_low)
+                && parse(event.data.get(4)) == Some(amount_high)
+        })
+        .count() as u64
+}
+
+// Internal helper that turns the set size into a deposit recommendation.
+fn anonymity_recommendation(size: u64, min_size: u64) -> (&'static str, String) {
+    if size >= min_size {
+        (
+            "ok",
+            format!(
+                "{} notes share this denomination; anonymity set meets the recommended minimum of {}.",
+                size, min_size
+            ),
+        )
+    } else {
+        (
+            "wait",
+            format!(
+                "Only {} notes share this denomination (recommended minimum {}). Consider waiting for more deposits before relying on Hide Balance.",
+                size, min_size
+            ),
+        )
+    }
+}
+
+// Internal helper that reads the executor `fixed_amount` for the requested bucket.
+async fn read_fixed_amount(
+    state: &AppState,
+    executor: Felt,
+    token: Felt,
+    denom_id: Felt,
+) -> Result<(Felt, Felt)> {
+    let reader = OnchainReader::from_config(&state.config)?;
+    let selector = get_selector_from_name("fixed_amount")
+        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
+    let out = reader
+        .call_cached(FunctionCall {
+            contract_address: executor,
+            entry_point_selector: selector,
+            calldata: vec![token, denom_id],
+        })
+        .await?;
+    let low = out.first().copied().ok_or_else(|| {
+        AppError::BadRequest("ShieldedPoolV3 fixed_amount returned empty response".to_string())
+    })?;
+    let high = out.get(1).copied().unwrap_or(Felt::ZERO);
+    Ok((low, high))
+}
+
+/// GET /api/v1/privacy/anonymity-set?token=&denom_id=
+///
+/// Reports how many shielded-pool notes share the requested denomination bucket, scanning
+/// `DepositRegisteredV3` events over the last `PRIVACY_ANONYMITY_SCAN_BLOCKS` blocks.
+///
+/// # Notes
+/// * Requires auth; `executor_address` must be one of the configured executors.
+/// * `scan_truncated` is set when the event page limit ended the scan early, in which case
+///   `anonymity_set_size` is a lower bound.
+pub async fn get_anonymity_set(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<AnonymitySetQuery>,
+) -> Result<Json<ApiResponse<AnonymitySetResponse>>> {
+    require_user(&headers, &state).await?;
+    let configured = configured_executor_addresses(&state.config)
+        .iter()
+        .filter_map(|raw| parse_felt(raw).ok())
+        .collect::<Vec<_>>();
+    let executor = match query
+        .executor_address
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(raw) => {
+            let requested = parse_felt(raw)?;
+            if !configured.contains(&requested) {
+                return Err(AppError::BadRequest(
+                    "executor_address is not a configured private executor".to_string(),
+                ));
+            }
+            requested
+        }
+        None => configured.first().copied().ok_or_else(|| {
+            AppError::BadRequest(
+                "PrivateActionExecutor is not configured. Set PRIVATE_ACTION_EXECUTOR_ADDRESS."
+                    .to_string(),
+            )
+        })?,
+    };
+    let token = parse_felt(query.token.trim())?;
+    let denom_id = parse_felt(query.denom_id.trim())?;
+
+    let cache_key = format!("{:#x}:{:#x}:{:#x}", executor, token, denom_id);
+    if let Some((fetched_at, cached)) = anonymity_set_cache().read().await.get(&cache_key) {
+        if fetched_at.elapsed() < Duration::from_secs(ANONYMITY_SET_CACHE_SECS) {
+            return Ok(Json(ApiResponse::success(cached.clone())));
+        }
+    }
+
+    let (amount_low, amount_high) = read_fixed_amount(&state, executor, token, denom_id).await?;
+    if amount_low == Felt::ZERO && amount_high == Felt::ZERO {
+        return Err(AppError::BadRequest(format!(
+            "Denomination {:#x} is not configured for token {:#x} on executor {:#x}",
+            denom_id, token, executor
+        )));
+    }
+
+    let client = StarknetClient::new(state.config.starknet_rpc_url.clone());
+    let to_block = client.get_block_number().await?;
+    let from_block = to_block.saturating_sub(state.config.privacy_anonymity_scan_blocks);
+    let executor_hex = format!("{:#x}", executor);
+    let (events, scan_truncated) = client
+        .get_events_bounded(Some(executor_hex.as_str()), from_block, to_block)
+        .await?;
+    let deposit_selector = get_selector_from_name("DepositRegisteredV3")
+        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
+    let size = count_bucket_deposits(
+        &events,
+        deposit_selector,
+        token,
+        denom_id,
+        amount_low,
+        amount_high,
+    );
+
+    let min_size = state.config.privacy_anonymity_min_set_size;
+    let (recommendation, mut message) = anonymity_recommendation(size, min_size);
+    if scan_truncated {
+        message.push_str(" The scan hit the event page limit, so this count is a lower bound.");
+    }
+    let response = AnonymitySetResponse {
+        executor: executor_hex,
+        token: format!("{:#x}", token),
+        denom_id: format!("{:#x}", denom_id),
+        fixed_amount_low: amount_low.to_string(),
+        fixed_amount_high: amount_high.to_string(),
+        anonymity_set_size: size,
+        min_recommended_size: min_size,
+        scanned_from_block: from_block,
+        scanned_to_block: to_block,
+        scan_truncated,
+        recommendation: recommendation.to_string(),
+        message,
+    };
+    anonymity_set_cache()
+        .write()
+        .await
+        .insert(cache_key, (Instant::now(), response.clone()));
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Internal helper that builds a `DepositRegisteredV3` event fixture.
+    fn deposit_event(selector: Felt, token: u64, denom: u64, low: u64) -> Event {
+        Event {
+            from_address: "0x1".to_string(),
+            keys: vec![format!("{:#x}", selector)],
+            data: vec![
+                "0x99".to_string(),
+                format!("{:#x}", token),
+                format!("{:#x}", denom),
+                format!("{:#x}", low),
+                "0x0".to_string(),
+                "0x1234".to_string(),
+                "0x5".to_string(),
+            ],
+            transaction_hash: None,
+            block_number: None,
+        }
+    }
+
+    #[test]
+    // Internal helper that supports `count_bucket_deposits_matches_token_denom_and_amount` operations.
+    fn count_bucket_deposits_matches_token_denom_and_amount() {
+        let selector = get_selector_from_name("DepositRegisteredV3").expect("selector");
+        let other = get_selector_from_name("PrivateExitV3").expect("selector");
+        let events = vec![
+            deposit_event(selector, 10, 1, 100),
+            deposit_event(selector, 10, 1, 100),
+            deposit_event(selector, 10, 2, 100),
+            deposit_event(selector, 10, 1, 50),
+            deposit_event(selector, 11, 1, 100),
+            deposit_event(other, 10, 1, 100),
+        ];
+        let size = count_bucket_deposits(
+            &events,
+            selector,
+            Felt::from(10_u64),
+            Felt::from(1_u64),
+            Felt::from(100_u64),
+            Felt::ZERO,
+        );
+        assert_eq!(size, 2);
+    }
+
+    #[test]
+    // Internal helper that supports `anonymity_recommendation_waits_below_minimum` operations.
+    fn anonymity_recommendation_waits_below_minimum() {
+        assert_eq!(anonymity_recommendation(3, 20).0, "wait");
+        assert_eq!(anonymity_recommendation(20, 20).0, "ok");
+    }
+}
@@ -9,11 +9,14 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
-use starknet_core::types::{Call, FunctionCall};
+use starknet_core::types::{Call, Felt, FunctionCall};
 use starknet_core::utils::get_selector_from_name;
+use std::collections::HashSet;
 
 use super::{require_user, AppState};
 
+const MAX_CREDENTIAL_BATCH_SIZE: usize = 50;
+
 #[derive(Debug, Deserialize)]
 pub struct SubmitCredentialRequest {
     pub nullifier: String,
@@ -26,6 +29,29 @@ pub struct CredentialResponse {
     pub tx_hash: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SubmitCredentialBatchRequest {
+    pub proofs: Vec<SubmitCredentialRequest>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CredentialBatchItemResult {
+    pub index: usize,
+    pub nullifier: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CredentialBatchResponse {
+    pub submitted: usize,
+    pub rejected: usize,
+    pub results: Vec<CredentialBatchItemResult>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct NullifierStatusResponse {
     pub nullifier: String,
@@ -60,6 +86,80 @@ pub async fn submit_credential_proof(
     })))
 }
 
+/// POST /api/v1/credentials/submit-batch
+///
+/// Submits each proof as its own transaction so one invalid proof does not fail the batch.
+/// Nullifiers must be unique within the request and unused on-chain.
+pub async fn submit_credential_proof_batch(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<SubmitCredentialBatchRequest>,
+) -> Result<Json<ApiResponse<CredentialBatchResponse>>> {
+    let _user = require_user(&headers, &state).await?;
+    if req.proofs.is_empty() || req.proofs.len() > MAX_CREDENTIAL_BATCH_SIZE {
+        return Err(crate::error::AppError::BadRequest(format!(
+            "Batch must contain between 1 and {} proofs",
+            MAX_CREDENTIAL_BATCH_SIZE
+        )));
+    }
+    let contract = state.config.anonymous_credentials_address.trim();
+    if contract.is_empty() || contract.starts_with("0x0000") {
+        return Err(crate::error::AppError::BadRequest(
+            "Anonymous credentials not configured".into(),
+        ));
+    }
+
+    let Some(invoker) = OnchainInvoker::from_config(&state.config).ok().flatten() else {
+        return Err(crate::error::AppError::BadRequest(
+            "On-chain invoker not configured".into(),
+        ));
+    };
+    let reader = OnchainReader::from_config(&state.config)?;
+    let contract_felt = parse_felt(contract)?;
+
+    let mut results = Vec::with_capacity(req.proofs.len());
+    let mut seen = HashSet::new();
+    for (index, proof) in req.proofs.iter().enumerate() {
+        let outcome = match precheck_batch_item(proof, &mut seen) {
+            Err(message) => Err(message),
+            Ok(nullifier) => match read_nullifier_used(&reader, contract_felt, nullifier).await {
+                Ok(true) => Err("nullifier already used".to_string()),
+                Ok(false) => match build_submit_call(contract, proof) {
+                    Ok(call) => invoker.invoke(call).await.map_err(|err| err.to_string()),
+                    Err(err) => Err(err.to_string()),
+                },
+                Err(err) => Err(err.to_string()),
+            },
+        };
+        results.push(match outcome {
+            Ok(tx_hash) => CredentialBatchItemResult {
+                index,
+                nullifier: proof.nullifier.clone(),
+                status: "submitted".to_string(),
+                tx_hash: Some(tx_hash.to_string()),
+                error: None,
+            },
+            Err(message) => CredentialBatchItemResult {
+                index,
+                nullifier: proof.nullifier.clone(),
+                status: "rejected".to_string(),
+                tx_hash: None,
+                error: Some(message),
+            },
+        });
+    }
+
+    let submitted = results
+        .iter()
+        .filter(|result| result.status == "submitted")
+        .count();
+    Ok(Json(ApiResponse::success(CredentialBatchResponse {
+        submitted,
+        rejected: results.len() - submitted,
+        results,
+    })))
+}
+
 /// GET /api/v1/credentials/nullifier/{nullifier}
 pub async fn is_nullifier_used(
     State(state): State<AppState>,
@@ -73,28 +173,48 @@ pub async fn is_nullifier_used(
     }
 
     let reader = OnchainReader::from_config(&state.config)?;
-    let to = parse_felt(contract)?;
+    let used = read_nullifier_used(&reader, parse_felt(contract)?, parse_felt(&nullifier)?).await?;
+
+    Ok(Json(ApiResponse::success(NullifierStatusResponse {
+        nullifier,
+        used,
+    })))
+}
+
+// Internal helper that validates a batch item and enforces in-batch nullifier uniqueness.
+fn precheck_batch_item(
+    req: &SubmitCredentialRequest,
+    seen: &mut HashSet<Felt>,
+) -> std::result::Result<Felt, String> {
+    let nullifier = parse_felt(req.nullifier.trim()).map_err(|err| err.to_string())?;
+    if req.proof.is_empty() || req.public_inputs.is_empty() {
+        return Err("proof and public_inputs must be non-empty".to_string());
+    }
+    if !seen.insert(nullifier) {
+        return Err("duplicate nullifier in batch".to_string());
+    }
+    Ok(nullifier)
+}
+
+// Internal helper that reads `is_nullifier_used` from the credentials contract.
+async fn read_nullifier_used(
+    reader: &OnchainReader,
+    contract: Felt,
+    nullifier: Felt,
+) -> Result<bool> {
     let selector = get_selector_from_name("is_nullifier_used")
         .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
-
-    let calldata = vec![parse_felt(&nullifier)?];
     let result = reader
         .call(FunctionCall {
-            contract_address: to,
+            contract_address: contract,
             entry_point_selector: selector,
-            calldata,
+            calldata: vec![nullifier],
         })
         .await?;
-
-    let used = result
+    Ok(result
         .first()
-        .map(|v| v == &starknet_core::types::Felt::from(1_u8))
-        .unwrap_or(false);
-
-    Ok(Json(ApiResponse::success(NullifierStatusResponse {
-        nullifier,
-        used,
-    })))
+        .map(|v| v == &Felt::from(1_u8))
+        .unwrap_or(false))
 }
 
 // Internal helper that builds inputs for `build_submit_call`.
@@ -125,3 +245,39 @@ fn build_submit_call(contract: &str, req: &SubmitCredentialRequest) -> Result<Ca
         calldata,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Internal helper that builds a credential proof fixture.
+    fn credential(nullifier: &str) -> SubmitCredentialRequest {
+        SubmitCredentialRequest {
+            nullifier: nullifier.to_string(),
+            proof: vec!["0x1".to_string()],
+            public_inputs: vec!["0x2".to_string()],
+        }
+    }
+
+    #[test]
+    // Internal helper that supports `precheck_batch_item_rejects_duplicate_nullifiers` operations.
+    fn precheck_batch_item_rejects_duplicate_nullifiers() {
+        let mut seen = HashSet::new();
+        assert!(precheck_batch_item(&credential("0xabc"), &mut seen).is_ok());
+        assert_eq!(
+            precheck_batch_item(&credential("0x0abc"), &mut seen),
+            Err("duplicate nullifier in batch".to_string())
+        );
+        assert!(precheck_batch_item(&credential("0xabd"), &mut seen).is_ok());
+    }
+
+    #[test]
+    // Internal helper that supports `precheck_batch_item_rejects_empty_proofs` operations.
+    fn precheck_batch_item_rejects_empty_proofs() {
+        let mut seen = HashSet::new();
+        let mut req = credential("0x1");
+        req.proof.clear();
+        assert!(precheck_batch_item(&req, &mut seen).is_err());
+        assert!(seen.is_empty());
+    }
+}
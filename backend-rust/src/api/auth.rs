@@ -9,6 +9,7 @@ use crate::{
     crypto::{hash, signature::SignatureVerifier},
     error::{AppError, Result},
     models::ApiResponse,
+    services::point_calculator::grant_onboarding_bonus,
 };
 
 use super::AppState;
@@ -166,6 +167,7 @@ pub async fn connect_wallet(
                 .db
                 .upsert_wallet_address(&canonical_user_address, chain, address, None)
                 .await?;
+            grant_onboarding_bonus(&state.db, &state.config, &canonical_user_address).await;
         }
     }
 
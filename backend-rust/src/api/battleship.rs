@@ -27,13 +27,16 @@ use crate::{
     models::{ApiResponse, StarknetWalletCall, Transaction},
     services::{
         onchain::{felt_to_u128, parse_felt, OnchainReader},
-        privacy_verifier::parse_privacy_verifier_kind,
+        privacy_verifier::select_privacy_verifier,
     },
 };
 
 use super::{
     onchain_privacy::normalize_onchain_tx_hash,
-    privacy::{generate_auto_garaga_payload, AutoPrivacyPayloadResponse, AutoPrivacyTxContext},
+    privacy::{
+        generate_auto_garaga_payload, AutoPrivacyPayloadResponse, AutoPrivacyTxContext,
+        PrivacyPayloadLimits,
+    },
     require_starknet_user, AppState,
 };
 
@@ -297,12 +300,20 @@ fn now_unix() -> i64 {
 }
 
 // Internal helper that parses or transforms values for `normalize_hex_items`.
-fn normalize_hex_items(items: &[String]) -> Vec<String> {
+fn normalize_hex_items(items: &[String], field: &str) -> Result<Vec<String>> {
     items
         .iter()
         .map(|item| item.trim())
-        .filter(|item| !item.is_empty())
-        .map(ToOwned::to_owned)
+        .enumerate()
+        .filter(|(_, item)| !item.is_empty())
+        .map(|(index, item)| {
+            parse_felt(item).map(|_| item.to_owned()).map_err(|_| {
+                AppError::BadRequest(format!(
+                    "privacy.{}[{}] is not a valid felt: {}",
+                    field, index, item
+                ))
+            })
+        })
         .collect()
 }
 
@@ -690,18 +701,26 @@ fn fire_binding(game_id: u64, shooter: &str, x: u8, y: u8) -> Result<Felt> {
 fn parse_or_generate_payload_from_request(
     payload: Option<&GaragaPayloadInput>,
     verifier: &str,
-) -> Option<AutoPrivacyPayloadResponse> {
-    let input = payload?;
-    let nullifier = input.nullifier.as_deref()?.trim();
+    limits: PrivacyPayloadLimits,
+) -> Result<Option<AutoPrivacyPayloadResponse>> {
+    let Some(input) = payload else {
+        return Ok(None);
+    };
+    let Some(nullifier) = input.nullifier.as_deref().map(str::trim) else {
+        return Ok(None);
+    };
     if nullifier.is_empty() {
-        return None;
+        return Ok(None);
     }
-    let proof = normalize_hex_items(input.proof.as_ref().unwrap_or(&Vec::new()));
-    let public_inputs = normalize_hex_items(input.public_inputs.as_ref().unwrap_or(&Vec::new()));
+    let raw_proof = input.proof.as_deref().unwrap_or_default();
+    let raw_public_inputs = input.public_inputs.as_deref().unwrap_or_default();
+    limits.ensure(raw_proof.len(), raw_public_inputs.len())?;
+    let proof = normalize_hex_items(raw_proof, "proof")?;
+    let public_inputs = normalize_hex_items(raw_public_inputs, "public_inputs")?;
     if proof.is_empty() || public_inputs.is_empty() {
-        return None;
+        return Ok(None);
     }
-    Some(AutoPrivacyPayloadResponse {
+    Ok(Some(AutoPrivacyPayloadResponse {
         verifier: input
             .verifier
             .as_deref()
@@ -725,7 +744,7 @@ fn parse_or_generate_payload_from_request(
         spendable_at_unix: None,
         proof,
         public_inputs,
-    })
+    }))
 }
 
 // Internal helper that fetches data for `resolve_battleship_payload`.
@@ -736,35 +755,38 @@ async fn resolve_battleship_payload(
     flow: &str,
     binding: Felt,
 ) -> Result<AutoPrivacyPayloadResponse> {
-    let verifier = parse_privacy_verifier_kind(
-        privacy
-            .and_then(|payload| payload.verifier.as_deref())
-            .or(Some("garaga")),
+    let verifier = select_privacy_verifier(
+        &state.config,
+        privacy.and_then(|payload| payload.verifier.as_deref()),
     )?
     .as_str()
     .to_string();
 
-    let mut payload =
-        if let Some(request_payload) = parse_or_generate_payload_from_request(privacy, &verifier) {
-            request_payload
-        } else {
-            generate_auto_garaga_payload(
-                &state.config,
-                user_address,
-                &verifier,
-                Some(&AutoPrivacyTxContext {
-                    flow: Some(format!("battleship_{}", flow)),
-                    from_token: Some("BATTLESHIP".to_string()),
-                    to_token: Some("BATTLESHIP".to_string()),
-                    amount: Some("1".to_string()),
-                    recipient: None,
-                    from_network: Some("starknet".to_string()),
-                    to_network: Some("starknet".to_string()),
-                    ..Default::default()
-                }),
-            )
-            .await?
-        };
+    let request_payload = parse_or_generate_payload_from_request(
+        privacy,
+        &verifier,
+        PrivacyPayloadLimits::from_config(&state.config),
+    )?;
+    let mut payload = if let Some(request_payload) = request_payload {
+        request_payload
+    } else {
+        generate_auto_garaga_payload(
+            &state.config,
+            user_address,
+            &verifier,
+            Some(&AutoPrivacyTxContext {
+                flow: Some(format!("battleship_{}", flow)),
+                from_token: Some("BATTLESHIP".to_string()),
+                to_token: Some("BATTLESHIP".to_string()),
+                amount: Some("1".to_string()),
+                recipient: None,
+                from_network: Some("starknet".to_string()),
+                to_network: Some("starknet".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await?
+    };
 
     if is_dummy_payload(&payload) {
         return Err(AppError::BadRequest(
@@ -4,44 +4,40 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::OnceLock;
-use std::time::Instant;
 
-use crate::services::onchain::{
-    felt_to_u128, parse_felt, u256_from_felts, OnchainInvoker, OnchainReader,
-};
+use crate::services::onchain::{parse_felt, OnchainInvoker, OnchainReader};
 use crate::services::privacy_verifier::{
-    parse_privacy_verifier_kind, resolve_privacy_router_for_verifier, PrivacyVerifierKind,
+    resolve_privacy_router_for_verifier, select_privacy_verifier, PrivacyVerifierKind,
 };
 use crate::{
     constants::{
         token_address_for, BRIDGE_ATOMIQ, BRIDGE_GARDEN, BRIDGE_LAYERSWAP, BRIDGE_STARKGATE,
-        EPOCH_DURATION_SECONDS, POINTS_MIN_USD_BRIDGE_BTC, POINTS_MIN_USD_BRIDGE_BTC_TESTNET,
-        POINTS_MIN_USD_BRIDGE_ETH, POINTS_MIN_USD_BRIDGE_ETH_TESTNET, POINTS_PER_USD_BRIDGE_BTC,
-        POINTS_PER_USD_BRIDGE_ETH,
     },
     // Mengimpor hasher untuk menghilangkan warning unused di crypto/hash.rs
     crypto::hash,
-    db::NftDiscountStateUpsert,
     error::Result,
     integrations::bridge::{
         AtomiqClient, AtomiqQuote, GardenClient, GardenEvmTransaction, GardenQuote,
         GardenStarknetTransaction, LayerSwapClient, LayerSwapQuote,
     },
-    models::{ApiResponse, BridgeQuoteRequest, BridgeQuoteResponse, LinkedWalletAddress},
-    services::nft_discount::consume_nft_usage,
+    models::{
+        ApiResponse, BridgeQuoteOption, BridgeQuoteRequest, BridgeQuoteResponse,
+        LinkedWalletAddress,
+    },
+    services::discount_service::DiscountService,
     services::price_guard::{
         fallback_price_for, first_sane_price, sanitize_points_usd_base, sanitize_usd_notional,
         symbol_candidates_for,
     },
+    services::route_optimizer::{BridgeRoute, SupportedBridges},
     services::RouteOptimizer,
 };
-use starknet_core::types::{Call, ExecutionResult, Felt, FunctionCall, TransactionFinalityStatus};
-use starknet_core::utils::{get_selector_from_name, get_storage_var_address};
-use tokio::time::{sleep, timeout, Duration};
+use starknet_core::types::{Call, ExecutionResult, Felt, TransactionFinalityStatus};
+use starknet_core::utils::get_selector_from_name;
+use std::collections::{hash_map::Entry, HashMap};
+use tokio::time::{sleep, Duration};
 
-use super::{require_starknet_user, require_user, AppState};
+use super::{maintenance, require_starknet_user, require_user, swap::validate_deadline, AppState};
 
 #[derive(Debug, Deserialize)]
 pub struct PrivacyVerificationPayload {
@@ -68,6 +64,8 @@ pub struct ExecuteBridgeRequest {
     pub mode: Option<String>,
     pub hide_balance: Option<bool>,
     pub privacy: Option<PrivacyVerificationPayload>,
+    // Unix deadline; required and validated against `MAX_DEADLINE_SECONDS`.
+    pub deadline: Option<i64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -82,6 +80,7 @@ pub struct ExecuteBridgeResponse {
     pub fee_before_discount: String,
     pub fee_discount_saved: String,
     pub nft_discount_percent: String,
+    pub fee_discount_percent: String,
     pub estimated_points_earned: String,
     pub points_pending: bool,
     pub ai_level_points_bonus_percent: String,
@@ -118,13 +117,7 @@ pub struct BridgeStatusResponse {
     pub destination_redeem_tx_hash: Option<String>,
 }
 
-const ONCHAIN_DISCOUNT_TIMEOUT_MS: u64 = 2_500;
-const NFT_DISCOUNT_CACHE_TTL_SECS: u64 = 300;
-const NFT_DISCOUNT_CACHE_STALE_SECS: u64 = 1_800;
-const NFT_DISCOUNT_CACHE_MAX_ENTRIES: usize = 100_000;
 const BRIDGE_MEV_FEE_RATE: f64 = 0.01;
-const BRIDGE_AI_LEVEL_2_POINTS_BONUS_PERCENT: f64 = 20.0;
-const BRIDGE_AI_LEVEL_3_POINTS_BONUS_PERCENT: f64 = 40.0;
 
 // Internal helper that supports `canonical_bridge_chain` operations in the bridge flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
@@ -142,133 +135,6 @@ fn canonical_bridge_chain(chain: &str) -> String {
     lower
 }
 
-#[derive(Clone, Copy)]
-struct CachedNftDiscount {
-    fetched_at: Instant,
-    discount: f64,
-}
-
-static NFT_DISCOUNT_CACHE: OnceLock<tokio::sync::RwLock<HashMap<String, CachedNftDiscount>>> =
-    OnceLock::new();
-
-// Internal helper that supports `nft_discount_cache` operations in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn nft_discount_cache() -> &'static tokio::sync::RwLock<HashMap<String, CachedNftDiscount>> {
-    NFT_DISCOUNT_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()))
-}
-
-// Internal helper that supports `nft_discount_cache_key` operations in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn nft_discount_cache_key(contract: &str, user: &str) -> String {
-    format!(
-        "{}|{}",
-        contract.trim().to_ascii_lowercase(),
-        user.trim().to_ascii_lowercase()
-    )
-}
-
-// Internal helper that fetches data for `get_cached_nft_discount` in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn get_cached_nft_discount(key: &str, max_age: Duration) -> Option<f64> {
-    let cache = nft_discount_cache();
-    let guard = cache.read().await;
-    let entry = guard.get(key)?;
-    if entry.fetched_at.elapsed() <= max_age {
-        return Some(entry.discount);
-    }
-    None
-}
-
-// Internal helper that supports `cache_nft_discount` operations in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn cache_nft_discount(key: &str, discount: f64) {
-    let cache = nft_discount_cache();
-    let mut guard = cache.write().await;
-    guard.insert(
-        key.to_string(),
-        CachedNftDiscount {
-            fetched_at: Instant::now(),
-            discount,
-        },
-    );
-    if guard.len() > NFT_DISCOUNT_CACHE_MAX_ENTRIES {
-        let stale_after = Duration::from_secs(NFT_DISCOUNT_CACHE_STALE_SECS);
-        guard.retain(|_, entry| entry.fetched_at.elapsed() <= stale_after);
-    }
-}
-
-// Internal helper that supports `invalidate_cached_nft_discount` operations in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn invalidate_cached_nft_discount(contract: &str, user: &str) {
-    let key = nft_discount_cache_key(contract, user);
-    let cache = nft_discount_cache();
-    let mut guard = cache.write().await;
-    guard.remove(&key);
-}
-
-#[derive(Clone, Copy, Debug, Default)]
-struct NftUsageSnapshot {
-    tier: i32,
-    discount_percent: f64,
-    max_usage: u128,
-    used_in_period: u128,
-}
-
-// Internal helper that supports `current_nft_period_epoch` operations in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn current_nft_period_epoch() -> i64 {
-    chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS
-}
-
-// Internal helper that supports `u128_to_i64_saturating` operations in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn u128_to_i64_saturating(value: u128) -> i64 {
-    if value > i64::MAX as u128 {
-        i64::MAX
-    } else {
-        value as i64
-    }
-}
-
-// Internal helper that fetches data for `read_nft_usage_snapshot` in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn read_nft_usage_snapshot(
-    reader: &OnchainReader,
-    contract_address: Felt,
-    user_felt: Felt,
-) -> Result<Option<NftUsageSnapshot>> {
-    let storage_key = get_storage_var_address("user_nft", &[user_felt]).map_err(|e| {
-        crate::error::AppError::Internal(format!("Storage key resolution error: {}", e))
-    })?;
-    let token_raw = reader.get_storage_at(contract_address, storage_key).await?;
-    let token_id = felt_to_u128(&token_raw).unwrap_or(0);
-    if token_id == 0 {
-        return Ok(None);
-    }
-
-    let info_call = FunctionCall {
-        contract_address,
-        entry_point_selector: get_selector_from_name("get_nft_info")
-            .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?,
-        calldata: vec![Felt::from(token_id), Felt::from(0_u8)],
-    };
-    let info = reader.call(info_call).await?;
-    if info.len() < 7 {
-        return Ok(None);
-    }
-
-    let tier = felt_to_u128(&info[0]).unwrap_or(0) as i32;
-    let discount = u256_from_felts(&info[1], &info[2]).unwrap_or(0) as f64;
-    let max_usage = u256_from_felts(&info[3], &info[4]).unwrap_or(0);
-    let used_in_period = u256_from_felts(&info[5], &info[6]).unwrap_or(0);
-    Ok(Some(NftUsageSnapshot {
-        tier: tier.max(0),
-        discount_percent: discount.clamp(0.0, 100.0),
-        max_usage,
-        used_in_period,
-    }))
-}
-
 // Internal helper that supports `estimate_time` operations in the bridge flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 fn estimate_time(provider: &str) -> &'static str {
@@ -281,311 +147,76 @@ fn estimate_time(provider: &str) -> &'static str {
     }
 }
 
-// Internal helper that supports `bridge_ai_level_points_bonus_percent` operations in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn bridge_ai_level_points_bonus_percent(level: u8) -> f64 {
-    match level {
-        2 => BRIDGE_AI_LEVEL_2_POINTS_BONUS_PERCENT,
-        3 => BRIDGE_AI_LEVEL_3_POINTS_BONUS_PERCENT,
-        _ => 0.0,
+// Internal helper that builds the per-provider quote comparison in the bridge flow.
+// `routes` arrive best routing score first, and that route is flagged as recommended.
+fn bridge_quote_options(
+    routes: &[BridgeRoute],
+    prices_usd: &HashMap<String, f64>,
+    sort_by: &str,
+) -> Vec<BridgeQuoteOption> {
+    let price = |token: &str| {
+        prices_usd
+            .get(&token.to_ascii_uppercase())
+            .copied()
+            .unwrap_or(0.0)
+    };
+    let mut options: Vec<(f64, BridgeQuoteOption)> = routes
+        .iter()
+        .enumerate()
+        .map(|(index, route)| {
+            let net_output_usd = sanitize_usd_notional(route.amount_out * price(&route.to_token));
+            let option = BridgeQuoteOption {
+                provider: route.provider.clone(),
+                fee: route.fee.to_string(),
+                fee_token: route.token.to_ascii_uppercase(),
+                fee_usd: sanitize_usd_notional(route.fee * price(&route.token)).to_string(),
+                estimated_time: format!("~{} min", route.estimated_time_minutes),
+                estimated_time_minutes: route.estimated_time_minutes,
+                net_output: route.amount_out.to_string(),
+                net_output_token: route.to_token.to_ascii_uppercase(),
+                net_output_usd: net_output_usd.to_string(),
+                recommended: index == 0,
+            };
+            (net_output_usd, option)
+        })
+        .collect();
+    let by_output = |left: &(f64, BridgeQuoteOption), right: &(f64, BridgeQuoteOption)| {
+        right
+            .0
+            .partial_cmp(&left.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    };
+    if sort_by == "fastest" {
+        options.sort_by(|left, right| {
+            left.1
+                .estimated_time_minutes
+                .cmp(&right.1.estimated_time_minutes)
+                .then_with(|| by_output(left, right))
+        });
+    } else {
+        options.sort_by(|left, right| {
+            by_output(left, right).then_with(|| {
+                left.1
+                    .estimated_time_minutes
+                    .cmp(&right.1.estimated_time_minutes)
+            })
+        });
     }
+    options.into_iter().map(|(_, option)| option).collect()
 }
 
 // Internal helper that supports `estimate_bridge_points_for_response` operations in the bridge flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 fn estimate_bridge_points_for_response(
     volume_usd: f64,
-    is_btc_bridge: bool,
-    nft_discount_percent: f64,
-    ai_level: u8,
-    is_testnet: bool,
+    (min_threshold, per_usd_rate): (f64, f64),
+    points_multiplier: f64,
 ) -> f64 {
     let sanitized = sanitize_points_usd_base(volume_usd);
-    let (min_threshold, per_usd_rate) = if is_btc_bridge {
-        (
-            if is_testnet {
-                POINTS_MIN_USD_BRIDGE_BTC_TESTNET
-            } else {
-                POINTS_MIN_USD_BRIDGE_BTC
-            },
-            POINTS_PER_USD_BRIDGE_BTC,
-        )
-    } else {
-        (
-            if is_testnet {
-                POINTS_MIN_USD_BRIDGE_ETH_TESTNET
-            } else {
-                POINTS_MIN_USD_BRIDGE_ETH
-            },
-            POINTS_PER_USD_BRIDGE_ETH,
-        )
-    };
     if sanitized < min_threshold {
         return 0.0;
     }
-    let nft_factor = 1.0 + (nft_discount_percent.clamp(0.0, 100.0) / 100.0);
-    let ai_factor = 1.0 + (bridge_ai_level_points_bonus_percent(ai_level) / 100.0);
-    (sanitized * per_usd_rate * nft_factor * ai_factor).max(0.0)
-}
-
-// Internal helper that supports `discount_contract_address` operations in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn discount_contract_address(state: &AppState) -> Option<&str> {
-    state
-        .config
-        .discount_soulbound_address
-        .as_deref()
-        .filter(|addr| !addr.trim().is_empty() && !addr.starts_with("0x0000"))
-}
-
-// Internal helper that supports `active_nft_discount_percent` operations in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn cached_nft_discount_from_local_state(state: &AppState, user_address: &str) -> f64 {
-    let Some(contract) = discount_contract_address(state) else {
-        return 0.0;
-    };
-    let cache_key = nft_discount_cache_key(contract, user_address);
-    if let Some(cached) =
-        get_cached_nft_discount(&cache_key, Duration::from_secs(NFT_DISCOUNT_CACHE_TTL_SECS)).await
-    {
-        return cached.max(0.0);
-    }
-
-    let period_epoch = current_nft_period_epoch();
-    match state
-        .db
-        .get_nft_discount_state(contract, user_address, period_epoch)
-        .await
-    {
-        Ok(Some(row)) => {
-            let age_secs = chrono::Utc::now()
-                .signed_duration_since(row.updated_at)
-                .num_seconds()
-                .max(0) as u64;
-            if age_secs > NFT_DISCOUNT_CACHE_STALE_SECS {
-                return 0.0;
-            }
-            let effective_used = row.local_used_in_period.max(row.chain_used_in_period);
-            let has_remaining_usage = row.max_usage > 0 && effective_used < row.max_usage;
-            let discount = if row.is_active && has_remaining_usage {
-                row.discount_percent.clamp(0.0, 100.0)
-            } else {
-                0.0
-            };
-            cache_nft_discount(&cache_key, discount).await;
-            discount
-        }
-        Ok(None) => 0.0,
-        Err(err) => {
-            tracing::warn!(
-                "Failed to read local NFT discount state in bridge for user={}: {}",
-                user_address,
-                err
-            );
-            0.0
-        }
-    }
-}
-
-// Internal helper that supports `refresh_nft_discount_for_submit` operations in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn refresh_nft_discount_for_submit(state: &AppState, user_address: &str) -> f64 {
-    let Some(contract) = discount_contract_address(state) else {
-        return 0.0;
-    };
-    let cache_key = nft_discount_cache_key(contract, user_address);
-    let period_epoch = current_nft_period_epoch();
-
-    let reader = match OnchainReader::from_config(&state.config) {
-        Ok(reader) => reader,
-        Err(err) => {
-            tracing::warn!(
-                "Failed to initialize on-chain reader for NFT discount submit validation in bridge: {}",
-                err
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-    };
-
-    let contract_address = match parse_felt(contract) {
-        Ok(value) => value,
-        Err(err) => {
-            tracing::warn!(
-                "Invalid discount contract address while validating bridge fee discount: {}",
-                err
-            );
-            return 0.0;
-        }
-    };
-    let user_felt = match parse_felt(user_address) {
-        Ok(value) => value,
-        Err(err) => {
-            tracing::warn!(
-                "Invalid user address while validating bridge fee discount: user={}, err={}",
-                user_address,
-                err
-            );
-            return 0.0;
-        }
-    };
-
-    let selector = match get_selector_from_name("has_active_discount") {
-        Ok(value) => value,
-        Err(err) => {
-            tracing::warn!(
-                "Selector resolution failed for has_active_discount in bridge submit validation: {}",
-                err
-            );
-            return 0.0;
-        }
-    };
-
-    let call = FunctionCall {
-        contract_address,
-        entry_point_selector: selector,
-        calldata: vec![user_felt],
-    };
-
-    let result = match timeout(
-        Duration::from_millis(ONCHAIN_DISCOUNT_TIMEOUT_MS),
-        reader.call(call),
-    )
-    .await
-    {
-        Ok(Ok(value)) => value,
-        Ok(Err(err)) => {
-            tracing::warn!(
-                "Failed on-chain NFT discount submit validation in bridge for user={}: {}. Falling back to local NFT discount state.",
-                user_address,
-                err
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-        Err(_) => {
-            tracing::warn!(
-                "Timeout on-chain NFT discount submit validation in bridge for user={}. Falling back to local NFT discount state.",
-                user_address
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-    };
-    if result.len() < 3 {
-        tracing::warn!(
-            "NFT discount submit validation returned malformed payload for user={}. Falling back to local NFT discount state.",
-            user_address
-        );
-        return cached_nft_discount_from_local_state(state, user_address).await;
-    }
-
-    let chain_active = felt_to_u128(&result[0]).unwrap_or(0) > 0;
-    let chain_discount = u256_from_felts(&result[1], &result[2]).unwrap_or(0) as f64;
-
-    let usage_snapshot = match timeout(
-        Duration::from_millis(ONCHAIN_DISCOUNT_TIMEOUT_MS),
-        read_nft_usage_snapshot(&reader, contract_address, user_felt),
-    )
-    .await
-    {
-        Ok(Ok(value)) => value.unwrap_or_default(),
-        Ok(Err(err)) => {
-            tracing::warn!(
-                "Failed to read NFT usage snapshot in bridge submit validation for user={}: {}. Falling back to local NFT discount state.",
-                user_address,
-                err
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-        Err(_) => {
-            tracing::warn!(
-                "Timeout reading NFT usage snapshot in bridge submit validation for user={}. Falling back to local NFT discount state.",
-                user_address
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-    };
-
-    let discount_percent = if chain_discount > 0.0 {
-        chain_discount
-    } else {
-        usage_snapshot.discount_percent
-    }
-    .clamp(0.0, 100.0);
-    let max_usage_i64 = u128_to_i64_saturating(usage_snapshot.max_usage);
-    let chain_used_i64 = u128_to_i64_saturating(usage_snapshot.used_in_period);
-
-    let db_row = state
-        .db
-        .upsert_nft_discount_state_from_chain(NftDiscountStateUpsert {
-            contract_address: contract,
-            user_address,
-            period_epoch,
-            tier: usage_snapshot.tier.max(0),
-            discount_percent,
-            is_active: chain_active,
-            max_usage: max_usage_i64,
-            chain_used_in_period: chain_used_i64,
-        })
-        .await;
-
-    let resolved_discount = match db_row {
-        Ok(row) => {
-            let effective_used = row.local_used_in_period.max(row.chain_used_in_period);
-            let has_remaining_usage = row.max_usage > 0 && effective_used < row.max_usage;
-            if row.is_active && has_remaining_usage {
-                row.discount_percent.clamp(0.0, 100.0)
-            } else {
-                0.0
-            }
-        }
-        Err(err) => {
-            tracing::warn!(
-                "Failed to persist NFT discount state in bridge for user={}: {}",
-                user_address,
-                err
-            );
-            let has_remaining_usage = usage_snapshot.max_usage > 0
-                && usage_snapshot.used_in_period < usage_snapshot.max_usage;
-            if chain_active && has_remaining_usage {
-                discount_percent
-            } else {
-                0.0
-            }
-        }
-    };
-
-    cache_nft_discount(&cache_key, resolved_discount).await;
-    resolved_discount
-}
-
-// Internal helper that runs side-effecting logic for `record_nft_discount_usage_after_submit` in the bridge flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn record_nft_discount_usage_after_submit(state: &AppState, user_address: &str) {
-    let Some(contract) = discount_contract_address(state) else {
-        return;
-    };
-    let period_epoch = current_nft_period_epoch();
-    match state
-        .db
-        .increment_nft_discount_local_usage(contract, user_address, period_epoch, 1)
-        .await
-    {
-        Ok(updated_usage) => {
-            tracing::debug!(
-                "Recorded local NFT usage after bridge submit user={} period={} local_used={}",
-                user_address,
-                period_epoch,
-                updated_usage
-            );
-        }
-        Err(err) => {
-            tracing::warn!(
-                "Failed recording local NFT usage after bridge submit for user={}: {}",
-                user_address,
-                err
-            );
-        }
-    }
-    invalidate_cached_nft_discount(contract, user_address).await;
+    (sanitized * per_usd_rate * points_multiplier.max(0.0)).max(0.0)
 }
 
 // Internal helper that supports `latest_price_usd` operations in the bridge flow.
@@ -1147,9 +778,20 @@ pub async fn get_bridge_quote(
     if token_address_for(&req.token).is_none() {
         return Err(crate::error::AppError::InvalidToken);
     }
+    let sort_by = req
+        .sort_by
+        .as_deref()
+        .unwrap_or(&state.config.bridge_quote_sort)
+        .trim()
+        .to_ascii_lowercase();
+    if !matches!(sort_by.as_str(), "best_output" | "fastest") {
+        return Err(crate::error::AppError::BadRequest(
+            "sort_by must be best_output or fastest".to_string(),
+        ));
+    }
     let optimizer = RouteOptimizer::new(state.config.clone());
-    let best_route = optimizer
-        .find_best_bridge_route(
+    let routes = optimizer
+        .quote_bridge_routes(
             &req.from_chain,
             &req.to_chain,
             &req.token,
@@ -1157,6 +799,23 @@ pub async fn get_bridge_quote(
             amount,
         )
         .await?;
+    let Some(best_route) = routes.first() else {
+        return Err(crate::error::AppError::NotFound(
+            "No bridge route available".to_string(),
+        ));
+    };
+
+    let mut prices_usd = HashMap::new();
+    for route in &routes {
+        for token in [&route.token, &route.to_token] {
+            let symbol = token.to_ascii_uppercase();
+            if let Entry::Vacant(entry) = prices_usd.entry(symbol) {
+                let price = latest_price_usd(&state, entry.key()).await?;
+                entry.insert(price);
+            }
+        }
+    }
+    let options = bridge_quote_options(&routes, &prices_usd, &sort_by);
 
     let provider = best_route.provider.as_str();
     let bridge_fee = best_route.fee;
@@ -1171,17 +830,32 @@ pub async fn get_bridge_quote(
         fee: bridge_fee.to_string(),
         estimated_time: estimated_time.to_string(),
         bridge_provider: provider.to_string(),
+        options,
     };
 
     Ok(Json(ApiResponse::success(response)))
 }
 
+// Internal helper that requires a bridge deadline inside the `MAX_DEADLINE_SECONDS` window.
+fn validate_bridge_deadline(deadline: Option<i64>, now: i64, max_window_secs: i64) -> Result<()> {
+    let deadline = deadline.ok_or_else(|| {
+        crate::error::AppError::BadRequest("Bridge execution requires a deadline".to_string())
+    })?;
+    validate_deadline(deadline, now, max_window_secs)
+}
+
 /// POST /api/v1/bridge/execute
 pub async fn execute_bridge(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(req): Json<ExecuteBridgeRequest>,
 ) -> Result<Json<ApiResponse<ExecuteBridgeResponse>>> {
+    maintenance::ensure_writable(&headers, &state).await?;
+    validate_bridge_deadline(
+        req.deadline,
+        chrono::Utc::now().timestamp(),
+        state.config.max_deadline_seconds,
+    )?;
     let user_address = require_user(&headers, &state).await?;
     let linked_wallets = state
         .db
@@ -1307,21 +981,22 @@ pub async fn execute_bridge(
     let is_garden_user_signed_source = is_garden_provider
         && (from_chain_normalized == "ethereum" || from_chain_normalized == "starknet");
 
-    let applied_nft_discount_percent = if let Some(discount_user) = discount_usage_user.as_deref() {
-        refresh_nft_discount_for_submit(&state, discount_user).await
-    } else {
-        0.0
-    };
+    let discount_service = DiscountService::new(state.db.clone(), state.config.clone());
+    let discounts = discount_service
+        .resolve_for_submit("bridge", &user_address, discount_usage_user.as_deref())
+        .await;
+    let applied_nft_discount_percent = discounts.nft_discount_percent;
+    let fee_discount_percent = discounts.fee_discount_percent;
     let mev_fee = mev_fee_for_mode(req.mode.as_deref(), amount);
     let route_fee_with_mev = best_route.fee + mev_fee;
     let effective_bridge_fee =
-        route_fee_with_mev * (1.0 - (applied_nft_discount_percent.clamp(0.0, 100.0) / 100.0));
-    if applied_nft_discount_percent > 0.0 || mev_fee > 0.0 {
+        route_fee_with_mev * (1.0 - (fee_discount_percent.clamp(0.0, 100.0) / 100.0));
+    if fee_discount_percent > 0.0 || mev_fee > 0.0 {
         tracing::debug!(
             "Bridge fee applied: user={} mode={} discount_percent={} route_fee={} mev_fee={} effective_fee={}",
             user_address,
             req.mode.as_deref().unwrap_or("transparent"),
-            applied_nft_discount_percent,
+            fee_discount_percent,
             best_route.fee,
             mev_fee,
             effective_bridge_fee
@@ -1340,24 +1015,11 @@ pub async fn execute_bridge(
         .trim()
         .to_ascii_uppercase();
     let from_token = req.token.trim().to_ascii_uppercase();
-    let user_ai_level = match state.db.get_user_ai_level(&user_address).await {
-        Ok(level) => level,
-        Err(err) => {
-            tracing::warn!(
-                "Failed to resolve user AI level for bridge points bonus (user={}): {}",
-                user_address,
-                err
-            );
-            1
-        }
-    };
-    let ai_level_points_bonus_percent = bridge_ai_level_points_bonus_percent(user_ai_level);
+    let ai_level_points_bonus_percent = discounts.ai_points_bonus_percent;
     let mut estimated_points_earned = estimate_bridge_points_for_response(
         sanitize_usd_notional(amount * fallback_price_for(&from_token)),
-        is_from_btc,
-        applied_nft_discount_percent,
-        user_ai_level,
-        state.config.is_testnet(),
+        state.config.bridge_points_rule(is_from_btc),
+        discounts.points_multiplier,
     );
 
     if req.existing_bridge_id.is_some() && !is_garden_provider {
@@ -1407,6 +1069,7 @@ pub async fn execute_bridge(
                 .max(0.0)
                 .to_string(),
             nft_discount_percent: applied_nft_discount_percent.to_string(),
+            fee_discount_percent: fee_discount_percent.to_string(),
             estimated_points_earned: estimated_points_earned.to_string(),
             points_pending: true,
             ai_level_points_bonus_percent: ai_level_points_bonus_percent.to_string(),
@@ -1453,8 +1116,10 @@ pub async fn execute_bridge(
     let mut privacy_verification_tx: Option<String> = None;
     let privacy_payload = req.privacy.as_ref();
     if should_hide {
-        let verifier =
-            parse_privacy_verifier_kind(privacy_payload.and_then(|p| p.verifier.as_deref()))?;
+        let verifier = select_privacy_verifier(
+            &state.config,
+            privacy_payload.and_then(|p| p.verifier.as_deref()),
+        )?;
         let privacy_seed = privacy_seed_from_tx_hash(&tx_hash);
         let privacy_tx =
             verify_private_trade_with_verifier(&state, &privacy_seed, privacy_payload, verifier)
@@ -1481,10 +1146,8 @@ pub async fn execute_bridge(
     let volume_usd = sanitize_usd_notional(amount * token_price);
     estimated_points_earned = estimate_bridge_points_for_response(
         volume_usd,
-        is_from_btc,
-        applied_nft_discount_percent,
-        user_ai_level,
-        state.config.is_testnet(),
+        state.config.bridge_points_rule(is_from_btc),
+        discounts.points_multiplier,
     );
 
     let tx = crate::models::Transaction {
@@ -1509,19 +1172,12 @@ pub async fn execute_bridge(
     }
     if let Some(discount_user) = discount_usage_user.as_deref() {
         if applied_nft_discount_percent > 0.0 {
-            record_nft_discount_usage_after_submit(&state, discount_user).await;
-            let consume_result = consume_nft_usage(&state.config, discount_user, "bridge").await;
-            if let Err(err) = consume_result {
-                tracing::warn!(
-                    "Failed to consume NFT discount usage after bridge success: user={} tx_hash={} err={}",
-                    discount_user,
-                    tx_hash,
-                    err
-                );
-            }
-        } else if let Some(contract) = discount_contract_address(&state) {
+            discount_service
+                .record_nft_usage("bridge", discount_user, &tx_hash)
+                .await;
+        } else {
             // Keep in-memory cache aligned with latest submit-time chain validation.
-            invalidate_cached_nft_discount(contract, discount_user).await;
+            discount_service.invalidate(discount_user).await;
         }
     }
 
@@ -1558,6 +1214,7 @@ pub async fn execute_bridge(
                 .max(0.0)
                 .to_string(),
             nft_discount_percent: applied_nft_discount_percent.to_string(),
+            fee_discount_percent: fee_discount_percent.to_string(),
             estimated_points_earned: estimated_points_earned.to_string(),
             points_pending: true,
             ai_level_points_bonus_percent: ai_level_points_bonus_percent.to_string(),
@@ -1677,6 +1334,7 @@ pub async fn execute_bridge(
             .max(0.0)
             .to_string(),
         nft_discount_percent: applied_nft_discount_percent.to_string(),
+        fee_discount_percent: fee_discount_percent.to_string(),
         estimated_points_earned: estimated_points_earned.to_string(),
         points_pending: true,
         ai_level_points_bonus_percent: ai_level_points_bonus_percent.to_string(),
@@ -1692,6 +1350,20 @@ pub async fn execute_bridge(
     Ok(Json(ApiResponse::success(response)))
 }
 
+/// GET /api/v1/bridge/supported
+///
+/// # Notes
+/// * Chains, tokens and routes merged across the configured bridge providers, each route
+///   annotated with the providers that may execute it.
+pub async fn get_supported_bridges(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<SupportedBridges>>> {
+    let optimizer = RouteOptimizer::new(state.config.clone());
+    Ok(Json(ApiResponse::success(
+        optimizer.supported_bridge_routes().await,
+    )))
+}
+
 /// GET /api/v1/bridge/status/{bridge_id}
 pub async fn get_bridge_status(
     State(state): State<AppState>,
@@ -1804,6 +1476,10 @@ fn to_u256_felt(value: f64) -> Result<(starknet_core::types::Felt, starknet_core
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::{
+        POINTS_MIN_USD_BRIDGE_ETH, POINTS_MIN_USD_BRIDGE_ETH_TESTNET, POINTS_PER_USD_BRIDGE_ETH,
+    };
+    use crate::services::discount_service::points_multiplier;
     use chrono::Utc;
 
     #[test]
@@ -1817,6 +1493,54 @@ mod tests {
         assert_eq!(estimate_time("Unknown"), "~15-20 min");
     }
 
+    #[test]
+    // Internal helper that supports `validate_bridge_deadline_requires_window` operations in the bridge flow.
+    fn validate_bridge_deadline_requires_window() {
+        assert!(validate_bridge_deadline(Some(1_000), 100, 3_600).is_ok());
+
+        let missing = validate_bridge_deadline(None, 100, 3_600).expect_err("missing");
+        assert!(missing.to_string().contains("requires a deadline"));
+        let expired = validate_bridge_deadline(Some(99), 100, 3_600).expect_err("expired");
+        assert!(expired.to_string().contains("expired"));
+        let too_far = validate_bridge_deadline(Some(3_701), 100, 3_600).expect_err("too far");
+        assert!(too_far.to_string().contains("too far out"));
+    }
+
+    #[test]
+    // Internal helper that supports `bridge_quote_options_sort_and_flag_recommended` operations in the bridge flow.
+    fn bridge_quote_options_sort_and_flag_recommended() {
+        let route = |provider: &str, amount_out: f64, fee: f64, minutes: u32| BridgeRoute {
+            provider: provider.to_string(),
+            token: "ETH".to_string(),
+            to_token: "WBTC".to_string(),
+            amount_in: 1.0,
+            amount_out,
+            fee,
+            estimated_time_minutes: minutes,
+        };
+        let routes = vec![
+            route(BRIDGE_GARDEN, 0.05, 0.01, 30),
+            route(BRIDGE_LAYERSWAP, 0.06, 0.02, 15),
+        ];
+        let prices = HashMap::from([("ETH".to_string(), 2000.0), ("WBTC".to_string(), 40000.0)]);
+
+        let by_output = bridge_quote_options(&routes, &prices, "best_output");
+        assert_eq!(by_output[0].provider, BRIDGE_LAYERSWAP);
+        assert_eq!(by_output[0].net_output_usd, "2400");
+        assert_eq!(by_output[0].fee_usd, "40");
+        assert!(!by_output[0].recommended);
+        assert!(by_output[1].recommended);
+
+        let routes = vec![
+            route(BRIDGE_GARDEN, 0.06, 0.01, 30),
+            route(BRIDGE_LAYERSWAP, 0.05, 0.02, 15),
+        ];
+        let fastest = bridge_quote_options(&routes, &prices, "fastest");
+        assert_eq!(fastest[0].provider, BRIDGE_LAYERSWAP);
+        assert_eq!(fastest[0].estimated_time, "~15 min");
+        assert!(fastest[1].recommended);
+    }
+
     #[test]
     // Internal helper that builds inputs for `build_bridge_id_uses_short_hash_prefix` in the bridge flow.
     // Keeps validation, normalization, and intent-binding logic centralized.
@@ -1915,8 +1639,16 @@ mod tests {
     // Internal helper that supports `estimate_bridge_points_applies_testnet_thresholds` operations in the bridge flow.
     // Keeps validation, normalization, and intent-binding logic centralized.
     fn estimate_bridge_points_applies_testnet_thresholds() {
-        let mainnet_points = estimate_bridge_points_for_response(9.5, false, 0.0, 1, false);
-        let testnet_points = estimate_bridge_points_for_response(9.5, false, 0.0, 1, true);
+        let mainnet_points = estimate_bridge_points_for_response(
+            9.5,
+            (POINTS_MIN_USD_BRIDGE_ETH, POINTS_PER_USD_BRIDGE_ETH),
+            1.0,
+        );
+        let testnet_points = estimate_bridge_points_for_response(
+            9.5,
+            (POINTS_MIN_USD_BRIDGE_ETH_TESTNET, POINTS_PER_USD_BRIDGE_ETH),
+            1.0,
+        );
         assert_eq!(mainnet_points, 0.0);
         assert!(testnet_points > 0.0);
     }
@@ -1925,9 +1657,10 @@ mod tests {
     // Internal helper that supports `estimate_bridge_points_includes_ai_level_bonus` operations in the bridge flow.
     // Keeps validation, normalization, and intent-binding logic centralized.
     fn estimate_bridge_points_includes_ai_level_bonus() {
-        let base = estimate_bridge_points_for_response(100.0, false, 0.0, 1, false);
-        let l2 = estimate_bridge_points_for_response(100.0, false, 0.0, 2, false);
-        let l3 = estimate_bridge_points_for_response(100.0, false, 0.0, 3, false);
+        let eth_rule = (POINTS_MIN_USD_BRIDGE_ETH, POINTS_PER_USD_BRIDGE_ETH);
+        let base = estimate_bridge_points_for_response(100.0, eth_rule, points_multiplier(0.0, 1));
+        let l2 = estimate_bridge_points_for_response(100.0, eth_rule, points_multiplier(0.0, 2));
+        let l3 = estimate_bridge_points_for_response(100.0, eth_rule, points_multiplier(0.0, 3));
         assert!(l2 > base);
         assert!(l3 > l2);
     }
@@ -3,37 +3,68 @@ use axum::{
     Json,
 };
 use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    error::Result,
-    models::{ApiResponse, OHLCVResponse},
-    services::PriceChartService,
+    error::{AppError, Result},
+    models::{ApiResponse, OHLCVResponse, PriceTick},
+    services::{
+        indicators::{vwap, IndicatorSpec, VwapReset},
+        PriceChartService,
+    },
 };
 
-use super::AppState;
+use super::{extract::TimeRange, AppState};
 
 #[derive(Debug, Deserialize)]
 pub struct OHLCVQuery {
+    pub interval: String,
+    pub limit: Option<i32>,
+    pub source: Option<String>,
+    // Include a VWAP series aligned with the candles.
+    #[serde(default)]
+    pub vwap: bool,
+    // `daily` (default) or `none`.
+    pub vwap_reset: Option<String>,
+    // UTC hour at which the daily VWAP session starts (default 0).
+    pub vwap_reset_hour: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IndicatorsQuery {
     pub interval: String,
     pub from: Option<String>,
     pub to: Option<String>,
     pub limit: Option<i32>,
-    pub source: Option<String>,
+    // Comma-separated list, e.g. `sma:20,ema:50,rsi:14,macd:12:26:9,bollinger:20:2`.
+    pub indicators: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct IndicatorsResponse {
     pub indicator: String,
+    pub params: Vec<f64>,
     pub data: Vec<IndicatorPoint>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct IndicatorPoint {
     pub timestamp: i64,
-    pub value: f64,
+    pub value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal: Option<Option<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub histogram: Option<Option<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upper: Option<Option<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lower: Option<Option<f64>>,
 }
 
+const DEFAULT_INDICATOR_CANDLES: i32 = 200;
+const MAX_INDICATOR_CANDLES: i32 = 1000;
+
 // Internal helper that parses or transforms values for `parse_rfc3339_or`.
 fn parse_rfc3339_or(
     value: Option<&str>,
@@ -45,29 +76,91 @@ fn parse_rfc3339_or(
         .unwrap_or(default)
 }
 
-// Internal helper that supports `map_indicator_points` operations.
-fn map_indicator_points(
-    data: Vec<(chrono::DateTime<chrono::Utc>, rust_decimal::Decimal)>,
-) -> Vec<IndicatorPoint> {
-    data.into_iter()
-        .map(|(ts, val)| IndicatorPoint {
-            timestamp: ts.timestamp(),
-            value: val.to_f64().unwrap_or(0.0),
+// Internal helper that supports `build_indicator_response` operations.
+fn build_indicator_response(candles: &[PriceTick], spec: &IndicatorSpec) -> IndicatorsResponse {
+    let closes: Vec<f64> = candles
+        .iter()
+        .map(|candle| candle.close.to_f64().unwrap_or(0.0))
+        .collect();
+    let series = spec.compute(&closes);
+    let at = |values: &Option<Vec<Option<f64>>>, i: usize| {
+        values
+            .as_ref()
+            .map(|values| values.get(i).copied().flatten())
+    };
+    let data = candles
+        .iter()
+        .enumerate()
+        .map(|(i, candle)| IndicatorPoint {
+            timestamp: candle.timestamp.timestamp(),
+            value: series.value.get(i).copied().flatten(),
+            signal: at(&series.signal, i),
+            histogram: at(&series.histogram, i),
+            upper: at(&series.upper, i),
+            lower: at(&series.lower, i),
+        })
+        .collect();
+    IndicatorsResponse {
+        indicator: spec.label().to_string(),
+        params: spec.params(),
+        data,
+    }
+}
+
+// Internal helper that parses or transforms values for `parse_vwap_reset`.
+fn parse_vwap_reset(mode: Option<&str>, hour: Option<u32>) -> Result<VwapReset> {
+    match mode
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("") | Some("daily") => {
+            let hour = hour.unwrap_or(0);
+            if hour > 23 {
+                return Err(AppError::BadRequest(
+                    "vwap_reset_hour must be between 0 and 23".to_string(),
+                ));
+            }
+            Ok(VwapReset::Daily { hour })
+        }
+        Some("none") | Some("never") => Ok(VwapReset::Never),
+        Some(other) => Err(AppError::BadRequest(format!(
+            "Unknown vwap_reset: {} (expected daily or none)",
+            other
+        ))),
+    }
+}
+
+// Internal helper that computes VWAP from candle typical price `(high + low + close) / 3`.
+fn vwap_for_candles(candles: &[PriceTick], reset: VwapReset) -> Vec<Option<f64>> {
+    let series: Vec<_> = candles
+        .iter()
+        .map(|candle| {
+            let typical = ((candle.high + candle.low + candle.close) / Decimal::from(3))
+                .to_f64()
+                .unwrap_or(0.0);
+            (
+                candle.timestamp,
+                typical,
+                candle.volume.to_f64().unwrap_or(0.0),
+            )
         })
-        .collect()
+        .collect();
+    vwap(&series, reset)
 }
 
 /// GET /api/v1/chart/:token/ohlcv
 pub async fn get_ohlcv(
     State(state): State<AppState>,
     Path(token): Path<String>,
+    range: TimeRange,
     Query(query): Query<OHLCVQuery>,
 ) -> Result<Json<ApiResponse<OHLCVResponse>>> {
     let service = PriceChartService::new(state.db, state.config);
 
-    let to = parse_rfc3339_or(query.to.as_deref(), chrono::Utc::now());
-    let from_default = to - chrono::Duration::hours(24);
-    let from = parse_rfc3339_or(query.from.as_deref(), from_default);
+    let (from, to) = range.resolve(chrono::Duration::hours(24));
+    let limit = query
+        .limit
+        .map(|limit| limit.clamp(1, MAX_INDICATOR_CANDLES));
     let source = query
         .source
         .as_deref()
@@ -77,52 +170,74 @@ pub async fn get_ohlcv(
 
     let data = if source == "coingecko" {
         service
-            .get_ohlcv_from_coingecko(&token, &query.interval, query.limit.unwrap_or(120))
+            .get_ohlcv_from_coingecko(&token, &query.interval, limit.unwrap_or(120))
             .await?
     } else {
-        let data = if let Some(limit) = query.limit {
+        let data = if let Some(limit) = limit {
             service
-                .get_latest_candles(&token, &query.interval, limit)
+                .get_latest_candles_any_interval(&token, &query.interval, limit)
                 .await?
         } else {
-            service.get_ohlcv(&token, &query.interval, from, to).await?
+            service
+                .get_ohlcv_any_interval(&token, &query.interval, from, to)
+                .await?
         };
         if data.is_empty() {
             service
-                .get_ohlcv_from_coingecko(&token, &query.interval, query.limit.unwrap_or(120))
+                .get_ohlcv_from_coingecko(&token, &query.interval, limit.unwrap_or(120))
                 .await?
         } else {
             data
         }
     };
 
+    let vwap = if query.vwap {
+        let reset = parse_vwap_reset(query.vwap_reset.as_deref(), query.vwap_reset_hour)?;
+        Some(vwap_for_candles(&data, reset))
+    } else {
+        None
+    };
+
     Ok(Json(ApiResponse::success(OHLCVResponse {
         token,
         interval: query.interval,
         data,
+        vwap,
     })))
 }
 
 /// GET /api/v1/chart/:token/indicators
+///
+/// `indicators` selects which series to return (default `sma,ema,rsi`); windows that are
+/// not yet filled are returned as `null` values.
 pub async fn get_indicators(
     State(state): State<AppState>,
     Path(token): Path<String>,
-    Query(query): Query<OHLCVQuery>,
+    Query(query): Query<IndicatorsQuery>,
 ) -> Result<Json<ApiResponse<Vec<IndicatorsResponse>>>> {
+    let specs = IndicatorSpec::parse_list(query.indicators.as_deref())?;
     let service = PriceChartService::new(state.db, state.config);
-    let mut indicators = vec![];
-
-    for (name, key) in [("SMA", "SMA"), ("EMA", "EMA"), ("RSI", "RSI")] {
-        if let Ok(data) = service
-            .calculate_indicators(&token, &query.interval, key)
-            .await
-        {
-            indicators.push(IndicatorsResponse {
-                indicator: name.to_string(),
-                data: map_indicator_points(data),
-            });
-        }
-    }
+
+    let candles = if query.from.is_some() || query.to.is_some() {
+        let to = parse_rfc3339_or(query.to.as_deref(), chrono::Utc::now());
+        let from = parse_rfc3339_or(query.from.as_deref(), to - chrono::Duration::hours(24));
+        service
+            .get_ohlcv_any_interval(&token, &query.interval, from, to)
+            .await?
+    } else {
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_INDICATOR_CANDLES)
+            .clamp(1, MAX_INDICATOR_CANDLES);
+        service
+            .get_latest_candles_any_interval(&token, &query.interval, limit)
+            .await?
+    };
+
+    let indicators = specs
+        .iter()
+        .map(|spec| build_indicator_response(&candles, spec))
+        .collect();
 
     Ok(Json(ApiResponse::success(indicators)))
 }
@@ -131,7 +246,6 @@ pub async fn get_indicators(
 mod tests {
     use super::*;
     use chrono::{TimeZone, Utc};
-    use rust_decimal::Decimal;
 
     #[test]
     // Internal helper that parses or transforms values for `parse_rfc3339_or_uses_default_on_invalid`.
@@ -143,14 +257,49 @@ mod tests {
     }
 
     #[test]
-    // Internal helper that supports `map_indicator_points_converts_decimal` operations.
-    fn map_indicator_points_converts_decimal() {
-        // Memastikan konversi indikator ke tipe response benar
-        let ts = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
-        let data = vec![(ts, Decimal::from(42))];
-        let out = map_indicator_points(data);
-        assert_eq!(out.len(), 1);
-        assert_eq!(out[0].timestamp, 1_700_000_000);
-        assert!((out[0].value - 42.0).abs() < f64::EPSILON);
+    // Internal helper that parses or transforms values for `parse_vwap_reset_defaults_to_daily_midnight`.
+    fn parse_vwap_reset_defaults_to_daily_midnight() {
+        assert_eq!(
+            parse_vwap_reset(None, None).unwrap(),
+            VwapReset::Daily { hour: 0 }
+        );
+        assert_eq!(
+            parse_vwap_reset(Some("none"), None).unwrap(),
+            VwapReset::Never
+        );
+        assert!(parse_vwap_reset(Some("daily"), Some(24)).is_err());
+        assert!(parse_vwap_reset(Some("weekly"), None).is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `build_indicator_response_pads_leading_window_with_nulls` operations.
+    fn build_indicator_response_pads_leading_window_with_nulls() {
+        let candles: Vec<PriceTick> = (0..3)
+            .map(|i| PriceTick {
+                token: "BTC".to_string(),
+                timestamp: Utc.timestamp_opt(1_700_000_000 + i * 60, 0).unwrap(),
+                open: Decimal::from(10 + i),
+                high: Decimal::from(10 + i),
+                low: Decimal::from(10 + i),
+                close: Decimal::from(10 + i),
+                volume: Decimal::ZERO,
+            })
+            .collect();
+        let out = build_indicator_response(&candles, &IndicatorSpec::Sma { period: 2 });
+        assert_eq!(out.indicator, "SMA");
+        assert_eq!(out.data.len(), 3);
+        assert_eq!(out.data[0].value, None);
+        assert_eq!(out.data[1].value, Some(10.5));
+        assert!(out.data[0].signal.is_none());
+
+        let macd = build_indicator_response(
+            &candles,
+            &IndicatorSpec::Macd {
+                fast: 2,
+                slow: 3,
+                signal: 2,
+            },
+        );
+        assert_eq!(macd.data[0].signal, Some(None));
     }
 }
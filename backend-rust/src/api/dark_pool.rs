@@ -1,7 +1,8 @@
 use crate::{
-    error::Result,
+    error::{AppError, Result},
     models::ApiResponse,
     services::onchain::{parse_felt, OnchainInvoker, OnchainReader},
+    services::price_guard::{fallback_price_for, first_sane_price, symbol_candidates_for},
 };
 use axum::{
     extract::{Path, State},
@@ -18,6 +19,9 @@ use super::{require_user, AppState};
 pub struct SubmitDarkOrderRequest {
     pub ciphertext: String,
     pub commitment: String,
+    // Declared order size, checked against the per-token notional limits.
+    pub token: String,
+    pub amount: String,
     pub proof: Vec<String>,
     pub public_inputs: Vec<String>,
 }
@@ -26,6 +30,9 @@ pub struct SubmitDarkOrderRequest {
 pub struct MatchDarkOrderRequest {
     pub order_id: u64,
     pub nullifier: String,
+    // Declared size of the matching order, checked like a submitted order.
+    pub token: String,
+    pub amount: String,
     pub proof: Vec<String>,
     pub public_inputs: Vec<String>,
 }
@@ -41,6 +48,90 @@ pub struct NullifierStatusResponse {
     pub used: bool,
 }
 
+#[derive(Debug, Serialize)]
+pub struct DarkPoolTokenLimit {
+    pub token: String,
+    pub min_notional_usd: f64,
+    pub max_notional_usd: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DarkPoolLimitsResponse {
+    pub default_min_notional_usd: f64,
+    pub default_max_notional_usd: f64,
+    pub tokens: Vec<DarkPoolTokenLimit>,
+}
+
+// Internal helper that fetches data for `latest_price_usd`.
+async fn latest_price_usd(state: &AppState, symbol: &str) -> Result<f64> {
+    let token = symbol.to_ascii_uppercase();
+    for candidate in symbol_candidates_for(&token) {
+        let prices: Vec<f64> = sqlx::query_scalar(
+            "SELECT close::FLOAT FROM price_history WHERE token = $1 ORDER BY timestamp DESC LIMIT 16",
+        )
+        .bind(&candidate)
+        .fetch_all(state.db.pool())
+        .await?;
+        if let Some(value) = first_sane_price(&candidate, &prices) {
+            return Ok(value);
+        }
+    }
+    Ok(fallback_price_for(&token))
+}
+
+// Internal helper that checks an order notional against the configured `[min, max]` window.
+// The error is identical for both bounds and omits the amount, so rejections leak no order size.
+fn ensure_notional_within_limits(notional_usd: f64, min: f64, max: f64) -> Result<()> {
+    if notional_usd < min || notional_usd > max {
+        return Err(AppError::BadRequest(
+            "Dark pool order size is outside the allowed limits (see /api/v1/dark-pool/limits)"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// Internal helper that validates a declared order size against per-token notional limits.
+async fn validate_order_size(state: &AppState, token: &str, amount: &str) -> Result<()> {
+    let token = token.trim().to_ascii_uppercase();
+    let amount = amount
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .ok_or_else(|| AppError::BadRequest("Invalid amount".to_string()))?;
+    let price = latest_price_usd(state, &token).await?;
+    if price <= 0.0 {
+        return Err(AppError::InvalidToken);
+    }
+    let (min, max) = state.config.dark_pool_limits_for(&token);
+    ensure_notional_within_limits(amount * price, min, max)
+}
+
+/// GET /api/v1/dark-pool/limits
+pub async fn get_limits(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<DarkPoolLimitsResponse>>> {
+    let tokens = state
+        .config
+        .price_tokens_list()
+        .into_iter()
+        .map(|token| {
+            let (min_notional_usd, max_notional_usd) = state.config.dark_pool_limits_for(&token);
+            DarkPoolTokenLimit {
+                token,
+                min_notional_usd,
+                max_notional_usd,
+            }
+        })
+        .collect();
+    Ok(Json(ApiResponse::success(DarkPoolLimitsResponse {
+        default_min_notional_usd: state.config.dark_pool_min_notional_usd,
+        default_max_notional_usd: state.config.dark_pool_max_notional_usd,
+        tokens,
+    })))
+}
+
 /// POST /api/v1/dark-pool/order
 pub async fn submit_order(
     State(state): State<AppState>,
@@ -54,6 +145,7 @@ pub async fn submit_order(
             "Dark pool not configured".into(),
         ));
     }
+    validate_order_size(&state, &req.token, &req.amount).await?;
 
     let Some(invoker) = OnchainInvoker::from_config(&state.config).ok().flatten() else {
         return Err(crate::error::AppError::BadRequest(
@@ -82,6 +174,7 @@ pub async fn match_order(
             "Dark pool not configured".into(),
         ));
     }
+    validate_order_size(&state, &req.token, &req.amount).await?;
 
     let Some(invoker) = OnchainInvoker::from_config(&state.config).ok().flatten() else {
         return Err(crate::error::AppError::BadRequest(
@@ -196,3 +289,30 @@ fn build_match_call(contract: &str, req: &MatchDarkOrderRequest) -> Result<Call>
         calldata,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that checks conditions for `ensure_notional_within_limits_enforces_window`.
+    fn ensure_notional_within_limits_enforces_window() {
+        assert!(ensure_notional_within_limits(100.0, 50.0, 1_000.0).is_ok());
+        assert!(ensure_notional_within_limits(10.0, 50.0, 1_000.0).is_err());
+        assert!(ensure_notional_within_limits(5_000.0, 50.0, 1_000.0).is_err());
+    }
+
+    #[test]
+    // Internal helper that checks conditions for `ensure_notional_within_limits_hides_order_size`.
+    fn ensure_notional_within_limits_hides_order_size() {
+        let too_small = ensure_notional_within_limits(12.34, 50.0, 1_000.0)
+            .unwrap_err()
+            .to_string();
+        let too_large = ensure_notional_within_limits(5_678.9, 50.0, 1_000.0)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(too_small, too_large);
+        assert!(!too_small.contains("12.34"));
+        assert!(!too_large.contains("5678"));
+    }
+}
@@ -1,4 +1,4 @@
-use super::{require_user, AppState};
+use super::{maintenance, require_starknet_user, require_user, AppState};
 use crate::{error::Result, models::ApiResponse, services::DepositService};
 use axum::{
     extract::{Path, State},
@@ -11,17 +11,34 @@ use serde::Deserialize;
 pub struct BankTransferRequest {
     pub amount: f64,
     pub currency: String,
+    pub target_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct QRISRequest {
     pub amount: f64,
+    pub target_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CardPaymentRequest {
     pub amount: f64,
     pub currency: String,
+    pub target_token: Option<String>,
+}
+
+// Internal helper that resolves the depositor; auto-swap output is sent on Starknet,
+// so a `target_token` requires a Starknet-linked user.
+async fn require_depositor(
+    headers: &HeaderMap,
+    state: &AppState,
+    target_token: Option<&str>,
+) -> Result<String> {
+    if target_token.is_some_and(|token| !token.trim().is_empty()) {
+        require_starknet_user(headers, state).await
+    } else {
+        require_user(headers, state).await
+    }
 }
 
 /// POST /api/v1/deposit/bank-transfer
@@ -30,11 +47,17 @@ pub async fn bank_transfer(
     headers: HeaderMap,
     Json(req): Json<BankTransferRequest>,
 ) -> Result<Json<ApiResponse<crate::services::deposit_service::DepositInfo>>> {
-    let user_address = require_user(&headers, &state).await?;
+    maintenance::ensure_writable(&headers, &state).await?;
+    let user_address = require_depositor(&headers, &state, req.target_token.as_deref()).await?;
 
     let service = DepositService::new(state.db, state.config);
     let deposit = service
-        .create_bank_transfer(&user_address, req.amount, &req.currency)
+        .create_bank_transfer(
+            &user_address,
+            req.amount,
+            &req.currency,
+            req.target_token.as_deref(),
+        )
         .await?;
 
     Ok(Json(ApiResponse::success(deposit)))
@@ -46,10 +69,13 @@ pub async fn qris(
     headers: HeaderMap,
     Json(req): Json<QRISRequest>,
 ) -> Result<Json<ApiResponse<crate::services::deposit_service::DepositInfo>>> {
-    let user_address = require_user(&headers, &state).await?;
+    maintenance::ensure_writable(&headers, &state).await?;
+    let user_address = require_depositor(&headers, &state, req.target_token.as_deref()).await?;
 
     let service = DepositService::new(state.db, state.config);
-    let deposit = service.create_qris(&user_address, req.amount).await?;
+    let deposit = service
+        .create_qris(&user_address, req.amount, req.target_token.as_deref())
+        .await?;
 
     Ok(Json(ApiResponse::success(deposit)))
 }
@@ -60,11 +86,17 @@ pub async fn card_payment(
     headers: HeaderMap,
     Json(req): Json<CardPaymentRequest>,
 ) -> Result<Json<ApiResponse<crate::services::deposit_service::DepositInfo>>> {
-    let user_address = require_user(&headers, &state).await?;
+    maintenance::ensure_writable(&headers, &state).await?;
+    let user_address = require_depositor(&headers, &state, req.target_token.as_deref()).await?;
 
     let service = DepositService::new(state.db, state.config);
     let deposit = service
-        .create_card_payment(&user_address, req.amount, &req.currency)
+        .create_card_payment(
+            &user_address,
+            req.amount,
+            &req.currency,
+            req.target_token.as_deref(),
+        )
         .await?;
 
     Ok(Json(ApiResponse::success(deposit)))
@@ -102,6 +134,15 @@ mod tests {
         let payload = r#"{"amount": 75.0}"#;
         let req: QRISRequest = serde_json::from_str(payload).expect("payload valid");
         assert_eq!(req.amount, 75.0);
+        assert!(req.target_token.is_none());
+    }
+
+    #[test]
+    // Internal helper that supports `deserialize_card_payment_request_with_target_token` operations.
+    fn deserialize_card_payment_request_with_target_token() {
+        let payload = r#"{"amount": 50.0, "currency": "USD", "target_token": "STRK"}"#;
+        let req: CardPaymentRequest = serde_json::from_str(payload).expect("payload valid");
+        assert_eq!(req.target_token.as_deref(), Some("STRK"));
     }
 
     #[test]
@@ -0,0 +1,225 @@
+use super::AppState;
+use crate::error::{AppError, Result};
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+const DEFAULT_PAGE_LIMIT: i32 = 20;
+
+#[derive(Debug, Deserialize)]
+struct PaginationParams {
+    page: Option<i32>,
+    limit: Option<i32>,
+    offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimeRangeParams {
+    #[serde(alias = "from_date")]
+    from: Option<String>,
+    #[serde(alias = "to_date")]
+    to: Option<String>,
+}
+
+/// Validated `page`/`limit`/`offset` query parameters.
+///
+/// `limit` defaults to 20 and is clamped to `API_MAX_PAGE_LIMIT`; an explicit `offset`
+/// takes precedence over `page` and must be a multiple of `limit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+    pub page: i32,
+    pub limit: i32,
+}
+
+impl Pagination {
+    // Internal helper that normalizes raw pagination params against the configured max.
+    fn from_params(params: PaginationParams, max_limit: u32) -> Result<Self> {
+        let limit = clamp_page_limit(params.limit.unwrap_or(DEFAULT_PAGE_LIMIT), max_limit);
+        if let Some(offset) = params.offset {
+            if offset < 0 || offset % i64::from(limit) != 0 {
+                return Err(AppError::BadRequest(format!(
+                    "offset must be a non-negative multiple of limit ({})",
+                    limit
+                )));
+            }
+            let page = i32::try_from(offset / i64::from(limit) + 1)
+                .map_err(|_| AppError::BadRequest(format!("offset {} is too large", offset)))?;
+            return Ok(Self { page, limit });
+        }
+        let page = params.page.unwrap_or(1);
+        if page < 1 {
+            return Err(AppError::BadRequest("page must be >= 1".to_string()));
+        }
+        Ok(Self { page, limit })
+    }
+}
+
+/// Clamps a requested page size into `1..=max_limit`.
+pub fn clamp_page_limit(limit: i32, max_limit: u32) -> i32 {
+    let max = i32::try_from(max_limit.max(1)).unwrap_or(i32::MAX);
+    limit.clamp(1, max)
+}
+
+impl FromRequestParts<AppState> for Pagination {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self> {
+        let Query(params) = Query::<PaginationParams>::try_from_uri(&parts.uri)
+            .map_err(|err| AppError::BadRequest(format!("Invalid pagination: {}", err)))?;
+        Self::from_params(params, state.config.api_max_page_limit)
+    }
+}
+
+/// Validated `from`/`to` (or `from_date`/`to_date`) RFC3339 query parameters.
+///
+/// Rejects unparsable timestamps, inverted ranges, and spans longer than
+/// `API_MAX_TIME_RANGE_DAYS`. A missing `to` is treated as "now" for validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    // Internal helper that parses and validates raw range params.
+    fn from_params(params: TimeRangeParams, max_days: i64, now: DateTime<Utc>) -> Result<Self> {
+        let from = parse_bound(params.from.as_deref(), "from")?;
+        let to = parse_bound(params.to.as_deref(), "to")?;
+        if let Some(from) = from {
+            let effective_to = to.unwrap_or(now);
+            if from > effective_to {
+                return Err(AppError::BadRequest(format!(
+                    "Invalid time range: from ({}) is after to ({})",
+                    from.to_rfc3339(),
+                    effective_to.to_rfc3339()
+                )));
+            }
+            if effective_to - from > Duration::days(max_days) {
+                return Err(AppError::BadRequest(format!(
+                    "Time range too large: maximum span is {} days",
+                    max_days
+                )));
+            }
+        }
+        Ok(Self { from, to })
+    }
+
+    /// Resolves both bounds, defaulting `to` to now and `from` to `to - default_span`.
+    pub fn resolve(&self, default_span: Duration) -> (DateTime<Utc>, DateTime<Utc>) {
+        let to = self.to.unwrap_or_else(Utc::now);
+        let from = self.from.unwrap_or(to - default_span);
+        (from, to)
+    }
+}
+
+// Internal helper that parses one optional RFC3339 bound.
+fn parse_bound(raw: Option<&str>, field: &str) -> Result<Option<DateTime<Utc>>> {
+    let Some(raw) = raw.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    DateTime::parse_from_rfc3339(raw)
+        .map(|dt| Some(dt.with_timezone(&Utc)))
+        .map_err(|_| AppError::BadRequest(format!("{} must be an RFC3339 timestamp", field)))
+}
+
+impl FromRequestParts<AppState> for TimeRange {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self> {
+        let Query(params) = Query::<TimeRangeParams>::try_from_uri(&parts.uri)
+            .map_err(|err| AppError::BadRequest(format!("Invalid time range: {}", err)))?;
+        Self::from_params(params, state.config.api_max_time_range_days, Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    // Internal helper that supports `pagination_clamps_limit_and_prefers_offset` operations.
+    fn pagination_clamps_limit_and_prefers_offset() {
+        let defaults = Pagination::from_params(
+            PaginationParams {
+                page: None,
+                limit: None,
+                offset: None,
+            },
+            100,
+        )
+        .expect("defaults");
+        assert_eq!((defaults.page, defaults.limit), (1, 20));
+
+        let clamped = Pagination::from_params(
+            PaginationParams {
+                page: Some(3),
+                limit: Some(10_000),
+                offset: None,
+            },
+            100,
+        )
+        .expect("clamped");
+        assert_eq!((clamped.page, clamped.limit), (3, 100));
+
+        let by_offset = Pagination::from_params(
+            PaginationParams {
+                page: Some(9),
+                limit: Some(10),
+                offset: Some(20),
+            },
+            100,
+        )
+        .expect("offset");
+        assert_eq!(by_offset.page, 3);
+
+        assert!(Pagination::from_params(
+            PaginationParams {
+                page: Some(0),
+                limit: None,
+                offset: None,
+            },
+            100,
+        )
+        .is_err());
+        assert!(Pagination::from_params(
+            PaginationParams {
+                page: None,
+                limit: Some(10),
+                offset: Some(25),
+            },
+            100,
+        )
+        .is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `time_range_rejects_inverted_and_oversized_spans` operations.
+    fn time_range_rejects_inverted_and_oversized_spans() {
+        let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let params = |from: &str, to: Option<&str>| TimeRangeParams {
+            from: Some(from.to_string()),
+            to: to.map(str::to_string),
+        };
+        assert!(TimeRange::from_params(
+            params("2023-11-10T00:00:00Z", Some("2023-11-01T00:00:00Z")),
+            30,
+            now
+        )
+        .is_err());
+        assert!(TimeRange::from_params(params("2023-01-01T00:00:00Z", None), 30, now).is_err());
+        assert!(TimeRange::from_params(params("not-a-date", None), 30, now).is_err());
+
+        let range = TimeRange::from_params(
+            params("2023-11-01T00:00:00Z", Some("2023-11-10T00:00:00Z")),
+            30,
+            now,
+        )
+        .expect("valid range");
+        let (from, to) = range.resolve(Duration::hours(24));
+        assert_eq!(to - from, Duration::days(9));
+    }
+}
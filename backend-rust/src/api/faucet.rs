@@ -1,19 +1,28 @@
-use axum::{extract::State, http::HeaderMap, Json};
-use serde::Serialize;
+use axum::{
+    extract::{ConnectInfo, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::{Deserialize, Serialize};
 use sqlx::Row;
+use std::net::{IpAddr, SocketAddr};
 
 use crate::{
     constants::{
         FAUCET_AMOUNT_BTC, FAUCET_AMOUNT_CAREL, FAUCET_AMOUNT_ETH, FAUCET_AMOUNT_STRK,
         FAUCET_AMOUNT_USDC, FAUCET_AMOUNT_USDT,
     },
-    error::Result,
+    crypto::hash,
+    error::{AppError, Result},
     models::{ApiResponse, FaucetClaimRequest, FaucetClaimResponse},
-    services::faucet_service::{FaucetService, FaucetStats},
+    services::faucet_service::{FaucetService, FaucetStats, FaucetStatsWindow},
 };
 
 use super::{require_starknet_user, AppState};
 
+/// Tokens the testnet faucet can dispense.
+pub(crate) const FAUCET_TOKENS: [&str; 3] = ["CAREL", "USDT", "USDC"];
+
 // Internal helper that supports `compute_next_claim_in` operations.
 fn compute_next_claim_in(
     next_claim: Option<chrono::DateTime<chrono::Utc>>,
@@ -84,7 +93,7 @@ fn faucet_policy_reset_at() -> Option<chrono::DateTime<chrono::Utc>> {
 }
 
 // Internal helper that supports `token_faucet_configured` operations.
-fn token_faucet_configured(state: &AppState, token: &str) -> bool {
+pub(crate) fn token_faucet_configured(state: &AppState, token: &str) -> bool {
     match token.to_ascii_uppercase().as_str() {
         "CAREL" => !state.config.carel_token_address.trim().is_empty(),
         "USDT" | "USDC" => true,
@@ -92,9 +101,36 @@ fn token_faucet_configured(state: &AppState, token: &str) -> bool {
     }
 }
 
+// Internal helper that resolves the client IP the faucet clusters on.
+// With `trusted_hops` proxies in front, the entry the outermost trusted proxy appended to
+// `X-Forwarded-For` is used; everything left of it is client-supplied. Without proxies, or
+// when the header is shorter than the trusted chain, only the TCP peer is trusted.
+fn client_ip(headers: &HeaderMap, peer: IpAddr, trusted_hops: usize) -> Option<IpAddr> {
+    if trusted_hops == 0 {
+        return Some(peer);
+    }
+    let forwarded: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect();
+    let index = forwarded.len().checked_sub(trusted_hops)?;
+    forwarded[index].parse().ok()
+}
+
+// Internal helper that hashes the client IP with the faucet salt so raw IPs are never stored.
+fn client_fingerprint(headers: &HeaderMap, peer: IpAddr, state: &AppState) -> Option<String> {
+    let salt = state.config.faucet_fingerprint_salt.as_deref()?;
+    let ip = client_ip(headers, peer, state.config.faucet_trusted_proxy_hops)?;
+    Some(hash::hash_string(&format!("{}:{}", salt, ip)))
+}
+
 /// POST /api/v1/faucet/claim
 pub async fn claim_tokens(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
     Json(req): Json<FaucetClaimRequest>,
 ) -> Result<Json<ApiResponse<FaucetClaimResponse>>> {
@@ -102,9 +138,12 @@ pub async fn claim_tokens(
     let token_symbol = req.token.trim().to_ascii_uppercase();
 
     let faucet = FaucetService::new(state.db.clone(), state.config.clone())?;
+    let fingerprint = client_fingerprint(&headers, peer.ip(), &state);
 
     // Eksekusi klaim (sekarang sudah mengecek saldo via provider)
-    let tx_hash = faucet.claim_tokens(&user_address, &token_symbol).await?;
+    let tx_hash = faucet
+        .claim_tokens(&user_address, &token_symbol, fingerprint.as_deref())
+        .await?;
 
     let next_claim = faucet
         .get_next_claim_time(&user_address, &token_symbol)
@@ -129,6 +168,7 @@ pub async fn claim_tokens(
 /// GET /api/v1/faucet/status
 pub async fn get_status(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
 ) -> Result<Json<ApiResponse<FaucetStatusResponse>>> {
     let user_address = require_starknet_user(&headers, &state).await?;
@@ -138,22 +178,32 @@ pub async fn get_status(
             "Faucet service init failed; using fallback status mode (check backend signer config/private key)."
         );
     }
+    let fingerprint = client_fingerprint(&headers, peer.ip(), &state);
     let mut token_status = Vec::new();
     let cooldown_hours = faucet_cooldown_hours(&state);
     let carel_unlimited = faucet_carel_unlimited();
 
-    for token in &["CAREL", "USDT", "USDC"] {
-        let token_symbol = (*token).to_string();
+    for token in FAUCET_TOKENS {
+        let token_symbol = token.to_string();
         let (can_claim, next_claim, last_claim_at) = if let Some(faucet_service) = &faucet {
-            let can_claim = faucet_service
+            let mut can_claim = faucet_service
                 .can_claim(&user_address, &token_symbol)
                 .await
                 .unwrap_or(false);
-            let next_claim = faucet_service
+            let mut next_claim = faucet_service
                 .get_next_claim_time(&user_address, &token_symbol)
                 .await
                 .ok()
                 .flatten();
+            let cluster_next = faucet_service
+                .cluster_next_eligible_at(&user_address, &token_symbol, fingerprint.as_deref())
+                .await
+                .ok()
+                .flatten();
+            if let Some(cluster_next) = cluster_next {
+                can_claim = false;
+                next_claim = Some(next_claim.map_or(cluster_next, |own| own.max(cluster_next)));
+            }
             let last_claim_at = faucet_service
                 .get_last_claim(&user_address, &token_symbol)
                 .await
@@ -211,15 +261,27 @@ pub async fn get_status(
     })))
 }
 
-/// GET /api/v1/faucet/stats
+#[derive(Debug, Deserialize)]
+pub struct FaucetStatsQuery {
+    pub window: Option<String>,
+}
+
+/// GET /api/v1/faucet/stats?window=24h|7d|all
 /// Menampilkan statistik distribusi faucet (DITAMBAHKAN)
 pub async fn get_faucet_stats(
     State(state): State<AppState>,
+    Query(query): Query<FaucetStatsQuery>,
 ) -> Result<Json<ApiResponse<FaucetStats>>> {
+    let window = match query.window.as_deref() {
+        None => FaucetStatsWindow::All,
+        Some(raw) => FaucetStatsWindow::parse(raw).ok_or_else(|| {
+            AppError::BadRequest("window must be one of 24h, 7d, all".to_string())
+        })?,
+    };
     let faucet = FaucetService::new(state.db.clone(), state.config.clone())?;
 
     // Memanggil method get_stats() yang sebelumnya dianggap dead_code
-    let stats = faucet.get_stats().await?;
+    let stats = faucet.get_stats(window).await?;
 
     Ok(Json(ApiResponse::success(stats)))
 }
@@ -268,4 +330,25 @@ mod tests {
         let amount_usdt = faucet_amount_from_options("USDT", None, None, Some(30.0));
         assert!((amount_usdt - FAUCET_AMOUNT_USDT).abs() < f64::EPSILON);
     }
+
+    #[test]
+    // Internal helper that supports `client_ip_trusts_only_proxy_appended_hops` operations.
+    fn client_ip_trusts_only_proxy_appended_hops() {
+        let peer: IpAddr = "192.0.2.10".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            "203.0.113.66, 198.51.100.7, 10.0.0.2".parse().unwrap(),
+        );
+        headers.insert("x-real-ip", "203.0.113.66".parse().unwrap());
+
+        // No proxies: forwarding headers are client-controlled and ignored.
+        assert_eq!(client_ip(&headers, peer, 0), Some(peer));
+        // One proxy: the entry it appended, not the spoofable leftmost one.
+        assert_eq!(client_ip(&headers, peer, 1), "10.0.0.2".parse().ok());
+        assert_eq!(client_ip(&headers, peer, 2), "198.51.100.7".parse().ok());
+        // Shorter than the trusted chain: the request bypassed the proxies.
+        assert_eq!(client_ip(&headers, peer, 4), None);
+        assert_eq!(client_ip(&HeaderMap::new(), peer, 1), None);
+    }
 }
@@ -0,0 +1,144 @@
+use super::{
+    faucet::{token_faucet_configured, FAUCET_TOKENS},
+    swap::{
+        hide_balance_flexible_denominations_enabled, hide_balance_max_uses_per_day,
+        hide_balance_min_note_age_secs, hide_balance_relayer_pool_enabled,
+        hide_balance_strict_privacy_mode_enabled, swap_token_configured,
+    },
+    AppState,
+};
+use crate::{
+    bridge_worker,
+    config::Config,
+    constants::{POINTS_MIN_USD_SWAP, POINTS_MIN_USD_SWAP_TESTNET},
+    error::Result,
+    models::ApiResponse,
+    services::{self, hide_executor},
+};
+use axum::{extract::State, Json};
+use serde::Serialize;
+
+// Only non-sensitive values belong here: no keys, secrets, signer or treasury addresses.
+#[derive(Debug, Serialize)]
+pub struct FeatureFlags {
+    pub event_indexer: bool,
+    pub btc_bridge_watcher: bool,
+    pub htlc_refund_monitor: bool,
+    pub hide_balance: bool,
+    pub hide_balance_relayer_pool: bool,
+    pub hide_balance_strict_privacy: bool,
+    pub hide_balance_fixed_denominations: bool,
+    pub faucet: bool,
+    pub snapshot_export: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeatureParameters {
+    pub supported_tokens: Vec<String>,
+    pub min_swap_usd_for_points: f64,
+    pub max_deadline_seconds: i64,
+    pub display_amount_max_decimals: u32,
+    pub hide_balance_executor: String,
+    pub hide_balance_min_note_age_secs: u64,
+    pub hide_balance_max_uses_per_day: u64,
+    pub hide_balance_swap_pairs: Vec<String>,
+    pub faucet_tokens: Vec<String>,
+    pub deposit_base_token: String,
+    pub dark_pool_min_notional_usd: f64,
+    pub dark_pool_max_notional_usd: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeaturesResponse {
+    pub network: String,
+    pub features: FeatureFlags,
+    pub params: FeatureParameters,
+}
+
+// Internal helper that checks conditions for `object_storage_configured`.
+fn object_storage_configured(config: &Config) -> bool {
+    [
+        &config.object_storage_endpoint,
+        &config.object_storage_bucket,
+        &config.object_storage_access_key_id,
+        &config.object_storage_secret_access_key,
+    ]
+    .iter()
+    .all(|value| {
+        value
+            .as_deref()
+            .is_some_and(|value| !value.trim().is_empty())
+    })
+}
+
+// Internal helper that builds inputs for `supported_tokens`.
+fn supported_tokens(config: &Config) -> Vec<String> {
+    config
+        .price_tokens_list()
+        .into_iter()
+        .filter(|token| swap_token_configured(config, token))
+        .collect()
+}
+
+/// GET /api/v1/config/features
+///
+/// # Notes
+/// * Public; lets clients adapt to the env-gated features enabled on this deployment.
+pub async fn get_features(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<FeaturesResponse>>> {
+    let config = &state.config;
+    let is_testnet = config.is_testnet();
+    let hide_balance = hide_executor::configured_executor_address(config).is_some();
+    let faucet_tokens: Vec<String> = if is_testnet {
+        FAUCET_TOKENS
+            .iter()
+            .filter(|token| token_faucet_configured(&state, token))
+            .map(|token| token.to_string())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let response = FeaturesResponse {
+        network: if is_testnet { "testnet" } else { "mainnet" }.to_string(),
+        features: FeatureFlags {
+            event_indexer: services::event_indexer_enabled(),
+            btc_bridge_watcher: bridge_worker::bridge_watcher_enabled(),
+            htlc_refund_monitor: bridge_worker::htlc_refund_monitor_enabled(config),
+            hide_balance,
+            hide_balance_relayer_pool: hide_balance && hide_balance_relayer_pool_enabled(),
+            hide_balance_strict_privacy: hide_balance && hide_balance_strict_privacy_mode_enabled(),
+            hide_balance_fixed_denominations: hide_balance
+                && !hide_balance_flexible_denominations_enabled(config),
+            faucet: !faucet_tokens.is_empty(),
+            snapshot_export: object_storage_configured(config),
+        },
+        params: FeatureParameters {
+            supported_tokens: supported_tokens(config),
+            min_swap_usd_for_points: if is_testnet {
+                POINTS_MIN_USD_SWAP_TESTNET
+            } else {
+                POINTS_MIN_USD_SWAP
+            },
+            max_deadline_seconds: config.max_deadline_seconds,
+            display_amount_max_decimals: config.display_amount_max_decimals,
+            hide_balance_executor: hide_executor::known_hide_executor_kind()
+                .map(|kind| kind.as_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            hide_balance_min_note_age_secs: hide_balance_min_note_age_secs(),
+            hide_balance_max_uses_per_day: hide_balance_max_uses_per_day(),
+            hide_balance_swap_pairs: config
+                .hide_balance_swap_pairs_list()
+                .into_iter()
+                .map(|(from, to)| format!("{}/{}", from, to))
+                .collect(),
+            faucet_tokens,
+            deposit_base_token: config.deposit_base_token.trim().to_ascii_uppercase(),
+            dark_pool_min_notional_usd: config.dark_pool_min_notional_usd,
+            dark_pool_max_notional_usd: config.dark_pool_max_notional_usd,
+        },
+    };
+    Ok(Json(ApiResponse::success(response)))
+}
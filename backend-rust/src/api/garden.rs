@@ -3,11 +3,31 @@ use axum::{
     http::HeaderMap,
     Json,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
-use super::{require_user, AppState};
-use crate::{error::Result, integrations::bridge::GardenClient, models::ApiResponse};
+use super::{extract::TimeRange, require_user, AppState};
+use crate::{
+    error::{AppError, Result},
+    indexer::starknet_client::StarknetClient,
+    integrations::bridge::{GardenClient, GardenSourceSwap},
+    models::ApiResponse,
+    services::garden_earnings::{self, AppEarningsPoint},
+};
+
+// Approximate average block times, used only to turn a block-based timelock into a unix time.
+const BITCOIN_BLOCK_SECS: i64 = 600;
+const ETHEREUM_BLOCK_SECS: i64 = 12;
+const STARKNET_BLOCK_SECS: i64 = 30;
+// Tip-height lookups only enrich the refund response, so they must not hold it up.
+const CHAIN_TIP_TIMEOUT_SECS: u64 = 5;
+
+// Proxied Garden schemas by name, with the time they were fetched.
+static GARDEN_SCHEMA_CACHE: OnceLock<tokio::sync::RwLock<HashMap<String, (Instant, Value)>>> =
+    OnceLock::new();
 
 #[derive(Debug, Deserialize)]
 pub struct GardenStatsQuery {
@@ -43,11 +63,182 @@ pub struct GardenOrderPath {
     pub order_id: String,
 }
 
+/// Refund eligibility of a Garden order, with its instant refund hash while a refund is possible.
+#[derive(Debug, Serialize)]
+pub struct GardenRefundInfo {
+    /// Instant refund hash, as in Garden's own `{ status, result }` response.
+    pub result: Option<String>,
+    pub order_id: String,
+    /// `completed`, `refunded`, `not_initiated`, `locked`, `claimable` or `unknown`.
+    pub refund_status: &'static str,
+    /// `None` when the order could not be loaded to check its timelock.
+    pub refund_eligible: Option<bool>,
+    pub source_chain: Option<String>,
+    pub current_block: Option<u64>,
+    pub claimable_at_block: Option<u64>,
+    /// Unix seconds, extrapolated from the source chain's average block time.
+    pub claimable_at: Option<i64>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GardenSchemaPath {
     pub name: String,
 }
 
+// Internal helper that returns the approximate block time of a Garden chain id.
+fn chain_block_secs(chain: &str) -> Option<i64> {
+    let chain = chain.to_ascii_lowercase();
+    if chain.contains("bitcoin") {
+        Some(BITCOIN_BLOCK_SECS)
+    } else if chain.contains("starknet") {
+        Some(STARKNET_BLOCK_SECS)
+    } else if chain.contains("ethereum") {
+        Some(ETHEREUM_BLOCK_SECS)
+    } else {
+        None
+    }
+}
+
+// Internal helper that derives refund eligibility from the source HTLC and the chain height.
+// The HTLC can be refunded once the chain is past `initiate_block_number + timelock`.
+fn garden_refund_info(
+    order_id: &str,
+    source: &GardenSourceSwap,
+    current_block: Option<u64>,
+    now: i64,
+) -> GardenRefundInfo {
+    let claimable_at_block = source
+        .initiate_block_number
+        .zip(source.timelock_blocks)
+        .map(|(initiated_at, timelock)| initiated_at.saturating_add(timelock).saturating_add(1));
+    let claimable_at = claimable_at_block
+        .zip(current_block)
+        .zip(source.chain.as_deref().and_then(chain_block_secs))
+        .map(|((claimable, current), block_secs)| {
+            let blocks_left = i64::try_from(claimable).unwrap_or(i64::MAX)
+                - i64::try_from(current).unwrap_or(i64::MAX);
+            now.saturating_add(blocks_left.saturating_mul(block_secs))
+        });
+    let refund_status = if source.completed {
+        "completed"
+    } else if source.refunded {
+        "refunded"
+    } else if !source.initiated {
+        "not_initiated"
+    } else {
+        match (claimable_at_block, current_block) {
+            (Some(claimable), Some(current)) if current >= claimable => "claimable",
+            (Some(_), Some(_)) => "locked",
+            _ => "unknown",
+        }
+    };
+    GardenRefundInfo {
+        result: None,
+        order_id: order_id.to_string(),
+        refund_status,
+        refund_eligible: Some(refund_status == "claimable"),
+        source_chain: source.chain.clone(),
+        current_block,
+        claimable_at_block,
+        claimable_at,
+    }
+}
+
+// Internal helper that reads the hash out of Garden's instant refund hash payload.
+fn instant_refund_hash(payload: &Value) -> Option<String> {
+    payload
+        .get("result")
+        .unwrap_or(payload)
+        .as_str()
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+}
+
+// Internal helper that fetches the current block height of a Garden source chain.
+async fn current_block_for_chain(state: &AppState, chain: &str) -> Result<u64> {
+    let chain = chain.to_ascii_lowercase();
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(CHAIN_TIP_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| AppError::Internal(format!("Failed to build HTTP client: {}", e)))?;
+    if chain.contains("bitcoin") {
+        let base_url = if chain.contains("testnet") {
+            &state.config.mempool_testnet_api_url
+        } else {
+            &state.config.mempool_api_url
+        };
+        let url = format!(
+            "{}/blocks/tip/height",
+            base_url.trim().trim_end_matches('/')
+        );
+        let body = client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| {
+                AppError::ExternalAPI(format!("Bitcoin tip height request failed: {}", e))
+            })?
+            .text()
+            .await
+            .map_err(|e| AppError::ExternalAPI(format!("Bitcoin tip height read failed: {}", e)))?;
+        return body
+            .trim()
+            .parse()
+            .map_err(|_| AppError::ExternalAPI("Bitcoin tip height is not a number".to_string()));
+    }
+    if chain.contains("starknet") {
+        return StarknetClient::new(state.config.starknet_rpc_url.clone())
+            .get_block_number()
+            .await;
+    }
+    if chain.contains("ethereum") {
+        let body: Value = client
+            .post(state.config.ethereum_rpc_url.trim())
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "eth_blockNumber",
+                "params": [],
+            }))
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::ExternalAPI(format!("Ethereum block number request failed: {}", e))
+            })?
+            .json()
+            .await
+            .map_err(|e| {
+                AppError::ExternalAPI(format!("Ethereum block number parse failed: {}", e))
+            })?;
+        return body
+            .get("result")
+            .and_then(Value::as_str)
+            .and_then(|raw| u64::from_str_radix(raw.trim_start_matches("0x"), 16).ok())
+            .ok_or_else(|| AppError::ExternalAPI("Ethereum block number missing".to_string()));
+    }
+    Err(AppError::BadRequest(format!(
+        "Refund timelock lookup is not supported for chain {}",
+        chain
+    )))
+}
+
+// Internal helper that maps a requested schema name onto the configured allowlist.
+fn resolve_garden_schema_name(allowed: &[String], name: &str) -> Result<String> {
+    let name = name.trim();
+    allowed
+        .iter()
+        .find(|candidate| candidate.as_str() == name)
+        .cloned()
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Unknown Garden schema '{}'; supported schemas: {}",
+                name,
+                allowed.join(", ")
+            ))
+        })
+}
+
 // Internal helper that supports `garden_client` operations.
 fn garden_client(state: &AppState) -> GardenClient {
     GardenClient::new(
@@ -169,27 +360,108 @@ pub async fn get_order_by_id(
 }
 
 /// GET /api/v1/garden/orders/{order_id}/instant-refund-hash
+///
+/// # Notes
+/// * `result` is the instant refund hash string; refund eligibility computed from the source
+///   HTLC timelock is returned beside it. The hash is only fetched while a refund is still
+///   possible (not completed or already refunded).
+/// * When the order itself cannot be loaded, the hash is still returned with
+///   `refund_status: "unknown"` and `refund_eligible: null`.
 pub async fn get_order_instant_refund_hash(
     State(state): State<AppState>,
     headers: HeaderMap,
     Path(path): Path<GardenOrderPath>,
-) -> Result<Json<ApiResponse<Value>>> {
+) -> Result<Json<ApiResponse<GardenRefundInfo>>> {
     let _ = require_user(&headers, &state).await?;
-    let payload = garden_client(&state)
-        .get_order_instant_refund_hash(&path.order_id)
-        .await?;
-    Ok(Json(ApiResponse::success(payload)))
+    let client = garden_client(&state);
+    let order = match client.get_order_by_id(&path.order_id).await {
+        Ok(order) => order,
+        Err(err) => {
+            tracing::warn!(
+                "Garden order {} lookup failed, returning refund hash without eligibility: {}",
+                path.order_id,
+                err
+            );
+            let payload = client.get_order_instant_refund_hash(&path.order_id).await?;
+            return Ok(Json(ApiResponse::success(GardenRefundInfo {
+                result: instant_refund_hash(&payload),
+                order_id: path.order_id,
+                refund_status: "unknown",
+                refund_eligible: None,
+                source_chain: None,
+                current_block: None,
+                claimable_at_block: None,
+                claimable_at: None,
+            })));
+        }
+    };
+    let source = GardenSourceSwap::from_order(&order);
+    let current_block = match source.chain.as_deref() {
+        Some(chain) if source.initiated && !source.completed && !source.refunded => {
+            match current_block_for_chain(&state, chain).await {
+                Ok(block) => Some(block),
+                Err(err) => {
+                    tracing::warn!("Garden refund timelock lookup failed on {}: {}", chain, err);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    let mut info = garden_refund_info(
+        &path.order_id,
+        &source,
+        current_block,
+        chrono::Utc::now().timestamp(),
+    );
+    if !matches!(info.refund_status, "completed" | "refunded") {
+        let payload = client.get_order_instant_refund_hash(&path.order_id).await?;
+        info.result = instant_refund_hash(&payload);
+    }
+    Ok(Json(ApiResponse::success(info)))
 }
 
 /// GET /api/v1/garden/schemas/{name}
+///
+/// # Notes
+/// * Only names listed in `GARDEN_SCHEMA_NAMES` are proxied; other names return 404.
+/// * Schemas are cached for `GARDEN_SCHEMA_CACHE_TTL_SECS`; when Garden fails, the last cached
+///   copy is served even if expired.
 pub async fn get_schema(
     State(state): State<AppState>,
     headers: HeaderMap,
     Path(path): Path<GardenSchemaPath>,
 ) -> Result<Json<ApiResponse<Value>>> {
     let _ = require_user(&headers, &state).await?;
-    let payload = garden_client(&state).get_schema(&path.name).await?;
-    Ok(Json(ApiResponse::success(payload)))
+    let name = resolve_garden_schema_name(&state.config.garden_schema_names_list(), &path.name)?;
+    let cache = GARDEN_SCHEMA_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()));
+    let cached = cache.read().await.get(&name).cloned();
+    if let Some((fetched_at, payload)) = cached.as_ref() {
+        if fetched_at.elapsed().as_secs() < state.config.garden_schema_cache_ttl_secs {
+            return Ok(Json(ApiResponse::success(payload.clone())));
+        }
+    }
+
+    match garden_client(&state).get_schema(&name).await {
+        Ok(payload) => {
+            cache
+                .write()
+                .await
+                .insert(name, (Instant::now(), payload.clone()));
+            Ok(Json(ApiResponse::success(payload)))
+        }
+        Err(err) => match cached {
+            Some((_, payload)) => {
+                tracing::warn!(
+                    "Garden schema {} refresh failed, serving stale copy: {}",
+                    name,
+                    err
+                );
+                Ok(Json(ApiResponse::success(payload)))
+            }
+            None => Err(err),
+        },
+    }
 }
 
 /// GET /api/v1/garden/apps/earnings
@@ -201,3 +473,89 @@ pub async fn get_app_earnings(
     let payload = garden_client(&state).get_app_earnings().await?;
     Ok(Json(ApiResponse::success(payload)))
 }
+
+/// GET /api/v1/garden/apps/earnings/history
+///
+/// # Notes
+/// * Served from local snapshots, so it keeps working while Garden is down. Defaults to the
+///   last 30 days, oldest first.
+pub async fn get_app_earnings_history(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    range: TimeRange,
+) -> Result<Json<ApiResponse<Vec<AppEarningsPoint>>>> {
+    let _ = require_user(&headers, &state).await?;
+    let (from, to) = range.resolve(chrono::Duration::days(30));
+    let points = garden_earnings::list_snapshots(&state.db, from, to).await?;
+    Ok(Json(ApiResponse::success(points)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `resolve_garden_schema_name_uses_allowlist` operations.
+    fn resolve_garden_schema_name_uses_allowlist() {
+        let allowed = vec!["evm:htlc".to_string(), "starknet:htlc_erc20".to_string()];
+        assert_eq!(
+            resolve_garden_schema_name(&allowed, " starknet:htlc_erc20 ").unwrap(),
+            "starknet:htlc_erc20"
+        );
+        assert!(matches!(
+            resolve_garden_schema_name(&allowed, "../orders"),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    // Internal helper that supports `garden_refund_info_tracks_timelock` operations.
+    fn garden_refund_info_tracks_timelock() {
+        let source = GardenSourceSwap {
+            chain: Some("ethereum_sepolia".to_string()),
+            timelock_blocks: Some(100),
+            initiate_block_number: Some(1_000),
+            initiated: true,
+            ..GardenSourceSwap::default()
+        };
+        let locked = garden_refund_info("order", &source, Some(1_050), 1_700_000_000);
+        assert_eq!(locked.refund_status, "locked");
+        assert_eq!(locked.refund_eligible, Some(false));
+        assert_eq!(locked.claimable_at_block, Some(1_101));
+        assert_eq!(
+            locked.claimable_at,
+            Some(1_700_000_000 + 51 * ETHEREUM_BLOCK_SECS)
+        );
+
+        let claimable = garden_refund_info("order", &source, Some(1_101), 1_700_000_000);
+        assert_eq!(claimable.refund_status, "claimable");
+        assert_eq!(claimable.refund_eligible, Some(true));
+
+        let unknown = garden_refund_info("order", &source, None, 1_700_000_000);
+        assert_eq!(unknown.refund_status, "unknown");
+        assert_eq!(unknown.claimable_at, None);
+
+        let completed = GardenSourceSwap {
+            completed: true,
+            ..source
+        };
+        let done = garden_refund_info("order", &completed, None, 1_700_000_000);
+        assert_eq!(done.refund_status, "completed");
+        assert_eq!(done.refund_eligible, Some(false));
+    }
+
+    #[test]
+    // Internal helper that supports `instant_refund_hash_reads_result_string` operations.
+    fn instant_refund_hash_reads_result_string() {
+        let payload = serde_json::json!({ "status": "Ok", "result": " 0xabc " });
+        assert_eq!(instant_refund_hash(&payload), Some("0xabc".to_string()));
+        assert_eq!(
+            instant_refund_hash(&Value::String("0xdef".to_string())),
+            Some("0xdef".to_string())
+        );
+        assert_eq!(
+            instant_refund_hash(&serde_json::json!({ "status": "Error", "result": null })),
+            None
+        );
+    }
+}
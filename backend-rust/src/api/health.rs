@@ -1,6 +1,7 @@
-use super::AppState;
+use super::{maintenance::MaintenanceState, AppState};
 use axum::{extract::State, Json};
 use serde::Serialize;
+use std::time::Instant;
 
 #[derive(Serialize)]
 pub struct HealthResponse {
@@ -8,12 +9,27 @@ pub struct HealthResponse {
     pub version: String,
     pub database: String,
     pub redis: String,
+    pub redis_latency_ms: Option<u64>,
+    pub maintenance: bool,
+    pub maintenance_message: Option<String>,
+    pub prover_queue_depth: usize,
 }
 
 // Internal helper that builds inputs for `build_health_response`.
-fn build_health_response(db_ok: bool, redis_ok: bool) -> HealthResponse {
+fn build_health_response(
+    db_ok: bool,
+    redis_latency_ms: Option<u64>,
+    maintenance: Option<&MaintenanceState>,
+    prover_queue_depth: usize,
+) -> HealthResponse {
+    let redis_ok = redis_latency_ms.is_some();
+    let maintenance = maintenance.filter(|maintenance| maintenance.enabled);
     HealthResponse {
-        status: "ok".to_string(),
+        status: if maintenance.is_some() {
+            "maintenance".to_string()
+        } else {
+            "ok".to_string()
+        },
         version: env!("CARGO_PKG_VERSION").to_string(),
         database: if db_ok {
             "connected".to_string()
@@ -25,6 +41,10 @@ fn build_health_response(db_ok: bool, redis_ok: bool) -> HealthResponse {
         } else {
             "disconnected".to_string()
         },
+        redis_latency_ms,
+        maintenance: maintenance.is_some(),
+        maintenance_message: maintenance.map(MaintenanceState::client_message),
+        prover_queue_depth,
     }
 }
 
@@ -46,12 +66,23 @@ pub async fn health_check(State(state): State<AppState>) -> Json<HealthResponse>
 
     // 2. Cek koneksi Redis
     let mut redis_conn = state.redis.clone();
-    let redis_ok = redis::cmd("PING")
+    let started = Instant::now();
+    let redis_latency_ms = redis::cmd("PING")
         .query_async::<String>(&mut redis_conn)
         .await
-        .is_ok();
+        .ok()
+        .map(|_| started.elapsed().as_millis() as u64);
+    state.set_redis_available(redis_latency_ms.is_some());
+
+    // 3. Maintenance mode (shared via Redis)
+    let maintenance = super::maintenance::current_maintenance(&state).await;
 
-    Json(build_health_response(db_ok, redis_ok))
+    Json(build_health_response(
+        db_ok,
+        redis_latency_ms,
+        maintenance.as_ref(),
+        super::privacy::proof_queue_depth(),
+    ))
 }
 
 #[cfg(test)]
@@ -62,9 +93,28 @@ mod tests {
     // Internal helper that builds inputs for `build_health_response_formats_status`.
     fn build_health_response_formats_status() {
         // Memastikan status koneksi dirender dengan benar
-        let response = build_health_response(true, false);
+        let response = build_health_response(true, None, None, 0);
         assert_eq!(response.database, "connected");
         assert_eq!(response.redis, "disconnected");
         assert_eq!(response.status, "ok");
+        assert!(!response.maintenance);
+
+        let response = build_health_response(true, Some(3), None, 2);
+        assert_eq!(response.redis, "connected");
+        assert_eq!(response.redis_latency_ms, Some(3));
+        assert_eq!(response.prover_queue_depth, 2);
+
+        let maintenance = MaintenanceState {
+            enabled: true,
+            message: Some("Database migration".to_string()),
+            updated_at: chrono::Utc::now(),
+        };
+        let response = build_health_response(true, Some(3), Some(&maintenance), 0);
+        assert_eq!(response.status, "maintenance");
+        assert!(response.maintenance);
+        assert_eq!(
+            response.maintenance_message.as_deref(),
+            Some("Database migration")
+        );
     }
 }
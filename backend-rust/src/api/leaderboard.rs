@@ -6,10 +6,19 @@ use axum::{
 use rust_decimal::Decimal;
 use serde::Serialize;
 
-use crate::{constants::EPOCH_DURATION_SECONDS, error::Result, models::ApiResponse};
+use crate::{
+    error::{AppError, Result},
+    models::ApiResponse,
+    services::{
+        achievement_service::{AchievementProgress, AchievementService},
+        snapshot_manager::{live_leaderboard, RankedIdentity, SnapshotManager},
+    },
+};
 
 use super::{ensure_user_exists, AppState};
 
+const LEADERBOARD_LIMIT: usize = 100;
+
 #[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct LeaderboardEntry {
     pub rank: i64,
@@ -38,6 +47,14 @@ pub struct LeaderboardResponse {
     pub total_users: i64,
 }
 
+#[derive(Debug, Serialize)]
+pub struct PointsRankingsResponse {
+    pub view: String,
+    pub epoch: i64,
+    pub entries: Vec<LeaderboardEntry>,
+    pub total_users: i64,
+}
+
 #[derive(Debug, Serialize)]
 pub struct GlobalMetricsResponse {
     pub points_total: f64,
@@ -120,10 +137,11 @@ impl<'a> GlobalMetricsStore for PgMetricsStore<'a> {
 // Internal helper that supports `epoch_window` operations.
 fn epoch_window(
     epoch: i64,
+    epoch_seconds: i64,
 ) -> Result<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)> {
-    let start = chrono::DateTime::<chrono::Utc>::from_timestamp(epoch * EPOCH_DURATION_SECONDS, 0)
+    let start = chrono::DateTime::<chrono::Utc>::from_timestamp(epoch * epoch_seconds, 0)
         .ok_or_else(|| crate::error::AppError::BadRequest("Invalid epoch".to_string()))?;
-    let end = start + chrono::Duration::seconds(EPOCH_DURATION_SECONDS);
+    let end = start + chrono::Duration::seconds(epoch_seconds);
     Ok((start, end))
 }
 
@@ -131,8 +149,9 @@ fn epoch_window(
 async fn get_global_metrics_epoch_with<S: GlobalMetricsStore + Sync>(
     store: &S,
     epoch: i64,
+    epoch_seconds: i64,
 ) -> Result<GlobalMetricsResponse> {
-    let (start, end) = epoch_window(epoch)?;
+    let (start, end) = epoch_window(epoch, epoch_seconds)?;
     let points_total = store.points_total(epoch).await?;
     let volume_total = store.volume_total(start, end).await?;
     let referral_total = store.referral_total(start, end).await?;
@@ -210,6 +229,7 @@ pub struct UserRankCategory {
 #[derive(Debug, Serialize)]
 pub struct UserRankCategoriesResponse {
     pub categories: Vec<UserRankCategory>,
+    pub achievements: Vec<AchievementProgress>,
 }
 
 /// GET /api/v1/leaderboard/:type
@@ -248,10 +268,13 @@ pub async fn get_user_rank(
     State(state): State<AppState>,
     Path(address): Path<String>,
 ) -> Result<Json<ApiResponse<UserRankResponse>>> {
-    let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+    let current_epoch = state.config.current_rewards_epoch();
 
     let (canonical_address, scope_addresses) =
         resolve_leaderboard_identity(&state, &address).await?;
+    if let Some(ranked) = live_rank_of(current_epoch, &canonical_address) {
+        return Ok(Json(ApiResponse::success(ranked)));
+    }
     let user_total: f64 = sqlx::query_scalar::<_, f64>(
         "SELECT COALESCE(SUM(total_points), 0)::FLOAT
          FROM points
@@ -345,11 +368,68 @@ pub async fn get_user_rank(
     })))
 }
 
+/// GET /api/v1/leaderboard/points/live
+pub async fn get_live_points_rankings(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<PointsRankingsResponse>>> {
+    let current_epoch = state.config.current_rewards_epoch();
+    // Until the first reconciliation seeds the live board, serve the same view from SQL.
+    let (entries, total_users) = match live_top(current_epoch, LEADERBOARD_LIMIT) {
+        Some((ranked, total_users)) => (
+            entries_with_display_names(&state, ranked).await?,
+            total_users,
+        ),
+        None => {
+            let total_users: CountResult =
+                sqlx::query_as("SELECT COUNT(DISTINCT address) as count FROM users")
+                    .fetch_one(state.db.pool())
+                    .await?;
+            (get_points_leaderboard(&state).await?, total_users.count)
+        }
+    };
+    Ok(Json(ApiResponse::success(PointsRankingsResponse {
+        view: "live".to_string(),
+        epoch: current_epoch,
+        entries,
+        total_users,
+    })))
+}
+
+/// GET /api/v1/leaderboard/points/finalized/:epoch
+pub async fn get_finalized_points_rankings(
+    State(state): State<AppState>,
+    Path(epoch): Path<i64>,
+) -> Result<Json<ApiResponse<PointsRankingsResponse>>> {
+    let manager = SnapshotManager::new(state.db.clone(), state.config.clone());
+    let Some(ranked) = manager
+        .finalized_rankings(epoch, LEADERBOARD_LIMIT as i64)
+        .await?
+    else {
+        return Err(AppError::NotFound(format!(
+            "Epoch {} has not been finalized",
+            epoch
+        )));
+    };
+    let total_users: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(total_users, 0)::BIGINT FROM epoch_snapshots WHERE epoch = $1 LIMIT 1",
+    )
+    .bind(epoch)
+    .fetch_one(state.db.pool())
+    .await?;
+    let entries = entries_with_display_names(&state, ranked).await?;
+    Ok(Json(ApiResponse::success(PointsRankingsResponse {
+        view: "finalized".to_string(),
+        epoch,
+        entries,
+        total_users,
+    })))
+}
+
 /// GET /api/v1/leaderboard/global
 pub async fn get_global_metrics(
     State(state): State<AppState>,
 ) -> Result<Json<ApiResponse<GlobalMetricsResponse>>> {
-    let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+    let current_epoch = state.config.current_rewards_epoch();
 
     let points_total: Decimal = sqlx::query_scalar::<_, Decimal>(
         "SELECT COALESCE(SUM(total_points), 0) FROM points WHERE epoch = $1",
@@ -384,7 +464,9 @@ pub async fn get_global_metrics_epoch(
     let store = PgMetricsStore {
         pool: state.db.pool(),
     };
-    let metrics = get_global_metrics_epoch_with(&store, epoch).await?;
+    let metrics =
+        get_global_metrics_epoch_with(&store, epoch, state.config.rewards_epoch_duration_seconds)
+            .await?;
     Ok(Json(ApiResponse::success(metrics)))
 }
 
@@ -396,7 +478,7 @@ pub async fn get_user_categories(
     let (canonical_address, scope_addresses) =
         resolve_leaderboard_identity(&state, &address).await?;
 
-    let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+    let current_epoch = state.config.current_rewards_epoch();
 
     let (user_points_total, points_rank, points_total): (f64, RankResult, CountResult) = tokio::try_join!(
         async {
@@ -652,14 +734,93 @@ pub async fn get_user_categories(
         },
     ];
 
+    let achievements = AchievementService::new(state.db.clone(), state.config.clone())
+        .progress_for(&canonical_address)
+        .await?;
+
     Ok(Json(ApiResponse::success(UserRankCategoriesResponse {
         categories,
+        achievements,
     })))
 }
 
+// Internal helper that reads the live leaderboard when it tracks `epoch`.
+fn live_top(epoch: i64, limit: usize) -> Option<(Vec<RankedIdentity>, i64)> {
+    let board = live_leaderboard().read().ok()?;
+    if board.epoch() != Some(epoch) {
+        return None;
+    }
+    Some((board.top(limit), board.total_identities()))
+}
+
+// Internal helper that resolves a user's rank from the live leaderboard when it tracks `epoch`.
+fn live_rank_of(epoch: i64, address: &str) -> Option<UserRankResponse> {
+    let board = live_leaderboard().read().ok()?;
+    if board.epoch() != Some(epoch) {
+        return None;
+    }
+    let ranked = board.rank_of(address);
+    let total_users = board.total_identities().max(1);
+    Some(UserRankResponse {
+        rank: ranked.rank,
+        total_users,
+        percentile: compute_percentile(ranked.rank, total_users),
+        value: ranked.value,
+    })
+}
+
+// Internal helper that attaches display names to ranked identities.
+async fn entries_with_display_names(
+    state: &AppState,
+    ranked: Vec<RankedIdentity>,
+) -> Result<Vec<LeaderboardEntry>> {
+    let identities: Vec<String> = ranked
+        .iter()
+        .map(|entry| entry.identity.to_ascii_lowercase())
+        .collect();
+    let names: Vec<(String, Option<String>)> = sqlx::query_as(
+        "SELECT LOWER(address), NULLIF(TRIM(display_name), '') FROM users WHERE LOWER(address) = ANY($1)",
+    )
+    .bind(&identities)
+    .fetch_all(state.db.pool())
+    .await?;
+    let names: std::collections::HashMap<String, String> = names
+        .into_iter()
+        .filter_map(|(address, name)| name.map(|name| (address, name)))
+        .collect();
+
+    Ok(ranked
+        .into_iter()
+        .map(|entry| {
+            let fallback = format!(
+                "user_{}",
+                entry
+                    .identity
+                    .get(entry.identity.len().saturating_sub(6)..)
+                    .unwrap_or(&entry.identity)
+            );
+            LeaderboardEntry {
+                rank: entry.rank,
+                display_name: Some(
+                    names
+                        .get(&entry.identity.to_ascii_lowercase())
+                        .cloned()
+                        .unwrap_or(fallback),
+                ),
+                address: entry.identity,
+                value: entry.value,
+                change_24h: None,
+            }
+        })
+        .collect())
+}
+
 // Internal helper that fetches data for `get_points_leaderboard`.
 async fn get_points_leaderboard(state: &AppState) -> Result<Vec<LeaderboardEntry>> {
-    let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+    let current_epoch = state.config.current_rewards_epoch();
+    if let Some((ranked, _)) = live_top(current_epoch, LEADERBOARD_LIMIT) {
+        return entries_with_display_names(state, ranked).await;
+    }
 
     let entries = sqlx::query_as::<_, LeaderboardEntry>(
         r#"
@@ -778,6 +939,7 @@ async fn get_referrals_leaderboard(state: &AppState) -> Result<Vec<LeaderboardEn
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::EPOCH_DURATION_SECONDS;
     use async_trait::async_trait;
 
     #[test]
@@ -845,7 +1007,9 @@ mod tests {
             referral_total: 42,
         };
 
-        let metrics = get_global_metrics_epoch_with(&store, 1).await.unwrap();
+        let metrics = get_global_metrics_epoch_with(&store, 1, EPOCH_DURATION_SECONDS)
+            .await
+            .unwrap();
         assert_eq!(metrics.points_total, 1234.0);
         assert_eq!(metrics.volume_total, 4567.0);
         assert_eq!(metrics.referral_total, 42);
@@ -9,23 +9,32 @@ use super::privacy::{
     bind_intent_hash_into_payload, ensure_public_inputs_bind_nullifier_commitment,
     ensure_public_inputs_bind_root_nullifier, ensure_public_inputs_bind_v3_shape,
     generate_auto_garaga_payload, AutoPrivacyPayloadResponse, AutoPrivacyTxContext,
+    PrivacyPayloadLimits,
+};
+use super::relayer_access::submit_relayer_calls;
+use super::swap::{
+    ensure_note_mixing_window_elapsed, hide_deposit_requires_asset_rule,
+    parse_decimal_to_u256_parts, token_decimals,
+};
+use crate::services::hide_executor::{
+    hide_executor_kind, shielded_current_root, shielded_note_deposit_timestamp,
+    shielded_note_registered, HideExecutorKind,
 };
-use super::swap::{parse_decimal_to_u256_parts, token_decimals};
 use crate::services::notification_service::{NotificationService, NotificationType};
-use crate::services::onchain::{felt_to_u128, parse_felt, OnchainReader};
-use crate::services::privacy_verifier::parse_privacy_verifier_kind;
+use crate::services::onchain::{felt_to_u128, parse_felt, OnchainError, OnchainReader};
+use crate::services::privacy_verifier::select_privacy_verifier;
 use crate::services::relayer::RelayerService;
 use crate::{
     // 1. Import modul hash agar terpakai
     constants::{
-        token_address_for, EPOCH_DURATION_SECONDS, POINTS_MIN_USD_LIMIT_ORDER,
-        POINTS_MIN_USD_LIMIT_ORDER_TESTNET, POINTS_PER_USD_LIMIT_ORDER,
+        token_address_for, POINTS_MIN_USD_LIMIT_ORDER, POINTS_MIN_USD_LIMIT_ORDER_TESTNET,
+        POINTS_PER_USD_LIMIT_ORDER,
     },
     crypto::hash,
     error::Result,
     models::{
         user::PrivacyVerificationPayload as ModelPrivacyVerificationPayload, ApiResponse,
-        CreateLimitOrderRequest, LimitOrder, PaginatedResponse,
+        CreateLimitOrderRequest, LimitOrder, OrderStatus, OrderType, PaginatedResponse,
     },
     services::nft_discount::{consume_nft_usage_if_active, read_active_discount_rate},
     services::price_guard::{
@@ -154,23 +163,12 @@ fn discount_contract_address(state: &AppState) -> Option<&str> {
         .filter(|addr| !addr.trim().is_empty() && !addr.starts_with("0x0000"))
 }
 
-// Internal helper that supports `current_nft_period_epoch` operations in the limit-order flow.
-fn current_nft_period_epoch() -> i64 {
-    let now = chrono::Utc::now().timestamp();
-    let period = (EPOCH_DURATION_SECONDS as i64).max(1);
-    if now <= 0 {
-        0
-    } else {
-        now / period
-    }
-}
-
 // Internal helper that fetches data for `fallback_nft_discount_from_local_state` in the limit-order flow.
 async fn fallback_nft_discount_from_local_state(state: &AppState, user_address: &str) -> f64 {
     let Some(contract) = discount_contract_address(state) else {
         return 0.0;
     };
-    let period_epoch = current_nft_period_epoch();
+    let period_epoch = state.config.current_nft_period_epoch();
     match state
         .db
         .get_nft_discount_state(contract, user_address, period_epoch)
@@ -288,6 +286,66 @@ fn ensure_supported_limit_order_pair(from_token: &str, to_token: &str) -> Result
     Ok(())
 }
 
+// Internal helper that parses or transforms values for `parse_order_trigger` in the limit-order flow.
+// Conditional orders need a positive trigger price; plain limit orders must not carry one.
+fn parse_order_trigger(
+    order_type: Option<&str>,
+    trigger_price: Option<&str>,
+) -> Result<(OrderType, Option<f64>)> {
+    let order_type = match order_type.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(raw) => raw
+            .parse::<OrderType>()
+            .map_err(crate::error::AppError::BadRequest)?,
+        None => OrderType::Limit,
+    };
+    let trigger_price = trigger_price
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|raw| {
+            raw.parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite() && *value > 0.0)
+                .ok_or_else(|| {
+                    crate::error::AppError::BadRequest("Invalid trigger_price".to_string())
+                })
+        })
+        .transpose()?;
+    match (order_type.is_conditional(), trigger_price) {
+        (true, None) => Err(crate::error::AppError::BadRequest(format!(
+            "trigger_price is required for {} orders",
+            order_type.as_str()
+        ))),
+        (false, Some(_)) => Err(crate::error::AppError::BadRequest(
+            "trigger_price is only supported for stop_loss and take_profit orders".to_string(),
+        )),
+        _ => Ok((order_type, trigger_price)),
+    }
+}
+
+// Internal helper that checks the trigger sits on the correct side of the current market price.
+// A trigger already satisfied at creation would fire immediately.
+fn ensure_trigger_side(order_type: OrderType, trigger_price: f64, market_price: f64) -> Result<()> {
+    let valid = match order_type {
+        OrderType::Limit => true,
+        OrderType::StopLoss => trigger_price < market_price,
+        OrderType::TakeProfit => trigger_price > market_price,
+    };
+    if valid {
+        return Ok(());
+    }
+    Err(crate::error::AppError::BadRequest(format!(
+        "{} trigger_price {} must be {} the current price {}",
+        order_type.as_str(),
+        trigger_price,
+        if order_type == OrderType::StopLoss {
+            "below"
+        } else {
+            "above"
+        },
+        market_price
+    )))
+}
+
 // Internal helper that supports `map_privacy_payload` operations in the limit-order flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 fn map_privacy_payload(
@@ -363,38 +421,6 @@ fn hide_balance_v2_redeem_only_enabled() -> bool {
     env_flag("HIDE_BALANCE_V2_REDEEM_ONLY", false)
 }
 
-fn hide_balance_min_note_age_secs() -> u64 {
-    std::env::var("HIDE_BALANCE_MIN_NOTE_AGE_SECS")
-        .or_else(|_| std::env::var("NEXT_PUBLIC_HIDE_BALANCE_MIN_NOTE_AGE_SECS"))
-        .ok()
-        .and_then(|value| value.trim().parse::<u64>().ok())
-        .filter(|value| *value > 0)
-        .unwrap_or(60)
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum HideExecutorKind {
-    PrivateActionExecutorV1,
-    ShieldedPoolV2,
-    ShieldedPoolV3,
-}
-
-// Internal helper that supports `hide_executor_kind` operations in the limit-order flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn hide_executor_kind() -> HideExecutorKind {
-    let raw = std::env::var("HIDE_BALANCE_EXECUTOR_KIND")
-        .unwrap_or_default()
-        .trim()
-        .to_ascii_lowercase();
-    if matches!(raw.as_str(), "shielded_pool_v3" | "shielded-v3" | "v3") {
-        HideExecutorKind::ShieldedPoolV3
-    } else if matches!(raw.as_str(), "shielded_pool_v2" | "shielded-v2" | "v2") {
-        HideExecutorKind::ShieldedPoolV2
-    } else {
-        HideExecutorKind::PrivateActionExecutorV1
-    }
-}
-
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum HidePoolVersion {
     V2,
@@ -481,43 +507,6 @@ fn resolve_limit_order_target_felt(state: &AppState) -> Result<Felt> {
     })
 }
 
-fn is_missing_entrypoint_error(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    if lower.contains("entry_point_not_found") || lower.contains("entrypoint_not_found") {
-        return true;
-    }
-    let mentions_entrypoint =
-        lower.contains("entrypoint") || lower.contains("entry point") || lower.contains("selector");
-    let mentions_missing = lower.contains("does not exist")
-        || lower.contains("not found")
-        || lower.contains("missing");
-    mentions_entrypoint && mentions_missing
-}
-
-fn is_transient_probe_error(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    lower.contains("timeout")
-        || lower.contains("timed out")
-        || lower.contains("too many requests")
-        || lower.contains("429")
-        || lower.contains("gateway")
-        || lower.contains("temporarily unavailable")
-        || lower.contains("invalid peer certificate")
-        || lower.contains("unknownissuer")
-        || lower.contains("connection reset")
-        || lower.contains("network")
-}
-
-fn is_contract_revert_probe_error(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    lower.contains("contracterror")
-        || lower.contains("contract error")
-        || lower.contains("revert_error")
-        || lower.contains("execution_error")
-        || lower.contains("innercontractexecutionerror")
-        || lower.contains("invalid caller")
-}
-
 async fn contract_supports_selector(
     state: &AppState,
     contract: Felt,
@@ -525,7 +514,7 @@ async fn contract_supports_selector(
 ) -> Result<bool> {
     let reader = OnchainReader::from_config(&state.config)?;
     let probe = reader
-        .call(FunctionCall {
+        .call_typed(FunctionCall {
             contract_address: contract,
             entry_point_selector: selector,
             calldata: vec![Felt::ONE],
@@ -533,24 +522,18 @@ async fn contract_supports_selector(
         .await;
     match probe {
         Ok(_) => Ok(true),
-        Err(crate::error::AppError::BlockchainRPC(message)) => {
-            if is_missing_entrypoint_error(&message) {
-                Ok(false)
-            } else if is_contract_revert_probe_error(&message) {
-                Ok(true)
-            } else if is_transient_probe_error(&message) {
-                Err(crate::error::AppError::BlockchainRPC(format!(
-                    "Failed to probe selector support on {}: {}",
-                    contract, message
-                )))
-            } else {
-                Err(crate::error::AppError::BlockchainRPC(format!(
-                    "Unexpected selector probe error on {}: {}",
-                    contract, message
-                )))
-            }
+        Err(OnchainError::EntrypointNotFound(_)) => Ok(false),
+        Err(OnchainError::ContractRevert(_)) => Ok(true),
+        Err(err @ (OnchainError::RateLimited(_) | OnchainError::Network(_))) => {
+            Err(crate::error::AppError::BlockchainRPC(format!(
+                "Failed to probe selector support on {}: {}",
+                contract, err
+            )))
         }
-        Err(err) => Err(err),
+        Err(OnchainError::Other(message)) => Err(crate::error::AppError::BlockchainRPC(format!(
+            "Unexpected selector probe error on {}: {}",
+            contract, message
+        ))),
     }
 }
 
@@ -571,7 +554,7 @@ async fn executor_supports_selectors(
 
 async fn resolve_private_action_executor_felt_for_limit_hide(state: &AppState) -> Result<Felt> {
     let candidates = resolve_private_action_executor_candidates(&state.config)?;
-    let required_selectors: &[&str] = match hide_executor_kind() {
+    let required_selectors: &[&str] = match hide_executor_kind()? {
         HideExecutorKind::PrivateActionExecutorV1 => &[
             "preview_limit_intent_hash",
             "submit_private_intent",
@@ -597,7 +580,7 @@ async fn resolve_private_action_executor_felt_for_limit_hide(state: &AppState) -
             tracing::info!(
                 "Using compatible limit-order hide executor {} (kind={:?})",
                 candidate,
-                hide_executor_kind()
+                hide_executor_kind()?
             );
             return Ok(candidate);
         }
@@ -606,7 +589,7 @@ async fn resolve_private_action_executor_felt_for_limit_hide(state: &AppState) -
 
     Err(crate::error::AppError::BadRequest(format!(
         "No compatible hide executor found for limit-order flow (kind={:?}). Checked: {}",
-        hide_executor_kind(),
+        hide_executor_kind()?,
         unsupported.join(", ")
     )))
 }
@@ -632,12 +615,20 @@ async fn resolve_limit_order_action_selector(
 
 // Internal helper that parses or transforms values for `normalize_hex_items` in the limit-order flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
-fn normalize_hex_items(items: &[String]) -> Vec<String> {
+fn normalize_hex_items(items: &[String], field: &str) -> Result<Vec<String>> {
     items
         .iter()
         .map(|item| item.trim())
-        .filter(|item| !item.is_empty())
-        .map(ToOwned::to_owned)
+        .enumerate()
+        .filter(|(_, item)| !item.is_empty())
+        .map(|(index, item)| {
+            parse_felt(item).map(|_| item.to_owned()).map_err(|_| {
+                crate::error::AppError::BadRequest(format!(
+                    "privacy.{}[{}] is not a valid felt: {}",
+                    field, index, item
+                ))
+            })
+        })
         .collect()
 }
 
@@ -646,11 +637,16 @@ fn normalize_hex_items(items: &[String]) -> Vec<String> {
 fn payload_from_request(
     payload: Option<&ModelPrivacyVerificationPayload>,
     verifier: &str,
-) -> Option<AutoPrivacyPayloadResponse> {
-    let payload = payload?;
-    let nullifier = payload.nullifier.as_deref()?.trim();
+    limits: PrivacyPayloadLimits,
+) -> Result<Option<AutoPrivacyPayloadResponse>> {
+    let Some(payload) = payload else {
+        return Ok(None);
+    };
+    let Some(nullifier) = payload.nullifier.as_deref().map(str::trim) else {
+        return Ok(None);
+    };
     if nullifier.is_empty() {
-        return None;
+        return Ok(None);
     }
     let commitment = payload
         .commitment
@@ -658,19 +654,25 @@ fn payload_from_request(
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .unwrap_or("0x0");
-    let proof = normalize_hex_items(payload.proof.as_ref()?);
-    let public_inputs = normalize_hex_items(payload.public_inputs.as_ref()?);
+    let (Some(raw_proof), Some(raw_public_inputs)) =
+        (payload.proof.as_ref(), payload.public_inputs.as_ref())
+    else {
+        return Ok(None);
+    };
+    limits.ensure(raw_proof.len(), raw_public_inputs.len())?;
+    let proof = normalize_hex_items(raw_proof, "proof")?;
+    let public_inputs = normalize_hex_items(raw_public_inputs, "public_inputs")?;
     if proof.is_empty() || public_inputs.is_empty() {
-        return None;
+        return Ok(None);
     }
     if proof.len() == 1
         && public_inputs.len() == 1
         && proof[0].eq_ignore_ascii_case("0x1")
         && public_inputs[0].eq_ignore_ascii_case("0x1")
     {
-        return None;
+        return Ok(None);
     }
-    Some(AutoPrivacyPayloadResponse {
+    Ok(Some(AutoPrivacyPayloadResponse {
         verifier: payload
             .verifier
             .as_deref()
@@ -708,7 +710,7 @@ fn payload_from_request(
         spendable_at_unix: payload.spendable_at_unix,
         proof,
         public_inputs,
-    })
+    }))
 }
 
 struct LimitActionCallInput<'a> {
@@ -731,14 +733,14 @@ async fn compute_limit_intent_hash_on_executor(
     input: &LimitActionCallInput<'_>,
 ) -> Result<String> {
     let reader = OnchainReader::from_config(&state.config)?;
-    let selector_name = match hide_executor_kind() {
+    let selector_name = match hide_executor_kind()? {
         HideExecutorKind::PrivateActionExecutorV1 => "preview_limit_intent_hash",
         HideExecutorKind::ShieldedPoolV2 => "preview_limit_action_hash",
         HideExecutorKind::ShieldedPoolV3 => "preview_limit_action_hash",
     };
     let selector = get_selector_from_name(selector_name)
         .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
-    let kind = hide_executor_kind();
+    let kind = hide_executor_kind()?;
     let mut calldata: Vec<Felt> = Vec::with_capacity(12 + input.action_calldata.len());
     if kind == HideExecutorKind::ShieldedPoolV2 || kind == HideExecutorKind::ShieldedPoolV3 {
         calldata.push(input.action_target);
@@ -778,7 +780,7 @@ fn build_submit_private_intent_call(
     executor: Felt,
     payload: &AutoPrivacyPayloadResponse,
 ) -> Result<Call> {
-    let kind = hide_executor_kind();
+    let kind = hide_executor_kind()?;
     let selector_name = match kind {
         HideExecutorKind::PrivateActionExecutorV1 => "submit_private_intent",
         HideExecutorKind::ShieldedPoolV2 => "submit_private_action",
@@ -831,7 +833,7 @@ fn build_execute_private_limit_call(
     payload: &AutoPrivacyPayloadResponse,
     input: &LimitActionCallInput<'_>,
 ) -> Result<Call> {
-    let kind = hide_executor_kind();
+    let kind = hide_executor_kind()?;
     let selector_name = match kind {
         HideExecutorKind::PrivateActionExecutorV1 => "execute_private_limit_order",
         HideExecutorKind::ShieldedPoolV2 => "execute_private_limit_order",
@@ -903,71 +905,6 @@ fn build_shielded_deposit_fixed_for_call(
     })
 }
 
-// Internal helper that supports `shielded_note_registered` operations in the limit-order flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn shielded_note_registered(
-    state: &AppState,
-    executor: Felt,
-    note_commitment: Felt,
-) -> Result<bool> {
-    let reader = OnchainReader::from_config(&state.config)?;
-    let selector = get_selector_from_name("is_note_registered")
-        .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
-    let out = reader
-        .call(FunctionCall {
-            contract_address: executor,
-            entry_point_selector: selector,
-            calldata: vec![note_commitment],
-        })
-        .await?;
-    let flag = out.first().copied().unwrap_or(Felt::ZERO);
-    Ok(flag != Felt::ZERO)
-}
-
-async fn shielded_note_deposit_timestamp(
-    state: &AppState,
-    executor: Felt,
-    note_commitment: Felt,
-) -> Result<u64> {
-    let reader = OnchainReader::from_config(&state.config)?;
-    let selector = get_selector_from_name("get_note_deposit_timestamp")
-        .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
-    let out = reader
-        .call(FunctionCall {
-            contract_address: executor,
-            entry_point_selector: selector,
-            calldata: vec![note_commitment],
-        })
-        .await?;
-    let raw = out.first().copied().unwrap_or(Felt::ZERO);
-    let value = felt_to_u128(&raw).map_err(|_| {
-        crate::error::AppError::BadRequest(
-            "Invalid note timestamp returned by shielded pool".to_string(),
-        )
-    })?;
-    Ok(value as u64)
-}
-
-async fn shielded_current_root(state: &AppState, executor: Felt) -> Result<Felt> {
-    let reader = OnchainReader::from_config(&state.config)?;
-    let selector = get_selector_from_name("get_root")
-        .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
-    let out = reader
-        .call(FunctionCall {
-            contract_address: executor,
-            entry_point_selector: selector,
-            calldata: vec![],
-        })
-        .await?;
-    let root = out.first().copied().unwrap_or(Felt::ZERO);
-    if root == Felt::ZERO {
-        return Err(crate::error::AppError::BadRequest(
-            "ShieldedPoolV3 root belum diinisialisasi (get_root=0).".to_string(),
-        ));
-    }
-    Ok(root)
-}
-
 // Internal helper that supports `shielded_fixed_amount` operations in the limit-order flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 async fn shielded_fixed_amount(
@@ -1140,6 +1077,19 @@ pub async fn create_order(
     );
 
     ensure_supported_limit_order_pair(&req.from_token, &req.to_token)?;
+    let (order_type, trigger_price) =
+        parse_order_trigger(req.order_type.as_deref(), req.trigger_price.as_deref())?;
+    if let Some(trigger_price) = trigger_price {
+        let to_token_symbol = req.to_token.trim().to_ascii_uppercase();
+        let to_token_price = latest_limit_order_price(&state, &to_token_symbol).await?;
+        if to_token_price <= 0.0 {
+            return Err(crate::error::AppError::BadRequest(format!(
+                "No market price available for {}",
+                to_token_symbol
+            )));
+        }
+        ensure_trigger_side(order_type, trigger_price, from_token_price / to_token_price)?;
+    }
     let strict_privacy_mode = should_hide && hide_balance_strict_privacy_mode_enabled();
     let hide_pool_version = if should_hide {
         Some(resolve_hide_pool_version(req.privacy.as_ref()))
@@ -1147,7 +1097,7 @@ pub async fn create_order(
         None
     };
     if should_hide {
-        match (hide_executor_kind(), hide_pool_version) {
+        match (hide_executor_kind()?, hide_pool_version) {
             (HideExecutorKind::ShieldedPoolV3, Some(HidePoolVersion::V2)) => {
                 return Err(crate::error::AppError::BadRequest(
                     "Hide Balance config mismatch: executor is V3 but payload/version resolved to V2."
@@ -1157,7 +1107,7 @@ pub async fn create_order(
             (HideExecutorKind::ShieldedPoolV2, Some(HidePoolVersion::V3))
             | (HideExecutorKind::PrivateActionExecutorV1, Some(HidePoolVersion::V3)) => {
                 return Err(crate::error::AppError::BadRequest(
-                    "Hide Balance V3 requires HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3."
+                    "Hide Balance V3 requires a ShieldedPoolV3 executor (auto-detected, or HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3)."
                         .to_string(),
                 ));
             }
@@ -1185,7 +1135,8 @@ pub async fn create_order(
     let tx_hash = if use_relayer_pool_hide {
         let executor = resolve_private_action_executor_felt_for_limit_hide(&state).await?;
         let action_target = resolve_limit_order_target_felt(&state)?;
-        let verifier_kind = parse_privacy_verifier_kind(
+        let verifier_kind = select_privacy_verifier(
+            &state.config,
             req.privacy
                 .as_ref()
                 .and_then(|payload| payload.verifier.as_deref()),
@@ -1233,7 +1184,11 @@ pub async fn create_order(
             );
             None
         } else {
-            payload_from_request(req.privacy.as_ref(), verifier_kind.as_str())
+            payload_from_request(
+                req.privacy.as_ref(),
+                verifier_kind.as_str(),
+                PrivacyPayloadLimits::from_config(&state.config),
+            )?
         };
         let payload_from_auto = request_payload.is_none();
         let mut payload = if let Some(request_payload) = request_payload {
@@ -1298,25 +1253,20 @@ pub async fn create_order(
                 })?;
             let note_commitment_felt = parse_felt(note_commitment_raw.trim())?;
             let deposit_ts =
-                shielded_note_deposit_timestamp(&state, executor, note_commitment_felt).await?;
+                shielded_note_deposit_timestamp(&state.config, executor, note_commitment_felt)
+                    .await?;
             if deposit_ts == 0 {
-                return Err(crate::error::AppError::BadRequest(
-                    "Hide Balance V3 note belum terdaftar. Deposit note dulu.".to_string(),
-                ));
+                return Err(crate::error::ErrorCode::HideNoteNotRegistered.into());
             }
-            payload.spendable_at_unix =
-                Some(deposit_ts.saturating_add(hide_balance_min_note_age_secs()));
-        } else if hide_executor_kind() == HideExecutorKind::ShieldedPoolV2 {
+            payload.spendable_at_unix = Some(ensure_note_mixing_window_elapsed(deposit_ts)?);
+        } else if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV2 {
             let commitment_felt = parse_felt(payload.commitment.trim())?;
             let user_felt = parse_felt(&user_address)?;
             let note_registered =
-                shielded_note_registered(&state, executor, commitment_felt).await?;
+                shielded_note_registered(&state.config, executor, commitment_felt).await?;
             if !note_registered {
                 if hide_balance_v2_redeem_only_enabled() {
-                    return Err(crate::error::AppError::BadRequest(
-                        "Hide Balance V2 is redeem-only. Deposit note baru ke V2 diblok; gunakan V3 untuk note baru."
-                            .to_string(),
-                    ));
+                    return Err(crate::error::ErrorCode::HideV2RedeemOnly.into());
                 }
                 if strict_privacy_mode {
                     return Err(crate::error::AppError::BadRequest(
@@ -1324,9 +1274,13 @@ pub async fn create_order(
                             .to_string(),
                     ));
                 }
-                let (fixed_low, fixed_high) =
-                    shielded_fixed_amount(&state, executor, from_token).await?;
-                if fixed_low != amount_low || fixed_high != amount_high {
+                let fixed_amount = shielded_fixed_amount(&state, executor, from_token).await?;
+                if hide_deposit_requires_asset_rule(
+                    &state.config,
+                    &req.from_token,
+                    (amount_low, amount_high),
+                    fixed_amount,
+                )? {
                     relayer_calls.push(build_shielded_set_asset_rule_call(
                         executor,
                         from_token,
@@ -1380,7 +1334,7 @@ pub async fn create_order(
         let intent_hash =
             compute_limit_intent_hash_on_executor(&state, executor, &limit_input).await?;
         if hide_pool_version == Some(HidePoolVersion::V3) && payload_from_auto {
-            let root = shielded_current_root(&state, executor).await?;
+            let root = shielded_current_root(&state.config, executor).await?;
             let tx_context = AutoPrivacyTxContext {
                 flow: Some("limit_order".to_string()),
                 from_token: Some(req.from_token.clone()),
@@ -1452,7 +1406,14 @@ pub async fn create_order(
         let execute_call = build_execute_private_limit_call(executor, &payload, &limit_input)?;
         relayer_calls.push(submit_call);
         relayer_calls.push(execute_call);
-        let submitted = relayer.submit_calls(relayer_calls).await?;
+        let submitted = submit_relayer_calls(
+            &state,
+            &relayer,
+            &user_address,
+            "hide_limit_create",
+            relayer_calls,
+        )
+        .await?;
         submitted.tx_hash
     } else {
         let tx_hash = normalized_onchain_tx_hash.ok_or_else(|| {
@@ -1486,8 +1447,10 @@ pub async fn create_order(
         price: rust_decimal::Decimal::from_f64_retain(price).unwrap(),
         expiry,
         recipient: req.recipient,
-        status: 0,
+        status: OrderStatus::Active,
         created_at: now,
+        order_type,
+        trigger_price: trigger_price.and_then(rust_decimal::Decimal::from_f64_retain),
     };
 
     state.db.create_limit_order(&order).await?;
@@ -1550,12 +1513,10 @@ pub async fn list_orders(
     let offset = (page - 1) * limit;
 
     // Logika penggunaan status agar tidak dead code
-    let status_int = query.status.as_ref().map(|s| match s.as_str() {
-        "active" => 0,
-        "filled" => 2,
-        "cancelled" => 3,
-        _ => 0,
-    });
+    let status_int = query
+        .status
+        .as_ref()
+        .map(|s| i16::from(s.parse::<OrderStatus>().unwrap_or(OrderStatus::Active)));
 
     // Menggunakan query dinamis sederhana
     let orders = if let Some(s) = status_int {
@@ -1595,12 +1556,7 @@ pub async fn list_orders(
 
     let total_res = total_query.fetch_one(state.db.pool()).await?;
 
-    let response = PaginatedResponse {
-        items: orders,
-        page,
-        limit,
-        total: total_res.count,
-    };
+    let response = PaginatedResponse::new(orders, page, limit, total_res.count);
 
     Ok(Json(ApiResponse::success(response)))
 }
@@ -1626,7 +1582,7 @@ pub async fn cancel_order(
         None
     };
     if should_hide {
-        match (hide_executor_kind(), hide_pool_version) {
+        match (hide_executor_kind()?, hide_pool_version) {
             (HideExecutorKind::ShieldedPoolV3, Some(HidePoolVersion::V2)) => {
                 return Err(crate::error::AppError::BadRequest(
                     "Hide Balance config mismatch: executor is V3 but payload/version resolved to V2."
@@ -1636,7 +1592,7 @@ pub async fn cancel_order(
             (HideExecutorKind::ShieldedPoolV2, Some(HidePoolVersion::V3))
             | (HideExecutorKind::PrivateActionExecutorV1, Some(HidePoolVersion::V3)) => {
                 return Err(crate::error::AppError::BadRequest(
-                    "Hide Balance V3 requires HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3."
+                    "Hide Balance V3 requires a ShieldedPoolV3 executor (auto-detected, or HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3)."
                         .to_string(),
                 ));
             }
@@ -1655,12 +1611,12 @@ pub async fn cancel_order(
         ));
     }
 
-    if order.status == 2 {
+    if order.status == OrderStatus::Filled {
         return Err(crate::error::AppError::BadRequest(
             "Order already filled".to_string(),
         ));
     }
-    if order.status == 4 {
+    if order.status == OrderStatus::Expired {
         return Err(crate::error::AppError::BadRequest(
             "Order already expired. Create a new order if you still want to trade.".to_string(),
         ));
@@ -1673,7 +1629,8 @@ pub async fn cancel_order(
     let tx_hash = if use_relayer_pool_hide {
         let executor = resolve_private_action_executor_felt_for_limit_hide(&state).await?;
         let action_target = resolve_limit_order_target_felt(&state)?;
-        let verifier_kind = parse_privacy_verifier_kind(
+        let verifier_kind = select_privacy_verifier(
+            &state.config,
             req.privacy
                 .as_ref()
                 .and_then(|payload| payload.verifier.as_deref()),
@@ -1685,7 +1642,7 @@ pub async fn cancel_order(
         )
         .await?;
         let action_calldata = vec![parse_felt(&order_id)?];
-        let approval_token = if hide_executor_kind() == HideExecutorKind::ShieldedPoolV2 {
+        let approval_token = if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV2 {
             token_address_for(&order.from_token)
                 .ok_or(crate::error::AppError::InvalidToken)
                 .and_then(parse_felt)?
@@ -1709,7 +1666,11 @@ pub async fn cancel_order(
             );
             None
         } else {
-            payload_from_request(req.privacy.as_ref(), verifier_kind.as_str())
+            payload_from_request(
+                req.privacy.as_ref(),
+                verifier_kind.as_str(),
+                PrivacyPayloadLimits::from_config(&state.config),
+            )?
         };
         let payload_from_auto = request_payload.is_none();
         let mut payload = if let Some(request_payload) = request_payload {
@@ -1751,7 +1712,7 @@ pub async fn cancel_order(
         let intent_hash =
             compute_limit_intent_hash_on_executor(&state, executor, &limit_input).await?;
         if hide_pool_version == Some(HidePoolVersion::V3) && payload_from_auto {
-            let root = shielded_current_root(&state, executor).await?;
+            let root = shielded_current_root(&state.config, executor).await?;
             let tx_context = AutoPrivacyTxContext {
                 flow: Some("limit_order_cancel".to_string()),
                 from_network: Some("starknet".to_string()),
@@ -1834,25 +1795,20 @@ pub async fn cancel_order(
                 })?;
             let note_commitment_felt = parse_felt(note_commitment_raw.trim())?;
             let deposit_ts =
-                shielded_note_deposit_timestamp(&state, executor, note_commitment_felt).await?;
+                shielded_note_deposit_timestamp(&state.config, executor, note_commitment_felt)
+                    .await?;
             if deposit_ts == 0 {
-                return Err(crate::error::AppError::BadRequest(
-                    "Hide Balance V3 note belum terdaftar. Deposit note dulu.".to_string(),
-                ));
+                return Err(crate::error::ErrorCode::HideNoteNotRegistered.into());
             }
-            payload.spendable_at_unix =
-                Some(deposit_ts.saturating_add(hide_balance_min_note_age_secs()));
-        } else if hide_executor_kind() == HideExecutorKind::ShieldedPoolV2 {
+            payload.spendable_at_unix = Some(ensure_note_mixing_window_elapsed(deposit_ts)?);
+        } else if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV2 {
             let commitment_felt = parse_felt(payload.commitment.trim())?;
             let user_felt = parse_felt(&user_address)?;
             let note_registered =
-                shielded_note_registered(&state, executor, commitment_felt).await?;
+                shielded_note_registered(&state.config, executor, commitment_felt).await?;
             if !note_registered {
                 if hide_balance_v2_redeem_only_enabled() {
-                    return Err(crate::error::AppError::BadRequest(
-                        "Hide Balance V2 is redeem-only. Deposit note baru ke V2 diblok; gunakan V3 untuk note baru."
-                            .to_string(),
-                    ));
+                    return Err(crate::error::ErrorCode::HideV2RedeemOnly.into());
                 }
                 if strict_privacy_mode {
                     return Err(crate::error::AppError::BadRequest(
@@ -1922,7 +1878,14 @@ pub async fn cancel_order(
         let execute_call = build_execute_private_limit_call(executor, &payload, &limit_input)?;
         relayer_calls.push(submit_call);
         relayer_calls.push(execute_call);
-        let submitted = relayer.submit_calls(relayer_calls).await?;
+        let submitted = submit_relayer_calls(
+            &state,
+            &relayer,
+            &user_address,
+            "hide_limit_cancel",
+            relayer_calls,
+        )
+        .await?;
         submitted.tx_hash
     } else {
         let tx_hash = normalized_onchain_tx_hash.ok_or_else(|| {
@@ -1946,7 +1909,10 @@ pub async fn cancel_order(
         tx_hash
     };
 
-    state.db.update_order_status(&order_id, 3).await?;
+    state
+        .db
+        .update_order_status(&order_id, OrderStatus::Cancelled)
+        .await?;
     tracing::info!(
         "Limit order cancelled: user={}, order_id={}, onchain_tx_hash={}",
         user_address,
@@ -1963,6 +1929,32 @@ pub async fn cancel_order(
 mod tests {
     use super::*;
 
+    #[test]
+    // Internal helper that supports `parse_order_trigger_requires_trigger_for_conditional_types` operations.
+    fn parse_order_trigger_requires_trigger_for_conditional_types() {
+        assert_eq!(
+            parse_order_trigger(None, None).unwrap(),
+            (OrderType::Limit, None)
+        );
+        assert_eq!(
+            parse_order_trigger(Some("stop_loss"), Some("90")).unwrap(),
+            (OrderType::StopLoss, Some(90.0))
+        );
+        assert!(parse_order_trigger(Some("take_profit"), None).is_err());
+        assert!(parse_order_trigger(Some("limit"), Some("90")).is_err());
+        assert!(parse_order_trigger(Some("stop_loss"), Some("-1")).is_err());
+        assert!(parse_order_trigger(Some("trailing"), Some("90")).is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `ensure_trigger_side_rejects_already_crossed_triggers` operations.
+    fn ensure_trigger_side_rejects_already_crossed_triggers() {
+        assert!(ensure_trigger_side(OrderType::StopLoss, 90.0, 100.0).is_ok());
+        assert!(ensure_trigger_side(OrderType::StopLoss, 110.0, 100.0).is_err());
+        assert!(ensure_trigger_side(OrderType::TakeProfit, 110.0, 100.0).is_ok());
+        assert!(ensure_trigger_side(OrderType::TakeProfit, 90.0, 100.0).is_err());
+    }
+
     #[test]
     fn resolve_hide_pool_version_prefers_note_version_field() {
         let payload_v3 = ModelPrivacyVerificationPayload {
@@ -0,0 +1,178 @@
+use super::{auth, AppState};
+use crate::error::{AppError, Result};
+use axum::http::{header::AUTHORIZATION, HeaderMap};
+use chrono::{DateTime, Utc};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use starknet_core::types::Felt;
+use std::sync::{OnceLock, RwLock};
+
+const MAINTENANCE_REDIS_KEY: &str = "maintenance:mode";
+const DEFAULT_MAINTENANCE_MESSAGE: &str =
+    "The service is in maintenance mode. Write operations are temporarily disabled.";
+
+// Last state read from Redis, served while Redis is unreachable.
+static LAST_KNOWN_MAINTENANCE: OnceLock<RwLock<Option<MaintenanceState>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MaintenanceState {
+    pub enabled: bool,
+    pub message: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MaintenanceState {
+    /// Message returned to clients while maintenance mode is active.
+    pub fn client_message(&self) -> String {
+        self.message
+            .as_deref()
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+            .unwrap_or(DEFAULT_MAINTENANCE_MESSAGE)
+            .to_string()
+    }
+}
+
+// Internal helper that supports `last_known_maintenance` operations.
+fn last_known_maintenance() -> &'static RwLock<Option<MaintenanceState>> {
+    LAST_KNOWN_MAINTENANCE.get_or_init(|| RwLock::new(None))
+}
+
+// Internal helper that supports `remember_maintenance` operations.
+fn remember_maintenance(maintenance: Option<MaintenanceState>) {
+    if let Ok(mut cached) = last_known_maintenance().write() {
+        *cached = maintenance;
+    }
+}
+
+// Internal helper that supports `cached_maintenance` operations.
+fn cached_maintenance() -> Option<MaintenanceState> {
+    last_known_maintenance()
+        .read()
+        .ok()
+        .and_then(|cached| cached.clone())
+}
+
+/// Reads the shared maintenance state, falling back to the last known value without Redis.
+pub async fn current_maintenance(state: &AppState) -> Option<MaintenanceState> {
+    if !state.redis_available() {
+        return cached_maintenance();
+    }
+    let mut conn = state.redis.clone();
+    match conn.get::<_, Option<String>>(MAINTENANCE_REDIS_KEY).await {
+        Ok(raw) => {
+            let maintenance =
+                raw.and_then(|raw| serde_json::from_str::<MaintenanceState>(&raw).ok());
+            remember_maintenance(maintenance.clone());
+            maintenance
+        }
+        Err(err) => {
+            tracing::warn!(
+                "Maintenance state Redis read failed, using last known state: {}",
+                err
+            );
+            state.set_redis_available(false);
+            cached_maintenance()
+        }
+    }
+}
+
+/// Persists the maintenance state in Redis so every replica sees it.
+pub async fn set_maintenance(
+    state: &AppState,
+    enabled: bool,
+    message: Option<String>,
+) -> Result<MaintenanceState> {
+    let maintenance = MaintenanceState {
+        enabled,
+        message: message
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty()),
+        updated_at: Utc::now(),
+    };
+    let raw = serde_json::to_string(&maintenance).map_err(|err| {
+        AppError::Internal(format!("Failed to encode maintenance state: {}", err))
+    })?;
+    let mut conn = state.redis.clone();
+    conn.set::<_, _, ()>(MAINTENANCE_REDIS_KEY, raw).await?;
+    remember_maintenance(Some(maintenance.clone()));
+    Ok(maintenance)
+}
+
+/// Rejects write requests with 503 while maintenance mode is active.
+///
+/// # Notes
+/// * Callers authenticated as an address in `MAINTENANCE_BYPASS_ADDRESSES` are let through.
+pub async fn ensure_writable(headers: &HeaderMap, state: &AppState) -> Result<()> {
+    let Some(maintenance) = current_maintenance(state)
+        .await
+        .filter(|maintenance| maintenance.enabled)
+    else {
+        return Ok(());
+    };
+
+    if let Some(user_address) = bearer_user(headers, state).await {
+        if is_bypass_address(&state.config.maintenance_bypass_addresses, &user_address) {
+            tracing::info!("Maintenance mode bypassed by {}", user_address);
+            return Ok(());
+        }
+    }
+    Err(AppError::MaintenanceMode(maintenance.client_message()))
+}
+
+// Internal helper that fetches data for `bearer_user`.
+async fn bearer_user(headers: &HeaderMap, state: &AppState) -> Option<String> {
+    let token = headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")?;
+    auth::extract_user_from_token(token, &state.config.jwt_secret)
+        .await
+        .ok()
+}
+
+/// Returns whether `address` appears in a comma-separated address allowlist.
+///
+/// # Notes
+/// * Hex entries are compared as felts, so zero-padding and case do not matter.
+pub(crate) fn is_bypass_address(allowlist: &str, address: &str) -> bool {
+    let address = address.trim();
+    let address_felt = Felt::from_hex(address).ok();
+    allowlist
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| match (address_felt, Felt::from_hex(entry).ok()) {
+            (Some(address_felt), Some(entry_felt)) => address_felt == entry_felt,
+            _ => entry.eq_ignore_ascii_case(address),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that checks conditions for `is_bypass_address_normalizes_hex`.
+    fn is_bypass_address_normalizes_hex() {
+        let allowlist = "0x00ABC, bc1qtester";
+        assert!(is_bypass_address(allowlist, "0xabc"));
+        assert!(is_bypass_address(allowlist, "BC1QTESTER"));
+        assert!(!is_bypass_address(allowlist, "0xabd"));
+        assert!(!is_bypass_address("", "0xabc"));
+    }
+
+    #[test]
+    // Internal helper that supports `client_message_falls_back_to_default` operations.
+    fn client_message_falls_back_to_default() {
+        let mut maintenance = MaintenanceState {
+            enabled: true,
+            message: Some("  ".to_string()),
+            updated_at: Utc::now(),
+        };
+        assert_eq!(maintenance.client_message(), DEFAULT_MAINTENANCE_MESSAGE);
+        maintenance.message = Some("Upgrading database".to_string());
+        assert_eq!(maintenance.client_message(), "Upgrading database");
+    }
+}
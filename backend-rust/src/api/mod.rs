@@ -3,7 +3,9 @@
 // Re-export your API endpoint modules here (sesuaikan kalau ada/tdk ada)
 pub mod admin;
 pub mod ai;
+pub mod alerts;
 pub mod analytics;
+pub mod anonymity_set;
 pub mod anonymous_credentials;
 pub mod auth;
 pub mod battleship;
@@ -11,11 +13,14 @@ pub mod bridge;
 pub mod charts;
 pub mod dark_pool;
 pub mod deposit;
+pub mod extract;
 pub mod faucet;
+pub mod features;
 pub mod garden;
 pub mod health;
 pub mod leaderboard;
 pub mod limit_order;
+pub mod maintenance;
 pub mod market;
 pub mod nft;
 pub mod notifications;
@@ -26,18 +31,28 @@ pub mod private_btc_swap;
 pub mod private_payments;
 pub mod profile;
 pub mod referral;
+pub mod relayer_access;
 pub mod rewards;
 pub mod social;
 pub mod stake;
 pub mod swap;
+pub mod tokenomics;
 pub mod transactions;
+pub mod treasury;
 pub mod wallet;
 pub mod webhooks;
 
 use crate::error::{AppError, Result};
 use axum::http::{header::AUTHORIZATION, HeaderMap};
 use redis::aio::ConnectionManager;
-use std::{collections::HashMap, sync::OnceLock, time::Instant};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+    time::Instant,
+};
 use tokio::time::{timeout, Duration};
 
 // AppState definition
@@ -94,6 +109,30 @@ pub struct AppState {
     pub db: Database,
     pub redis: ConnectionManager,
     pub config: Config,
+    /// Cleared when a Redis call fails; the keepalive and `/health` probes set it again.
+    pub redis_healthy: Arc<AtomicBool>,
+}
+
+impl AppState {
+    /// Whether Redis-backed paths should be tried before their in-process fallbacks.
+    pub fn redis_available(&self) -> bool {
+        self.redis_healthy.load(Ordering::Relaxed)
+    }
+
+    /// Records a Redis health probe result, logging state transitions once.
+    pub fn set_redis_available(&self, available: bool) {
+        mark_redis_health(&self.redis_healthy, available);
+    }
+}
+
+/// Updates a shared Redis health flag and logs when it flips.
+pub fn mark_redis_health(flag: &AtomicBool, available: bool) {
+    let previous = flag.swap(available, Ordering::Relaxed);
+    if previous && !available {
+        tracing::warn!("Redis unavailable; falling back to in-process rate limiting");
+    } else if !previous && available {
+        tracing::info!("Redis reachable again; resuming Redis-backed rate limiting");
+    }
 }
 
 /// Handles `require_user` logic.
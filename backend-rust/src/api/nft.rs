@@ -1,12 +1,12 @@
-use super::{require_starknet_user, AppState};
+use super::{extract::Pagination, require_starknet_user, AppState};
 use crate::{
     constants::{
-        EPOCH_DURATION_SECONDS, NFT_TIER_1_DISCOUNT, NFT_TIER_2_DISCOUNT, NFT_TIER_3_DISCOUNT,
-        NFT_TIER_4_DISCOUNT, NFT_TIER_5_DISCOUNT, NFT_TIER_6_DISCOUNT,
+        NFT_TIER_1_DISCOUNT, NFT_TIER_2_DISCOUNT, NFT_TIER_3_DISCOUNT, NFT_TIER_4_DISCOUNT,
+        NFT_TIER_5_DISCOUNT, NFT_TIER_6_DISCOUNT,
     },
     db::NftDiscountStateUpsert,
     error::Result,
-    models::ApiResponse,
+    models::{ApiResponse, PaginatedResponse},
     services::onchain::{felt_to_u128, parse_felt, u256_from_felts, OnchainReader},
 };
 use axum::{extract::State, http::HeaderMap, Json};
@@ -177,17 +177,6 @@ fn tier_for_discount(discount: f64) -> i32 {
     }
 }
 
-// Internal helper that supports `current_nft_period_epoch` operations.
-fn current_nft_period_epoch() -> i64 {
-    let now = chrono::Utc::now().timestamp();
-    let period = (EPOCH_DURATION_SECONDS as i64).max(1);
-    if now <= 0 {
-        0
-    } else {
-        now / period
-    }
-}
-
 // Internal helper that parses or transforms values for `u128_to_i64_saturating`.
 fn u128_to_i64_saturating(value: u128) -> i64 {
     if value > i64::MAX as u128 {
@@ -255,7 +244,7 @@ async fn sync_discount_state_from_owned_nfts(
         .upsert_nft_discount_state_from_chain(NftDiscountStateUpsert {
             contract_address: contract,
             user_address,
-            period_epoch: current_nft_period_epoch(),
+            period_epoch: state.config.current_nft_period_epoch(),
             tier,
             discount_percent,
             is_active,
@@ -497,7 +486,7 @@ pub async fn mint_nft(
             "Invalid tier".to_string(),
         ));
     }
-    let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+    let current_epoch = state.config.current_rewards_epoch();
     let _ = discount_contract_or_error(&state)?;
     let onchain_tx_hash = normalize_onchain_tx_hash(req.onchain_tx_hash.as_deref())?;
     let tx_hash = onchain_tx_hash.ok_or_else(|| {
@@ -510,7 +499,7 @@ pub async fn mint_nft(
     if cost_points > 0 {
         if let Err(err) = state
             .db
-            .consume_points(
+            .consume_points_across_epochs(
                 &user_address,
                 current_epoch,
                 rust_decimal::Decimal::from_i64(cost_points).unwrap(),
@@ -545,21 +534,32 @@ pub async fn mint_nft(
     Ok(Json(ApiResponse::success(nft)))
 }
 
-/// GET /api/v1/nft/owned
+/// GET /api/v1/nft/owned?page=&limit=
 pub async fn get_owned_nfts(
     State(state): State<AppState>,
     headers: HeaderMap,
-) -> Result<Json<ApiResponse<Vec<Nft>>>> {
+    pagination: Pagination,
+) -> Result<Json<ApiResponse<PaginatedResponse<Nft>>>> {
     let user_address = require_starknet_user(&headers, &state).await?;
-    let Some(contract) = discount_contract(&state) else {
-        return Ok(Json(ApiResponse::success(Vec::new())));
+    let nfts = load_owned_nfts(&state, &user_address).await?;
+    Ok(Json(ApiResponse::success(PaginatedResponse::from_vec(
+        nfts,
+        pagination.page,
+        pagination.limit,
+    ))))
+}
+
+// Internal helper that loads owned NFTs through the shared cache and stale fallbacks.
+async fn load_owned_nfts(state: &AppState, user_address: &str) -> Result<Vec<Nft>> {
+    let Some(contract) = discount_contract(state) else {
+        return Ok(Vec::new());
     };
-    let cache_key = owned_nft_cache_key(contract, &user_address);
+    let cache_key = owned_nft_cache_key(contract, user_address);
     if let Some(cached) =
         get_cached_owned_nfts(&cache_key, Duration::from_secs(OWNED_NFT_CACHE_TTL_SECS)).await
     {
-        sync_discount_state_from_owned_nfts(&state, contract, &user_address, &cached).await;
-        return Ok(Json(ApiResponse::success(cached)));
+        sync_discount_state_from_owned_nfts(state, contract, user_address, &cached).await;
+        return Ok(cached);
     }
 
     let fetch_lock = owned_nft_fetch_lock_for(&cache_key).await;
@@ -567,11 +567,11 @@ pub async fn get_owned_nfts(
     if let Some(cached) =
         get_cached_owned_nfts(&cache_key, Duration::from_secs(OWNED_NFT_CACHE_TTL_SECS)).await
     {
-        sync_discount_state_from_owned_nfts(&state, contract, &user_address, &cached).await;
-        return Ok(Json(ApiResponse::success(cached)));
+        sync_discount_state_from_owned_nfts(state, contract, user_address, &cached).await;
+        return Ok(cached);
     }
 
-    match get_owned_nfts_uncached(&state, contract, &user_address).await {
+    match get_owned_nfts_uncached(state, contract, user_address).await {
         Ok(nfts) => {
             if nfts.is_empty() {
                 if let Some(stale) = get_cached_owned_nfts(
@@ -585,14 +585,14 @@ pub async fn get_owned_nfts(
                         user_address,
                         contract
                     );
-                    sync_discount_state_from_owned_nfts(&state, contract, &user_address, &stale)
+                    sync_discount_state_from_owned_nfts(state, contract, user_address, &stale)
                         .await;
-                    return Ok(Json(ApiResponse::success(stale)));
+                    return Ok(stale);
                 }
             }
             cache_owned_nfts(cache_key, nfts.clone()).await;
-            sync_discount_state_from_owned_nfts(&state, contract, &user_address, &nfts).await;
-            Ok(Json(ApiResponse::success(nfts)))
+            sync_discount_state_from_owned_nfts(state, contract, user_address, &nfts).await;
+            Ok(nfts)
         }
         Err(err) => {
             if let Some(stale) =
@@ -604,8 +604,8 @@ pub async fn get_owned_nfts(
                     user_address,
                     contract
                 );
-                sync_discount_state_from_owned_nfts(&state, contract, &user_address, &stale).await;
-                return Ok(Json(ApiResponse::success(stale)));
+                sync_discount_state_from_owned_nfts(state, contract, user_address, &stale).await;
+                return Ok(stale);
             }
             Err(err)
         }
@@ -2,25 +2,26 @@ use axum::{extract::State, http::HeaderMap, Json};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    error::Result,
+    error::{AppError, Result},
     models::{ApiResponse, Notification, NotificationPreferences, PaginatedResponse},
-    services::NotificationService,
-    utils::ensure_page_limit,
+    services::{
+        notification_service::{DigestFrequency, NotificationType, TelegramLinkCode},
+        NotificationService,
+    },
 };
 
-use super::{require_user, AppState};
-
-#[derive(Debug, Deserialize)]
-pub struct ListNotificationsQuery {
-    pub page: Option<i32>,
-    pub limit: Option<i32>,
-}
+use super::{extract::Pagination, require_user, AppState};
 
 #[derive(Debug, Deserialize)]
 pub struct MarkReadRequest {
     pub notification_ids: Vec<i64>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct TelegramLinkStatus {
+    pub linked: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct NotificationStats {
     pub unread_count: i64,
@@ -47,14 +48,10 @@ fn should_mark_all(notification_ids: &[i64]) -> bool {
 pub async fn list(
     State(state): State<AppState>,
     headers: HeaderMap,
-    axum::extract::Query(query): axum::extract::Query<ListNotificationsQuery>,
+    Pagination { page, limit }: Pagination,
 ) -> Result<Json<ApiResponse<PaginatedResponse<Notification>>>> {
     let user_address = require_user(&headers, &state).await?;
 
-    let page = query.page.unwrap_or(1);
-    let limit = query.limit.unwrap_or(20);
-    ensure_page_limit(limit, state.config.rate_limit_authenticated)?;
-
     let service = NotificationService::new(state.db.clone(), state.config.clone());
     let notifications = service
         .get_user_notifications(&user_address, page, limit)
@@ -67,12 +64,7 @@ pub async fn list(
             .fetch_one(state.db.pool())
             .await?;
 
-    let response = PaginatedResponse {
-        items: notifications,
-        page,
-        limit,
-        total: total_res.count,
-    };
+    let response = PaginatedResponse::new(notifications, page, limit, total_res.count);
 
     Ok(Json(ApiResponse::success(response)))
 }
@@ -99,6 +91,73 @@ pub async fn mark_read(
     )))
 }
 
+// Internal helper that validates mute types and the quiet-hours window for `update_preferences`.
+fn normalize_preferences(mut prefs: NotificationPreferences) -> Result<NotificationPreferences> {
+    let known: Vec<String> = NotificationType::all()
+        .iter()
+        .map(|notif_type| notif_type.to_string())
+        .collect();
+    let mut muted_types = Vec::new();
+    for raw in &prefs.muted_types {
+        let value = raw.trim().to_ascii_lowercase();
+        if !known.contains(&value) {
+            return Err(AppError::BadRequest(format!(
+                "Unknown notification type: {}",
+                raw
+            )));
+        }
+        if !muted_types.contains(&value) {
+            muted_types.push(value);
+        }
+    }
+    prefs.muted_types = muted_types;
+
+    prefs.email_address = prefs
+        .email_address
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+    prefs.digest_frequency = match prefs
+        .digest_frequency
+        .as_deref()
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("") | Some("off") => None,
+        Some(value) if DigestFrequency::parse(value).is_some() => Some(value.to_string()),
+        Some(value) => {
+            return Err(AppError::BadRequest(format!(
+                "Unknown digest_frequency: {} (expected hourly, daily, or off)",
+                value
+            )))
+        }
+    };
+
+    if let Some(email) = prefs.email_address.as_deref() {
+        let valid = email.len() <= 254
+            && email
+                .split_once('@')
+                .map(|(local, domain)| !local.is_empty() && domain.contains('.'))
+                .unwrap_or(false);
+        if !valid {
+            return Err(AppError::BadRequest("Invalid email_address".to_string()));
+        }
+    }
+
+    match (prefs.quiet_hours_start, prefs.quiet_hours_end) {
+        (None, None) => {}
+        (Some(start), Some(end)) if (0..24).contains(&start) && (0..24).contains(&end) => {}
+        _ => {
+            return Err(AppError::BadRequest(
+                "quiet_hours_start and quiet_hours_end must both be set to UTC hours 0-23"
+                    .to_string(),
+            ))
+        }
+    }
+    Ok(prefs)
+}
+
 /// PUT /api/v1/notifications/preferences
 pub async fn update_preferences(
     State(state): State<AppState>,
@@ -106,28 +165,65 @@ pub async fn update_preferences(
     Json(req): Json<NotificationPreferences>,
 ) -> Result<Json<ApiResponse<NotificationPreferences>>> {
     let user_address = require_user(&headers, &state).await?;
+    let req = normalize_preferences(req)?;
 
     // Perbaikan: Gunakan query biasa (execute) untuk INSERT/UPDATE
     sqlx::query(
-        "INSERT INTO notification_preferences (user_address, email_enabled, push_enabled, telegram_enabled, discord_enabled)
-         VALUES ($1, $2, $3, $4, $5)
+        "INSERT INTO notification_preferences (user_address, email_enabled, push_enabled, telegram_enabled, discord_enabled, muted_all, muted_types, quiet_hours_start, quiet_hours_end, email_address, digest_frequency)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
          ON CONFLICT (user_address) DO UPDATE
          SET email_enabled = $2,
              push_enabled = $3,
              telegram_enabled = $4,
-             discord_enabled = $5"
+             discord_enabled = $5,
+             muted_all = $6,
+             muted_types = $7,
+             quiet_hours_start = $8,
+             quiet_hours_end = $9,
+             email_address = $10,
+             digest_frequency = $11"
     )
     .bind(&user_address)
     .bind(req.email_enabled)
     .bind(req.push_enabled)
     .bind(req.telegram_enabled)
     .bind(req.discord_enabled)
+    .bind(req.muted_all)
+    .bind(&req.muted_types)
+    .bind(req.quiet_hours_start)
+    .bind(req.quiet_hours_end)
+    .bind(&req.email_address)
+    .bind(&req.digest_frequency)
     .execute(state.db.pool())
     .await?;
 
     Ok(Json(ApiResponse::success(req)))
 }
 
+/// POST /api/v1/notifications/telegram/link
+pub async fn create_telegram_link(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<TelegramLinkCode>>> {
+    let user_address = require_user(&headers, &state).await?;
+    let service = NotificationService::new(state.db.clone(), state.config.clone());
+    let link = service.create_telegram_link_code(&user_address).await?;
+    Ok(Json(ApiResponse::success(link)))
+}
+
+/// POST /api/v1/notifications/telegram/verify
+pub async fn verify_telegram_link(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<TelegramLinkStatus>>> {
+    let user_address = require_user(&headers, &state).await?;
+    let service = NotificationService::new(state.db.clone(), state.config.clone());
+    service.verify_telegram_link(&user_address).await?;
+    Ok(Json(ApiResponse::success(TelegramLinkStatus {
+        linked: true,
+    })))
+}
+
 /// GET /api/v1/notifications/stats
 pub async fn get_stats(
     State(state): State<AppState>,
@@ -169,4 +265,29 @@ mod tests {
         // Memastikan daftar berisi ID tidak menandai semua
         assert!(!should_mark_all(&[1, 2, 3]));
     }
+
+    #[test]
+    // Internal helper that supports `normalize_preferences_validates_types_and_quiet_hours` operations.
+    fn normalize_preferences_validates_types_and_quiet_hours() {
+        let prefs = NotificationPreferences {
+            muted_types: vec![" Swap.Completed ".to_string(), "swap.completed".to_string()],
+            quiet_hours_start: Some(22),
+            quiet_hours_end: Some(6),
+            ..Default::default()
+        };
+        let normalized = normalize_preferences(prefs).expect("valid preferences");
+        assert_eq!(normalized.muted_types, vec!["swap.completed".to_string()]);
+
+        let unknown = NotificationPreferences {
+            muted_types: vec!["swap.unknown".to_string()],
+            ..Default::default()
+        };
+        assert!(normalize_preferences(unknown).is_err());
+
+        let half_window = NotificationPreferences {
+            quiet_hours_start: Some(22),
+            ..Default::default()
+        };
+        assert!(normalize_preferences(half_window).is_err());
+    }
 }
@@ -3,8 +3,9 @@ use crate::{
     crypto::hash,
     error::{AppError, Result},
     services::{
+        hide_executor,
         onchain::{felt_to_u128, parse_felt, OnchainReader},
-        privacy_verifier::{parse_privacy_verifier_kind, resolve_privacy_router_for_verifier},
+        privacy_verifier::{resolve_privacy_router_for_verifier, select_privacy_verifier},
     },
 };
 use serde::Deserialize;
@@ -28,16 +29,12 @@ enum HideExecutorKind {
 }
 
 // Internal helper that supports `hide_executor_kind` operations.
-fn hide_executor_kind() -> HideExecutorKind {
-    let raw = std::env::var("HIDE_BALANCE_EXECUTOR_KIND")
-        .unwrap_or_default()
-        .trim()
-        .to_ascii_lowercase();
-    if matches!(raw.as_str(), "shielded_pool_v2" | "shielded-v2" | "v2") {
-        HideExecutorKind::ShieldedPoolV2
-    } else {
-        HideExecutorKind::PrivateActionExecutorV1
-    }
+// On-chain verification only distinguishes V2 submissions; other kinds use the V1 layout.
+fn hide_executor_kind() -> Result<HideExecutorKind> {
+    Ok(match hide_executor::hide_executor_kind()? {
+        hide_executor::HideExecutorKind::ShieldedPoolV2 => HideExecutorKind::ShieldedPoolV2,
+        _ => HideExecutorKind::PrivateActionExecutorV1,
+    })
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -433,7 +430,7 @@ fn verify_hide_balance_privacy_call_in_invoke_payload(
         ));
     };
 
-    let executor_kind = hide_executor_kind();
+    let executor_kind = hide_executor_kind()?;
     let submit_selector_name = match executor_kind {
         HideExecutorKind::PrivateActionExecutorV1 => "submit_private_intent",
         HideExecutorKind::ShieldedPoolV2 => "submit_private_action",
@@ -732,7 +729,8 @@ pub async fn verify_onchain_hide_balance_invoke_tx(
     payload: Option<&PrivacyVerificationPayload>,
     flow: Option<HideBalanceFlow>,
 ) -> Result<()> {
-    let verifier = parse_privacy_verifier_kind(payload.and_then(|p| p.verifier.as_deref()))?;
+    let verifier =
+        select_privacy_verifier(&state.config, payload.and_then(|p| p.verifier.as_deref()))?;
     let router = resolve_privacy_router_for_verifier(&state.config, verifier)?;
     let expected_router = parse_felt(&router)?;
     let (nullifier, commitment, proof, public_inputs) = resolve_privacy_inputs(tx_hash, payload)?;
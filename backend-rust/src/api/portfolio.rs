@@ -17,6 +17,7 @@ use crate::{
 };
 
 use super::{
+    extract::TimeRange,
     resolve_user_scope_addresses,
     wallet::{
         fetch_btc_balance, fetch_evm_erc20_balance, fetch_evm_native_balance,
@@ -46,6 +47,10 @@ pub struct HistoryResponse {
     pub total_value: Vec<HistoryPoint>,
     pub pnl: f64,
     pub pnl_percentage: f64,
+    /// True when candles were merged in SQL so `total_value` fits `ANALYTICS_MAX_POINTS`;
+    /// `original_count` is then the unmerged candle count.
+    pub downsampled: bool,
+    pub original_count: usize,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -56,7 +61,8 @@ pub struct HistoryPoint {
 
 #[derive(Debug, Deserialize)]
 pub struct HistoryQuery {
-    pub period: String, // 1d, 7d, 30d, all
+    #[serde(default)]
+    pub period: String, // 1d, 7d, 30d, all; ignored when `from` is supplied
 }
 
 #[derive(Debug, Deserialize)]
@@ -627,6 +633,25 @@ fn period_to_interval(period: &str) -> (&'static str, i64) {
     }
 }
 
+// Internal helper that picks a candle interval and count covering `span_secs` back from now.
+fn range_to_interval(span_secs: i64) -> (&'static str, i64) {
+    let span_secs = span_secs.max(0);
+    if span_secs <= 86_400 {
+        ("1h", span_secs / 3_600 + 1)
+    } else if span_secs <= 90 * 86_400 {
+        ("1d", span_secs / 86_400 + 1)
+    } else {
+        ("1w", span_secs / 604_800 + 1)
+    }
+}
+
+// Internal helper that returns how many candles to merge per point so a series of `limit`
+// candles fits in `max_points`.
+fn history_bucket_stride(limit: i64, max_points: usize) -> i64 {
+    let max_points = i64::try_from(max_points.max(1)).unwrap_or(i64::MAX);
+    ((limit.max(1) + max_points - 1) / max_points).max(1)
+}
+
 // Internal helper that supports `decimal_to_f64` operations.
 fn decimal_to_f64(value: rust_decimal::Decimal) -> f64 {
     value.to_f64().unwrap_or(0.0)
@@ -1171,6 +1196,7 @@ async fn build_portfolio_ohlcv(
     user_addresses: &[String],
     interval: &str,
     limit: i64,
+    stride: i64,
 ) -> Result<Vec<PortfolioOHLCVPoint>> {
     let rows = fetch_token_holdings(state, user_addresses).await?;
     let mut holding_map = HashMap::new();
@@ -1185,6 +1211,8 @@ async fn build_portfolio_ohlcv(
     }
 
     let interval_secs = interval_seconds(interval);
+    let bucket_secs = interval_secs * stride.max(1);
+    let points = (limit + stride.max(1) - 1) / stride.max(1);
     let now_ts = align_timestamp(chrono::Utc::now().timestamp(), interval_secs);
     let start_ts = now_ts - interval_secs * (limit - 1);
     let from = chrono::Utc
@@ -1200,7 +1228,7 @@ async fn build_portfolio_ohlcv(
     for (token, amount) in holding_map {
         let ticks = state
             .db
-            .get_price_history(&token, interval, from, to)
+            .get_price_history_bucketed(&token, interval, from, to, bucket_secs)
             .await?
             .into_iter()
             .map(|tick| (tick.timestamp.timestamp(), tick))
@@ -1215,9 +1243,9 @@ async fn build_portfolio_ohlcv(
         });
     }
 
-    let mut data = Vec::with_capacity(limit as usize);
-    for idx in 0..limit {
-        let ts = start_ts + interval_secs * idx;
+    let mut data = Vec::with_capacity(points as usize);
+    for idx in 0..points {
+        let ts = start_ts + bucket_secs * idx;
         let mut open_total = 0.0;
         let mut high_total = 0.0;
         let mut low_total = 0.0;
@@ -1315,11 +1343,20 @@ pub async fn get_balance(
 pub async fn get_history(
     State(state): State<AppState>,
     headers: HeaderMap,
+    range: TimeRange,
     axum::extract::Query(query): axum::extract::Query<HistoryQuery>,
 ) -> Result<Json<ApiResponse<HistoryResponse>>> {
     let user_addresses = resolve_user_scope_addresses(&headers, &state).await?;
     let auth_subject = user_addresses.first().cloned().unwrap_or_default();
-    let cache_key = portfolio_history_cache_key(&auth_subject, &user_addresses, &query.period);
+    let bounds = range.from.map(|from| {
+        let (_, to) = range.resolve(chrono::Duration::zero());
+        (from.timestamp(), to.timestamp())
+    });
+    let period_key = match bounds {
+        Some((from, to)) => format!("{}..{}", from, to),
+        None => query.period.clone(),
+    };
+    let cache_key = portfolio_history_cache_key(&auth_subject, &user_addresses, &period_key);
     if let Some(cached) = get_cached_portfolio_history(
         &cache_key,
         Duration::from_secs(PORTFOLIO_HISTORY_CACHE_TTL_SECS),
@@ -1340,9 +1377,25 @@ pub async fn get_history(
         return Ok(Json(ApiResponse::success(cached)));
     }
 
-    let (interval, limit) = period_to_interval(&query.period);
-    match build_portfolio_ohlcv(&state, &auth_subject, &user_addresses, interval, limit).await {
-        Ok(ohlcv) => {
+    let (interval, limit) = match bounds {
+        Some((from, _)) => range_to_interval(chrono::Utc::now().timestamp() - from),
+        None => period_to_interval(&query.period),
+    };
+    let stride = history_bucket_stride(limit, state.config.analytics_max_points);
+    match build_portfolio_ohlcv(
+        &state,
+        &auth_subject,
+        &user_addresses,
+        interval,
+        limit,
+        stride,
+    )
+    .await
+    {
+        Ok(mut ohlcv) => {
+            if let Some((from, to)) = bounds {
+                ohlcv.retain(|point| point.timestamp >= from && point.timestamp <= to);
+            }
             let total_value = ohlcv
                 .iter()
                 .map(|point| HistoryPoint {
@@ -1350,6 +1403,12 @@ pub async fn get_history(
                     value: point.close,
                 })
                 .collect::<Vec<_>>();
+            let downsampled = stride > 1;
+            let original_count = if downsampled {
+                usize::try_from(limit).unwrap_or(usize::MAX)
+            } else {
+                total_value.len()
+            };
 
             let (pnl, pnl_percentage) =
                 if let (Some(first), Some(last)) = (ohlcv.first(), ohlcv.last()) {
@@ -1368,6 +1427,8 @@ pub async fn get_history(
                 total_value,
                 pnl,
                 pnl_percentage,
+                downsampled,
+                original_count,
             };
             cache_portfolio_history(&cache_key, response.clone()).await;
             Ok(Json(ApiResponse::success(response)))
@@ -1421,7 +1482,7 @@ pub async fn get_portfolio_ohlcv(
         return Ok(Json(ApiResponse::success(cached)));
     }
 
-    match build_portfolio_ohlcv(&state, &auth_subject, &user_addresses, &interval, limit).await {
+    match build_portfolio_ohlcv(&state, &auth_subject, &user_addresses, &interval, limit, 1).await {
         Ok(data) => {
             let response = PortfolioOHLCVResponse { interval, data };
             cache_portfolio_ohlcv(&cache_key, response.clone()).await;
@@ -1472,6 +1533,17 @@ mod tests {
         assert!((total_value_usd(&balances) - 25.5).abs() < f64::EPSILON);
     }
 
+    #[test]
+    // Internal helper that supports `history_bucket_stride_fits_max_points` operations.
+    fn history_bucket_stride_fits_max_points() {
+        assert_eq!(history_bucket_stride(24, 500), 1);
+        assert_eq!(history_bucket_stride(500, 500), 1);
+        assert_eq!(history_bucket_stride(501, 500), 2);
+        let stride = history_bucket_stride(1_000, 3);
+        assert_eq!(stride, 334);
+        assert!((1_000 + stride - 1) / stride <= 3);
+    }
+
     #[test]
     // Internal helper that supports `period_to_interval_defaults_to_weekly` operations.
     fn period_to_interval_defaults_to_weekly() {
@@ -1481,6 +1553,14 @@ mod tests {
         assert_eq!(limit, 26);
     }
 
+    #[test]
+    // Internal helper that supports `range_to_interval_scales_with_span` operations.
+    fn range_to_interval_scales_with_span() {
+        assert_eq!(range_to_interval(6 * 3_600), ("1h", 7));
+        assert_eq!(range_to_interval(10 * 86_400), ("1d", 11));
+        assert_eq!(range_to_interval(365 * 86_400), ("1w", 53));
+    }
+
     #[test]
     // Internal helper that supports `interval_seconds_defaults_to_hour` operations.
     fn interval_seconds_defaults_to_hour() {
@@ -1,10 +1,17 @@
 use crate::{
+    config::Config,
     error::{AppError, Result},
     models::{ApiResponse, StarknetWalletCall},
+    services::hide_executor::{hide_executor_kind, shielded_current_root, HideExecutorKind},
+    services::privacy_audit::PrivacyAudit,
     services::privacy_verifier::{
-        parse_privacy_verifier_kind, resolve_privacy_router_for_verifier,
+        resolve_privacy_router_for_verifier, select_privacy_verifier, PrivacyVerifierKind,
+    },
+    services::public_input_schema::public_input_schema,
+    services::{
+        onchain::{parse_felt, OnchainError},
+        relayer::RelayerService,
     },
-    services::{onchain::parse_felt, relayer::RelayerService},
 };
 use axum::{extract::State, http::HeaderMap, Json};
 use serde::{Deserialize, Serialize};
@@ -12,10 +19,25 @@ use serde_json::Value;
 use starknet_core::types::{Call, Felt, FunctionCall};
 use starknet_core::utils::get_selector_from_name;
 use starknet_crypto::poseidon_hash_many;
-use std::{process::Stdio, time::Duration};
-use tokio::{io::AsyncWriteExt, process::Command};
+use std::{
+    process::Stdio,
+    sync::atomic::{AtomicI64, AtomicUsize, Ordering},
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+use tokio::{io::AsyncWriteExt, process::Command, sync::Semaphore};
+
+use super::{
+    maintenance, relayer_access::submit_relayer_calls, require_starknet_user, require_user,
+    AppState,
+};
 
-use super::{require_starknet_user, require_user, AppState};
+// Tracks real prover activity so scheduled warmups can skip while the prover is hot.
+static LAST_AUTO_GARAGA_PROOF_AT: AtomicI64 = AtomicI64::new(0);
+// Bounds concurrent prover runs (`MAX_CONCURRENT_PROOFS`); sized on first use.
+static PROOF_SEMAPHORE: OnceLock<Arc<Semaphore>> = OnceLock::new();
+// Requests currently waiting for a prover slot.
+static PROOF_QUEUE_DEPTH: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Debug, Deserialize)]
 pub struct PrivacyActionRequest {
@@ -268,8 +290,9 @@ pub async fn submit_private_action(
     headers: HeaderMap,
     Json(req): Json<PrivacyActionRequest>,
 ) -> Result<Json<ApiResponse<PrivacyActionResponse>>> {
+    maintenance::ensure_writable(&headers, &state).await?;
     let user_address = require_user(&headers, &state).await?;
-    let tx_hash = submit_private_action_internal(&state, &user_address, &req).await?;
+    let tx_hash = submit_private_action_internal(&state, &user_address, &req, None).await?;
 
     Ok(Json(ApiResponse::success(PrivacyActionResponse {
         tx_hash: tx_hash.to_string(),
@@ -295,8 +318,9 @@ pub async fn auto_submit_private_action(
     headers: HeaderMap,
     Json(req): Json<AutoPrivacyActionRequest>,
 ) -> Result<Json<ApiResponse<AutoPrivacyActionResponse>>> {
+    maintenance::ensure_writable(&headers, &state).await?;
     let user_address = require_user(&headers, &state).await?;
-    let verifier_kind = parse_privacy_verifier_kind(req.verifier.as_deref())?;
+    let verifier_kind = select_privacy_verifier(&state.config, req.verifier.as_deref())?;
     let mut payload = generate_auto_garaga_payload(
         &state.config,
         &user_address,
@@ -309,6 +333,26 @@ pub async fn auto_submit_private_action(
     }
 
     let tx_hash = if req.submit_onchain.unwrap_or(false) {
+        let root_check = RootFreshnessMode::from_config(&state.config);
+        if let Some(current_root) = advanced_root(&state, &payload, root_check).await? {
+            if root_check != RootFreshnessMode::Regenerate {
+                return Err(root_advanced_error(&payload, current_root));
+            }
+            let mut tx_context = req.tx_context.clone().unwrap_or_default();
+            tx_context.root = Some(format!("{:#x}", current_root));
+            let executor_address = payload.executor_address.clone();
+            payload = generate_auto_garaga_payload(
+                &state.config,
+                &user_address,
+                verifier_kind.as_str(),
+                Some(&tx_context),
+            )
+            .await?;
+            payload.executor_address = executor_address;
+            if let Some(current_root) = advanced_root(&state, &payload, root_check).await? {
+                return Err(root_advanced_error(&payload, current_root));
+            }
+        }
         let submit_req = PrivacyActionRequest {
             verifier: Some(payload.verifier.clone()),
             action_type: None,
@@ -321,7 +365,15 @@ pub async fn auto_submit_private_action(
             proof: payload.proof.clone(),
             public_inputs: payload.public_inputs.clone(),
         };
-        Some(submit_private_action_internal(&state, &user_address, &submit_req).await?)
+        Some(
+            submit_private_action_internal(
+                &state,
+                &user_address,
+                &submit_req,
+                payload.note_version.as_deref(),
+            )
+            .await?,
+        )
     } else {
         None
     };
@@ -332,6 +384,68 @@ pub async fn auto_submit_private_action(
     })))
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RootFreshnessMode {
+    Off,
+    Reject,
+    Regenerate,
+}
+
+impl RootFreshnessMode {
+    // Internal helper that reads `PRIVACY_AUTO_SUBMIT_ROOT_CHECK` (validated at startup).
+    fn from_config(config: &crate::config::Config) -> Self {
+        match config
+            .privacy_auto_submit_root_check
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "off" => Self::Off,
+            "regenerate" => Self::Regenerate,
+            _ => Self::Reject,
+        }
+    }
+}
+
+// Re-reads the shielded pool root right before submission and returns it when it no longer
+// matches the root the proof was generated against. Payloads without a root are not root-bound.
+async fn advanced_root(
+    state: &AppState,
+    payload: &AutoPrivacyPayloadResponse,
+    mode: RootFreshnessMode,
+) -> Result<Option<Felt>> {
+    if mode == RootFreshnessMode::Off {
+        return Ok(None);
+    }
+    let (Some(proof_root), Some(executor_address)) = (
+        payload
+            .root
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty()),
+        payload.executor_address.as_deref(),
+    ) else {
+        return Ok(None);
+    };
+    let proof_root = parse_felt(proof_root)?;
+    let current_root = shielded_current_root(&state.config, parse_felt(executor_address)?).await?;
+    Ok(root_if_advanced(proof_root, current_root))
+}
+
+// Internal helper that returns `current_root` only when it differs from the proof root.
+fn root_if_advanced(proof_root: Felt, current_root: Felt) -> Option<Felt> {
+    (proof_root != current_root).then_some(current_root)
+}
+
+// Internal helper that builds the retryable "root advanced" error for auto-submit.
+fn root_advanced_error(payload: &AutoPrivacyPayloadResponse, current_root: Felt) -> AppError {
+    AppError::PrivacyRootAdvanced(format!(
+        "Shielded pool root advanced from {} to {:#x} after proof generation; retry to regenerate the proof",
+        payload.root.as_deref().unwrap_or("0x0"),
+        current_root
+    ))
+}
+
 /// Prepares executor calldata for private execution with intent-hash binding.
 ///
 /// # Arguments
@@ -353,7 +467,7 @@ pub async fn prepare_private_execution(
     Json(req): Json<PreparePrivateExecutionRequest>,
 ) -> Result<Json<ApiResponse<PreparePrivateExecutionResponse>>> {
     let user_address = require_starknet_user(&headers, &state).await?;
-    let verifier_kind = parse_privacy_verifier_kind(req.verifier.as_deref())?;
+    let verifier_kind = select_privacy_verifier(&state.config, req.verifier.as_deref())?;
     let flow = PrivateExecutionFlow::parse(&req.flow)?;
     if req.action_calldata.is_empty() {
         return Err(AppError::BadRequest(
@@ -437,7 +551,7 @@ pub async fn prepare_private_exit(
     Json(req): Json<PreparePrivateExitRequest>,
 ) -> Result<Json<ApiResponse<PreparePrivateExitResponse>>> {
     let user_address = require_starknet_user(&headers, &state).await?;
-    let verifier_kind = parse_privacy_verifier_kind(req.verifier.as_deref())?;
+    let verifier_kind = select_privacy_verifier(&state.config, req.verifier.as_deref())?;
 
     let executor_address = req
         .executor_address
@@ -447,7 +561,8 @@ pub async fn prepare_private_exit(
         .map(ToOwned::to_owned)
         .unwrap_or(resolve_private_action_executor_address(&state.config)?);
 
-    let executor_root_felt = shielded_current_root(&state, &executor_address).await?;
+    let executor_root_felt =
+        shielded_current_root(&state.config, parse_felt(&executor_address)?).await?;
     let nullifier_felt = parse_felt(req.nullifier.trim())?;
     let token_felt = parse_felt(req.token.trim())?;
     let amount_low_felt = parse_felt(req.amount_low.trim())?;
@@ -591,26 +706,228 @@ pub async fn get_private_fixed_amount(
     })))
 }
 
-async fn shielded_current_root(state: &AppState, executor_address: &str) -> Result<Felt> {
+const SELF_TEST_USER_ADDRESS: &str = "0x1";
+const SELF_TEST_VERIFIER: PrivacyVerifierKind = PrivacyVerifierKind::Garaga;
+
+#[derive(Debug, Serialize)]
+pub struct PrivacySelfTestStep {
+    pub name: String,
+    pub status: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PrivacySelfTestResponse {
+    pub ok: bool,
+    pub executor_kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executor_address: Option<String>,
+    pub steps: Vec<PrivacySelfTestStep>,
+}
+
+impl PrivacySelfTestStep {
+    // Internal helper that builds a passing self-test step.
+    fn ok(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: "ok".to_string(),
+            detail: detail.into(),
+        }
+    }
+
+    // Internal helper that builds a failing self-test step.
+    fn failed(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: "failed".to_string(),
+            detail: detail.into(),
+        }
+    }
+
+    // Internal helper that builds a step skipped because an earlier step failed.
+    fn skipped(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: "skipped".to_string(),
+            detail: detail.into(),
+        }
+    }
+}
+
+/// POST /api/v1/privacy/self-test
+///
+/// Runs a hide-balance dry run without submitting anything on-chain: generates a Garaga
+/// payload for a canned context, validates its shape, and probes the configured executor
+/// for the selectors the active `HIDE_BALANCE_EXECUTOR_KIND` relies on.
+pub async fn privacy_self_test(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ApiResponse<PrivacySelfTestResponse>>> {
+    super::admin::require_admin_key(&headers, &state)?;
+
+    let executor_kind = hide_executor_kind()?;
+    let mut steps = Vec::new();
+
+    let payload = match generate_auto_garaga_payload(
+        &state.config,
+        SELF_TEST_USER_ADDRESS,
+        SELF_TEST_VERIFIER.as_str(),
+        Some(&self_test_tx_context()),
+    )
+    .await
+    {
+        Ok(payload) => {
+            steps.push(PrivacySelfTestStep::ok(
+                "prover",
+                format!(
+                    "Prover returned {} proof felts and {} public inputs",
+                    payload.proof.len(),
+                    payload.public_inputs.len()
+                ),
+            ));
+            Some(payload)
+        }
+        Err(err) => {
+            steps.push(PrivacySelfTestStep::failed("prover", err.to_string()));
+            None
+        }
+    };
+
+    steps.push(match payload.as_ref() {
+        Some(payload) => match validate_self_test_payload(payload) {
+            Ok(()) => PrivacySelfTestStep::ok(
+                "payload_shape",
+                "Proof is non-empty and public inputs bind nullifier/commitment",
+            ),
+            Err(err) => PrivacySelfTestStep::failed("payload_shape", err.to_string()),
+        },
+        None => PrivacySelfTestStep::skipped("payload_shape", "Prover step failed"),
+    });
+
+    steps.push(
+        match resolve_privacy_router_for_verifier(&state.config, SELF_TEST_VERIFIER) {
+            Ok(router) => PrivacySelfTestStep::ok("verifier_router", router),
+            Err(err) => PrivacySelfTestStep::failed("verifier_router", err.to_string()),
+        },
+    );
+
+    let executor_address = match resolve_private_action_executor_address(&state.config) {
+        Ok(address) => {
+            steps.push(PrivacySelfTestStep::ok("executor_address", address.clone()));
+            Some(address)
+        }
+        Err(err) => {
+            steps.push(PrivacySelfTestStep::failed(
+                "executor_address",
+                err.to_string(),
+            ));
+            None
+        }
+    };
+
+    match executor_address.as_deref() {
+        Some(address) => {
+            for selector_name in self_test_required_selectors(executor_kind) {
+                let step_name = format!("selector:{}", selector_name);
+                steps.push(
+                    match executor_has_selector(&state, address, selector_name).await {
+                        Ok(true) => PrivacySelfTestStep::ok(&step_name, "Entrypoint present"),
+                        Ok(false) => {
+                            PrivacySelfTestStep::failed(&step_name, "Entrypoint not found")
+                        }
+                        Err(err) => PrivacySelfTestStep::failed(&step_name, err.to_string()),
+                    },
+                );
+            }
+        }
+        None => steps.push(PrivacySelfTestStep::skipped(
+            "executor_selectors",
+            "Executor address is not configured",
+        )),
+    }
+
+    let ok = steps.iter().all(|step| step.status == "ok");
+    Ok(Json(ApiResponse::success(PrivacySelfTestResponse {
+        ok,
+        executor_kind: executor_kind.as_str().to_string(),
+        executor_address,
+        steps,
+    })))
+}
+
+// Internal helper that builds the canned swap context used for the self-test dry run.
+fn self_test_tx_context() -> AutoPrivacyTxContext {
+    AutoPrivacyTxContext {
+        flow: Some("swap".to_string()),
+        from_token: Some("STRK".to_string()),
+        to_token: Some("CAREL".to_string()),
+        amount: Some("1".to_string()),
+        recipient: Some(SELF_TEST_USER_ADDRESS.to_string()),
+        from_network: Some("starknet".to_string()),
+        to_network: Some("starknet".to_string()),
+        ..AutoPrivacyTxContext::default()
+    }
+}
+
+// Internal helper that checks the generated payload has the shape the relayer submits.
+fn validate_self_test_payload(payload: &AutoPrivacyPayloadResponse) -> Result<()> {
+    if payload.proof.is_empty() {
+        return Err(AppError::BadRequest(
+            "Self-test payload proof is empty".to_string(),
+        ));
+    }
+    if payload.public_inputs.is_empty() {
+        return Err(AppError::BadRequest(
+            "Self-test payload public_inputs is empty".to_string(),
+        ));
+    }
+    if is_dummy_garaga_payload(&payload.proof, &payload.public_inputs) {
+        return Err(AppError::BadRequest(
+            "Self-test payload looks like a dummy proof".to_string(),
+        ));
+    }
+    ensure_public_inputs_bind_nullifier_commitment(
+        &payload.nullifier,
+        &payload.commitment,
+        &payload.public_inputs,
+        "Self-test payload",
+    )
+}
+
+// Internal helper that lists the deposit/submit selectors each executor kind must expose.
+fn self_test_required_selectors(executor_kind: HideExecutorKind) -> &'static [&'static str] {
+    match executor_kind {
+        HideExecutorKind::ShieldedPoolV3 => &["deposit_fixed_v3", "submit_private_swap"],
+        HideExecutorKind::ShieldedPoolV2 => &["deposit_fixed_for", "submit_private_action"],
+        HideExecutorKind::PrivateActionExecutorV1 => &["submit_private_intent"],
+    }
+}
+
+// Internal helper that probes whether the executor exposes `selector_name`.
+// A revert still proves the entrypoint exists; only a missing entrypoint counts as unsupported.
+async fn executor_has_selector(
+    state: &AppState,
+    executor_address: &str,
+    selector_name: &str,
+) -> Result<bool> {
     let reader = crate::services::onchain::OnchainReader::from_config(&state.config)?;
-    let contract_address = parse_felt(executor_address)?;
-    let selector = get_selector_from_name("get_root")
+    let selector = get_selector_from_name(selector_name)
         .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
-
-    let out = reader
-        .call(FunctionCall {
-            contract_address,
+    let probe = reader
+        .call_typed(FunctionCall {
+            contract_address: parse_felt(executor_address)?,
             entry_point_selector: selector,
-            calldata: vec![],
+            calldata: vec![Felt::ONE],
         })
-        .await?;
-    let root = out.first().copied().unwrap_or(Felt::ZERO);
-    if root == Felt::ZERO {
-        return Err(AppError::BadRequest(
-            "ShieldedPoolV3 root is not initialized yet (get_root=0).".to_string(),
-        ));
+        .await;
+    match probe {
+        Ok(_) | Err(OnchainError::ContractRevert(_)) => Ok(true),
+        Err(OnchainError::EntrypointNotFound(_)) => Ok(false),
+        Err(err) => Err(AppError::BlockchainRPC(format!(
+            "Failed to probe {} on {}: {}",
+            selector_name, executor_address, err
+        ))),
     }
-    Ok(root)
 }
 
 pub async fn relay_private_execution(
@@ -618,6 +935,7 @@ pub async fn relay_private_execution(
     headers: HeaderMap,
     Json(req): Json<RelayerPrivateExecutionRequest>,
 ) -> Result<Json<ApiResponse<RelayerPrivateExecutionResponse>>> {
+    maintenance::ensure_writable(&headers, &state).await?;
     let signed_user = require_starknet_user(&headers, &state).await?;
     let signed_user_felt = parse_felt(&signed_user)?;
     let req_user_felt = parse_felt(&req.user)?;
@@ -632,6 +950,10 @@ pub async fn relay_private_execution(
             "signature/proof/public_inputs must be non-empty".to_string(),
         ));
     }
+    PrivacyPayloadLimits::from_config(&state.config)
+        .ensure(req.proof.len(), req.public_inputs.len())?;
+    ensure_felt_items(&req.proof, "proof")?;
+    ensure_felt_items(&req.public_inputs, "public_inputs")?;
 
     let intermediary_address = std::env::var("PRIVACY_INTERMEDIARY_ADDRESS")
         .ok()
@@ -683,30 +1005,68 @@ pub async fn relay_private_execution(
         calldata.push(parse_felt(value)?);
     }
 
-    let relayer = RelayerService::from_config(&state.config)?;
-    let submitted = relayer
-        .submit_call(Call {
-            to,
-            selector,
-            calldata,
-        })
-        .await?;
+    let audit = PrivacyAudit::start(
+        "relayer_execute",
+        select_privacy_verifier(&state.config, None)
+            .map(|kind| kind.as_str())
+            .unwrap_or("unknown"),
+        hide_executor_kind()?.as_str(),
+        None,
+    );
+    let submitted: Result<String> = async {
+        let relayer = RelayerService::from_config(&state.config)?;
+        submit_relayer_calls(
+            &state,
+            &relayer,
+            &signed_user,
+            "relayer_execute",
+            vec![Call {
+                to,
+                selector,
+                calldata,
+            }],
+        )
+        .await
+        .map(|submitted| submitted.tx_hash)
+    }
+    .await;
+    audit.finish(&state.db, &submitted);
 
     Ok(Json(ApiResponse::success(
         RelayerPrivateExecutionResponse {
-            tx_hash: submitted.tx_hash,
+            tx_hash: submitted?,
         },
     )))
 }
 
+// Submits a privacy action and records its outcome in the privacy audit log.
+async fn submit_private_action_internal(
+    state: &AppState,
+    user_address: &str,
+    req: &PrivacyActionRequest,
+    pool_version: Option<&str>,
+) -> Result<String> {
+    let audit = PrivacyAudit::start(
+        "submit",
+        select_privacy_verifier(&state.config, req.verifier.as_deref())
+            .map(|kind| kind.as_str())
+            .unwrap_or("unknown"),
+        hide_executor_kind()?.as_str(),
+        pool_version,
+    );
+    let outcome = submit_private_action_onchain(state, user_address, req).await;
+    audit.finish(&state.db, &outcome);
+    outcome
+}
+
 // Routes privacy submissions to V1 (`submit_private_action`) or V2 (`submit_action`) based on payload shape.
 // Enforces payload integrity (including nullifier/commitment binding) before relayer execution.
-async fn submit_private_action_internal(
+async fn submit_private_action_onchain(
     state: &AppState,
     user_address: &str,
     req: &PrivacyActionRequest,
 ) -> Result<String> {
-    let verifier_kind = parse_privacy_verifier_kind(req.verifier.as_deref())?;
+    let verifier_kind = select_privacy_verifier(&state.config, req.verifier.as_deref())?;
 
     let router_v2 = state
         .config
@@ -749,6 +1109,10 @@ async fn submit_private_action_internal(
             user_address
         );
     }
+    PrivacyPayloadLimits::from_config(&state.config)
+        .ensure(req.proof.len(), req.public_inputs.len())?;
+    ensure_felt_items(&req.proof, "proof")?;
+    ensure_felt_items(&req.public_inputs, "public_inputs")?;
     if is_dummy_garaga_payload(&req.proof, &req.public_inputs) {
         return Err(crate::error::AppError::BadRequest(
             "privacy.proof/public_inputs dummy payload (0x1) is not allowed; submit a real Garaga proof"
@@ -799,10 +1163,60 @@ async fn submit_private_action_internal(
         );
         build_submit_call_v1(&router_v1, req)?
     };
-    let submitted = relayer.submit_call(call).await?;
+    let submitted =
+        submit_relayer_calls(state, &relayer, user_address, "privacy_submit", vec![call]).await?;
     Ok(submitted.tx_hash)
 }
 
+/// Upper bounds on client-supplied `proof`/`public_inputs` array lengths.
+#[derive(Debug, Clone, Copy)]
+pub struct PrivacyPayloadLimits {
+    pub max_proof_len: usize,
+    pub max_public_inputs_len: usize,
+}
+
+impl PrivacyPayloadLimits {
+    /// Reads `PRIVACY_MAX_PROOF_LEN` / `PRIVACY_MAX_PUBLIC_INPUTS_LEN` from config.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_proof_len: config.privacy_max_proof_len,
+            max_public_inputs_len: config.privacy_max_public_inputs_len,
+        }
+    }
+
+    /// Rejects oversized payloads before any calldata is built or relayed.
+    pub fn ensure(&self, proof_len: usize, public_inputs_len: usize) -> Result<()> {
+        if proof_len > self.max_proof_len {
+            return Err(AppError::BadRequest(format!(
+                "privacy.proof has {} items; maximum is {}",
+                proof_len, self.max_proof_len
+            )));
+        }
+        if public_inputs_len > self.max_public_inputs_len {
+            return Err(AppError::BadRequest(format!(
+                "privacy.public_inputs has {} items; maximum is {}",
+                public_inputs_len, self.max_public_inputs_len
+            )));
+        }
+        Ok(())
+    }
+}
+
+// Internal helper that validates each item of a felt array, naming the first offending index.
+fn ensure_felt_items(items: &[String], field: &str) -> Result<()> {
+    for (index, item) in items.iter().enumerate() {
+        if parse_felt(item.trim()).is_err() {
+            return Err(AppError::BadRequest(format!(
+                "{}[{}] is not a valid felt: {}",
+                field,
+                index,
+                item.trim()
+            )));
+        }
+    }
+    Ok(())
+}
+
 // Detects mock placeholder payloads (`proof=[0x1]`, `public_inputs=[0x1]`) and rejects them in real Hide Mode.
 fn is_dummy_garaga_payload(proof: &[String], public_inputs: &[String]) -> bool {
     if proof.len() != 1 || public_inputs.len() != 1 {
@@ -949,14 +1363,78 @@ pub(crate) async fn generate_auto_garaga_payload(
             )
         })?;
 
-    load_auto_garaga_payload_from_prover_cmd(
+    let _permit = acquire_proof_slot(config).await?;
+    let payload = load_auto_garaga_payload_from_prover_cmd(
         cmd,
         config.privacy_auto_garaga_prover_timeout_ms,
         user_address,
         verifier,
         tx_context,
     )
+    .await?;
+    LAST_AUTO_GARAGA_PROOF_AT.store(chrono::Utc::now().timestamp(), Ordering::Relaxed);
+    Ok(payload)
+}
+
+// Decrements the proof queue depth when a waiter leaves, including on cancellation.
+struct ProofQueueGuard;
+
+impl Drop for ProofQueueGuard {
+    fn drop(&mut self) {
+        PROOF_QUEUE_DEPTH.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+// Internal helper that waits for a prover slot, giving up after `PROOF_QUEUE_TIMEOUT_MS`.
+async fn acquire_proof_slot(config: &Config) -> Result<tokio::sync::OwnedSemaphorePermit> {
+    let semaphore = PROOF_SEMAPHORE
+        .get_or_init(|| Arc::new(Semaphore::new(config.max_concurrent_proofs.max(1))))
+        .clone();
+    PROOF_QUEUE_DEPTH.fetch_add(1, Ordering::Relaxed);
+    let _queued = ProofQueueGuard;
+    match tokio::time::timeout(
+        Duration::from_millis(config.proof_queue_timeout_ms),
+        semaphore.acquire_owned(),
+    )
     .await
+    {
+        Ok(Ok(permit)) => Ok(permit),
+        Ok(Err(err)) => Err(AppError::Internal(format!(
+            "Prover semaphore closed: {}",
+            err
+        ))),
+        Err(_) => {
+            tracing::warn!(
+                "Prover busy: no slot within {}ms (queue depth {})",
+                config.proof_queue_timeout_ms,
+                proof_queue_depth()
+            );
+            Err(AppError::ProverBusy)
+        }
+    }
+}
+
+/// Number of requests currently queued for a prover slot.
+pub(crate) fn proof_queue_depth() -> usize {
+    PROOF_QUEUE_DEPTH.load(Ordering::Relaxed)
+}
+
+/// Unix timestamp of the last successful prover run, if any since startup.
+pub(crate) fn last_auto_garaga_proof_at() -> Option<i64> {
+    let value = LAST_AUTO_GARAGA_PROOF_AT.load(Ordering::Relaxed);
+    (value > 0).then_some(value)
+}
+
+/// Returns whether a scheduled Garaga warmup should run: the prover must have been idle
+/// (no real proof and no warmup) for at least `idle_secs`.
+pub(crate) fn garaga_warmup_due(
+    now: i64,
+    last_proof_at: Option<i64>,
+    last_warmup_at: i64,
+    idle_secs: u64,
+) -> bool {
+    let last_activity = last_proof_at.unwrap_or(0).max(last_warmup_at);
+    now.saturating_sub(last_activity) >= idle_secs as i64
 }
 
 // Executes the external prover command and parses the returned proof/public_inputs payload.
@@ -982,6 +1460,7 @@ async fn load_auto_garaga_payload_from_prover_cmd(
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
+        .kill_on_drop(true)
         .spawn()
         .map_err(|error| {
             AppError::BadRequest(format!(
@@ -1221,7 +1700,7 @@ fn extract_optional_u64(value: &Value, keys: &[&str]) -> Option<u64> {
 /// * `Err(AppError)` - Binding is missing, index out-of-range, or values mismatch.
 ///
 /// # Notes
-/// - Index positions come from `GARAGA_NULLIFIER_PUBLIC_INPUT_INDEX` and `GARAGA_COMMITMENT_PUBLIC_INPUT_INDEX`.
+/// - Index positions come from the `v2` layout of `GARAGA_PUBLIC_INPUT_SCHEMA`.
 /// - This check is mandatory before relayer submits Hide Mode actions on-chain.
 pub(crate) fn ensure_public_inputs_bind_nullifier_commitment(
     nullifier: &str,
@@ -1229,8 +1708,9 @@ pub(crate) fn ensure_public_inputs_bind_nullifier_commitment(
     public_inputs: &[String],
     source_label: &str,
 ) -> Result<()> {
-    let nullifier_index = privacy_binding_index("GARAGA_NULLIFIER_PUBLIC_INPUT_INDEX", 0)?;
-    let commitment_index = privacy_binding_index("GARAGA_COMMITMENT_PUBLIC_INPUT_INDEX", 1)?;
+    let layout = public_input_schema().v2();
+    let nullifier_index = layout.nullifier;
+    let commitment_index = schema_index(layout.commitment, "v2", "commitment")?;
     let required_len = std::cmp::max(nullifier_index, commitment_index) + 1;
 
     if public_inputs.len() < required_len {
@@ -1265,8 +1745,9 @@ pub(crate) fn ensure_public_inputs_bind_root_nullifier(
     public_inputs: &[String],
     source_label: &str,
 ) -> Result<()> {
-    let root_index = privacy_binding_index("GARAGA_ROOT_PUBLIC_INPUT_INDEX", 0)?;
-    let nullifier_index = privacy_binding_index("GARAGA_NULLIFIER_PUBLIC_INPUT_INDEX_V3", 1)?;
+    let layout = public_input_schema().v3();
+    let root_index = schema_index(layout.root, "v3", "root")?;
+    let nullifier_index = layout.nullifier;
     let required_len = std::cmp::max(root_index, nullifier_index) + 1;
 
     if public_inputs.len() < required_len {
@@ -1306,8 +1787,9 @@ pub(crate) fn ensure_public_inputs_bind_v3_shape(
             )
         })
         .unwrap_or(false);
-    let root_index = privacy_binding_index("GARAGA_ROOT_PUBLIC_INPUT_INDEX", 0)?;
-    let nullifier_index = privacy_binding_index("GARAGA_NULLIFIER_PUBLIC_INPUT_INDEX_V3", 1)?;
+    let layout = public_input_schema().v3();
+    let root_index = schema_index(layout.root, "v3", "root")?;
+    let nullifier_index = layout.nullifier;
     if legacy_compat {
         let required_len = std::cmp::max(root_index, nullifier_index) + 1;
         if public_inputs.len() < required_len {
@@ -1318,11 +1800,8 @@ pub(crate) fn ensure_public_inputs_bind_v3_shape(
         }
         return Ok(());
     }
-    let action_hash_index = intent_hash_public_input_index()?;
-    let required_len = std::cmp::max(
-        std::cmp::max(root_index, nullifier_index),
-        action_hash_index,
-    ) + 1;
+    let action_hash_index = schema_index(layout.action_hash, "v3", "action_hash")?;
+    let required_len = layout.required_len();
     if public_inputs.len() < required_len {
         return Err(AppError::BadRequest(format!(
             "{} V3 verifier output too short: public_inputs length is {}, required >= {} (root={}, nullifier={}, action_hash={}). Regenerate Garaga PK/VK and redeploy verifier.",
@@ -1338,17 +1817,14 @@ pub(crate) fn ensure_public_inputs_bind_v3_shape(
     Ok(())
 }
 
-// Reads and validates the configured public input index reserved for intent-hash binding.
-fn intent_hash_public_input_index() -> Result<usize> {
-    let raw =
-        std::env::var("GARAGA_INTENT_HASH_PUBLIC_INPUT_INDEX").unwrap_or_else(|_| "2".to_string());
-    let parsed = raw.trim().parse::<usize>().map_err(|_| {
-        AppError::BadRequest(format!(
-            "GARAGA_INTENT_HASH_PUBLIC_INPUT_INDEX must be a non-negative integer, got '{}'",
-            raw
+// Resolves an optional public-input index from the schema; startup validation keeps required ones set.
+fn schema_index(index: Option<usize>, version: &str, field: &str) -> Result<usize> {
+    index.ok_or_else(|| {
+        AppError::Internal(format!(
+            "GARAGA_PUBLIC_INPUT_SCHEMA: {} has no {} index",
+            version, field
         ))
-    })?;
-    Ok(parsed)
+    })
 }
 
 /// Binds executor `intent_hash` into the configured public input slot.
@@ -1363,13 +1839,20 @@ fn intent_hash_public_input_index() -> Result<usize> {
 ///
 /// # Notes
 /// - Pads `public_inputs` with `0x0` when the configured index exceeds current length.
+/// - The slot comes from the `action_hash` index of the payload's `GARAGA_PUBLIC_INPUT_SCHEMA` layout.
 /// - Used to couple off-chain generated proof payload with on-chain private execution intent.
 pub(crate) fn bind_intent_hash_into_payload(
     payload: &mut AutoPrivacyPayloadResponse,
     intent_hash: &str,
 ) -> Result<()> {
     let intent_hash_felt = parse_felt(intent_hash)?;
-    let index = intent_hash_public_input_index()?;
+    // V3 payloads carry a note root; everything else uses the V2 layout.
+    let (layout, version) = if payload.root.is_some() {
+        (public_input_schema().v3(), "v3")
+    } else {
+        (public_input_schema().v2(), "v2")
+    };
+    let index = schema_index(layout.action_hash, version, "action_hash")?;
     while payload.public_inputs.len() <= index {
         payload.public_inputs.push("0x0".to_string());
     }
@@ -1655,18 +2138,6 @@ fn build_private_exit_wallet_call(
     })
 }
 
-// Reads numeric binding indexes from env and validates they are usable for payload integrity checks.
-fn privacy_binding_index(env_key: &str, default_value: usize) -> Result<usize> {
-    let raw = std::env::var(env_key).unwrap_or_else(|_| default_value.to_string());
-    let parsed = raw.trim().parse::<usize>().map_err(|_| {
-        AppError::BadRequest(format!(
-            "{} must be a non-negative integer, got '{}'",
-            env_key, raw
-        ))
-    })?;
-    Ok(parsed)
-}
-
 // Parses textual felt lists (comma/newline-delimited) from prover outputs.
 fn parse_hex_string(raw: &str, field_label: &str) -> Result<Vec<String>> {
     let values: Vec<String> = raw
@@ -1711,3 +2182,68 @@ fn parse_hex_array(array: &[Value], field_label: &str) -> Result<Vec<String>> {
     }
     Ok(values)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `privacy_payload_limits_reject_oversized_arrays` operations.
+    fn privacy_payload_limits_reject_oversized_arrays() {
+        let limits = PrivacyPayloadLimits {
+            max_proof_len: 4,
+            max_public_inputs_len: 2,
+        };
+        assert!(limits.ensure(4, 2).is_ok());
+        assert!(limits.ensure(5, 2).is_err());
+        assert!(limits.ensure(4, 3).is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `ensure_felt_items_names_offending_index` operations.
+    fn ensure_felt_items_names_offending_index() {
+        let items = vec!["0x1".to_string(), " 0x2 ".to_string(), "0xzz".to_string()];
+        let err = ensure_felt_items(&items, "public_inputs").expect_err("invalid felt");
+        assert!(err
+            .to_string()
+            .contains("public_inputs[2] is not a valid felt"));
+        assert!(ensure_felt_items(&items[..2], "public_inputs").is_ok());
+    }
+
+    #[test]
+    // Internal helper that supports `garaga_warmup_due_skips_recent_activity` operations.
+    fn garaga_warmup_due_skips_recent_activity() {
+        assert!(!garaga_warmup_due(1_000, Some(900), 0, 300));
+        assert!(!garaga_warmup_due(1_000, None, 800, 300));
+        assert!(garaga_warmup_due(1_000, Some(600), 500, 300));
+        assert!(garaga_warmup_due(1_000, None, 0, 300));
+    }
+
+    #[test]
+    // Internal helper that supports `root_if_advanced_only_reports_changed_roots` operations.
+    fn root_if_advanced_only_reports_changed_roots() {
+        let root = Felt::from(11_u64);
+        assert_eq!(root_if_advanced(root, root), None);
+        assert_eq!(
+            root_if_advanced(root, Felt::from(12_u64)),
+            Some(Felt::from(12_u64))
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `self_test_required_selectors_follow_executor_kind` operations.
+    fn self_test_required_selectors_follow_executor_kind() {
+        assert_eq!(
+            self_test_required_selectors(HideExecutorKind::ShieldedPoolV3),
+            &["deposit_fixed_v3", "submit_private_swap"]
+        );
+        assert_eq!(
+            self_test_required_selectors(HideExecutorKind::ShieldedPoolV2),
+            &["deposit_fixed_for", "submit_private_action"]
+        );
+        assert_eq!(
+            self_test_required_selectors(HideExecutorKind::PrivateActionExecutorV1),
+            &["submit_private_intent"]
+        );
+    }
+}
@@ -1,10 +1,10 @@
 use crate::{
     error::Result,
-    models::ApiResponse,
+    models::{ApiResponse, PrivateBtcSwap},
     services::onchain::{parse_felt, OnchainInvoker, OnchainReader},
 };
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::HeaderMap,
     Json,
 };
@@ -18,6 +18,11 @@ use super::{require_user, AppState};
 pub struct InitiatePrivateBtcSwapRequest {
     pub ciphertext: String,
     pub commitment: String,
+    // HTLC refund timelock (unix seconds) for refund reminders, and whether the user holds the
+    // refund preimage; both are only stored with the swap history.
+    pub timelock: Option<i64>,
+    #[serde(default)]
+    pub refund_preimage_available: bool,
     pub proof: Vec<String>,
     pub public_inputs: Vec<String>,
 }
@@ -25,6 +30,8 @@ pub struct InitiatePrivateBtcSwapRequest {
 #[derive(Debug, Deserialize)]
 pub struct FinalizePrivateBtcSwapRequest {
     pub swap_id: u64,
+    // Commitment from `initiate`, used only to mark the caller's own history row finalized.
+    pub commitment: Option<String>,
     pub recipient: String,
     pub nullifier: String,
     pub proof: Vec<String>,
@@ -42,13 +49,36 @@ pub struct NullifierStatusResponse {
     pub used: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PrivateBtcSwapHistoryQuery {
+    pub status: Option<String>,
+}
+
+// Internal helper that parses or transforms values for `normalize_status_filter`.
+fn normalize_status_filter(raw: Option<&str>) -> Result<Option<&'static str>> {
+    match raw
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("") | Some("all") => Ok(None),
+        Some("initiated") => Ok(Some("initiated")),
+        Some("finalized") => Ok(Some("finalized")),
+        Some("refunded") => Ok(Some("refunded")),
+        Some("failed") => Ok(Some("failed")),
+        Some(other) => Err(crate::error::AppError::BadRequest(format!(
+            "Unknown status: {} (expected initiated, finalized, refunded or failed)",
+            other
+        ))),
+    }
+}
+
 /// POST /api/v1/private-btc-swap/initiate
 pub async fn initiate_private_btc_swap(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(req): Json<InitiatePrivateBtcSwapRequest>,
 ) -> Result<Json<ApiResponse<PrivateSwapResponse>>> {
-    let _user = require_user(&headers, &state).await?;
+    let user = require_user(&headers, &state).await?;
     let contract = state.config.private_btc_swap_address.trim();
     if contract.is_empty() || contract.starts_with("0x0000") {
         return Err(crate::error::AppError::BadRequest(
@@ -62,9 +92,31 @@ pub async fn initiate_private_btc_swap(
         ));
     };
 
+    let timelock_at = match req.timelock {
+        Some(secs) => Some(parse_timelock(secs)?),
+        None => None,
+    };
     let call = build_initiate_call(contract, &req)?;
     let tx_hash = invoker.invoke(call).await?;
 
+    let commitment = format!("{:#x}", parse_felt(&req.commitment)?);
+    if let Err(err) = sqlx::query(
+        "INSERT INTO private_btc_swaps
+            (user_address, commitment, initiate_tx_hash, timelock_at, refund_preimage_available)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (commitment) DO NOTHING",
+    )
+    .bind(&user)
+    .bind(&commitment)
+    .bind(format!("{:#x}", tx_hash))
+    .bind(timelock_at)
+    .bind(req.refund_preimage_available)
+    .execute(state.db.pool())
+    .await
+    {
+        tracing::warn!("Failed to record private BTC swap history: {}", err);
+    }
+
     Ok(Json(ApiResponse::success(PrivateSwapResponse {
         tx_hash: tx_hash.to_string(),
     })))
@@ -76,7 +128,7 @@ pub async fn finalize_private_btc_swap(
     headers: HeaderMap,
     Json(req): Json<FinalizePrivateBtcSwapRequest>,
 ) -> Result<Json<ApiResponse<PrivateSwapResponse>>> {
-    let _user = require_user(&headers, &state).await?;
+    let user = require_user(&headers, &state).await?;
     let contract = state.config.private_btc_swap_address.trim();
     if contract.is_empty() || contract.starts_with("0x0000") {
         return Err(crate::error::AppError::BadRequest(
@@ -93,6 +145,28 @@ pub async fn finalize_private_btc_swap(
     let call = build_finalize_call(contract, &req)?;
     let tx_hash = invoker.invoke(call).await?;
 
+    let commitment = match req.commitment.as_deref() {
+        Some(raw) => Some(format!("{:#x}", parse_felt(raw)?)),
+        None => None,
+    };
+    let swap_id = i64::try_from(req.swap_id).unwrap_or(i64::MAX);
+    // Only the status changes: the finalize tx pays the recipient, so its hash is not stored.
+    if let Err(err) = sqlx::query(
+        "UPDATE private_btc_swaps
+         SET status = 'finalized', updated_at = NOW()
+         WHERE user_address = $1
+           AND status = 'initiated'
+           AND (commitment = $2 OR swap_id = $3)",
+    )
+    .bind(&user)
+    .bind(commitment)
+    .bind(swap_id)
+    .execute(state.db.pool())
+    .await
+    {
+        tracing::warn!("Failed to update private BTC swap history: {}", err);
+    }
+
     Ok(Json(ApiResponse::success(PrivateSwapResponse {
         tx_hash: tx_hash.to_string(),
     })))
@@ -135,6 +209,44 @@ pub async fn is_nullifier_used(
     })))
 }
 
+// Internal helper that validates the HTLC refund timelock supplied at initiation.
+fn parse_timelock(secs: i64) -> Result<chrono::DateTime<chrono::Utc>> {
+    let timelock = chrono::DateTime::<chrono::Utc>::from_timestamp(secs, 0).ok_or_else(|| {
+        crate::error::AppError::BadRequest("timelock must be a unix timestamp".to_string())
+    })?;
+    if timelock <= chrono::Utc::now() {
+        return Err(crate::error::AppError::BadRequest(
+            "timelock must be in the future".to_string(),
+        ));
+    }
+    Ok(timelock)
+}
+
+/// GET /api/v1/private-btc-swap/history
+///
+/// Lists the caller's own private BTC swaps; counterparties and BTC-side details are never stored.
+pub async fn list_private_btc_swaps(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<PrivateBtcSwapHistoryQuery>,
+) -> Result<Json<ApiResponse<Vec<PrivateBtcSwap>>>> {
+    let user = require_user(&headers, &state).await?;
+    let status = normalize_status_filter(query.status.as_deref())?;
+    let swaps = sqlx::query_as::<_, PrivateBtcSwap>(
+        "SELECT id, commitment, swap_id, status, initiate_tx_hash,
+                timelock_at, refund_preimage_available, refund_tx_hash, created_at, updated_at
+         FROM private_btc_swaps
+         WHERE user_address = $1 AND ($2::TEXT IS NULL OR status = $2)
+         ORDER BY created_at DESC
+         LIMIT 200",
+    )
+    .bind(&user)
+    .bind(status)
+    .fetch_all(state.db.pool())
+    .await?;
+    Ok(Json(ApiResponse::success(swaps)))
+}
+
 // Internal helper that builds inputs for `build_initiate_call`.
 fn build_initiate_call(contract: &str, req: &InitiatePrivateBtcSwapRequest) -> Result<Call> {
     let to = parse_felt(contract)?;
@@ -202,3 +314,28 @@ fn build_finalize_call(contract: &str, req: &FinalizePrivateBtcSwapRequest) -> R
         calldata,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `normalize_status_filter_accepts_known_states` operations.
+    fn normalize_status_filter_accepts_known_states() {
+        assert_eq!(normalize_status_filter(None).unwrap(), None);
+        assert_eq!(normalize_status_filter(Some("all")).unwrap(), None);
+        assert_eq!(
+            normalize_status_filter(Some("Finalized")).unwrap(),
+            Some("finalized")
+        );
+        assert!(normalize_status_filter(Some("pending")).is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `parse_timelock_rejects_past_values` operations.
+    fn parse_timelock_rejects_past_values() {
+        assert!(parse_timelock(1_000).is_err());
+        let future = chrono::Utc::now().timestamp() + 3_600;
+        assert_eq!(parse_timelock(future).unwrap().timestamp(), future);
+    }
+}
@@ -1,12 +1,12 @@
-use super::{require_user, AppState};
+use super::{extract::Pagination, require_user, resolve_user_scope_addresses, AppState};
 use crate::services::onchain::{felt_to_u128, parse_felt, OnchainReader};
 use crate::{
+    db::ReferralPayout,
     error::Result,
     models::{ApiResponse, PaginatedResponse},
-    utils::ensure_page_limit,
 };
 use axum::{extract::State, http::HeaderMap, Json};
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use starknet_core::types::FunctionCall;
 use starknet_core::utils::get_selector_from_name;
 
@@ -24,12 +24,6 @@ pub struct ReferralStats {
     pub total_rewards: f64,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ReferralHistoryQuery {
-    pub page: Option<i32>,
-    pub limit: Option<i32>,
-}
-
 #[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct ReferralHistoryItem {
     pub tx_hash: String,
@@ -41,6 +35,13 @@ pub struct ReferralHistoryItem {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct ReferralHistoryResponse {
+    #[serde(flatten)]
+    pub page: PaginatedResponse<ReferralHistoryItem>,
+    pub payouts: Vec<ReferralPayout>,
+}
+
 // Struct bantuan untuk mapping hasil query COUNT
 #[derive(sqlx::FromRow)]
 struct CountResult {
@@ -178,12 +179,9 @@ pub async fn get_stats(
 pub async fn get_history(
     State(state): State<AppState>,
     headers: HeaderMap,
-    axum::extract::Query(query): axum::extract::Query<ReferralHistoryQuery>,
-) -> Result<Json<ApiResponse<PaginatedResponse<ReferralHistoryItem>>>> {
+    Pagination { page, limit }: Pagination,
+) -> Result<Json<ApiResponse<ReferralHistoryResponse>>> {
     let user_address = require_user(&headers, &state).await?;
-    let page = query.page.unwrap_or(1);
-    let limit = query.limit.unwrap_or(10);
-    ensure_page_limit(limit, state.config.rate_limit_authenticated)?;
 
     let offset = (page - 1) * limit;
 
@@ -246,11 +244,16 @@ pub async fn get_history(
     .fetch_one(state.db.pool())
     .await?;
 
-    let response = PaginatedResponse {
-        items,
-        page,
-        limit,
-        total: total_res.total,
+    let scope_addresses: Vec<String> = resolve_user_scope_addresses(&headers, &state)
+        .await?
+        .into_iter()
+        .map(|address| address.to_ascii_lowercase())
+        .collect();
+    let payouts = state.db.list_referral_payouts(&scope_addresses).await?;
+
+    let response = ReferralHistoryResponse {
+        page: PaginatedResponse::new(items, page, limit, total_res.total),
+        payouts,
     };
 
     Ok(Json(ApiResponse::success(response)))
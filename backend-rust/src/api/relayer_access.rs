@@ -0,0 +1,194 @@
+use super::{maintenance::is_bypass_address, AppState};
+use crate::{
+    error::{AppError, Result},
+    services::relayer::{RelayerService, RelayerSubmitResult},
+};
+use chrono::{DateTime, Utc};
+use redis::AsyncCommands;
+use serde::Serialize;
+use starknet_core::types::Call;
+
+const RELAYER_RATE_WINDOW_SECS: i64 = 3600;
+
+/// Relayer gas spend aggregated per user and fee unit.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct RelayerSpendSummary {
+    pub user_address: String,
+    pub fee_unit: String,
+    pub submissions: i64,
+    pub total_fee: String,
+    pub last_submitted_at: DateTime<Utc>,
+}
+
+/// Checks that `user_address` may use the relayer pool right now.
+///
+/// # Notes
+/// * With `RELAYER_ALLOWED_ADDRESSES` or `RELAYER_MIN_AI_LEVEL` set, only listed addresses or
+///   users at or above the AI level pass.
+/// * `RELAYER_RATE_LIMIT_PER_HOUR` caps submissions per user; the limiter fails open when Redis
+///   is unavailable.
+pub async fn ensure_relayer_access(state: &AppState, user_address: &str) -> Result<()> {
+    let config = &state.config;
+    if config.relayer_allowlist_enabled() {
+        let level = if config.relayer_min_ai_level > 0
+            && !is_bypass_address(&config.relayer_allowed_addresses, user_address)
+        {
+            state.db.get_user_ai_level(user_address).await?
+        } else {
+            0
+        };
+        if !relayer_access_allowed(
+            &config.relayer_allowed_addresses,
+            config.relayer_min_ai_level,
+            user_address,
+            level,
+        ) {
+            return Err(AppError::RelayerAccessDenied(
+                "this account is not on the relayer allowlist".to_string(),
+            ));
+        }
+    }
+    enforce_relayer_rate_limit(state, user_address).await
+}
+
+/// Submits `calls` through the relayer pool on behalf of `user_address`.
+///
+/// # Notes
+/// * Runs `ensure_relayer_access` first and records the paid fee under `flow` once confirmed.
+pub async fn submit_relayer_calls(
+    state: &AppState,
+    relayer: &RelayerService,
+    user_address: &str,
+    flow: &'static str,
+    calls: Vec<Call>,
+) -> Result<RelayerSubmitResult> {
+    ensure_relayer_access(state, user_address).await?;
+    let submitted = relayer.submit_calls(calls).await?;
+    record_relayer_spend(state, user_address, flow, &submitted);
+    Ok(submitted)
+}
+
+/// Lists relayer gas spend per user, highest spenders first, with the total group count.
+pub async fn list_spend(
+    state: &AppState,
+    user_address: Option<&str>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<RelayerSpendSummary>, i64)> {
+    let filters = "($1::TEXT IS NULL OR user_address = $1)
+           AND ($2::TIMESTAMPTZ IS NULL OR created_at >= $2)
+           AND ($3::TIMESTAMPTZ IS NULL OR created_at <= $3)";
+
+    let items = sqlx::query_as::<_, RelayerSpendSummary>(&format!(
+        "SELECT user_address, fee_unit, COUNT(*) AS submissions,
+                SUM(fee_amount)::TEXT AS total_fee, MAX(created_at) AS last_submitted_at
+         FROM relayer_gas_spend
+         WHERE {}
+         GROUP BY user_address, fee_unit
+         ORDER BY SUM(fee_amount) DESC, user_address
+         LIMIT $4 OFFSET $5",
+        filters
+    ))
+    .bind(user_address)
+    .bind(from)
+    .bind(to)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let total: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM (
+             SELECT 1 FROM relayer_gas_spend WHERE {} GROUP BY user_address, fee_unit
+         ) groups",
+        filters
+    ))
+    .bind(user_address)
+    .bind(from)
+    .bind(to)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((items, total))
+}
+
+// Internal helper that checks conditions for `relayer_access_allowed`.
+fn relayer_access_allowed(allowlist: &str, min_level: u8, user_address: &str, level: u8) -> bool {
+    is_bypass_address(allowlist, user_address) || (min_level > 0 && level >= min_level)
+}
+
+// Internal helper that supports `enforce_relayer_rate_limit` operations.
+async fn enforce_relayer_rate_limit(state: &AppState, user_address: &str) -> Result<()> {
+    let limit = state.config.relayer_rate_limit_per_hour;
+    if limit == 0 || !state.redis_available() {
+        return Ok(());
+    }
+    let bucket = Utc::now().timestamp() / RELAYER_RATE_WINDOW_SECS;
+    let key = format!(
+        "relayer:submit:{}:{}",
+        user_address.trim().to_ascii_lowercase(),
+        bucket
+    );
+    let mut conn = state.redis.clone();
+    let count = match conn.incr::<_, _, i64>(&key, 1_i64).await {
+        Ok(count) => count,
+        Err(err) => {
+            tracing::warn!("Relayer rate limiter Redis incr failed: {}", err);
+            state.set_redis_available(false);
+            return Ok(());
+        }
+    };
+    if count == 1 {
+        let _: std::result::Result<bool, redis::RedisError> =
+            conn.expire(&key, RELAYER_RATE_WINDOW_SECS).await;
+    }
+    if count > i64::from(limit) {
+        return Err(AppError::RateLimitExceeded);
+    }
+    Ok(())
+}
+
+// Internal helper that records relayer gas spend in the background.
+fn record_relayer_spend(
+    state: &AppState,
+    user_address: &str,
+    flow: &'static str,
+    submitted: &RelayerSubmitResult,
+) {
+    let db = state.db.clone();
+    let user_address = user_address.to_string();
+    let submitted = submitted.clone();
+    tokio::spawn(async move {
+        let inserted = sqlx::query(
+            "INSERT INTO relayer_gas_spend (user_address, flow, tx_hash, fee_amount, fee_unit)
+             VALUES ($1, $2, $3, $4::NUMERIC, $5)",
+        )
+        .bind(&user_address)
+        .bind(flow)
+        .bind(&submitted.tx_hash)
+        .bind(&submitted.actual_fee)
+        .bind(submitted.fee_unit)
+        .execute(db.pool())
+        .await;
+        if let Err(err) = inserted {
+            tracing::warn!("Failed to record relayer gas spend: {}", err);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that checks conditions for `relayer_access_allowed_by_address_or_level`.
+    fn relayer_access_allowed_by_address_or_level() {
+        assert!(relayer_access_allowed("0x0ABC", 0, "0xabc", 0));
+        assert!(!relayer_access_allowed("0xabc", 0, "0xdef", 3));
+        assert!(relayer_access_allowed("", 2, "0xdef", 2));
+        assert!(!relayer_access_allowed("", 2, "0xdef", 1));
+        assert!(relayer_access_allowed("0xabc", 3, "0xdef", 3));
+    }
+}
@@ -16,10 +16,10 @@ use crate::tokenomics::{
     rewards_distribution_pool_for_environment, BPS_DENOM, CLAIM_FEE_BPS, CLAIM_FEE_DEV_BPS,
     CLAIM_FEE_MANAGEMENT_BPS,
 };
-use crate::{constants::EPOCH_DURATION_SECONDS, error::Result, models::ApiResponse};
+use crate::{error::Result, models::ApiResponse};
 
 use super::{require_user, resolve_user_scope_addresses, AppState};
-use crate::error::AppError;
+use crate::error::{AppError, ErrorCode};
 use crate::indexer::starknet_client::StarknetClient;
 use sqlx::FromRow;
 use starknet_core::types::Felt;
@@ -404,7 +404,7 @@ pub async fn get_points(
     headers: HeaderMap,
 ) -> Result<Json<ApiResponse<PointsResponse>>> {
     let user_addresses = resolve_user_scope_addresses(&headers, &state).await?;
-    let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS; // ~30 days
+    let current_epoch = state.config.current_rewards_epoch();
     let cache_key = points_response_cache_key(&user_addresses, current_epoch);
     if let Some(cached) = get_cached_points_response(
         &cache_key,
@@ -531,11 +531,7 @@ async fn build_points_response(
 
     let distribution_mode = distribution_mode_for_environment(&state.config.environment);
     let distribution_pool = rewards_distribution_pool_for_environment(&state.config.environment);
-    let global_epoch_points: Decimal =
-        sqlx::query_scalar("SELECT COALESCE(SUM(total_points), 0) FROM points WHERE epoch = $1")
-            .bind(current_epoch)
-            .fetch_one(state.db.pool())
-            .await?;
+    let global_epoch_points = state.db.reward_points_total(current_epoch).await?;
     let estimated_reward_carel =
         (calculate_epoch_reward(points.total_points, global_epoch_points, distribution_pool)
             * claim_fee_multiplier())
@@ -575,16 +571,19 @@ pub async fn sync_points_onchain(
 ) -> Result<Json<ApiResponse<SyncOnchainPointsResponse>>> {
     let user_addresses = resolve_user_scope_addresses(&headers, &state).await?;
     let starknet_user = super::require_starknet_user(&headers, &state).await?;
-    let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+    let current_epoch = state.config.current_rewards_epoch();
     let points = aggregate_points_for_scope(&state, &user_addresses, current_epoch).await?;
     let offchain_points = points.total_points.max(Decimal::ZERO).trunc();
     let offchain_points_u128 = offchain_points.to_u128().unwrap_or(0);
     let required_points_u128 = req.minimum_points.unwrap_or(0.0).max(0.0).floor() as u128;
     if required_points_u128 > offchain_points_u128 {
-        return Err(AppError::BadRequest(format!(
-            "Points backend belum cukup: required={} available={}",
-            required_points_u128, offchain_points_u128
-        )));
+        return Err(AppError::Coded(
+            ErrorCode::InsufficientPoints,
+            format!(
+                "Not enough backend points: required={} available={}",
+                required_points_u128, offchain_points_u128
+            ),
+        ));
     }
 
     let contract = configured_point_storage_contract(&state).ok_or_else(|| {
@@ -654,10 +653,13 @@ pub async fn sync_points_onchain(
     }
 
     if onchain_after < required_points_u128 {
-        return Err(AppError::BadRequest(format!(
-            "On-chain points belum cukup untuk mint: required={} onchain={}",
-            required_points_u128, onchain_after
-        )));
+        return Err(AppError::Coded(
+            ErrorCode::InsufficientPoints,
+            format!(
+                "Not enough on-chain points to mint: required={} onchain={}",
+                required_points_u128, onchain_after
+            ),
+        ));
     }
 
     let response = SyncOnchainPointsResponse {
@@ -682,7 +684,7 @@ pub async fn claim_rewards(
     let user_address = require_user(&headers, &state).await?;
 
     // Get previous epoch (finalized)
-    let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+    let current_epoch = state.config.current_rewards_epoch();
     let prev_epoch = current_epoch - 1;
 
     // Get user points from previous epoch
@@ -699,41 +701,54 @@ pub async fn claim_rewards(
         ));
     }
 
+    // Take the claim first so the same points cannot be spent on an NFT mint meanwhile.
+    // It stays uncommitted until the payout succeeds; any early return rolls it back.
+    let claim = state
+        .db
+        .begin_rewards_claim(&user_address, prev_epoch)
+        .await?
+        .ok_or_else(|| {
+            crate::error::AppError::BadRequest(
+                "Rewards for this epoch were already claimed".to_string(),
+            )
+        })?;
+    let claim_points = claim.points;
+
     // Calculate CAREL amount based on monthly ecosystem pool
-    let total_points_epoch: rust_decimal::Decimal =
-        sqlx::query_scalar("SELECT COALESCE(SUM(total_points), 0) FROM points WHERE epoch = $1")
-            .bind(prev_epoch)
-            .fetch_one(state.db.pool())
-            .await?;
+    let total_points_epoch = state.db.reward_points_total(prev_epoch).await?;
 
     let total_distribution = resolve_total_distribution(&state, None).await?;
     let carel_amount_dec =
-        calculate_epoch_reward(points.total_points, total_points_epoch, total_distribution);
+        calculate_epoch_reward(claim_points, total_points_epoch, total_distribution);
     let net_carel_dec = carel_amount_dec * claim_fee_multiplier();
     let carel_amount = net_carel_dec.to_f64().unwrap_or(0.0);
-    let total_points: f64 = points.total_points.to_string().parse().unwrap_or(0.0);
+    let total_points: f64 = claim_points.to_string().parse().unwrap_or(0.0);
 
     let mut tx_hash = format!("0x{}", hex::encode(rand::random::<[u8; 32]>()));
     let mut carel_amount_out = carel_amount;
 
-    match claim_rewards_onchain(
+    if let Some((onchain_tx, net_amount)) = claim_rewards_onchain(
         &state,
         prev_epoch,
         &user_address,
-        points.total_points,
+        claim_points,
         total_points_epoch,
         total_distribution,
     )
-    .await
+    .await?
     {
-        Ok(Some((onchain_tx, net_amount))) => {
-            tx_hash = onchain_tx;
-            carel_amount_out = net_amount.to_f64().unwrap_or(carel_amount);
-        }
-        Ok(None) => {}
-        Err(err) => {
-            return Err(err);
-        }
+        tx_hash = onchain_tx;
+        carel_amount_out = net_amount.to_f64().unwrap_or(carel_amount);
+    }
+    if let Err(err) = claim.commit().await {
+        tracing::error!(
+            "Rewards paid but claim commit failed: user={} epoch={} tx_hash={} err={}",
+            user_address,
+            prev_epoch,
+            tx_hash,
+            err
+        );
+        return Err(err);
     }
 
     tracing::info!(
@@ -760,7 +775,7 @@ pub async fn convert_to_carel(
 ) -> Result<Json<ApiResponse<ClaimResponse>>> {
     let user_address = require_user(&headers, &state).await?;
 
-    let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+    let current_epoch = state.config.current_rewards_epoch();
     let epoch = req.epoch.unwrap_or(current_epoch);
     if epoch < 0 {
         return Err(AppError::BadRequest("Invalid epoch".into()));
@@ -789,11 +804,7 @@ pub async fn convert_to_carel(
             .unwrap_or(Decimal::ZERO)
     };
 
-    let total_points_epoch: Decimal =
-        sqlx::query_scalar("SELECT COALESCE(SUM(total_points), 0) FROM points WHERE epoch = $1")
-            .bind(epoch)
-            .fetch_one(state.db.pool())
-            .await?;
+    let total_points_epoch = state.db.reward_points_total(epoch).await?;
 
     let total_distribution =
         resolve_total_distribution(&state, req.total_distribution_carel).await?;
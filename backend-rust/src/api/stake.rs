@@ -5,28 +5,35 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::collections::{HashMap, HashSet};
 
-use crate::services::onchain::{felt_to_u128, parse_felt, u256_from_felts, OnchainReader};
+use crate::services::onchain::{
+    felt_to_u128, parse_felt, u256_from_felts, OnchainError, OnchainReader,
+};
 use crate::{
     constants::{
-        token_address_for, EPOCH_DURATION_SECONDS, POINTS_MIN_STAKE_BTC,
-        POINTS_MIN_STAKE_BTC_TESTNET, POINTS_MIN_STAKE_CAREL, POINTS_MIN_STAKE_LP,
-        POINTS_MIN_STAKE_LP_TESTNET, POINTS_MIN_STAKE_STABLECOIN,
-        POINTS_MIN_STAKE_STABLECOIN_TESTNET, POINTS_MIN_STAKE_STRK, POINTS_MIN_STAKE_STRK_TESTNET,
-        POINTS_MULTIPLIER_STAKE_BTC, POINTS_MULTIPLIER_STAKE_CAREL_TIER_1,
-        POINTS_MULTIPLIER_STAKE_CAREL_TIER_2, POINTS_MULTIPLIER_STAKE_CAREL_TIER_3,
-        POINTS_MULTIPLIER_STAKE_LP, POINTS_MULTIPLIER_STAKE_STABLECOIN, POINTS_PER_USD_STAKE,
+        token_address_for, POINTS_MIN_STAKE_BTC, POINTS_MIN_STAKE_BTC_TESTNET,
+        POINTS_MIN_STAKE_CAREL, POINTS_MIN_STAKE_LP, POINTS_MIN_STAKE_LP_TESTNET,
+        POINTS_MIN_STAKE_STABLECOIN, POINTS_MIN_STAKE_STABLECOIN_TESTNET, POINTS_MIN_STAKE_STRK,
+        POINTS_MIN_STAKE_STRK_TESTNET, POINTS_MULTIPLIER_STAKE_BTC,
+        POINTS_MULTIPLIER_STAKE_CAREL_TIER_1, POINTS_MULTIPLIER_STAKE_CAREL_TIER_2,
+        POINTS_MULTIPLIER_STAKE_CAREL_TIER_3, POINTS_MULTIPLIER_STAKE_LP,
+        POINTS_MULTIPLIER_STAKE_STABLECOIN, POINTS_PER_USD_STAKE,
     },
     // 1. Import hasher agar fungsi di hash.rs terhitung "used"
     crypto::hash,
     error::Result,
     models::{user::PrivacyVerificationPayload as ModelPrivacyVerificationPayload, ApiResponse},
+    services::hide_executor::{
+        hide_executor_kind, shielded_current_root, shielded_note_deposit_timestamp,
+        shielded_note_registered, HideExecutorKind,
+    },
     services::nft_discount::{consume_nft_usage_if_active, read_active_discount_rate},
     services::price_guard::{
         fallback_price_for, first_sane_price, sanitize_points_usd_base, sanitize_usd_notional,
         symbol_candidates_for,
     },
-    services::privacy_verifier::parse_privacy_verifier_kind,
+    services::privacy_verifier::select_privacy_verifier,
     services::relayer::RelayerService,
+    services::staking_discount::fetch_carel_stake_info,
 };
 use starknet_core::types::{Call, Felt, FunctionCall};
 use starknet_core::utils::get_selector_from_name;
@@ -41,9 +48,14 @@ use super::{
         bind_intent_hash_into_payload, ensure_public_inputs_bind_nullifier_commitment,
         ensure_public_inputs_bind_root_nullifier, ensure_public_inputs_bind_v3_shape,
         generate_auto_garaga_payload, AutoPrivacyPayloadResponse, AutoPrivacyTxContext,
+        PrivacyPayloadLimits,
     },
+    relayer_access::submit_relayer_calls,
     require_starknet_user, require_user,
-    swap::{parse_decimal_to_u256_parts, token_decimals},
+    swap::{
+        ensure_note_mixing_window_elapsed, hide_deposit_requires_asset_rule,
+        parse_decimal_to_u256_parts, token_decimals,
+    },
     AppState,
 };
 use tokio::time::{sleep, timeout, Duration};
@@ -302,23 +314,12 @@ fn discount_contract_address(state: &AppState) -> Option<&str> {
         .filter(|addr| !addr.trim().is_empty() && !addr.starts_with("0x0000"))
 }
 
-// Internal helper that supports `current_nft_period_epoch` operations.
-fn current_nft_period_epoch() -> i64 {
-    let now = chrono::Utc::now().timestamp();
-    let period = (EPOCH_DURATION_SECONDS as i64).max(1);
-    if now <= 0 {
-        0
-    } else {
-        now / period
-    }
-}
-
 // Internal helper that fetches data for `fallback_nft_discount_from_local_state`.
 async fn fallback_nft_discount_from_local_state(state: &AppState, user_address: &str) -> f64 {
     let Some(contract) = discount_contract_address(state) else {
         return 0.0;
     };
-    let period_epoch = current_nft_period_epoch();
+    let period_epoch = state.config.current_nft_period_epoch();
     match state
         .db
         .get_nft_discount_state(contract, user_address, period_epoch)
@@ -614,37 +615,6 @@ fn hide_balance_v2_redeem_only_enabled() -> bool {
     env_flag("HIDE_BALANCE_V2_REDEEM_ONLY", false)
 }
 
-fn hide_balance_min_note_age_secs() -> u64 {
-    std::env::var("HIDE_BALANCE_MIN_NOTE_AGE_SECS")
-        .or_else(|_| std::env::var("NEXT_PUBLIC_HIDE_BALANCE_MIN_NOTE_AGE_SECS"))
-        .ok()
-        .and_then(|value| value.trim().parse::<u64>().ok())
-        .filter(|value| *value > 0)
-        .unwrap_or(60)
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum HideExecutorKind {
-    PrivateActionExecutorV1,
-    ShieldedPoolV2,
-    ShieldedPoolV3,
-}
-
-// Internal helper that supports `hide_executor_kind` operations.
-fn hide_executor_kind() -> HideExecutorKind {
-    let raw = std::env::var("HIDE_BALANCE_EXECUTOR_KIND")
-        .unwrap_or_default()
-        .trim()
-        .to_ascii_lowercase();
-    if matches!(raw.as_str(), "shielded_pool_v3" | "shielded-v3" | "v3") {
-        HideExecutorKind::ShieldedPoolV3
-    } else if matches!(raw.as_str(), "shielded_pool_v2" | "shielded-v2" | "v2") {
-        HideExecutorKind::ShieldedPoolV2
-    } else {
-        HideExecutorKind::PrivateActionExecutorV1
-    }
-}
-
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum HidePoolVersion {
     V2,
@@ -719,44 +689,6 @@ fn resolve_private_action_executor_candidates(config: &crate::config::Config) ->
     ))
 }
 
-fn is_missing_entrypoint_error(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    if lower.contains("entry_point_not_found") || lower.contains("entrypoint_not_found") {
-        return true;
-    }
-    let mentions_entrypoint =
-        lower.contains("entrypoint") || lower.contains("entry point") || lower.contains("selector");
-    let mentions_missing = lower.contains("does not exist")
-        || lower.contains("not found")
-        || lower.contains("missing");
-    mentions_entrypoint && mentions_missing
-}
-
-fn is_transient_probe_error(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    lower.contains("timeout")
-        || lower.contains("timed out")
-        || lower.contains("too many requests")
-        || lower.contains("429")
-        || lower.contains("gateway")
-        || lower.contains("temporarily unavailable")
-        || lower.contains("invalid peer certificate")
-        || lower.contains("unknownissuer")
-        || lower.contains("connection reset")
-        || lower.contains("network")
-}
-
-fn is_contract_revert_probe_error(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    lower.contains("contracterror")
-        || lower.contains("contract error")
-        || lower.contains("revert_error")
-        || lower.contains("execution_error")
-        || lower.contains("innercontractexecutionerror")
-        || lower.contains("sender required")
-        || lower.contains("failed to deserialize param")
-}
-
 async fn contract_supports_selector(
     state: &AppState,
     contract: Felt,
@@ -764,7 +696,7 @@ async fn contract_supports_selector(
 ) -> Result<bool> {
     let reader = OnchainReader::from_config(&state.config)?;
     let probe = reader
-        .call(FunctionCall {
+        .call_typed(FunctionCall {
             contract_address: contract,
             entry_point_selector: selector,
             calldata: vec![Felt::ONE],
@@ -772,24 +704,18 @@ async fn contract_supports_selector(
         .await;
     match probe {
         Ok(_) => Ok(true),
-        Err(crate::error::AppError::BlockchainRPC(message)) => {
-            if is_missing_entrypoint_error(&message) {
-                Ok(false)
-            } else if is_contract_revert_probe_error(&message) {
-                Ok(true)
-            } else if is_transient_probe_error(&message) {
-                Err(crate::error::AppError::BlockchainRPC(format!(
-                    "Failed to probe selector support on {}: {}",
-                    contract, message
-                )))
-            } else {
-                Err(crate::error::AppError::BlockchainRPC(format!(
-                    "Unexpected selector probe error on {}: {}",
-                    contract, message
-                )))
-            }
+        Err(OnchainError::EntrypointNotFound(_)) => Ok(false),
+        Err(OnchainError::ContractRevert(_)) => Ok(true),
+        Err(err @ (OnchainError::RateLimited(_) | OnchainError::Network(_))) => {
+            Err(crate::error::AppError::BlockchainRPC(format!(
+                "Failed to probe selector support on {}: {}",
+                contract, err
+            )))
         }
-        Err(err) => Err(err),
+        Err(OnchainError::Other(message)) => Err(crate::error::AppError::BlockchainRPC(format!(
+            "Unexpected selector probe error on {}: {}",
+            contract, message
+        ))),
     }
 }
 
@@ -810,7 +736,7 @@ async fn executor_supports_selectors(
 
 async fn resolve_private_action_executor_felt_for_stake_hide(state: &AppState) -> Result<Felt> {
     let candidates = resolve_private_action_executor_candidates(&state.config)?;
-    let required_selectors: &[&str] = match hide_executor_kind() {
+    let required_selectors: &[&str] = match hide_executor_kind()? {
         HideExecutorKind::PrivateActionExecutorV1 => &[
             "preview_stake_intent_hash",
             "submit_private_intent",
@@ -836,7 +762,7 @@ async fn resolve_private_action_executor_felt_for_stake_hide(state: &AppState) -
             tracing::info!(
                 "Using compatible stake hide executor {} (kind={:?})",
                 candidate,
-                hide_executor_kind()
+                hide_executor_kind()?
             );
             return Ok(candidate);
         }
@@ -845,7 +771,7 @@ async fn resolve_private_action_executor_felt_for_stake_hide(state: &AppState) -
 
     Err(crate::error::AppError::BadRequest(format!(
         "No compatible hide executor found for stake flow (kind={:?}). Checked: {}",
-        hide_executor_kind(),
+        hide_executor_kind()?,
         unsupported.join(", ")
     )))
 }
@@ -950,7 +876,7 @@ fn build_stake_action(
     let token = pool_token.trim().to_ascii_uppercase();
 
     let shielded_mode = matches!(
-        hide_executor_kind(),
+        hide_executor_kind()?,
         HideExecutorKind::ShieldedPoolV2 | HideExecutorKind::ShieldedPoolV3
     );
 
@@ -1023,12 +949,20 @@ fn build_stake_action(
 }
 
 // Internal helper that parses or transforms values for `normalize_hex_items`.
-fn normalize_hex_items(items: &[String]) -> Vec<String> {
+fn normalize_hex_items(items: &[String], field: &str) -> Result<Vec<String>> {
     items
         .iter()
         .map(|item| item.trim())
-        .filter(|item| !item.is_empty())
-        .map(ToOwned::to_owned)
+        .enumerate()
+        .filter(|(_, item)| !item.is_empty())
+        .map(|(index, item)| {
+            parse_felt(item).map(|_| item.to_owned()).map_err(|_| {
+                crate::error::AppError::BadRequest(format!(
+                    "privacy.{}[{}] is not a valid felt: {}",
+                    field, index, item
+                ))
+            })
+        })
         .collect()
 }
 
@@ -1036,11 +970,16 @@ fn normalize_hex_items(items: &[String]) -> Vec<String> {
 fn payload_from_request(
     payload: Option<&ModelPrivacyVerificationPayload>,
     verifier: &str,
-) -> Option<AutoPrivacyPayloadResponse> {
-    let payload = payload?;
-    let nullifier = payload.nullifier.as_deref()?.trim();
+    limits: PrivacyPayloadLimits,
+) -> Result<Option<AutoPrivacyPayloadResponse>> {
+    let Some(payload) = payload else {
+        return Ok(None);
+    };
+    let Some(nullifier) = payload.nullifier.as_deref().map(str::trim) else {
+        return Ok(None);
+    };
     if nullifier.is_empty() {
-        return None;
+        return Ok(None);
     }
     let commitment = payload
         .commitment
@@ -1048,19 +987,25 @@ fn payload_from_request(
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .unwrap_or("0x0");
-    let proof = normalize_hex_items(payload.proof.as_ref()?);
-    let public_inputs = normalize_hex_items(payload.public_inputs.as_ref()?);
+    let (Some(raw_proof), Some(raw_public_inputs)) =
+        (payload.proof.as_ref(), payload.public_inputs.as_ref())
+    else {
+        return Ok(None);
+    };
+    limits.ensure(raw_proof.len(), raw_public_inputs.len())?;
+    let proof = normalize_hex_items(raw_proof, "proof")?;
+    let public_inputs = normalize_hex_items(raw_public_inputs, "public_inputs")?;
     if proof.is_empty() || public_inputs.is_empty() {
-        return None;
+        return Ok(None);
     }
     if proof.len() == 1
         && public_inputs.len() == 1
         && proof[0].eq_ignore_ascii_case("0x1")
         && public_inputs[0].eq_ignore_ascii_case("0x1")
     {
-        return None;
+        return Ok(None);
     }
-    Some(AutoPrivacyPayloadResponse {
+    Ok(Some(AutoPrivacyPayloadResponse {
         verifier: payload
             .verifier
             .as_deref()
@@ -1098,7 +1043,7 @@ fn payload_from_request(
         spendable_at_unix: payload.spendable_at_unix,
         proof,
         public_inputs,
-    })
+    }))
 }
 
 struct StakeActionCallInput<'a> {
@@ -1120,7 +1065,7 @@ async fn compute_stake_intent_hash_on_executor(
     input: &StakeActionCallInput<'_>,
 ) -> Result<(String, StakeExecuteMode)> {
     let reader = OnchainReader::from_config(&state.config)?;
-    if hide_executor_kind() == HideExecutorKind::ShieldedPoolV2 {
+    if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV2 {
         let selector = get_selector_from_name("preview_stake_action_hash")
             .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
         let mut calldata: Vec<Felt> = Vec::with_capacity(5 + input.action_calldata.len());
@@ -1144,7 +1089,7 @@ async fn compute_stake_intent_hash_on_executor(
         })?;
         return Ok((intent_hash.to_string(), StakeExecuteMode::ShieldedPoolV2));
     }
-    if hide_executor_kind() == HideExecutorKind::ShieldedPoolV3 {
+    if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV3 {
         let selector = get_selector_from_name("preview_stake_action_hash")
             .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
         let mut calldata: Vec<Felt> = Vec::with_capacity(10 + input.action_calldata.len());
@@ -1277,7 +1222,7 @@ fn build_submit_private_intent_call(
     executor: Felt,
     payload: &AutoPrivacyPayloadResponse,
 ) -> Result<Call> {
-    let kind = hide_executor_kind();
+    let kind = hide_executor_kind()?;
     let selector_name = match kind {
         HideExecutorKind::PrivateActionExecutorV1 => "submit_private_intent",
         HideExecutorKind::ShieldedPoolV2 => "submit_private_action",
@@ -1419,70 +1364,6 @@ fn build_shielded_deposit_fixed_for_call(
     })
 }
 
-// Internal helper that supports `shielded_note_registered` operations.
-async fn shielded_note_registered(
-    state: &AppState,
-    executor: Felt,
-    note_commitment: Felt,
-) -> Result<bool> {
-    let reader = OnchainReader::from_config(&state.config)?;
-    let selector = get_selector_from_name("is_note_registered")
-        .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
-    let out = reader
-        .call(FunctionCall {
-            contract_address: executor,
-            entry_point_selector: selector,
-            calldata: vec![note_commitment],
-        })
-        .await?;
-    let flag = out.first().copied().unwrap_or(Felt::ZERO);
-    Ok(flag != Felt::ZERO)
-}
-
-async fn shielded_note_deposit_timestamp(
-    state: &AppState,
-    executor: Felt,
-    note_commitment: Felt,
-) -> Result<u64> {
-    let reader = OnchainReader::from_config(&state.config)?;
-    let selector = get_selector_from_name("get_note_deposit_timestamp")
-        .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
-    let out = reader
-        .call(FunctionCall {
-            contract_address: executor,
-            entry_point_selector: selector,
-            calldata: vec![note_commitment],
-        })
-        .await?;
-    let raw = out.first().copied().unwrap_or(Felt::ZERO);
-    let value = felt_to_u128(&raw).map_err(|_| {
-        crate::error::AppError::BadRequest(
-            "Invalid note timestamp returned by shielded pool".to_string(),
-        )
-    })?;
-    Ok(value as u64)
-}
-
-async fn shielded_current_root(state: &AppState, executor: Felt) -> Result<Felt> {
-    let reader = OnchainReader::from_config(&state.config)?;
-    let selector = get_selector_from_name("get_root")
-        .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
-    let out = reader
-        .call(FunctionCall {
-            contract_address: executor,
-            entry_point_selector: selector,
-            calldata: vec![],
-        })
-        .await?;
-    let root = out.first().copied().unwrap_or(Felt::ZERO);
-    if root == Felt::ZERO {
-        return Err(crate::error::AppError::BadRequest(
-            "ShieldedPoolV3 root is not initialized yet (get_root=0).".to_string(),
-        ));
-    }
-    Ok(root)
-}
-
 // Internal helper that supports `shielded_fixed_amount` operations.
 async fn shielded_fixed_amount(
     state: &AppState,
@@ -1649,20 +1530,22 @@ async fn append_shielded_note_registration_calls(
             "ShieldedPoolV2 requires non-zero note amount".to_string(),
         ));
     }
-    let note_registered = shielded_note_registered(state, input.executor, input.commitment).await?;
+    let note_registered =
+        shielded_note_registered(&state.config, input.executor, input.commitment).await?;
     if note_registered {
         return Ok(());
     }
     if hide_balance_v2_redeem_only_enabled() {
-        return Err(crate::error::AppError::BadRequest(
-            "Hide Balance V2 is redeem-only. New note deposits to V2 are blocked; use V3 for new notes."
-                .to_string(),
-        ));
+        return Err(crate::error::ErrorCode::HideV2RedeemOnly.into());
     }
 
-    let (fixed_low, fixed_high) =
-        shielded_fixed_amount(state, input.executor, input.note_token).await?;
-    if fixed_low != input.amount_low || fixed_high != input.amount_high {
+    let fixed_amount = shielded_fixed_amount(state, input.executor, input.note_token).await?;
+    if hide_deposit_requires_asset_rule(
+        &state.config,
+        input.symbol,
+        (input.amount_low, input.amount_high),
+        fixed_amount,
+    )? {
         relayer_calls.push(build_shielded_set_asset_rule_call(
             input.executor,
             input.note_token,
@@ -2061,7 +1944,7 @@ pub async fn deposit(
         None
     };
     if should_hide {
-        match (hide_executor_kind(), hide_pool_version) {
+        match (hide_executor_kind()?, hide_pool_version) {
             (HideExecutorKind::ShieldedPoolV3, Some(HidePoolVersion::V2)) => {
                 return Err(crate::error::AppError::BadRequest(
                     "Hide Balance config mismatch: executor is V3 but payload/version resolved to V2."
@@ -2071,7 +1954,7 @@ pub async fn deposit(
             (HideExecutorKind::ShieldedPoolV2, Some(HidePoolVersion::V3))
             | (HideExecutorKind::PrivateActionExecutorV1, Some(HidePoolVersion::V3)) => {
                 return Err(crate::error::AppError::BadRequest(
-                    "Hide Balance V3 requires HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3."
+                    "Hide Balance V3 requires a ShieldedPoolV3 executor (auto-detected, or HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3)."
                         .to_string(),
                 ));
             }
@@ -2084,7 +1967,8 @@ pub async fn deposit(
 
     let tx_hash = if use_relayer_pool_hide {
         let executor = resolve_private_action_executor_felt_for_stake_hide(&state).await?;
-        let verifier_kind = parse_privacy_verifier_kind(
+        let verifier_kind = select_privacy_verifier(
+            &state.config,
             req.privacy
                 .as_ref()
                 .and_then(|payload| payload.verifier.as_deref()),
@@ -2117,7 +2001,11 @@ pub async fn deposit(
             );
             None
         } else {
-            payload_from_request(req.privacy.as_ref(), verifier_kind.as_str())
+            payload_from_request(
+                req.privacy.as_ref(),
+                verifier_kind.as_str(),
+                PrivacyPayloadLimits::from_config(&state.config),
+            )?
         };
         let payload_from_auto = request_payload.is_none();
         let mut payload = if let Some(request_payload) = request_payload {
@@ -2163,7 +2051,7 @@ pub async fn deposit(
         let (intent_hash, execute_mode) =
             compute_stake_intent_hash_on_executor(&state, executor, &stake_input).await?;
         if hide_pool_version == Some(HidePoolVersion::V3) && payload_from_auto {
-            let root = shielded_current_root(&state, executor).await?;
+            let root = shielded_current_root(&state.config, executor).await?;
             let tx_context = AutoPrivacyTxContext {
                 flow: Some("stake".to_string()),
                 from_token: Some(pool_token.to_string()),
@@ -2252,23 +2140,20 @@ pub async fn deposit(
                 })?;
             let note_commitment_felt = parse_felt(note_commitment_raw.trim())?;
             let deposit_ts =
-                shielded_note_deposit_timestamp(&state, executor, note_commitment_felt).await?;
+                shielded_note_deposit_timestamp(&state.config, executor, note_commitment_felt)
+                    .await?;
             if deposit_ts == 0 {
-                return Err(crate::error::AppError::BadRequest(
-                    "Hide Balance V3 note is not registered yet. Deposit the note first."
-                        .to_string(),
-                ));
+                return Err(crate::error::ErrorCode::HideNoteNotRegistered.into());
             }
-            payload.spendable_at_unix =
-                Some(deposit_ts.saturating_add(hide_balance_min_note_age_secs()));
-        } else if hide_executor_kind() == HideExecutorKind::ShieldedPoolV2 {
+            payload.spendable_at_unix = Some(ensure_note_mixing_window_elapsed(deposit_ts)?);
+        } else if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV2 {
             let commitment_felt = parse_felt(payload.commitment.trim())?;
             let user_felt = parse_felt(&user_address)?;
             let (note_amount_low, note_amount_high) =
                 parse_decimal_to_u256_parts(&req.amount, token_decimals(pool_token))?;
             if strict_privacy_mode {
                 let note_registered =
-                    shielded_note_registered(&state, executor, commitment_felt).await?;
+                    shielded_note_registered(&state.config, executor, commitment_felt).await?;
                 if !note_registered {
                     return Err(crate::error::AppError::BadRequest(
                         "Hide Balance strict mode blocks inline deposit+execute in one tx. Pre-fund shielded note first."
@@ -2317,7 +2202,14 @@ pub async fn deposit(
             build_execute_private_stake_call(executor, &payload, &stake_input, execute_mode)?;
         relayer_calls.push(submit_call);
         relayer_calls.push(execute_call);
-        let submitted = relayer.submit_calls(relayer_calls).await?;
+        let submitted = submit_relayer_calls(
+            &state,
+            &relayer,
+            &user_address,
+            "hide_stake_deposit",
+            relayer_calls,
+        )
+        .await?;
         submitted.tx_hash
     } else {
         let auth_subject = require_user(&headers, &state).await?;
@@ -2460,7 +2352,7 @@ pub async fn withdraw(
         None
     };
     if should_hide {
-        match (hide_executor_kind(), hide_pool_version) {
+        match (hide_executor_kind()?, hide_pool_version) {
             (HideExecutorKind::ShieldedPoolV3, Some(HidePoolVersion::V2)) => {
                 return Err(crate::error::AppError::BadRequest(
                     "Hide Balance config mismatch: executor is V3 but payload/version resolved to V2."
@@ -2470,7 +2362,7 @@ pub async fn withdraw(
             (HideExecutorKind::ShieldedPoolV2, Some(HidePoolVersion::V3))
             | (HideExecutorKind::PrivateActionExecutorV1, Some(HidePoolVersion::V3)) => {
                 return Err(crate::error::AppError::BadRequest(
-                    "Hide Balance V3 requires HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3."
+                    "Hide Balance V3 requires a ShieldedPoolV3 executor (auto-detected, or HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3)."
                         .to_string(),
                 ));
             }
@@ -2482,7 +2374,8 @@ pub async fn withdraw(
         should_hide && hide_balance_relayer_pool_enabled() && normalized_onchain_tx_hash.is_none();
     let tx_hash = if use_relayer_pool_hide {
         let executor = resolve_private_action_executor_felt_for_stake_hide(&state).await?;
-        let verifier_kind = parse_privacy_verifier_kind(
+        let verifier_kind = select_privacy_verifier(
+            &state.config,
             req.privacy
                 .as_ref()
                 .and_then(|payload| payload.verifier.as_deref()),
@@ -2510,7 +2403,11 @@ pub async fn withdraw(
             );
             None
         } else {
-            payload_from_request(req.privacy.as_ref(), verifier_kind.as_str())
+            payload_from_request(
+                req.privacy.as_ref(),
+                verifier_kind.as_str(),
+                PrivacyPayloadLimits::from_config(&state.config),
+            )?
         };
         let payload_from_auto = request_payload.is_none();
         let mut payload = if let Some(request_payload) = request_payload {
@@ -2556,7 +2453,7 @@ pub async fn withdraw(
         let (intent_hash, execute_mode) =
             compute_stake_intent_hash_on_executor(&state, executor, &stake_input).await?;
         if hide_pool_version == Some(HidePoolVersion::V3) && payload_from_auto {
-            let root = shielded_current_root(&state, executor).await?;
+            let root = shielded_current_root(&state.config, executor).await?;
             let tx_context = AutoPrivacyTxContext {
                 flow: Some("unstake".to_string()),
                 from_token: Some(pool_token.clone()),
@@ -2645,23 +2542,20 @@ pub async fn withdraw(
                 })?;
             let note_commitment_felt = parse_felt(note_commitment_raw.trim())?;
             let deposit_ts =
-                shielded_note_deposit_timestamp(&state, executor, note_commitment_felt).await?;
+                shielded_note_deposit_timestamp(&state.config, executor, note_commitment_felt)
+                    .await?;
             if deposit_ts == 0 {
-                return Err(crate::error::AppError::BadRequest(
-                    "Hide Balance V3 note is not registered yet. Deposit the note first."
-                        .to_string(),
-                ));
+                return Err(crate::error::ErrorCode::HideNoteNotRegistered.into());
             }
-            payload.spendable_at_unix =
-                Some(deposit_ts.saturating_add(hide_balance_min_note_age_secs()));
-        } else if hide_executor_kind() == HideExecutorKind::ShieldedPoolV2 {
+            payload.spendable_at_unix = Some(ensure_note_mixing_window_elapsed(deposit_ts)?);
+        } else if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV2 {
             let commitment_felt = parse_felt(payload.commitment.trim())?;
             let user_felt = parse_felt(&user_address)?;
             let (note_amount_low, note_amount_high) =
                 parse_decimal_to_u256_parts(&req.amount, token_decimals(&pool_token))?;
             if strict_privacy_mode {
                 let note_registered =
-                    shielded_note_registered(&state, executor, commitment_felt).await?;
+                    shielded_note_registered(&state.config, executor, commitment_felt).await?;
                 if !note_registered {
                     return Err(crate::error::AppError::BadRequest(
                         "Hide Balance strict mode blocks inline deposit+execute in one tx. Pre-fund shielded note first."
@@ -2687,7 +2581,14 @@ pub async fn withdraw(
             build_execute_private_stake_call(executor, &payload, &stake_input, execute_mode)?;
         relayer_calls.push(submit_call);
         relayer_calls.push(execute_call);
-        let submitted = relayer.submit_calls(relayer_calls).await?;
+        let submitted = submit_relayer_calls(
+            &state,
+            &relayer,
+            &user_address,
+            "hide_stake_withdraw",
+            relayer_calls,
+        )
+        .await?;
         submitted.tx_hash
     } else {
         let auth_subject = require_user(&headers, &state).await?;
@@ -2799,7 +2700,7 @@ pub async fn claim(
         None
     };
     if should_hide {
-        match (hide_executor_kind(), hide_pool_version) {
+        match (hide_executor_kind()?, hide_pool_version) {
             (HideExecutorKind::ShieldedPoolV3, Some(HidePoolVersion::V2)) => {
                 return Err(crate::error::AppError::BadRequest(
                     "Hide Balance config mismatch: executor is V3 but payload/version resolved to V2."
@@ -2809,7 +2710,7 @@ pub async fn claim(
             (HideExecutorKind::ShieldedPoolV2, Some(HidePoolVersion::V3))
             | (HideExecutorKind::PrivateActionExecutorV1, Some(HidePoolVersion::V3)) => {
                 return Err(crate::error::AppError::BadRequest(
-                    "Hide Balance V3 requires HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3."
+                    "Hide Balance V3 requires a ShieldedPoolV3 executor (auto-detected, or HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3)."
                         .to_string(),
                 ));
             }
@@ -2821,7 +2722,8 @@ pub async fn claim(
         should_hide && hide_balance_relayer_pool_enabled() && normalized_onchain_tx_hash.is_none();
     let tx_hash = if use_relayer_pool_hide {
         let executor = resolve_private_action_executor_felt_for_stake_hide(&state).await?;
-        let verifier_kind = parse_privacy_verifier_kind(
+        let verifier_kind = select_privacy_verifier(
+            &state.config,
             req.privacy
                 .as_ref()
                 .and_then(|payload| payload.verifier.as_deref()),
@@ -2849,7 +2751,11 @@ pub async fn claim(
             );
             None
         } else {
-            payload_from_request(req.privacy.as_ref(), verifier_kind.as_str())
+            payload_from_request(
+                req.privacy.as_ref(),
+                verifier_kind.as_str(),
+                PrivacyPayloadLimits::from_config(&state.config),
+            )?
         };
         let payload_from_auto = request_payload.is_none();
         let mut payload = if let Some(request_payload) = request_payload {
@@ -2894,7 +2800,7 @@ pub async fn claim(
         let (intent_hash, execute_mode) =
             compute_stake_intent_hash_on_executor(&state, executor, &stake_input).await?;
         if hide_pool_version == Some(HidePoolVersion::V3) && payload_from_auto {
-            let root = shielded_current_root(&state, executor).await?;
+            let root = shielded_current_root(&state.config, executor).await?;
             let tx_context = AutoPrivacyTxContext {
                 flow: Some("stake_claim".to_string()),
                 from_token: Some(pool_token.clone()),
@@ -2982,21 +2888,18 @@ pub async fn claim(
                 })?;
             let note_commitment_felt = parse_felt(note_commitment_raw.trim())?;
             let deposit_ts =
-                shielded_note_deposit_timestamp(&state, executor, note_commitment_felt).await?;
+                shielded_note_deposit_timestamp(&state.config, executor, note_commitment_felt)
+                    .await?;
             if deposit_ts == 0 {
-                return Err(crate::error::AppError::BadRequest(
-                    "Hide Balance V3 note is not registered yet. Deposit the note first."
-                        .to_string(),
-                ));
+                return Err(crate::error::ErrorCode::HideNoteNotRegistered.into());
             }
-            payload.spendable_at_unix =
-                Some(deposit_ts.saturating_add(hide_balance_min_note_age_secs()));
-        } else if hide_executor_kind() == HideExecutorKind::ShieldedPoolV2 {
+            payload.spendable_at_unix = Some(ensure_note_mixing_window_elapsed(deposit_ts)?);
+        } else if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV2 {
             let commitment_felt = parse_felt(payload.commitment.trim())?;
             let user_felt = parse_felt(&user_address)?;
             if strict_privacy_mode {
                 let note_registered =
-                    shielded_note_registered(&state, executor, commitment_felt).await?;
+                    shielded_note_registered(&state.config, executor, commitment_felt).await?;
                 if !note_registered {
                     return Err(crate::error::AppError::BadRequest(
                         "Hide Balance strict mode blocks inline deposit+execute in one tx. Pre-fund shielded note first."
@@ -3028,7 +2931,14 @@ pub async fn claim(
             build_execute_private_stake_call(executor, &payload, &stake_input, execute_mode)?;
         relayer_calls.push(submit_call);
         relayer_calls.push(execute_call);
-        let submitted = relayer.submit_calls(relayer_calls).await?;
+        let submitted = submit_relayer_calls(
+            &state,
+            &relayer,
+            &user_address,
+            "hide_stake_claim",
+            relayer_calls,
+        )
+        .await?;
         submitted.tx_hash
     } else {
         let auth_subject = require_user(&headers, &state).await?;
@@ -3231,35 +3141,6 @@ pub async fn get_positions(
     Ok(Json(ApiResponse::success(positions)))
 }
 
-struct CarelStakeInfo {
-    amount: u128,
-    start_time: u64,
-}
-
-// Internal helper that fetches data for `fetch_carel_stake_info`.
-async fn fetch_carel_stake_info(
-    reader: &OnchainReader,
-    contract: &str,
-    user_address: &str,
-) -> Result<Option<CarelStakeInfo>> {
-    let call = FunctionCall {
-        contract_address: parse_felt(contract)?,
-        entry_point_selector: get_selector_from_name("get_stake_info")
-            .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?,
-        calldata: vec![parse_felt(user_address)?],
-    };
-
-    let result = reader.call(call).await?;
-    if result.len() < 7 {
-        return Ok(None);
-    }
-
-    let amount = u256_from_felts(&result[0], &result[1])?;
-    let start_time = felt_to_u128(&result[3])? as u64;
-
-    Ok(Some(CarelStakeInfo { amount, start_time }))
-}
-
 // Internal helper that fetches data for `fetch_carel_rewards`.
 async fn fetch_carel_rewards(
     reader: &OnchainReader,
@@ -1,4 +1,5 @@
 use super::{
+    maintenance,
     onchain_privacy::{
         verify_onchain_hide_balance_invoke_tx, HideBalanceFlow,
         PrivacyVerificationPayload as OnchainPrivacyPayload,
@@ -6,106 +7,71 @@ use super::{
     privacy::{
         bind_intent_hash_into_payload, ensure_public_inputs_bind_nullifier_commitment,
         ensure_public_inputs_bind_root_nullifier, generate_auto_garaga_payload,
-        AutoPrivacyPayloadResponse, AutoPrivacyTxContext,
+        AutoPrivacyPayloadResponse, AutoPrivacyTxContext, PrivacyPayloadLimits,
     },
+    relayer_access::{ensure_relayer_access, submit_relayer_calls},
     require_starknet_user, require_user, AppState,
 };
-use crate::services::onchain::{felt_to_u128, parse_felt, u256_from_felts, OnchainReader};
+use crate::services::onchain::{felt_to_u128, parse_felt, OnchainError, OnchainReader};
 use crate::{
+    config::Config,
     constants::{
-        token_address_for, DEX_EKUBO, DEX_HAIKO, EPOCH_DURATION_SECONDS, POINTS_MIN_USD_SWAP,
-        POINTS_MIN_USD_SWAP_TESTNET, POINTS_PER_USD_SWAP,
+        token_address_for, DEX_EKUBO, DEX_HAIKO, POINTS_MIN_USD_SWAP, POINTS_MIN_USD_SWAP_TESTNET,
+        POINTS_PER_USD_SWAP,
     },
-    db::NftDiscountStateUpsert,
-    error::{AppError, Result},
-    models::{ApiResponse, StarknetWalletCall, SwapQuoteRequest, SwapQuoteResponse},
+    db::FeeLedgerEntry,
+    error::{AppError, ErrorCode, Result},
+    models::{
+        ApiResponse, StarknetWalletCall, SwapFeeBreakdown, SwapQuoteRequest, SwapQuoteResponse,
+    },
+    services::discount_service::{points_multiplier, DiscountService, DiscountSource},
     services::gas_optimizer::GasOptimizer,
-    services::nft_discount::consume_nft_usage,
+    services::hide_executor::{
+        hide_executor_kind, shielded_current_root, shielded_note_deposit_timestamp,
+        shielded_note_registered, HideExecutorKind,
+    },
     services::notification_service::NotificationType,
     services::price_guard::{
         fallback_price_for, first_sane_price, sanitize_points_usd_base, sanitize_usd_notional,
         symbol_candidates_for,
     },
-    services::privacy_verifier::parse_privacy_verifier_kind,
-    services::relayer::RelayerService,
+    services::privacy_verifier::select_privacy_verifier,
+    services::public_input_schema::public_input_schema,
+    services::relayer::{relayer_revert_reason, RelayerService},
     services::LiquidityAggregator,
     services::NotificationService,
 };
-use axum::{extract::State, http::HeaderMap, Json};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use ethers::types::U256;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use serde::{Deserialize, Serialize};
 use starknet_core::types::{
     Call, ExecutionResult, Felt, FunctionCall, InvokeTransaction, Transaction,
     TransactionFinalityStatus,
 };
-use starknet_core::utils::{get_selector_from_name, get_storage_var_address};
+use starknet_core::utils::get_selector_from_name;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::sync::OnceLock;
 use std::time::Instant;
-use tokio::time::{sleep, timeout, Duration};
-
-const ORACLE_ROUTE_DEX_ID_HEX: &str = "0x4f52434c"; // 'ORCL'
-const ONCHAIN_DISCOUNT_TIMEOUT_MS: u64 = 2_500;
-const NFT_DISCOUNT_CACHE_TTL_SECS: u64 = 300;
-const NFT_DISCOUNT_CACHE_STALE_SECS: u64 = 1_800;
-const NFT_DISCOUNT_CACHE_MAX_ENTRIES: usize = 100_000;
-const AI_LEVEL_2_POINTS_BONUS_PERCENT: f64 = 20.0;
-const AI_LEVEL_3_POINTS_BONUS_PERCENT: f64 = 40.0;
-
-#[derive(Clone, Copy)]
-struct CachedNftDiscount {
-    fetched_at: Instant,
-    discount: f64,
-}
-
-static NFT_DISCOUNT_CACHE: OnceLock<tokio::sync::RwLock<HashMap<String, CachedNftDiscount>>> =
-    OnceLock::new();
-
-// Internal helper that supports `nft_discount_cache` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn nft_discount_cache() -> &'static tokio::sync::RwLock<HashMap<String, CachedNftDiscount>> {
-    NFT_DISCOUNT_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()))
-}
+use tokio::time::{sleep, Duration};
 
-// Internal helper that supports `nft_discount_cache_key` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn nft_discount_cache_key(contract: &str, user: &str) -> String {
-    format!(
-        "{}|{}",
-        contract.trim().to_ascii_lowercase(),
-        user.trim().to_ascii_lowercase()
-    )
-}
+const SWAP_TX_CONFIRM_ATTEMPTS_DEFAULT: u32 = 5;
+const SWAP_TX_CONFIRM_INTERVAL_MS_DEFAULT: u64 = 1_000;
+const MAX_SWAP_AMOUNT_IN: f64 = 1_000_000_000_000.0;
+const POINTS_DISPLAY_DECIMALS: u32 = 2;
+const SWAP_ROUTE_CACHE_MAX_ENTRIES: usize = 2_048;
 
-// Internal helper that fetches data for `get_cached_nft_discount` in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn get_cached_nft_discount(key: &str, max_age: Duration) -> Option<f64> {
-    let cache = nft_discount_cache();
-    let guard = cache.read().await;
-    let entry = guard.get(key)?;
-    if entry.fetched_at.elapsed() <= max_age {
-        return Some(entry.discount);
-    }
-    None
-}
+static EVENT_ONLY_SWAP_CONTRACT: OnceLock<bool> = OnceLock::new();
 
-// Internal helper that supports `cache_nft_discount` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn cache_nft_discount(key: &str, discount: f64) {
-    let cache = nft_discount_cache();
-    let mut guard = cache.write().await;
-    guard.insert(
-        key.to_string(),
-        CachedNftDiscount {
-            fetched_at: Instant::now(),
-            discount,
-        },
-    );
-    if guard.len() > NFT_DISCOUNT_CACHE_MAX_ENTRIES {
-        let stale_after = Duration::from_secs(NFT_DISCOUNT_CACHE_STALE_SECS);
-        guard.retain(|_, entry| entry.fetched_at.elapsed() <= stale_after);
-    }
-}
+// (from symbol, to symbol, amount bucket)
+type SwapRouteKey = (String, String, String);
+static SWAP_ROUTE_CACHE: OnceLock<tokio::sync::RwLock<HashMap<SwapRouteKey, CachedSwapRoute>>> =
+    OnceLock::new();
 
 #[derive(Debug, Deserialize)]
 pub struct PrivacyVerificationPayload {
@@ -147,12 +113,44 @@ pub struct ExecuteSwapResponse {
     pub fee_before_discount: String,
     pub fee_discount_saved: String,
     pub nft_discount_percent: String,
+    // Staked-CAREL fee discount and the capped NFT + staking discount actually applied.
+    pub stake_discount_percent: String,
+    pub fee_discount_percent: String,
+    pub discount_sources: Vec<DiscountSource>,
     pub estimated_points_earned: String,
     pub points_pending: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub privacy_tx_hash: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct SwapReceiptResponse {
+    pub transaction: crate::models::Transaction,
+    // None for swaps executed before fee breakdowns were stored.
+    pub fee_breakdown: Option<SwapFeeBreakdown>,
+    pub usd_notional: Option<rust_decimal::Decimal>,
+    // Only set once the points worker has processed the swap.
+    pub points_earned: Option<rust_decimal::Decimal>,
+    pub points_pending: bool,
+    pub is_private: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RelayerCallPreview {
+    pub contract_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entrypoint: Option<String>,
+    pub selector: String,
+    pub calldata: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RelayerCallsPreviewResponse {
+    pub executor: String,
+    pub executor_kind: String,
+    pub calls: Vec<RelayerCallPreview>,
+}
+
 // Internal helper that supports `env_flag` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 fn env_flag(name: &str, default: bool) -> bool {
@@ -169,13 +167,13 @@ fn env_flag(name: &str, default: bool) -> bool {
 
 // Internal helper that supports `hide_balance_relayer_pool_enabled` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
-fn hide_balance_relayer_pool_enabled() -> bool {
+pub(crate) fn hide_balance_relayer_pool_enabled() -> bool {
     env_flag("HIDE_BALANCE_RELAYER_POOL_ENABLED", false)
 }
 
 // Internal helper that supports `hide_balance_strict_privacy_mode_enabled` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
-fn hide_balance_strict_privacy_mode_enabled() -> bool {
+pub(crate) fn hide_balance_strict_privacy_mode_enabled() -> bool {
     env_flag("HIDE_BALANCE_STRICT_PRIVACY_MODE", false)
 }
 
@@ -183,7 +181,87 @@ fn hide_balance_v2_redeem_only_enabled() -> bool {
     env_flag("HIDE_BALANCE_V2_REDEEM_ONLY", false)
 }
 
-fn hide_balance_min_note_age_secs() -> u64 {
+// Internal helper that supports `hide_balance_flexible_denominations_enabled` operations in the swap flow.
+// Flexible V2 denominations fragment the anonymity set, so they are only honoured on testnet.
+pub(crate) fn hide_balance_flexible_denominations_enabled(config: &Config) -> bool {
+    config.is_testnet() && env_flag("HIDE_BALANCE_FLEXIBLE_DENOMINATIONS", true)
+}
+
+/// Checks a ShieldedPoolV2 deposit amount against the executor's current `fixed_amount`.
+///
+/// Returns whether a `set_asset_rule` call must be queued ahead of the deposit.
+///
+/// # Notes
+/// * In flexible mode the rule is retargeted to any requested amount (previous behaviour).
+/// * Otherwise the amount must be one of the `HIDE_BALANCE_DENOMINATIONS` entries for the
+///   token; tokens without entries only accept the current fixed amount.
+pub(crate) fn hide_deposit_requires_asset_rule(
+    config: &Config,
+    symbol: &str,
+    amount: (Felt, Felt),
+    fixed_amount: (Felt, Felt),
+) -> Result<bool> {
+    check_hide_deposit_denomination(
+        symbol,
+        &config.hide_balance_denominations_for(symbol),
+        hide_balance_flexible_denominations_enabled(config),
+        amount,
+        fixed_amount,
+    )
+}
+
+// Internal helper that checks conditions for `ensure_hide_swap_pair_allowed` in the swap flow.
+fn ensure_hide_swap_pair_allowed(pairs: &[(String, String)], from: &str, to: &str) -> Result<()> {
+    let from = from.trim().to_ascii_uppercase();
+    let to = to.trim().to_ascii_uppercase();
+    if pairs.is_empty() || pairs.iter().any(|pair| pair.0 == from && pair.1 == to) {
+        return Ok(());
+    }
+    Err(AppError::Coded(
+        ErrorCode::HideSwapPairNotAllowed,
+        format!(
+            "Hide Balance is not available for {} -> {} yet because its anonymity set is still too small. Retry with hide_balance=false to swap in transparent mode.",
+            from, to
+        ),
+    ))
+}
+
+// Internal helper that checks conditions for `check_hide_deposit_denomination` in the swap flow.
+fn check_hide_deposit_denomination(
+    symbol: &str,
+    denominations: &[String],
+    flexible: bool,
+    amount: (Felt, Felt),
+    fixed_amount: (Felt, Felt),
+) -> Result<bool> {
+    if amount == fixed_amount {
+        return Ok(false);
+    }
+    if flexible {
+        return Ok(true);
+    }
+    let decimals = token_decimals(symbol);
+    for denomination in denominations {
+        if parse_decimal_to_u256_parts(denomination, decimals)? == amount {
+            return Ok(true);
+        }
+    }
+    let allowed = if denominations.is_empty() {
+        "the executor's current fixed amount".to_string()
+    } else {
+        denominations.join(", ")
+    };
+    Err(AppError::Coded(
+        ErrorCode::HideDenominationNotAllowed,
+        format!(
+            "Hide Balance {} deposits must use a fixed denomination: {}",
+            symbol.to_ascii_uppercase(),
+            allowed
+        ),
+    ))
+}
+
+pub(crate) fn hide_balance_min_note_age_secs() -> u64 {
     std::env::var("HIDE_BALANCE_MIN_NOTE_AGE_SECS")
         .or_else(|_| std::env::var("NEXT_PUBLIC_HIDE_BALANCE_MIN_NOTE_AGE_SECS"))
         .ok()
@@ -192,7 +270,55 @@ fn hide_balance_min_note_age_secs() -> u64 {
         .unwrap_or(60)
 }
 
-fn hide_balance_max_uses_per_day() -> u64 {
+// Internal helper that supports `hide_balance_note_age_tolerance_secs` operations in the swap flow.
+// Absorbs clock skew between chain block timestamps and the backend at the mixing-window boundary.
+pub(crate) fn hide_balance_note_age_tolerance_secs() -> u64 {
+    std::env::var("HIDE_BALANCE_NOTE_AGE_TOLERANCE_SECS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(5)
+}
+
+/// Returns when a note deposited at `deposit_ts` becomes spendable.
+///
+/// # Notes
+/// * Rejects notes still inside the `HIDE_BALANCE_MIN_NOTE_AGE_SECS` mixing window, less
+///   `HIDE_BALANCE_NOTE_AGE_TOLERANCE_SECS` of allowed clock skew.
+pub(crate) fn ensure_note_mixing_window_elapsed(deposit_ts: u64) -> Result<u64> {
+    check_note_mixing_window(
+        deposit_ts,
+        chrono::Utc::now().timestamp().max(0) as u64,
+        hide_balance_min_note_age_secs(),
+        hide_balance_note_age_tolerance_secs(),
+    )
+}
+
+// Internal helper that checks conditions for `check_note_mixing_window` in the swap flow.
+fn check_note_mixing_window(
+    deposit_ts: u64,
+    now: u64,
+    min_age_secs: u64,
+    tolerance_secs: u64,
+) -> Result<u64> {
+    let spendable_at = deposit_ts.saturating_add(min_age_secs);
+    let required_at = spendable_at.saturating_sub(tolerance_secs.min(min_age_secs));
+    if now < required_at {
+        return Err(AppError::Coded(
+            ErrorCode::HideNoteMixingWindow,
+            format!(
+                "Hide Balance note is still in its mixing window: {} seconds remaining (deposit_ts={}, now={}, min_age={}s, tolerance={}s).",
+                required_at - now,
+                deposit_ts,
+                now,
+                min_age_secs,
+                tolerance_secs
+            ),
+        ));
+    }
+    Ok(spendable_at)
+}
+
+pub(crate) fn hide_balance_max_uses_per_day() -> u64 {
     std::env::var("HIDE_BALANCE_MAX_USES_PER_DAY")
         .ok()
         .and_then(|value| value.trim().parse::<u64>().ok())
@@ -206,61 +332,67 @@ fn resolve_swap_final_recipient(
     user_address: &str,
     hide_mode: bool,
     strict_privacy_mode: bool,
+) -> Result<String> {
+    let default_hide_recipient = std::env::var("HIDE_BALANCE_DEFAULT_RECIPIENT").ok();
+    resolve_swap_final_recipient_with_default(
+        requested_recipient,
+        user_address,
+        hide_mode,
+        strict_privacy_mode,
+        default_hide_recipient.as_deref(),
+    )
+}
+
+// Internal helper that applies the hide-mode recipient policy against an explicit default recipient.
+// Strict mode never falls back to the depositor, so a missing default surfaces as an operator hint.
+fn resolve_swap_final_recipient_with_default(
+    requested_recipient: Option<&str>,
+    user_address: &str,
+    hide_mode: bool,
+    strict_privacy_mode: bool,
+    default_hide_recipient: Option<&str>,
 ) -> Result<String> {
     let requested = requested_recipient
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .map(|value| value.to_string());
-    let default_hide_recipient = std::env::var("HIDE_BALANCE_DEFAULT_RECIPIENT")
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
+    let default_hide_recipient = default_hide_recipient
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string());
 
     if !hide_mode {
         return Ok(requested.unwrap_or_else(|| user_address.to_string()));
     }
 
-    let final_recipient = requested
-        .or(default_hide_recipient)
-        .unwrap_or_else(|| user_address.to_string());
+    let uses_default_recipient = requested.is_none() && default_hide_recipient.is_some();
+    let final_recipient = match requested.or(default_hide_recipient) {
+        Some(recipient) => recipient,
+        None if strict_privacy_mode => {
+            return Err(AppError::BadRequest(
+                "Hide Balance strict mode: no recipient provided and HIDE_BALANCE_DEFAULT_RECIPIENT is not configured. Provide a recipient different from the depositor, or ask the operator to set HIDE_BALANCE_DEFAULT_RECIPIENT."
+                    .to_string(),
+            ));
+        }
+        None => user_address.to_string(),
+    };
 
     if strict_privacy_mode {
         let recipient_lower = final_recipient.trim().to_ascii_lowercase();
         let user_lower = user_address.trim().to_ascii_lowercase();
         if recipient_lower == user_lower {
-            return Err(AppError::BadRequest(
+            let message = if uses_default_recipient {
+                "Hide Balance strict mode: HIDE_BALANCE_DEFAULT_RECIPIENT matches the depositor address. Provide a different recipient, or ask the operator to change HIDE_BALANCE_DEFAULT_RECIPIENT."
+            } else {
                 "Hide Balance strict mode: recipient must be different from depositor address."
-                    .to_string(),
-            ));
+            };
+            return Err(AppError::BadRequest(message.to_string()));
         }
     }
 
     Ok(final_recipient)
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum HideExecutorKind {
-    PrivateActionExecutorV1,
-    ShieldedPoolV2,
-    ShieldedPoolV3,
-}
-
-// Internal helper that supports `hide_executor_kind` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn hide_executor_kind() -> HideExecutorKind {
-    let raw = std::env::var("HIDE_BALANCE_EXECUTOR_KIND")
-        .unwrap_or_default()
-        .trim()
-        .to_ascii_lowercase();
-    if matches!(raw.as_str(), "shielded_pool_v3" | "shielded-v3" | "v3") {
-        HideExecutorKind::ShieldedPoolV3
-    } else if matches!(raw.as_str(), "shielded_pool_v2" | "shielded-v2" | "v2") {
-        HideExecutorKind::ShieldedPoolV2
-    } else {
-        HideExecutorKind::PrivateActionExecutorV1
-    }
-}
-
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum HidePoolVersion {
     V2,
@@ -389,49 +521,6 @@ fn resolve_private_action_executor_candidates(config: &crate::config::Config) ->
     ))
 }
 
-// Internal helper that supports `is_missing_entrypoint_error` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn is_missing_entrypoint_error(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    if lower.contains("entry_point_not_found") {
-        return true;
-    }
-    let mentions_entrypoint =
-        lower.contains("entrypoint") || lower.contains("entry point") || lower.contains("selector");
-    let mentions_missing = lower.contains("does not exist")
-        || lower.contains("not found")
-        || lower.contains("missing");
-    mentions_entrypoint && mentions_missing
-}
-
-// Internal helper that checks conditions for `is_transient_probe_error` in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn is_transient_probe_error(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    lower.contains("timeout")
-        || lower.contains("timed out")
-        || lower.contains("too many requests")
-        || lower.contains("429")
-        || lower.contains("gateway")
-        || lower.contains("temporarily unavailable")
-        || lower.contains("invalid peer certificate")
-        || lower.contains("unknownissuer")
-        || lower.contains("connection reset")
-        || lower.contains("network")
-}
-
-// Internal helper that checks whether an RPC error is a contract-side revert during selector probing.
-// Such errors still indicate the selector exists and should be treated as "supported".
-fn is_contract_revert_probe_error(message: &str) -> bool {
-    let lower = message.to_ascii_lowercase();
-    lower.contains("contracterror")
-        || lower.contains("contract error")
-        || lower.contains("revert_error")
-        || lower.contains("execution_error")
-        || lower.contains("innercontractexecutionerror")
-        || lower.contains("sender required")
-}
-
 // Internal helper that supports `shielded_executor_supports_deposit_fixed_for` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 async fn shielded_executor_supports_deposit_fixed_for(
@@ -442,7 +531,7 @@ async fn shielded_executor_supports_deposit_fixed_for(
     let selector = get_selector_from_name("deposit_fixed_for")
         .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
     let probe = reader
-        .call(FunctionCall {
+        .call_typed(FunctionCall {
             contract_address: executor,
             entry_point_selector: selector,
             calldata: vec![Felt::ONE, Felt::ONE, Felt::ONE, Felt::ONE],
@@ -450,32 +539,30 @@ async fn shielded_executor_supports_deposit_fixed_for(
         .await;
     match probe {
         Ok(_) => Ok(true),
-        Err(AppError::BlockchainRPC(message)) => {
-            if is_missing_entrypoint_error(&message) {
-                Ok(false)
-            } else if is_contract_revert_probe_error(&message) {
-                tracing::info!(
-                    "ShieldedPoolV2 probe for executor {} returned contract revert (treated as supported): {}",
-                    executor,
-                    message
-                );
-                Ok(true)
-            } else if is_transient_probe_error(&message) {
-                Err(AppError::BlockchainRPC(format!(
-                    "Failed to probe ShieldedPoolV2 executor {}: {}",
-                    executor, message
-                )))
-            } else {
-                // Any non-missing-entrypoint revert still proves the selector exists.
-                tracing::info!(
-                    "ShieldedPoolV2 probe for executor {} returned non-entrypoint error (treated as supported): {}",
-                    executor,
-                    message
-                );
-                Ok(true)
-            }
+        Err(OnchainError::EntrypointNotFound(_)) => Ok(false),
+        Err(OnchainError::ContractRevert(message)) => {
+            tracing::info!(
+                "ShieldedPoolV2 probe for executor {} returned contract revert (treated as supported): {}",
+                executor,
+                message
+            );
+            Ok(true)
+        }
+        Err(err @ (OnchainError::RateLimited(_) | OnchainError::Network(_))) => {
+            Err(AppError::BlockchainRPC(format!(
+                "Failed to probe ShieldedPoolV2 executor {}: {}",
+                executor, err
+            )))
+        }
+        Err(OnchainError::Other(message)) => {
+            // Any non-missing-entrypoint error still proves the selector exists.
+            tracing::info!(
+                "ShieldedPoolV2 probe for executor {} returned non-entrypoint error (treated as supported): {}",
+                executor,
+                message
+            );
+            Ok(true)
         }
-        Err(err) => Err(err),
     }
 }
 
@@ -487,7 +574,7 @@ async fn shielded_executor_supports_deposit_fixed_v3(
     let selector = get_selector_from_name("deposit_fixed_v3")
         .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
     let probe = reader
-        .call(FunctionCall {
+        .call_typed(FunctionCall {
             contract_address: executor,
             entry_point_selector: selector,
             calldata: vec![Felt::ONE, Felt::ONE, Felt::ONE],
@@ -495,31 +582,30 @@ async fn shielded_executor_supports_deposit_fixed_v3(
         .await;
     match probe {
         Ok(_) => Ok(true),
-        Err(AppError::BlockchainRPC(message)) => {
-            if is_missing_entrypoint_error(&message) {
-                Ok(false)
-            } else if is_contract_revert_probe_error(&message) {
-                tracing::info!(
-                    "ShieldedPoolV3 probe for executor {} returned contract revert (treated as supported): {}",
-                    executor,
-                    message
-                );
-                Ok(true)
-            } else if is_transient_probe_error(&message) {
-                Err(AppError::BlockchainRPC(format!(
-                    "Failed to probe ShieldedPoolV3 executor {}: {}",
-                    executor, message
-                )))
-            } else {
-                tracing::info!(
-                    "ShieldedPoolV3 probe for executor {} returned non-entrypoint error (treated as supported): {}",
-                    executor,
-                    message
-                );
-                Ok(true)
-            }
+        Err(OnchainError::EntrypointNotFound(_)) => Ok(false),
+        Err(OnchainError::ContractRevert(message)) => {
+            tracing::info!(
+                "ShieldedPoolV3 probe for executor {} returned contract revert (treated as supported): {}",
+                executor,
+                message
+            );
+            Ok(true)
+        }
+        Err(err @ (OnchainError::RateLimited(_) | OnchainError::Network(_))) => {
+            Err(AppError::BlockchainRPC(format!(
+                "Failed to probe ShieldedPoolV3 executor {}: {}",
+                executor, err
+            )))
+        }
+        Err(OnchainError::Other(message)) => {
+            // Any non-missing-entrypoint error still proves the selector exists.
+            tracing::info!(
+                "ShieldedPoolV3 probe for executor {} returned non-entrypoint error (treated as supported): {}",
+                executor,
+                message
+            );
+            Ok(true)
         }
-        Err(err) => Err(err),
     }
 }
 
@@ -527,7 +613,7 @@ async fn shielded_executor_supports_deposit_fixed_v3(
 // Keeps validation, normalization, and intent-binding logic centralized.
 async fn resolve_private_action_executor_felt_for_swap_hide(state: &AppState) -> Result<Felt> {
     let candidates = resolve_private_action_executor_candidates(&state.config)?;
-    if hide_executor_kind() == HideExecutorKind::PrivateActionExecutorV1 {
+    if hide_executor_kind()? == HideExecutorKind::PrivateActionExecutorV1 {
         let selected = candidates[0];
         tracing::info!("Using private executor {} for swap hide mode", selected);
         return Ok(selected);
@@ -535,7 +621,7 @@ async fn resolve_private_action_executor_felt_for_swap_hide(state: &AppState) ->
 
     let mut unsupported: Vec<String> = Vec::new();
     for candidate in candidates {
-        if hide_executor_kind() == HideExecutorKind::ShieldedPoolV2 {
+        if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV2 {
             if shielded_executor_supports_deposit_fixed_for(state, candidate).await? {
                 tracing::info!(
                     "Using ShieldedPoolV2 executor {} for swap hide mode",
@@ -565,7 +651,7 @@ async fn resolve_private_action_executor_felt_for_swap_hide(state: &AppState) ->
         unsupported.push(candidate.to_string());
     }
 
-    if hide_executor_kind() == HideExecutorKind::ShieldedPoolV3 {
+    if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV3 {
         return Err(AppError::BadRequest(format!(
             "Configured ShieldedPoolV3 executor is outdated (missing deposit_fixed_v3): {}. Redeploy latest ShieldedPoolV3 and set PRIVATE_ACTION_EXECUTOR_ADDRESS.",
             unsupported.join(", ")
@@ -580,24 +666,25 @@ async fn resolve_private_action_executor_felt_for_swap_hide(state: &AppState) ->
 
 // Internal helper that parses or transforms values for `normalize_hex_items` in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
-fn normalize_hex_items(items: &[String]) -> Vec<String> {
+fn normalize_hex_items(items: &[String], field: &str) -> Result<Vec<String>> {
     items
         .iter()
         .map(|item| item.trim())
-        .filter(|item| !item.is_empty())
-        .map(ToOwned::to_owned)
+        .enumerate()
+        .filter(|(_, item)| !item.is_empty())
+        .map(|(index, item)| {
+            parse_felt(item).map(|_| item.to_owned()).map_err(|_| {
+                AppError::BadRequest(format!(
+                    "privacy.{}[{}] is not a valid felt: {}",
+                    field, index, item
+                ))
+            })
+        })
         .collect()
 }
 
-fn configured_root_public_input_index() -> usize {
-    std::env::var("GARAGA_ROOT_PUBLIC_INPUT_INDEX")
-        .ok()
-        .and_then(|raw| raw.trim().parse::<usize>().ok())
-        .unwrap_or(0)
-}
-
 fn infer_v3_root_from_public_inputs(public_inputs: &[String]) -> Option<String> {
-    let index = configured_root_public_input_index();
+    let index = public_input_schema().v3().root?;
     let candidate = public_inputs.get(index)?.trim();
     if candidate.is_empty() {
         return None;
@@ -608,20 +695,6 @@ fn infer_v3_root_from_public_inputs(public_inputs: &[String]) -> Option<String>
     Some(candidate.to_string())
 }
 
-fn configured_v3_nullifier_public_input_index() -> usize {
-    std::env::var("GARAGA_NULLIFIER_PUBLIC_INPUT_INDEX_V3")
-        .ok()
-        .and_then(|raw| raw.trim().parse::<usize>().ok())
-        .unwrap_or(1)
-}
-
-fn configured_v3_action_hash_public_input_index() -> usize {
-    std::env::var("GARAGA_INTENT_HASH_PUBLIC_INPUT_INDEX")
-        .ok()
-        .and_then(|raw| raw.trim().parse::<usize>().ok())
-        .unwrap_or(2)
-}
-
 fn ensure_v3_payload_public_inputs_shape(
     payload: &AutoPrivacyPayloadResponse,
     source_label: &str,
@@ -635,8 +708,9 @@ fn ensure_v3_payload_public_inputs_shape(
             )
         })
         .unwrap_or(false);
-    let root_index = configured_root_public_input_index();
-    let nullifier_index = configured_v3_nullifier_public_input_index();
+    let layout = public_input_schema().v3();
+    let root_index = layout.root.unwrap_or(0);
+    let nullifier_index = layout.nullifier;
     if legacy_compat {
         let required_len = std::cmp::max(root_index, nullifier_index) + 1;
         if payload.public_inputs.len() < required_len {
@@ -650,11 +724,8 @@ fn ensure_v3_payload_public_inputs_shape(
         }
         return Ok(());
     }
-    let action_hash_index = configured_v3_action_hash_public_input_index();
-    let required_len = std::cmp::max(
-        std::cmp::max(root_index, nullifier_index),
-        action_hash_index,
-    ) + 1;
+    let action_hash_index = layout.action_hash.unwrap_or(2);
+    let required_len = layout.required_len();
 
     if payload.public_inputs.len() < required_len {
         return Err(AppError::BadRequest(format!(
@@ -684,8 +755,9 @@ fn normalize_v3_public_inputs_binding(payload: &mut AutoPrivacyPayloadResponse)
         .ok_or_else(|| AppError::BadRequest("Hide Balance V3 requires privacy.root".to_string()))?;
     let expected_root = parse_felt(root.trim())?;
     let expected_nullifier = parse_felt(payload.nullifier.trim())?;
-    let root_index = configured_root_public_input_index();
-    let nullifier_index = configured_v3_nullifier_public_input_index();
+    let layout = public_input_schema().v3();
+    let root_index = layout.root.unwrap_or(0);
+    let nullifier_index = layout.nullifier;
     let required_len = std::cmp::max(root_index, nullifier_index) + 1;
     while payload.public_inputs.len() < required_len {
         payload.public_inputs.push("0x0".to_string());
@@ -729,11 +801,16 @@ fn ensure_v3_payload_root(
 fn payload_from_request(
     payload: Option<&PrivacyVerificationPayload>,
     verifier: &str,
-) -> Option<AutoPrivacyPayloadResponse> {
-    let payload = payload?;
-    let nullifier = payload.nullifier.as_deref()?.trim();
+    limits: PrivacyPayloadLimits,
+) -> Result<Option<AutoPrivacyPayloadResponse>> {
+    let Some(payload) = payload else {
+        return Ok(None);
+    };
+    let Some(nullifier) = payload.nullifier.as_deref().map(str::trim) else {
+        return Ok(None);
+    };
     if nullifier.is_empty() {
-        return None;
+        return Ok(None);
     }
     let commitment = payload
         .commitment
@@ -741,17 +818,23 @@ fn payload_from_request(
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .unwrap_or("0x0");
-    let proof = normalize_hex_items(payload.proof.as_ref()?);
-    let public_inputs = normalize_hex_items(payload.public_inputs.as_ref()?);
+    let (Some(raw_proof), Some(raw_public_inputs)) =
+        (payload.proof.as_ref(), payload.public_inputs.as_ref())
+    else {
+        return Ok(None);
+    };
+    limits.ensure(raw_proof.len(), raw_public_inputs.len())?;
+    let proof = normalize_hex_items(raw_proof, "proof")?;
+    let public_inputs = normalize_hex_items(raw_public_inputs, "public_inputs")?;
     if proof.is_empty() || public_inputs.is_empty() {
-        return None;
+        return Ok(None);
     }
     if proof.len() == 1
         && public_inputs.len() == 1
         && proof[0].eq_ignore_ascii_case("0x1")
         && public_inputs[0].eq_ignore_ascii_case("0x1")
     {
-        return None;
+        return Ok(None);
     }
     let note_version = payload
         .note_version
@@ -774,7 +857,7 @@ fn payload_from_request(
         root = infer_v3_root_from_public_inputs(&public_inputs);
     }
 
-    Some(AutoPrivacyPayloadResponse {
+    Ok(Some(AutoPrivacyPayloadResponse {
         verifier: payload
             .verifier
             .as_deref()
@@ -802,7 +885,7 @@ fn payload_from_request(
         spendable_at_unix: payload.spendable_at_unix,
         proof,
         public_inputs,
-    })
+    }))
 }
 
 // Internal helper that builds inputs for `build_swap_executor_action_calldata` in the swap flow.
@@ -832,7 +915,7 @@ fn build_submit_private_intent_call(
     executor: Felt,
     payload: &AutoPrivacyPayloadResponse,
 ) -> Result<Call> {
-    let kind = hide_executor_kind();
+    let kind = hide_executor_kind()?;
     let selector_name = match kind {
         HideExecutorKind::PrivateActionExecutorV1 => "submit_private_intent",
         HideExecutorKind::ShieldedPoolV2 => "submit_private_action",
@@ -893,6 +976,34 @@ struct SwapPayoutCallInput<'a> {
     min_payout_high: Felt,
 }
 
+// Internal helper that checks conditions for `validate_swap_payout_input` in the swap flow.
+// Guards the relayer against a malformed context that would pay out the wrong token or
+// accept any output amount.
+fn validate_swap_payout_input(
+    input: &SwapPayoutCallInput<'_>,
+    from_token_candidates: &[Felt],
+    to_token_candidates: &[Felt],
+) -> Result<()> {
+    if !from_token_candidates.contains(&input.approval_token) {
+        return Err(AppError::BadRequest(format!(
+            "Hide swap approval token {} does not match the requested from_token",
+            felt_hex(input.approval_token)
+        )));
+    }
+    if !to_token_candidates.contains(&input.payout_token) {
+        return Err(AppError::BadRequest(format!(
+            "Hide swap payout token {} does not match the requested to_token",
+            felt_hex(input.payout_token)
+        )));
+    }
+    if input.min_payout_low == Felt::ZERO && input.min_payout_high == Felt::ZERO {
+        return Err(AppError::BadRequest(
+            "Hide swap minimum payout must be non-zero. Refresh the quote and retry.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 fn build_execute_private_swap_with_payout_call(
     executor: Felt,
     payload: &AutoPrivacyPayloadResponse,
@@ -901,7 +1012,7 @@ fn build_execute_private_swap_with_payout_call(
     let selector = get_selector_from_name("execute_private_swap_with_payout")
         .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
 
-    let kind = hide_executor_kind();
+    let kind = hide_executor_kind()?;
     let mut calldata: Vec<Felt> = Vec::with_capacity(12 + input.action_calldata.len());
     if kind == HideExecutorKind::ShieldedPoolV3 {
         calldata.push(parse_felt(payload.nullifier.trim())?);
@@ -967,69 +1078,6 @@ fn build_shielded_deposit_fixed_for_call(
     })
 }
 
-// Internal helper that supports `shielded_note_registered` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn shielded_note_registered(
-    state: &AppState,
-    executor: Felt,
-    note_commitment: Felt,
-) -> Result<bool> {
-    let reader = OnchainReader::from_config(&state.config)?;
-    let selector = get_selector_from_name("is_note_registered")
-        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
-    let out = reader
-        .call(FunctionCall {
-            contract_address: executor,
-            entry_point_selector: selector,
-            calldata: vec![note_commitment],
-        })
-        .await?;
-    let flag = out.first().copied().unwrap_or(Felt::ZERO);
-    Ok(flag != Felt::ZERO)
-}
-
-async fn shielded_note_deposit_timestamp(
-    state: &AppState,
-    executor: Felt,
-    note_commitment: Felt,
-) -> Result<u64> {
-    let reader = OnchainReader::from_config(&state.config)?;
-    let selector = get_selector_from_name("get_note_deposit_timestamp")
-        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
-    let out = reader
-        .call(FunctionCall {
-            contract_address: executor,
-            entry_point_selector: selector,
-            calldata: vec![note_commitment],
-        })
-        .await?;
-    let raw = out.first().copied().unwrap_or(Felt::ZERO);
-    let value = felt_to_u128(&raw).map_err(|_| {
-        AppError::BadRequest("Invalid note timestamp returned by shielded pool".to_string())
-    })?;
-    Ok(value as u64)
-}
-
-async fn shielded_current_root(state: &AppState, executor: Felt) -> Result<Felt> {
-    let reader = OnchainReader::from_config(&state.config)?;
-    let selector = get_selector_from_name("get_root")
-        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
-    let out = reader
-        .call(FunctionCall {
-            contract_address: executor,
-            entry_point_selector: selector,
-            calldata: vec![],
-        })
-        .await?;
-    let root = out.first().copied().unwrap_or(Felt::ZERO);
-    if root == Felt::ZERO {
-        return Err(AppError::BadRequest(
-            "ShieldedPoolV3 root is not initialized yet (get_root=0).".to_string(),
-        ));
-    }
-    Ok(root)
-}
-
 // Internal helper that supports `shielded_fixed_amount` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 async fn shielded_fixed_amount(
@@ -1063,7 +1111,7 @@ async fn compute_swap_payout_intent_hash_on_executor(
     input: &SwapPayoutCallInput<'_>,
 ) -> Result<String> {
     let reader = OnchainReader::from_config(&state.config)?;
-    let kind = hide_executor_kind();
+    let kind = hide_executor_kind()?;
     let selector_name = match kind {
         HideExecutorKind::PrivateActionExecutorV1 => "preview_swap_payout_intent_hash",
         HideExecutorKind::ShieldedPoolV2 => "preview_swap_payout_action_hash",
@@ -1109,101 +1157,132 @@ fn is_deadline_valid(deadline: i64, now: i64) -> bool {
     deadline >= now
 }
 
-// Internal helper that supports `invalidate_cached_nft_discount` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn invalidate_cached_nft_discount(contract: &str, user: &str) {
-    let key = nft_discount_cache_key(contract, user);
-    let cache = nft_discount_cache();
-    let mut guard = cache.write().await;
-    guard.remove(&key);
-}
-
-#[derive(Clone, Copy, Debug, Default)]
-struct NftUsageSnapshot {
-    tier: i32,
-    discount_percent: f64,
-    max_usage: u128,
-    used_in_period: u128,
-}
-
-// Internal helper that supports `current_nft_period_epoch` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn current_nft_period_epoch() -> i64 {
-    chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS
+/// Rejects expired deadlines and deadlines more than `max_window_secs` ahead of `now`,
+/// so stale signed calldata cannot be replayed long after it was built.
+pub(crate) fn validate_deadline(deadline: i64, now: i64, max_window_secs: i64) -> Result<()> {
+    if !is_deadline_valid(deadline, now) {
+        return Err(AppError::BadRequest(
+            "Transaction deadline expired".to_string(),
+        ));
+    }
+    if deadline.saturating_sub(now) > max_window_secs {
+        return Err(AppError::BadRequest(format!(
+            "Transaction deadline too far out: {}s ahead exceeds MAX_DEADLINE_SECONDS ({}s)",
+            deadline.saturating_sub(now),
+            max_window_secs
+        )));
+    }
+    Ok(())
 }
 
-// Internal helper that supports `u128_to_i64_saturating` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn u128_to_i64_saturating(value: u128) -> i64 {
-    if value > i64::MAX as u128 {
-        i64::MAX
+// Internal helper that returns the swap fee rate: 0.3% base plus 1% MEV protection in
+// private mode. The single source for every swap fee amount.
+fn swap_fee_rate(mode: &str) -> rust_decimal::Decimal {
+    let base = rust_decimal::Decimal::new(3, 3);
+    if mode.eq_ignore_ascii_case("private") {
+        base + rust_decimal::Decimal::new(1, 2)
     } else {
-        value as i64
+        base
     }
 }
 
-// Internal helper that fetches data for `read_nft_usage_snapshot` in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn read_nft_usage_snapshot(
-    reader: &OnchainReader,
-    contract_address: Felt,
-    user_felt: Felt,
-) -> Result<Option<NftUsageSnapshot>> {
-    let storage_key = get_storage_var_address("user_nft", &[user_felt])
-        .map_err(|e| AppError::Internal(format!("Storage key resolution error: {}", e)))?;
-    let token_raw = reader.get_storage_at(contract_address, storage_key).await?;
-    let token_id = felt_to_u128(&token_raw).unwrap_or(0);
-    if token_id == 0 {
-        return Ok(None);
+// Internal helper that rejects swap amounts above `MAX_SWAP_AMOUNT_IN`, keeping fee and
+// points math far from f64 overflow.
+fn ensure_swap_amount_in_bounds(amount_in: f64) -> Result<()> {
+    if amount_in > MAX_SWAP_AMOUNT_IN {
+        return Err(AppError::BadRequest(format!(
+            "Amount exceeds the maximum supported swap amount ({})",
+            MAX_SWAP_AMOUNT_IN
+        )));
     }
+    Ok(())
+}
+
+// Internal helper that computes the swap fee in `Decimal` so stored and displayed fees do
+// not inherit f64 rounding.
+fn total_fee_decimal(
+    amount_in: rust_decimal::Decimal,
+    mode: &str,
+    discount_percent: f64,
+) -> rust_decimal::Decimal {
+    let rate = swap_fee_rate(mode);
+    let discount = rust_decimal::Decimal::from_f64_retain(discount_percent.clamp(0.0, 100.0))
+        .unwrap_or_default();
+    let factor = rust_decimal::Decimal::ONE - discount / rust_decimal::Decimal::ONE_HUNDRED;
+    amount_in
+        .checked_mul(rate)
+        .and_then(|fee| fee.checked_mul(factor))
+        .unwrap_or_default()
+        .max(rust_decimal::Decimal::ZERO)
+}
 
-    let info_call = FunctionCall {
-        contract_address,
-        entry_point_selector: get_selector_from_name("get_nft_info")
-            .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?,
-        calldata: vec![Felt::from(token_id), Felt::from(0_u8)],
+// Internal helper that builds the stored fee breakdown for a swap; the net fee matches
+// `total_fee_decimal` and the saving is attributed to NFT vs staking by their share of the
+// uncapped discounts.
+fn swap_fee_breakdown(
+    tx_hash: &str,
+    amount_in: rust_decimal::Decimal,
+    mode: &str,
+    nft_discount_percent: f64,
+    stake_discount_percent: f64,
+    fee_discount_percent: f64,
+) -> SwapFeeBreakdown {
+    let percent = |value: f64| {
+        rust_decimal::Decimal::from_f64_retain(value.clamp(0.0, 100.0))
+            .unwrap_or_default()
+            .round_dp(4)
     };
-    let info = reader.call(info_call).await?;
-    if info.len() < 7 {
-        return Ok(None);
+    let base_fee = total_fee_decimal(amount_in, "transparent", 0.0);
+    let fee_before_discount = total_fee_decimal(amount_in, mode, 0.0);
+    let net_fee = total_fee_decimal(amount_in, mode, fee_discount_percent);
+    let saved = (fee_before_discount - net_fee).max(rust_decimal::Decimal::ZERO);
+    let nft = percent(nft_discount_percent);
+    let stake = percent(stake_discount_percent);
+    let nft_discount_saved = if nft + stake > rust_decimal::Decimal::ZERO {
+        saved
+            .checked_mul(nft)
+            .and_then(|value| value.checked_div(nft + stake))
+            .unwrap_or_default()
+    } else {
+        rust_decimal::Decimal::ZERO
+    };
+    SwapFeeBreakdown {
+        tx_hash: tx_hash.to_string(),
+        mode: mode.to_ascii_lowercase(),
+        base_fee,
+        mev_fee: fee_before_discount - base_fee,
+        fee_before_discount,
+        nft_discount_percent: nft,
+        stake_discount_percent: stake,
+        fee_discount_percent: percent(fee_discount_percent),
+        nft_discount_saved,
+        stake_discount_saved: saved - nft_discount_saved,
+        net_fee,
     }
-
-    let tier = felt_to_u128(&info[0]).unwrap_or(0) as i32;
-    let discount = u256_from_felts(&info[1], &info[2]).unwrap_or(0) as f64;
-    let max_usage = u256_from_felts(&info[3], &info[4]).unwrap_or(0);
-    let used_in_period = u256_from_felts(&info[5], &info[6]).unwrap_or(0);
-    Ok(Some(NftUsageSnapshot {
-        tier: tier.max(0),
-        discount_percent: discount.clamp(0.0, 100.0),
-        max_usage,
-        used_in_period,
-    }))
 }
 
-// Internal helper that supports `base_fee` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn base_fee(amount_in: f64) -> f64 {
-    amount_in * 0.003
+// Internal helper that parses the request amount as `Decimal`, falling back to the f64 value
+// for inputs such as scientific notation.
+fn parse_amount_decimal(raw: &str, fallback: f64) -> rust_decimal::Decimal {
+    raw.trim()
+        .parse::<rust_decimal::Decimal>()
+        .ok()
+        .or_else(|| rust_decimal::Decimal::from_f64_retain(fallback))
+        .unwrap_or_default()
 }
 
-// Internal helper that supports `mev_fee_for_mode` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn mev_fee_for_mode(mode: &str, amount_in: f64) -> f64 {
-    if mode.eq_ignore_ascii_case("private") {
-        amount_in * 0.01
+// Internal helper that rejects NaN/infinite intermediate swap values.
+fn ensure_finite(value: f64, label: &str) -> Result<f64> {
+    if value.is_finite() {
+        Ok(value)
     } else {
-        0.0
+        Err(AppError::BadRequest(format!(
+            "Swap {} is not a finite number",
+            label
+        )))
     }
 }
 
-// Internal helper that supports `total_fee` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn total_fee(amount_in: f64, mode: &str, nft_discount_percent: f64) -> f64 {
-    let undiscounted = base_fee(amount_in) + mev_fee_for_mode(mode, amount_in);
-    let discount_factor = 1.0 - (nft_discount_percent.clamp(0.0, 100.0) / 100.0);
-    undiscounted * discount_factor
-}
-
 // Internal helper that supports `estimate_swap_points_for_response` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 fn estimate_swap_points_for_response(
@@ -1211,8 +1290,7 @@ fn estimate_swap_points_for_response(
     usdt_equivalent_volume: f64,
     hide_mode: bool,
     is_testnet: bool,
-    nft_discount_percent: f64,
-    ai_level: u8,
+    points_multiplier: f64,
 ) -> f64 {
     let sanitized = sanitize_points_usd_base(volume_usd);
     let min_threshold = if is_testnet {
@@ -1223,22 +1301,16 @@ fn estimate_swap_points_for_response(
     if sanitized < min_threshold {
         return 0.0;
     }
-    let nft_factor = 1.0 + (nft_discount_percent.clamp(0.0, 100.0) / 100.0);
-    let ai_factor = 1.0 + (ai_level_points_bonus_percent(ai_level) / 100.0);
     let usdt_tier_factor = if hide_mode {
         1.0 + (usdt_tier_bonus_percent(usdt_equivalent_volume) / 100.0)
     } else {
         1.0
     };
-    (sanitized * POINTS_PER_USD_SWAP * nft_factor * ai_factor * usdt_tier_factor).max(0.0)
-}
-
-// Internal helper that supports `ai_level_points_bonus_percent` operations in the swap flow.
-fn ai_level_points_bonus_percent(level: u8) -> f64 {
-    match level {
-        2 => AI_LEVEL_2_POINTS_BONUS_PERCENT,
-        3 => AI_LEVEL_3_POINTS_BONUS_PERCENT,
-        _ => 0.0,
+    let points = sanitized * POINTS_PER_USD_SWAP * points_multiplier.max(0.0) * usdt_tier_factor;
+    if points.is_finite() {
+        points.max(0.0)
+    } else {
+        0.0
     }
 }
 
@@ -1276,262 +1348,6 @@ fn derive_usdt_equivalent_volume(
     sanitize_points_usd_base(volume_usd)
 }
 
-// Internal helper that supports `discount_contract_address` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-fn discount_contract_address(state: &AppState) -> Option<&str> {
-    state
-        .config
-        .discount_soulbound_address
-        .as_deref()
-        .filter(|addr| !addr.trim().is_empty() && !addr.starts_with("0x0000"))
-}
-
-// Internal helper that supports `active_nft_discount_percent` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn cached_nft_discount_from_local_state(state: &AppState, user_address: &str) -> f64 {
-    let Some(contract) = discount_contract_address(state) else {
-        return 0.0;
-    };
-    let cache_key = nft_discount_cache_key(contract, user_address);
-    if let Some(cached) =
-        get_cached_nft_discount(&cache_key, Duration::from_secs(NFT_DISCOUNT_CACHE_TTL_SECS)).await
-    {
-        return cached.max(0.0);
-    }
-
-    let period_epoch = current_nft_period_epoch();
-    match state
-        .db
-        .get_nft_discount_state(contract, user_address, period_epoch)
-        .await
-    {
-        Ok(Some(row)) => {
-            let age_secs = chrono::Utc::now()
-                .signed_duration_since(row.updated_at)
-                .num_seconds()
-                .max(0) as u64;
-            if age_secs > NFT_DISCOUNT_CACHE_STALE_SECS {
-                return 0.0;
-            }
-            let effective_used = row.local_used_in_period.max(row.chain_used_in_period);
-            let has_remaining_usage = row.max_usage > 0 && effective_used < row.max_usage;
-            let discount = if row.is_active && has_remaining_usage {
-                row.discount_percent.clamp(0.0, 100.0)
-            } else {
-                0.0
-            };
-            cache_nft_discount(&cache_key, discount).await;
-            discount
-        }
-        Ok(None) => 0.0,
-        Err(err) => {
-            tracing::warn!(
-                "Failed to read local NFT discount state in swap for user={}: {}",
-                user_address,
-                err
-            );
-            0.0
-        }
-    }
-}
-
-// Internal helper that supports `refresh_nft_discount_for_submit` operations in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn refresh_nft_discount_for_submit(state: &AppState, user_address: &str) -> f64 {
-    let Some(contract) = discount_contract_address(state) else {
-        return 0.0;
-    };
-    let cache_key = nft_discount_cache_key(contract, user_address);
-    let period_epoch = current_nft_period_epoch();
-
-    let reader = match OnchainReader::from_config(&state.config) {
-        Ok(reader) => reader,
-        Err(err) => {
-            tracing::warn!(
-                "Failed to initialize on-chain reader for NFT discount submit validation in swap: {}",
-                err
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-    };
-
-    let contract_address = match parse_felt(contract) {
-        Ok(value) => value,
-        Err(err) => {
-            tracing::warn!(
-                "Invalid discount contract address while validating swap fee discount: {}",
-                err
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-    };
-    let user_felt = match parse_felt(user_address) {
-        Ok(value) => value,
-        Err(err) => {
-            tracing::warn!(
-                "Invalid user address while validating swap fee discount: user={}, err={}",
-                user_address,
-                err
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-    };
-
-    let selector = match get_selector_from_name("has_active_discount") {
-        Ok(value) => value,
-        Err(err) => {
-            tracing::warn!(
-                "Selector resolution failed for has_active_discount in swap submit validation: {}",
-                err
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-    };
-
-    let call = FunctionCall {
-        contract_address,
-        entry_point_selector: selector,
-        calldata: vec![user_felt],
-    };
-
-    let result = match timeout(
-        Duration::from_millis(ONCHAIN_DISCOUNT_TIMEOUT_MS),
-        reader.call(call),
-    )
-    .await
-    {
-        Ok(Ok(value)) => value,
-        Ok(Err(err)) => {
-            tracing::warn!(
-                "Failed on-chain NFT discount submit validation in swap for user={}: {}",
-                user_address,
-                err
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-        Err(_) => {
-            tracing::warn!(
-                "Timeout on-chain NFT discount submit validation in swap for user={}",
-                user_address
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-    };
-    if result.len() < 3 {
-        return cached_nft_discount_from_local_state(state, user_address).await;
-    }
-
-    let chain_active = felt_to_u128(&result[0]).unwrap_or(0) > 0;
-    let chain_discount = u256_from_felts(&result[1], &result[2]).unwrap_or(0) as f64;
-
-    let usage_snapshot = match timeout(
-        Duration::from_millis(ONCHAIN_DISCOUNT_TIMEOUT_MS),
-        read_nft_usage_snapshot(&reader, contract_address, user_felt),
-    )
-    .await
-    {
-        Ok(Ok(value)) => value.unwrap_or_default(),
-        Ok(Err(err)) => {
-            tracing::warn!(
-                "Failed to read NFT usage snapshot in swap submit validation for user={}: {}",
-                user_address,
-                err
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-        Err(_) => {
-            tracing::warn!(
-                "Timeout reading NFT usage snapshot in swap submit validation for user={}",
-                user_address
-            );
-            return cached_nft_discount_from_local_state(state, user_address).await;
-        }
-    };
-
-    let discount_percent = if chain_discount > 0.0 {
-        chain_discount
-    } else {
-        usage_snapshot.discount_percent
-    }
-    .clamp(0.0, 100.0);
-    let max_usage_i64 = u128_to_i64_saturating(usage_snapshot.max_usage);
-    let chain_used_i64 = u128_to_i64_saturating(usage_snapshot.used_in_period);
-
-    let db_row = state
-        .db
-        .upsert_nft_discount_state_from_chain(NftDiscountStateUpsert {
-            contract_address: contract,
-            user_address,
-            period_epoch,
-            tier: usage_snapshot.tier.max(0),
-            discount_percent,
-            is_active: chain_active,
-            max_usage: max_usage_i64,
-            chain_used_in_period: chain_used_i64,
-        })
-        .await;
-
-    let resolved_discount = match db_row {
-        Ok(row) => {
-            let effective_used = row.local_used_in_period.max(row.chain_used_in_period);
-            let has_remaining_usage = row.max_usage > 0 && effective_used < row.max_usage;
-            if row.is_active && has_remaining_usage {
-                row.discount_percent.clamp(0.0, 100.0)
-            } else {
-                0.0
-            }
-        }
-        Err(err) => {
-            tracing::warn!(
-                "Failed to persist NFT discount state in swap for user={}: {}",
-                user_address,
-                err
-            );
-            let has_remaining_usage = usage_snapshot.max_usage > 0
-                && usage_snapshot.used_in_period < usage_snapshot.max_usage;
-            if chain_active && has_remaining_usage {
-                discount_percent
-            } else {
-                0.0
-            }
-        }
-    };
-
-    cache_nft_discount(&cache_key, resolved_discount).await;
-    resolved_discount
-}
-
-// Internal helper that runs side-effecting logic for `record_nft_discount_usage_after_submit`.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn record_nft_discount_usage_after_submit(state: &AppState, user_address: &str) {
-    let Some(contract) = discount_contract_address(state) else {
-        return;
-    };
-    let period_epoch = current_nft_period_epoch();
-    match state
-        .db
-        .increment_nft_discount_local_usage(contract, user_address, period_epoch, 1)
-        .await
-    {
-        Ok(updated_usage) => {
-            tracing::debug!(
-                "Recorded local NFT usage after swap submit user={} period={} local_used={}",
-                user_address,
-                period_epoch,
-                updated_usage
-            );
-        }
-        Err(err) => {
-            tracing::warn!(
-                "Failed recording local NFT usage after swap submit for user={}: {}",
-                user_address,
-                err
-            );
-        }
-    }
-    invalidate_cached_nft_discount(contract, user_address).await;
-}
-
 // Internal helper that parses or transforms values for `normalize_usd_volume` in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 fn normalize_usd_volume(usd_in: f64, usd_out: f64) -> f64 {
@@ -1623,6 +1439,23 @@ pub(crate) fn token_decimals(symbol: &str) -> u32 {
     }
 }
 
+/// Display decimals for `symbol`: registry decimals capped by `DISPLAY_AMOUNT_MAX_DECIMALS`.
+pub(crate) fn display_decimals(config: &Config, symbol: &str) -> u32 {
+    token_decimals(symbol).min(config.display_amount_max_decimals)
+}
+
+/// Rounds an `f64` amount to `decimals` for response strings, trimming trailing zeros.
+/// Only affects what clients see; persisted `rust_decimal` values keep full precision.
+pub(crate) fn format_display_amount(value: f64, decimals: u32) -> String {
+    if !value.is_finite() {
+        return "0".to_string();
+    }
+    match rust_decimal::Decimal::from_f64(value) {
+        Some(amount) => amount.round_dp(decimals).normalize().to_string(),
+        None => format!("{:.*}", decimals as usize, value),
+    }
+}
+
 // Internal helper that supports `pow10_u128` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 fn pow10_u128(exp: u32) -> Result<u128> {
@@ -1945,6 +1778,11 @@ fn parse_execute_calls(calldata: &[Felt]) -> Result<Vec<ParsedExecuteCall>> {
 // Internal helper that supports `configured_swap_contract` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 fn configured_swap_contract(_state: &AppState) -> Result<Option<Felt>> {
+    configured_swap_contract_from_env()
+}
+
+// Internal helper that resolves the swap aggregator address from env without request state.
+fn configured_swap_contract_from_env() -> Result<Option<Felt>> {
     let mut candidates = vec![
         std::env::var("STARKNET_SWAP_CONTRACT_ADDRESS").ok(),
         std::env::var("SWAP_AGGREGATOR_ADDRESS").ok(),
@@ -1971,15 +1809,31 @@ fn env_truthy(name: &str) -> bool {
     )
 }
 
+// Internal helper that checks conditions for `ensure_swap_contract_moves_tokens` in the swap flow.
+// Detection is env-derived, so it runs once per process; parse errors are returned and retried.
+fn ensure_swap_contract_moves_tokens() -> Result<()> {
+    let event_only = match EVENT_ONLY_SWAP_CONTRACT.get() {
+        Some(event_only) => *event_only,
+        None => {
+            let event_only = is_event_only_swap_contract_configured()?;
+            *EVENT_ONLY_SWAP_CONTRACT.get_or_init(|| event_only)
+        }
+    };
+    if event_only {
+        return Err(ErrorCode::SwapContractEventOnly.into());
+    }
+    Ok(())
+}
+
 // Internal helper that checks conditions for `is_event_only_swap_contract_configured` in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
-fn is_event_only_swap_contract_configured(state: &AppState) -> Result<bool> {
+fn is_event_only_swap_contract_configured() -> Result<bool> {
     if env_truthy("SWAP_CONTRACT_EVENT_ONLY") || env_truthy("NEXT_PUBLIC_SWAP_CONTRACT_EVENT_ONLY")
     {
         return Ok(true);
     }
 
-    let Some(configured_swap) = configured_swap_contract(state)? else {
+    let Some(configured_swap) = configured_swap_contract_from_env()? else {
         return Ok(false);
     };
 
@@ -2037,6 +1891,11 @@ fn push_token_candidate(raw: Option<String>, out: &mut Vec<Felt>) {
 // Internal helper that supports `configured_token_candidates` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 fn configured_token_candidates(state: &AppState, token: &str) -> Vec<Felt> {
+    configured_token_candidates_for_config(&state.config, token)
+}
+
+// Internal helper that resolves token address candidates from config and env only.
+fn configured_token_candidates_for_config(config: &Config, token: &str) -> Vec<Felt> {
     let token = token.to_ascii_uppercase();
     let mut candidates = Vec::new();
     match token.as_str() {
@@ -2046,22 +1905,19 @@ fn configured_token_candidates(state: &AppState, token: &str) -> Vec<Felt> {
                 env_value("NEXT_PUBLIC_TOKEN_CAREL_ADDRESS"),
                 &mut candidates,
             );
-            push_token_candidate(
-                Some(state.config.carel_token_address.clone()),
-                &mut candidates,
-            );
+            push_token_candidate(Some(config.carel_token_address.clone()), &mut candidates);
         }
         "STRK" => {
             push_token_candidate(env_value("TOKEN_STRK_ADDRESS"), &mut candidates);
             push_token_candidate(env_value("NEXT_PUBLIC_TOKEN_STRK_ADDRESS"), &mut candidates);
-            push_token_candidate(state.config.token_strk_address.clone(), &mut candidates);
+            push_token_candidate(config.token_strk_address.clone(), &mut candidates);
         }
         "WBTC" | "BTC" => {
             push_token_candidate(env_value("TOKEN_WBTC_ADDRESS"), &mut candidates);
             push_token_candidate(env_value("NEXT_PUBLIC_TOKEN_WBTC_ADDRESS"), &mut candidates);
             push_token_candidate(env_value("TOKEN_BTC_ADDRESS"), &mut candidates);
             push_token_candidate(env_value("NEXT_PUBLIC_TOKEN_BTC_ADDRESS"), &mut candidates);
-            push_token_candidate(state.config.token_btc_address.clone(), &mut candidates);
+            push_token_candidate(config.token_btc_address.clone(), &mut candidates);
         }
         "USDT" => {
             push_token_candidate(env_value("TOKEN_USDT_ADDRESS"), &mut candidates);
@@ -2074,7 +1930,7 @@ fn configured_token_candidates(state: &AppState, token: &str) -> Vec<Felt> {
         "ETH" => {
             push_token_candidate(env_value("TOKEN_ETH_ADDRESS"), &mut candidates);
             push_token_candidate(env_value("NEXT_PUBLIC_TOKEN_ETH_ADDRESS"), &mut candidates);
-            push_token_candidate(state.config.token_eth_address.clone(), &mut candidates);
+            push_token_candidate(config.token_eth_address.clone(), &mut candidates);
         }
         _ => {}
     }
@@ -2152,25 +2008,18 @@ async fn read_erc20_balance_parts(
     token: Felt,
     owner: Felt,
 ) -> Result<(Felt, Felt)> {
+    let mut reads = Vec::with_capacity(2);
     for selector_name in ["balance_of", "balanceOf"] {
-        let selector = get_selector_from_name(selector_name)
-            .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
         let response = reader
-            .call(FunctionCall {
-                contract_address: token,
-                entry_point_selector: selector,
-                calldata: vec![owner],
-            })
+            .call(erc20_u256_read_call(token, selector_name, vec![owner])?)
             .await;
-        if let Ok(values) = response {
-            if values.len() >= 2 {
-                return Ok((values[0], values[1]));
-            }
+        let found = matches!(&response, Ok(values) if values.len() >= 2);
+        reads.push(response);
+        if found {
+            break;
         }
     }
-    Err(AppError::BadRequest(
-        "Failed to read on-chain token liquidity (balance_of)".to_string(),
-    ))
+    first_u256_pair(reads, "ERC20 balance_of")
 }
 
 // Internal helper that validates hide executor liquidity before private swap execution.
@@ -2209,6 +2058,46 @@ async fn ensure_hide_executor_has_input_balance(
     Ok(())
 }
 
+// Internal helper that builds inputs for `erc20_u256_read_call` in the swap flow.
+// Used to group ERC20 reads into one `OnchainReader::call_batch` round trip.
+fn erc20_u256_read_call(
+    token: Felt,
+    selector_name: &str,
+    calldata: Vec<Felt>,
+) -> Result<FunctionCall> {
+    let selector = get_selector_from_name(selector_name)
+        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
+    Ok(FunctionCall {
+        contract_address: token,
+        entry_point_selector: selector,
+        calldata,
+    })
+}
+
+// Internal helper that parses or transforms values for `u256_pair_from_read` in the swap flow.
+fn u256_pair_from_read(result: Result<Vec<Felt>>) -> Option<(Felt, Felt)> {
+    match result {
+        Ok(values) if values.len() >= 2 => Some((values[0], values[1])),
+        _ => None,
+    }
+}
+
+// Internal helper that parses or transforms values for `first_u256_pair` in the swap flow.
+// Takes the first well-formed read; RPC failures surface as upstream errors, not bad input.
+fn first_u256_pair(reads: Vec<Result<Vec<Felt>>>, label: &str) -> Result<(Felt, Felt)> {
+    let mut last_error: Option<AppError> = None;
+    for read in reads {
+        match read {
+            Ok(values) if values.len() >= 2 => return Ok((values[0], values[1])),
+            Ok(_) => {}
+            Err(err) => last_error = Some(err),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        AppError::BlockchainRPC(format!("{} returned an invalid response", label))
+    }))
+}
+
 // Internal helper that fetches data for `read_erc20_allowance_parts` in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 async fn read_erc20_allowance_parts(
@@ -2217,33 +2106,30 @@ async fn read_erc20_allowance_parts(
     owner: Felt,
     spender: Felt,
 ) -> Result<(Felt, Felt)> {
-    for selector_name in ["allowance"] {
-        let selector = get_selector_from_name(selector_name)
-            .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
-        let response = reader
-            .call(FunctionCall {
-                contract_address: token,
-                entry_point_selector: selector,
-                calldata: vec![owner, spender],
-            })
-            .await;
-        if let Ok(values) = response {
-            if values.len() >= 2 {
-                return Ok((values[0], values[1]));
-            }
-        }
-    }
-    Err(AppError::BadRequest(
-        "Failed to read on-chain token allowance".to_string(),
-    ))
+    let response = reader
+        .call(erc20_u256_read_call(
+            token,
+            "allowance",
+            vec![owner, spender],
+        )?)
+        .await;
+    first_u256_pair(vec![response], "ERC20 allowance")
+}
+
+// Internal helper that parses or transforms values for `oracle_route_dex_ids` in the swap flow.
+// Entries are validated at startup, so unparsable ids can only come from tests and are skipped.
+fn oracle_route_dex_ids(config: &Config) -> Vec<Felt> {
+    config
+        .oracle_route_dex_ids_list()
+        .iter()
+        .filter_map(|dex_id| parse_felt(dex_id).ok())
+        .collect()
 }
 
 // Internal helper that checks conditions for `is_oracle_route` in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
-fn is_oracle_route(route: &OnchainSwapRoute) -> bool {
-    parse_felt(ORACLE_ROUTE_DEX_ID_HEX)
-        .map(|oracle_id| route.dex_id == oracle_id)
-        .unwrap_or(false)
+fn is_oracle_route(route: &OnchainSwapRoute, oracle_dex_ids: &[Felt]) -> bool {
+    oracle_dex_ids.contains(&route.dex_id)
 }
 
 // Internal helper that runs side-effecting logic for `ensure_oracle_route_liquidity` in the swap flow.
@@ -2255,23 +2141,28 @@ async fn ensure_oracle_route_liquidity(
     to_token: &str,
     from_amount: &str,
 ) -> Result<()> {
-    if !is_oracle_route(&context.route) {
+    if !is_oracle_route(&context.route, &oracle_route_dex_ids(&state.config)) {
         return Ok(());
     }
 
     let reader = OnchainReader::from_config(&state.config)?;
-    let (available_low, available_high) =
-        read_erc20_balance_parts(&reader, context.to_token, context.swap_contract).await?;
+    let balance_reads = reader
+        .call_batch(&[
+            erc20_u256_read_call(context.to_token, "balance_of", vec![context.swap_contract])?,
+            erc20_u256_read_call(context.to_token, "balanceOf", vec![context.swap_contract])?,
+        ])
+        .await;
+    let (available_low, available_high) = first_u256_pair(balance_reads, "ERC20 balance_of")?;
 
-    let required_is_higher = u256_is_greater(
+    let buffer_pct = state.config.oracle_route_liquidity_buffer_pct;
+    let required_buffered = buffered_required_output(
         context.route.expected_amount_out_low,
         context.route.expected_amount_out_high,
-        available_low,
-        available_high,
-        "required output",
-        "available liquidity",
+        buffer_pct,
     )?;
-    if !required_is_higher {
+    let (available_low_u, available_high_u) =
+        u256_limbs_to_u128_parts(available_low, available_high, "available liquidity")?;
+    if required_buffered <= u256_from_u128_parts(available_low_u, available_high_u) {
         return Ok(());
     }
 
@@ -2282,47 +2173,318 @@ async fn ensure_oracle_route_liquidity(
     )?;
     let available = onchain_u256_to_f64(available_low, available_high, token_decimals(to_token))?;
     let input_amount = from_amount.trim().parse::<f64>().unwrap_or(0.0);
+    let buffer_factor = 1.0 + buffer_pct / 100.0;
     let max_input = if required > 0.0 && available > 0.0 && input_amount > 0.0 {
-        input_amount * (available / required)
+        input_amount * (available / (required * buffer_factor))
     } else {
         0.0
     };
 
-    Err(AppError::BadRequest(format!(
-        "On-chain liquidity for {} is too low for {} -> {} via the oracle route. Needed about {:.6} {}, but only about {:.6} {} is available in the swap aggregator. Reduce the amount (max about {:.6} {}) or top up liquidity.",
+    Err(AppError::Coded(
+        ErrorCode::OracleRouteLiquidityLow,
+        format!(
+        "On-chain liquidity for {} is too low for {} -> {} via the oracle route. Needed about {:.6} {} (including a {}% safety buffer), but only about {:.6} {} is available in the swap aggregator. Reduce the amount (max about {:.6} {}) or top up liquidity.",
         to_token.to_ascii_uppercase(),
         from_token.to_ascii_uppercase(),
         to_token.to_ascii_uppercase(),
-        required,
+        required * buffer_factor,
         to_token.to_ascii_uppercase(),
+        buffer_pct,
         available,
         to_token.to_ascii_uppercase(),
         max_input.max(0.0),
         from_token.to_ascii_uppercase(),
-    )))
+    ),
+    ))
 }
 
-// Internal helper that fetches data for `fetch_onchain_swap_context` in the swap flow.
-// Keeps validation, normalization, and intent-binding logic centralized.
-async fn fetch_onchain_swap_context(
-    state: &AppState,
-    from_token: &str,
-    to_token: &str,
-    amount: &str,
-) -> Result<OnchainSwapContext> {
-    let swap_contract = configured_swap_contract(state)?.ok_or_else(|| {
-        AppError::BadRequest(
-            "Swap contract is not configured for on-chain swap. Set STARKNET_SWAP_CONTRACT_ADDRESS (or SWAP_AGGREGATOR_ADDRESS).".to_string(),
-        )
-    })?;
-    let from_token_candidates = configured_token_candidates(state, from_token);
-    if from_token_candidates.is_empty() {
-        return Err(AppError::BadRequest(format!(
-            "Token address is not configured for {}",
-            from_token
-        )));
+// Internal helper that supports `u256_from_u128_parts` operations in the swap flow.
+fn u256_from_u128_parts(low: u128, high: u128) -> U256 {
+    (U256::from(high) << 128) | U256::from(low)
+}
+
+// Internal helper that applies the oracle-route liquidity buffer to the required output.
+// The buffer is applied in basis points and saturates instead of overflowing.
+fn buffered_required_output(low: Felt, high: Felt, buffer_pct: f64) -> Result<U256> {
+    let (low_u, high_u) = u256_limbs_to_u128_parts(low, high, "required output")?;
+    let required = u256_from_u128_parts(low_u, high_u);
+    let buffer_bps = (buffer_pct.max(0.0) * 100.0).round() as u64;
+    Ok(required
+        .checked_mul(U256::from(10_000 + buffer_bps))
+        .map(|scaled| scaled / U256::from(10_000))
+        .unwrap_or(U256::MAX))
+}
+
+/// How a resolved swap route will be used; execution tolerates less staleness than quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SwapRouteUse {
+    Quote,
+    Execute,
+}
+
+#[derive(Debug, Clone)]
+struct CachedSwapRoute {
+    context: OnchainSwapContext,
+    amount: String,
+    fetched_at: Instant,
+    refreshing: bool,
+}
+
+// Internal helper that supports `swap_route_cache` operations in the swap flow.
+fn swap_route_cache() -> &'static tokio::sync::RwLock<HashMap<SwapRouteKey, CachedSwapRoute>> {
+    SWAP_ROUTE_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()))
+}
+
+// Internal helper that buckets a raw token amount by its two leading digits and magnitude,
+// so nearby amounts share a cached route. Amounts above u128 are never cached.
+fn swap_route_amount_bucket(amount_low: Felt, amount_high: Felt) -> Option<String> {
+    if amount_high != Felt::ZERO {
+        return None;
     }
-    let to_token_candidates = configured_token_candidates(state, to_token);
+    let digits = felt_to_u128(&amount_low).ok()?.to_string();
+    if digits.len() <= 2 {
+        return Some(digits);
+    }
+    Some(format!("{}e{}", &digits[..2], digits.len() - 2))
+}
+
+// Internal helper that builds inputs for `swap_route_cache_key` in the swap flow.
+fn swap_route_cache_key(
+    from_token: &str,
+    to_token: &str,
+    amount_low: Felt,
+    amount_high: Felt,
+) -> Option<SwapRouteKey> {
+    Some((
+        from_token.trim().to_ascii_uppercase(),
+        to_token.trim().to_ascii_uppercase(),
+        swap_route_amount_bucket(amount_low, amount_high)?,
+    ))
+}
+
+// Internal helper that rescales a cached route's outputs to the requested input amount.
+// Returns `None` when any amount does not fit the u128 fast path.
+fn scale_swap_route(
+    context: &OnchainSwapContext,
+    amount_low: Felt,
+    amount_high: Felt,
+) -> Option<OnchainSwapContext> {
+    if context.amount_low == amount_low && context.amount_high == amount_high {
+        return Some(context.clone());
+    }
+    if amount_high != Felt::ZERO || context.amount_high != Felt::ZERO {
+        return None;
+    }
+    let cached_amount = U256::from(felt_to_u128(&context.amount_low).ok()?);
+    let requested_amount = U256::from(felt_to_u128(&amount_low).ok()?);
+    if cached_amount.is_zero() {
+        return None;
+    }
+    let scale = |low: Felt, high: Felt| -> Option<Felt> {
+        if high != Felt::ZERO {
+            return None;
+        }
+        let scaled = U256::from(felt_to_u128(&low).ok()?) * requested_amount / cached_amount;
+        (scaled <= U256::from(u128::MAX)).then(|| Felt::from(scaled.as_u128()))
+    };
+
+    let mut scaled = context.clone();
+    scaled.amount_low = amount_low;
+    scaled.route.expected_amount_out_low = scale(
+        context.route.expected_amount_out_low,
+        context.route.expected_amount_out_high,
+    )?;
+    scaled.route.min_amount_out_low = scale(
+        context.route.min_amount_out_low,
+        context.route.min_amount_out_high,
+    )?;
+    Some(scaled)
+}
+
+// Internal helper that adapts a cached route to the requested amount for `route_use`.
+// Quotes may use a route rescaled from a nearby amount; execution only reuses a route
+// resolved for exactly the requested amount, since rescaled outputs set `min_amount_out`.
+fn cached_route_for_use(
+    context: &OnchainSwapContext,
+    route_use: SwapRouteUse,
+    amount_low: Felt,
+    amount_high: Felt,
+) -> Option<OnchainSwapContext> {
+    match route_use {
+        SwapRouteUse::Quote => scale_swap_route(context, amount_low, amount_high),
+        SwapRouteUse::Execute => (context.amount_low == amount_low
+            && context.amount_high == amount_high)
+            .then(|| context.clone()),
+    }
+}
+
+// Internal helper that stores a freshly resolved route in the swap flow.
+async fn store_swap_route(key: SwapRouteKey, amount: &str, context: OnchainSwapContext) {
+    let mut cache = swap_route_cache().write().await;
+    if cache.len() >= SWAP_ROUTE_CACHE_MAX_ENTRIES && !cache.contains_key(&key) {
+        cache.clear();
+    }
+    cache.insert(
+        key,
+        CachedSwapRoute {
+            context,
+            amount: amount.to_string(),
+            fetched_at: Instant::now(),
+            refreshing: false,
+        },
+    );
+}
+
+// Internal helper that fetches data for `cached_swap_route` in the swap flow.
+// Entries past half their TTL are refreshed in the background while still being served.
+async fn cached_swap_route(
+    config: &Config,
+    key: &SwapRouteKey,
+    route_use: SwapRouteUse,
+    max_age: Duration,
+    ttl: Duration,
+    amount_low: Felt,
+    amount_high: Felt,
+) -> Option<OnchainSwapContext> {
+    let (context, age, refresh_amount) = {
+        let cache = swap_route_cache().read().await;
+        let entry = cache.get(key)?;
+        let age = entry.fetched_at.elapsed();
+        if age > max_age {
+            return None;
+        }
+        let refresh_amount = (age > ttl / 2 && !entry.refreshing).then(|| entry.amount.clone());
+        (
+            cached_route_for_use(&entry.context, route_use, amount_low, amount_high)?,
+            age,
+            refresh_amount,
+        )
+    };
+
+    if let Some(amount) = refresh_amount {
+        if let Some(entry) = swap_route_cache().write().await.get_mut(key) {
+            entry.refreshing = true;
+        }
+        let config = config.clone();
+        let key = key.clone();
+        tokio::spawn(async move {
+            match fetch_onchain_swap_context_for_config(&config, &key.0, &key.1, &amount).await {
+                Ok(context) => store_swap_route(key, &amount, context).await,
+                Err(err) => {
+                    tracing::debug!("Background swap route refresh failed: {}", err);
+                    swap_route_cache().write().await.remove(&key);
+                }
+            }
+        });
+    }
+    tracing::debug!("Serving cached swap route (age {}ms)", age.as_millis());
+    Some(context)
+}
+
+// Internal helper that fetches data for `fetch_onchain_swap_context` in the swap flow.
+// Serves routes from the pair/amount-bucket cache when fresh enough for `route_use`
+// (execution needs an exact-amount hit); the returned flag tells whether the route came
+// from the cache.
+async fn fetch_onchain_swap_context(
+    state: &AppState,
+    from_token: &str,
+    to_token: &str,
+    amount: &str,
+    route_use: SwapRouteUse,
+) -> Result<(OnchainSwapContext, bool)> {
+    let config = &state.config;
+    let ttl = Duration::from_millis(config.swap_route_cache_ttl_ms);
+    if ttl.is_zero() {
+        let context =
+            fetch_onchain_swap_context_for_config(config, from_token, to_token, amount).await?;
+        return Ok((context, false));
+    }
+    let max_age = match route_use {
+        SwapRouteUse::Quote => ttl,
+        SwapRouteUse::Execute => {
+            ttl.min(Duration::from_millis(config.swap_route_execute_max_age_ms))
+        }
+    };
+    let (amount_low, amount_high) =
+        parse_decimal_to_u256_parts(amount, token_decimals(from_token))?;
+    let key = swap_route_cache_key(from_token, to_token, amount_low, amount_high);
+    if let Some(key) = &key {
+        if let Some(context) = cached_swap_route(
+            config,
+            key,
+            route_use,
+            max_age,
+            ttl,
+            amount_low,
+            amount_high,
+        )
+        .await
+        {
+            return Ok((context, true));
+        }
+    }
+
+    let context =
+        fetch_onchain_swap_context_for_config(config, from_token, to_token, amount).await?;
+    if let Some(key) = key {
+        store_swap_route(key, amount, context.clone()).await;
+    }
+    Ok((context, false))
+}
+
+// Internal helper that resolves the swap route and checks oracle-route liquidity.
+// A cached route that fails the liquidity check is dropped and resolved again on-chain,
+// so a stale route is never served past a failing check.
+async fn resolve_swap_route(
+    state: &AppState,
+    from_token: &str,
+    to_token: &str,
+    amount: &str,
+    route_use: SwapRouteUse,
+) -> Result<OnchainSwapContext> {
+    let (context, cached) =
+        fetch_onchain_swap_context(state, from_token, to_token, amount, route_use).await?;
+    match ensure_oracle_route_liquidity(state, &context, from_token, to_token, amount).await {
+        Ok(()) => Ok(context),
+        Err(_) if cached => {
+            if let Some(key) = swap_route_cache_key(
+                from_token,
+                to_token,
+                context.amount_low,
+                context.amount_high,
+            ) {
+                swap_route_cache().write().await.remove(&key);
+            }
+            let (context, _) =
+                fetch_onchain_swap_context(state, from_token, to_token, amount, route_use).await?;
+            ensure_oracle_route_liquidity(state, &context, from_token, to_token, amount).await?;
+            Ok(context)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+// Internal helper that resolves the on-chain route using config only, so background
+// workers can reuse the same route lookup as the HTTP swap flow.
+async fn fetch_onchain_swap_context_for_config(
+    config: &Config,
+    from_token: &str,
+    to_token: &str,
+    amount: &str,
+) -> Result<OnchainSwapContext> {
+    let swap_contract = configured_swap_contract_from_env()?.ok_or_else(|| {
+        AppError::BadRequest(
+            "Swap contract is not configured for on-chain swap. Set STARKNET_SWAP_CONTRACT_ADDRESS (or SWAP_AGGREGATOR_ADDRESS).".to_string(),
+        )
+    })?;
+    let from_token_candidates = configured_token_candidates_for_config(config, from_token);
+    if from_token_candidates.is_empty() {
+        return Err(AppError::BadRequest(format!(
+            "Token address is not configured for {}",
+            from_token
+        )));
+    }
+    let to_token_candidates = configured_token_candidates_for_config(config, to_token);
     if to_token_candidates.is_empty() {
         return Err(AppError::BadRequest(format!(
             "Token address is not configured for {}",
@@ -2336,7 +2498,7 @@ async fn fetch_onchain_swap_context(
         felt_debug(swap_contract)
     );
 
-    let reader = OnchainReader::from_config(&state.config)?;
+    let reader = OnchainReader::from_config(config)?;
     let route_selector = get_selector_from_name("get_best_swap_route")
         .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
     let mut saw_no_active_dex = false;
@@ -2425,36 +2587,132 @@ fn build_onchain_swap_wallet_calls(
     context: &OnchainSwapContext,
     mev_protected: bool,
 ) -> Vec<StarknetWalletCall> {
+    onchain_swap_call_parts(context, mev_protected)
+        .into_iter()
+        .map(|(to, entrypoint, calldata)| StarknetWalletCall {
+            contract_address: felt_hex(to),
+            entrypoint: entrypoint.to_string(),
+            calldata: calldata.into_iter().map(felt_hex).collect(),
+        })
+        .collect()
+}
+
+// Internal helper that lays out the approve + `execute_swap` pair shared by wallet and
+// relayer submissions.
+fn onchain_swap_call_parts(
+    context: &OnchainSwapContext,
+    mev_protected: bool,
+) -> Vec<(Felt, &'static str, Vec<Felt>)> {
     let mev_flag = if mev_protected { Felt::ONE } else { Felt::ZERO };
     vec![
-        StarknetWalletCall {
-            contract_address: felt_hex(context.from_token),
-            entrypoint: "approve".to_string(),
-            calldata: vec![
-                felt_hex(context.swap_contract),
-                felt_hex(context.amount_low),
-                felt_hex(context.amount_high),
+        (
+            context.from_token,
+            "approve",
+            vec![
+                context.swap_contract,
+                context.amount_low,
+                context.amount_high,
             ],
-        },
-        StarknetWalletCall {
-            contract_address: felt_hex(context.swap_contract),
-            entrypoint: "execute_swap".to_string(),
-            calldata: vec![
-                felt_hex(context.route.dex_id),
-                felt_hex(context.route.expected_amount_out_low),
-                felt_hex(context.route.expected_amount_out_high),
-                felt_hex(context.route.min_amount_out_low),
-                felt_hex(context.route.min_amount_out_high),
-                felt_hex(context.from_token),
-                felt_hex(context.to_token),
-                felt_hex(context.amount_low),
-                felt_hex(context.amount_high),
-                felt_hex(mev_flag),
+        ),
+        (
+            context.swap_contract,
+            "execute_swap",
+            vec![
+                context.route.dex_id,
+                context.route.expected_amount_out_low,
+                context.route.expected_amount_out_high,
+                context.route.min_amount_out_low,
+                context.route.min_amount_out_high,
+                context.from_token,
+                context.to_token,
+                context.amount_low,
+                context.amount_high,
+                mev_flag,
             ],
-        },
+        ),
     ]
 }
 
+/// Whether `token` resolves to at least one configured Starknet address.
+pub(crate) fn swap_token_configured(config: &Config, token: &str) -> bool {
+    !configured_token_candidates_for_config(config, token).is_empty()
+}
+
+/// Configured Starknet addresses for `token`, in lookup order.
+pub(crate) fn swap_token_addresses(config: &Config, token: &str) -> Vec<Felt> {
+    configured_token_candidates_for_config(config, token)
+}
+
+/// Builds the calls the backend account submits to swap its own `from_token` balance
+/// into `to_token` via the swap aggregator. Uses the same on-chain route lookup as
+/// `execute_swap`.
+///
+/// # Notes
+/// * The output stays in the backend account; callers forward the amount the receipt
+///   shows was received, so the user gets any surplus over the routed minimum.
+pub(crate) async fn build_relayed_swap_calls(
+    config: &Config,
+    from_token: &str,
+    to_token: &str,
+    amount: &str,
+) -> Result<Vec<Call>> {
+    let context =
+        fetch_onchain_swap_context_for_config(config, from_token, to_token, amount).await?;
+    let mut calls = Vec::new();
+    for (to, entrypoint, calldata) in onchain_swap_call_parts(&context, false) {
+        let selector = get_selector_from_name(entrypoint)
+            .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
+        calls.push(Call {
+            to,
+            selector,
+            calldata,
+        });
+    }
+    Ok(calls)
+}
+
+/// Builds an ERC20 `transfer` of `amount` `token` from the backend account to `recipient`.
+pub(crate) fn build_token_transfer_call(
+    config: &Config,
+    token: &str,
+    recipient: &str,
+    amount: &str,
+) -> Result<Call> {
+    let token_address = configured_token_candidates_for_config(config, token)
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            AppError::BadRequest(format!("Token address is not configured for {}", token))
+        })?;
+    let (amount_low, amount_high) = parse_decimal_to_u256_parts(amount, token_decimals(token))?;
+    build_token_transfer_call_parts(token_address, recipient, amount_low, amount_high)
+}
+
+/// Builds an ERC20 `transfer` of a raw `amount` (smallest unit) from `token_address`.
+pub(crate) fn build_raw_token_transfer_call(
+    token_address: Felt,
+    recipient: &str,
+    amount: u128,
+) -> Result<Call> {
+    build_token_transfer_call_parts(token_address, recipient, Felt::from(amount), Felt::ZERO)
+}
+
+// Internal helper that builds the `transfer` call shared by the decimal and raw builders.
+fn build_token_transfer_call_parts(
+    token_address: Felt,
+    recipient: &str,
+    amount_low: Felt,
+    amount_high: Felt,
+) -> Result<Call> {
+    let selector = get_selector_from_name("transfer")
+        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
+    Ok(Call {
+        to: token_address,
+        selector,
+        calldata: vec![parse_felt(recipient)?, amount_low, amount_high],
+    })
+}
+
 // Internal helper that supports `first_index_of_any` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 fn first_index_of_any(calldata: &[Felt], candidates: &[Felt]) -> Option<usize> {
@@ -2719,6 +2977,53 @@ fn extract_invoke_sender_and_calldata(tx: &Transaction) -> Result<(Felt, &[Felt]
     }
 }
 
+// Confirmation-wait strategy for user-submitted swap transactions.
+// Configured via SWAP_TX_CONFIRM_ATTEMPTS / SWAP_TX_CONFIRM_INTERVAL_MS / SWAP_TX_REQUIRE_L1_FINALITY.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SwapTxConfirmationPolicy {
+    attempts: u32,
+    interval: Duration,
+    require_l1: bool,
+}
+
+impl SwapTxConfirmationPolicy {
+    // Internal helper that builds inputs for `from_env` in the swap flow.
+    fn from_env() -> Self {
+        let attempts = std::env::var("SWAP_TX_CONFIRM_ATTEMPTS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u32>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(SWAP_TX_CONFIRM_ATTEMPTS_DEFAULT);
+        let interval_ms = std::env::var("SWAP_TX_CONFIRM_INTERVAL_MS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(SWAP_TX_CONFIRM_INTERVAL_MS_DEFAULT);
+        Self {
+            attempts,
+            interval: Duration::from_millis(interval_ms),
+            require_l1: env_flag("SWAP_TX_REQUIRE_L1_FINALITY", false),
+        }
+    }
+
+    // Internal helper that checks conditions for `accepts` in the swap flow.
+    fn accepts(&self, finality: &TransactionFinalityStatus) -> bool {
+        match finality {
+            TransactionFinalityStatus::AcceptedOnL1 => true,
+            TransactionFinalityStatus::AcceptedOnL2 => !self.require_l1,
+            _ => false,
+        }
+    }
+
+    // Internal helper that supports `required_finality_label` operations in the swap flow.
+    fn required_finality_label(&self) -> &'static str {
+        if self.require_l1 {
+            "AcceptedOnL1"
+        } else {
+            "AcceptedOnL2"
+        }
+    }
+}
+
 // Internal helper that supports `verify_onchain_swap_tx_hash` operations in the swap flow.
 // Keeps validation, normalization, and intent-binding logic centralized.
 async fn verify_onchain_swap_tx_hash(
@@ -2748,15 +3053,18 @@ async fn verify_onchain_swap_tx_hash(
             to_token
         )));
     }
+    let policy = SwapTxConfirmationPolicy::from_env();
     let mut last_rpc_error = String::new();
+    let mut last_finality: Option<String> = None;
 
-    for attempt in 0..5 {
+    for attempt in 0..policy.attempts {
+        let has_next = attempt + 1 < policy.attempts;
         let tx = match reader.get_transaction(&tx_hash_felt).await {
             Ok(tx) => tx,
             Err(err) => {
                 last_rpc_error = err.to_string();
-                if attempt < 4 {
-                    sleep(Duration::from_millis(1000)).await;
+                if has_next {
+                    sleep(policy.interval).await;
                     continue;
                 }
                 break;
@@ -2774,18 +3082,25 @@ async fn verify_onchain_swap_tx_hash(
         match reader.get_transaction_receipt(&tx_hash_felt).await {
             Ok(receipt) => {
                 if let ExecutionResult::Reverted { reason } = receipt.receipt.execution_result() {
+                    NotificationService::new(state.db.clone(), state.config.clone())
+                        .notify_swap_failed(
+                            resolved_starknet_user,
+                            from_token,
+                            to_token,
+                            Some(tx_hash),
+                            reason,
+                        )
+                        .await;
                     return Err(AppError::BadRequest(format!(
                         "onchain_tx_hash reverted on Starknet: {}",
                         reason
                     )));
                 }
-                if matches!(
-                    receipt.receipt.finality_status(),
-                    TransactionFinalityStatus::PreConfirmed
-                ) {
-                    last_rpc_error = "transaction still pre-confirmed".to_string();
-                    if attempt < 4 {
-                        sleep(Duration::from_millis(1000)).await;
+                let finality = receipt.receipt.finality_status();
+                if !policy.accepts(finality) {
+                    last_finality = Some(format!("{:?}", finality));
+                    if has_next {
+                        sleep(policy.interval).await;
                         continue;
                     }
                     break;
@@ -2795,22 +3110,32 @@ async fn verify_onchain_swap_tx_hash(
                     "Verified Starknet swap tx {} at block {} with finality {:?}",
                     tx_hash,
                     block_number,
-                    receipt.receipt.finality_status()
+                    finality
                 );
                 return Ok(block_number);
             }
             Err(err) => {
+                // The transaction itself is known; only its receipt is not available yet.
+                last_finality = Some("receipt unavailable".to_string());
                 last_rpc_error = err.to_string();
-                if attempt < 4 {
-                    sleep(Duration::from_millis(1000)).await;
+                if has_next {
+                    sleep(policy.interval).await;
                 }
             }
         }
     }
 
-    Err(AppError::BadRequest(format!(
-        "onchain_tx_hash not found/confirmed on Starknet RPC: {}",
-        last_rpc_error
+    if let Some(finality) = last_finality {
+        return Err(AppError::TransactionPending(format!(
+            "onchain_tx_hash is on Starknet but not final yet (status: {}, required: {}) after {} attempts. Retry shortly.",
+            finality,
+            policy.required_finality_label(),
+            policy.attempts
+        )));
+    }
+    Err(AppError::NotFound(format!(
+        "onchain_tx_hash not found on Starknet RPC after {} attempts: {}",
+        policy.attempts, last_rpc_error
     )))
 }
 
@@ -2866,9 +3191,54 @@ fn normalize_onchain_tx_hash(tx_hash: Option<&str>) -> Result<Option<String>> {
     Ok(Some(raw.to_ascii_lowercase()))
 }
 
+/// GET /api/v1/swap/receipt/{tx_hash}
+///
+/// # Notes
+/// * Only the account that executed the swap may read its receipt; other callers get
+///   the same 404 as for an unknown hash, so receipts do not reveal which hashes exist.
+pub async fn get_swap_receipt(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(tx_hash): Path<String>,
+) -> Result<Json<ApiResponse<SwapReceiptResponse>>> {
+    let user_address = require_starknet_user(&headers, &state).await?;
+    let tx_hash = tx_hash.trim().to_ascii_lowercase();
+    let transaction = state
+        .db
+        .get_transaction(&tx_hash)
+        .await?
+        .filter(|tx| {
+            tx.tx_type == "swap"
+                && tx
+                    .user_address
+                    .trim()
+                    .eq_ignore_ascii_case(user_address.trim())
+        })
+        .ok_or_else(|| AppError::NotFound("Swap not found".to_string()))?;
+
+    let fee_breakdown = state.db.get_swap_fee_breakdown(&tx_hash).await?;
+    let is_private = state.db.is_transaction_private(&tx_hash).await?;
+    Ok(Json(ApiResponse::success(SwapReceiptResponse {
+        usd_notional: transaction.usd_value,
+        points_earned: transaction
+            .processed
+            .then_some(transaction.points_earned)
+            .flatten(),
+        points_pending: !transaction.processed,
+        fee_breakdown,
+        is_private,
+        transaction,
+    })))
+}
+
 /// POST /api/v1/swap/quote
+///
+/// # Notes
+/// * Authentication is optional; when present, the points preview applies the caller's
+///   `DiscountService` profile. The preview assumes a transparent (non-hide) swap.
 pub async fn get_quote(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(req): Json<SwapQuoteRequest>,
 ) -> Result<Json<ApiResponse<SwapQuoteResponse>>> {
     let amount_in: f64 = req
@@ -2880,6 +3250,7 @@ pub async fn get_quote(
             "Amount must be greater than zero".to_string(),
         ));
     }
+    ensure_swap_amount_in_bounds(amount_in)?;
 
     tracing::debug!(
         "Swap quote: from={}, to={}, slippage={}, mode={}",
@@ -2890,11 +3261,7 @@ pub async fn get_quote(
     );
 
     ensure_supported_starknet_swap_pair(&req.from_token, &req.to_token)?;
-    if is_event_only_swap_contract_configured(&state)? {
-        return Err(AppError::BadRequest(
-            "Real-token swap is not active yet. The configured swap contract is still event-only. Activate an on-chain swap router that moves real tokens first.".to_string(),
-        ));
-    }
+    ensure_swap_contract_moves_tokens()?;
 
     if token_address_for(&req.from_token).is_none() || token_address_for(&req.to_token).is_none() {
         return Err(AppError::InvalidToken);
@@ -2910,14 +3277,12 @@ pub async fn get_quote(
     let best_route = aggregator
         .get_best_quote(&req.from_token, &req.to_token, amount_in)
         .await?;
-    let onchain_context =
-        fetch_onchain_swap_context(&state, &req.from_token, &req.to_token, &req.amount).await?;
-    ensure_oracle_route_liquidity(
+    let onchain_context = resolve_swap_route(
         &state,
-        &onchain_context,
         &req.from_token,
         &req.to_token,
         &req.amount,
+        SwapRouteUse::Quote,
     )
     .await?;
     let onchain_calls =
@@ -2952,78 +3317,86 @@ pub async fn get_quote(
     let gas = gas_optimizer.get_optimal_gas_price().await?;
     tracing::debug!("Estimated swap gas cost: {}", estimated_cost);
 
+    let quote_points_multiplier = match require_user(&headers, &state).await {
+        Ok(user_address) => {
+            DiscountService::new(state.db.clone(), state.config.clone())
+                .resolve_for_quote("swap", &user_address)
+                .await
+                .points_multiplier
+        }
+        Err(_) => points_multiplier(0.0, 1),
+    };
+    let from_price = latest_price_usd(&state, &req.from_token)
+        .await
+        .unwrap_or(0.0);
+    let to_price = latest_price_usd(&state, &req.to_token).await.unwrap_or(0.0);
+    let volume_usd = sanitize_usd_notional(normalize_usd_volume(
+        amount_in * from_price,
+        quoted_to_amount * to_price,
+    ));
+    let estimated_points_earned = estimate_swap_points_for_response(
+        volume_usd,
+        derive_usdt_equivalent_volume(
+            &req.from_token,
+            &req.to_token,
+            amount_in,
+            quoted_to_amount,
+            volume_usd,
+        ),
+        false,
+        state.config.is_testnet(),
+        quote_points_multiplier,
+    );
+
+    let from_decimals = display_decimals(&state.config, &req.from_token);
+    let to_decimals = display_decimals(&state.config, &req.to_token);
     let response = SwapQuoteResponse {
         from_amount: req.amount.clone(),
-        to_amount: quoted_to_amount.to_string(),
-        rate: (quoted_to_amount / amount_in).to_string(),
+        to_amount: format_display_amount(quoted_to_amount, to_decimals),
+        rate: format_display_amount(quoted_to_amount / amount_in, to_decimals),
         price_impact: format!("{:.2}%", best_route.price_impact * 100.0),
-        fee: best_route.fee.to_string(),
-        fee_usd: best_route.fee.to_string(),
+        fee: format_display_amount(best_route.fee, from_decimals),
+        fee_usd: format_display_amount(best_route.fee, from_decimals),
         route: best_route.path,
         estimated_gas: gas.standard.to_string(),
         estimated_time: estimated_time_for_dex(best_route.dex.as_str()).to_string(),
         onchain_calls: Some(onchain_calls),
+        estimated_points_earned: format_display_amount(
+            estimated_points_earned,
+            POINTS_DISPLAY_DECIMALS,
+        ),
     };
 
     Ok(Json(ApiResponse::success(response)))
 }
 
-/// POST /api/v1/swap/execute
-pub async fn execute_swap(
-    State(state): State<AppState>,
-    headers: HeaderMap,
-    Json(req): Json<ExecuteSwapRequest>,
-) -> Result<Json<ApiResponse<ExecuteSwapResponse>>> {
-    // 1. VALIDASI DEADLINE
-    let now = chrono::Utc::now().timestamp();
-    if !is_deadline_valid(req.deadline, now) {
-        return Err(AppError::BadRequest(
-            "Transaction deadline expired".to_string(),
-        ));
-    }
-
-    let auth_subject = require_user(&headers, &state).await?;
-    let user_address = require_starknet_user(&headers, &state).await?;
-    let should_hide = should_run_privacy_verification(req.hide_balance.unwrap_or(false));
-    let strict_privacy_mode = should_hide && hide_balance_strict_privacy_mode_enabled();
-    let hide_pool_version = if should_hide {
-        Some(resolve_hide_pool_version(req.privacy.as_ref()))
-    } else {
-        None
-    };
-    if should_hide {
-        match (hide_executor_kind(), hide_pool_version) {
-            (HideExecutorKind::ShieldedPoolV3, Some(HidePoolVersion::V2)) => {
-                return Err(AppError::BadRequest(
-                    "Hide Balance config mismatch: executor is V3 but payload/version resolved to V2."
-                        .to_string(),
-                ));
-            }
-            (HideExecutorKind::ShieldedPoolV2, Some(HidePoolVersion::V3))
-            | (HideExecutorKind::PrivateActionExecutorV1, Some(HidePoolVersion::V3)) => {
-                return Err(AppError::BadRequest(
-                    "Hide Balance V3 requires HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3."
-                        .to_string(),
-                ));
-            }
-            _ => {}
-        }
-    }
-    if should_hide {
-        let max_uses = hide_balance_max_uses_per_day();
-        if max_uses > 0 {
-            let used_today = state.db.count_private_swaps_today(&user_address).await?;
-            if used_today >= max_uses as i64 {
-                return Err(AppError::BadRequest(format!(
-                    "Hide Balance daily limit reached: {}/{} private swaps used today (UTC). Try again tomorrow or increase HIDE_BALANCE_MAX_USES_PER_DAY in backend config.",
-                    used_today, max_uses
-                )));
-            }
+// Internal helper that rejects hide-mode requests whose pool version does not match the executor.
+fn ensure_hide_pool_matches_executor(hide_pool_version: Option<HidePoolVersion>) -> Result<()> {
+    match (hide_executor_kind()?, hide_pool_version) {
+        (HideExecutorKind::ShieldedPoolV3, Some(HidePoolVersion::V2)) => Err(AppError::BadRequest(
+            "Hide Balance config mismatch: executor is V3 but payload/version resolved to V2."
+                .to_string(),
+        )),
+        (HideExecutorKind::ShieldedPoolV2, Some(HidePoolVersion::V3))
+        | (HideExecutorKind::PrivateActionExecutorV1, Some(HidePoolVersion::V3)) => {
+            Err(AppError::BadRequest(
+                "Hide Balance V3 requires a ShieldedPoolV3 executor (auto-detected, or HIDE_BALANCE_EXECUTOR_KIND=shielded_pool_v3)."
+                    .to_string(),
+            ))
         }
+        _ => Ok(()),
     }
+}
 
-    // 2. LOGIKA RECIPIENT
-    let final_recipient = if should_hide && hide_pool_version == Some(HidePoolVersion::V3) {
+// Internal helper that resolves the swap recipient; V3 binds the recipient inside the note.
+fn resolve_execute_swap_recipient(
+    req: &ExecuteSwapRequest,
+    user_address: &str,
+    should_hide: bool,
+    strict_privacy_mode: bool,
+    hide_pool_version: Option<HidePoolVersion>,
+) -> Result<String> {
+    if should_hide && hide_pool_version == Some(HidePoolVersion::V3) {
         if req
             .recipient
             .as_deref()
@@ -3036,16 +3409,386 @@ pub async fn execute_swap(
                     .to_string(),
             ));
         }
-        String::new()
+        Ok(String::new())
     } else {
         resolve_swap_final_recipient(
             req.recipient.as_deref(),
-            &user_address,
+            user_address,
             should_hide,
             strict_privacy_mode,
-        )?
+        )
+    }
+}
+
+// Internal helper that assembles the exact relayer calls for a hide-mode swap in the swap flow.
+// Shared by `execute_swap` and the relayer preview so both submit/inspect identical calldata.
+async fn build_hide_swap_relayer_calls(
+    state: &AppState,
+    req: &ExecuteSwapRequest,
+    user_address: &str,
+    onchain_context: &OnchainSwapContext,
+    final_recipient: &str,
+    hide_pool_version: Option<HidePoolVersion>,
+    strict_privacy_mode: bool,
+) -> Result<(Felt, Vec<Call>)> {
+    let executor = resolve_private_action_executor_felt_for_swap_hide(state).await?;
+    let verifier_kind = select_privacy_verifier(
+        &state.config,
+        req.privacy
+            .as_ref()
+            .and_then(|payload| payload.verifier.as_deref()),
+    )?;
+    let action_selector = get_selector_from_name("execute_swap")
+        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
+    let action_calldata = build_swap_executor_action_calldata(
+        onchain_context,
+        req.mode.eq_ignore_ascii_case("private"),
+    );
+    let recipient_felt = if hide_pool_version == Some(HidePoolVersion::V3) {
+        Felt::ZERO
+    } else {
+        parse_felt(final_recipient)?
+    };
+    let swap_payout_input = SwapPayoutCallInput {
+        action_target: onchain_context.swap_contract,
+        action_selector,
+        action_calldata: &action_calldata,
+        approval_token: onchain_context.from_token,
+        approval_amount_low: onchain_context.amount_low,
+        approval_amount_high: onchain_context.amount_high,
+        payout_token: onchain_context.to_token,
+        recipient: recipient_felt,
+        min_payout_low: onchain_context.route.min_amount_out_low,
+        min_payout_high: onchain_context.route.min_amount_out_high,
+    };
+    validate_swap_payout_input(
+        &swap_payout_input,
+        &configured_token_candidates_for_config(&state.config, &req.from_token),
+        &configured_token_candidates_for_config(&state.config, &req.to_token),
+    )?;
+    let intent_hash =
+        compute_swap_payout_intent_hash_on_executor(state, executor, &swap_payout_input).await?;
+
+    let mut tx_context = AutoPrivacyTxContext {
+        flow: Some("swap".to_string()),
+        from_token: Some(req.from_token.clone()),
+        to_token: Some(req.to_token.clone()),
+        amount: Some(req.amount.clone()),
+        recipient: if hide_pool_version == Some(HidePoolVersion::V3) {
+            None
+        } else {
+            Some(final_recipient.to_string())
+        },
+        from_network: Some("starknet".to_string()),
+        to_network: Some("starknet".to_string()),
+        note_version: if hide_pool_version == Some(HidePoolVersion::V3) {
+            Some("v3".to_string())
+        } else {
+            None
+        },
+        ..Default::default()
+    };
+
+    if hide_pool_version == Some(HidePoolVersion::V3) {
+        let current_root = shielded_current_root(&state.config, executor).await?;
+        tx_context.root = Some(felt_hex(current_root));
+        tx_context.intent_hash = Some(intent_hash.clone());
+        tx_context.action_hash = Some(intent_hash.clone());
+        tx_context.action_target = Some(felt_hex(onchain_context.swap_contract));
+        tx_context.action_selector = Some(felt_hex(action_selector));
+        tx_context.approval_token = Some(felt_hex(onchain_context.from_token));
+        tx_context.payout_token = Some(felt_hex(onchain_context.to_token));
+        tx_context.min_payout = Some(format!(
+            "{}:{}",
+            felt_hex(onchain_context.route.min_amount_out_low),
+            felt_hex(onchain_context.route.min_amount_out_high)
+        ));
+        if let Some(request_privacy) = req.privacy.as_ref() {
+            tx_context.note_commitment = request_privacy.note_commitment.clone();
+            tx_context.denom_id = request_privacy.denom_id.clone();
+            tx_context.nullifier = request_privacy.nullifier.clone();
+        }
+    }
+
+    let request_payload = payload_from_request(
+        req.privacy.as_ref(),
+        verifier_kind.as_str(),
+        PrivacyPayloadLimits::from_config(&state.config),
+    )?;
+    let mut payload = if hide_pool_version == Some(HidePoolVersion::V3) {
+        if request_payload.is_some() {
+            tracing::info!(
+                "Ignoring client-provided Hide Balance V3 proof/public_inputs; regenerating payload server-side"
+            );
+        }
+        generate_auto_garaga_payload(
+            &state.config,
+            user_address,
+            verifier_kind.as_str(),
+            Some(&tx_context),
+        )
+        .await?
+    } else if let Some(request_payload) = request_payload {
+        request_payload
+    } else {
+        generate_auto_garaga_payload(
+            &state.config,
+            user_address,
+            verifier_kind.as_str(),
+            Some(&tx_context),
+        )
+        .await?
     };
 
+    bind_intent_hash_into_payload(&mut payload, &intent_hash)?;
+    if hide_pool_version == Some(HidePoolVersion::V3) {
+        payload.note_version = Some("v3".to_string());
+        ensure_v3_payload_root(&mut payload, &tx_context);
+        let root = payload.root.clone().ok_or_else(|| {
+            AppError::BadRequest(
+                "Hide Balance V3 requires privacy.root in prover payload".to_string(),
+            )
+        })?;
+        if let Err(binding_err) = ensure_public_inputs_bind_root_nullifier(
+            root.as_str(),
+            &payload.nullifier,
+            &payload.public_inputs,
+            "swap hide payload (bound)",
+        ) {
+            tracing::warn!(
+                "swap hide payload V3 binding mismatch; normalizing public_inputs root/nullifier indexes: {}",
+                binding_err
+            );
+            normalize_v3_public_inputs_binding(&mut payload)?;
+            ensure_public_inputs_bind_root_nullifier(
+                root.as_str(),
+                &payload.nullifier,
+                &payload.public_inputs,
+                "swap hide payload (bound, normalized)",
+            )?;
+        }
+        ensure_v3_payload_public_inputs_shape(&payload, "swap hide payload (bound)")?;
+    } else {
+        ensure_public_inputs_bind_nullifier_commitment(
+            &payload.nullifier,
+            &payload.commitment,
+            &payload.public_inputs,
+            "swap hide payload (bound)",
+        )?;
+    }
+
+    let mut relayer_calls: Vec<Call> = Vec::new();
+    if hide_pool_version == Some(HidePoolVersion::V3) {
+        let note_commitment_raw = payload
+            .note_commitment
+            .as_deref()
+            .or_else(|| {
+                if payload.commitment.trim().is_empty()
+                    || payload.commitment.trim().eq_ignore_ascii_case("0x0")
+                {
+                    None
+                } else {
+                    Some(payload.commitment.as_str())
+                }
+            })
+            .ok_or_else(|| {
+                AppError::BadRequest(
+                    "Hide Balance V3 requires privacy.note_commitment in payload".to_string(),
+                )
+            })?;
+        let note_commitment_felt = parse_felt(note_commitment_raw.trim())?;
+        let deposit_ts =
+            shielded_note_deposit_timestamp(&state.config, executor, note_commitment_felt).await?;
+        if deposit_ts == 0 {
+            return Err(ErrorCode::HideNoteNotRegistered.into());
+        }
+        payload.spendable_at_unix = Some(ensure_note_mixing_window_elapsed(deposit_ts)?);
+        ensure_hide_executor_has_input_balance(
+            state,
+            executor,
+            onchain_context,
+            &req.from_token,
+            &req.amount,
+        )
+        .await?;
+    } else if hide_executor_kind()? == HideExecutorKind::ShieldedPoolV2 {
+        let commitment_felt = parse_felt(payload.commitment.trim())?;
+        let user_felt = parse_felt(user_address)?;
+        let note_registered =
+            shielded_note_registered(&state.config, executor, commitment_felt).await?;
+        if !note_registered {
+            if hide_balance_v2_redeem_only_enabled() {
+                return Err(ErrorCode::HideV2RedeemOnly.into());
+            }
+            if strict_privacy_mode {
+                return Err(AppError::BadRequest(
+                        "Hide Balance strict mode blocks inline deposit+swap in one tx. Pre-fund shielded note first, then execute swap in a separate tx."
+                            .to_string(),
+                    ));
+            }
+            let fixed_amount =
+                shielded_fixed_amount(state, executor, onchain_context.from_token).await?;
+            if hide_deposit_requires_asset_rule(
+                &state.config,
+                &req.from_token,
+                (onchain_context.amount_low, onchain_context.amount_high),
+                fixed_amount,
+            )? {
+                relayer_calls.push(build_shielded_set_asset_rule_call(
+                    executor,
+                    onchain_context.from_token,
+                    onchain_context.amount_low,
+                    onchain_context.amount_high,
+                )?);
+            }
+            let reader = OnchainReader::from_config(&state.config)?;
+            let mut funding_reads = reader
+                .call_batch(&[
+                    erc20_u256_read_call(
+                        onchain_context.from_token,
+                        "balance_of",
+                        vec![user_felt],
+                    )?,
+                    erc20_u256_read_call(
+                        onchain_context.from_token,
+                        "allowance",
+                        vec![user_felt, executor],
+                    )?,
+                ])
+                .await
+                .into_iter();
+            let batched_balance = funding_reads.next().and_then(u256_pair_from_read);
+            let batched_allowance = funding_reads.next().and_then(u256_pair_from_read);
+            let (balance_low, balance_high) = match batched_balance {
+                Some(parts) => parts,
+                None => {
+                    read_erc20_balance_parts(&reader, onchain_context.from_token, user_felt).await?
+                }
+            };
+            if u256_is_greater(
+                onchain_context.amount_low,
+                onchain_context.amount_high,
+                balance_low,
+                balance_high,
+                "requested hide deposit",
+                "user balance",
+            )? {
+                let available =
+                    onchain_u256_to_f64(balance_low, balance_high, token_decimals(&req.from_token))
+                        .unwrap_or(0.0);
+                return Err(AppError::BadRequest(format!(
+                    "Shielded note funding failed: insufficient {} balance. Needed {}, available {:.8}.",
+                    req.from_token.to_ascii_uppercase(),
+                    req.amount,
+                    available
+                )));
+            }
+            let (allowance_low, allowance_high) = match batched_allowance {
+                Some(parts) => parts,
+                None => {
+                    read_erc20_allowance_parts(
+                        &reader,
+                        onchain_context.from_token,
+                        user_felt,
+                        executor,
+                    )
+                    .await?
+                }
+            };
+            if u256_is_greater(
+                onchain_context.amount_low,
+                onchain_context.amount_high,
+                allowance_low,
+                allowance_high,
+                "requested hide deposit",
+                "token allowance",
+            )? {
+                let approved = onchain_u256_to_f64(
+                    allowance_low,
+                    allowance_high,
+                    token_decimals(&req.from_token),
+                )
+                .unwrap_or(0.0);
+                return Err(AppError::BadRequest(format!(
+                    "Shielded note funding failed: insufficient allowance. Approve {} {} to executor {} first (current allowance {:.8}).",
+                    req.amount,
+                    req.from_token.to_ascii_uppercase(),
+                    felt_hex(executor),
+                    approved
+                )));
+            }
+            relayer_calls.push(build_shielded_deposit_fixed_for_call(
+                executor,
+                user_felt,
+                onchain_context.from_token,
+                commitment_felt,
+            )?);
+        }
+    }
+    let submit_call = build_submit_private_intent_call(executor, &payload)?;
+    let execute_call =
+        build_execute_private_swap_with_payout_call(executor, &payload, &swap_payout_input)?;
+    relayer_calls.push(submit_call);
+    relayer_calls.push(execute_call);
+    Ok((executor, relayer_calls))
+}
+
+// Internal helper that maps a relayer call selector back to its executor entrypoint name.
+fn relayer_call_entrypoint(selector: Felt) -> Option<&'static str> {
+    [
+        "set_asset_rule",
+        "deposit_fixed_for",
+        "submit_private_intent",
+        "submit_private_action",
+        "submit_private_swap",
+        "execute_private_swap_with_payout",
+    ]
+    .into_iter()
+    .find(|name| get_selector_from_name(name).ok() == Some(selector))
+}
+
+/// POST /api/v1/swap/relayer-preview
+///
+/// Assembles the exact relayer calls a hide-mode `execute_swap` would submit
+/// (V2 set-asset-rule/deposit prefixes, submit, execute) and returns them without submitting.
+///
+/// # Notes
+/// * Runs the same maintenance, relayer allowlist/rate-limit and hide-pair guards as
+///   `execute_swap` before any proof is generated; proving shares the prover slot cap.
+pub async fn preview_hide_swap_relayer_calls(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<ExecuteSwapRequest>,
+) -> Result<Json<ApiResponse<RelayerCallsPreviewResponse>>> {
+    maintenance::ensure_writable(&headers, &state).await?;
+    let user_address = require_starknet_user(&headers, &state).await?;
+    if !should_run_privacy_verification(req.hide_balance.unwrap_or(false)) {
+        return Err(AppError::BadRequest(
+            "Relayer preview requires hide_balance=true".to_string(),
+        ));
+    }
+    if !hide_balance_relayer_pool_enabled() {
+        return Err(AppError::BadRequest(
+            "Relayer preview requires the Hide Balance relayer pool".to_string(),
+        ));
+    }
+    ensure_relayer_access(&state, &user_address).await?;
+    let strict_privacy_mode = hide_balance_strict_privacy_mode_enabled();
+    let hide_pool_version = Some(resolve_hide_pool_version(req.privacy.as_ref()));
+    ensure_hide_pool_matches_executor(hide_pool_version)?;
+    ensure_hide_swap_pair_allowed(
+        &state.config.hide_balance_swap_pairs_list(),
+        &req.from_token,
+        &req.to_token,
+    )?;
+    let final_recipient = resolve_execute_swap_recipient(
+        &req,
+        &user_address,
+        true,
+        strict_privacy_mode,
+        hide_pool_version,
+    )?;
+
     let amount_in: f64 = req
         .amount
         .parse()
@@ -3055,26 +3798,125 @@ pub async fn execute_swap(
             "Amount must be greater than zero".to_string(),
         ));
     }
-
+    ensure_swap_amount_in_bounds(amount_in)?;
     ensure_supported_starknet_swap_pair(&req.from_token, &req.to_token)?;
-    if is_event_only_swap_contract_configured(&state)? {
+    ensure_swap_contract_moves_tokens()?;
+    if token_address_for(&req.from_token).is_none() || token_address_for(&req.to_token).is_none() {
+        return Err(AppError::InvalidToken);
+    }
+    let onchain_context = resolve_swap_route(
+        &state,
+        &req.from_token,
+        &req.to_token,
+        &req.amount,
+        SwapRouteUse::Execute,
+    )
+    .await?;
+
+    let (executor, relayer_calls) = build_hide_swap_relayer_calls(
+        &state,
+        &req,
+        &user_address,
+        &onchain_context,
+        &final_recipient,
+        hide_pool_version,
+        strict_privacy_mode,
+    )
+    .await?;
+
+    let calls = relayer_calls
+        .into_iter()
+        .map(|call| RelayerCallPreview {
+            contract_address: felt_hex(call.to),
+            entrypoint: relayer_call_entrypoint(call.selector).map(ToOwned::to_owned),
+            selector: felt_hex(call.selector),
+            calldata: call.calldata.into_iter().map(felt_hex).collect(),
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(RelayerCallsPreviewResponse {
+        executor: felt_hex(executor),
+        executor_kind: hide_executor_kind()?.as_str().to_string(),
+        calls,
+    })))
+}
+
+/// POST /api/v1/swap/execute
+pub async fn execute_swap(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<ExecuteSwapRequest>,
+) -> Result<Json<ApiResponse<ExecuteSwapResponse>>> {
+    maintenance::ensure_writable(&headers, &state).await?;
+
+    // 1. VALIDASI DEADLINE
+    let now = chrono::Utc::now().timestamp();
+    validate_deadline(req.deadline, now, state.config.max_deadline_seconds)?;
+
+    let auth_subject = require_user(&headers, &state).await?;
+    let user_address = require_starknet_user(&headers, &state).await?;
+    let should_hide = should_run_privacy_verification(req.hide_balance.unwrap_or(false));
+    let strict_privacy_mode = should_hide && hide_balance_strict_privacy_mode_enabled();
+    let hide_pool_version = if should_hide {
+        Some(resolve_hide_pool_version(req.privacy.as_ref()))
+    } else {
+        None
+    };
+    if should_hide {
+        ensure_hide_pool_matches_executor(hide_pool_version)?;
+        ensure_hide_swap_pair_allowed(
+            &state.config.hide_balance_swap_pairs_list(),
+            &req.from_token,
+            &req.to_token,
+        )?;
+    }
+    if should_hide {
+        let max_uses = hide_balance_max_uses_per_day();
+        if max_uses > 0 {
+            let used_today = state.db.count_private_swaps_today(&user_address).await?;
+            if used_today >= max_uses as i64 {
+                return Err(AppError::BadRequest(format!(
+                    "Hide Balance daily limit reached: {}/{} private swaps used today (UTC). Try again tomorrow or increase HIDE_BALANCE_MAX_USES_PER_DAY in backend config.",
+                    used_today, max_uses
+                )));
+            }
+        }
+    }
+
+    // 2. LOGIKA RECIPIENT
+    let final_recipient = resolve_execute_swap_recipient(
+        &req,
+        &user_address,
+        should_hide,
+        strict_privacy_mode,
+        hide_pool_version,
+    )?;
+
+    let amount_in: f64 = req
+        .amount
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid amount".to_string()))?;
+    if !amount_in.is_finite() || amount_in <= 0.0 {
         return Err(AppError::BadRequest(
-            "Real-token swap is not active yet. The configured swap contract is still event-only. Activate an on-chain swap router that moves real tokens first.".to_string(),
+            "Amount must be greater than zero".to_string(),
         ));
     }
+    ensure_swap_amount_in_bounds(amount_in)?;
+    let amount_in_decimal = parse_amount_decimal(&req.amount, amount_in);
+
+    ensure_supported_starknet_swap_pair(&req.from_token, &req.to_token)?;
+    ensure_swap_contract_moves_tokens()?;
 
     if token_address_for(&req.from_token).is_none() || token_address_for(&req.to_token).is_none() {
         return Err(AppError::InvalidToken);
     }
 
-    let onchain_context =
-        fetch_onchain_swap_context(&state, &req.from_token, &req.to_token, &req.amount).await?;
-    ensure_oracle_route_liquidity(
+    let onchain_context = resolve_swap_route(
         &state,
-        &onchain_context,
         &req.from_token,
         &req.to_token,
         &req.amount,
+        SwapRouteUse::Execute,
     )
     .await?;
 
@@ -3105,297 +3947,55 @@ pub async fn execute_swap(
 
     let (tx_hash, onchain_block_number, is_user_signed_onchain, privacy_verification_tx) =
         if use_relayer_pool_hide {
-            let executor = resolve_private_action_executor_felt_for_swap_hide(&state).await?;
-            let verifier_kind = parse_privacy_verifier_kind(
-                req.privacy
-                    .as_ref()
-                    .and_then(|payload| payload.verifier.as_deref()),
-            )?;
-            let action_selector = get_selector_from_name("execute_swap")
-                .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
-            let action_calldata = build_swap_executor_action_calldata(
-                &onchain_context,
-                req.mode.eq_ignore_ascii_case("private"),
-            );
-            let recipient_felt = if hide_pool_version == Some(HidePoolVersion::V3) {
-                Felt::ZERO
-            } else {
-                parse_felt(&final_recipient)?
-            };
-            let swap_payout_input = SwapPayoutCallInput {
-                action_target: onchain_context.swap_contract,
-                action_selector,
-                action_calldata: &action_calldata,
-                approval_token: onchain_context.from_token,
-                approval_amount_low: onchain_context.amount_low,
-                approval_amount_high: onchain_context.amount_high,
-                payout_token: onchain_context.to_token,
-                recipient: recipient_felt,
-                min_payout_low: onchain_context.route.min_amount_out_low,
-                min_payout_high: onchain_context.route.min_amount_out_high,
-            };
-            let intent_hash =
-                compute_swap_payout_intent_hash_on_executor(&state, executor, &swap_payout_input)
-                    .await?;
-
-            let mut tx_context = AutoPrivacyTxContext {
-                flow: Some("swap".to_string()),
-                from_token: Some(req.from_token.clone()),
-                to_token: Some(req.to_token.clone()),
-                amount: Some(req.amount.clone()),
-                recipient: if hide_pool_version == Some(HidePoolVersion::V3) {
-                    None
-                } else {
-                    Some(final_recipient.to_string())
-                },
-                from_network: Some("starknet".to_string()),
-                to_network: Some("starknet".to_string()),
-                note_version: if hide_pool_version == Some(HidePoolVersion::V3) {
-                    Some("v3".to_string())
-                } else {
-                    None
-                },
-                ..Default::default()
-            };
-
-            if hide_pool_version == Some(HidePoolVersion::V3) {
-                let current_root = shielded_current_root(&state, executor).await?;
-                tx_context.root = Some(felt_hex(current_root));
-                tx_context.intent_hash = Some(intent_hash.clone());
-                tx_context.action_hash = Some(intent_hash.clone());
-                tx_context.action_target = Some(felt_hex(onchain_context.swap_contract));
-                tx_context.action_selector = Some(felt_hex(action_selector));
-                tx_context.approval_token = Some(felt_hex(onchain_context.from_token));
-                tx_context.payout_token = Some(felt_hex(onchain_context.to_token));
-                tx_context.min_payout = Some(format!(
-                    "{}:{}",
-                    felt_hex(onchain_context.route.min_amount_out_low),
-                    felt_hex(onchain_context.route.min_amount_out_high)
-                ));
-                if let Some(request_privacy) = req.privacy.as_ref() {
-                    tx_context.note_commitment = request_privacy.note_commitment.clone();
-                    tx_context.denom_id = request_privacy.denom_id.clone();
-                    tx_context.nullifier = request_privacy.nullifier.clone();
-                }
-            }
-
-            let request_payload =
-                payload_from_request(req.privacy.as_ref(), verifier_kind.as_str());
-            let mut payload = if hide_pool_version == Some(HidePoolVersion::V3) {
-                if request_payload.is_some() {
-                    tracing::info!(
-                        "Ignoring client-provided Hide Balance V3 proof/public_inputs; regenerating payload server-side"
-                    );
-                }
-                generate_auto_garaga_payload(
-                    &state.config,
-                    &user_address,
-                    verifier_kind.as_str(),
-                    Some(&tx_context),
-                )
-                .await?
-            } else if let Some(request_payload) = request_payload {
-                request_payload
-            } else {
-                generate_auto_garaga_payload(
-                    &state.config,
-                    &user_address,
-                    verifier_kind.as_str(),
-                    Some(&tx_context),
-                )
-                .await?
-            };
-
-            bind_intent_hash_into_payload(&mut payload, &intent_hash)?;
-            if hide_pool_version == Some(HidePoolVersion::V3) {
-                payload.note_version = Some("v3".to_string());
-                ensure_v3_payload_root(&mut payload, &tx_context);
-                let root = payload.root.clone().ok_or_else(|| {
-                    AppError::BadRequest(
-                        "Hide Balance V3 requires privacy.root in prover payload".to_string(),
-                    )
-                })?;
-                if let Err(binding_err) = ensure_public_inputs_bind_root_nullifier(
-                    root.as_str(),
-                    &payload.nullifier,
-                    &payload.public_inputs,
-                    "swap hide payload (bound)",
-                ) {
-                    tracing::warn!(
-                        "swap hide payload V3 binding mismatch; normalizing public_inputs root/nullifier indexes: {}",
-                        binding_err
-                    );
-                    normalize_v3_public_inputs_binding(&mut payload)?;
-                    ensure_public_inputs_bind_root_nullifier(
-                        root.as_str(),
-                        &payload.nullifier,
-                        &payload.public_inputs,
-                        "swap hide payload (bound, normalized)",
-                    )?;
-                }
-                ensure_v3_payload_public_inputs_shape(&payload, "swap hide payload (bound)")?;
-            } else {
-                ensure_public_inputs_bind_nullifier_commitment(
-                    &payload.nullifier,
-                    &payload.commitment,
-                    &payload.public_inputs,
-                    "swap hide payload (bound)",
-                )?;
-            }
-
             let relayer = RelayerService::from_config(&state.config)
                 .map_err(map_hide_relayer_invoke_error)?;
-            let mut relayer_calls: Vec<Call> = Vec::new();
-            if hide_pool_version == Some(HidePoolVersion::V3) {
-                let note_commitment_raw = payload
-                    .note_commitment
-                    .as_deref()
-                    .or_else(|| {
-                        if payload.commitment.trim().is_empty()
-                            || payload.commitment.trim().eq_ignore_ascii_case("0x0")
-                        {
-                            None
-                        } else {
-                            Some(payload.commitment.as_str())
-                        }
-                    })
-                    .ok_or_else(|| {
-                        AppError::BadRequest(
-                            "Hide Balance V3 requires privacy.note_commitment in payload"
-                                .to_string(),
-                        )
-                    })?;
-                let note_commitment_felt = parse_felt(note_commitment_raw.trim())?;
-                let deposit_ts =
-                    shielded_note_deposit_timestamp(&state, executor, note_commitment_felt).await?;
-                if deposit_ts == 0 {
-                    return Err(AppError::BadRequest(
-                        "Hide Balance V3 note is not registered yet. Deposit the note first."
-                            .to_string(),
-                    ));
-                }
-                payload.spendable_at_unix =
-                    Some(deposit_ts.saturating_add(hide_balance_min_note_age_secs()));
-                ensure_hide_executor_has_input_balance(
-                    &state,
-                    executor,
-                    &onchain_context,
-                    &req.from_token,
-                    &req.amount,
-                )
-                .await?;
-            } else if hide_executor_kind() == HideExecutorKind::ShieldedPoolV2 {
-                let commitment_felt = parse_felt(payload.commitment.trim())?;
-                let user_felt = parse_felt(&user_address)?;
-                let note_registered =
-                    shielded_note_registered(&state, executor, commitment_felt).await?;
-                if !note_registered {
-                    if hide_balance_v2_redeem_only_enabled() {
-                        return Err(AppError::BadRequest(
-                            "Hide Balance V2 is redeem-only. New note deposits to V2 are blocked; use V3 for new notes."
-                                .to_string(),
-                        ));
-                    }
-                    if strict_privacy_mode {
-                        return Err(AppError::BadRequest(
-                                "Hide Balance strict mode blocks inline deposit+swap in one tx. Pre-fund shielded note first, then execute swap in a separate tx."
-                                    .to_string(),
-                            ));
-                    }
-                    let (fixed_low, fixed_high) =
-                        shielded_fixed_amount(&state, executor, onchain_context.from_token).await?;
-                    if fixed_low != onchain_context.amount_low
-                        || fixed_high != onchain_context.amount_high
-                    {
-                        relayer_calls.push(build_shielded_set_asset_rule_call(
-                            executor,
-                            onchain_context.from_token,
-                            onchain_context.amount_low,
-                            onchain_context.amount_high,
-                        )?);
-                    }
-                    let reader = OnchainReader::from_config(&state.config)?;
-                    let (balance_low, balance_high) =
-                        read_erc20_balance_parts(&reader, onchain_context.from_token, user_felt)
-                            .await?;
-                    if u256_is_greater(
-                        onchain_context.amount_low,
-                        onchain_context.amount_high,
-                        balance_low,
-                        balance_high,
-                        "requested hide deposit",
-                        "user balance",
-                    )? {
-                        let available = onchain_u256_to_f64(
-                            balance_low,
-                            balance_high,
-                            token_decimals(&req.from_token),
-                        )
-                        .unwrap_or(0.0);
-                        return Err(AppError::BadRequest(format!(
-                            "Shielded note funding failed: insufficient {} balance. Needed {}, available {:.8}.",
-                            req.from_token.to_ascii_uppercase(),
-                            req.amount,
-                            available
-                        )));
-                    }
-                    let (allowance_low, allowance_high) = read_erc20_allowance_parts(
-                        &reader,
-                        onchain_context.from_token,
-                        user_felt,
-                        executor,
-                    )
-                    .await?;
-                    if u256_is_greater(
-                        onchain_context.amount_low,
-                        onchain_context.amount_high,
-                        allowance_low,
-                        allowance_high,
-                        "requested hide deposit",
-                        "token allowance",
-                    )? {
-                        let approved = onchain_u256_to_f64(
-                            allowance_low,
-                            allowance_high,
-                            token_decimals(&req.from_token),
-                        )
-                        .unwrap_or(0.0);
-                        return Err(AppError::BadRequest(format!(
-                            "Shielded note funding failed: insufficient allowance. Approve {} {} to executor {} first (current allowance {:.8}).",
-                            req.amount,
-                            req.from_token.to_ascii_uppercase(),
-                            felt_hex(executor),
-                            approved
-                        )));
+            let (executor, relayer_calls) = build_hide_swap_relayer_calls(
+                &state,
+                &req,
+                &user_address,
+                &onchain_context,
+                &final_recipient,
+                hide_pool_version,
+                strict_privacy_mode,
+            )
+            .await?;
+            let submitted = match submit_relayer_calls(
+                &state,
+                &relayer,
+                &user_address,
+                "hide_swap",
+                relayer_calls,
+            )
+            .await
+            {
+                Ok(submitted) => submitted,
+                Err(err) => {
+                    if let Some(reason) = relayer_revert_reason(&err) {
+                        NotificationService::new(state.db.clone(), state.config.clone())
+                            .notify_swap_failed(
+                                &user_address,
+                                &req.from_token,
+                                &req.to_token,
+                                None,
+                                reason,
+                            )
+                            .await;
                     }
-                    relayer_calls.push(build_shielded_deposit_fixed_for_call(
-                        executor,
-                        user_felt,
-                        onchain_context.from_token,
-                        commitment_felt,
-                    )?);
+                    return Err(map_hide_relayer_invoke_error(err));
                 }
-            }
-            let submit_call = build_submit_private_intent_call(executor, &payload)?;
-            let execute_call = build_execute_private_swap_with_payout_call(
-                executor,
-                &payload,
-                &swap_payout_input,
-            )?;
-            relayer_calls.push(submit_call);
-            relayer_calls.push(execute_call);
-            let submitted = relayer
-                .submit_calls(relayer_calls)
-                .await
-                .map_err(map_hide_relayer_invoke_error)?;
+            };
+            // The relayer waits for inclusion, so the receipt's block can be stored directly;
+            // rows that still end up at 0 are backfilled by the transaction reconciler.
+            let block_number = i64::try_from(submitted.block_number).unwrap_or_default();
             let tx_hash = submitted.tx_hash;
             tracing::info!(
-                "Submitted hide swap via relayer pool user={} tx_hash={} executor={}",
+                "Submitted hide swap via relayer pool user={} tx_hash={} executor={} block={}",
                 user_address,
                 tx_hash,
-                felt_hex(executor)
+                felt_hex(executor),
+                block_number
             );
-            (tx_hash.clone(), 0_i64, false, Some(tx_hash))
+            (tx_hash.clone(), block_number, false, Some(tx_hash))
         } else {
             let onchain_tx_hash = normalized_onchain_tx_hash.clone().ok_or_else(|| {
                 AppError::BadRequest(
@@ -3456,9 +4056,19 @@ pub async fn execute_swap(
         .await
         .unwrap_or_default();
 
-    let nft_discount_percent = refresh_nft_discount_for_submit(&state, &user_address).await;
-    let fee_before_discount = base_fee(amount_in) + mev_fee_for_mode(&req.mode, amount_in);
-    let total_fee = total_fee(amount_in, &req.mode, nft_discount_percent);
+    let discount_service = DiscountService::new(state.db.clone(), state.config.clone());
+    let discounts = discount_service
+        .resolve_for_submit("swap", &user_address, Some(&user_address))
+        .await;
+    let fee_discount_percent = discounts.fee_discount_percent;
+    let total_fee_persisted = total_fee_decimal(amount_in_decimal, &req.mode, fee_discount_percent);
+    let fee_before_discount = ensure_finite(
+        total_fee_decimal(amount_in_decimal, &req.mode, 0.0)
+            .to_f64()
+            .unwrap_or(f64::NAN),
+        "fee",
+    )?;
+    let total_fee = ensure_finite(total_fee_persisted.to_f64().unwrap_or(f64::NAN), "fee")?;
     let fee_discount_saved = (fee_before_discount - total_fee).max(0.0);
     let from_price = latest_price_usd(&state, &req.from_token).await?;
     let to_price = latest_price_usd(&state, &req.to_token).await?;
@@ -3473,24 +4083,12 @@ pub async fn execute_swap(
         expected_out,
         volume_usd,
     );
-    let user_ai_level = match state.db.get_user_ai_level(&user_address).await {
-        Ok(level) => level,
-        Err(err) => {
-            tracing::warn!(
-                "Failed to resolve user AI level for swap points bonus (user={}): {}",
-                user_address,
-                err
-            );
-            1
-        }
-    };
     let estimated_points_earned = estimate_swap_points_for_response(
         volume_usd,
         usdt_equivalent_volume,
         should_hide,
         state.config.is_testnet(),
-        nft_discount_percent,
-        user_ai_level,
+        discounts.points_multiplier,
     );
 
     // Simpan ke database
@@ -3501,30 +4099,53 @@ pub async fn execute_swap(
         tx_type: "swap".to_string(),
         token_in: Some(req.from_token.clone()),
         token_out: Some(req.to_token.clone()),
-        amount_in: Some(rust_decimal::Decimal::from_f64_retain(amount_in).unwrap()),
-        amount_out: Some(rust_decimal::Decimal::from_f64_retain(expected_out).unwrap()),
+        amount_in: Some(amount_in_decimal),
+        amount_out: Some(
+            rust_decimal::Decimal::from_f64_retain(ensure_finite(expected_out, "output amount")?)
+                .unwrap_or_default(),
+        ),
         usd_value: Some(rust_decimal::Decimal::from_f64_retain(volume_usd).unwrap_or_default()),
-        fee_paid: Some(rust_decimal::Decimal::from_f64_retain(total_fee).unwrap()),
+        fee_paid: Some(total_fee_persisted),
         points_earned: Some(rust_decimal::Decimal::ZERO),
         timestamp: chrono::Utc::now(),
         processed: false,
     };
 
-    state.db.save_transaction(&tx).await?;
+    let fee_usd = sanitize_usd_notional(total_fee * from_price);
+    state
+        .db
+        .save_transaction_with_fee(
+            &tx,
+            Some(FeeLedgerEntry {
+                token: &req.from_token,
+                fee_amount: total_fee_persisted,
+                fee_usd: rust_decimal::Decimal::from_f64_retain(fee_usd).unwrap_or_default(),
+                fee_recipient: state.config.fee_recipient(),
+            }),
+        )
+        .await?;
     if should_hide {
         state.db.mark_transaction_private(&tx_hash).await?;
     }
-    if nft_discount_percent > 0.0 {
-        record_nft_discount_usage_after_submit(&state, &user_address).await;
-        let consume_result = consume_nft_usage(&state.config, &user_address, "swap").await;
-        if let Err(err) = consume_result {
-            tracing::warn!(
-                "Failed to consume NFT discount usage after swap success: user={} tx_hash={} err={}",
-                user_address,
-                tx_hash,
-                err
-            );
-        }
+    let breakdown = swap_fee_breakdown(
+        &tx_hash,
+        amount_in_decimal,
+        &req.mode,
+        discounts.nft_discount_percent,
+        discounts.stake_discount_percent,
+        fee_discount_percent,
+    );
+    if let Err(err) = state.db.save_swap_fee_breakdown(&breakdown).await {
+        tracing::warn!(
+            "Failed to store swap fee breakdown for {}: {}",
+            tx_hash,
+            err
+        );
+    }
+    if discounts.nft_discount_percent > 0.0 {
+        discount_service
+            .record_nft_usage("swap", &user_address, &tx_hash)
+            .await;
     }
 
     if let Ok(batch) = gas_optimizer.optimize_batch(vec![tx_hash.clone()]).await {
@@ -3567,6 +4188,8 @@ pub async fn execute_swap(
         final_recipient
     );
 
+    let from_decimals = display_decimals(&state.config, &req.from_token);
+    let to_decimals = display_decimals(&state.config, &req.to_token);
     Ok(Json(ApiResponse::success(ExecuteSwapResponse {
         tx_hash,
         status: if is_user_signed_onchain {
@@ -3575,13 +4198,19 @@ pub async fn execute_swap(
             "submitted_relayer".to_string()
         },
         from_amount: req.amount,
-        to_amount: expected_out.to_string(),
-        actual_rate: (expected_out / amount_in).to_string(),
-        fee_paid: total_fee.to_string(),
-        fee_before_discount: fee_before_discount.to_string(),
-        fee_discount_saved: fee_discount_saved.to_string(),
-        nft_discount_percent: nft_discount_percent.to_string(),
-        estimated_points_earned: estimated_points_earned.to_string(),
+        to_amount: format_display_amount(expected_out, to_decimals),
+        actual_rate: format_display_amount(expected_out / amount_in, to_decimals),
+        fee_paid: format_display_amount(total_fee, from_decimals),
+        fee_before_discount: format_display_amount(fee_before_discount, from_decimals),
+        fee_discount_saved: format_display_amount(fee_discount_saved, from_decimals),
+        nft_discount_percent: discounts.nft_discount_percent.to_string(),
+        stake_discount_percent: discounts.stake_discount_percent.to_string(),
+        fee_discount_percent: fee_discount_percent.to_string(),
+        discount_sources: discounts.sources(),
+        estimated_points_earned: format_display_amount(
+            estimated_points_earned,
+            POINTS_DISPLAY_DECIMALS,
+        ),
         points_pending: true,
         privacy_tx_hash: privacy_verification_tx,
     })))
@@ -3591,6 +4220,308 @@ pub async fn execute_swap(
 mod tests {
     use super::*;
 
+    #[test]
+    // Internal helper that supports `default_oracle_route_dex_id_is_ascii_orcl` operations.
+    fn default_oracle_route_dex_id_is_ascii_orcl() {
+        let parsed = parse_felt(crate::constants::DEFAULT_ORACLE_ROUTE_DEX_IDS).unwrap();
+        assert_eq!(parsed, Felt::from_bytes_be_slice(b"ORCL"));
+    }
+
+    #[test]
+    // Internal helper that checks conditions for `check_note_mixing_window_at_boundary`.
+    fn check_note_mixing_window_at_boundary() {
+        let deposit_ts = 1_700_000_000;
+        let min_age = 3_600;
+        let boundary = deposit_ts + min_age;
+
+        assert_eq!(
+            check_note_mixing_window(deposit_ts, boundary, min_age, 0).expect("exact boundary"),
+            boundary
+        );
+        let err = check_note_mixing_window(deposit_ts, boundary - 1, min_age, 0)
+            .expect_err("one second early");
+        assert!(matches!(
+            err,
+            AppError::Coded(ErrorCode::HideNoteMixingWindow, ref message)
+                if message.contains("1 seconds remaining")
+                    && message.contains("deposit_ts=1700000000")
+        ));
+
+        assert_eq!(
+            check_note_mixing_window(deposit_ts, boundary - 5, min_age, 5)
+                .expect("within tolerance"),
+            boundary
+        );
+        assert!(check_note_mixing_window(deposit_ts, boundary - 6, min_age, 5).is_err());
+        assert!(check_note_mixing_window(deposit_ts, deposit_ts, 10, 60).is_ok());
+    }
+
+    #[test]
+    // Internal helper that checks conditions for `validate_swap_payout_input_rejects_mismatches`.
+    fn validate_swap_payout_input_rejects_mismatches() {
+        let from_token = Felt::from(0x111_u64);
+        let to_token = Felt::from(0x222_u64);
+        let input =
+            |approval_token: Felt, payout_token: Felt, min_payout_low: Felt| SwapPayoutCallInput {
+                action_target: Felt::ONE,
+                action_selector: Felt::ONE,
+                action_calldata: &[],
+                approval_token,
+                approval_amount_low: Felt::from(10_u64),
+                approval_amount_high: Felt::ZERO,
+                payout_token,
+                recipient: Felt::ZERO,
+                min_payout_low,
+                min_payout_high: Felt::ZERO,
+            };
+        let check = |input: &SwapPayoutCallInput<'_>| {
+            validate_swap_payout_input(input, &[from_token], &[to_token])
+        };
+
+        assert!(check(&input(from_token, to_token, Felt::ONE)).is_ok());
+        assert!(check(&input(to_token, to_token, Felt::ONE)).is_err());
+        assert!(check(&input(from_token, from_token, Felt::ONE)).is_err());
+        assert!(check(&input(from_token, to_token, Felt::ZERO)).is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `buffered_required_output_applies_percentage` operations.
+    fn buffered_required_output_applies_percentage() {
+        let required = Felt::from(1_000_000_u64);
+        assert_eq!(
+            buffered_required_output(required, Felt::ZERO, 0.0).expect("no buffer"),
+            U256::from(1_000_000_u64)
+        );
+        assert_eq!(
+            buffered_required_output(required, Felt::ZERO, 2.5).expect("2.5% buffer"),
+            U256::from(1_025_000_u64)
+        );
+        assert_eq!(
+            buffered_required_output(Felt::ONE, Felt::ONE, 0.0).expect("high limb"),
+            (U256::one() << 128) + U256::one()
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `swap_route_cache_buckets_and_rescales` operations.
+    fn swap_route_cache_buckets_and_rescales() {
+        assert_eq!(
+            swap_route_amount_bucket(Felt::from(1_234_000_u64), Felt::ZERO),
+            Some("12e5".to_string())
+        );
+        assert_eq!(
+            swap_route_amount_bucket(Felt::from(1_290_000_u64), Felt::ZERO),
+            Some("12e5".to_string())
+        );
+        assert_eq!(swap_route_amount_bucket(Felt::ONE, Felt::ONE), None);
+
+        let context = OnchainSwapContext {
+            swap_contract: Felt::from(1_u8),
+            from_token: Felt::from(2_u8),
+            to_token: Felt::from(3_u8),
+            amount_low: Felt::from(1_000_u64),
+            amount_high: Felt::ZERO,
+            route: OnchainSwapRoute {
+                dex_id: Felt::from(4_u8),
+                expected_amount_out_low: Felt::from(2_000_u64),
+                expected_amount_out_high: Felt::ZERO,
+                min_amount_out_low: Felt::from(1_990_u64),
+                min_amount_out_high: Felt::ZERO,
+            },
+        };
+        let scaled =
+            scale_swap_route(&context, Felt::from(1_050_u64), Felt::ZERO).expect("scaled route");
+        assert_eq!(scaled.amount_low, Felt::from(1_050_u64));
+        assert_eq!(scaled.route.expected_amount_out_low, Felt::from(2_100_u64));
+        assert_eq!(scaled.route.min_amount_out_low, Felt::from(2_089_u64));
+        assert_eq!(scaled.route.dex_id, context.route.dex_id);
+
+        assert!(cached_route_for_use(
+            &context,
+            SwapRouteUse::Execute,
+            Felt::from(1_050_u64),
+            Felt::ZERO
+        )
+        .is_none());
+        let exact = cached_route_for_use(
+            &context,
+            SwapRouteUse::Execute,
+            Felt::from(1_000_u64),
+            Felt::ZERO,
+        )
+        .expect("exact route");
+        assert_eq!(exact.route.min_amount_out_low, Felt::from(1_990_u64));
+        assert!(cached_route_for_use(
+            &context,
+            SwapRouteUse::Quote,
+            Felt::from(1_050_u64),
+            Felt::ZERO
+        )
+        .is_some());
+    }
+
+    #[test]
+    // Internal helper that checks conditions for `ensure_hide_swap_pair_allowed_is_directional`.
+    fn ensure_hide_swap_pair_allowed_is_directional() {
+        let pairs = vec![("STRK".to_string(), "USDC".to_string())];
+        assert!(ensure_hide_swap_pair_allowed(&pairs, "strk", "usdc").is_ok());
+        let err = ensure_hide_swap_pair_allowed(&pairs, "USDC", "STRK").expect_err("reverse pair");
+        assert!(matches!(
+            err,
+            AppError::Coded(ErrorCode::HideSwapPairNotAllowed, _)
+        ));
+        assert!(ensure_hide_swap_pair_allowed(&[], "WBTC", "CAREL").is_ok());
+    }
+
+    #[test]
+    // Internal helper that checks conditions for `check_hide_deposit_denomination_rejects_off_denomination`.
+    fn check_hide_deposit_denomination_rejects_off_denomination() {
+        let denominations = vec!["10".to_string(), "100".to_string()];
+        let amount = |raw: &str| parse_decimal_to_u256_parts(raw, 18).expect("amount");
+        let fixed = amount("10");
+
+        assert!(
+            !check_hide_deposit_denomination("STRK", &denominations, false, fixed, fixed)
+                .expect("matches fixed amount")
+        );
+        assert!(check_hide_deposit_denomination(
+            "STRK",
+            &denominations,
+            false,
+            amount("100"),
+            fixed
+        )
+        .expect("allowed denomination"));
+        let err =
+            check_hide_deposit_denomination("STRK", &denominations, false, amount("12.5"), fixed)
+                .expect_err("off-denomination deposit");
+        assert!(matches!(
+            err,
+            AppError::Coded(ErrorCode::HideDenominationNotAllowed, _)
+        ));
+        assert!(check_hide_deposit_denomination("STRK", &[], false, amount("100"), fixed).is_err());
+        assert!(
+            check_hide_deposit_denomination("STRK", &[], true, amount("12.5"), fixed)
+                .expect("flexible mode")
+        );
+    }
+
+    #[test]
+    // Internal helper that checks conditions for `is_oracle_route_matches_any_configured_id`.
+    fn is_oracle_route_matches_any_configured_id() {
+        let route = |dex_id: Felt| OnchainSwapRoute {
+            dex_id,
+            expected_amount_out_low: Felt::ZERO,
+            expected_amount_out_high: Felt::ZERO,
+            min_amount_out_low: Felt::ZERO,
+            min_amount_out_high: Felt::ZERO,
+        };
+        let oracle_ids = [Felt::from_bytes_be_slice(b"ORCL"), Felt::from(7_u64)];
+        assert!(is_oracle_route(
+            &route(Felt::from_bytes_be_slice(b"ORCL")),
+            &oracle_ids
+        ));
+        assert!(is_oracle_route(&route(Felt::from(7_u64)), &oracle_ids));
+        assert!(!is_oracle_route(&route(Felt::from(8_u64)), &oracle_ids));
+        assert!(!is_oracle_route(&route(Felt::from(7_u64)), &[]));
+    }
+
+    #[test]
+    // Internal helper that supports `format_display_amount_rounds_and_trims` operations.
+    fn format_display_amount_rounds_and_trims() {
+        assert_eq!(format_display_amount(1.1 * 3.0, 8), "3.3");
+        assert_eq!(format_display_amount(0.1 + 0.2, 6), "0.3");
+        assert_eq!(format_display_amount(2.0, 6), "2");
+        assert_eq!(format_display_amount(1.23456789, 2), "1.23");
+        assert_eq!(format_display_amount(f64::NAN, 6), "0");
+    }
+
+    #[test]
+    // Internal helper that supports `relayer_call_entrypoint_resolves_known_selectors` operations.
+    fn relayer_call_entrypoint_resolves_known_selectors() {
+        let selector =
+            get_selector_from_name("execute_private_swap_with_payout").expect("selector");
+        assert_eq!(
+            relayer_call_entrypoint(selector),
+            Some("execute_private_swap_with_payout")
+        );
+        assert_eq!(relayer_call_entrypoint(Felt::from(7_u64)), None);
+    }
+
+    #[test]
+    // Internal helper that supports `strict_mode_without_default_recipient_requires_explicit_recipient` operations.
+    fn strict_mode_without_default_recipient_requires_explicit_recipient() {
+        let user = "0x123";
+        let err = resolve_swap_final_recipient_with_default(None, user, true, true, None)
+            .expect_err("strict mode must not fall back to depositor");
+        assert!(err.to_string().contains("HIDE_BALANCE_DEFAULT_RECIPIENT"));
+        let err = resolve_swap_final_recipient_with_default(Some("  "), user, true, true, Some(""))
+            .expect_err("blank default counts as unset");
+        assert!(err.to_string().contains("HIDE_BALANCE_DEFAULT_RECIPIENT"));
+        assert_eq!(
+            resolve_swap_final_recipient_with_default(Some("0x456"), user, true, true, None)
+                .expect("explicit recipient"),
+            "0x456"
+        );
+        assert!(
+            resolve_swap_final_recipient_with_default(Some("0X123"), user, true, true, None)
+                .is_err()
+        );
+        assert_eq!(
+            resolve_swap_final_recipient_with_default(None, user, true, false, None)
+                .expect("non-strict falls back"),
+            user
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `strict_mode_with_default_recipient_uses_configured_address` operations.
+    fn strict_mode_with_default_recipient_uses_configured_address() {
+        let user = "0x123";
+        assert_eq!(
+            resolve_swap_final_recipient_with_default(None, user, true, true, Some(" 0x789 "))
+                .expect("default recipient"),
+            "0x789"
+        );
+        assert_eq!(
+            resolve_swap_final_recipient_with_default(
+                Some("0x456"),
+                user,
+                true,
+                true,
+                Some("0x789")
+            )
+            .expect("explicit recipient wins"),
+            "0x456"
+        );
+        let err = resolve_swap_final_recipient_with_default(None, user, true, true, Some("0x123"))
+            .expect_err("default equal to depositor");
+        assert!(err.to_string().contains("matches the depositor"));
+        assert_eq!(
+            resolve_swap_final_recipient_with_default(None, user, false, true, Some("0x789"))
+                .expect("non-hide ignores default"),
+            user
+        );
+    }
+
+    #[test]
+    fn swap_tx_confirmation_policy_respects_l1_requirement() {
+        let l2_policy = SwapTxConfirmationPolicy {
+            attempts: 5,
+            interval: Duration::from_millis(1_000),
+            require_l1: false,
+        };
+        let l1_policy = SwapTxConfirmationPolicy {
+            require_l1: true,
+            ..l2_policy
+        };
+        assert!(l2_policy.accepts(&TransactionFinalityStatus::AcceptedOnL2));
+        assert!(l2_policy.accepts(&TransactionFinalityStatus::AcceptedOnL1));
+        assert!(!l2_policy.accepts(&TransactionFinalityStatus::PreConfirmed));
+        assert!(!l1_policy.accepts(&TransactionFinalityStatus::AcceptedOnL2));
+        assert!(l1_policy.accepts(&TransactionFinalityStatus::AcceptedOnL1));
+    }
+
     #[test]
     fn resolve_hide_pool_version_prefers_payload_note_version() {
         let payload_v3 = PrivacyVerificationPayload {
@@ -3627,6 +4558,43 @@ mod tests {
         ));
     }
 
+    // Internal helper that returns generous payload limits for request-mapping tests.
+    fn test_payload_limits() -> PrivacyPayloadLimits {
+        PrivacyPayloadLimits {
+            max_proof_len: 16,
+            max_public_inputs_len: 8,
+        }
+    }
+
+    #[test]
+    // Internal helper that supports `payload_from_request_rejects_oversized_or_invalid_items` operations.
+    fn payload_from_request_rejects_oversized_or_invalid_items() {
+        let mut payload = PrivacyVerificationPayload {
+            verifier: Some("garaga".to_string()),
+            note_version: None,
+            root: None,
+            nullifier: Some("0x456".to_string()),
+            commitment: Some("0x789".to_string()),
+            note_commitment: None,
+            denom_id: None,
+            spendable_at_unix: None,
+            proof: Some(vec!["0x1".to_string(); 17]),
+            public_inputs: Some(vec!["0x456".to_string(), "0x789".to_string()]),
+        };
+        assert!(payload_from_request(Some(&payload), "garaga", test_payload_limits()).is_err());
+
+        payload.proof = Some(vec![
+            "0x1".to_string(),
+            " ".to_string(),
+            "not-a-felt".to_string(),
+        ]);
+        let err = payload_from_request(Some(&payload), "garaga", test_payload_limits())
+            .expect_err("invalid felt must be rejected");
+        assert!(err
+            .to_string()
+            .contains("privacy.proof[2] is not a valid felt"));
+    }
+
     #[test]
     fn payload_from_request_preserves_v3_metadata() {
         let payload = PrivacyVerificationPayload {
@@ -3645,7 +4613,9 @@ mod tests {
                 "0x999".to_string(),
             ]),
         };
-        let mapped = payload_from_request(Some(&payload), "garaga").expect("payload must map");
+        let mapped = payload_from_request(Some(&payload), "garaga", test_payload_limits())
+            .expect("payload must be valid")
+            .expect("payload must map");
         assert_eq!(mapped.note_version.as_deref(), Some("v3"));
         assert_eq!(mapped.root.as_deref(), Some("0x123"));
         assert_eq!(mapped.note_commitment.as_deref(), Some("0xabc"));
@@ -3671,7 +4641,9 @@ mod tests {
                 "0x999".to_string(),
             ]),
         };
-        let mapped = payload_from_request(Some(&payload), "garaga").expect("payload must map");
+        let mapped = payload_from_request(Some(&payload), "garaga", test_payload_limits())
+            .expect("payload must be valid")
+            .expect("payload must map");
         assert_eq!(mapped.note_version.as_deref(), Some("v3"));
         assert_eq!(mapped.root.as_deref(), Some("0x123"));
     }
@@ -3693,8 +4665,8 @@ mod tests {
 
     #[test]
     fn usdt_tier_bonus_is_hide_mode_only() {
-        let normal = estimate_swap_points_for_response(100.0, 100.0, false, true, 0.0, 1);
-        let hide = estimate_swap_points_for_response(100.0, 100.0, true, true, 0.0, 1);
+        let normal = estimate_swap_points_for_response(100.0, 100.0, false, true, 1.0);
+        let hide = estimate_swap_points_for_response(100.0, 100.0, true, true, 1.0);
         assert!(normal > 0.0);
         assert!(hide > normal);
     }
@@ -3708,13 +4680,88 @@ mod tests {
     }
 
     #[test]
-    // Internal helper that supports `mev_fee_for_mode_only_private` operations in the swap flow.
+    // Internal helper that supports `total_fee_decimal_matches_f64_fee_schedule` operations.
+    fn total_fee_decimal_matches_f64_fee_schedule() {
+        let amount = parse_amount_decimal("1000", 1000.0);
+        assert_eq!(
+            total_fee_decimal(amount, "normal", 0.0),
+            rust_decimal::Decimal::new(3, 0)
+        );
+        assert_eq!(
+            total_fee_decimal(amount, "private", 50.0),
+            rust_decimal::Decimal::new(65, 1)
+        );
+        assert_eq!(
+            parse_amount_decimal("1e3", 1000.0),
+            rust_decimal::Decimal::new(1000, 0)
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `swap_fee_breakdown_splits_discount_savings` operations.
+    fn swap_fee_breakdown_splits_discount_savings() {
+        let amount = parse_amount_decimal("1000", 1000.0);
+        let breakdown = swap_fee_breakdown("0xabc", amount, "Private", 30.0, 20.0, 50.0);
+        assert_eq!(breakdown.mode, "private");
+        assert_eq!(breakdown.base_fee, rust_decimal::Decimal::new(3, 0));
+        assert_eq!(breakdown.mev_fee, rust_decimal::Decimal::new(10, 0));
+        assert_eq!(
+            breakdown.fee_before_discount,
+            rust_decimal::Decimal::new(13, 0)
+        );
+        assert_eq!(breakdown.net_fee, rust_decimal::Decimal::new(65, 1));
+        assert_eq!(
+            breakdown.nft_discount_saved,
+            rust_decimal::Decimal::new(39, 1)
+        );
+        assert_eq!(
+            breakdown.stake_discount_saved,
+            rust_decimal::Decimal::new(26, 1)
+        );
+
+        let plain = swap_fee_breakdown("0xabc", amount, "normal", 0.0, 0.0, 0.0);
+        assert_eq!(plain.mev_fee, rust_decimal::Decimal::ZERO);
+        assert_eq!(plain.net_fee, rust_decimal::Decimal::new(3, 0));
+        assert_eq!(plain.nft_discount_saved, rust_decimal::Decimal::ZERO);
+    }
+
+    #[test]
+    // Internal helper that supports `ensure_finite_rejects_overflowed_values` operations.
+    fn ensure_finite_rejects_overflowed_values() {
+        assert!(ensure_finite(f64::MAX * 2.0, "fee").is_err());
+        assert!(ensure_finite(f64::NAN, "fee").is_err());
+        assert_eq!(ensure_finite(1.5, "fee").unwrap(), 1.5);
+        assert!(ensure_swap_amount_in_bounds(MAX_SWAP_AMOUNT_IN).is_ok());
+        assert!(ensure_swap_amount_in_bounds(MAX_SWAP_AMOUNT_IN * 10.0).is_err());
+        assert_eq!(
+            estimate_swap_points_for_response(f64::INFINITY, f64::INFINITY, true, true, 1.0),
+            0.0
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `validate_deadline_checks_both_window_boundaries` operations.
+    fn validate_deadline_checks_both_window_boundaries() {
+        assert!(validate_deadline(100, 100, 3_600).is_ok());
+        assert!(validate_deadline(3_700, 100, 3_600).is_ok());
+
+        let expired = validate_deadline(99, 100, 3_600).expect_err("expired");
+        assert!(expired.to_string().contains("expired"));
+        let too_far = validate_deadline(3_701, 100, 3_600).expect_err("too far");
+        assert!(too_far.to_string().contains("too far out"));
+    }
+
+    #[test]
+    // Internal helper that supports `swap_fee_rate_adds_mev_only_private` operations in the swap flow.
     // Keeps validation, normalization, and intent-binding logic centralized.
-    fn mev_fee_for_mode_only_private() {
+    fn swap_fee_rate_adds_mev_only_private() {
         // Memastikan fee MEV hanya untuk mode private
-        assert!((mev_fee_for_mode("private", 100.0) - 1.0).abs() < 1e-9);
-        assert!((mev_fee_for_mode("PRIVATE", 100.0) - 1.0).abs() < 1e-9);
-        assert!((mev_fee_for_mode("transparent", 100.0) - 0.0).abs() < 1e-9);
+        assert_eq!(swap_fee_rate("private"), rust_decimal::Decimal::new(13, 3));
+        assert_eq!(swap_fee_rate("PRIVATE"), rust_decimal::Decimal::new(13, 3));
+        assert_eq!(
+            swap_fee_rate("transparent"),
+            rust_decimal::Decimal::new(3, 3)
+        );
     }
 
     #[test]
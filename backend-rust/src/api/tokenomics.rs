@@ -0,0 +1,328 @@
+use super::AppState;
+use crate::{
+    config::Config,
+    error::{AppError, Result},
+    models::ApiResponse,
+    services::onchain::{parse_felt, u256_from_felts, OnchainReader},
+    tokenomics::{distribution_mode_for_environment, project_emissions, CLAIM_FEE_BPS},
+};
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use starknet_core::types::{Felt, FunctionCall};
+use starknet_core::utils::get_selector_from_name;
+use std::{
+    collections::HashMap,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+const DEFAULT_PROJECTION_EPOCHS: u32 = 12;
+const MAX_PROJECTION_EPOCHS: u32 = 240;
+
+const CAREL_DECIMALS: u32 = 18;
+
+// Projections only change at epoch boundaries, so entries are keyed by the current epoch.
+static PROJECTION_CACHE: OnceLock<tokio::sync::RwLock<HashMap<String, ProjectionResponse>>> =
+    OnceLock::new();
+
+static SUPPLY_CACHE: OnceLock<tokio::sync::RwLock<Option<(Instant, SupplyResponse)>>> =
+    OnceLock::new();
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectionQuery {
+    pub epochs: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectedEpoch {
+    pub epoch: i64,
+    pub pool_emission: Decimal,
+    pub claim_fees: Decimal,
+    pub distributed_to_users: Decimal,
+    pub cumulative_emission: Decimal,
+    pub circulating_supply: Decimal,
+    pub remaining_supply: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectionResponse {
+    pub distribution_mode: String,
+    pub distribution_label: String,
+    pub current_epoch: i64,
+    pub epoch_duration_seconds: i64,
+    pub total_supply: Decimal,
+    pub circulating_supply: Decimal,
+    pub claim_fee_bps: i64,
+    pub total_emission: Decimal,
+    pub total_distributed_to_users: Decimal,
+    pub epochs: Vec<ProjectedEpoch>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LockedBalance {
+    pub label: String,
+    pub address: String,
+    pub balance_wei: String,
+    pub balance: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SupplyResponse {
+    pub token_address: String,
+    pub decimals: u32,
+    pub total_supply_wei: String,
+    pub locked_supply_wei: String,
+    pub circulating_supply_wei: String,
+    pub total_supply: Decimal,
+    pub locked_supply: Decimal,
+    pub circulating_supply: Decimal,
+    pub locked: Vec<LockedBalance>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Internal helper that supports `projection_cache` operations.
+fn projection_cache() -> &'static tokio::sync::RwLock<HashMap<String, ProjectionResponse>> {
+    PROJECTION_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()))
+}
+
+// Internal helper that parses or transforms values for `normalize_epochs`.
+fn normalize_epochs(epochs: Option<u32>) -> Result<u32> {
+    match epochs.unwrap_or(DEFAULT_PROJECTION_EPOCHS) {
+        0 => Err(AppError::BadRequest(
+            "epochs must be greater than zero".to_string(),
+        )),
+        value if value > MAX_PROJECTION_EPOCHS => Err(AppError::BadRequest(format!(
+            "epochs must be at most {}",
+            MAX_PROJECTION_EPOCHS
+        ))),
+        value => Ok(value),
+    }
+}
+
+// Internal helper that builds the projection for the configured distribution mode.
+// Emissions start from the current circulating supply and draw down what is still locked.
+fn build_projection(
+    state: &AppState,
+    current_epoch: i64,
+    epochs: u32,
+    total_supply: Decimal,
+    circulating_supply: Decimal,
+) -> ProjectionResponse {
+    let mode = distribution_mode_for_environment(&state.config.environment);
+    let unreleased = (total_supply - circulating_supply).max(Decimal::ZERO);
+    let projected: Vec<ProjectedEpoch> = project_emissions(mode, unreleased, epochs)
+        .into_iter()
+        .map(|row| ProjectedEpoch {
+            epoch: current_epoch + row.epoch_offset,
+            pool_emission: row.pool_emission,
+            claim_fees: row.claim_fees,
+            distributed_to_users: row.distributed_to_users,
+            cumulative_emission: row.cumulative_emission,
+            circulating_supply: circulating_supply + row.cumulative_emission,
+            remaining_supply: row.remaining_supply,
+        })
+        .collect();
+    ProjectionResponse {
+        distribution_mode: mode.as_str().to_string(),
+        distribution_label: mode.label().to_string(),
+        current_epoch,
+        epoch_duration_seconds: state.config.rewards_epoch_duration_seconds,
+        total_supply,
+        circulating_supply,
+        claim_fee_bps: CLAIM_FEE_BPS,
+        total_emission: projected.iter().map(|row| row.pool_emission).sum(),
+        total_distributed_to_users: projected.iter().map(|row| row.distributed_to_users).sum(),
+        epochs: projected,
+    }
+}
+
+// Internal helper that supports `supply_cache` operations.
+fn supply_cache() -> &'static tokio::sync::RwLock<Option<(Instant, SupplyResponse)>> {
+    SUPPLY_CACHE.get_or_init(|| tokio::sync::RwLock::new(None))
+}
+
+// Internal helper that lists the configured addresses whose CAREL balance is not circulating.
+fn locked_addresses(config: &Config) -> Vec<(&'static str, String)> {
+    let candidates = [
+        ("treasury", config.treasury()),
+        ("staking", config.staking_carel_address.as_deref()),
+        ("vesting", config.vesting_manager_address.as_deref()),
+    ];
+    let mut out: Vec<(&'static str, String)> = Vec::new();
+    for (label, address) in candidates {
+        let Some(address) = address
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && !value.starts_with("0x0000"))
+        else {
+            continue;
+        };
+        // The same contract may back several roles; count its balance once.
+        if out
+            .iter()
+            .any(|(_, existing)| existing.eq_ignore_ascii_case(address))
+        {
+            continue;
+        }
+        out.push((label, address.to_string()));
+    }
+    out
+}
+
+// Internal helper that parses or transforms values for `wei_to_decimal`.
+fn wei_to_decimal(raw: u128, decimals: u32) -> Decimal {
+    i128::try_from(raw)
+        .ok()
+        .and_then(|value| Decimal::try_from_i128_with_scale(value, decimals).ok())
+        .or_else(|| Decimal::from_f64(raw as f64 / 10_f64.powi(decimals as i32)))
+        .unwrap_or(Decimal::ZERO)
+}
+
+// Internal helper that fetches a u256 view value, trying snake_case then camelCase selectors.
+async fn read_u256(
+    reader: &OnchainReader,
+    contract: Felt,
+    selectors: [&str; 2],
+    calldata: Vec<Felt>,
+) -> Result<u128> {
+    let mut last_error = None;
+    for selector_name in selectors {
+        let selector = get_selector_from_name(selector_name)
+            .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
+        let call = FunctionCall {
+            contract_address: contract,
+            entry_point_selector: selector,
+            calldata: calldata.clone(),
+        };
+        match reader.call(call).await {
+            Ok(values) if values.len() >= 2 => return u256_from_felts(&values[0], &values[1]),
+            Ok(_) => {}
+            Err(err) => last_error = Some(err),
+        }
+    }
+    Err(last_error
+        .unwrap_or_else(|| AppError::Internal(format!("Unexpected {} response", selectors[0]))))
+}
+
+// Internal helper that reads total and locked CAREL balances on-chain.
+async fn load_supply(config: &Config) -> Result<SupplyResponse> {
+    let reader = OnchainReader::from_config(config)?;
+    let token = parse_felt(&config.carel_token_address)?;
+    let total_wei = read_u256(&reader, token, ["total_supply", "totalSupply"], vec![]).await?;
+
+    let mut locked = Vec::new();
+    let mut locked_wei: u128 = 0;
+    for (label, address) in locked_addresses(config) {
+        let owner = parse_felt(&address)?;
+        let balance_wei =
+            read_u256(&reader, token, ["balance_of", "balanceOf"], vec![owner]).await?;
+        locked_wei = locked_wei.saturating_add(balance_wei);
+        locked.push(LockedBalance {
+            label: label.to_string(),
+            address,
+            balance_wei: balance_wei.to_string(),
+            balance: wei_to_decimal(balance_wei, CAREL_DECIMALS),
+        });
+    }
+    let circulating_wei = total_wei.saturating_sub(locked_wei);
+
+    Ok(SupplyResponse {
+        token_address: config.carel_token_address.trim().to_string(),
+        decimals: CAREL_DECIMALS,
+        total_supply_wei: total_wei.to_string(),
+        locked_supply_wei: locked_wei.to_string(),
+        circulating_supply_wei: circulating_wei.to_string(),
+        total_supply: wei_to_decimal(total_wei, CAREL_DECIMALS),
+        locked_supply: wei_to_decimal(locked_wei, CAREL_DECIMALS),
+        circulating_supply: wei_to_decimal(circulating_wei, CAREL_DECIMALS),
+        locked,
+        updated_at: Utc::now(),
+    })
+}
+
+/// GET /api/v1/tokenomics/supply
+///
+/// Circulating supply is the on-chain total minus treasury, staking and vesting balances;
+/// cached for `CAREL_SUPPLY_CACHE_SECS`.
+pub async fn get_supply(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<SupplyResponse>>> {
+    Ok(Json(ApiResponse::success(cached_supply(&state).await?)))
+}
+
+// Internal helper that returns the supply snapshot, reading on-chain once the cache expires.
+async fn cached_supply(state: &AppState) -> Result<SupplyResponse> {
+    let max_age = Duration::from_secs(state.config.carel_supply_cache_secs);
+    if let Some((fetched_at, cached)) = supply_cache().read().await.as_ref() {
+        if fetched_at.elapsed() < max_age {
+            return Ok(cached.clone());
+        }
+    }
+
+    let supply = load_supply(&state.config).await?;
+    *supply_cache().write().await = Some((Instant::now(), supply.clone()));
+    Ok(supply)
+}
+
+/// GET /api/v1/tokenomics/projection
+///
+/// Read-only simulation of reward-pool emission over the next `epochs` epochs, starting from
+/// the circulating supply reported by `/tokenomics/supply`.
+pub async fn get_projection(
+    State(state): State<AppState>,
+    Query(query): Query<ProjectionQuery>,
+) -> Result<Json<ApiResponse<ProjectionResponse>>> {
+    let epochs = normalize_epochs(query.epochs)?;
+    let current_epoch = state.config.current_rewards_epoch();
+    let cache_key = format!("{}:{}", current_epoch, epochs);
+
+    if let Some(cached) = projection_cache().read().await.get(&cache_key) {
+        return Ok(Json(ApiResponse::success(cached.clone())));
+    }
+
+    let supply = cached_supply(&state).await?;
+    let projection = build_projection(
+        &state,
+        current_epoch,
+        epochs,
+        supply.total_supply,
+        supply.circulating_supply,
+    );
+    let mut guard = projection_cache().write().await;
+    guard.retain(|_, entry| entry.current_epoch == current_epoch);
+    guard.insert(cache_key, projection.clone());
+    Ok(Json(ApiResponse::success(projection)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `normalize_epochs_applies_default_and_bounds` operations.
+    fn normalize_epochs_applies_default_and_bounds() {
+        assert_eq!(normalize_epochs(None).unwrap(), DEFAULT_PROJECTION_EPOCHS);
+        assert_eq!(normalize_epochs(Some(3)).unwrap(), 3);
+        assert!(normalize_epochs(Some(0)).is_err());
+        assert!(normalize_epochs(Some(MAX_PROJECTION_EPOCHS + 1)).is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `wei_to_decimal_scales_by_decimals` operations.
+    fn wei_to_decimal_scales_by_decimals() {
+        let one_billion_wei = 1_000_000_000u128 * 10u128.pow(18);
+        assert_eq!(
+            wei_to_decimal(one_billion_wei, CAREL_DECIMALS),
+            Decimal::from(1_000_000_000)
+        );
+        assert_eq!(
+            wei_to_decimal(1_500_000_000_000_000_000, CAREL_DECIMALS).to_string(),
+            "1.500000000000000000"
+        );
+    }
+}
@@ -1,20 +1,63 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
     Json,
 };
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use starknet_core::types::{
+    ExecutionResult, TransactionFinalityStatus, TransactionReceiptWithBlockInfo,
+};
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
 use crate::{
-    error::Result,
-    models::{ApiResponse, PaginatedResponse, Transaction},
-    services::TransactionHistoryService,
-    utils::ensure_page_limit,
+    error::{AppError, Result},
+    models::{ApiResponse, PaginatedResponse, Transaction, TransactionHistoryItem},
+    services::{
+        onchain::{
+            fee_unit_label, is_tx_hash_not_found, parse_felt, receipt_actual_fee, OnchainReader,
+        },
+        TransactionHistoryService,
+    },
+};
+
+use super::{
+    extract::{Pagination, TimeRange},
+    resolve_user_scope_addresses, AppState,
 };
 
-use super::{resolve_user_scope_addresses, AppState};
+const FINAL_RECEIPT_CACHE_MAX_ENTRIES: usize = 10_000;
+
+static FINAL_RECEIPT_CACHE: OnceLock<tokio::sync::RwLock<HashMap<String, OnchainReceipt>>> =
+    OnceLock::new();
+
+#[derive(Debug, Deserialize)]
+pub struct TransactionDetailsQuery {
+    pub live: Option<bool>,
+}
+
+/// Live Starknet receipt data attached to `GET /api/v1/transactions/:tx_hash?live=true`.
+#[derive(Debug, Clone, Serialize)]
+pub struct OnchainReceipt {
+    /// `found`, `not_found` (off-chain or relayer-internal hash) or `unavailable` (RPC error).
+    pub status: String,
+    pub finality_status: Option<String>,
+    pub execution_status: Option<String>,
+    pub revert_reason: Option<String>,
+    pub actual_fee: Option<String>,
+    pub fee_unit: Option<String>,
+    pub block_number: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionDetails {
+    #[serde(flatten)]
+    pub transaction: Transaction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onchain: Option<OnchainReceipt>,
+}
 
 #[derive(Debug, Deserialize)]
 pub struct HistoryQuery {
@@ -44,22 +87,20 @@ fn parse_dates(query: &HistoryQuery) -> (Option<DateTime<Utc>>, Option<DateTime<
 pub async fn get_history(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Pagination { page, limit }: Pagination,
+    range: TimeRange,
     axum::extract::Query(query): axum::extract::Query<HistoryQuery>,
-) -> Result<Json<ApiResponse<PaginatedResponse<Transaction>>>> {
+) -> Result<Json<ApiResponse<PaginatedResponse<TransactionHistoryItem>>>> {
     let user_addresses = resolve_user_scope_addresses(&headers, &state).await?;
 
-    let (from_date, to_date) = parse_dates(&query);
-    let page = query.page.unwrap_or(1);
-    let limit = query.limit.unwrap_or(20);
-    ensure_page_limit(limit, state.config.rate_limit_authenticated)?;
-
-    let service = TransactionHistoryService::new(state.db);
+    let service =
+        TransactionHistoryService::new(state.db, state.config.point_calculator_max_attempts);
     let history = service
         .get_user_history(
             &user_addresses,
             query.tx_type,
-            from_date,
-            to_date,
+            range.from,
+            range.to,
             page,
             limit,
         )
@@ -78,14 +119,118 @@ pub async fn get_history(
 }
 
 /// GET /api/v1/transactions/:tx_hash
+///
+/// # Notes
+/// * `live=true` requires auth and only resolves transactions owned by the caller's
+///   linked wallets; others are reported as not found.
 pub async fn get_details(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Path(tx_hash): Path<String>,
-) -> Result<Json<ApiResponse<Transaction>>> {
-    let service = TransactionHistoryService::new(state.db);
-    let tx = service.get_transaction_details(&tx_hash).await?;
+    Query(query): Query<TransactionDetailsQuery>,
+) -> Result<Json<ApiResponse<TransactionDetails>>> {
+    let service = TransactionHistoryService::new(
+        state.db.clone(),
+        state.config.point_calculator_max_attempts,
+    );
+    let transaction = service.get_transaction_details(&tx_hash).await?;
+    let onchain = if query.live.unwrap_or(false) {
+        let scopes = resolve_user_scope_addresses(&headers, &state).await?;
+        if !scopes
+            .iter()
+            .any(|scope| scope.eq_ignore_ascii_case(transaction.user_address.trim()))
+        {
+            return Err(AppError::NotFound("Transaction not found".to_string()));
+        }
+        Some(live_receipt(&state, &transaction.tx_hash).await)
+    } else {
+        None
+    };
+
+    Ok(Json(ApiResponse::success(TransactionDetails {
+        transaction,
+        onchain,
+    })))
+}
 
-    Ok(Json(ApiResponse::success(tx)))
+// Internal helper that fetches the live receipt, reusing cached results once L1-final.
+async fn live_receipt(state: &AppState, tx_hash: &str) -> OnchainReceipt {
+    let cache_key = tx_hash.trim().to_ascii_lowercase();
+    if let Some(cached) = final_receipt_cache().read().await.get(&cache_key) {
+        return cached.clone();
+    }
+    // Synthetic hashes (e.g. battle point rows) are not valid felts and never reach the chain.
+    let Ok(tx_hash_felt) = parse_felt(tx_hash) else {
+        return receipt_status_only("not_found");
+    };
+    let reader = match OnchainReader::from_config(&state.config) {
+        Ok(reader) => reader,
+        Err(err) => {
+            tracing::warn!("transaction receipt lookup unavailable: {}", err);
+            return receipt_status_only("unavailable");
+        }
+    };
+    match reader.get_transaction_receipt(&tx_hash_felt).await {
+        Ok(receipt) => {
+            let summary = summarize_receipt(&receipt);
+            if is_final(receipt.receipt.finality_status()) {
+                let mut cache = final_receipt_cache().write().await;
+                if cache.len() >= FINAL_RECEIPT_CACHE_MAX_ENTRIES {
+                    cache.clear();
+                }
+                cache.insert(cache_key, summary.clone());
+            }
+            summary
+        }
+        Err(err) if is_tx_hash_not_found(&err) => receipt_status_only("not_found"),
+        Err(err) => {
+            tracing::warn!("transaction receipt lookup failed tx={}: {}", tx_hash, err);
+            receipt_status_only("unavailable")
+        }
+    }
+}
+
+// Internal helper that supports `final_receipt_cache` operations.
+fn final_receipt_cache() -> &'static tokio::sync::RwLock<HashMap<String, OnchainReceipt>> {
+    FINAL_RECEIPT_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()))
+}
+
+// Internal helper that checks conditions for `is_final`.
+// L2 acceptance can still change, so only L1 acceptance is treated as final.
+fn is_final(finality: &TransactionFinalityStatus) -> bool {
+    matches!(finality, TransactionFinalityStatus::AcceptedOnL1)
+}
+
+// Internal helper that builds inputs for `receipt_status_only`.
+fn receipt_status_only(status: &str) -> OnchainReceipt {
+    OnchainReceipt {
+        status: status.to_string(),
+        finality_status: None,
+        execution_status: None,
+        revert_reason: None,
+        actual_fee: None,
+        fee_unit: None,
+        block_number: None,
+    }
+}
+
+// Internal helper that builds inputs for `summarize_receipt`.
+fn summarize_receipt(receipt: &TransactionReceiptWithBlockInfo) -> OnchainReceipt {
+    let (execution_status, revert_reason) = match receipt.receipt.execution_result() {
+        ExecutionResult::Succeeded => ("succeeded", None),
+        ExecutionResult::Reverted { reason } => ("reverted", Some(reason.clone())),
+    };
+    let fee = receipt_actual_fee(&receipt.receipt);
+    let fee_unit = fee_unit_label(&fee.unit);
+    OnchainReceipt {
+        status: "found".to_string(),
+        finality_status: Some(format!("{:?}", receipt.receipt.finality_status())),
+        execution_status: Some(execution_status.to_string()),
+        revert_reason,
+        actual_fee: Some(fee.amount.to_string()),
+        fee_unit: Some(fee_unit.to_string()),
+        block_number: Some(receipt.block.block_number()),
+    }
 }
 
 /// POST /api/v1/transactions/export
@@ -108,7 +253,8 @@ pub async fn export_csv(
         query.limit
     );
 
-    let service = TransactionHistoryService::new(state.db);
+    let service =
+        TransactionHistoryService::new(state.db, state.config.point_calculator_max_attempts);
     let csv = service
         .export_to_csv(&user_addresses, from_date, to_date)
         .await?;
@@ -146,6 +292,21 @@ mod tests {
         assert!(to.is_none());
     }
 
+    #[test]
+    // Internal helper that supports `receipt_lookup_classifies_not_found_and_finality` operations.
+    fn receipt_lookup_classifies_not_found_and_finality() {
+        assert!(is_tx_hash_not_found(&AppError::BlockchainRPC(
+            "Transaction hash not found".to_string()
+        )));
+        assert!(!is_tx_hash_not_found(&AppError::BlockchainRPC(
+            "connection reset".to_string()
+        )));
+        assert!(is_final(&TransactionFinalityStatus::AcceptedOnL1));
+        assert!(!is_final(&TransactionFinalityStatus::AcceptedOnL2));
+        assert!(!is_final(&TransactionFinalityStatus::PreConfirmed));
+        assert_eq!(receipt_status_only("not_found").status, "not_found");
+    }
+
     #[test]
     // Internal helper that parses or transforms values for `parse_dates_parses_valid_rfc3339`.
     fn parse_dates_parses_valid_rfc3339() {
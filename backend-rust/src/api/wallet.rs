@@ -19,7 +19,10 @@ use crate::{
     error::{AppError, Result},
     indexer::starknet_client::{ContractBatchCall, StarknetClient},
     models::ApiResponse,
-    services::onchain::{parse_felt, u256_from_felts, OnchainReader},
+    services::{
+        onchain::{parse_felt, u256_from_felts, OnchainReader},
+        point_calculator::grant_onboarding_bonus,
+    },
 };
 
 use super::{
@@ -727,6 +730,7 @@ pub async fn link_wallet_address(
             req.provider.as_deref(),
         )
         .await?;
+    grant_onboarding_bonus(&state.db, &state.config, &user_address).await;
 
     Ok(Json(ApiResponse::success(LinkWalletAddressResponse {
         user_address,
@@ -1104,7 +1108,7 @@ pub(crate) async fn fetch_starknet_decimals(config: &Config, token: &str) -> Res
         entry_point_selector: selector,
         calldata: vec![],
     };
-    let values = reader.call(call).await?;
+    let values = reader.call_cached(call).await?;
     let value = values
         .first()
         .ok_or_else(|| AppError::Internal("Decimals missing".into()))?;
@@ -1,20 +1,44 @@
-use super::{require_user, AppState};
+use super::{
+    extract::{Pagination, TimeRange},
+    require_user, AppState,
+};
 use crate::{
-    error::Result,
-    models::{ApiResponse, Webhook},
-    services::WebhookService,
+    error::{AppError, Result},
+    models::{ApiResponse, PaginatedResponse, Webhook},
+    services::{webhook_service::truncate_response_body, WebhookService},
 };
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::HeaderMap,
     Json,
 };
+use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const WEBHOOK_TEST_WINDOW_SECS: i64 = 300;
+const WEBHOOK_TEST_MAX_PER_WINDOW: i64 = 5;
+const LOCAL_TEST_COUNTER_MAX_ENTRIES: usize = 10_000;
+
+// Per-replica test delivery counters used while Redis is unavailable.
+static LOCAL_TEST_COUNTERS: OnceLock<std::sync::Mutex<HashMap<i64, (i64, i64)>>> = OnceLock::new();
+
+/// Result of a test delivery, including a capped snippet of the receiver's response.
+#[derive(Debug, Serialize)]
+pub struct WebhookTestResult {
+    pub success: bool,
+    pub status_code: Option<u16>,
+    pub latency_ms: u64,
+    pub response_snippet: Option<String>,
+    pub error: Option<String>,
+}
 
 #[derive(Debug, Deserialize)]
 pub struct RegisterWebhookRequest {
     pub url: String,
+    #[serde(default)]
     pub events: Vec<String>,
 }
 
@@ -27,16 +51,99 @@ pub struct WebhookInfo {
     pub created_at: i64,
 }
 
-// Internal helper that parses or transforms values for `format_webhook_log`.
-fn format_webhook_log(
-    event: &str,
-    status: &str,
-    delivered_at: chrono::DateTime<chrono::Utc>,
-) -> String {
-    format!("{} | {} | {}", delivered_at.to_rfc3339(), event, status)
+#[derive(Debug, Deserialize)]
+pub struct WebhookLogsQuery {
+    pub webhook_id: Option<i64>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookLogEntry {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event: String,
+    pub status: String,
+    pub response_status_code: Option<i32>,
+    pub response_body: Option<String>,
+    pub error_message: Option<String>,
+    pub created_at: i64,
+    pub delivered_at: Option<i64>,
+}
+
+// Internal helper that parses or transforms values for `normalize_log_status_filter`.
+fn normalize_log_status_filter(raw: Option<&str>) -> Result<Option<&'static str>> {
+    match raw
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("") | Some("all") => Ok(None),
+        Some("success") => Ok(Some("success")),
+        Some("failure") | Some("failed") => Ok(Some("failure")),
+        Some(other) => Err(AppError::BadRequest(format!(
+            "Unknown status: {} (expected success or failure)",
+            other
+        ))),
+    }
+}
+
+// Internal helper that counts a test delivery in process memory for the current window.
+fn local_test_counter_incr(webhook_id: i64, bucket: i64) -> i64 {
+    let Ok(mut counters) = LOCAL_TEST_COUNTERS
+        .get_or_init(|| std::sync::Mutex::new(HashMap::new()))
+        .lock()
+    else {
+        return i64::MAX;
+    };
+    if counters.len() > LOCAL_TEST_COUNTER_MAX_ENTRIES {
+        counters.retain(|_, (entry_bucket, _)| *entry_bucket >= bucket);
+    }
+    let entry = counters.entry(webhook_id).or_insert((bucket, 0));
+    if entry.0 != bucket {
+        *entry = (bucket, 0);
+    }
+    entry.1 += 1;
+    entry.1
+}
+
+// Internal helper that supports `enforce_test_delivery_limit` operations.
+// Counts test deliveries per webhook in a fixed Redis window, or per replica in process memory
+// while Redis is unavailable.
+async fn enforce_test_delivery_limit(state: &AppState, webhook_id: i64) -> Result<()> {
+    let bucket = chrono::Utc::now().timestamp() / WEBHOOK_TEST_WINDOW_SECS;
+    let mut count = None;
+    if state.redis_available() {
+        let key = format!("webhook:test:{}:{}", webhook_id, bucket);
+        let mut conn = state.redis.clone();
+        match conn.incr::<_, _, i64>(&key, 1_i64).await {
+            Ok(value) => {
+                if value == 1 {
+                    let _: std::result::Result<bool, redis::RedisError> =
+                        conn.expire(&key, WEBHOOK_TEST_WINDOW_SECS).await;
+                }
+                count = Some(value);
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Webhook test limiter Redis incr failed, using local counter: {}",
+                    err
+                );
+                state.set_redis_available(false);
+            }
+        }
+    }
+    let count = count.unwrap_or_else(|| local_test_counter_incr(webhook_id, bucket));
+    if count > WEBHOOK_TEST_MAX_PER_WINDOW {
+        return Err(AppError::RateLimitExceeded);
+    }
+    Ok(())
 }
 
 /// POST /api/v1/webhooks/register
+///
+/// # Notes
+/// * `events` filters deliveries to `swap`, `bridge`, `order_filled`, `deposit` or `reward`;
+///   an empty list subscribes to all of them.
+/// * `url` must be `https` on a public host; see `validate_webhook_url`.
 pub async fn register(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -45,11 +152,11 @@ pub async fn register(
     let user_address = require_user(&headers, &state).await?;
     let service = WebhookService::new(state.db.clone(), state.config.clone());
 
-    let id = service
-        .register(&user_address, &req.url, req.events.clone())
+    let (id, url, events) = service
+        .register(&user_address, &req.url, req.events)
         .await?;
 
-    if let Some(first_event) = req.events.first() {
+    if let Some(first_event) = events.first() {
         let _ = service
             .send(
                 &user_address,
@@ -61,8 +168,8 @@ pub async fn register(
 
     let webhook = WebhookInfo {
         id,
-        url: req.url,
-        events: req.events,
+        url,
+        events,
         active: true,
         created_at: chrono::Utc::now().timestamp(),
     };
@@ -111,51 +218,134 @@ pub async fn delete(
     Ok(Json(ApiResponse::success("Webhook deleted".to_string())))
 }
 
+/// POST /api/v1/webhooks/{id}/test
+///
+/// # Notes
+/// * Sends a signed sample event through the regular delivery path and reports the outcome,
+///   status code, latency and the first 512 characters of the receiver's response.
+/// * Limited to 5 test deliveries per webhook every 5 minutes.
+pub async fn test_delivery(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> Result<Json<ApiResponse<WebhookTestResult>>> {
+    let user_address = require_user(&headers, &state).await?;
+    let service = WebhookService::new(state.db.clone(), state.config.clone());
+    service.ensure_active_owner(id, &user_address).await?;
+    enforce_test_delivery_limit(&state, id).await?;
+    let delivery = service.send_test(id, &user_address).await?;
+    Ok(Json(ApiResponse::success(WebhookTestResult {
+        success: delivery.success,
+        status_code: delivery.status_code,
+        latency_ms: delivery.latency_ms,
+        response_snippet: delivery.response_snippet,
+        error: delivery.error,
+    })))
+}
+
 /// GET /api/v1/webhooks/logs
+///
+/// # Notes
+/// * Filters: `webhook_id`, `status` (`success`/`failure`) and `from`/`to` on the log time.
+/// * Only the last `WEBHOOK_LOG_RETENTION_DAYS` of logs are retained.
 pub async fn get_logs(
     State(state): State<AppState>,
     headers: HeaderMap,
-) -> Result<Json<ApiResponse<Vec<String>>>> {
+    Pagination { page, limit }: Pagination,
+    range: TimeRange,
+    Query(query): Query<WebhookLogsQuery>,
+) -> Result<Json<ApiResponse<PaginatedResponse<WebhookLogEntry>>>> {
     let user_address = require_user(&headers, &state).await?;
+    let status = normalize_log_status_filter(query.status.as_deref())?;
+    let offset = i64::from(page - 1) * i64::from(limit);
+
+    // `$3` selects successful deliveries or everything else; a NULL status counts as failure.
+    let filters = "w.user_address = $1
+           AND ($2::BIGINT IS NULL OR wl.webhook_id = $2)
+           AND ($3::TEXT IS NULL
+                OR (COALESCE(wl.status, '') = 'success') = ($3 = 'success'))
+           AND ($4::TIMESTAMPTZ IS NULL OR wl.created_at >= $4)
+           AND ($5::TIMESTAMPTZ IS NULL OR wl.created_at <= $5)";
 
-    let rows = sqlx::query(
-        "SELECT event, delivered_at, status
+    let rows = sqlx::query(&format!(
+        "SELECT wl.id, wl.webhook_id, wl.event, wl.status, wl.response_status_code,
+                wl.response_body, wl.error_message, wl.created_at, wl.delivered_at
          FROM webhook_logs wl
          JOIN webhooks w ON wl.webhook_id = w.id
-         WHERE w.user_address = $1
-         ORDER BY delivered_at DESC
-         LIMIT 50",
-    )
+         WHERE {}
+         ORDER BY wl.created_at DESC, wl.id DESC
+         LIMIT $6 OFFSET $7",
+        filters
+    ))
     .bind(&user_address)
+    .bind(query.webhook_id)
+    .bind(status)
+    .bind(range.from)
+    .bind(range.to)
+    .bind(i64::from(limit))
+    .bind(offset)
     .fetch_all(state.db.pool())
     .await?;
 
+    let total: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*)
+         FROM webhook_logs wl
+         JOIN webhooks w ON wl.webhook_id = w.id
+         WHERE {}",
+        filters
+    ))
+    .bind(&user_address)
+    .bind(query.webhook_id)
+    .bind(status)
+    .bind(range.from)
+    .bind(range.to)
+    .fetch_one(state.db.pool())
+    .await?;
+
     let logs = rows
         .into_iter()
         .map(|row| {
-            let event: String = row.get("event");
-            let status: String = row.get("status");
-            let delivered_at: chrono::DateTime<chrono::Utc> = row.get("delivered_at");
-            format_webhook_log(&event, &status, delivered_at)
+            let created_at: chrono::DateTime<chrono::Utc> = row.get("created_at");
+            let delivered_at: Option<chrono::DateTime<chrono::Utc>> = row.get("delivered_at");
+            let response_body: Option<String> = row.get("response_body");
+            WebhookLogEntry {
+                id: row.get("id"),
+                webhook_id: row.get("webhook_id"),
+                event: row.get("event"),
+                status: row
+                    .get::<Option<String>, _>("status")
+                    .unwrap_or_else(|| "unknown".to_string()),
+                response_status_code: row.get("response_status_code"),
+                response_body: response_body.as_deref().map(truncate_response_body),
+                error_message: row.get("error_message"),
+                created_at: created_at.timestamp(),
+                delivered_at: delivered_at.map(|value| value.timestamp()),
+            }
         })
         .collect();
 
-    Ok(Json(ApiResponse::success(logs)))
+    Ok(Json(ApiResponse::success(PaginatedResponse::new(
+        logs, page, limit, total,
+    ))))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{TimeZone, Utc};
 
     #[test]
-    // Internal helper that parses or transforms values for `format_webhook_log_includes_fields`.
-    fn format_webhook_log_includes_fields() {
-        // Memastikan format log berisi waktu, event, dan status
-        let ts = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
-        let log = format_webhook_log("swap", "ok", ts);
-        assert!(log.contains("swap"));
-        assert!(log.contains("ok"));
-        assert!(log.contains("T"));
+    // Internal helper that parses or transforms values for `normalize_log_status_filter_accepts_known_states`.
+    fn normalize_log_status_filter_accepts_known_states() {
+        assert_eq!(normalize_log_status_filter(None).unwrap(), None);
+        assert_eq!(normalize_log_status_filter(Some("all")).unwrap(), None);
+        assert_eq!(
+            normalize_log_status_filter(Some("Success")).unwrap(),
+            Some("success")
+        );
+        assert_eq!(
+            normalize_log_status_filter(Some("failed")).unwrap(),
+            Some("failure")
+        );
+        assert!(normalize_log_status_filter(Some("pending")).is_err());
     }
 }
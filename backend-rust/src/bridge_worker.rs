@@ -1,10 +1,19 @@
+use crate::{
+    config::Config,
+    db::Database,
+    services::{
+        notification_service::{NotificationService, NotificationType},
+        onchain::OnchainReader,
+    },
+};
+use chrono::{DateTime, Utc};
 use dotenv::dotenv;
 use reqwest::Client;
 use serde::Deserialize;
 use starknet::{
     accounts::{Account, ExecutionEncoding, SingleOwnerAccount},
     core::{
-        types::{BlockId, BlockTag, Call, Felt},
+        types::{BlockId, BlockTag, Call, Event, ExecutionResult, Felt},
         utils::get_selector_from_name,
     },
     providers::jsonrpc::{HttpTransport, JsonRpcClient},
@@ -29,6 +38,9 @@ const MIN_USD_THRESHOLD: f64 = 100.0;
 const POINTS_PER_USD: f64 = 25.0;
 const POINT_DECIMALS_FACTOR: f64 = 1_000_000_000_000_000_000.0; // 1e18
 
+const HTLC_MONITOR_INTERVAL_SECS: u64 = 60;
+const HTLC_REFUND_WARNING_SECS: i64 = 6 * 3600;
+
 #[derive(Debug, Clone)]
 struct BridgeWatcherConfig {
     btc_vault_address: String,
@@ -199,6 +211,22 @@ struct MempoolVout {
     scriptpubkey_address: Option<String>,
 }
 
+/// Whether the optional BTC -> Starknet bridge watcher is enabled (`ENABLE_BTC_BRIDGE_WATCHER`).
+pub fn bridge_watcher_enabled() -> bool {
+    std::env::var("ENABLE_BTC_BRIDGE_WATCHER")
+        .map(|v| {
+            let normalized = v.trim().to_ascii_lowercase();
+            normalized == "1" || normalized == "true" || normalized == "yes" || normalized == "on"
+        })
+        .unwrap_or(false)
+}
+
+/// Whether the HTLC refund monitor runs (a non-placeholder private BTC swap contract is set).
+pub fn htlc_refund_monitor_enabled(config: &Config) -> bool {
+    let private_btc_swap_address = config.private_btc_swap_address.trim();
+    !private_btc_swap_address.is_empty() && !private_btc_swap_address.starts_with("0x0000")
+}
+
 /// Start the Bitcoin->Starknet bridge watcher loop.
 ///
 /// Required env:
@@ -250,6 +278,243 @@ pub async fn start_bridge_watcher() {
     }
 }
 
+#[derive(Debug, Clone)]
+struct HtlcRefundMonitorConfig {
+    warning_secs: i64,
+}
+
+impl HtlcRefundMonitorConfig {
+    // Internal helper that supports `from_env` operations.
+    fn from_env() -> Self {
+        let warning_secs = env::var("HTLC_REFUND_WARNING_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<i64>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(HTLC_REFUND_WARNING_SECS);
+        Self { warning_secs }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct PendingHtlcSwap {
+    id: i64,
+    user_address: String,
+    commitment: String,
+    swap_id: Option<i64>,
+    initiate_tx_hash: String,
+    timelock_at: DateTime<Utc>,
+    refund_preimage_available: bool,
+    refund_notified_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HtlcRefundAction {
+    Wait,
+    Notify,
+}
+
+// Internal helper that decides what the monitor should do for one in-flight swap.
+fn htlc_refund_action(
+    swap: &PendingHtlcSwap,
+    now: DateTime<Utc>,
+    warning_secs: i64,
+) -> HtlcRefundAction {
+    let warn_from = swap.timelock_at - chrono::Duration::seconds(warning_secs);
+    if swap.swap_id.is_some() && now >= warn_from && swap.refund_notified_at.is_none() {
+        return HtlcRefundAction::Notify;
+    }
+    HtlcRefundAction::Wait
+}
+
+/// Start the HTLC refund monitor for in-flight private BTC swaps.
+///
+/// Optional env:
+/// - `HTLC_REFUND_WARNING_SECS` how long before the timelock the user is notified (default 6h)
+///
+/// Only swaps whose initiate transaction emitted `SwapInitiated` are tracked; rows whose
+/// initiate reverted are marked `failed`. Relayer auto-refund is out of scope: the
+/// `PrivateBTCSwap` contract has no refund entrypoint or on-chain timelock, so the monitor
+/// notifies the user (including whether they reported holding the refund preimage) and leaves
+/// the refund to them.
+pub async fn start_htlc_refund_monitor(db: Database, config: Config) {
+    let monitor = HtlcRefundMonitorConfig::from_env();
+    let notifier = NotificationService::new(db.clone(), config.clone());
+    let mut ticker = time::interval(Duration::from_secs(HTLC_MONITOR_INTERVAL_SECS));
+    ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+
+    info!(
+        "HTLC refund monitor started. warning={}s",
+        monitor.warning_secs
+    );
+
+    loop {
+        ticker.tick().await;
+        if let Err(err) = monitor_htlc_refunds(&db, &config, &notifier, &monitor).await {
+            error!("HTLC refund monitor tick failed: {err}");
+        }
+    }
+}
+
+// Internal helper that supports `monitor_htlc_refunds` operations.
+async fn monitor_htlc_refunds(
+    db: &Database,
+    config: &Config,
+    notifier: &NotificationService,
+    monitor: &HtlcRefundMonitorConfig,
+) -> anyhow::Result<()> {
+    let now = Utc::now();
+    let horizon = now + chrono::Duration::seconds(monitor.warning_secs);
+    let swaps: Vec<PendingHtlcSwap> = sqlx::query_as(
+        "SELECT id, user_address, commitment, swap_id, initiate_tx_hash, timelock_at,
+                refund_preimage_available, refund_notified_at
+         FROM private_btc_swaps
+         WHERE status = 'initiated' AND timelock_at IS NOT NULL AND timelock_at <= $1
+           AND refund_notified_at IS NULL
+         ORDER BY timelock_at ASC
+         LIMIT 200",
+    )
+    .bind(horizon)
+    .fetch_all(db.pool())
+    .await?;
+
+    for mut swap in swaps {
+        if swap.swap_id.is_none() {
+            match resolve_initiated_swap_id(db, config, &swap).await {
+                Ok(swap_id) => swap.swap_id = swap_id,
+                Err(err) => {
+                    warn!(
+                        "Unable to confirm private BTC swap row {} on chain: {err}",
+                        swap.id
+                    );
+                    continue;
+                }
+            }
+        }
+        match htlc_refund_action(&swap, now, monitor.warning_secs) {
+            HtlcRefundAction::Wait => {}
+            HtlcRefundAction::Notify => notify_refund_due(db, notifier, &swap, now).await?,
+        }
+    }
+    Ok(())
+}
+
+// Internal helper that reads the on-chain `swap_id` from the initiate receipt and stores it.
+// Returns `None` while the receipt is unavailable, when it carries no matching event, or after
+// marking a reverted initiate as failed.
+async fn resolve_initiated_swap_id(
+    db: &Database,
+    config: &Config,
+    swap: &PendingHtlcSwap,
+) -> anyhow::Result<Option<i64>> {
+    let reader = OnchainReader::from_config(config)?;
+    let tx_hash = parse_felt(&swap.initiate_tx_hash)?;
+    let receipt = match reader.get_transaction_receipt(&tx_hash).await {
+        Ok(receipt) => receipt,
+        Err(err) => {
+            info!(
+                "Initiate receipt for private BTC swap row {} unavailable: {err}",
+                swap.id
+            );
+            return Ok(None);
+        }
+    };
+    if let ExecutionResult::Reverted { reason } = receipt.receipt.execution_result() {
+        warn!(
+            "Initiate for private BTC swap row {} reverted, marking it failed: {reason}",
+            swap.id
+        );
+        sqlx::query(
+            "UPDATE private_btc_swaps SET status = 'failed', updated_at = NOW()
+             WHERE id = $1 AND status = 'initiated'",
+        )
+        .bind(swap.id)
+        .execute(db.pool())
+        .await?;
+        return Ok(None);
+    }
+    let contract = parse_felt(&config.private_btc_swap_address)?;
+    let commitment = parse_felt(&swap.commitment)?;
+    let Some(swap_id) = swap_initiated_id(receipt.receipt.events(), contract, commitment)? else {
+        return Ok(None);
+    };
+    sqlx::query("UPDATE private_btc_swaps SET swap_id = $2, updated_at = NOW() WHERE id = $1")
+        .bind(swap.id)
+        .bind(swap_id)
+        .execute(db.pool())
+        .await?;
+    Ok(Some(swap_id))
+}
+
+// Internal helper that finds the `SwapInitiated { swap_id, commitment }` event for `commitment`.
+fn swap_initiated_id(
+    events: &[Event],
+    contract: Felt,
+    commitment: Felt,
+) -> anyhow::Result<Option<i64>> {
+    let selector = get_selector_from_name("SwapInitiated")
+        .map_err(|e| anyhow::anyhow!("Unable to resolve SwapInitiated selector: {e}"))?;
+    for event in events {
+        if event.from_address != contract || event.keys.first() != Some(&selector) {
+            continue;
+        }
+        if let [swap_id, event_commitment, ..] = event.data.as_slice() {
+            if *event_commitment == commitment {
+                let swap_id = u64::try_from(*swap_id)
+                    .map_err(|_| anyhow::anyhow!("SwapInitiated swap_id out of range"))?;
+                return Ok(Some(i64::try_from(swap_id)?));
+            }
+        }
+    }
+    Ok(None)
+}
+
+// Internal helper that builds the refund reminder text for one swap.
+fn refund_due_message(swap: &PendingHtlcSwap, now: DateTime<Utc>) -> String {
+    let mut message = if swap.timelock_at <= now {
+        "Your private BTC swap was not completed and its timelock has passed. Submit a refund to recover your funds.".to_string()
+    } else {
+        format!(
+            "Your private BTC swap has not been completed. It becomes refundable at {}.",
+            swap.timelock_at.to_rfc3339()
+        )
+    };
+    if !swap.refund_preimage_available {
+        message.push_str(
+            " You did not report holding the refund preimage; locate it before the refund window.",
+        );
+    }
+    message
+}
+
+// Internal helper that tells the user their swap is close to, or past, its refund timelock.
+async fn notify_refund_due(
+    db: &Database,
+    notifier: &NotificationService,
+    swap: &PendingHtlcSwap,
+    now: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let message = refund_due_message(swap, now);
+    notifier
+        .send_notification(
+            &swap.user_address,
+            NotificationType::SwapRefundDue,
+            "Private BTC swap refund".to_string(),
+            message,
+            Some(serde_json::json!({
+                "swap_row_id": swap.id,
+                "swap_id": swap.swap_id,
+                "timelock_at": swap.timelock_at,
+                "refund_preimage_available": swap.refund_preimage_available,
+            })),
+        )
+        .await?;
+    sqlx::query("UPDATE private_btc_swaps SET refund_notified_at = NOW() WHERE id = $1")
+        .bind(swap.id)
+        .execute(db.pool())
+        .await?;
+    Ok(())
+}
+
 // Internal helper that supports `process_once` operations.
 async fn process_once(
     client: &Client,
@@ -465,3 +730,89 @@ fn parse_felt(raw: &str) -> anyhow::Result<Felt> {
         Felt::from_dec_str(value).map_err(|e| anyhow::anyhow!("Invalid felt dec '{value}': {e}"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Internal helper that builds a pending swap fixture for `htlc_refund_action` tests.
+    fn pending_swap(timelock_at: DateTime<Utc>) -> PendingHtlcSwap {
+        PendingHtlcSwap {
+            id: 1,
+            user_address: "0x1".to_string(),
+            commitment: "0xc0".to_string(),
+            swap_id: Some(7),
+            initiate_tx_hash: "0xabc".to_string(),
+            timelock_at,
+            refund_preimage_available: true,
+            refund_notified_at: None,
+        }
+    }
+
+    #[test]
+    // Internal helper that supports `refund_due_message_flags_missing_preimage` operations.
+    fn refund_due_message_flags_missing_preimage() {
+        let now = Utc::now();
+        let mut swap = pending_swap(now - chrono::Duration::minutes(1));
+        assert!(refund_due_message(&swap, now).contains("timelock has passed"));
+        assert!(!refund_due_message(&swap, now).contains("preimage"));
+        swap.refund_preimage_available = false;
+        assert!(refund_due_message(&swap, now).contains("refund preimage"));
+    }
+
+    #[test]
+    // Internal helper that supports `htlc_refund_action_notifies_confirmed_swaps_once` operations.
+    fn htlc_refund_action_notifies_confirmed_swaps_once() {
+        let now = Utc::now();
+        let far = pending_swap(now + chrono::Duration::hours(12));
+        assert_eq!(htlc_refund_action(&far, now, 3600), HtlcRefundAction::Wait);
+
+        let mut near = pending_swap(now + chrono::Duration::minutes(30));
+        assert_eq!(
+            htlc_refund_action(&near, now, 3600),
+            HtlcRefundAction::Notify
+        );
+        near.refund_notified_at = Some(now);
+        assert_eq!(htlc_refund_action(&near, now, 3600), HtlcRefundAction::Wait);
+
+        let mut unconfirmed = pending_swap(now - chrono::Duration::minutes(1));
+        unconfirmed.swap_id = None;
+        assert_eq!(
+            htlc_refund_action(&unconfirmed, now, 3600),
+            HtlcRefundAction::Wait
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `swap_initiated_id_matches_contract_and_commitment` operations.
+    fn swap_initiated_id_matches_contract_and_commitment() {
+        let selector = get_selector_from_name("SwapInitiated").unwrap();
+        let contract = Felt::from(0x10_u64);
+        let commitment = Felt::from(0xc0_u64);
+        let events = vec![
+            Event {
+                from_address: Felt::from(0x99_u64),
+                keys: vec![selector],
+                data: vec![Felt::from(3_u64), commitment],
+            },
+            Event {
+                from_address: contract,
+                keys: vec![selector],
+                data: vec![Felt::from(4_u64), Felt::from(0xc1_u64)],
+            },
+            Event {
+                from_address: contract,
+                keys: vec![selector],
+                data: vec![Felt::from(5_u64), commitment],
+            },
+        ];
+        assert_eq!(
+            swap_initiated_id(&events, contract, commitment).unwrap(),
+            Some(5)
+        );
+        assert_eq!(
+            swap_initiated_id(&events[..2], contract, commitment).unwrap(),
+            None
+        );
+    }
+}
@@ -1,8 +1,17 @@
+use crate::constants::{
+    EPOCH_DURATION_SECONDS, POINTS_MIN_USD_BRIDGE_BTC, POINTS_MIN_USD_BRIDGE_BTC_TESTNET,
+    POINTS_MIN_USD_BRIDGE_ETH, POINTS_MIN_USD_BRIDGE_ETH_TESTNET, POINTS_PER_USD_BRIDGE_BTC,
+    POINTS_PER_USD_BRIDGE_ETH,
+};
+use crate::services::staking_discount::{parse_stake_discount_tiers, StakeDiscountTier};
 use serde::Deserialize;
 use std::env;
 use std::fs;
 use std::path::Path;
 
+// Shortest epoch accepted for rewards or NFT usage periods.
+const MIN_EPOCH_DURATION_SECONDS: i64 = 3600;
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     // Server
@@ -16,10 +25,15 @@ pub struct Config {
 
     // Redis
     pub redis_url: String,
+    pub redis_keepalive_interval_secs: u64,
 
     // Background workers
     pub point_calculator_batch_size: u32,
     pub point_calculator_max_batches_per_tick: u32,
+    pub point_calculator_batches_ceiling: u32,
+    pub point_calculator_max_attempts: u32,
+    pub transaction_stuck_after_secs: u64,
+    pub tx_reconcile_sample_size: u32,
 
     // Blockchain
     pub starknet_rpc_url: String,
@@ -33,8 +47,10 @@ pub struct Config {
     pub price_oracle_address: String,
     pub limit_order_book_address: String,
     pub staking_carel_address: Option<String>,
+    pub vesting_manager_address: Option<String>,
     pub discount_soulbound_address: Option<String>,
     pub treasury_address: Option<String>,
+    pub fee_recipient_address: Option<String>,
     pub referral_system_address: Option<String>,
     pub ai_executor_address: String,
     pub ai_signature_verifier_address: Option<String>,
@@ -47,8 +63,22 @@ pub struct Config {
     pub privacy_auto_garaga_public_inputs_file: Option<String>,
     pub privacy_auto_garaga_prover_cmd: Option<String>,
     pub privacy_auto_garaga_prover_timeout_ms: u64,
+    pub privacy_auto_submit_root_check: String,
+    pub garaga_warmup_interval_secs: u64,
+    pub privacy_max_proof_len: usize,
+    pub privacy_max_public_inputs_len: usize,
+    pub garaga_warmup_idle_secs: u64,
+    pub max_concurrent_proofs: usize,
+    pub proof_queue_timeout_ms: u64,
+    pub garaga_public_input_schema: Option<String>,
+    pub privacy_anonymity_min_set_size: u64,
+    pub privacy_anonymity_scan_blocks: u64,
     pub private_btc_swap_address: String,
     pub dark_pool_address: String,
+    pub dark_pool_min_notional_usd: f64,
+    pub dark_pool_max_notional_usd: f64,
+    pub dark_pool_token_limits: String,
+    pub achievement_definitions: String,
     pub private_payments_address: String,
     pub anonymous_credentials_address: String,
     // Token Addresses
@@ -62,6 +92,18 @@ pub struct Config {
     pub faucet_strk_amount: Option<f64>,
     pub faucet_carel_amount: Option<f64>,
     pub faucet_cooldown_hours: Option<u64>,
+    pub faucet_cluster_mode: String,
+    /// Reverse proxies that append to `X-Forwarded-For`; `0` fingerprints the TCP peer.
+    pub faucet_trusted_proxy_hops: usize,
+    pub faucet_fingerprint_salt: Option<String>,
+    pub deposit_base_token: String,
+
+    // Epochs
+    pub rewards_epoch_duration_seconds: i64,
+    pub nft_period_duration_seconds: i64,
+    pub carel_supply_cache_secs: u64,
+    pub display_amount_max_decimals: u32,
+    pub analytics_max_points: usize,
 
     // Backend Signing
     pub backend_private_key: String,
@@ -86,6 +128,10 @@ pub struct Config {
     pub ai_llm_rewrite_timeout_ms: u64,
     pub twitter_bearer_token: Option<String>,
     pub telegram_bot_token: Option<String>,
+    pub telegram_api_url: String,
+    pub email_api_url: Option<String>,
+    pub email_api_key: Option<String>,
+    pub email_from: Option<String>,
     pub discord_bot_token: Option<String>,
     pub social_tasks_json: Option<String>,
     pub admin_manual_key: Option<String>,
@@ -97,6 +143,11 @@ pub struct Config {
     pub atomiq_api_url: String,
     pub garden_api_key: Option<String>,
     pub garden_api_url: String,
+    pub garden_schema_names: String,
+    pub garden_schema_cache_ttl_secs: u64,
+    pub garden_earnings_snapshot_interval_secs: u64,
+    pub mempool_api_url: String,
+    pub mempool_testnet_api_url: String,
     pub sumo_login_api_key: Option<String>,
     pub sumo_login_api_url: String,
     pub xverse_api_key: Option<String>,
@@ -107,19 +158,62 @@ pub struct Config {
     pub stripe_secret_key: Option<String>,
     pub moonpay_api_key: Option<String>,
 
+    // Object storage (S3-compatible, epoch snapshot export)
+    pub object_storage_endpoint: Option<String>,
+    pub object_storage_bucket: Option<String>,
+    pub object_storage_region: String,
+    pub object_storage_access_key_id: Option<String>,
+    pub object_storage_secret_access_key: Option<String>,
+    pub snapshot_export_prefix: String,
+
     // Rate Limiting
     pub rate_limit_public: u32,
     pub rate_limit_authenticated: u32,
+    pub api_max_page_limit: u32,
+    pub api_max_time_range_days: i64,
+    pub http_body_limit_bytes: usize,
+    pub http_body_limit_small_bytes: usize,
+    pub http_body_limit_proof_bytes: usize,
+    pub http_request_timeout_secs: u64,
+    pub http_long_request_timeout_secs: u64,
+    pub webhook_log_retention_days: i64,
+    pub max_deadline_seconds: i64,
+    pub carel_stake_discount_tiers: Vec<StakeDiscountTier>,
+    pub max_combined_fee_discount_percent: f64,
+    pub ws_max_connections_per_user: usize,
+    pub ws_max_messages_per_minute: u32,
+    pub ws_auth_timeout_secs: u64,
+    pub points_per_usd_bridge_eth: f64,
+    pub points_per_usd_bridge_btc: f64,
+    pub points_min_usd_bridge_eth: Option<f64>,
+    pub points_min_usd_bridge_btc: Option<f64>,
+    pub stake_points_accrual_rates: String,
+    pub hide_balance_denominations: String,
+    pub hide_balance_swap_pairs: String,
+    pub onboarding_bonus_points: f64,
     pub ai_rate_limit_window_seconds: u64,
     pub ai_rate_limit_global_per_window: u32,
     pub ai_rate_limit_level_1_per_window: u32,
     pub ai_rate_limit_level_2_per_window: u32,
     pub ai_rate_limit_level_3_per_window: u32,
 
+    // Maintenance mode
+    pub maintenance_bypass_addresses: String,
+
+    // Relayer pool access
+    pub relayer_allowed_addresses: String,
+    pub relayer_min_ai_level: u8,
+    pub relayer_rate_limit_per_hour: u32,
+
     // CORS
     pub cors_allowed_origins: String,
     pub oracle_asset_ids: String,
+    pub oracle_route_dex_ids: String,
+    pub oracle_route_liquidity_buffer_pct: f64,
+    pub swap_route_cache_ttl_ms: u64,
+    pub swap_route_execute_max_age_ms: u64,
     pub bridge_provider_ids: String,
+    pub bridge_quote_sort: String,
     pub price_tokens: String,
     pub coingecko_api_url: String,
     pub coingecko_api_key: Option<String>,
@@ -174,6 +268,10 @@ impl Config {
                 }
                 None
             });
+        let point_calculator_max_batches_per_tick: u32 =
+            env::var("POINT_CALCULATOR_MAX_BATCHES_PER_TICK")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()?;
 
         Ok(Config {
             host: env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
@@ -189,15 +287,31 @@ impl Config {
 
             redis_url: env::var("REDIS_URL")
                 .unwrap_or_else(|_| "redis://localhost:6379".to_string()),
+            redis_keepalive_interval_secs: env::var("REDIS_KEEPALIVE_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
 
             point_calculator_batch_size: env::var("POINT_CALCULATOR_BATCH_SIZE")
                 .unwrap_or_else(|_| "500".to_string())
                 .parse()?,
-            point_calculator_max_batches_per_tick: env::var(
-                "POINT_CALCULATOR_MAX_BATCHES_PER_TICK",
-            )
-            .unwrap_or_else(|_| "20".to_string())
-            .parse()?,
+            point_calculator_max_batches_per_tick,
+            point_calculator_batches_ceiling: env::var("POINT_CALCULATOR_BATCHES_CEILING")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_else(|| point_calculator_max_batches_per_tick.max(200)),
+            point_calculator_max_attempts: env::var("POINT_CALCULATOR_MAX_ATTEMPTS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(5),
+            transaction_stuck_after_secs: env::var("TRANSACTION_STUCK_AFTER_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(900),
+            tx_reconcile_sample_size: env::var("TX_RECONCILE_SAMPLE_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(100),
 
             starknet_rpc_url: env::var("STARKNET_RPC_URL")?,
             starknet_chain_id: env::var("STARKNET_CHAIN_ID")
@@ -210,8 +324,10 @@ impl Config {
             price_oracle_address: env::var("PRICE_ORACLE_ADDRESS")?,
             limit_order_book_address: env::var("LIMIT_ORDER_BOOK_ADDRESS")?,
             staking_carel_address: env::var("STAKING_CAREL_ADDRESS").ok(),
+            vesting_manager_address: env::var("VESTING_MANAGER_ADDRESS").ok(),
             discount_soulbound_address: env::var("DISCOUNT_SOULBOUND_ADDRESS").ok(),
             treasury_address: env::var("TREASURY_ADDRESS").ok(),
+            fee_recipient_address: env::var("FEE_RECIPIENT_ADDRESS").ok(),
             referral_system_address: env::var("REFERRAL_SYSTEM_ADDRESS").ok(),
             ai_executor_address: env::var("AI_EXECUTOR_ADDRESS")?,
             ai_signature_verifier_address: env::var("AI_SIGNATURE_VERIFIER_ADDRESS").ok(),
@@ -233,8 +349,53 @@ impl Config {
             )
             .unwrap_or_else(|_| "45000".to_string())
             .parse()?,
+            privacy_auto_submit_root_check: env::var("PRIVACY_AUTO_SUBMIT_ROOT_CHECK")
+                .unwrap_or_else(|_| "reject".to_string()),
+            garaga_warmup_interval_secs: env::var("GARAGA_WARMUP_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0),
+            garaga_warmup_idle_secs: env::var("GARAGA_WARMUP_IDLE_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1800),
+            max_concurrent_proofs: env::var("MAX_CONCURRENT_PROOFS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(2),
+            proof_queue_timeout_ms: env::var("PROOF_QUEUE_TIMEOUT_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30_000),
+            privacy_max_proof_len: env::var("PRIVACY_MAX_PROOF_LEN")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(8192),
+            privacy_max_public_inputs_len: env::var("PRIVACY_MAX_PUBLIC_INPUTS_LEN")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(64),
+            garaga_public_input_schema: env::var("GARAGA_PUBLIC_INPUT_SCHEMA").ok(),
+            privacy_anonymity_min_set_size: env::var("PRIVACY_ANONYMITY_MIN_SET_SIZE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(20),
+            privacy_anonymity_scan_blocks: env::var("PRIVACY_ANONYMITY_SCAN_BLOCKS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(100_000),
             private_btc_swap_address: env::var("PRIVATE_BTC_SWAP_ADDRESS")?,
             dark_pool_address: env::var("DARK_POOL_ADDRESS")?,
+            dark_pool_min_notional_usd: env::var("DARK_POOL_MIN_NOTIONAL_USD")
+                .unwrap_or_else(|_| "50".to_string())
+                .parse()?,
+            dark_pool_max_notional_usd: env::var("DARK_POOL_MAX_NOTIONAL_USD")
+                .unwrap_or_else(|_| "250000".to_string())
+                .parse()?,
+            dark_pool_token_limits: env::var("DARK_POOL_TOKEN_LIMITS")
+                .unwrap_or_else(|_| "".to_string()),
+            achievement_definitions: env::var("ACHIEVEMENT_DEFINITIONS")
+                .unwrap_or_else(|_| "".to_string()),
             private_payments_address: env::var("PRIVATE_PAYMENTS_ADDRESS")?,
             anonymous_credentials_address: env::var("ANONYMOUS_CREDENTIALS_ADDRESS")?,
 
@@ -255,6 +416,39 @@ impl Config {
             faucet_cooldown_hours: env::var("FAUCET_COOLDOWN_HOURS")
                 .ok()
                 .and_then(|s| s.parse().ok()),
+            faucet_cluster_mode: env::var("FAUCET_CLUSTER_MODE")
+                .unwrap_or_else(|_| "linked".to_string()),
+            faucet_trusted_proxy_hops: env::var("FAUCET_TRUSTED_PROXY_HOPS")
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(0),
+            faucet_fingerprint_salt: env::var("FAUCET_FINGERPRINT_SALT")
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty()),
+            deposit_base_token: env::var("DEPOSIT_BASE_TOKEN")
+                .unwrap_or_else(|_| "USDC".to_string()),
+
+            rewards_epoch_duration_seconds: env::var("REWARDS_EPOCH_DURATION_SECONDS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(EPOCH_DURATION_SECONDS),
+            nft_period_duration_seconds: env::var("NFT_PERIOD_DURATION_SECONDS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(EPOCH_DURATION_SECONDS),
+            carel_supply_cache_secs: env::var("CAREL_SUPPLY_CACHE_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(300),
+            display_amount_max_decimals: env::var("DISPLAY_AMOUNT_MAX_DECIMALS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(8),
+            analytics_max_points: env::var("ANALYTICS_MAX_POINTS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(500),
 
             backend_private_key: env::var("BACKEND_PRIVATE_KEY")?,
             backend_public_key: env::var("BACKEND_PUBLIC_KEY")?,
@@ -298,6 +492,11 @@ impl Config {
                 .parse()?,
             twitter_bearer_token: env::var("TWITTER_BEARER_TOKEN").ok(),
             telegram_bot_token: env::var("TELEGRAM_BOT_TOKEN").ok(),
+            telegram_api_url: env::var("TELEGRAM_API_URL")
+                .unwrap_or_else(|_| "https://api.telegram.org".to_string()),
+            email_api_url: env::var("EMAIL_API_URL").ok(),
+            email_api_key: env::var("EMAIL_API_KEY").ok(),
+            email_from: env::var("EMAIL_FROM").ok(),
             discord_bot_token: env::var("DISCORD_BOT_TOKEN").ok(),
             social_tasks_json: env::var("SOCIAL_TASKS_JSON").ok(),
             admin_manual_key: env::var("ADMIN_MANUAL_KEY").ok(),
@@ -317,6 +516,21 @@ impl Config {
                 .ok()
                 .or_else(|| env::var("GARDEN_API_KEY").ok()),
             garden_api_url: env::var("GARDEN_API_URL").unwrap_or_else(|_| "".to_string()),
+            garden_schema_names: env::var("GARDEN_SCHEMA_NAMES").unwrap_or_else(|_| "".to_string()),
+            garden_schema_cache_ttl_secs: env::var("GARDEN_SCHEMA_CACHE_TTL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(3_600),
+            garden_earnings_snapshot_interval_secs: env::var(
+                "GARDEN_EARNINGS_SNAPSHOT_INTERVAL_SECS",
+            )
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(3_600),
+            mempool_api_url: env::var("MEMPOOL_API_URL")
+                .unwrap_or_else(|_| "https://mempool.space/api".to_string()),
+            mempool_testnet_api_url: env::var("MEMPOOL_TESTNET_API_URL")
+                .unwrap_or_else(|_| "https://mempool.space/testnet/api".to_string()),
             sumo_login_api_key: env::var("SUMO_LOGIN_API_KEY").ok(),
             sumo_login_api_url: env::var("SUMO_LOGIN_API_URL").unwrap_or_else(|_| "".to_string()),
             xverse_api_key: env::var("XVERSE_API_KEY").ok(),
@@ -327,12 +541,100 @@ impl Config {
             stripe_secret_key: env::var("STRIPE_SECRET_KEY").ok(),
             moonpay_api_key: env::var("MOONPAY_API_KEY").ok(),
 
+            object_storage_endpoint: env::var("OBJECT_STORAGE_ENDPOINT").ok(),
+            object_storage_bucket: env::var("OBJECT_STORAGE_BUCKET").ok(),
+            object_storage_region: env::var("OBJECT_STORAGE_REGION")
+                .unwrap_or_else(|_| "us-east-1".to_string()),
+            object_storage_access_key_id: env::var("OBJECT_STORAGE_ACCESS_KEY_ID").ok(),
+            object_storage_secret_access_key: env::var("OBJECT_STORAGE_SECRET_ACCESS_KEY").ok(),
+            snapshot_export_prefix: env::var("SNAPSHOT_EXPORT_PREFIX")
+                .unwrap_or_else(|_| "reward-snapshots".to_string()),
+
             rate_limit_public: env::var("RATE_LIMIT_PUBLIC")
                 .unwrap_or_else(|_| "100".to_string())
                 .parse()?,
             rate_limit_authenticated: env::var("RATE_LIMIT_AUTHENTICATED")
                 .unwrap_or_else(|_| "300".to_string())
                 .parse()?,
+            api_max_page_limit: env::var("API_MAX_PAGE_LIMIT")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(100),
+            api_max_time_range_days: env::var("API_MAX_TIME_RANGE_DAYS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(366),
+            http_body_limit_bytes: env::var("HTTP_BODY_LIMIT_BYTES")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1024 * 1024),
+            http_body_limit_small_bytes: env::var("HTTP_BODY_LIMIT_SMALL_BYTES")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(16 * 1024),
+            http_body_limit_proof_bytes: env::var("HTTP_BODY_LIMIT_PROOF_BYTES")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(4 * 1024 * 1024),
+            http_request_timeout_secs: env::var("HTTP_REQUEST_TIMEOUT_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(15),
+            http_long_request_timeout_secs: env::var("HTTP_LONG_REQUEST_TIMEOUT_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(120),
+            webhook_log_retention_days: env::var("WEBHOOK_LOG_RETENTION_DAYS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
+            max_deadline_seconds: env::var("MAX_DEADLINE_SECONDS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(3600),
+            carel_stake_discount_tiers: parse_stake_discount_tiers(
+                &env::var("CAREL_STAKE_DISCOUNT_TIERS").unwrap_or_default(),
+            )?,
+            max_combined_fee_discount_percent: env::var("MAX_COMBINED_FEE_DISCOUNT_PERCENT")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(50.0),
+            ws_max_connections_per_user: env::var("WS_MAX_CONNECTIONS_PER_USER")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(5),
+            ws_max_messages_per_minute: env::var("WS_MAX_MESSAGES_PER_MINUTE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(60),
+            ws_auth_timeout_secs: env::var("WS_AUTH_TIMEOUT_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(10),
+            points_per_usd_bridge_eth: env::var("POINTS_PER_USD_BRIDGE_ETH")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(POINTS_PER_USD_BRIDGE_ETH),
+            points_per_usd_bridge_btc: env::var("POINTS_PER_USD_BRIDGE_BTC")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(POINTS_PER_USD_BRIDGE_BTC),
+            points_min_usd_bridge_eth: env::var("POINTS_MIN_USD_BRIDGE_ETH")
+                .ok()
+                .and_then(|s| s.parse().ok()),
+            points_min_usd_bridge_btc: env::var("POINTS_MIN_USD_BRIDGE_BTC")
+                .ok()
+                .and_then(|s| s.parse().ok()),
+            stake_points_accrual_rates: env::var("STAKE_POINTS_ACCRUAL_RATES")
+                .unwrap_or_else(|_| "".to_string()),
+            hide_balance_denominations: env::var("HIDE_BALANCE_DENOMINATIONS")
+                .unwrap_or_else(|_| "".to_string()),
+            hide_balance_swap_pairs: env::var("HIDE_BALANCE_SWAP_PAIRS")
+                .unwrap_or_else(|_| "".to_string()),
+            onboarding_bonus_points: env::var("ONBOARDING_BONUS_POINTS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0.0),
             ai_rate_limit_window_seconds: env::var("AI_RATE_LIMIT_WINDOW_SECONDS")
                 .unwrap_or_else(|_| "60".to_string())
                 .parse()?,
@@ -349,10 +651,39 @@ impl Config {
                 .unwrap_or_else(|_| "8".to_string())
                 .parse()?,
 
+            maintenance_bypass_addresses: env::var("MAINTENANCE_BYPASS_ADDRESSES")
+                .unwrap_or_default(),
+
+            relayer_allowed_addresses: env::var("RELAYER_ALLOWED_ADDRESSES").unwrap_or_default(),
+            relayer_min_ai_level: env::var("RELAYER_MIN_AI_LEVEL")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0),
+            relayer_rate_limit_per_hour: env::var("RELAYER_RATE_LIMIT_PER_HOUR")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
+
             cors_allowed_origins: env::var("CORS_ALLOWED_ORIGINS")
                 .unwrap_or_else(|_| "*".to_string()),
             oracle_asset_ids: env::var("ORACLE_ASSET_IDS").unwrap_or_else(|_| "".to_string()),
+            oracle_route_dex_ids: env::var("ORACLE_ROUTE_DEX_IDS")
+                .unwrap_or_else(|_| crate::constants::DEFAULT_ORACLE_ROUTE_DEX_IDS.to_string()),
+            oracle_route_liquidity_buffer_pct: env::var("ORACLE_ROUTE_LIQUIDITY_BUFFER_PCT")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0.0),
+            swap_route_cache_ttl_ms: env::var("SWAP_ROUTE_CACHE_TTL_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(5_000),
+            swap_route_execute_max_age_ms: env::var("SWAP_ROUTE_EXECUTE_MAX_AGE_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1_000),
             bridge_provider_ids: env::var("BRIDGE_PROVIDER_IDS").unwrap_or_else(|_| "".to_string()),
+            bridge_quote_sort: env::var("BRIDGE_QUOTE_SORT")
+                .unwrap_or_else(|_| "best_output".to_string()),
             price_tokens: env::var("PRICE_TOKENS")
                 .unwrap_or_else(|_| "BTC,ETH,STRK,CAREL,USDT,USDC".to_string()),
             coingecko_api_url: env::var("COINGECKO_API_URL")
@@ -410,6 +741,11 @@ impl Config {
                 tracing::warn!("Using placeholder staking carel address");
             }
         }
+        if let Some(addr) = &self.vesting_manager_address {
+            if is_placeholder_address(addr) {
+                tracing::warn!("Using placeholder vesting manager address");
+            }
+        }
         if let Some(addr) = &self.discount_soulbound_address {
             if is_placeholder_address(addr) {
                 tracing::warn!("Using placeholder discount soulbound address");
@@ -420,6 +756,7 @@ impl Config {
                 tracing::warn!("Using placeholder treasury address");
             }
         }
+        let _ = &self.fee_recipient_address;
         if is_placeholder_address(&self.ai_executor_address) {
             tracing::warn!("Using placeholder AI executor address");
         }
@@ -446,8 +783,8 @@ impl Config {
         }
         if let Some(addr) = &self.ai_level_burn_address {
             if !addr.trim().is_empty() {
-                tracing::warn!(
-                    "AI_LEVEL_BURN_ADDRESS is legacy for AI upgrades. Upgrade payment verification now uses TREASURY_ADDRESS."
+                tracing::info!(
+                    "AI_LEVEL_BURN_ADDRESS is set; CAREL burn payments are verified against it instead of TREASURY_ADDRESS."
                 );
             }
         }
@@ -463,6 +800,59 @@ impl Config {
         if is_placeholder_address(&self.dark_pool_address) {
             tracing::warn!("Using placeholder dark pool address");
         }
+        if self.dark_pool_min_notional_usd < 0.0
+            || self.dark_pool_max_notional_usd < self.dark_pool_min_notional_usd
+        {
+            anyhow::bail!(
+                "DARK_POOL_MIN_NOTIONAL_USD must be >= 0 and <= DARK_POOL_MAX_NOTIONAL_USD"
+            );
+        }
+        let _ = &self.dark_pool_token_limits;
+        if !self.onboarding_bonus_points.is_finite() || self.onboarding_bonus_points < 0.0 {
+            anyhow::bail!("ONBOARDING_BONUS_POINTS must be a finite value >= 0");
+        }
+        if self.rewards_epoch_duration_seconds < MIN_EPOCH_DURATION_SECONDS {
+            anyhow::bail!(
+                "REWARDS_EPOCH_DURATION_SECONDS must be at least {} seconds",
+                MIN_EPOCH_DURATION_SECONDS
+            );
+        }
+        if self.nft_period_duration_seconds < MIN_EPOCH_DURATION_SECONDS {
+            anyhow::bail!(
+                "NFT_PERIOD_DURATION_SECONDS must be at least {} seconds",
+                MIN_EPOCH_DURATION_SECONDS
+            );
+        }
+        let _ = &self.carel_supply_cache_secs;
+        if self.display_amount_max_decimals > 18 {
+            anyhow::bail!("DISPLAY_AMOUNT_MAX_DECIMALS must be at most 18");
+        }
+        if self.analytics_max_points < 3 {
+            anyhow::bail!("ANALYTICS_MAX_POINTS must be at least 3");
+        }
+        if !matches!(
+            self.faucet_cluster_mode
+                .trim()
+                .to_ascii_lowercase()
+                .as_str(),
+            "off" | "linked" | "linked_ip"
+        ) {
+            anyhow::bail!("FAUCET_CLUSTER_MODE must be one of off, linked, linked_ip");
+        }
+        if self
+            .faucet_cluster_mode
+            .trim()
+            .eq_ignore_ascii_case("linked_ip")
+            && self.faucet_fingerprint_salt.is_none()
+        {
+            anyhow::bail!("FAUCET_FINGERPRINT_SALT is required when FAUCET_CLUSTER_MODE=linked_ip");
+        }
+        if !matches!(
+            self.deposit_base_token.trim().to_ascii_uppercase().as_str(),
+            "USDC" | "USDT"
+        ) {
+            anyhow::bail!("DEPOSIT_BASE_TOKEN must be USDC or USDT");
+        }
         if is_placeholder_address(&self.private_payments_address) {
             tracing::warn!("Using placeholder private payments address");
         }
@@ -484,6 +874,28 @@ impl Config {
             }
         }
 
+        let hide_default_recipient = env::var("HIDE_BALANCE_DEFAULT_RECIPIENT")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        if let Some(recipient) = hide_default_recipient.as_deref() {
+            if is_placeholder_address(recipient) {
+                anyhow::bail!(
+                    "HIDE_BALANCE_DEFAULT_RECIPIENT must be a real Starknet address, not a placeholder"
+                );
+            }
+        }
+        if env_truthy("HIDE_BALANCE_STRICT_PRIVACY_MODE") && hide_default_recipient.is_none() {
+            if is_production {
+                anyhow::bail!(
+                    "HIDE_BALANCE_STRICT_PRIVACY_MODE requires HIDE_BALANCE_DEFAULT_RECIPIENT in production"
+                );
+            }
+            tracing::warn!(
+                "HIDE_BALANCE_STRICT_PRIVACY_MODE is enabled without HIDE_BALANCE_DEFAULT_RECIPIENT; hide-mode swaps without an explicit recipient will be rejected"
+            );
+        }
+
         if is_production {
             if is_placeholder_address(&self.ai_executor_address) {
                 anyhow::bail!("AI_EXECUTOR_ADDRESS must be set to a real contract in production");
@@ -548,12 +960,128 @@ impl Config {
         if self.rate_limit_public == 0 || self.rate_limit_authenticated == 0 {
             tracing::warn!("Rate limit values should be > 0");
         }
+        if self.api_max_page_limit == 0 {
+            anyhow::bail!("API_MAX_PAGE_LIMIT must be > 0");
+        }
+        if self.api_max_time_range_days <= 0 {
+            anyhow::bail!("API_MAX_TIME_RANGE_DAYS must be > 0");
+        }
+        if self.http_body_limit_bytes == 0
+            || self.http_body_limit_small_bytes == 0
+            || self.http_body_limit_proof_bytes == 0
+        {
+            anyhow::bail!("HTTP_BODY_LIMIT_* values must be > 0");
+        }
+        if self.http_request_timeout_secs == 0 {
+            anyhow::bail!("HTTP_REQUEST_TIMEOUT_SECS must be > 0");
+        }
+        if self.http_long_request_timeout_secs < self.http_request_timeout_secs {
+            anyhow::bail!("HTTP_LONG_REQUEST_TIMEOUT_SECS must be >= HTTP_REQUEST_TIMEOUT_SECS");
+        }
+        if self.webhook_log_retention_days <= 0 {
+            anyhow::bail!("WEBHOOK_LOG_RETENTION_DAYS must be > 0");
+        }
+        if !self.oracle_route_liquidity_buffer_pct.is_finite()
+            || !(0.0..=100.0).contains(&self.oracle_route_liquidity_buffer_pct)
+        {
+            anyhow::bail!("ORACLE_ROUTE_LIQUIDITY_BUFFER_PCT must be between 0 and 100");
+        }
+        if !matches!(
+            self.bridge_quote_sort.trim().to_ascii_lowercase().as_str(),
+            "best_output" | "fastest"
+        ) {
+            anyhow::bail!("BRIDGE_QUOTE_SORT must be best_output or fastest");
+        }
+        for name in self.garden_schema_names_list() {
+            if name.contains('/') {
+                anyhow::bail!("GARDEN_SCHEMA_NAMES contains an invalid name: {}", name);
+            }
+        }
+        for dex_id in self.oracle_route_dex_ids_list() {
+            if starknet_core::types::Felt::from_hex(&dex_id).is_err() {
+                anyhow::bail!("ORACLE_ROUTE_DEX_IDS contains an invalid felt: {}", dex_id);
+            }
+        }
+        for entry in self.hide_balance_swap_pairs.split(',') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let valid = entry
+                .split_once('/')
+                .is_some_and(|(from, to)| !from.trim().is_empty() && !to.trim().is_empty());
+            if !valid {
+                anyhow::bail!("HIDE_BALANCE_SWAP_PAIRS entry must be FROM/TO: {}", entry);
+            }
+        }
+        for entry in self.hide_balance_denominations.split(',') {
+            let Some((_, amounts)) = entry.split_once('=').or_else(|| entry.split_once(':')) else {
+                if entry.trim().is_empty() {
+                    continue;
+                }
+                anyhow::bail!(
+                    "HIDE_BALANCE_DENOMINATIONS entry must be TOKEN=amount|amount: {}",
+                    entry
+                );
+            };
+            let valid = amounts.split('|').all(|amount| {
+                amount
+                    .trim()
+                    .parse::<f64>()
+                    .is_ok_and(|amount| amount.is_finite() && amount > 0.0)
+            });
+            if !valid {
+                anyhow::bail!(
+                    "HIDE_BALANCE_DENOMINATIONS has an invalid amount: {}",
+                    entry
+                );
+            }
+        }
+        if self.max_deadline_seconds <= 0 {
+            anyhow::bail!("MAX_DEADLINE_SECONDS must be > 0");
+        }
+        if !(0.0..=100.0).contains(&self.max_combined_fee_discount_percent) {
+            anyhow::bail!("MAX_COMBINED_FEE_DISCOUNT_PERCENT must be within 0..=100");
+        }
+        if self.ws_max_connections_per_user == 0 {
+            anyhow::bail!("WS_MAX_CONNECTIONS_PER_USER must be > 0");
+        }
+        if self.ws_max_messages_per_minute == 0 {
+            anyhow::bail!("WS_MAX_MESSAGES_PER_MINUTE must be > 0");
+        }
+        if self.ws_auth_timeout_secs == 0 {
+            anyhow::bail!("WS_AUTH_TIMEOUT_SECS must be > 0");
+        }
+        for (name, value) in [
+            (
+                "POINTS_PER_USD_BRIDGE_ETH",
+                Some(self.points_per_usd_bridge_eth),
+            ),
+            (
+                "POINTS_PER_USD_BRIDGE_BTC",
+                Some(self.points_per_usd_bridge_btc),
+            ),
+            ("POINTS_MIN_USD_BRIDGE_ETH", self.points_min_usd_bridge_eth),
+            ("POINTS_MIN_USD_BRIDGE_BTC", self.points_min_usd_bridge_btc),
+        ] {
+            if value.is_some_and(|value| !value.is_finite() || value < 0.0) {
+                anyhow::bail!("{} must be a finite value >= 0", name);
+            }
+        }
         if self.point_calculator_batch_size == 0 {
             tracing::warn!("POINT_CALCULATOR_BATCH_SIZE should be > 0");
         }
         if self.point_calculator_max_batches_per_tick == 0 {
             tracing::warn!("POINT_CALCULATOR_MAX_BATCHES_PER_TICK should be > 0");
         }
+        if self.point_calculator_batches_ceiling < self.point_calculator_max_batches_per_tick {
+            anyhow::bail!(
+                "POINT_CALCULATOR_BATCHES_CEILING must be >= POINT_CALCULATOR_MAX_BATCHES_PER_TICK"
+            );
+        }
+        if self.point_calculator_max_attempts == 0 {
+            anyhow::bail!("POINT_CALCULATOR_MAX_ATTEMPTS must be > 0");
+        }
         if self.ai_rate_limit_window_seconds == 0
             || self.ai_rate_limit_global_per_window == 0
             || self.ai_rate_limit_level_1_per_window == 0
@@ -583,6 +1111,10 @@ impl Config {
         let _ = &self.ai_llm_rewrite_timeout_ms;
         let _ = &self.twitter_bearer_token;
         let _ = &self.telegram_bot_token;
+        let _ = &self.telegram_api_url;
+        let _ = &self.email_api_url;
+        let _ = &self.email_api_key;
+        let _ = &self.email_from;
         let _ = &self.discord_bot_token;
         let _ = &self.social_tasks_json;
         let _ = &self.admin_manual_key;
@@ -598,12 +1130,59 @@ impl Config {
         let _ = &self.sumo_login_api_url;
         let _ = &self.xverse_api_key;
         let _ = &self.xverse_api_url;
-        let _ = &self.privacy_verifier_routers;
+        for (verifier, router) in self.privacy_verifier_router_entries() {
+            let kind =
+                crate::services::privacy_verifier::parse_privacy_verifier_kind(Some(&verifier))
+                    .map_err(|err| anyhow::anyhow!("PRIVACY_VERIFIER_ROUTERS: {}", err))?;
+            if !kind.as_str().eq_ignore_ascii_case(&verifier) {
+                anyhow::bail!(
+                    "PRIVACY_VERIFIER_ROUTERS: use the canonical name '{}' instead of '{}'",
+                    kind.as_str(),
+                    verifier
+                );
+            }
+            if router.is_empty() || is_placeholder_address(&router) {
+                anyhow::bail!(
+                    "PRIVACY_VERIFIER_ROUTERS: verifier '{}' has no router address configured",
+                    verifier
+                );
+            }
+        }
         let _ = &self.privacy_auto_garaga_payload_file;
         let _ = &self.privacy_auto_garaga_proof_file;
         let _ = &self.privacy_auto_garaga_public_inputs_file;
         let _ = &self.privacy_auto_garaga_prover_cmd;
         let _ = &self.privacy_auto_garaga_prover_timeout_ms;
+        let _ = &self.garaga_warmup_interval_secs;
+        let _ = &self.garaga_warmup_idle_secs;
+        if self.max_concurrent_proofs == 0 {
+            anyhow::bail!("MAX_CONCURRENT_PROOFS must be > 0");
+        }
+        if self.relayer_min_ai_level > 3 {
+            anyhow::bail!("RELAYER_MIN_AI_LEVEL must be between 0 and 3");
+        }
+        if self.privacy_max_proof_len == 0 || self.privacy_max_public_inputs_len == 0 {
+            anyhow::bail!("PRIVACY_MAX_PROOF_LEN and PRIVACY_MAX_PUBLIC_INPUTS_LEN must be > 0");
+        }
+        if !matches!(
+            self.privacy_auto_submit_root_check
+                .trim()
+                .to_ascii_lowercase()
+                .as_str(),
+            "off" | "reject" | "regenerate"
+        ) {
+            anyhow::bail!(
+                "PRIVACY_AUTO_SUBMIT_ROOT_CHECK must be one of off, reject, regenerate (got '{}')",
+                self.privacy_auto_submit_root_check
+            );
+        }
+        crate::services::public_input_schema::PublicInputSchema::parse(
+            self.garaga_public_input_schema.as_deref(),
+        )?;
+        let _ = &self.privacy_anonymity_min_set_size;
+        if self.privacy_anonymity_scan_blocks == 0 {
+            anyhow::bail!("PRIVACY_ANONYMITY_SCAN_BLOCKS must be greater than zero");
+        }
         let _ = &self.stripe_secret_key;
         let _ = &self.moonpay_api_key;
         let _ = &self.starknet_chain_id;
@@ -732,6 +1311,42 @@ impl Config {
         parse_kv_map(&self.bridge_provider_ids, provider)
     }
 
+    /// Garden schema names the schema proxy is allowed to forward.
+    ///
+    /// # Notes
+    /// * Falls back to `DEFAULT_GARDEN_SCHEMA_NAMES` when unset.
+    pub fn garden_schema_names_list(&self) -> Vec<String> {
+        let raw = self.garden_schema_names.trim();
+        let raw = if raw.is_empty() {
+            crate::constants::DEFAULT_GARDEN_SCHEMA_NAMES
+        } else {
+            raw
+        };
+        raw.split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Aggregator dex ids (felt hex) whose routes are priced by the oracle.
+    ///
+    /// # Notes
+    /// * Falls back to `DEFAULT_ORACLE_ROUTE_DEX_IDS` ('ORCL') when unset.
+    pub fn oracle_route_dex_ids_list(&self) -> Vec<String> {
+        let raw = self.oracle_route_dex_ids.trim();
+        let raw = if raw.is_empty() {
+            crate::constants::DEFAULT_ORACLE_ROUTE_DEX_IDS
+        } else {
+            raw
+        };
+        raw.split(',')
+            .map(str::trim)
+            .filter(|dex_id| !dex_id.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
     /// Handles `price_tokens_list` logic.
     ///
     /// # Arguments
@@ -776,6 +1391,176 @@ impl Config {
         parse_kv_map(&self.coingecko_ids, symbol)
     }
 
+    /// Returns the address credited with protocol fees in `fee_ledger`.
+    ///
+    /// # Notes
+    /// * Falls back to `TREASURY_ADDRESS` when `FEE_RECIPIENT_ADDRESS` is unset.
+    pub fn fee_recipient(&self) -> Option<&str> {
+        self.fee_recipient_address
+            .as_deref()
+            .or(self.treasury_address.as_deref())
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && !is_placeholder_address(value))
+    }
+
+    /// Returns the rewards/points epoch that contains `timestamp`.
+    pub fn rewards_epoch_at(&self, timestamp: i64) -> i64 {
+        epoch_at(timestamp, self.rewards_epoch_duration_seconds)
+    }
+
+    /// Returns the current rewards/points epoch.
+    pub fn current_rewards_epoch(&self) -> i64 {
+        self.rewards_epoch_at(chrono::Utc::now().timestamp())
+    }
+
+    /// Returns the current NFT discount usage period.
+    ///
+    /// # Notes
+    /// * Uses `NFT_PERIOD_DURATION_SECONDS`, which may differ from the rewards epoch.
+    pub fn current_nft_period_epoch(&self) -> i64 {
+        epoch_at(
+            chrono::Utc::now().timestamp(),
+            self.nft_period_duration_seconds,
+        )
+    }
+
+    /// Returns `(min_usd, points_per_usd)` for bridge points.
+    ///
+    /// # Notes
+    /// * Minimums default to the mainnet or testnet constant unless `POINTS_MIN_USD_BRIDGE_*` is set.
+    pub fn bridge_points_rule(&self, is_btc_bridge: bool) -> (f64, f64) {
+        let testnet = self.is_testnet();
+        if is_btc_bridge {
+            let default_min = if testnet {
+                POINTS_MIN_USD_BRIDGE_BTC_TESTNET
+            } else {
+                POINTS_MIN_USD_BRIDGE_BTC
+            };
+            (
+                self.points_min_usd_bridge_btc.unwrap_or(default_min),
+                self.points_per_usd_bridge_btc,
+            )
+        } else {
+            let default_min = if testnet {
+                POINTS_MIN_USD_BRIDGE_ETH_TESTNET
+            } else {
+                POINTS_MIN_USD_BRIDGE_ETH
+            };
+            (
+                self.points_min_usd_bridge_eth.unwrap_or(default_min),
+                self.points_per_usd_bridge_eth,
+            )
+        }
+    }
+
+    /// Returns the points accrued per staked USD per day for `pool`.
+    ///
+    /// # Notes
+    /// * `STAKE_POINTS_ACCRUAL_RATES` entries look like `CAREL=0.5`; unlisted pools do not accrue.
+    pub fn stake_points_accrual_rate(&self, pool: &str) -> f64 {
+        parse_kv_map(&self.stake_points_accrual_rates, pool)
+            .and_then(|raw| raw.parse::<f64>().ok())
+            .filter(|rate| rate.is_finite() && *rate > 0.0)
+            .unwrap_or(0.0)
+    }
+
+    /// Token pairs eligible for Hide Balance swaps, as upper-case `(from, to)` symbols.
+    ///
+    /// # Notes
+    /// * `HIDE_BALANCE_SWAP_PAIRS` entries look like `STRK/USDC` and are directional; an empty
+    ///   list leaves every pair eligible.
+    pub fn hide_balance_swap_pairs_list(&self) -> Vec<(String, String)> {
+        self.hide_balance_swap_pairs
+            .split(',')
+            .filter_map(|entry| entry.split_once('/'))
+            .map(|(from, to)| {
+                (
+                    from.trim().to_ascii_uppercase(),
+                    to.trim().to_ascii_uppercase(),
+                )
+            })
+            .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+            .collect()
+    }
+
+    /// Whether relayer pool submissions are restricted to an allowlist.
+    ///
+    /// # Notes
+    /// * Enabled when `RELAYER_ALLOWED_ADDRESSES` is set or `RELAYER_MIN_AI_LEVEL` is above 0.
+    pub fn relayer_allowlist_enabled(&self) -> bool {
+        !self.relayer_allowed_addresses.trim().is_empty() || self.relayer_min_ai_level > 0
+    }
+
+    /// Allowed fixed Hide Balance deposit amounts for `symbol`, as decimal token amounts.
+    ///
+    /// # Notes
+    /// * `HIDE_BALANCE_DENOMINATIONS` entries look like `STRK=10|100|1000`; unlisted tokens
+    ///   return an empty list.
+    pub fn hide_balance_denominations_for(&self, symbol: &str) -> Vec<String> {
+        parse_kv_map(&self.hide_balance_denominations, symbol)
+            .map(|raw| {
+                raw.split('|')
+                    .map(str::trim)
+                    .filter(|amount| !amount.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the configured treasury address, ignoring empty or placeholder values.
+    pub fn treasury(&self) -> Option<&str> {
+        self.treasury_address
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && !is_placeholder_address(value))
+    }
+
+    /// Returns the `(min, max)` USD notional allowed for one dark-pool order in `token`.
+    ///
+    /// # Notes
+    /// * `DARK_POOL_TOKEN_LIMITS` entries look like `WBTC=100:500000`; an empty side falls back
+    ///   to `DARK_POOL_MIN_NOTIONAL_USD` / `DARK_POOL_MAX_NOTIONAL_USD`.
+    pub fn dark_pool_limits_for(&self, token: &str) -> (f64, f64) {
+        let default = (
+            self.dark_pool_min_notional_usd,
+            self.dark_pool_max_notional_usd,
+        );
+        let Some(raw) = parse_kv_map(&self.dark_pool_token_limits, token) else {
+            return default;
+        };
+        let (min_raw, max_raw) = raw.split_once(':').unwrap_or((raw.as_str(), ""));
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite() && *value >= 0.0)
+        };
+        (
+            parse(min_raw).unwrap_or(default.0),
+            parse(max_raw).unwrap_or(default.1),
+        )
+    }
+
+    /// Returns every `verifier=router` pair listed in `PRIVACY_VERIFIER_ROUTERS`, in order.
+    pub fn privacy_verifier_router_entries(&self) -> Vec<(String, String)> {
+        self.privacy_verifier_routers
+            .split(',')
+            .filter_map(|entry| {
+                let trimmed = entry.trim();
+                if trimmed.is_empty() {
+                    return None;
+                }
+                let (k, v) = trimmed
+                    .split_once('=')
+                    .or_else(|| trimmed.split_once(':'))
+                    .unwrap_or((trimmed, ""));
+                Some((k.trim().to_string(), v.trim().to_string()))
+            })
+            .collect()
+    }
+
     /// Handles `privacy_router_for_verifier` logic.
     ///
     /// # Arguments
@@ -814,6 +1599,15 @@ fn parse_kv_map(raw: &str, key: &str) -> Option<String> {
         .next()
 }
 
+// Internal helper that maps a unix timestamp to its epoch index.
+fn epoch_at(timestamp: i64, duration_seconds: i64) -> i64 {
+    if timestamp <= 0 {
+        0
+    } else {
+        timestamp / duration_seconds.max(1)
+    }
+}
+
 // Internal helper that checks conditions for `is_placeholder_address`.
 fn is_placeholder_address(address: &str) -> bool {
     let trimmed = address.trim();
@@ -81,10 +81,6 @@ pub const FAUCET_AMOUNT_CAREL: f64 = 25.0;
 pub const FAUCET_AMOUNT_USDT: f64 = 25.0;
 pub const FAUCET_AMOUNT_USDC: f64 = 25.0;
 
-// Rate limits
-pub const RATE_LIMIT_REQUESTS_PER_MINUTE: u32 = 60;
-pub const RATE_LIMIT_REQUESTS_PER_HOUR: u32 = 1000;
-
 // Gas configuration
 pub const GAS_PRICE_SLOW: f64 = 0.001;
 pub const GAS_PRICE_STANDARD: f64 = 0.002;
@@ -101,6 +97,27 @@ pub const BRIDGE_GARDEN: &str = "Garden";
 pub const DEX_EKUBO: &str = "Ekubo";
 pub const DEX_HAIKO: &str = "Haiko";
 pub const DEX_AVNU: &str = "Avnu";
+// Aggregator dex id for oracle-priced routes: the ASCII bytes of 'ORCL' as a felt.
+pub const DEFAULT_ORACLE_ROUTE_DEX_IDS: &str = "0x4f52434c";
+// Garden schema names the `/garden/schemas/{name}` proxy forwards; override with GARDEN_SCHEMA_NAMES.
+pub const DEFAULT_GARDEN_SCHEMA_NAMES: &str = "evm:htlc,evm:htlc_erc20,starknet:htlc_erc20";
+
+// Transaction types accepted by `Database::save_transaction`; extend when adding a new flow.
+pub const TRANSACTION_TYPES: &[&str] = &[
+    "swap",
+    "bridge",
+    "stake",
+    "unstake",
+    "claim",
+    "limit_order",
+    "ai_level_upgrade",
+    "rename_fee",
+    "battle_hit",
+    "battle_miss",
+    "battle_win",
+    "battle_loss",
+    "battle_tmo_win",
+];
 
 // API version
 pub const API_VERSION: &str = "v1";
@@ -114,6 +131,12 @@ pub const INDEXER_INTERVAL_SECS: u64 = 5;
 pub const POINT_CALCULATOR_INTERVAL_SECS: u64 = 60;
 pub const PRICE_UPDATER_INTERVAL_SECS: u64 = 60;
 pub const ORDER_EXECUTOR_INTERVAL_SECS: u64 = 10;
+pub const NOTIFICATION_DIGEST_FLUSH_INTERVAL_SECS: u64 = 300;
+pub const LIVE_LEADERBOARD_RECONCILE_INTERVAL_SECS: u64 = 300;
+pub const ACHIEVEMENT_RECOMPUTE_INTERVAL_SECS: u64 = 600;
+pub const DEPOSIT_SWAP_WORKER_INTERVAL_SECS: u64 = 30;
+pub const DEPOSIT_SWAP_RECONCILE_AFTER_SECS: i64 = 120;
+pub const TX_RECONCILE_INTERVAL_SECS: u64 = 300;
 
 /// Map token symbol to Starknet address constant.
 pub fn token_address_for(symbol: &str) -> Option<&'static str> {
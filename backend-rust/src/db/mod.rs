@@ -1,5 +1,6 @@
 use crate::{
     config::Config,
+    constants::TRANSACTION_TYPES,
     error::{AppError, Result},
     models::*,
 };
@@ -23,6 +24,63 @@ pub struct NftDiscountState {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AiLevelUpgradeRecord {
+    pub user_address: String,
+    pub previous_level: i16,
+    pub target_level: i16,
+    pub payment_carel: rust_decimal::Decimal,
+    pub block_number: Option<i64>,
+    pub superseded_by_downgrade: bool,
+}
+
+/// Settled on-chain referral bonus alongside the off-chain referral points for its epoch.
+#[derive(Debug, Clone, serde::Serialize, sqlx::FromRow)]
+pub struct ReferralPayout {
+    pub tx_hash: String,
+    pub epoch: i64,
+    /// Referral points moved into PointStorage by the claim.
+    pub amount_points: f64,
+    pub offchain_referral_points: f64,
+    pub block_number: i64,
+    pub settled_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Unprocessed transaction older than the stuck threshold.
+#[derive(Debug, Clone, serde::Serialize, sqlx::FromRow)]
+pub struct StuckTransaction {
+    pub tx_hash: String,
+    pub user_address: String,
+    pub tx_type: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub processing_attempts: i32,
+    pub last_processing_error: Option<String>,
+    pub last_processing_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AiLevelDowngrade<'a> {
+    pub user_address: &'a str,
+    pub target_level: u8,
+    pub actor: &'a str,
+    pub refund_carel: rust_decimal::Decimal,
+    pub reason: Option<&'a str>,
+}
+
+/// Pending rewards claim taken by `Database::begin_rewards_claim`.
+pub struct RewardsClaim {
+    db_tx: sqlx::Transaction<'static, sqlx::Postgres>,
+    pub points: rust_decimal::Decimal,
+}
+
+impl RewardsClaim {
+    /// Makes the claim permanent once the payout went through.
+    pub async fn commit(self) -> Result<()> {
+        self.db_tx.commit().await?;
+        Ok(())
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct PriceTickUpsert<'a> {
     pub token: &'a str,
@@ -35,6 +93,15 @@ pub struct PriceTickUpsert<'a> {
     pub interval: &'a str,
 }
 
+/// Protocol fee recorded in `fee_ledger` alongside the transaction that paid it.
+#[derive(Clone, Copy, Debug)]
+pub struct FeeLedgerEntry<'a> {
+    pub token: &'a str,
+    pub fee_amount: rust_decimal::Decimal,
+    pub fee_usd: rust_decimal::Decimal,
+    pub fee_recipient: Option<&'a str>,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct NftDiscountStateUpsert<'a> {
     pub contract_address: &'a str,
@@ -47,6 +114,26 @@ pub struct NftDiscountStateUpsert<'a> {
     pub chain_used_in_period: i64,
 }
 
+// Internal helper that splits a spend over epoch balances oldest-first; `None` when short.
+fn plan_cross_epoch_spend(
+    balances: &[(i64, rust_decimal::Decimal)],
+    amount: rust_decimal::Decimal,
+) -> Option<Vec<(i64, rust_decimal::Decimal)>> {
+    let mut remaining = amount;
+    let mut plan = Vec::new();
+    for (epoch, balance) in balances {
+        if remaining <= rust_decimal::Decimal::ZERO {
+            break;
+        }
+        let spend = (*balance).min(remaining);
+        if spend > rust_decimal::Decimal::ZERO {
+            plan.push((*epoch, spend));
+            remaining -= spend;
+        }
+    }
+    (remaining <= rust_decimal::Decimal::ZERO).then_some(plan)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,8 +147,13 @@ mod tests {
             database_url: database_url.to_string(),
             database_max_connections: 1,
             redis_url: "redis://localhost:6379".to_string(),
+            redis_keepalive_interval_secs: 30,
             point_calculator_batch_size: 100,
             point_calculator_max_batches_per_tick: 1,
+            point_calculator_batches_ceiling: 1,
+            point_calculator_max_attempts: 5,
+            transaction_stuck_after_secs: 900,
+            tx_reconcile_sample_size: 100,
             starknet_rpc_url: "http://localhost:5050".to_string(),
             starknet_chain_id: "SN_MAIN".to_string(),
             ethereum_rpc_url: "http://localhost:8545".to_string(),
@@ -71,8 +163,10 @@ mod tests {
             price_oracle_address: "0x0000000000000000000000000000000000000004".to_string(),
             limit_order_book_address: "0x0000000000000000000000000000000000000005".to_string(),
             staking_carel_address: None,
+            vesting_manager_address: None,
             discount_soulbound_address: None,
             treasury_address: None,
+            fee_recipient_address: None,
             referral_system_address: None,
             ai_executor_address: "0x0000000000000000000000000000000000000006".to_string(),
             ai_signature_verifier_address: None,
@@ -85,8 +179,22 @@ mod tests {
             privacy_auto_garaga_public_inputs_file: None,
             privacy_auto_garaga_prover_cmd: None,
             privacy_auto_garaga_prover_timeout_ms: 45_000,
+            privacy_auto_submit_root_check: "reject".to_string(),
+            garaga_warmup_interval_secs: 0,
+            garaga_warmup_idle_secs: 1800,
+            max_concurrent_proofs: 2,
+            proof_queue_timeout_ms: 30_000,
+            privacy_max_proof_len: 8192,
+            privacy_max_public_inputs_len: 64,
+            garaga_public_input_schema: None,
+            privacy_anonymity_min_set_size: 20,
+            privacy_anonymity_scan_blocks: 100_000,
             private_btc_swap_address: "0x0000000000000000000000000000000000000009".to_string(),
             dark_pool_address: "0x0000000000000000000000000000000000000010".to_string(),
+            dark_pool_min_notional_usd: 50.0,
+            dark_pool_max_notional_usd: 250_000.0,
+            dark_pool_token_limits: "".to_string(),
+            achievement_definitions: "".to_string(),
             private_payments_address: "0x0000000000000000000000000000000000000011".to_string(),
             anonymous_credentials_address: "0x0000000000000000000000000000000000000012".to_string(),
             token_strk_address: None,
@@ -97,6 +205,15 @@ mod tests {
             faucet_strk_amount: None,
             faucet_carel_amount: None,
             faucet_cooldown_hours: None,
+            faucet_cluster_mode: "linked".to_string(),
+            faucet_trusted_proxy_hops: 0,
+            faucet_fingerprint_salt: None,
+            deposit_base_token: "USDC".to_string(),
+            rewards_epoch_duration_seconds: 2_592_000,
+            nft_period_duration_seconds: 2_592_000,
+            carel_supply_cache_secs: 300,
+            display_amount_max_decimals: 8,
+            analytics_max_points: 500,
             backend_private_key: "test_private".to_string(),
             backend_public_key: "test_public".to_string(),
             backend_account_address: None,
@@ -115,6 +232,10 @@ mod tests {
             ai_llm_rewrite_timeout_ms: 8_000,
             twitter_bearer_token: None,
             telegram_bot_token: None,
+            telegram_api_url: "https://api.telegram.org".to_string(),
+            email_api_url: None,
+            email_api_key: None,
+            email_from: None,
             discord_bot_token: None,
             social_tasks_json: None,
             admin_manual_key: None,
@@ -126,6 +247,11 @@ mod tests {
             atomiq_api_url: "".to_string(),
             garden_api_key: None,
             garden_api_url: "".to_string(),
+            garden_schema_names: "".to_string(),
+            garden_schema_cache_ttl_secs: 0,
+            garden_earnings_snapshot_interval_secs: 0,
+            mempool_api_url: "https://mempool.space/api".to_string(),
+            mempool_testnet_api_url: "https://mempool.space/testnet/api".to_string(),
             sumo_login_api_key: None,
             sumo_login_api_url: "".to_string(),
             xverse_api_key: None,
@@ -133,16 +259,53 @@ mod tests {
             privacy_verifier_routers: "".to_string(),
             stripe_secret_key: None,
             moonpay_api_key: None,
+            object_storage_endpoint: None,
+            object_storage_bucket: None,
+            object_storage_region: "us-east-1".to_string(),
+            object_storage_access_key_id: None,
+            object_storage_secret_access_key: None,
+            snapshot_export_prefix: "reward-snapshots".to_string(),
             rate_limit_public: 1,
             rate_limit_authenticated: 1,
+            api_max_page_limit: 100,
+            api_max_time_range_days: 366,
+            http_body_limit_bytes: 1024 * 1024,
+            http_body_limit_small_bytes: 16 * 1024,
+            http_body_limit_proof_bytes: 4 * 1024 * 1024,
+            http_request_timeout_secs: 15,
+            http_long_request_timeout_secs: 120,
+            webhook_log_retention_days: 30,
+            max_deadline_seconds: 3600,
+            carel_stake_discount_tiers: Vec::new(),
+            max_combined_fee_discount_percent: 50.0,
+            ws_max_connections_per_user: 5,
+            ws_max_messages_per_minute: 60,
+            ws_auth_timeout_secs: 10,
+            points_per_usd_bridge_eth: 15.0,
+            points_per_usd_bridge_btc: 25.0,
+            points_min_usd_bridge_eth: None,
+            points_min_usd_bridge_btc: None,
+            stake_points_accrual_rates: "".to_string(),
+            hide_balance_denominations: "".to_string(),
+            hide_balance_swap_pairs: "".to_string(),
+            onboarding_bonus_points: 0.0,
             ai_rate_limit_window_seconds: 60,
             ai_rate_limit_global_per_window: 40,
             ai_rate_limit_level_1_per_window: 20,
             ai_rate_limit_level_2_per_window: 10,
             ai_rate_limit_level_3_per_window: 8,
+            maintenance_bypass_addresses: String::new(),
+            relayer_allowed_addresses: String::new(),
+            relayer_min_ai_level: 0,
+            relayer_rate_limit_per_hour: 30,
             cors_allowed_origins: "*".to_string(),
             oracle_asset_ids: "".to_string(),
+            oracle_route_dex_ids: crate::constants::DEFAULT_ORACLE_ROUTE_DEX_IDS.to_string(),
+            oracle_route_liquidity_buffer_pct: 0.0,
+            swap_route_cache_ttl_ms: 0,
+            swap_route_execute_max_age_ms: 0,
             bridge_provider_ids: "".to_string(),
+            bridge_quote_sort: "best_output".to_string(),
             price_tokens: "BTC,ETH,STRK,CAREL,USDT,USDC".to_string(),
             coingecko_api_url: "https://api.coingecko.com/api/v3".to_string(),
             coingecko_api_key: None,
@@ -158,6 +321,151 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    // Internal helper that supports `consume_points_never_overdraws_under_concurrency` operations.
+    async fn consume_points_never_overdraws_under_concurrency() {
+        // Needs a migrated PostgreSQL instance; skipped when TEST_DATABASE_URL is unset.
+        let Ok(database_url) = std::env::var("TEST_DATABASE_URL") else {
+            return;
+        };
+        let db = Database::new(&test_config(&database_url))
+            .await
+            .expect("connect test database");
+        db.run_migrations().await.expect("run migrations");
+
+        let user = format!("0xconsume{}", chrono::Utc::now().timestamp_micros());
+        let epoch = 1;
+        db.create_or_update_points(
+            &user,
+            epoch,
+            rust_decimal::Decimal::from(100),
+            rust_decimal::Decimal::ZERO,
+            rust_decimal::Decimal::ZERO,
+        )
+        .await
+        .expect("seed points");
+
+        let attempts = (0..10).map(|_| {
+            let db = db.clone();
+            let user = user.clone();
+            tokio::spawn(async move {
+                db.consume_points_across_epochs(&user, epoch, rust_decimal::Decimal::from(30))
+                    .await
+                    .is_ok()
+            })
+        });
+        let mut succeeded = 0;
+        for attempt in attempts.collect::<Vec<_>>() {
+            if attempt.await.expect("consume task") {
+                succeeded += 1;
+            }
+        }
+        assert_eq!(succeeded, 3);
+
+        let remaining: rust_decimal::Decimal = sqlx::query_scalar(
+            "SELECT total_points FROM points WHERE user_address = $1 AND epoch = $2",
+        )
+        .bind(&user)
+        .bind(epoch)
+        .fetch_one(db.pool())
+        .await
+        .expect("read remaining points");
+        assert_eq!(remaining, rust_decimal::Decimal::from(10));
+
+        sqlx::query("DELETE FROM points WHERE user_address = $1")
+            .bind(&user)
+            .execute(db.pool())
+            .await
+            .expect("cleanup points");
+    }
+
+    #[test]
+    // Internal helper that supports `ensure_known_tx_type_rejects_typos` operations.
+    fn ensure_known_tx_type_rejects_typos() {
+        assert!(ensure_known_tx_type("swap").is_ok());
+        assert!(ensure_known_tx_type("battle_tmo_win").is_ok());
+        let err = ensure_known_tx_type("swpa").expect_err("typo must be rejected");
+        assert!(err.to_string().contains("swpa"));
+        assert!(ensure_known_tx_type("Swap").is_err());
+    }
+
+    #[tokio::test]
+    // Internal helper that supports `grant_onboarding_bonus_is_idempotent` operations.
+    async fn grant_onboarding_bonus_is_idempotent() {
+        // Needs a migrated PostgreSQL instance; skipped when TEST_DATABASE_URL is unset.
+        let Ok(database_url) = std::env::var("TEST_DATABASE_URL") else {
+            return;
+        };
+        let db = Database::new(&test_config(&database_url))
+            .await
+            .expect("connect test database");
+        db.run_migrations().await.expect("run migrations");
+
+        let user = format!("0xonboard{}", chrono::Utc::now().timestamp_micros());
+        let epoch = 1;
+        let bonus = rust_decimal::Decimal::from(50);
+        db.create_user(&user).await.expect("create user");
+
+        let attempts = (0..5).map(|_| {
+            let db = db.clone();
+            let user = user.clone();
+            tokio::spawn(async move {
+                db.grant_onboarding_bonus(&user, epoch, bonus)
+                    .await
+                    .expect("grant bonus")
+            })
+        });
+        let mut granted = 0;
+        for attempt in attempts.collect::<Vec<_>>() {
+            if attempt.await.expect("grant task") {
+                granted += 1;
+            }
+        }
+        assert_eq!(granted, 1);
+        assert!(!db
+            .grant_onboarding_bonus(&user, epoch, bonus)
+            .await
+            .expect("repeat grant"));
+
+        let total: rust_decimal::Decimal = sqlx::query_scalar(
+            "SELECT total_points FROM points WHERE user_address = $1 AND epoch = $2",
+        )
+        .bind(&user)
+        .bind(epoch)
+        .fetch_one(db.pool())
+        .await
+        .expect("read bonus points");
+        assert_eq!(total, bonus);
+
+        sqlx::query("DELETE FROM points WHERE user_address = $1")
+            .bind(&user)
+            .execute(db.pool())
+            .await
+            .expect("cleanup points");
+        sqlx::query("DELETE FROM users WHERE address = $1")
+            .bind(&user)
+            .execute(db.pool())
+            .await
+            .expect("cleanup user");
+    }
+
+    #[test]
+    // Internal helper that supports `plan_cross_epoch_spend_drains_oldest_first` operations.
+    fn plan_cross_epoch_spend_drains_oldest_first() {
+        let d = rust_decimal::Decimal::from;
+        let balances = vec![(1, d(40)), (2, d(25)), (5, d(100))];
+        assert_eq!(
+            plan_cross_epoch_spend(&balances, d(50)),
+            Some(vec![(1, d(40)), (2, d(10))])
+        );
+        assert_eq!(
+            plan_cross_epoch_spend(&balances, d(165)),
+            Some(vec![(1, d(40)), (2, d(25)), (5, d(100))])
+        );
+        assert_eq!(plan_cross_epoch_spend(&balances, d(166)), None);
+        assert_eq!(plan_cross_epoch_spend(&[], d(1)), None);
+    }
+
     #[test]
     // Internal helper that parses or transforms values for `normalize_wallet_address_is_case_insensitive_per_chain`.
     fn normalize_wallet_address_is_case_insensitive_per_chain() {
@@ -355,7 +663,9 @@ impl Database {
     /// * `Err(AppError)` when validation, authorization, or integration checks fail.
     ///
     /// # Notes
-    /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
+    /// * Only ever raises the stored level and is called solely by paid upgrades, so it cannot
+    ///   undo a downgrade unless the user pays for the higher level again.
+    /// * Use `downgrade_user_ai_level` to lower a level.
     pub async fn upsert_user_ai_level(&self, address: &str, level: u8) -> Result<u8> {
         ensure_varchar_max("user_ai_levels.user_address", address, 66)?;
         if !(1..=3).contains(&level) {
@@ -439,6 +749,99 @@ impl Database {
         Ok(())
     }
 
+    /// Fetches the upgrade recorded for `onchain_tx_hash`, if any.
+    pub async fn find_ai_level_upgrade_by_tx(
+        &self,
+        onchain_tx_hash: &str,
+    ) -> Result<Option<AiLevelUpgradeRecord>> {
+        let record = sqlx::query_as::<_, AiLevelUpgradeRecord>(
+            "SELECT u.user_address, u.previous_level, u.target_level, u.payment_carel, u.block_number,
+                    EXISTS (
+                        SELECT 1 FROM ai_level_upgrades d
+                        WHERE d.user_address = u.user_address
+                          AND d.direction = 'downgrade'
+                          AND d.id > u.id
+                    ) AS superseded_by_downgrade
+             FROM ai_level_upgrades u
+             WHERE u.onchain_tx_hash = $1 AND u.direction = 'upgrade'
+             LIMIT 1",
+        )
+        .bind(onchain_tx_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(record)
+    }
+
+    /// Lowers a user's AI level and records the downgrade in `ai_level_upgrades`.
+    ///
+    /// # Returns
+    /// * `Ok((previous_level, current_level))` when the level was lowered.
+    /// * `Err(AppError::BadRequest)` when `target_level` is not below the current level.
+    ///
+    /// # Notes
+    /// * The level row is locked so a concurrent upgrade cannot interleave with the downgrade.
+    pub async fn downgrade_user_ai_level(&self, change: AiLevelDowngrade<'_>) -> Result<(u8, u8)> {
+        ensure_varchar_max("user_ai_levels.user_address", change.user_address, 66)?;
+        if !(1..=2).contains(&change.target_level) {
+            return Err(AppError::BadRequest(
+                "target_level must be 1 or 2".to_string(),
+            ));
+        }
+        if !matches!(change.actor, "user" | "admin") {
+            return Err(AppError::BadRequest("Invalid downgrade actor".to_string()));
+        }
+        if change.refund_carel < rust_decimal::Decimal::ZERO {
+            return Err(AppError::BadRequest(
+                "refund_carel must be >= 0".to_string(),
+            ));
+        }
+
+        let mut db_tx = self.pool.begin().await?;
+        let previous = sqlx::query_scalar::<_, i16>(
+            "SELECT level FROM user_ai_levels WHERE user_address = $1 FOR UPDATE",
+        )
+        .bind(change.user_address)
+        .fetch_optional(&mut *db_tx)
+        .await?
+        .unwrap_or(1)
+        .clamp(1, 3) as u8;
+        if change.target_level >= previous {
+            return Err(AppError::BadRequest(format!(
+                "AI Level {} is not above target level {}",
+                previous, change.target_level
+            )));
+        }
+
+        sqlx::query(
+            "UPDATE user_ai_levels
+             SET level = $2,
+                 upgraded_at = CASE WHEN $2 > 1 THEN upgraded_at ELSE NULL END,
+                 updated_at = NOW()
+             WHERE user_address = $1",
+        )
+        .bind(change.user_address)
+        .bind(change.target_level as i16)
+        .execute(&mut *db_tx)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO ai_level_upgrades
+                (user_address, previous_level, target_level, payment_carel, direction, actor, refund_carel, reason)
+             VALUES ($1, $2, $3, 0, 'downgrade', $4, $5, $6)",
+        )
+        .bind(change.user_address)
+        .bind(previous as i16)
+        .bind(change.target_level as i16)
+        .bind(change.actor)
+        .bind(change.refund_carel)
+        .bind(change.reason)
+        .execute(&mut *db_tx)
+        .await?;
+
+        db_tx.commit().await?;
+        Ok((previous, change.target_level))
+    }
+
     /// Fetches data for `find_user_by_sumo_subject`.
     ///
     /// # Arguments
@@ -876,7 +1279,7 @@ impl Database {
         let total = swap_points + bridge_points + stake_points;
 
         // Upsert yang menambah nilai yang sudah ada (accumulate deltas)
-        sqlx::query(
+        let new_total: rust_decimal::Decimal = sqlx::query_scalar(
             r#"
             INSERT INTO points
                 (user_address, epoch, swap_points, bridge_points, stake_points, total_points)
@@ -887,6 +1290,7 @@ impl Database {
                 stake_points  = points.stake_points  + EXCLUDED.stake_points,
                 total_points  = points.total_points  + EXCLUDED.total_points,
                 updated_at    = NOW()
+            RETURNING total_points
             "#,
         )
         .bind(address)
@@ -895,56 +1299,121 @@ impl Database {
         .bind(bridge_points)
         .bind(stake_points)
         .bind(total)
-        .execute(&self.pool)
+        .fetch_one(&self.pool)
         .await?;
+        crate::services::snapshot_manager::record_points_total(address, epoch, new_total);
         Ok(())
     }
 
-    /// Handles `consume_points` logic.
-    ///
-    /// # Arguments
-    /// * Uses function parameters as validated input and runtime context.
+    /// Spends `amount` points from the user's unclaimed finalized epochs oldest-first, then
+    /// `current_epoch`.
     ///
     /// # Returns
-    /// * `Ok(...)` when processing succeeds.
-    /// * `Err(AppError)` when validation, authorization, or integration checks fail.
+    /// * `Ok(())` when the combined balance covered the spend.
+    /// * `Err(AppError::BadRequest)` when it is short; nothing is spent in that case.
     ///
     /// # Notes
-    /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
-    pub async fn consume_points(
+    /// * Runs in one locked transaction so concurrent spends cannot overdraw.
+    /// * Epochs whose rewards were claimed are skipped, and points taken from finalized epochs
+    ///   are recorded in `redeemed_points` so `reward_points_total` keeps other users' shares
+    ///   unchanged.
+    /// * Rewards claims convert one finalized epoch via `begin_rewards_claim`; AI level
+    ///   upgrades are paid in CAREL and do not consume points.
+    pub async fn consume_points_across_epochs(
         &self,
         address: &str,
-        epoch: i64,
+        current_epoch: i64,
         amount: rust_decimal::Decimal,
     ) -> Result<()> {
-        let current: Option<rust_decimal::Decimal> = sqlx::query_scalar(
-            "SELECT total_points FROM points WHERE user_address = $1 AND epoch = $2",
+        let mut db_tx = self.pool.begin().await?;
+        let balances: Vec<(i64, rust_decimal::Decimal)> = sqlx::query_as(
+            "SELECT epoch, total_points FROM points
+             WHERE user_address = $1
+               AND total_points > 0
+               AND ((COALESCE(finalized, false) = true
+                     AND COALESCE(rewards_claimed, false) = false)
+                    OR epoch = $2)
+             ORDER BY epoch ASC
+             FOR UPDATE",
         )
         .bind(address)
-        .bind(epoch)
-        .fetch_optional(&self.pool)
+        .bind(current_epoch)
+        .fetch_all(&mut *db_tx)
         .await?;
 
-        let current_points = current.unwrap_or(rust_decimal::Decimal::ZERO);
-        if current_points < amount {
+        let Some(plan) = plan_cross_epoch_spend(&balances, amount) else {
             return Err(crate::error::AppError::BadRequest(
                 "Insufficient points".to_string(),
             ));
+        };
+        let mut remaining_by_epoch = Vec::with_capacity(plan.len());
+        for (epoch, spend) in plan {
+            let remaining: rust_decimal::Decimal = sqlx::query_scalar(
+                "UPDATE points
+                 SET spent_points = COALESCE(spent_points, 0) + $3,
+                     redeemed_points = COALESCE(redeemed_points, 0)
+                         + CASE WHEN COALESCE(finalized, false) THEN $3 ELSE 0 END,
+                     total_points = total_points - $3
+                 WHERE user_address = $1 AND epoch = $2
+                 RETURNING total_points",
+            )
+            .bind(address)
+            .bind(epoch)
+            .bind(spend)
+            .fetch_one(&mut *db_tx)
+            .await?;
+            remaining_by_epoch.push((epoch, remaining));
         }
+        db_tx.commit().await?;
+        for (epoch, remaining) in remaining_by_epoch {
+            crate::services::snapshot_manager::record_points_total(address, epoch, remaining);
+        }
+        Ok(())
+    }
 
-        sqlx::query(
-            "UPDATE points
-             SET spent_points = COALESCE(spent_points, 0) + $3,
-                 total_points = GREATEST(0, total_points - $3)
-             WHERE user_address = $1 AND epoch = $2",
+    /// Takes the rewards claim for a finalized epoch inside an open transaction.
+    ///
+    /// # Returns
+    /// * `Ok(Some(claim))` holding the claimable points; the claim only sticks once
+    ///   `RewardsClaim::commit` runs, and dropping it releases the epoch.
+    /// * `Ok(None)` when the epoch is not finalized or was already claimed.
+    ///
+    /// # Notes
+    /// * The row stays locked until commit, so point spends on the epoch wait for the payout.
+    pub async fn begin_rewards_claim(
+        &self,
+        address: &str,
+        epoch: i64,
+    ) -> Result<Option<RewardsClaim>> {
+        let mut db_tx = self.pool.begin().await?;
+        let points = sqlx::query_scalar(
+            "UPDATE points SET rewards_claimed = true
+             WHERE user_address = $1 AND epoch = $2
+               AND COALESCE(finalized, false) = true
+               AND COALESCE(rewards_claimed, false) = false
+             RETURNING total_points",
         )
         .bind(address)
         .bind(epoch)
-        .bind(amount)
-        .execute(&self.pool)
+        .fetch_optional(&mut *db_tx)
         .await?;
+        Ok(points.map(|points| RewardsClaim { db_tx, points }))
+    }
 
-        Ok(())
+    /// Denominator for an epoch's pro-rata reward shares.
+    ///
+    /// # Notes
+    /// * Includes points redeemed from the finalized epoch, so a spend only forfeits the
+    ///   spender's share instead of growing everyone else's.
+    pub async fn reward_points_total(&self, epoch: i64) -> Result<rust_decimal::Decimal> {
+        let total = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(total_points + COALESCE(redeemed_points, 0)), 0)
+             FROM points WHERE epoch = $1",
+        )
+        .bind(epoch)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(total)
     }
 
     /// Handles `add_referral_points` logic.
@@ -964,7 +1433,7 @@ impl Database {
         epoch: i64,
         amount: rust_decimal::Decimal,
     ) -> Result<()> {
-        sqlx::query(
+        let new_total: rust_decimal::Decimal = sqlx::query_scalar(
             r#"
             INSERT INTO points
                 (user_address, epoch, referral_points, total_points)
@@ -973,13 +1442,15 @@ impl Database {
             SET referral_points = points.referral_points + EXCLUDED.referral_points,
                 total_points = points.total_points + EXCLUDED.total_points,
                 updated_at = NOW()
+            RETURNING total_points
             "#,
         )
         .bind(address)
         .bind(epoch)
         .bind(amount)
-        .execute(&self.pool)
+        .fetch_one(&self.pool)
         .await?;
+        crate::services::snapshot_manager::record_points_total(address, epoch, new_total);
 
         Ok(())
     }
@@ -1001,7 +1472,7 @@ impl Database {
         epoch: i64,
         amount: rust_decimal::Decimal,
     ) -> Result<()> {
-        sqlx::query(
+        let new_total: rust_decimal::Decimal = sqlx::query_scalar(
             r#"
             INSERT INTO points
                 (user_address, epoch, social_points, total_points)
@@ -1010,16 +1481,124 @@ impl Database {
             SET social_points = points.social_points + EXCLUDED.social_points,
                 total_points = points.total_points + EXCLUDED.total_points,
                 updated_at = NOW()
+            RETURNING total_points
             "#,
         )
         .bind(address)
         .bind(epoch)
         .bind(amount)
-        .execute(&self.pool)
+        .fetch_one(&self.pool)
         .await?;
+        crate::services::snapshot_manager::record_points_total(address, epoch, new_total);
 
         Ok(())
     }
+
+    /// Grants the one-time onboarding bonus as social points in `epoch`.
+    ///
+    /// # Returns
+    /// * `Ok(true)` when the bonus was granted by this call.
+    /// * `Ok(false)` when the user already received it or has no user row.
+    ///
+    /// # Notes
+    /// * The user-row marker and the points upsert commit together, so concurrent
+    ///   wallet links or transactions cannot grant twice.
+    pub async fn grant_onboarding_bonus(
+        &self,
+        address: &str,
+        epoch: i64,
+        amount: rust_decimal::Decimal,
+    ) -> Result<bool> {
+        if amount <= rust_decimal::Decimal::ZERO {
+            return Ok(false);
+        }
+        let mut db_tx = self.pool.begin().await?;
+        let marked = sqlx::query(
+            "UPDATE users SET onboarding_bonus_granted_at = NOW()
+             WHERE address = $1 AND onboarding_bonus_granted_at IS NULL",
+        )
+        .bind(address)
+        .execute(&mut *db_tx)
+        .await?;
+        if marked.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        let new_total: rust_decimal::Decimal = sqlx::query_scalar(
+            r#"
+            INSERT INTO points
+                (user_address, epoch, social_points, total_points)
+            VALUES ($1, $2, $3, $3)
+            ON CONFLICT (user_address, epoch) DO UPDATE
+            SET social_points = points.social_points + EXCLUDED.social_points,
+                total_points = points.total_points + EXCLUDED.total_points,
+                updated_at = NOW()
+            RETURNING total_points
+            "#,
+        )
+        .bind(address)
+        .bind(epoch)
+        .bind(amount)
+        .fetch_one(&mut *db_tx)
+        .await?;
+        db_tx.commit().await?;
+        crate::services::snapshot_manager::record_points_total(address, epoch, new_total);
+
+        Ok(true)
+    }
+
+    /// Records an on-chain referral bonus settlement; replays of the same event are ignored.
+    pub async fn record_referral_payout(
+        &self,
+        tx_hash: &str,
+        referrer: &str,
+        epoch: i64,
+        amount_points: rust_decimal::Decimal,
+        block_number: i64,
+    ) -> Result<bool> {
+        ensure_varchar_max("referral_payouts.tx_hash", tx_hash, 66)?;
+        ensure_varchar_max("referral_payouts.referrer", referrer, 66)?;
+        let result = sqlx::query(
+            "INSERT INTO referral_payouts (tx_hash, referrer, epoch, amount_points, block_number)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (tx_hash, referrer, epoch) DO NOTHING",
+        )
+        .bind(tx_hash)
+        .bind(referrer)
+        .bind(epoch)
+        .bind(amount_points)
+        .bind(block_number)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Lists settled referral payouts for any of `scope_addresses`, newest epoch first.
+    pub async fn list_referral_payouts(
+        &self,
+        scope_addresses: &[String],
+    ) -> Result<Vec<ReferralPayout>> {
+        let payouts = sqlx::query_as::<_, ReferralPayout>(
+            "SELECT
+                 rp.tx_hash,
+                 rp.epoch,
+                 CAST(rp.amount_points AS FLOAT) as amount_points,
+                 COALESCE((
+                     SELECT CAST(SUM(p.referral_points) AS FLOAT)
+                     FROM points p
+                     WHERE LOWER(p.user_address) = ANY($1) AND p.epoch = rp.epoch
+                 ), 0) as offchain_referral_points,
+                 rp.block_number,
+                 rp.settled_at
+             FROM referral_payouts rp
+             WHERE LOWER(rp.referrer) = ANY($1)
+             ORDER BY rp.epoch DESC, rp.block_number DESC",
+        )
+        .bind(scope_addresses)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(payouts)
+    }
 }
 
 // ==================== TRANSACTION QUERIES ====================
@@ -1036,9 +1615,22 @@ impl Database {
     /// # Notes
     /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
     pub async fn save_transaction(&self, tx: &Transaction) -> Result<()> {
+        self.save_transaction_with_fee(tx, None).await
+    }
+
+    /// Saves `tx` and, when given, its `fee_ledger` row in one database transaction.
+    ///
+    /// # Notes
+    /// * The ledger row is keyed by `tx_hash`; re-saving the same transaction keeps the first row.
+    pub async fn save_transaction_with_fee(
+        &self,
+        tx: &Transaction,
+        fee: Option<FeeLedgerEntry<'_>>,
+    ) -> Result<()> {
         ensure_varchar_max("transactions.tx_hash", &tx.tx_hash, 66)?;
         ensure_varchar_max("transactions.user_address", &tx.user_address, 66)?;
         ensure_varchar_max("transactions.tx_type", &tx.tx_type, 20)?;
+        ensure_known_tx_type(&tx.tx_type)?;
         if tx.user_address.trim().is_empty() {
             return Err(AppError::BadRequest(
                 "transactions.user_address cannot be empty".to_string(),
@@ -1099,10 +1691,137 @@ impl Database {
         .execute(&mut *db_tx)
         .await?;
 
+        if let Some(fee) = fee {
+            sqlx::query(
+                r#"
+                INSERT INTO fee_ledger
+                    (tx_hash, user_address, tx_type, token, fee_amount, fee_usd, fee_recipient)
+                VALUES ($1,$2,$3,$4,$5,$6,$7)
+                ON CONFLICT (tx_hash) DO NOTHING
+                "#,
+            )
+            .bind(&tx.tx_hash)
+            .bind(&tx.user_address)
+            .bind(&tx.tx_type)
+            .bind(fee.token)
+            .bind(fee.fee_amount)
+            .bind(fee.fee_usd)
+            .bind(fee.fee_recipient)
+            .execute(&mut *db_tx)
+            .await?;
+        }
+
         db_tx.commit().await?;
         Ok(())
     }
 
+    /// Marks a transaction as processed once its points are awarded (or it was skipped).
+    pub async fn mark_transaction_processed(
+        &self,
+        tx_hash: &str,
+        points_earned: Option<rust_decimal::Decimal>,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE transactions
+             SET processed = true,
+                 processed_at = NOW(),
+                 points_earned = COALESCE($2, points_earned),
+                 last_processing_error = NULL
+             WHERE tx_hash = $1",
+        )
+        .bind(tx_hash)
+        .bind(points_earned)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Records a failed processing attempt so the calculator can back off and alert.
+    pub async fn record_transaction_processing_failure(
+        &self,
+        tx_hash: &str,
+        error: &str,
+    ) -> Result<()> {
+        let error: String = error.chars().take(500).collect();
+        sqlx::query(
+            "UPDATE transactions
+             SET processing_attempts = processing_attempts + 1,
+                 last_processing_error = $2,
+                 last_processing_attempt_at = NOW()
+             WHERE tx_hash = $1 AND processed = false",
+        )
+        .bind(tx_hash)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Counts unprocessed transactions the point calculator can still pick up (processing lag).
+    pub async fn count_pending_transactions(&self, max_attempts: i32) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM transactions
+             WHERE processed = false AND processing_attempts < $1",
+        )
+        .bind(max_attempts)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    /// Lists unprocessed transactions older than `older_than_secs`, oldest first.
+    pub async fn list_stuck_transactions(
+        &self,
+        older_than_secs: i64,
+        limit: i64,
+    ) -> Result<Vec<StuckTransaction>> {
+        let rows = sqlx::query_as::<_, StuckTransaction>(
+            "SELECT tx_hash, user_address, tx_type, timestamp, processing_attempts,
+                    last_processing_error, last_processing_attempt_at
+             FROM transactions
+             WHERE processed = false
+               AND timestamp < NOW() - make_interval(secs => $1)
+             ORDER BY timestamp ASC
+             LIMIT $2",
+        )
+        .bind(older_than_secs as f64)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Counts unprocessed transactions older than `older_than_secs`.
+    pub async fn count_stuck_transactions(&self, older_than_secs: i64) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM transactions
+             WHERE processed = false
+               AND timestamp < NOW() - make_interval(secs => $1)",
+        )
+        .bind(older_than_secs as f64)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    /// Re-queues unprocessed transactions by clearing their attempt counter and backoff.
+    ///
+    /// # Notes
+    /// * `tx_hashes = None` re-queues every unprocessed transaction that has failed at least once.
+    pub async fn reset_transaction_processing(&self, tx_hashes: Option<&[String]>) -> Result<u64> {
+        let result = sqlx::query(
+            "UPDATE transactions
+             SET processing_attempts = 0, last_processing_attempt_at = NULL
+             WHERE processed = false
+               AND processing_attempts > 0
+               AND ($1::text[] IS NULL OR tx_hash = ANY($1))",
+        )
+        .bind(tx_hashes)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     /// Fetches data for `get_transaction`.
     ///
     /// # Arguments
@@ -1146,6 +1865,62 @@ impl Database {
         Ok(())
     }
 
+    /// Returns whether the transaction `tx_hash` was marked private (hide-balance).
+    pub async fn is_transaction_private(&self, tx_hash: &str) -> Result<bool> {
+        let private: Option<bool> = sqlx::query_scalar(
+            "SELECT COALESCE(is_private, false) FROM transactions WHERE tx_hash = $1",
+        )
+        .bind(tx_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(private.unwrap_or(false))
+    }
+
+    /// Stores the fee breakdown of an executed swap.
+    ///
+    /// # Notes
+    /// * Keyed by `tx_hash`; re-saving the same swap keeps the first row.
+    pub async fn save_swap_fee_breakdown(&self, breakdown: &SwapFeeBreakdown) -> Result<()> {
+        ensure_varchar_max("swap_fee_breakdowns.tx_hash", &breakdown.tx_hash, 66)?;
+        sqlx::query(
+            "INSERT INTO swap_fee_breakdowns
+                (tx_hash, mode, base_fee, mev_fee, fee_before_discount,
+                 nft_discount_percent, stake_discount_percent, fee_discount_percent,
+                 nft_discount_saved, stake_discount_saved, net_fee)
+             VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
+             ON CONFLICT (tx_hash) DO NOTHING",
+        )
+        .bind(&breakdown.tx_hash)
+        .bind(&breakdown.mode)
+        .bind(breakdown.base_fee)
+        .bind(breakdown.mev_fee)
+        .bind(breakdown.fee_before_discount)
+        .bind(breakdown.nft_discount_percent)
+        .bind(breakdown.stake_discount_percent)
+        .bind(breakdown.fee_discount_percent)
+        .bind(breakdown.nft_discount_saved)
+        .bind(breakdown.stake_discount_saved)
+        .bind(breakdown.net_fee)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Fetches the stored fee breakdown for the swap `tx_hash`, if one was recorded.
+    pub async fn get_swap_fee_breakdown(&self, tx_hash: &str) -> Result<Option<SwapFeeBreakdown>> {
+        let breakdown = sqlx::query_as::<_, SwapFeeBreakdown>(
+            "SELECT tx_hash, mode, base_fee, mev_fee, fee_before_discount,
+                    nft_discount_percent, stake_discount_percent, fee_discount_percent,
+                    nft_discount_saved, stake_discount_saved, net_fee
+             FROM swap_fee_breakdowns
+             WHERE tx_hash = $1",
+        )
+        .bind(tx_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(breakdown)
+    }
+
     /// Fetches data for `count_private_swaps_today`.
     ///
     /// # Arguments
@@ -1195,6 +1970,18 @@ fn ensure_varchar_max(field: &str, value: &str, max_len: usize) -> Result<()> {
     Ok(())
 }
 
+// Internal helper that rejects transaction types outside `TRANSACTION_TYPES`.
+fn ensure_known_tx_type(tx_type: &str) -> Result<()> {
+    if TRANSACTION_TYPES.contains(&tx_type) {
+        return Ok(());
+    }
+    Err(AppError::BadRequest(format!(
+        "Unknown transactions.tx_type '{}' (expected one of: {})",
+        tx_type,
+        TRANSACTION_TYPES.join(", ")
+    )))
+}
+
 // Internal helper that parses or transforms values for `normalize_wallet_chain_value`.
 fn normalize_wallet_chain_value(chain: &str) -> String {
     chain.trim().to_ascii_lowercase()
@@ -1278,6 +2065,46 @@ impl Database {
         Ok(!recent_claim)
     }
 
+    /// Latest in-cooldown claim of `token` by another member of the requester's cluster.
+    ///
+    /// # Notes
+    /// * The cluster is `cluster_addresses` (lowercased linked wallets) plus any claim
+    ///   carrying `fingerprint`; the requester's own claims are excluded because the
+    ///   per-address cooldown already covers them.
+    /// * Claims before `not_before` (faucet policy reset) are ignored.
+    pub async fn latest_faucet_cluster_claim(
+        &self,
+        requester: &str,
+        cluster_addresses: &[String],
+        fingerprint: Option<&str>,
+        token: &str,
+        cooldown_hours: i64,
+        not_before: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+        let latest: Option<chrono::DateTime<chrono::Utc>> = sqlx::query_scalar(
+            r#"
+            SELECT MAX(claimed_at) FROM faucet_claims
+            WHERE token = $1
+              AND claimed_at >= NOW() - make_interval(hours => $2)
+              AND ($3::timestamptz IS NULL OR claimed_at >= $3)
+              AND LOWER(user_address) <> LOWER($4)
+              AND (
+                LOWER(user_address) = ANY($5)
+                OR ($6::text IS NOT NULL AND client_fingerprint = $6)
+              )
+            "#,
+        )
+        .bind(token)
+        .bind(cooldown_hours)
+        .bind(not_before)
+        .bind(requester)
+        .bind(cluster_addresses)
+        .bind(fingerprint)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(latest)
+    }
+
     /// Handles `record_faucet_claim` logic.
     ///
     /// # Arguments
@@ -1295,18 +2122,20 @@ impl Database {
         token: &str,
         amount: f64,
         tx_hash: &str,
+        fingerprint: Option<&str>,
     ) -> Result<()> {
         // lebih aman: gunakan from_f64 dan handle Option di caller jika perlu
         let amount_dec = rust_decimal::Decimal::from_f64_retain(amount);
 
         sqlx::query(
-            "INSERT INTO faucet_claims (user_address, token, amount, tx_hash)
-             VALUES ($1, $2, $3, $4)",
+            "INSERT INTO faucet_claims (user_address, token, amount, tx_hash, client_fingerprint)
+             VALUES ($1, $2, $3, $4, $5)",
         )
         .bind(address)
         .bind(token)
         .bind(amount_dec)
         .bind(tx_hash)
+        .bind(fingerprint)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -1489,6 +2318,55 @@ impl Database {
 
         Ok(rows)
     }
+
+    /// Price candles in `[from, to]` aggregated into `bucket_secs` buckets counted from `from`.
+    ///
+    /// # Notes
+    /// * Each row is stamped with its bucket start; open/close come from the first/last candle
+    ///   in the bucket, so at most one row per bucket is read back.
+    pub async fn get_price_history_bucketed(
+        &self,
+        token: &str,
+        interval: &str,
+        from: chrono::DateTime<chrono::Utc>,
+        to: chrono::DateTime<chrono::Utc>,
+        bucket_secs: i64,
+    ) -> Result<Vec<PriceTick>> {
+        let rows = sqlx::query_as::<_, PriceTick>(
+            r#"
+            SELECT
+                token,
+                TO_TIMESTAMP(
+                    EXTRACT(EPOCH FROM $3::TIMESTAMPTZ)::DOUBLE PRECISION + bucket * $5::DOUBLE PRECISION
+                ) AS "timestamp",
+                (ARRAY_AGG(open ORDER BY timestamp ASC))[1] AS "open",
+                MAX(high) AS "high",
+                MIN(low) AS "low",
+                (ARRAY_AGG(close ORDER BY timestamp DESC))[1] AS "close",
+                SUM(volume) AS "volume"
+            FROM (
+                SELECT
+                    token, timestamp, open, high, low, close, volume,
+                    FLOOR(EXTRACT(EPOCH FROM (timestamp - $3::TIMESTAMPTZ)) / $5)::BIGINT AS bucket
+                FROM price_history
+                WHERE token = $1
+                  AND interval = $2
+                  AND timestamp BETWEEN $3 AND $4
+            ) candles
+            GROUP BY token, bucket
+            ORDER BY bucket ASC
+            "#,
+        )
+        .bind(token)
+        .bind(interval)
+        .bind(from)
+        .bind(to)
+        .bind(bucket_secs.max(1))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
 }
 
 // ==================== LIMIT ORDER QUERIES ====================
@@ -1508,8 +2386,9 @@ impl Database {
         sqlx::query(
             r#"
             INSERT INTO limit_orders
-                (order_id, owner, from_token, to_token, amount, price, expiry, recipient, status)
-            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9)
+                (order_id, owner, from_token, to_token, amount, price, expiry, recipient, status,
+                 order_type, trigger_price)
+            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
             "#,
         )
         .bind(&order.order_id)
@@ -1520,7 +2399,9 @@ impl Database {
         .bind(order.price)
         .bind(order.expiry)
         .bind(&order.recipient)
-        .bind(order.status)
+        .bind(i16::from(order.status))
+        .bind(order.order_type.as_str())
+        .bind(order.trigger_price)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -1559,9 +2440,10 @@ impl Database {
     /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
     pub async fn get_active_orders_for_owner(&self, owner: &str) -> Result<Vec<LimitOrder>> {
         let orders = sqlx::query_as::<_, LimitOrder>(
-            "SELECT * FROM limit_orders WHERE owner = $1 AND status = 0 AND expiry > NOW() ORDER BY created_at ASC",
+            "SELECT * FROM limit_orders WHERE owner = $1 AND status = $2 AND expiry > NOW() ORDER BY created_at ASC",
         )
         .bind(owner)
+        .bind(i16::from(OrderStatus::Active))
         .fetch_all(&self.pool)
         .await?;
         Ok(orders)
@@ -1570,22 +2452,27 @@ impl Database {
     /// Marks expired limit orders for a specific owner.
     ///
     /// Status transition:
-    /// - 0 (active) -> 4 (expired)
-    /// - 1 (partial) -> 4 (expired)
+    /// - `Active` -> `Expired`
+    /// - `Partial` -> `Expired`
     pub async fn expire_limit_orders_for_owner(&self, owner: &str) -> Result<u64> {
-        let result = sqlx::query(
+        let rows = sqlx::query(
             r#"
             UPDATE limit_orders
-            SET status = 4
+            SET status = $2
             WHERE owner = $1
-              AND status IN (0, 1)
+              AND status IN ($3, $4)
               AND expiry <= NOW()
+            RETURNING order_id, owner, filled, status
             "#,
         )
         .bind(owner)
-        .execute(&self.pool)
+        .bind(i16::from(OrderStatus::Expired))
+        .bind(i16::from(OrderStatus::Active))
+        .bind(i16::from(OrderStatus::Partial))
+        .fetch_all(&self.pool)
         .await?;
-        Ok(result.rows_affected())
+        publish_order_event_rows(&rows);
+        Ok(rows.len() as u64)
     }
 
     /// Updates state for `update_order_status`.
@@ -1599,12 +2486,15 @@ impl Database {
     ///
     /// # Notes
     /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
-    pub async fn update_order_status(&self, order_id: &str, status: i16) -> Result<()> {
-        sqlx::query("UPDATE limit_orders SET status = $1 WHERE order_id = $2")
-            .bind(status)
-            .bind(order_id)
-            .execute(&self.pool)
-            .await?;
+    pub async fn update_order_status(&self, order_id: &str, status: OrderStatus) -> Result<()> {
+        let rows = sqlx::query(
+            "UPDATE limit_orders SET status = $1 WHERE order_id = $2 RETURNING order_id, owner, filled, status",
+        )
+        .bind(i16::from(status))
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await?;
+        publish_order_event_rows(&rows);
         Ok(())
     }
 
@@ -1620,22 +2510,51 @@ impl Database {
     /// # Notes
     /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
     pub async fn fill_order(&self, order_id: &str, amount: rust_decimal::Decimal) -> Result<()> {
-        sqlx::query(
+        let rows = sqlx::query(
             r#"
             UPDATE limit_orders
             SET filled = filled + $1,
-                status = CASE WHEN filled + $1 >= amount THEN 2 ELSE 1 END
+                status = CASE WHEN filled + $1 >= amount THEN $3 ELSE $4 END
             WHERE order_id = $2
+            RETURNING order_id, owner, filled, status
             "#,
         )
         .bind(amount)
         .bind(order_id)
-        .execute(&self.pool)
+        .bind(i16::from(OrderStatus::Filled))
+        .bind(i16::from(OrderStatus::Partial))
+        .fetch_all(&self.pool)
         .await?;
+        publish_order_event_rows(&rows);
         Ok(())
     }
 }
 
+// Internal helper that publishes `RETURNING order_id, owner, filled, status` rows to the order event bus.
+fn publish_order_event_rows(rows: &[sqlx::postgres::PgRow]) {
+    let timestamp = chrono::Utc::now().timestamp();
+    for row in rows {
+        let (Ok(order_id), Ok(owner), Ok(status)) = (
+            row.try_get::<String, _>("order_id"),
+            row.try_get::<String, _>("owner"),
+            row.try_get::<i16, _>("status").map(OrderStatus::from),
+        ) else {
+            continue;
+        };
+        let filled = row
+            .try_get::<rust_decimal::Decimal, _>("filled")
+            .map(|value| value.to_string())
+            .unwrap_or_else(|_| "0".to_string());
+        crate::services::order_events::publish(crate::services::order_events::OrderEvent {
+            order_id,
+            owner,
+            status,
+            filled,
+            timestamp,
+        });
+    }
+}
+
 // ==================== NFT DISCOUNT STATE ====================
 impl Database {
     /// Fetches data for `get_nft_discount_state`.
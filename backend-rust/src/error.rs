@@ -6,6 +6,85 @@ use axum::{
 use serde::Serialize;
 use thiserror::Error;
 
+/// Stable machine-readable codes for client-facing conditions that clients handle specifically.
+///
+/// # Notes
+/// * `message` is the English default; localized messages can be keyed on the code.
+/// * `status` is 400 unless the condition is a server-side state, e.g. an event-only swap contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    ShieldedRootUninitialized,
+    HideNoteNotRegistered,
+    HideV2RedeemOnly,
+    HideDenominationNotAllowed,
+    HideSwapPairNotAllowed,
+    HideNoteMixingWindow,
+    OracleRouteLiquidityLow,
+    BridgeAmountBelowMinimum,
+    InsufficientPoints,
+    SwapContractEventOnly,
+}
+
+impl ErrorCode {
+    /// Code returned in `error.code`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::ShieldedRootUninitialized => "SHIELDED_ROOT_UNINITIALIZED",
+            ErrorCode::HideNoteNotRegistered => "HIDE_NOTE_NOT_REGISTERED",
+            ErrorCode::HideV2RedeemOnly => "HIDE_V2_REDEEM_ONLY",
+            ErrorCode::HideDenominationNotAllowed => "HIDE_DENOMINATION_NOT_ALLOWED",
+            ErrorCode::HideSwapPairNotAllowed => "HIDE_SWAP_PAIR_NOT_ALLOWED",
+            ErrorCode::HideNoteMixingWindow => "HIDE_NOTE_MIXING_WINDOW",
+            ErrorCode::OracleRouteLiquidityLow => "ORACLE_ROUTE_LIQUIDITY_LOW",
+            ErrorCode::BridgeAmountBelowMinimum => "BRIDGE_AMOUNT_BELOW_MINIMUM",
+            ErrorCode::InsufficientPoints => "INSUFFICIENT_POINTS",
+            ErrorCode::SwapContractEventOnly => "SWAP_CONTRACT_EVENT_ONLY",
+        }
+    }
+
+    /// HTTP status returned with the code.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ErrorCode::SwapContractEventOnly => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    /// Default English message for the code.
+    pub fn message(&self) -> &'static str {
+        match self {
+            ErrorCode::ShieldedRootUninitialized => {
+                "ShieldedPoolV3 root is not initialized yet (get_root=0)."
+            }
+            ErrorCode::HideNoteNotRegistered => {
+                "Hide Balance V3 note is not registered yet. Deposit the note first."
+            }
+            ErrorCode::HideV2RedeemOnly => {
+                "Hide Balance V2 is redeem-only. New note deposits to V2 are blocked; use V3 for new notes."
+            }
+            ErrorCode::HideDenominationNotAllowed => {
+                "Hide Balance deposits must use one of the allowed fixed denominations."
+            }
+            ErrorCode::HideSwapPairNotAllowed => {
+                "This token pair is not available for Hide Balance yet. Use transparent mode."
+            }
+            ErrorCode::HideNoteMixingWindow => {
+                "Hide Balance note is still in its mixing window. Retry once it is spendable."
+            }
+            ErrorCode::OracleRouteLiquidityLow => {
+                "On-chain liquidity is too low for this swap route."
+            }
+            ErrorCode::BridgeAmountBelowMinimum => {
+                "The amount is below the bridge provider's minimum for this route."
+            }
+            ErrorCode::InsufficientPoints => "Not enough points for this operation.",
+            ErrorCode::SwapContractEventOnly => {
+                "Real-token swaps are not active yet: the configured swap contract only emits events and does not move tokens."
+            }
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Database error: {0}")]
@@ -38,6 +117,9 @@ pub enum AppError {
     #[error("Faucet cooldown active")]
     FaucetCooldown,
 
+    #[error("Faucet cooldown active for linked wallets until {0}")]
+    FaucetClusterCooldown(chrono::DateTime<chrono::Utc>),
+
     #[error("Invalid token")]
     InvalidToken,
 
@@ -47,6 +129,24 @@ pub enum AppError {
     #[error("Insufficient liquidity")]
     InsufficientLiquidity,
 
+    #[error("Transaction pending: {0}")]
+    TransactionPending(String),
+
+    #[error("Privacy root advanced: {0}")]
+    PrivacyRootAdvanced(String),
+
+    #[error("Maintenance mode: {0}")]
+    MaintenanceMode(String),
+
+    #[error("Prover busy")]
+    ProverBusy,
+
+    #[error("Relayer access not permitted: {0}")]
+    RelayerAccessDenied(String),
+
+    #[error("{1}")]
+    Coded(ErrorCode, String),
+
     #[error("External API error: {0}")]
     ExternalAPI(String),
 
@@ -71,17 +171,26 @@ pub struct ErrorDetail {
 impl IntoResponse for AppError {
     // Internal helper that supports `into_response` operations.
     fn into_response(self) -> Response {
+        let details = match &self {
+            AppError::FaucetClusterCooldown(next_eligible_at) => Some(serde_json::json!({
+                "next_eligible_at": next_eligible_at,
+            })),
+            _ => None,
+        };
         let (status, code, message) = match self {
             AppError::Database(ref e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "DATABASE_ERROR",
                 e.to_string(),
             ),
-            AppError::Redis(ref e) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "CACHE_ERROR",
-                e.to_string(),
-            ),
+            AppError::Redis(ref e) => {
+                tracing::warn!("Redis unavailable: {}", e);
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "CACHE_UNAVAILABLE",
+                    "Cache is temporarily unavailable. Please retry shortly.".to_string(),
+                )
+            }
             AppError::AuthError(ref msg) => (StatusCode::UNAUTHORIZED, "AUTH_ERROR", msg.clone()),
             AppError::InvalidSignature => (
                 StatusCode::UNAUTHORIZED,
@@ -110,6 +219,14 @@ impl IntoResponse for AppError {
                 "FAUCET_COOLDOWN",
                 "Please wait before claiming again".to_string(),
             ),
+            AppError::FaucetClusterCooldown(next_eligible_at) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "FAUCET_COOLDOWN",
+                format!(
+                    "A linked wallet or this device already claimed this token recently. Next eligible at {}.",
+                    next_eligible_at.to_rfc3339()
+                ),
+            ),
             AppError::InsufficientBalance => (
                 StatusCode::BAD_REQUEST,
                 "INSUFFICIENT_BALANCE",
@@ -120,6 +237,28 @@ impl IntoResponse for AppError {
                 "INSUFFICIENT_LIQUIDITY",
                 "Not enough liquidity available".to_string(),
             ),
+            AppError::TransactionPending(ref msg) => {
+                (StatusCode::CONFLICT, "TX_PENDING_FINALITY", msg.clone())
+            }
+            AppError::PrivacyRootAdvanced(ref msg) => {
+                (StatusCode::CONFLICT, "PRIVACY_ROOT_ADVANCED", msg.clone())
+            }
+            AppError::MaintenanceMode(ref msg) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "MAINTENANCE_MODE",
+                msg.clone(),
+            ),
+            AppError::ProverBusy => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "PROVER_BUSY",
+                "Prover is busy with other requests. Please retry shortly.".to_string(),
+            ),
+            AppError::RelayerAccessDenied(ref msg) => (
+                StatusCode::FORBIDDEN,
+                "RELAYER_ACCESS_DENIED",
+                format!("Relayer access not permitted: {}", msg),
+            ),
+            AppError::Coded(code, ref msg) => (code.status(), code.as_str(), msg.clone()),
             AppError::ExternalAPI(ref msg) => {
                 (StatusCode::BAD_GATEWAY, "EXTERNAL_API_ERROR", msg.clone())
             }
@@ -135,7 +274,7 @@ impl IntoResponse for AppError {
             error: ErrorDetail {
                 code: code.to_string(),
                 message,
-                details: None,
+                details,
             },
         });
 
@@ -143,4 +282,11 @@ impl IntoResponse for AppError {
     }
 }
 
+impl From<ErrorCode> for AppError {
+    // Internal helper that supports `from` operations.
+    fn from(code: ErrorCode) -> Self {
+        AppError::Coded(code, code.message().to_string())
+    }
+}
+
 pub type Result<T> = std::result::Result<T, AppError>;
@@ -1,5 +1,5 @@
 use super::{event_parser::EventParser, starknet_client::StarknetClient};
-use crate::{db::Database, error::Result};
+use crate::{db::Database, error::Result, models::OrderStatus};
 
 /// Block Processor - Processes blocks and extracts events
 pub struct BlockProcessor {
@@ -149,7 +149,9 @@ impl BlockProcessor {
     async fn handle_order_filled(&self, _tx_hash: &str, data: serde_json::Value) -> Result<()> {
         let order_id = data.get("order_id").and_then(|v| v.as_str()).unwrap_or("");
 
-        self.db.update_order_status(order_id, 2).await?;
+        self.db
+            .update_order_status(order_id, OrderStatus::Filled)
+            .await?;
         Ok(())
     }
 }
@@ -46,6 +46,9 @@ impl EventParser {
         if key_is(event_key, "LimitOrderFilled") {
             return self.parse_order_filled_event(event);
         }
+        if key_is(event_key, "BonusClaimed") {
+            return self.parse_referral_payout_event(event);
+        }
 
         None
     }
@@ -159,6 +162,22 @@ impl EventParser {
         })
     }
 
+    // Internal helper that parses `ReferralSystem::BonusClaimed { referrer, amount: u256, epoch }`.
+    fn parse_referral_payout_event(&self, event: &Event) -> Option<ParsedEvent> {
+        let referrer = user_from_keys_or_data(event, 0)?;
+        let offset = if event.keys.len() > 1 { 0 } else { 1 };
+
+        Some(ParsedEvent {
+            event_type: "ReferralPayout".to_string(),
+            data: serde_json::json!({
+                "user": referrer,
+                "amount_low": event.data.get(offset)?.clone(),
+                "amount_high": event.data.get(offset + 1)?.clone(),
+                "epoch": event.data.get(offset + 2)?.clone(),
+            }),
+        })
+    }
+
     /// Convert hex string to decimal
     pub fn hex_to_decimal(&self, hex: &str) -> Option<u64> {
         u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok()
@@ -243,6 +262,30 @@ mod tests {
         assert_eq!(parsed.event_type, "Swap");
     }
 
+    #[test]
+    // Internal helper that parses or transforms values for `parse_referral_payout_event_reads_u256`.
+    fn parse_referral_payout_event_reads_u256() {
+        let parser = EventParser::new();
+        let event = Event {
+            from_address: "0x123".to_string(),
+            keys: vec![selector_hex("BonusClaimed").unwrap()],
+            data: vec![
+                "0xabc".to_string(),
+                "0x14".to_string(),
+                "0x0".to_string(),
+                "0x7".to_string(),
+            ],
+            transaction_hash: None,
+            block_number: None,
+        };
+
+        let parsed = parser.parse_event(&event).expect("payout event");
+        assert_eq!(parsed.event_type, "ReferralPayout");
+        assert_eq!(parsed.data["user"], "0xabc");
+        assert_eq!(parsed.data["amount_low"], "0x14");
+        assert_eq!(parsed.data["epoch"], "0x7");
+    }
+
     #[test]
     // Internal helper that parses or transforms values for `parse_event_returns_none_for_empty_keys`.
     fn parse_event_returns_none_for_empty_keys() {
@@ -450,6 +450,19 @@ impl StarknetClient {
         from_block: u64,
         to_block: u64,
     ) -> Result<Vec<Event>> {
+        let (events, _truncated) = self
+            .get_events_bounded(contract_address, from_block, to_block)
+            .await?;
+        Ok(events)
+    }
+
+    /// Fetches events like `get_events`, also reporting whether the page limit cut the range short.
+    pub async fn get_events_bounded(
+        &self,
+        contract_address: Option<&str>,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<(Vec<Event>, bool)> {
         let mut all_events = Vec::new();
         let mut continuation_token: Option<String> = None;
 
@@ -490,7 +503,8 @@ impl StarknetClient {
             }
         }
 
-        if continuation_token.is_some() {
+        let truncated = continuation_token.is_some();
+        if truncated {
             tracing::warn!(
                 "starknet_getEvents hit page limit ({} pages) for range {}..{}",
                 EVENTS_MAX_PAGES,
@@ -499,7 +513,7 @@ impl StarknetClient {
             );
         }
 
-        Ok(all_events)
+        Ok((all_events, truncated))
     }
 
     /// Call contract view function
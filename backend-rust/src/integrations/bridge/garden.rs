@@ -1,6 +1,7 @@
 use crate::error::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 use url::Url;
 
@@ -508,6 +509,19 @@ impl GardenClient {
         self.get_json("/v2/assets", &params).await
     }
 
+    /// Lists the assets Garden currently offers with their minimum order amounts.
+    pub async fn get_asset_catalog(&self) -> Result<GardenAssetCatalog> {
+        let payload = self.get_supported_assets(None).await?;
+        let mut catalog = GardenAssetCatalog::default();
+        collect_garden_assets(&payload, &mut catalog);
+        Ok(catalog)
+    }
+
+    /// Returns the Garden asset id used for `token` on `chain`.
+    pub fn asset_id(chain: &str, token: &str) -> String {
+        map_garden_asset(chain, token)
+    }
+
     /// Fetches data for `get_available_liquidity`.
     ///
     /// # Arguments
@@ -705,6 +719,14 @@ impl GardenClient {
     }
 }
 
+/// Asset ids (`chain:token`, lowercased) listed by Garden and, where given, their minimum
+/// order amount in base units.
+#[derive(Debug, Clone, Default)]
+pub struct GardenAssetCatalog {
+    pub ids: HashSet<String>,
+    pub min_amounts: HashMap<String, u128>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GardenQuote {
     pub from_chain: String,
@@ -728,6 +750,61 @@ pub struct GardenOrderSubmission {
     pub starknet_initiate_transaction: Option<GardenStarknetTransaction>,
 }
 
+/// Refund-relevant state of a Garden order's source HTLC.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GardenSourceSwap {
+    pub chain: Option<String>,
+    /// Relative timelock in source-chain blocks, counted from `initiate_block_number`.
+    pub timelock_blocks: Option<u64>,
+    pub initiate_block_number: Option<u64>,
+    pub initiated: bool,
+    pub completed: bool,
+    pub refunded: bool,
+}
+
+impl GardenSourceSwap {
+    /// Reads the source swap of a `GET /v2/orders/{id}` payload.
+    pub fn from_order(order: &Value) -> Self {
+        let result = pick_value_by_path(order, &["result"]).unwrap_or(order);
+        let block = |path: &[&str]| {
+            pick_u128(result, &[path])
+                .and_then(|value| u64::try_from(value).ok())
+                .filter(|value| *value > 0)
+        };
+        Self {
+            chain: pick_string_non_empty(result, &[&["source_swap", "chain"]]),
+            timelock_blocks: block(&["source_swap", "timelock"]),
+            initiate_block_number: block(&["source_swap", "initiate_block_number"]),
+            initiated: pick_string_non_empty(
+                result,
+                &[
+                    &["source_swap", "initiate_tx_hash"],
+                    &["source_swap", "initiateTxHash"],
+                ],
+            )
+            .is_some(),
+            completed: pick_string_non_empty(
+                result,
+                &[
+                    &["destination_swap", "redeem_tx_hash"],
+                    &["destination_swap", "redeemTxHash"],
+                    &["source_swap", "redeem_tx_hash"],
+                    &["source_swap", "redeemTxHash"],
+                ],
+            )
+            .is_some(),
+            refunded: pick_string_non_empty(
+                result,
+                &[
+                    &["source_swap", "refund_tx_hash"],
+                    &["source_swap", "refundTxHash"],
+                ],
+            )
+            .is_some(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GardenOrderStatus {
     pub order_id: String,
@@ -860,6 +937,40 @@ fn map_garden_asset(chain: &str, token: &str) -> String {
     )
 }
 
+// Internal helper that collects `chain:token` ids (as keys or string values) and `min_amount`
+// fields (beside an `id` or under an id key) from an assets payload.
+fn collect_garden_assets(value: &Value, catalog: &mut GardenAssetCatalog) {
+    fn asset_id(raw: &str) -> Option<String> {
+        (raw.contains(':') && !raw.contains(char::is_whitespace)).then(|| raw.to_ascii_lowercase())
+    }
+    match value {
+        Value::String(raw) => {
+            if let Some(id) = asset_id(raw) {
+                catalog.ids.insert(id);
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_garden_assets(item, catalog)),
+        Value::Object(map) => {
+            let id = map.get("id").and_then(Value::as_str).and_then(asset_id);
+            if let (Some(id), Some(min)) = (id, map.get("min_amount").and_then(value_to_u128)) {
+                catalog.min_amounts.insert(id, min);
+            }
+            for (key, item) in map {
+                if let Some(id) = asset_id(key) {
+                    if let Some(min) = item.get("min_amount").and_then(value_to_u128) {
+                        catalog.min_amounts.insert(id.clone(), min);
+                    }
+                    catalog.ids.insert(id);
+                }
+                collect_garden_assets(item, catalog);
+            }
+        }
+        _ => {}
+    }
+}
+
 // Internal helper that supports `garden_decimals` operations.
 fn garden_decimals(token: &str) -> u32 {
     match token.trim().to_ascii_uppercase().as_str() {
@@ -1173,6 +1284,61 @@ mod tests {
         );
     }
 
+    #[test]
+    // Internal helper that supports `collect_garden_assets_reads_ids_and_minimums` operations.
+    fn collect_garden_assets_reads_ids_and_minimums() {
+        let body = json!({
+            "status": "Ok",
+            "result": {
+                "starknet_sepolia:WBTC": { "decimals": 8, "min_amount": "50000" },
+                "bitcoin_testnet": {
+                    "assets": [{ "id": "bitcoin_testnet:btc", "min_amount": 60000 }]
+                },
+                "ethereum_sepolia": { "assets": [{ "id": "ethereum_sepolia:eth" }] },
+                "message": "see https: docs"
+            }
+        });
+        let mut catalog = GardenAssetCatalog::default();
+        collect_garden_assets(&body, &mut catalog);
+        assert!(catalog.ids.contains("starknet_sepolia:wbtc"));
+        assert!(catalog.ids.contains("bitcoin_testnet:btc"));
+        assert_eq!(catalog.ids.len(), 3);
+        assert_eq!(
+            catalog.min_amounts.get("starknet_sepolia:wbtc"),
+            Some(&50_000)
+        );
+        assert_eq!(
+            catalog.min_amounts.get("bitcoin_testnet:btc"),
+            Some(&60_000)
+        );
+        assert!(!catalog.min_amounts.contains_key("ethereum_sepolia:eth"));
+    }
+
+    #[test]
+    // Internal helper that supports `source_swap_reads_timelock_and_lifecycle` operations.
+    fn source_swap_reads_timelock_and_lifecycle() {
+        let order = json!({
+            "result": {
+                "source_swap": {
+                    "chain": "ethereum_sepolia",
+                    "timelock": 7200,
+                    "initiate_block_number": "5000000",
+                    "initiate_tx_hash": "0xabc",
+                    "redeem_tx_hash": "",
+                    "refund_tx_hash": ""
+                },
+                "destination_swap": { "redeem_tx_hash": "" }
+            }
+        });
+        let source = GardenSourceSwap::from_order(&order);
+        assert_eq!(source.chain.as_deref(), Some("ethereum_sepolia"));
+        assert_eq!(source.timelock_blocks, Some(7200));
+        assert_eq!(source.initiate_block_number, Some(5_000_000));
+        assert!(source.initiated);
+        assert!(!source.completed);
+        assert!(!source.refunded);
+    }
+
     #[test]
     // Internal helper that supports `pick_string_reads_order_id_from_result` operations.
     fn pick_string_reads_order_id_from_result() {
@@ -3,5 +3,7 @@ pub mod garden;
 pub mod layerswap;
 
 pub use atomiq::{AtomiqClient, AtomiqQuote};
-pub use garden::{GardenClient, GardenEvmTransaction, GardenQuote, GardenStarknetTransaction};
+pub use garden::{
+    GardenClient, GardenEvmTransaction, GardenQuote, GardenSourceSwap, GardenStarknetTransaction,
+};
 pub use layerswap::{LayerSwapClient, LayerSwapQuote};
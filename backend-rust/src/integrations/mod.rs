@@ -1,3 +1,4 @@
 pub mod bridge;
+pub mod object_storage;
 pub mod sumo_login;
 pub mod xverse;
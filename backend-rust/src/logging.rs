@@ -0,0 +1,108 @@
+use crate::error::{AppError, Result};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    OnceLock,
+};
+use tracing_subscriber::{
+    layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry,
+};
+
+const DEFAULT_LOG_FILTER: &str = "carel_backend=info,tower_http=warn,sqlx::query=error,sqlx::pool::acquire=error,rustls_platform_verifier=error";
+
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+static STARTUP_FILTER: OnceLock<String> = OnceLock::new();
+// Bumped on every change so a scheduled revert only fires if nothing changed since.
+static FILTER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Installs the global tracing subscriber with a runtime-reloadable filter.
+///
+/// # Notes
+/// * `RUST_LOG` sets the startup filter; admins can override it via `set_filter`.
+pub fn init() {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+    let _ = STARTUP_FILTER.set(filter.to_string());
+    let (filter_layer, handle) = reload::Layer::new(filter);
+    let _ = FILTER_HANDLE.set(handle);
+
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+}
+
+/// Filter directives configured at startup.
+pub fn startup_filter() -> String {
+    STARTUP_FILTER
+        .get()
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_LOG_FILTER.to_string())
+}
+
+/// Filter directives currently in effect.
+pub fn current_filter() -> Result<String> {
+    filter_handle()?
+        .with_current(|filter| filter.to_string())
+        .map_err(|err| AppError::Internal(format!("Failed to read log filter: {}", err)))
+}
+
+/// Replaces the active filter and returns the previous directives.
+///
+/// # Notes
+/// * With `revert_after`, the startup filter is restored once it elapses unless the filter
+///   was changed again in the meantime.
+pub fn set_filter(directives: &str, revert_after: Option<std::time::Duration>) -> Result<String> {
+    let filter = parse_filter(directives)?;
+    let previous = current_filter()?;
+    filter_handle()?
+        .reload(filter)
+        .map_err(|err| AppError::Internal(format!("Failed to reload log filter: {}", err)))?;
+    let generation = FILTER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    if let Some(delay) = revert_after {
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if FILTER_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let startup = startup_filter();
+            match set_filter(&startup, None) {
+                Ok(_) => tracing::warn!("Log filter reverted to startup filter: {}", startup),
+                Err(err) => tracing::error!("Failed to revert log filter: {}", err),
+            }
+        });
+    }
+    Ok(previous)
+}
+
+// Internal helper that fetches data for `filter_handle`.
+fn filter_handle() -> Result<&'static reload::Handle<EnvFilter, Registry>> {
+    FILTER_HANDLE
+        .get()
+        .ok_or_else(|| AppError::Internal("Log filter is not reloadable".to_string()))
+}
+
+// Internal helper that parses or transforms values for `parse_filter`.
+fn parse_filter(directives: &str) -> Result<EnvFilter> {
+    let directives = directives.trim();
+    if directives.is_empty() {
+        return Err(AppError::BadRequest("filter must not be empty".to_string()));
+    }
+    EnvFilter::try_new(directives).map_err(|err| {
+        AppError::BadRequest(format!("Invalid log filter '{}': {}", directives, err))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that parses or transforms values for `parse_filter_validates_directives`.
+    fn parse_filter_validates_directives() {
+        assert!(parse_filter("carel_backend=debug,tower_http=warn").is_ok());
+        assert!(parse_filter(DEFAULT_LOG_FILTER).is_ok());
+        assert!(parse_filter("   ").is_err());
+        assert!(parse_filter("carel_backend=loud").is_err());
+    }
+}
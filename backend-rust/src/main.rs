@@ -1,12 +1,15 @@
-use axum::http::HeaderValue;
+use axum::http::{HeaderValue, StatusCode};
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{get, post},
     Router,
 };
 use std::net::SocketAddr;
+use std::sync::{atomic::AtomicBool, Arc};
 use std::time::Duration;
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
 use url::Url;
 
 mod api;
@@ -18,10 +21,10 @@ mod db;
 mod error;
 mod indexer;
 mod integrations;
+mod logging;
 mod models;
 mod services;
 mod tokenomics;
-mod utils;
 mod websocket;
 
 use anyhow::Context;
@@ -56,39 +59,60 @@ fn spawn_auto_garaga_warmup(config: &Config) {
     }
 
     let warmup_cmd = format!("{cmd} --warmup");
+    let interval_secs = config.garaga_warmup_interval_secs;
+    let idle_secs = config.garaga_warmup_idle_secs;
     tokio::spawn(async move {
-        tracing::info!("Starting Garaga calldata warmup...");
-        let child = match tokio::process::Command::new("sh")
-            .arg("-lc")
-            .arg(&warmup_cmd)
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
-            .spawn()
-        {
-            Ok(child) => child,
-            Err(err) => {
-                tracing::warn!("Garaga warmup spawn failed: {}", err);
-                return;
+        run_garaga_warmup(&warmup_cmd).await;
+        if interval_secs == 0 {
+            return;
+        }
+        let mut last_warmup_at = chrono::Utc::now().timestamp();
+        loop {
+            tokio::time::sleep(Duration::from_secs(interval_secs)).await;
+            let now = chrono::Utc::now().timestamp();
+            let last_proof_at = api::privacy::last_auto_garaga_proof_at();
+            if !api::privacy::garaga_warmup_due(now, last_proof_at, last_warmup_at, idle_secs) {
+                continue;
             }
-        };
+            run_garaga_warmup(&warmup_cmd).await;
+            last_warmup_at = chrono::Utc::now().timestamp();
+        }
+    });
+}
 
-        match tokio::time::timeout(Duration::from_secs(180), child.wait_with_output()).await {
-            Ok(Ok(output)) => {
-                if output.status.success() {
-                    tracing::info!("Garaga warmup completed");
-                } else {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    tracing::warn!("Garaga warmup failed: {}", stderr.trim());
-                }
-            }
-            Ok(Err(err)) => {
-                tracing::warn!("Garaga warmup process error: {}", err);
-            }
-            Err(_) => {
-                tracing::warn!("Garaga warmup timed out after 180s");
+// Internal helper that runs the bundled prover warmup command once.
+async fn run_garaga_warmup(warmup_cmd: &str) {
+    tracing::info!("Starting Garaga calldata warmup...");
+    let child = match tokio::process::Command::new("sh")
+        .arg("-lc")
+        .arg(warmup_cmd)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) => {
+            tracing::warn!("Garaga warmup spawn failed: {}", err);
+            return;
+        }
+    };
+
+    match tokio::time::timeout(Duration::from_secs(180), child.wait_with_output()).await {
+        Ok(Ok(output)) => {
+            if output.status.success() {
+                tracing::info!("Garaga warmup completed");
+            } else {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                tracing::warn!("Garaga warmup failed: {}", stderr.trim());
             }
         }
-    });
+        Ok(Err(err)) => {
+            tracing::warn!("Garaga warmup process error: {}", err);
+        }
+        Err(_) => {
+            tracing::warn!("Garaga warmup timed out after 180s");
+        }
+    }
 }
 
 fn normalize_redis_url(raw_url: &str) -> anyhow::Result<String> {
@@ -162,6 +186,7 @@ fn redis_url_candidates(raw_url: &str) -> anyhow::Result<Vec<String>> {
 
 async fn init_redis_connection_manager(
     raw_url: &str,
+    response_timeout: Duration,
 ) -> anyhow::Result<redis::aio::ConnectionManager> {
     let mut errors = Vec::new();
 
@@ -177,7 +202,7 @@ async fn init_redis_connection_manager(
 
         let redis_manager_config = redis::aio::ConnectionManagerConfig::new()
             .set_connection_timeout(Some(Duration::from_secs(10)))
-            .set_response_timeout(Some(Duration::from_secs(5)))
+            .set_response_timeout(Some(response_timeout))
             .set_number_of_retries(10)
             .set_min_delay(Duration::from_millis(200))
             .set_max_delay(Duration::from_secs(3));
@@ -200,17 +225,29 @@ async fn init_redis_connection_manager(
     );
 }
 
+// Internal helper that pings Redis periodically so idle connections are not dropped by proxies.
+async fn run_redis_keepalive(
+    mut manager: redis::aio::ConnectionManager,
+    interval_secs: u64,
+    healthy: Arc<AtomicBool>,
+) {
+    let mut ticker = tokio::time::interval(Duration::from_secs(interval_secs));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        ticker.tick().await;
+        match redis::cmd("PING").query_async::<String>(&mut manager).await {
+            Ok(_) => api::mark_redis_health(&healthy, true),
+            Err(err) => {
+                tracing::warn!("Redis keepalive PING failed: {}", err);
+                api::mark_redis_health(&healthy, false);
+            }
+        }
+    }
+}
+
 async fn run() -> anyhow::Result<()> {
-    // Initialize tracing
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| {
-                    "carel_backend=info,tower_http=warn,sqlx::query=error,sqlx::pool::acquire=error,rustls_platform_verifier=error".into()
-                }),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+    // Initialize tracing (filter is reloadable via the admin log-level endpoint)
+    logging::init();
 
     install_rustls_crypto_provider()?;
 
@@ -221,11 +258,12 @@ async fn run() -> anyhow::Result<()> {
     tracing::info!("Starting CAREL Backend Server");
     tracing::info!("Environment: {}", config.environment);
     tracing::info!("API Version: {}", API_VERSION);
-    let api_rpc = std::env::var("STARKNET_API_RPC_URL")
-        .ok()
-        .filter(|v| !v.trim().is_empty())
-        .unwrap_or_else(|| config.starknet_rpc_url.clone());
-    tracing::info!("Starknet API RPC URL: {}", api_rpc);
+    let api_rpc_urls = services::onchain::resolve_api_rpc_urls(&config);
+    tracing::info!(
+        "Starknet API RPC failover list ({}): {}",
+        api_rpc_urls.len(),
+        api_rpc_urls.join(", ")
+    );
     tracing::info!(
         "Auto Garaga Prover Command Configured: {}",
         config
@@ -280,16 +318,40 @@ async fn run() -> anyhow::Result<()> {
     // Initialize Redis
     eprintln!("Startup stage: initializing Redis connection manager");
     tracing::info!("Initializing Redis connection manager...");
-    let redis_manager =
-        tokio::time::timeout(Duration::from_secs(30), init_redis_connection_manager(&config.redis_url))
-            .await
-            .context("timed out initializing Redis connection manager")??;
+    let redis_manager = tokio::time::timeout(
+        Duration::from_secs(30),
+        init_redis_connection_manager(&config.redis_url, Duration::from_secs(5)),
+    )
+    .await
+    .context("timed out initializing Redis connection manager")??;
+    // Order events go through a Redis stream so every instance sees fills from any executor.
+    // The relay gets its own connection because its blocking XREAD would stall shared commands.
+    let order_event_reader = tokio::time::timeout(
+        Duration::from_secs(30),
+        init_redis_connection_manager(
+            &config.redis_url,
+            Duration::from_millis(services::order_events::ORDER_EVENT_READ_BLOCK_MS as u64 + 5_000),
+        ),
+    )
+    .await
+    .context("timed out initializing Redis order event reader")??;
+    services::order_events::install_stream_publisher(redis_manager.clone());
+    tokio::spawn(services::order_events::run_stream_relay(order_event_reader));
+    let redis_healthy = Arc::new(AtomicBool::new(true));
+    if config.redis_keepalive_interval_secs > 0 {
+        tokio::spawn(run_redis_keepalive(
+            redis_manager.clone(),
+            config.redis_keepalive_interval_secs,
+            redis_healthy.clone(),
+        ));
+    }
 
     // Masukkan manager ke AppState
     let app_state = api::AppState {
         db: db.clone(),
         redis: redis_manager,
         config: config.clone(),
+        redis_healthy,
     };
 
     // Build router
@@ -302,19 +364,26 @@ async fn run() -> anyhow::Result<()> {
     ));
 
     // Optional BTC -> Starknet bridge watcher.
-    let enable_bridge_watcher = std::env::var("ENABLE_BTC_BRIDGE_WATCHER")
-        .map(|v| {
-            let normalized = v.trim().to_ascii_lowercase();
-            normalized == "1" || normalized == "true" || normalized == "yes" || normalized == "on"
-        })
-        .unwrap_or(false);
-    if enable_bridge_watcher {
+    if bridge_worker::bridge_watcher_enabled() {
         tracing::info!("Starting BTC bridge watcher...");
         tokio::spawn(async {
             bridge_worker::start_bridge_watcher().await;
         });
     }
 
+    // Hide-balance executor kind detection; HIDE_BALANCE_EXECUTOR_KIND stays an override.
+    tokio::spawn(services::hide_executor::start_hide_executor_detection(
+        config.clone(),
+    ));
+
+    // HTLC refund monitor for in-flight private BTC swaps.
+    if bridge_worker::htlc_refund_monitor_enabled(&config) {
+        tokio::spawn(bridge_worker::start_htlc_refund_monitor(
+            db.clone(),
+            config.clone(),
+        ));
+    }
+
     // Start server
     let addr: SocketAddr = format!("{}:{}", config.host, config.port)
         .parse()
@@ -324,7 +393,11 @@ async fn run() -> anyhow::Result<()> {
     tracing::info!("Listening on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -346,23 +419,148 @@ async fn main() -> anyhow::Result<()> {
 fn build_router(state: api::AppState) -> Router {
     // CORS configuration
     let cors = cors_from_config(&state.config);
+    // Per-route body caps: small for auth/profile, large for proof-carrying privacy routes.
+    let small_body = body_limit(state.config.http_body_limit_small_bytes);
+    let proof_body = body_limit(state.config.http_body_limit_proof_bytes);
+    let default_body_bytes = state.config.http_body_limit_bytes;
+
+    // Handlers that wait on RPC retries, the relayer or the prover get the long timeout budget.
+    let long_running = Router::new()
+        .route("/api/v1/swap/execute", post(api::swap::execute_swap))
+        .route("/api/v1/bridge/execute", post(api::bridge::execute_bridge))
+        .route("/api/v1/ai/execute", post(api::ai::execute_command))
+        .route(
+            "/api/v1/privacy/submit",
+            post(api::privacy::submit_private_action).layer(proof_body),
+        )
+        .route(
+            "/api/v1/privacy/auto-submit",
+            post(api::privacy::auto_submit_private_action).layer(proof_body),
+        )
+        .route(
+            "/api/v1/privacy/prepare-private-execution",
+            post(api::privacy::prepare_private_execution),
+        )
+        .route(
+            "/api/v1/privacy/prepare-private-exit",
+            post(api::privacy::prepare_private_exit),
+        )
+        .route(
+            "/api/v1/privacy/relayer-execute",
+            post(api::privacy::relay_private_execution).layer(proof_body),
+        )
+        .route(
+            "/api/v1/privacy/self-test",
+            post(api::privacy::privacy_self_test),
+        )
+        .route(
+            "/api/v1/private-btc-swap/initiate",
+            post(api::private_btc_swap::initiate_private_btc_swap),
+        )
+        .route(
+            "/api/v1/private-btc-swap/finalize",
+            post(api::private_btc_swap::finalize_private_btc_swap),
+        )
+        .route(
+            "/api/v1/swap/relayer-preview",
+            post(api::swap::preview_hide_swap_relayer_calls),
+        )
+        // Dark pool
+        .route(
+            "/api/v1/dark-pool/order",
+            post(api::dark_pool::submit_order),
+        )
+        .route("/api/v1/dark-pool/match", post(api::dark_pool::match_order))
+        .route("/api/v1/dark-pool/limits", get(api::dark_pool::get_limits))
+        .route(
+            "/api/v1/dark-pool/nullifier/{nullifier}",
+            get(api::dark_pool::is_nullifier_used),
+        )
+        // Private payments
+        .route(
+            "/api/v1/private-payments/submit",
+            post(api::private_payments::submit_private_payment),
+        )
+        .route(
+            "/api/v1/private-payments/finalize",
+            post(api::private_payments::finalize_private_payment),
+        )
+        .route(
+            "/api/v1/private-payments/nullifier/{nullifier}",
+            get(api::private_payments::is_nullifier_used),
+        )
+        // Anonymous credentials
+        .route(
+            "/api/v1/credentials/submit",
+            post(api::anonymous_credentials::submit_credential_proof),
+        )
+        .route(
+            "/api/v1/credentials/submit-batch",
+            post(api::anonymous_credentials::submit_credential_proof_batch),
+        )
+        .route(
+            "/api/v1/credentials/nullifier/{nullifier}",
+            get(api::anonymous_credentials::is_nullifier_used),
+        )
+        // Relayer-submitting routes: a timeout after broadcast would skip the bookkeeping.
+        .route(
+            "/api/v1/rewards/sync-onchain",
+            post(api::rewards::sync_points_onchain),
+        )
+        .route("/api/v1/rewards/claim", post(api::rewards::claim_rewards))
+        .route(
+            "/api/v1/rewards/convert",
+            post(api::rewards::convert_to_carel),
+        )
+        .route(
+            "/api/v1/admin/deposits/settle",
+            post(api::admin::settle_deposit),
+        )
+        .route(
+            "/api/v1/limit-order/create",
+            post(api::limit_order::create_order),
+        )
+        .route(
+            "/api/v1/limit-order/{order_id}",
+            axum::routing::delete(api::limit_order::cancel_order),
+        )
+        .route("/api/v1/stake/deposit", post(api::stake::deposit))
+        .route("/api/v1/stake/withdraw", post(api::stake::withdraw))
+        .route("/api/v1/stake/claim", post(api::stake::claim))
+        .route("/api/v1/nft/mint", post(api::nft::mint_nft))
+        .route("/api/v1/faucet/claim", post(api::faucet::claim_tokens))
+        .route("/api/v1/ai/upgrade", post(api::ai::upgrade_ai_level))
+        .layer(request_timeout(state.config.http_long_request_timeout_secs));
 
     Router::new()
         // Health check
         .route("/health", get(api::health::health_check))
+        .route("/api/v1/config/features", get(api::features::get_features))
         // Authentication
-        .route("/api/v1/auth/connect", post(api::auth::connect_wallet))
-        .route("/api/v1/auth/refresh", post(api::auth::refresh_token))
+        .route(
+            "/api/v1/auth/connect",
+            post(api::auth::connect_wallet).layer(small_body),
+        )
+        .route(
+            "/api/v1/auth/refresh",
+            post(api::auth::refresh_token).layer(small_body),
+        )
         .route("/api/v1/profile/me", get(api::profile::get_profile))
         .route(
             "/api/v1/profile/display-name",
-            axum::routing::put(api::profile::set_display_name),
+            axum::routing::put(api::profile::set_display_name).layer(small_body),
         )
         // Swap & Bridge
         .route("/api/v1/swap/quote", post(api::swap::get_quote))
-        .route("/api/v1/swap/execute", post(api::swap::execute_swap))
+        .route(
+            "/api/v1/swap/receipt/{tx_hash}",
+            get(api::swap::get_swap_receipt),
+        )
         .route("/api/v1/bridge/quote", post(api::bridge::get_bridge_quote))
-        .route("/api/v1/bridge/execute", post(api::bridge::execute_bridge))
+        .route(
+            "/api/v1/bridge/supported",
+            get(api::bridge::get_supported_bridges),
+        )
         .route(
             "/api/v1/bridge/status/{bridge_id}",
             get(api::bridge::get_bridge_status),
@@ -399,24 +597,17 @@ fn build_router(state: api::AppState) -> Router {
             "/api/v1/garden/apps/earnings",
             get(api::garden::get_app_earnings),
         )
-        // Limit Orders
         .route(
-            "/api/v1/limit-order/create",
-            post(api::limit_order::create_order),
+            "/api/v1/garden/apps/earnings/history",
+            get(api::garden::get_app_earnings_history),
         )
+        // Limit Orders
         .route(
             "/api/v1/limit-order/list",
             get(api::limit_order::list_orders),
         )
-        .route(
-            "/api/v1/limit-order/{order_id}", // PERBAIKAN: :order_id -> {order_id}
-            axum::routing::delete(api::limit_order::cancel_order),
-        )
         // Staking
         .route("/api/v1/stake/pools", get(api::stake::get_pools))
-        .route("/api/v1/stake/deposit", post(api::stake::deposit))
-        .route("/api/v1/stake/withdraw", post(api::stake::withdraw))
-        .route("/api/v1/stake/claim", post(api::stake::claim))
         .route("/api/v1/stake/positions", get(api::stake::get_positions))
         // Portfolio
         .route(
@@ -460,6 +651,14 @@ fn build_router(state: api::AppState) -> Router {
             "/api/v1/leaderboard/global/{epoch}",
             get(api::leaderboard::get_global_metrics_epoch),
         )
+        .route(
+            "/api/v1/leaderboard/points/live",
+            get(api::leaderboard::get_live_points_rankings),
+        )
+        .route(
+            "/api/v1/leaderboard/points/finalized/{epoch}",
+            get(api::leaderboard::get_finalized_points_rankings),
+        )
         .route(
             "/api/v1/leaderboard/user/{address}", // PERBAIKAN: :address -> {address}
             get(api::leaderboard::get_user_rank),
@@ -469,18 +668,16 @@ fn build_router(state: api::AppState) -> Router {
             get(api::leaderboard::get_user_categories),
         )
         // Rewards & Points
-        .route("/api/v1/rewards/points", get(api::rewards::get_points))
         .route(
-            "/api/v1/rewards/sync-onchain",
-            post(api::rewards::sync_points_onchain),
+            "/api/v1/tokenomics/projection",
+            get(api::tokenomics::get_projection),
         )
-        .route("/api/v1/rewards/claim", post(api::rewards::claim_rewards))
         .route(
-            "/api/v1/rewards/convert",
-            post(api::rewards::convert_to_carel),
+            "/api/v1/tokenomics/supply",
+            get(api::tokenomics::get_supply),
         )
+        .route("/api/v1/rewards/points", get(api::rewards::get_points))
         // NFT
-        .route("/api/v1/nft/mint", post(api::nft::mint_nft))
         .route("/api/v1/nft/owned", get(api::nft::get_owned_nfts))
         // Referral
         .route("/api/v1/referral/code", get(api::referral::get_code))
@@ -491,78 +688,62 @@ fn build_router(state: api::AppState) -> Router {
         .route("/api/v1/social/verify", post(api::social::verify_task))
         // Admin (manual maintenance)
         .route("/api/v1/admin/points/reset", post(api::admin::reset_points))
-        // Privacy
-        .route(
-            "/api/v1/privacy/submit",
-            post(api::privacy::submit_private_action),
-        )
-        .route(
-            "/api/v1/privacy/auto-submit",
-            post(api::privacy::auto_submit_private_action),
-        )
-        .route(
-            "/api/v1/privacy/prepare-private-execution",
-            post(api::privacy::prepare_private_execution),
-        )
+        .route("/api/v1/admin/fees/summary", get(api::admin::fee_summary))
         .route(
-            "/api/v1/privacy/prepare-private-exit",
-            post(api::privacy::prepare_private_exit),
+            "/api/v1/admin/log-level",
+            get(api::admin::get_log_level).post(api::admin::set_log_level),
         )
         .route(
-            "/api/v1/privacy/fixed-amount",
-            post(api::privacy::get_private_fixed_amount),
+            "/api/v1/admin/maintenance",
+            get(api::admin::get_maintenance).post(api::admin::set_maintenance),
         )
         .route(
-            "/api/v1/privacy/relayer-execute",
-            post(api::privacy::relay_private_execution),
+            "/api/v1/admin/transactions/stuck",
+            get(api::admin::stuck_transactions),
         )
-        // Private BTC swap
         .route(
-            "/api/v1/private-btc-swap/initiate",
-            post(api::private_btc_swap::initiate_private_btc_swap),
+            "/api/v1/admin/transactions/retry",
+            post(api::admin::retry_transactions),
         )
         .route(
-            "/api/v1/private-btc-swap/finalize",
-            post(api::private_btc_swap::finalize_private_btc_swap),
+            "/api/v1/admin/transactions/reconciliation",
+            get(api::admin::reconciliation_issues),
         )
         .route(
-            "/api/v1/private-btc-swap/nullifier/{nullifier}",
-            get(api::private_btc_swap::is_nullifier_used),
+            "/api/v1/admin/ai/downgrade",
+            post(api::admin::downgrade_ai_level),
         )
-        // Dark pool
         .route(
-            "/api/v1/dark-pool/order",
-            post(api::dark_pool::submit_order),
+            "/api/v1/admin/privacy/audit",
+            get(api::admin::privacy_audit_log),
         )
-        .route("/api/v1/dark-pool/match", post(api::dark_pool::match_order))
         .route(
-            "/api/v1/dark-pool/nullifier/{nullifier}",
-            get(api::dark_pool::is_nullifier_used),
+            "/api/v1/admin/relayer/spend",
+            get(api::admin::relayer_spend),
         )
-        // Private payments
         .route(
-            "/api/v1/private-payments/submit",
-            post(api::private_payments::submit_private_payment),
+            "/api/v1/treasury/balances",
+            get(api::treasury::get_balances),
         )
+        // Privacy
         .route(
-            "/api/v1/private-payments/finalize",
-            post(api::private_payments::finalize_private_payment),
+            "/api/v1/privacy/fixed-amount",
+            post(api::privacy::get_private_fixed_amount),
         )
         .route(
-            "/api/v1/private-payments/nullifier/{nullifier}",
-            get(api::private_payments::is_nullifier_used),
+            "/api/v1/privacy/anonymity-set",
+            get(api::anonymity_set::get_anonymity_set),
         )
-        // Anonymous credentials
+        // Private BTC swap
         .route(
-            "/api/v1/credentials/submit",
-            post(api::anonymous_credentials::submit_credential_proof),
+            "/api/v1/private-btc-swap/nullifier/{nullifier}",
+            get(api::private_btc_swap::is_nullifier_used),
         )
         .route(
-            "/api/v1/credentials/nullifier/{nullifier}",
-            get(api::anonymous_credentials::is_nullifier_used),
+            "/api/v1/private-btc-swap/history",
+            get(api::private_btc_swap::list_private_btc_swaps),
         )
         // Faucet (Testnet)
-        .route("/api/v1/faucet/claim", post(api::faucet::claim_tokens))
         .route("/api/v1/faucet/status", get(api::faucet::get_status))
         .route("/api/v1/faucet/stats", get(api::faucet::get_faucet_stats))
         // Deposit (Fiat On-Ramp)
@@ -583,6 +764,14 @@ fn build_router(state: api::AppState) -> Router {
             "/api/v1/notifications/preferences",
             axum::routing::put(api::notifications::update_preferences),
         )
+        .route(
+            "/api/v1/notifications/telegram/link",
+            post(api::notifications::create_telegram_link),
+        )
+        .route(
+            "/api/v1/notifications/telegram/verify",
+            post(api::notifications::verify_telegram_link),
+        )
         .route(
             "/api/v1/notifications/stats",
             get(api::notifications::get_stats),
@@ -611,6 +800,15 @@ fn build_router(state: api::AppState) -> Router {
             "/api/v1/market/depth/{token}",
             get(api::market::get_market_depth),
         )
+        // Price alerts
+        .route(
+            "/api/v1/alerts",
+            post(api::alerts::create).get(api::alerts::list),
+        )
+        .route(
+            "/api/v1/alerts/{id}",
+            axum::routing::delete(api::alerts::delete),
+        )
         // Webhooks
         .route("/api/v1/webhooks/register", post(api::webhooks::register))
         .route("/api/v1/webhooks/list", get(api::webhooks::list))
@@ -619,19 +817,23 @@ fn build_router(state: api::AppState) -> Router {
             axum::routing::delete(api::webhooks::delete),
         )
         .route("/api/v1/webhooks/logs", get(api::webhooks::get_logs))
+        .route(
+            "/api/v1/webhooks/{id}/test",
+            post(api::webhooks::test_delivery),
+        )
         // AI Assistant
         .route(
             "/api/v1/ai/prepare-action",
             post(api::ai::prepare_action_signature),
         )
         .route("/api/v1/ai/level", get(api::ai::get_ai_level))
-        .route("/api/v1/ai/upgrade", post(api::ai::upgrade_ai_level))
+        .route("/api/v1/ai/levels", get(api::ai::get_ai_levels))
+        .route("/api/v1/ai/downgrade", post(api::ai::downgrade_ai_level))
         .route("/api/v1/ai/config", get(api::ai::get_runtime_config))
         .route(
             "/api/v1/ai/ensure-executor",
             post(api::ai::ensure_executor_ready),
         )
-        .route("/api/v1/ai/execute", post(api::ai::execute_command))
         .route("/api/v1/ai/pending", get(api::ai::get_pending_actions))
         // DeFi Futures (Battleship with Garaga payload flow)
         .route(
@@ -660,10 +862,31 @@ fn build_router(state: api::AppState) -> Router {
         .route("/ws/notifications", get(websocket::notifications::handler))
         .route("/ws/prices", get(websocket::prices::handler))
         .route("/ws/orders", get(websocket::orders::handler))
+        // Applied before the merge so the long-running group keeps only its own budget.
+        .layer(request_timeout(state.config.http_request_timeout_secs))
+        .merge(long_running)
+        .layer(DefaultBodyLimit::max(default_body_bytes))
         .layer(cors)
         .with_state(state)
 }
 
+// Internal helper that builds inputs for `request_timeout`.
+// Responds 504 once the budget elapses; the handler future is dropped at that point, which
+// cancels any RPC, relayer or prover call it was awaiting instead of leaving it running.
+fn request_timeout(secs: u64) -> TimeoutLayer {
+    TimeoutLayer::with_status_code(StatusCode::GATEWAY_TIMEOUT, Duration::from_secs(secs))
+}
+
+// Internal helper that builds inputs for `body_limit`.
+// Disables axum's extractor limit for the route so `RequestBodyLimitLayer` alone applies,
+// rejecting oversized bodies with 413 before the handler runs.
+fn body_limit(max_bytes: usize) -> (DefaultBodyLimit, RequestBodyLimitLayer) {
+    (
+        DefaultBodyLimit::disable(),
+        RequestBodyLimitLayer::new(max_bytes),
+    )
+}
+
 // Internal helper that supports `cors_from_config` operations.
 fn cors_from_config(config: &Config) -> CorsLayer {
     let raw = config.cors_allowed_origins.trim();
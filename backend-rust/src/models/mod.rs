@@ -4,6 +4,7 @@ pub mod user;
 // Re-export commonly used types from user.rs so other modules can use `crate::models::X`
 pub use user::{
     ApiResponse,
+    BridgeQuoteOption,
     BridgeQuoteRequest,
     BridgeQuoteResponse,
     CreateLimitOrderRequest,
@@ -15,13 +16,19 @@ pub use user::{
     Notification,
     NotificationPreferences,
     OHLCVResponse,
+    OrderStatus,
+    OrderType,
     PaginatedResponse,
+    PriceAlert,
     PriceTick,
+    PrivateBtcSwap,
     StarknetWalletCall,
+    SwapFeeBreakdown,
     SwapQuoteRequest,
     SwapQuoteResponse,
     // add other exports as needed
     Transaction,
+    TransactionHistoryItem,
     User,
     UserPoints,
     Webhook,
@@ -63,6 +63,37 @@ pub struct Transaction {
     pub processed: bool,
 }
 
+/// History row: a transaction plus its points-processing lifecycle.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct TransactionHistoryItem {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub transaction: Transaction,
+    /// `pending` (not attempted yet), `retrying` (a previous attempt failed), `failed`
+    /// (`POINT_CALCULATOR_MAX_ATTEMPTS` reached) or `processed`.
+    pub points_status: String,
+    pub processing_attempts: i32,
+    /// User-facing summary of the processing state; raw worker errors stay server-side.
+    pub processing_error: Option<String>,
+}
+
+/// Fee breakdown stored for an executed swap, in units of the input token.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SwapFeeBreakdown {
+    pub tx_hash: String,
+    pub mode: String,
+    pub base_fee: Decimal,
+    pub mev_fee: Decimal,
+    pub fee_before_discount: Decimal,
+    pub nft_discount_percent: Decimal,
+    pub stake_discount_percent: Decimal,
+    /// Combined NFT + staking discount actually applied, after the cap.
+    pub fee_discount_percent: Decimal,
+    pub nft_discount_saved: Decimal,
+    pub stake_discount_saved: Decimal,
+    pub net_fee: Decimal,
+}
+
 // ==================== FAUCET ====================
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FaucetClaim {
@@ -106,6 +137,29 @@ pub struct NotificationPreferences {
     pub push_enabled: bool,
     pub telegram_enabled: bool,
     pub discord_enabled: bool,
+    // Global mute for every notification type.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub muted_all: bool,
+    // Muted notification types, e.g. "swap.completed".
+    #[serde(default)]
+    #[sqlx(default)]
+    pub muted_types: Vec<String>,
+    // Quiet-hours window in UTC hours [start, end); wraps past midnight when start > end.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub quiet_hours_start: Option<i16>,
+    #[serde(default)]
+    #[sqlx(default)]
+    pub quiet_hours_end: Option<i16>,
+    // Recipient for the email channel.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub email_address: Option<String>,
+    // Digest schedule for low-priority types: "hourly", "daily", or unset for immediate delivery.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub digest_frequency: Option<String>,
 }
 
 // ==================== SWAP ====================
@@ -130,6 +184,8 @@ pub struct SwapQuoteResponse {
     pub estimated_gas: String,
     pub estimated_time: String,
     pub onchain_calls: Option<Vec<StarknetWalletCall>>,
+    // Points preview; includes the caller's NFT/AI-level boosts when the request is authenticated.
+    pub estimated_points_earned: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -147,6 +203,8 @@ pub struct BridgeQuoteRequest {
     pub token: String,
     pub to_token: Option<String>,
     pub amount: String,
+    /// `best_output` or `fastest`; defaults to `BRIDGE_QUOTE_SORT`.
+    pub sort_by: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -158,9 +216,180 @@ pub struct BridgeQuoteResponse {
     pub fee: String,
     pub estimated_time: String,
     pub bridge_provider: String,
+    /// Every provider that returned a quote, ordered by `sort_by`.
+    pub options: Vec<BridgeQuoteOption>,
+}
+
+/// One provider's bridge quote, normalized for side-by-side comparison.
+#[derive(Debug, Clone, Serialize)]
+pub struct BridgeQuoteOption {
+    pub provider: String,
+    pub fee: String,
+    pub fee_token: String,
+    pub fee_usd: String,
+    pub estimated_time: String,
+    pub estimated_time_minutes: u32,
+    pub net_output: String,
+    pub net_output_token: String,
+    pub net_output_usd: String,
+    /// Set on the option the backend executes when no provider is chosen.
+    pub recommended: bool,
 }
 
 // ==================== LIMIT ORDER ====================
+/// Lifecycle state of a limit order, stored as SMALLINT in `limit_orders.status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrderStatus {
+    Active,
+    Partial,
+    Filled,
+    Cancelled,
+    Expired,
+    // Code not known to this build; kept so the stored value is never rewritten.
+    Unknown(i16),
+}
+
+impl OrderStatus {
+    /// Returns the API label for this status.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Partial => "partially_filled",
+            Self::Filled => "filled",
+            Self::Cancelled => "cancelled",
+            Self::Expired => "expired",
+            Self::Unknown(_) => "unknown",
+        }
+    }
+}
+
+impl From<i16> for OrderStatus {
+    // Internal helper that supports `from` operations.
+    fn from(code: i16) -> Self {
+        match code {
+            0 => Self::Active,
+            1 => Self::Partial,
+            2 => Self::Filled,
+            3 => Self::Cancelled,
+            4 => Self::Expired,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+impl From<OrderStatus> for i16 {
+    // Internal helper that supports `from` operations.
+    fn from(status: OrderStatus) -> Self {
+        match status {
+            OrderStatus::Active => 0,
+            OrderStatus::Partial => 1,
+            OrderStatus::Filled => 2,
+            OrderStatus::Cancelled => 3,
+            OrderStatus::Expired => 4,
+            OrderStatus::Unknown(code) => code,
+        }
+    }
+}
+
+impl std::str::FromStr for OrderStatus {
+    type Err = String;
+
+    // Internal helper that parses or transforms values for `from_str`.
+    fn from_str(raw: &str) -> std::result::Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "active" => Ok(Self::Active),
+            "partial" | "partially_filled" => Ok(Self::Partial),
+            "filled" => Ok(Self::Filled),
+            "cancelled" | "canceled" => Ok(Self::Cancelled),
+            "expired" => Ok(Self::Expired),
+            other => Err(format!("unknown order status: {}", other)),
+        }
+    }
+}
+
+impl Serialize for OrderStatus {
+    // Internal helper that supports `serialize` operations.
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderStatus {
+    // Internal helper that supports `deserialize` operations.
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Execution rule of an order; conditional types fire at `trigger_price` and fill at market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderType {
+    #[default]
+    Limit,
+    // Sells `from_token` once its price falls to or below the trigger.
+    StopLoss,
+    // Sells `from_token` once its price rises to or above the trigger.
+    TakeProfit,
+}
+
+impl OrderType {
+    /// Returns the stored/API label for this order type.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Limit => "limit",
+            Self::StopLoss => "stop_loss",
+            Self::TakeProfit => "take_profit",
+        }
+    }
+
+    /// Returns true for stop-loss and take-profit orders.
+    pub fn is_conditional(self) -> bool {
+        !matches!(self, Self::Limit)
+    }
+
+    /// Returns true when `market_price` satisfies the trigger of a conditional order.
+    pub fn is_triggered(self, market_price: f64, trigger_price: f64) -> bool {
+        if !market_price.is_finite() || !trigger_price.is_finite() || trigger_price <= 0.0 {
+            return false;
+        }
+        match self {
+            Self::Limit => false,
+            Self::StopLoss => market_price <= trigger_price,
+            Self::TakeProfit => market_price >= trigger_price,
+        }
+    }
+}
+
+impl std::str::FromStr for OrderType {
+    type Err = String;
+
+    // Internal helper that parses or transforms values for `from_str`.
+    fn from_str(raw: &str) -> std::result::Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "limit" => Ok(Self::Limit),
+            "stop_loss" | "stop-loss" => Ok(Self::StopLoss),
+            "take_profit" | "take-profit" => Ok(Self::TakeProfit),
+            other => Err(format!("unknown order type: {}", other)),
+        }
+    }
+}
+
+impl TryFrom<String> for OrderType {
+    type Error = String;
+
+    // Internal helper that supports `try_from` operations.
+    fn try_from(raw: String) -> std::result::Result<Self, Self::Error> {
+        raw.parse()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct LimitOrder {
     pub order_id: String,
@@ -172,8 +401,13 @@ pub struct LimitOrder {
     pub price: Decimal,
     pub expiry: DateTime<Utc>,
     pub recipient: Option<String>,
-    pub status: i16, // 0=active, 1=partial, 2=filled, 3=cancelled, 4=expired
+    #[sqlx(try_from = "i16")]
+    pub status: OrderStatus,
     pub created_at: DateTime<Utc>,
+    #[sqlx(try_from = "String")]
+    pub order_type: OrderType,
+    // Market price (`from_token` quoted in `to_token`) that fires a conditional order.
+    pub trigger_price: Option<Decimal>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -198,6 +432,8 @@ pub struct CreateLimitOrderRequest {
     pub price: String,
     pub expiry: String, // "1d", "7d", "30d"
     pub recipient: Option<String>,
+    pub order_type: Option<String>, // limit/stop_loss/take_profit
+    pub trigger_price: Option<String>,
     pub client_order_id: Option<String>,
     pub onchain_tx_hash: Option<String>,
     pub hide_balance: Option<bool>,
@@ -221,6 +457,41 @@ pub struct OHLCVResponse {
     pub token: String,
     pub interval: String,
     pub data: Vec<PriceTick>,
+    // VWAP aligned with `data`, present only when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vwap: Option<Vec<Option<f64>>>,
+}
+
+// ==================== PRICE ALERT ====================
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PriceAlert {
+    pub id: i64,
+    pub user_address: String,
+    pub token: String,
+    pub direction: String, // above/below
+    pub threshold: Decimal,
+    pub recurring: bool,
+    pub active: bool,
+    // Price seen at the previous evaluation; a crossing is detected against it.
+    pub last_price: Option<Decimal>,
+    pub triggered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ==================== PRIVATE BTC SWAP ====================
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PrivateBtcSwap {
+    pub id: i64,
+    pub commitment: String,
+    pub swap_id: Option<i64>,
+    pub status: String, // initiated/finalized/refunded/failed
+    pub initiate_tx_hash: String,
+    // HTLC refund timelock; the swap becomes refundable once it has passed.
+    pub timelock_at: Option<DateTime<Utc>>,
+    pub refund_preimage_available: bool,
+    pub refund_tx_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 // ==================== WEBHOOK ====================
@@ -267,7 +538,37 @@ pub struct PaginatedResponse<T> {
     pub items: Vec<T>,
     pub page: i32,
     pub limit: i32,
+    pub offset: i64,
     pub total: i64,
+    pub has_more: bool,
+}
+
+impl<T> PaginatedResponse<T> {
+    /// Builds one page of a list response from a 1-based `page`, deriving `offset` and `has_more`.
+    pub fn new(items: Vec<T>, page: i32, limit: i32, total: i64) -> Self {
+        let offset = i64::from(page.max(1) - 1) * i64::from(limit.max(0));
+        let has_more = offset + (items.len() as i64) < total;
+        Self {
+            items,
+            page,
+            limit,
+            offset,
+            total,
+            has_more,
+        }
+    }
+
+    /// Slices an already-loaded list into the requested page.
+    pub fn from_vec(all: Vec<T>, page: i32, limit: i32) -> Self {
+        let total = all.len() as i64;
+        let start = (page.max(1) - 1) as usize * limit.max(0) as usize;
+        let items = all
+            .into_iter()
+            .skip(start)
+            .take(limit.max(0) as usize)
+            .collect();
+        Self::new(items, page, limit, total)
+    }
 }
 
 #[cfg(test)]
@@ -283,6 +584,18 @@ mod tests {
         assert_eq!(response.data, "ok");
     }
 
+    #[test]
+    // Internal helper that supports `paginated_response_derives_offset_and_has_more` operations.
+    fn paginated_response_derives_offset_and_has_more() {
+        let first = PaginatedResponse::new(vec![1, 2], 1, 2, 5);
+        assert_eq!(first.offset, 0);
+        assert!(first.has_more);
+        let last = PaginatedResponse::from_vec(vec![1, 2, 3, 4, 5], 3, 2);
+        assert_eq!(last.offset, 4);
+        assert_eq!(last.items, vec![5]);
+        assert!(!last.has_more);
+    }
+
     #[test]
     // Internal helper that supports `notification_preferences_default_false` operations.
     fn notification_preferences_default_false() {
@@ -292,5 +605,40 @@ mod tests {
         assert!(!prefs.push_enabled);
         assert!(!prefs.telegram_enabled);
         assert!(!prefs.discord_enabled);
+        assert!(!prefs.muted_all);
+        assert!(prefs.muted_types.is_empty());
+        assert!(prefs.quiet_hours_start.is_none());
+    }
+
+    #[test]
+    // Internal helper that supports `order_status_round_trips_numeric_codes` operations.
+    fn order_status_round_trips_numeric_codes() {
+        for code in 0..=4i16 {
+            let status = OrderStatus::from(code);
+            assert!(!matches!(status, OrderStatus::Unknown(_)));
+            assert_eq!(i16::from(status), code);
+            assert_eq!(status.as_str().parse::<OrderStatus>().unwrap(), status);
+        }
+        assert_eq!(OrderStatus::from(9), OrderStatus::Unknown(9));
+        assert_eq!(i16::from(OrderStatus::from(9)), 9);
+        assert_eq!(
+            serde_json::to_string(&OrderStatus::Partial).unwrap(),
+            "\"partially_filled\""
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `order_type_trigger_semantics` operations.
+    fn order_type_trigger_semantics() {
+        assert!(OrderType::StopLoss.is_triggered(95.0, 100.0));
+        assert!(!OrderType::StopLoss.is_triggered(105.0, 100.0));
+        assert!(OrderType::TakeProfit.is_triggered(105.0, 100.0));
+        assert!(!OrderType::TakeProfit.is_triggered(95.0, 100.0));
+        assert!(!OrderType::Limit.is_triggered(95.0, 100.0));
+        assert_eq!(
+            "stop-loss".parse::<OrderType>().unwrap(),
+            OrderType::StopLoss
+        );
+        assert_eq!(OrderType::TakeProfit.as_str(), "take_profit");
     }
 }
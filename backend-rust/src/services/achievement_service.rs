@@ -0,0 +1,378 @@
+use crate::{
+    config::Config,
+    constants::ACHIEVEMENT_RECOMPUTE_INTERVAL_SECS,
+    db::Database,
+    error::Result,
+    services::notification_service::{NotificationService, NotificationType},
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+// Used when `ACHIEVEMENT_DEFINITIONS` is unset or has no valid entries.
+const DEFAULT_ACHIEVEMENT_DEFINITIONS: &str =
+    "top_swapper=swap_volume_usd:10000,bridge_pioneer=bridge_count:5,privacy_advocate=private_tx_count:10";
+
+/// Transaction statistic an achievement is measured against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AchievementMetric {
+    SwapCount,
+    SwapVolumeUsd,
+    BridgeCount,
+    BridgeVolumeUsd,
+    PrivateTxCount,
+    ReferralCount,
+}
+
+impl AchievementMetric {
+    /// Parses the config name of a metric.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "swap_count" => Some(Self::SwapCount),
+            "swap_volume_usd" => Some(Self::SwapVolumeUsd),
+            "bridge_count" => Some(Self::BridgeCount),
+            "bridge_volume_usd" => Some(Self::BridgeVolumeUsd),
+            "private_tx_count" => Some(Self::PrivateTxCount),
+            "referral_count" => Some(Self::ReferralCount),
+            _ => None,
+        }
+    }
+}
+
+/// One configurable achievement category.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AchievementDefinition {
+    pub key: String,
+    pub name: String,
+    pub metric: AchievementMetric,
+    pub threshold: f64,
+}
+
+/// Per-identity transaction statistics the achievements are computed from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AchievementStats {
+    pub swap_count: f64,
+    pub swap_volume_usd: f64,
+    pub bridge_count: f64,
+    pub bridge_volume_usd: f64,
+    pub private_tx_count: f64,
+    pub referral_count: f64,
+}
+
+impl AchievementStats {
+    /// Returns the statistic measured by `metric`.
+    pub fn value(&self, metric: AchievementMetric) -> f64 {
+        match metric {
+            AchievementMetric::SwapCount => self.swap_count,
+            AchievementMetric::SwapVolumeUsd => self.swap_volume_usd,
+            AchievementMetric::BridgeCount => self.bridge_count,
+            AchievementMetric::BridgeVolumeUsd => self.bridge_volume_usd,
+            AchievementMetric::PrivateTxCount => self.private_tx_count,
+            AchievementMetric::ReferralCount => self.referral_count,
+        }
+    }
+}
+
+// Internal helper that turns `top_swapper` into `Top Swapper`.
+fn display_name_for_key(key: &str) -> String {
+    key.split(['_', '-'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses `key=metric:threshold` entries separated by commas.
+///
+/// Invalid entries are logged and skipped; an empty result falls back to the built-in set.
+pub fn parse_achievement_definitions(raw: &str) -> Vec<AchievementDefinition> {
+    let parse = |raw: &str| -> Vec<AchievementDefinition> {
+        let mut seen = HashSet::new();
+        raw.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let parsed = entry.split_once('=').and_then(|(key, rule)| {
+                    let (metric, threshold) = rule.split_once(':')?;
+                    let key = key.trim().to_ascii_lowercase();
+                    let threshold = threshold.trim().parse::<f64>().ok()?;
+                    if key.is_empty() || !threshold.is_finite() || threshold <= 0.0 {
+                        return None;
+                    }
+                    Some(AchievementDefinition {
+                        name: display_name_for_key(&key),
+                        key,
+                        metric: AchievementMetric::parse(metric)?,
+                        threshold,
+                    })
+                });
+                if parsed.is_none() {
+                    tracing::warn!("Ignoring invalid ACHIEVEMENT_DEFINITIONS entry: {}", entry);
+                }
+                parsed
+            })
+            .filter(|definition| seen.insert(definition.key.clone()))
+            .collect()
+    };
+    let definitions = parse(raw);
+    if definitions.is_empty() {
+        parse(DEFAULT_ACHIEVEMENT_DEFINITIONS)
+    } else {
+        definitions
+    }
+}
+
+/// Progress of one identity toward one achievement.
+#[derive(Debug, Clone, Serialize)]
+pub struct AchievementProgress {
+    pub key: String,
+    pub name: String,
+    pub metric: AchievementMetric,
+    pub threshold: f64,
+    pub progress: f64,
+    pub progress_pct: f64,
+    pub earned: bool,
+    pub earned_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// Internal helper that supports `progress_pct` operations.
+fn progress_pct(progress: f64, threshold: f64) -> f64 {
+    if threshold <= 0.0 {
+        return 100.0;
+    }
+    (progress / threshold * 100.0).clamp(0.0, 100.0)
+}
+
+/// Achievement Service - Recomputes per-user categories from transaction stats
+pub struct AchievementService {
+    db: Database,
+    config: Config,
+}
+
+impl AchievementService {
+    /// Constructs a new instance via `new`.
+    pub fn new(db: Database, config: Config) -> Self {
+        Self { db, config }
+    }
+
+    /// Active achievement definitions from `ACHIEVEMENT_DEFINITIONS`.
+    pub fn definitions(&self) -> Vec<AchievementDefinition> {
+        parse_achievement_definitions(&self.config.achievement_definitions)
+    }
+
+    /// Starts the background loop that recomputes achievements for every user.
+    ///
+    /// # Notes
+    /// * Runs every `ACHIEVEMENT_RECOMPUTE_INTERVAL_SECS`; errors are logged and retried next tick.
+    pub async fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(
+                ACHIEVEMENT_RECOMPUTE_INTERVAL_SECS,
+            ));
+            loop {
+                ticker.tick().await;
+                match self.recompute_all().await {
+                    Ok(0) => {}
+                    Ok(earned) => tracing::info!("Awarded {} new achievements", earned),
+                    Err(err) => tracing::error!("Achievement recompute error: {}", err),
+                }
+            }
+        });
+    }
+
+    /// Recomputes progress for every identity and notifies newly earned achievements.
+    ///
+    /// # Returns
+    /// * `Ok(count)` with the number of achievements earned in this run.
+    ///
+    /// # Notes
+    /// * Earned achievements are sticky; progress keeps updating but `earned_at` never resets.
+    pub async fn recompute_all(&self) -> Result<usize> {
+        let definitions = self.definitions();
+        let stats = self.load_stats().await?;
+        let already_earned: HashSet<(String, String)> = sqlx::query_as::<_, (String, String)>(
+            "SELECT LOWER(user_address), achievement_key FROM user_achievements
+             WHERE earned_at IS NOT NULL",
+        )
+        .fetch_all(self.db.pool())
+        .await?
+        .into_iter()
+        .collect();
+
+        let notifications = NotificationService::new(self.db.clone(), self.config.clone());
+        let mut newly_earned = 0;
+        for (identity, identity_stats) in stats {
+            for definition in &definitions {
+                let progress = identity_stats.value(definition.metric);
+                let earned = progress >= definition.threshold;
+                sqlx::query(
+                    "INSERT INTO user_achievements
+                         (user_address, achievement_key, progress, threshold, earned_at, updated_at)
+                     VALUES ($1, $2, $3, $4, CASE WHEN $5 THEN NOW() ELSE NULL END, NOW())
+                     ON CONFLICT (user_address, achievement_key) DO UPDATE
+                     SET progress = EXCLUDED.progress,
+                         threshold = EXCLUDED.threshold,
+                         earned_at = COALESCE(user_achievements.earned_at, EXCLUDED.earned_at),
+                         updated_at = NOW()",
+                )
+                .bind(&identity)
+                .bind(&definition.key)
+                .bind(progress)
+                .bind(definition.threshold)
+                .bind(earned)
+                .execute(self.db.pool())
+                .await?;
+
+                let key = (identity.to_ascii_lowercase(), definition.key.clone());
+                if earned && !already_earned.contains(&key) {
+                    newly_earned += 1;
+                    if let Err(err) = notifications
+                        .send_notification(
+                            &identity,
+                            NotificationType::AchievementEarned,
+                            format!("Achievement unlocked: {}", definition.name),
+                            format!("You earned the {} category. Keep it up!", definition.name),
+                            Some(serde_json::json!({
+                                "achievement": definition.key,
+                                "name": definition.name,
+                                "progress": progress,
+                                "threshold": definition.threshold,
+                            })),
+                        )
+                        .await
+                    {
+                        tracing::warn!("Achievement notification failed for {}: {}", identity, err);
+                    }
+                }
+            }
+        }
+        Ok(newly_earned)
+    }
+
+    /// Earned and in-progress achievements for an identity, one entry per active definition.
+    pub async fn progress_for(&self, identity: &str) -> Result<Vec<AchievementProgress>> {
+        let rows: Vec<(String, f64, Option<chrono::DateTime<chrono::Utc>>)> = sqlx::query_as(
+            "SELECT achievement_key, progress::FLOAT, earned_at
+             FROM user_achievements
+             WHERE LOWER(user_address) = LOWER($1)",
+        )
+        .bind(identity)
+        .fetch_all(self.db.pool())
+        .await?;
+        let stored: HashMap<String, (f64, Option<chrono::DateTime<chrono::Utc>>)> = rows
+            .into_iter()
+            .map(|(key, progress, earned_at)| (key, (progress, earned_at)))
+            .collect();
+
+        Ok(self
+            .definitions()
+            .into_iter()
+            .map(|definition| {
+                let (progress, earned_at) =
+                    stored.get(&definition.key).copied().unwrap_or((0.0, None));
+                AchievementProgress {
+                    progress_pct: progress_pct(progress, definition.threshold),
+                    earned: earned_at.is_some(),
+                    key: definition.key,
+                    name: definition.name,
+                    metric: definition.metric,
+                    threshold: definition.threshold,
+                    progress,
+                    earned_at,
+                }
+            })
+            .collect())
+    }
+
+    // Internal helper that aggregates transaction and referral stats per identity.
+    async fn load_stats(&self) -> Result<HashMap<String, AchievementStats>> {
+        let rows: Vec<(String, f64, f64, f64, f64, f64)> = sqlx::query_as(
+            "SELECT
+                 COALESCE(uw.user_address, t.user_address) as identity,
+                 COUNT(*) FILTER (WHERE t.tx_type = 'swap')::FLOAT,
+                 COALESCE(SUM(t.usd_value) FILTER (WHERE t.tx_type = 'swap'), 0)::FLOAT,
+                 COUNT(*) FILTER (WHERE t.tx_type = 'bridge')::FLOAT,
+                 COALESCE(SUM(t.usd_value) FILTER (WHERE t.tx_type = 'bridge'), 0)::FLOAT,
+                 COUNT(*) FILTER (WHERE COALESCE(t.is_private, false) = true)::FLOAT
+             FROM transactions t
+             LEFT JOIN user_wallet_addresses uw
+               ON LOWER(uw.wallet_address) = LOWER(t.user_address)
+             GROUP BY COALESCE(uw.user_address, t.user_address)",
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+        let mut stats: HashMap<String, AchievementStats> = HashMap::new();
+        for (identity, swaps, swap_volume, bridges, bridge_volume, private_txs) in rows {
+            let entry = stats.entry(identity).or_default();
+            entry.swap_count += swaps;
+            entry.swap_volume_usd += swap_volume;
+            entry.bridge_count += bridges;
+            entry.bridge_volume_usd += bridge_volume;
+            entry.private_tx_count += private_txs;
+        }
+
+        let referrals: Vec<(String, f64)> = sqlx::query_as(
+            "SELECT COALESCE(uw.user_address, u.referrer) as identity, COUNT(*)::FLOAT
+             FROM users u
+             LEFT JOIN user_wallet_addresses uw
+               ON LOWER(uw.wallet_address) = LOWER(u.referrer)
+             WHERE u.referrer IS NOT NULL
+             GROUP BY COALESCE(uw.user_address, u.referrer)",
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+        for (identity, count) in referrals {
+            stats.entry(identity).or_default().referral_count += count;
+        }
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `parse_achievement_definitions_reads_config_entries` operations.
+    fn parse_achievement_definitions_reads_config_entries() {
+        let definitions = parse_achievement_definitions(
+            "whale=swap_volume_usd:50000, bad=unknown:1, referrer=referral_count:3, whale=swap_count:1",
+        );
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(definitions[0].key, "whale");
+        assert_eq!(definitions[0].metric, AchievementMetric::SwapVolumeUsd);
+        assert_eq!(definitions[1].name, "Referrer");
+        assert_eq!(definitions[1].threshold, 3.0);
+    }
+
+    #[test]
+    // Internal helper that supports `parse_achievement_definitions_falls_back_to_defaults` operations.
+    fn parse_achievement_definitions_falls_back_to_defaults() {
+        let definitions = parse_achievement_definitions("");
+        let names: Vec<_> = definitions.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Top Swapper", "Bridge Pioneer", "Privacy Advocate"]
+        );
+        assert_eq!(parse_achievement_definitions("x=swap_count:0").len(), 3);
+    }
+
+    #[test]
+    // Internal helper that supports `achievement_progress_pct_clamps` operations.
+    fn achievement_progress_pct_clamps() {
+        let stats = AchievementStats {
+            bridge_count: 2.0,
+            ..AchievementStats::default()
+        };
+        assert_eq!(stats.value(AchievementMetric::BridgeCount), 2.0);
+        assert_eq!(progress_pct(2.0, 5.0), 40.0);
+        assert_eq!(progress_pct(9.0, 5.0), 100.0);
+    }
+}
@@ -1,6 +1,5 @@
 use crate::{
     config::Config,
-    constants::EPOCH_DURATION_SECONDS,
     db::Database,
     error::Result,
     services::price_guard::{fallback_price_for, first_sane_price, symbol_candidates_for},
@@ -1452,7 +1451,7 @@ impl AIService {
     // Internal helper that runs side-effecting logic for `execute_points_command`.
     async fn execute_points_command(&self, user_address: &str, locale: &str) -> Result<AIResponse> {
         let is_id = is_indonesian_locale(locale);
-        let epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+        let epoch = self.config.current_rewards_epoch();
 
         let points = self.db.get_user_points(user_address, epoch).await?;
 
@@ -1461,12 +1460,7 @@ impl AIService {
             .map(|p| p.total_points.to_string().parse::<f64>().unwrap_or(0.0))
             .unwrap_or(0.0);
 
-        let total_points_epoch: Decimal = sqlx::query_scalar(
-            "SELECT COALESCE(SUM(total_points), 0) FROM points WHERE epoch = $1",
-        )
-        .bind(epoch)
-        .fetch_one(self.db.pool())
-        .await?;
+        let total_points_epoch = self.db.reward_points_total(epoch).await?;
 
         let estimated_carel = estimate_carel_from_points(
             Decimal::from_f64_retain(total).unwrap_or(Decimal::ZERO),
@@ -1,13 +1,25 @@
 use crate::{
+    api::swap::{
+        build_raw_token_transfer_call, build_relayed_swap_calls, build_token_transfer_call,
+        swap_token_addresses, swap_token_configured, token_decimals,
+    },
     config::Config,
+    constants::{DEPOSIT_SWAP_RECONCILE_AFTER_SECS, DEPOSIT_SWAP_WORKER_INTERVAL_SECS},
     db::Database,
     error::{AppError, Result},
+    services::onchain::{erc20_received_amount, parse_felt, resolve_backend_account},
+    services::relayer::{relayer_revert_reason, RelayerService},
 };
 use hex;
 use rand;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
 use serde::Serialize; // Disederhanakan (menghapus Deserialize yang tidak terpakai)
 use sqlx::Row; // Tambahkan ini untuk akses .get()
+use starknet_core::types::Call;
+use std::sync::Arc;
+
+const DEPOSIT_SWAP_BATCH_SIZE: i64 = 20;
 
 // Internal helper that builds inputs for `build_bank_details`.
 fn build_bank_details(deposit_id: &str) -> BankDetails {
@@ -29,6 +41,67 @@ fn build_stripe_url(deposit_id: &str) -> String {
     format!("https://checkout.stripe.com{}", deposit_id)
 }
 
+// Internal helper that normalizes the optional auto-swap target for a new deposit.
+// Returns `None` when no swap is needed (absent, blank, or already the base stablecoin).
+fn normalize_target_token(target_token: Option<&str>, base_token: &str) -> Option<String> {
+    let target = target_token?.trim().to_ascii_uppercase();
+    if target.is_empty() || target == base_token.trim().to_ascii_uppercase() {
+        return None;
+    }
+    Some(target)
+}
+
+// Internal helper that resolves how much base stablecoin a settled deposit credited.
+// Falls back to the fiat amount only for USD deposits, where the peg makes it exact enough.
+fn settled_stable_amount(
+    currency: &str,
+    amount: Decimal,
+    settled_amount: Option<Decimal>,
+) -> Option<Decimal> {
+    match settled_amount {
+        Some(value) if value > Decimal::ZERO => Some(value),
+        Some(_) => None,
+        None if currency.trim().eq_ignore_ascii_case("USD") && amount > Decimal::ZERO => {
+            Some(amount)
+        }
+        None => None,
+    }
+}
+
+// Settled deposit row the auto-swap worker is acting on.
+struct SettledSwap {
+    deposit_id: String,
+    user_address: String,
+    target_token: String,
+    amount: Decimal,
+}
+
+impl SettledSwap {
+    // Internal helper that reads the swap inputs from a `deposits` row.
+    // Returns `None` when the settled stablecoin amount is unknown.
+    fn from_row(row: &sqlx::postgres::PgRow) -> Option<Self> {
+        let amount = settled_stable_amount(
+            &row.get::<String, _>("currency"),
+            row.get("amount"),
+            row.get("settled_amount"),
+        )?;
+        Some(Self {
+            deposit_id: row.get("deposit_id"),
+            user_address: row.get("user_address"),
+            target_token: row.get("target_token"),
+            amount,
+        })
+    }
+
+    // Internal helper that formats the settled amount at the base token's precision.
+    fn amount_text(&self, base_token: &str) -> String {
+        self.amount
+            .round_dp(token_decimals(base_token))
+            .normalize()
+            .to_string()
+    }
+}
+
 pub struct DepositService {
     db: Database,
     config: Config,
@@ -66,7 +139,9 @@ impl DepositService {
         user_address: &str,
         amount: f64,
         currency: &str,
+        target_token: Option<&str>,
     ) -> Result<DepositInfo> {
+        let target_token = self.validate_target_token(target_token)?;
         let deposit_id = format!("DEP_BANK_{}", hex::encode(rand::random::<[u8; 16]>()));
         let bank_details = build_bank_details(&deposit_id);
 
@@ -78,6 +153,7 @@ impl DepositService {
             amount_dec,
             currency,
             "bank_transfer",
+            target_token.as_deref(),
         )
         .await?;
 
@@ -88,6 +164,7 @@ impl DepositService {
             amount,
             currency: currency.to_string(),
             details: serde_json::to_value(bank_details).ok(),
+            swap: target_token.map(|target| DepositSwapInfo::pending(target, self.base_token())),
         })
     }
 
@@ -102,20 +179,33 @@ impl DepositService {
     ///
     /// # Notes
     /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
-    pub async fn create_qris(&self, user_address: &str, amount: f64) -> Result<DepositInfo> {
+    pub async fn create_qris(
+        &self,
+        user_address: &str,
+        amount: f64,
+        target_token: Option<&str>,
+    ) -> Result<DepositInfo> {
         if self.config.moonpay_api_key.is_none() {
             return Err(AppError::ExternalAPI(
                 "Moonpay API key not configured".into(),
             ));
         }
+        let target_token = self.validate_target_token(target_token)?;
 
         let deposit_id = format!("DEP_QRIS_{}", hex::encode(rand::random::<[u8; 16]>()));
         let qr_data = build_qris_payload(&deposit_id, amount);
         let amount_dec =
             rust_decimal::Decimal::from_f64(amount).unwrap_or(rust_decimal::Decimal::ZERO);
 
-        self.save_deposit_with_decimal(&deposit_id, user_address, amount_dec, "IDR", "qris")
-            .await?;
+        self.save_deposit_with_decimal(
+            &deposit_id,
+            user_address,
+            amount_dec,
+            "IDR",
+            "qris",
+            target_token.as_deref(),
+        )
+        .await?;
 
         Ok(DepositInfo {
             deposit_id,
@@ -124,6 +214,7 @@ impl DepositService {
             amount,
             currency: "IDR".to_string(),
             details: Some(serde_json::json!({ "qr_code": qr_data, "expires_in": 300 })),
+            swap: target_token.map(|target| DepositSwapInfo::pending(target, self.base_token())),
         })
     }
 
@@ -143,20 +234,29 @@ impl DepositService {
         user_address: &str,
         amount: f64,
         currency: &str,
+        target_token: Option<&str>,
     ) -> Result<DepositInfo> {
         if self.config.stripe_secret_key.is_none() {
             return Err(AppError::ExternalAPI(
                 "Stripe secret key not configured".into(),
             ));
         }
+        let target_token = self.validate_target_token(target_token)?;
 
         let deposit_id = format!("DEP_CARD_{}", hex::encode(rand::random::<[u8; 16]>()));
         let payment_url = build_stripe_url(&deposit_id);
         let amount_dec =
             rust_decimal::Decimal::from_f64(amount).unwrap_or(rust_decimal::Decimal::ZERO);
 
-        self.save_deposit_with_decimal(&deposit_id, user_address, amount_dec, currency, "card")
-            .await?;
+        self.save_deposit_with_decimal(
+            &deposit_id,
+            user_address,
+            amount_dec,
+            currency,
+            "card",
+            target_token.as_deref(),
+        )
+        .await?;
 
         Ok(DepositInfo {
             deposit_id,
@@ -165,6 +265,7 @@ impl DepositService {
             amount,
             currency: currency.to_string(),
             details: Some(serde_json::json!({ "payment_url": payment_url })),
+            swap: target_token.map(|target| DepositSwapInfo::pending(target, self.base_token())),
         })
     }
 
@@ -176,17 +277,19 @@ impl DepositService {
         amount_dec: rust_decimal::Decimal,
         currency: &str,
         method: &str,
+        target_token: Option<&str>,
     ) -> Result<()> {
         // Ganti query! menjadi query (tanpa tanda seru)
         sqlx::query(
-            "INSERT INTO deposits (deposit_id, user_address, amount, currency, payment_method, status, created_at)
-             VALUES ($1, $2, $3, $4, $5, 'pending', NOW())"
+            "INSERT INTO deposits (deposit_id, user_address, amount, currency, payment_method, status, created_at, target_token, swap_status)
+             VALUES ($1, $2, $3, $4, $5, 'pending', NOW(), $6, CASE WHEN $6::text IS NULL THEN NULL ELSE 'pending' END)"
         )
         .bind(id)
         .bind(user)
         .bind(amount_dec)
         .bind(currency)
         .bind(method)
+        .bind(target_token)
         .execute(self.db.pool())
         .await?;
 
@@ -207,11 +310,28 @@ impl DepositService {
     pub async fn get_status(&self, deposit_id: &str) -> Result<DepositInfo> {
         // Ganti query! menjadi query
         let row = sqlx::query(
-            "SELECT deposit_id, status, payment_method, amount, currency FROM deposits WHERE deposit_id = $1"
+            "SELECT deposit_id, status, payment_method, amount, currency, target_token, swap_status,
+                    swap_tx_hash, swap_payout_tx_hash, swap_amount_out, swap_error
+             FROM deposits WHERE deposit_id = $1"
         )
         .bind(deposit_id)
         .fetch_one(self.db.pool())
         .await?;
+        let swap = row
+            .get::<Option<String>, _>("target_token")
+            .map(|target_token| DepositSwapInfo {
+                target_token,
+                base_token: self.base_token(),
+                status: row
+                    .get::<Option<String>, _>("swap_status")
+                    .unwrap_or_else(|| "pending".to_string()),
+                tx_hash: row.get("swap_tx_hash"),
+                payout_tx_hash: row.get("swap_payout_tx_hash"),
+                amount_out: row
+                    .get::<Option<Decimal>, _>("swap_amount_out")
+                    .and_then(|value| value.to_f64()),
+                error: row.get("swap_error"),
+            });
 
         Ok(DepositInfo {
             deposit_id: row.get("deposit_id"),
@@ -223,8 +343,498 @@ impl DepositService {
                 .unwrap_or(0.0),
             currency: row.get("currency"),
             details: None,
+            swap,
         })
     }
+
+    // Internal helper that supports `base_token` operations.
+    fn base_token(&self) -> String {
+        self.config.deposit_base_token.trim().to_ascii_uppercase()
+    }
+
+    // Internal helper that validates the optional auto-swap target against configured tokens.
+    fn validate_target_token(&self, target_token: Option<&str>) -> Result<Option<String>> {
+        let Some(target) = normalize_target_token(target_token, &self.base_token()) else {
+            return Ok(None);
+        };
+        if !swap_token_configured(&self.config, &target) {
+            return Err(AppError::BadRequest(format!(
+                "target_token {} is not supported for auto-swap",
+                target
+            )));
+        }
+        Ok(Some(target))
+    }
+
+    /// Start the settled-deposit auto-swap loop.
+    pub async fn start_settlement_worker(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(
+                DEPOSIT_SWAP_WORKER_INTERVAL_SECS,
+            ));
+            loop {
+                ticker.tick().await;
+                match self.process_settled_swaps().await {
+                    Ok(0) => {}
+                    Ok(processed) => tracing::info!("Processed {} deposit auto-swaps", processed),
+                    Err(err) => tracing::error!("Deposit auto-swap worker error: {}", err),
+                }
+            }
+        });
+    }
+
+    /// Marks a deposit as settled by the payment provider, making it eligible for auto-swap.
+    ///
+    /// # Arguments
+    /// * `settled_amount` - base stablecoin credited to the backend account; required unless the
+    ///   deposit is in USD, where the fiat amount is used.
+    ///
+    /// # Returns
+    /// * `Ok(DepositInfo)` with the updated deposit.
+    /// * `Err(AppError)` when the deposit is unknown, already settled, or the amount is missing.
+    pub async fn mark_settled(
+        &self,
+        deposit_id: &str,
+        settled_amount: Option<Decimal>,
+    ) -> Result<DepositInfo> {
+        let row =
+            sqlx::query("SELECT amount, currency, status FROM deposits WHERE deposit_id = $1")
+                .bind(deposit_id)
+                .fetch_optional(self.db.pool())
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Deposit {} not found", deposit_id)))?;
+        if row.get::<String, _>("status") != "pending" {
+            return Err(AppError::BadRequest(format!(
+                "Deposit {} is already {}",
+                deposit_id,
+                row.get::<String, _>("status")
+            )));
+        }
+        let settled = settled_stable_amount(
+            &row.get::<String, _>("currency"),
+            row.get("amount"),
+            settled_amount,
+        )
+        .ok_or_else(|| {
+            AppError::BadRequest(
+                "settled_amount (base stablecoin received) is required for non-USD deposits"
+                    .to_string(),
+            )
+        })?;
+
+        sqlx::query(
+            "UPDATE deposits
+             SET status = 'completed', completed_at = NOW(), settled_amount = $2
+             WHERE deposit_id = $1 AND status = 'pending'",
+        )
+        .bind(deposit_id)
+        .bind(settled)
+        .execute(self.db.pool())
+        .await?;
+        self.get_status(deposit_id).await
+    }
+
+    /// Swaps settled deposits that requested a `target_token`.
+    ///
+    /// # Returns
+    /// * `Ok(count)` with the number of deposits that reached a terminal swap status.
+    ///
+    /// # Notes
+    /// * Settlement (`status = 'completed'`) means the base stablecoin landed in the backend
+    ///   account; this worker swaps it and forwards the received output to the depositor.
+    /// * Every transaction hash is stored before its receipt is awaited (`submitting` for the
+    ///   swap, `paying` for the payout). Rows left in those states are reconciled from their
+    ///   receipts on later ticks instead of being resent.
+    /// * A reverted swap forwards the base stablecoin instead (`swap_status = 'fallback'`); a
+    ///   reverted payout is marked `failed` for a manual check.
+    pub async fn process_settled_swaps(&self) -> Result<usize> {
+        let mut processed = self.reconcile_in_flight_swaps().await?;
+
+        let rows = sqlx::query(
+            "SELECT deposit_id, user_address, amount, currency, target_token, settled_amount
+             FROM deposits
+             WHERE status = 'completed' AND swap_status = 'pending' AND target_token IS NOT NULL
+             ORDER BY completed_at ASC NULLS FIRST
+             LIMIT $1",
+        )
+        .bind(DEPOSIT_SWAP_BATCH_SIZE)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        for row in rows {
+            let deposit_id: String = row.get("deposit_id");
+            let claimed = sqlx::query(
+                "UPDATE deposits SET swap_status = 'processing', swap_updated_at = NOW()
+                 WHERE deposit_id = $1 AND swap_status = 'pending'",
+            )
+            .bind(&deposit_id)
+            .execute(self.db.pool())
+            .await?;
+            if claimed.rows_affected() == 0 {
+                continue;
+            }
+
+            let Some(swap) = SettledSwap::from_row(&row) else {
+                self.update_swap_state(
+                    &deposit_id,
+                    "failed",
+                    None,
+                    Some("settled stablecoin amount is unknown; set deposits.settled_amount"),
+                )
+                .await?;
+                processed += 1;
+                continue;
+            };
+            if self.settle_swap(&swap).await? {
+                processed += 1;
+            }
+        }
+        Ok(processed)
+    }
+
+    // Internal helper that resumes swaps whose swap or payout transaction was sent on an
+    // earlier tick. Rows are claimed by bumping `swap_updated_at` so replicas don't race.
+    async fn reconcile_in_flight_swaps(&self) -> Result<usize> {
+        let rows = sqlx::query(
+            "SELECT deposit_id, user_address, amount, currency, target_token, settled_amount,
+                    swap_status, swap_tx_hash, swap_payout_tx_hash, swap_amount_out,
+                    swap_updated_at
+             FROM deposits
+             WHERE swap_status IN ('submitting', 'paying') AND target_token IS NOT NULL
+               AND swap_updated_at < NOW() - make_interval(secs => $2)
+             ORDER BY swap_updated_at ASC
+             LIMIT $1",
+        )
+        .bind(DEPOSIT_SWAP_BATCH_SIZE)
+        .bind(DEPOSIT_SWAP_RECONCILE_AFTER_SECS as f64)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut processed = 0;
+        for row in rows {
+            let deposit_id: String = row.get("deposit_id");
+            let status: String = row.get("swap_status");
+            let claimed = sqlx::query(
+                "UPDATE deposits SET swap_updated_at = NOW()
+                 WHERE deposit_id = $1 AND swap_status = $2 AND swap_updated_at = $3",
+            )
+            .bind(&deposit_id)
+            .bind(&status)
+            .bind(row.get::<chrono::DateTime<chrono::Utc>, _>("swap_updated_at"))
+            .execute(self.db.pool())
+            .await?;
+            if claimed.rows_affected() == 0 {
+                continue;
+            }
+            let Some(swap) = SettledSwap::from_row(&row) else {
+                continue;
+            };
+
+            let done = match (
+                status.as_str(),
+                row.get::<Option<String>, _>("swap_tx_hash"),
+                row.get::<Option<String>, _>("swap_payout_tx_hash"),
+            ) {
+                ("submitting", Some(tx_hash), _) => self.confirm_swap(&swap, &tx_hash).await?,
+                ("paying", _, Some(payout_hash)) => {
+                    let amount_out: Option<Decimal> = row.get("swap_amount_out");
+                    self.confirm_payout(&swap, &payout_hash, amount_out).await?
+                }
+                _ => {
+                    self.update_swap_state(
+                        &deposit_id,
+                        "failed",
+                        None,
+                        Some("in-flight swap has no stored tx hash; check on-chain manually"),
+                    )
+                    .await?;
+                    true
+                }
+            };
+            if done {
+                processed += 1;
+            }
+        }
+        Ok(processed)
+    }
+
+    // Internal helper that sends the swap for one settled deposit, falling back to the base
+    // stablecoin when it cannot be sent. Returns whether the deposit reached a terminal status.
+    async fn settle_swap(&self, swap: &SettledSwap) -> Result<bool> {
+        let base_token = self.base_token();
+        let sent = match build_relayed_swap_calls(
+            &self.config,
+            &base_token,
+            &swap.target_token,
+            &swap.amount_text(&base_token),
+        )
+        .await
+        {
+            Ok(calls) => match RelayerService::from_config(&self.config) {
+                Ok(relayer) => relayer.send_calls(calls).await,
+                Err(err) => Err(err),
+            },
+            Err(err) => Err(err),
+        };
+        match sent {
+            Ok(tx_hash) => {
+                self.update_swap_state(&swap.deposit_id, "submitting", Some(&tx_hash), None)
+                    .await?;
+                self.confirm_swap(swap, &tx_hash).await
+            }
+            Err(err) => {
+                self.pay_out_fallback(swap, &err.to_string(), "pending")
+                    .await
+            }
+        }
+    }
+
+    // Internal helper that waits for the swap receipt and forwards the received output.
+    // Leaves the row in `submitting` while the receipt is unavailable.
+    async fn confirm_swap(&self, swap: &SettledSwap, tx_hash: &str) -> Result<bool> {
+        let relayer = RelayerService::from_config(&self.config)?;
+        let result = match relayer.confirm(tx_hash).await {
+            Ok(result) => result,
+            Err(err) if relayer_revert_reason(&err).is_some() => {
+                return self
+                    .pay_out_fallback(swap, &err.to_string(), "submitting")
+                    .await;
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Deposit {} swap {} not confirmed yet, reconciling later: {}",
+                    swap.deposit_id,
+                    tx_hash,
+                    err
+                );
+                return Ok(false);
+            }
+        };
+
+        let backend = resolve_backend_account(&self.config)
+            .ok_or_else(|| {
+                AppError::BadRequest("BACKEND_ACCOUNT_ADDRESS is not configured".to_string())
+            })
+            .and_then(parse_felt)?;
+        let received = swap_token_addresses(&self.config, &swap.target_token)
+            .into_iter()
+            .find_map(|token| {
+                erc20_received_amount(&result.events, token, backend)
+                    .ok()
+                    .filter(|amount| *amount > 0)
+                    .map(|amount| (token, amount))
+            });
+        let Some((token_address, amount)) = received else {
+            self.update_swap_state(
+                &swap.deposit_id,
+                "failed",
+                None,
+                Some("swap confirmed but no target token was received; check swap_tx_hash"),
+            )
+            .await?;
+            return Ok(true);
+        };
+        let amount_out =
+            Decimal::try_from_i128_with_scale(amount as i128, token_decimals(&swap.target_token))
+                .ok();
+        let call = build_raw_token_transfer_call(token_address, &swap.user_address, amount);
+        self.pay_out(swap, call, amount_out, None, "submitting")
+            .await
+    }
+
+    // Internal helper that forwards the base stablecoin after the swap could not complete.
+    async fn pay_out_fallback(
+        &self,
+        swap: &SettledSwap,
+        swap_error: &str,
+        retry_status: &str,
+    ) -> Result<bool> {
+        let base_token = self.base_token();
+        tracing::warn!(
+            "Deposit {} auto-swap {} -> {} failed, crediting base stablecoin: {}",
+            swap.deposit_id,
+            base_token,
+            swap.target_token,
+            swap_error
+        );
+        let call = build_token_transfer_call(
+            &self.config,
+            &base_token,
+            &swap.user_address,
+            &swap.amount_text(&base_token),
+        );
+        self.pay_out(swap, call, None, Some(swap_error), retry_status)
+            .await
+    }
+
+    // Internal helper that sends a payout transfer and waits for it. If the transfer cannot
+    // be sent, the row returns to `retry_status` so the step before it is redone next tick.
+    async fn pay_out(
+        &self,
+        swap: &SettledSwap,
+        call: Result<Call>,
+        amount_out: Option<Decimal>,
+        swap_error: Option<&str>,
+        retry_status: &str,
+    ) -> Result<bool> {
+        let sent = match call {
+            Ok(call) => match RelayerService::from_config(&self.config) {
+                Ok(relayer) => relayer.send_calls(vec![call]).await,
+                Err(err) => Err(err),
+            },
+            Err(err) => Err(err),
+        };
+        match sent {
+            Ok(payout_hash) => {
+                sqlx::query(
+                    "UPDATE deposits
+                     SET swap_status = 'paying',
+                         swap_payout_tx_hash = $2,
+                         swap_amount_out = $3,
+                         swap_error = $4,
+                         swap_updated_at = NOW()
+                     WHERE deposit_id = $1",
+                )
+                .bind(&swap.deposit_id)
+                .bind(&payout_hash)
+                .bind(amount_out)
+                .bind(swap_error)
+                .execute(self.db.pool())
+                .await?;
+                self.confirm_payout(swap, &payout_hash, amount_out).await
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Deposit {} payout could not be sent, retrying next tick: {}",
+                    swap.deposit_id,
+                    err
+                );
+                let error = match swap_error {
+                    Some(swap_error) => format!("swap: {}; payout: {}", swap_error, err),
+                    None => format!("payout: {}", err),
+                };
+                self.update_swap_state(&swap.deposit_id, retry_status, None, Some(&error))
+                    .await?;
+                Ok(false)
+            }
+        }
+    }
+
+    // Internal helper that waits for the payout receipt and finalizes the deposit.
+    // `amount_out` is set when the swap succeeded; otherwise the payout was the fallback.
+    async fn confirm_payout(
+        &self,
+        swap: &SettledSwap,
+        payout_hash: &str,
+        amount_out: Option<Decimal>,
+    ) -> Result<bool> {
+        let relayer = RelayerService::from_config(&self.config)?;
+        match relayer.confirm(payout_hash).await {
+            Ok(_) => {}
+            Err(err) if relayer_revert_reason(&err).is_some() => {
+                tracing::error!(
+                    "Deposit {} payout {} reverted: {}",
+                    swap.deposit_id,
+                    payout_hash,
+                    err
+                );
+                self.update_swap_state(
+                    &swap.deposit_id,
+                    "failed",
+                    None,
+                    Some(&format!("payout transfer reverted: {}", err)),
+                )
+                .await?;
+                return Ok(true);
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Deposit {} payout {} not confirmed yet, reconciling later: {}",
+                    swap.deposit_id,
+                    payout_hash,
+                    err
+                );
+                return Ok(false);
+            }
+        }
+
+        let base_token = self.base_token();
+        let (status, message) = match amount_out {
+            Some(amount_out) => (
+                "completed",
+                format!(
+                    "Deposit {} swapped from {} {} to {} {}",
+                    swap.deposit_id,
+                    swap.amount_text(&base_token),
+                    base_token,
+                    amount_out.normalize(),
+                    swap.target_token
+                ),
+            ),
+            None => (
+                "fallback",
+                format!(
+                    "Deposit {} credited as {} {}; swap to {} failed",
+                    swap.deposit_id,
+                    swap.amount_text(&base_token),
+                    base_token,
+                    swap.target_token
+                ),
+            ),
+        };
+        sqlx::query(
+            "UPDATE deposits SET swap_status = $2, swap_updated_at = NOW() WHERE deposit_id = $1",
+        )
+        .bind(&swap.deposit_id)
+        .bind(status)
+        .execute(self.db.pool())
+        .await?;
+        self.notify_swap(&swap.user_address, &message, payout_hash)
+            .await;
+        Ok(true)
+    }
+
+    // Internal helper that updates state for `update_swap_state`.
+    async fn update_swap_state(
+        &self,
+        deposit_id: &str,
+        status: &str,
+        tx_hash: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE deposits
+             SET swap_status = $2,
+                 swap_tx_hash = COALESCE($3, swap_tx_hash),
+                 swap_error = $4,
+                 swap_updated_at = NOW()
+             WHERE deposit_id = $1",
+        )
+        .bind(deposit_id)
+        .bind(status)
+        .bind(tx_hash)
+        .bind(error)
+        .execute(self.db.pool())
+        .await?;
+        Ok(())
+    }
+
+    // Internal helper that notifies the depositor about the auto-swap outcome.
+    async fn notify_swap(&self, user_address: &str, message: &str, tx_hash: &str) {
+        let _ = self
+            .db
+            .create_notification(
+                user_address,
+                "deposit.swap",
+                "Deposit settled",
+                message,
+                Some(serde_json::json!({
+                    "tx_hash": tx_hash,
+                    "tx_network": "starknet"
+                })),
+            )
+            .await;
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -235,6 +845,37 @@ pub struct DepositInfo {
     pub amount: f64,
     pub currency: String,
     pub details: Option<serde_json::Value>,
+    pub swap: Option<DepositSwapInfo>,
+}
+
+/// Auto-swap leg of a deposit created with a `target_token`.
+#[derive(Debug, Serialize)]
+pub struct DepositSwapInfo {
+    pub target_token: String,
+    pub base_token: String,
+    /// `pending`, `processing`, `submitting` (swap sent), `paying` (payout sent), `completed`,
+    /// `fallback` (stablecoin credited) or `failed`.
+    pub status: String,
+    pub tx_hash: Option<String>,
+    /// Transfer that forwarded the swap output (or the fallback stablecoin) to the depositor.
+    pub payout_tx_hash: Option<String>,
+    pub amount_out: Option<f64>,
+    pub error: Option<String>,
+}
+
+impl DepositSwapInfo {
+    // Internal helper that supports `pending` operations.
+    fn pending(target_token: String, base_token: String) -> Self {
+        Self {
+            target_token,
+            base_token,
+            status: "pending".to_string(),
+            tx_hash: None,
+            payout_tx_hash: None,
+            amount_out: None,
+            error: None,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -273,4 +914,33 @@ mod tests {
         let url = build_stripe_url("DEP_CARD_TEST");
         assert_eq!(url, "https://checkout.stripe.comDEP_CARD_TEST");
     }
+
+    #[test]
+    // Internal helper that supports `normalize_target_token_skips_base_and_blank` operations.
+    fn normalize_target_token_skips_base_and_blank() {
+        assert_eq!(normalize_target_token(None, "USDC"), None);
+        assert_eq!(normalize_target_token(Some("  "), "USDC"), None);
+        assert_eq!(normalize_target_token(Some("usdc"), "USDC"), None);
+        assert_eq!(
+            normalize_target_token(Some(" strk "), "USDC"),
+            Some("STRK".to_string())
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `settled_stable_amount_falls_back_for_usd_only` operations.
+    fn settled_stable_amount_falls_back_for_usd_only() {
+        let amount = Decimal::new(1500, 2);
+        let settled = Decimal::new(1499, 2);
+        assert_eq!(
+            settled_stable_amount("IDR", amount, Some(settled)),
+            Some(settled)
+        );
+        assert_eq!(settled_stable_amount("usd", amount, None), Some(amount));
+        assert_eq!(settled_stable_amount("IDR", amount, None), None);
+        assert_eq!(
+            settled_stable_amount("USD", amount, Some(Decimal::ZERO)),
+            None
+        );
+    }
 }
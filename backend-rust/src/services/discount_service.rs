@@ -0,0 +1,626 @@
+use crate::{
+    config::Config,
+    db::{Database, NftDiscountStateUpsert},
+    error::{AppError, Result},
+    services::nft_discount::consume_nft_usage,
+    services::onchain::{felt_to_u128, parse_felt, u256_from_felts, OnchainReader},
+    services::staking_discount::{combine_fee_discounts, staked_carel_discount_percent},
+};
+use serde::Serialize;
+use starknet_core::types::{Felt, FunctionCall};
+use starknet_core::utils::{get_selector_from_name, get_storage_var_address};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use tokio::time::{timeout, Duration};
+
+const ONCHAIN_DISCOUNT_TIMEOUT_MS: u64 = 2_500;
+const NFT_DISCOUNT_CACHE_TTL_SECS: u64 = 300;
+const NFT_DISCOUNT_CACHE_STALE_SECS: u64 = 1_800;
+const NFT_DISCOUNT_CACHE_MAX_ENTRIES: usize = 100_000;
+const AI_LEVEL_2_POINTS_BONUS_PERCENT: f64 = 20.0;
+const AI_LEVEL_3_POINTS_BONUS_PERCENT: f64 = 40.0;
+
+#[derive(Clone, Copy)]
+struct CachedNftDiscount {
+    fetched_at: Instant,
+    discount: f64,
+}
+
+static NFT_DISCOUNT_CACHE: OnceLock<tokio::sync::RwLock<HashMap<String, CachedNftDiscount>>> =
+    OnceLock::new();
+// Cache keys with a background quote-path refresh in flight.
+static NFT_DISCOUNT_REFRESHING: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, Default)]
+struct NftUsageSnapshot {
+    tier: i32,
+    discount_percent: f64,
+    max_usage: u128,
+    used_in_period: u128,
+}
+
+/// One contributor to the consolidated fee discount.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DiscountSource {
+    pub source: String,
+    pub percent: f64,
+}
+
+/// Discounts and point boosts that apply to a user's next fee-bearing action.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiscountProfile {
+    pub nft_discount_percent: f64,
+    pub stake_discount_percent: f64,
+    /// NFT + staking discount, capped at `MAX_COMBINED_FEE_DISCOUNT_PERCENT`.
+    pub fee_discount_percent: f64,
+    pub ai_points_bonus_percent: f64,
+    /// Factor applied to base points (NFT boost times AI-level bonus).
+    pub points_multiplier: f64,
+}
+
+impl DiscountProfile {
+    // Internal helper that assembles a profile from its resolved parts.
+    fn build(nft_percent: f64, stake_percent: f64, max_combined: f64, ai_level: u8) -> Self {
+        let nft_discount_percent = nft_percent.clamp(0.0, 100.0);
+        let stake_discount_percent = stake_percent.clamp(0.0, 100.0);
+        Self {
+            nft_discount_percent,
+            stake_discount_percent,
+            fee_discount_percent: combine_fee_discounts(
+                nft_discount_percent,
+                stake_discount_percent,
+                max_combined,
+            ),
+            ai_points_bonus_percent: ai_level_points_bonus_percent(ai_level),
+            points_multiplier: points_multiplier(nft_discount_percent, ai_level),
+        }
+    }
+
+    /// Non-zero fee discount contributors, in application order.
+    pub fn sources(&self) -> Vec<DiscountSource> {
+        [
+            ("nft", self.nft_discount_percent),
+            ("staked_carel", self.stake_discount_percent),
+        ]
+        .into_iter()
+        .filter(|(_, percent)| *percent > 0.0)
+        .map(|(source, percent)| DiscountSource {
+            source: source.to_string(),
+            percent,
+        })
+        .collect()
+    }
+}
+
+/// Points bonus (percent) unlocked by the user's AI level.
+pub fn ai_level_points_bonus_percent(level: u8) -> f64 {
+    match level {
+        2 => AI_LEVEL_2_POINTS_BONUS_PERCENT,
+        3 => AI_LEVEL_3_POINTS_BONUS_PERCENT,
+        _ => 0.0,
+    }
+}
+
+/// Combined points factor for an NFT discount and AI level.
+pub fn points_multiplier(nft_discount_percent: f64, ai_level: u8) -> f64 {
+    let nft_factor = 1.0 + (nft_discount_percent.clamp(0.0, 100.0) / 100.0);
+    let ai_factor = 1.0 + (ai_level_points_bonus_percent(ai_level) / 100.0);
+    nft_factor * ai_factor
+}
+
+// Internal helper that supports `nft_discount_cache` operations.
+fn nft_discount_cache() -> &'static tokio::sync::RwLock<HashMap<String, CachedNftDiscount>> {
+    NFT_DISCOUNT_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()))
+}
+
+// Internal helper that supports `nft_discount_cache_key` operations.
+fn nft_discount_cache_key(contract: &str, user: &str) -> String {
+    format!(
+        "{}|{}",
+        contract.trim().to_ascii_lowercase(),
+        user.trim().to_ascii_lowercase()
+    )
+}
+
+// Internal helper that fetches data for `get_cached_nft_discount`.
+async fn get_cached_nft_discount(key: &str, max_age: Duration) -> Option<f64> {
+    let guard = nft_discount_cache().read().await;
+    let entry = guard.get(key)?;
+    if entry.fetched_at.elapsed() <= max_age {
+        return Some(entry.discount);
+    }
+    None
+}
+
+// Internal helper that supports `cache_nft_discount` operations.
+async fn cache_nft_discount(key: &str, discount: f64) {
+    let mut guard = nft_discount_cache().write().await;
+    guard.insert(
+        key.to_string(),
+        CachedNftDiscount {
+            fetched_at: Instant::now(),
+            discount,
+        },
+    );
+    if guard.len() > NFT_DISCOUNT_CACHE_MAX_ENTRIES {
+        let stale_after = Duration::from_secs(NFT_DISCOUNT_CACHE_STALE_SECS);
+        guard.retain(|_, entry| entry.fetched_at.elapsed() <= stale_after);
+    }
+}
+
+// Internal helper that supports `u128_to_i64_saturating` operations.
+fn u128_to_i64_saturating(value: u128) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+// Internal helper that fetches data for `read_nft_usage_snapshot`.
+async fn read_nft_usage_snapshot(
+    reader: &OnchainReader,
+    contract_address: Felt,
+    user_felt: Felt,
+) -> Result<Option<NftUsageSnapshot>> {
+    let storage_key = get_storage_var_address("user_nft", &[user_felt])
+        .map_err(|e| AppError::Internal(format!("Storage key resolution error: {}", e)))?;
+    let token_raw = reader.get_storage_at(contract_address, storage_key).await?;
+    let token_id = felt_to_u128(&token_raw).unwrap_or(0);
+    if token_id == 0 {
+        return Ok(None);
+    }
+
+    let info_call = FunctionCall {
+        contract_address,
+        entry_point_selector: get_selector_from_name("get_nft_info")
+            .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?,
+        calldata: vec![Felt::from(token_id), Felt::from(0_u8)],
+    };
+    let info = reader.call(info_call).await?;
+    if info.len() < 7 {
+        return Ok(None);
+    }
+
+    let tier = felt_to_u128(&info[0]).unwrap_or(0) as i32;
+    let discount = u256_from_felts(&info[1], &info[2]).unwrap_or(0) as f64;
+    let max_usage = u256_from_felts(&info[3], &info[4]).unwrap_or(0);
+    let used_in_period = u256_from_felts(&info[5], &info[6]).unwrap_or(0);
+    Ok(Some(NftUsageSnapshot {
+        tier: tier.max(0),
+        discount_percent: discount.clamp(0.0, 100.0),
+        max_usage,
+        used_in_period,
+    }))
+}
+
+/// Resolves fee discounts and point boosts from on-chain state, the local NFT usage
+/// ledger, and in-process caches, so every fee-bearing flow applies the same rules.
+#[derive(Clone)]
+pub struct DiscountService {
+    db: Database,
+    config: Config,
+}
+
+impl DiscountService {
+    /// Constructs a new instance via `new`.
+    pub fn new(db: Database, config: Config) -> Self {
+        Self { db, config }
+    }
+
+    // Internal helper that supports `discount_contract_address` operations.
+    fn discount_contract_address(&self) -> Option<&str> {
+        self.config
+            .discount_soulbound_address
+            .as_deref()
+            .filter(|addr| !addr.trim().is_empty() && !addr.starts_with("0x0000"))
+    }
+
+    /// Resolves the discount profile for a submit in `action` (e.g. `swap`, `bridge`).
+    ///
+    /// NFT and staking discounts are read for `discount_owner` (skipped when `None`, e.g.
+    /// non-Starknet bridge users); the AI-level points bonus is read for `user_address`.
+    pub async fn resolve_for_submit(
+        &self,
+        action: &str,
+        user_address: &str,
+        discount_owner: Option<&str>,
+    ) -> DiscountProfile {
+        let (nft_percent, stake_percent) = match discount_owner {
+            Some(owner) => (
+                self.refresh_nft_discount(action, owner).await,
+                staked_carel_discount_percent(&self.config, owner).await,
+            ),
+            None => (0.0, 0.0),
+        };
+        let ai_level = self.ai_level(action, user_address).await;
+        DiscountProfile::build(
+            nft_percent,
+            stake_percent,
+            self.config.max_combined_fee_discount_percent,
+            ai_level,
+        )
+    }
+
+    /// Resolves the discount profile for a quote in `action` without touching the hot path
+    /// with chain reads or ledger writes.
+    ///
+    /// # Notes
+    /// * The NFT discount comes from the in-process cache or the local usage ledger; on a
+    ///   cache miss the on-chain refresh (and its ledger upsert) runs in the background.
+    pub async fn resolve_for_quote(
+        &self,
+        action: &'static str,
+        user_address: &str,
+    ) -> DiscountProfile {
+        let nft_percent = self.quote_nft_discount(action, user_address).await;
+        let stake_percent = staked_carel_discount_percent(&self.config, user_address).await;
+        let ai_level = self.ai_level(action, user_address).await;
+        DiscountProfile::build(
+            nft_percent,
+            stake_percent,
+            self.config.max_combined_fee_discount_percent,
+            ai_level,
+        )
+    }
+
+    // Internal helper that reads the AI level for the points bonus, defaulting to level 1.
+    async fn ai_level(&self, action: &str, user_address: &str) -> u8 {
+        match self.db.get_user_ai_level(user_address).await {
+            Ok(level) => level,
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to resolve user AI level for {} points bonus (user={}): {}",
+                    action,
+                    user_address,
+                    err
+                );
+                1
+            }
+        }
+    }
+
+    // Internal helper that answers the quote-path NFT discount from local state and
+    // schedules at most one background on-chain refresh per user on a cache miss.
+    async fn quote_nft_discount(&self, action: &'static str, user_address: &str) -> f64 {
+        let Some(contract) = self.discount_contract_address() else {
+            return 0.0;
+        };
+        let cache_key = nft_discount_cache_key(contract, user_address);
+        if let Some(cached) =
+            get_cached_nft_discount(&cache_key, Duration::from_secs(NFT_DISCOUNT_CACHE_TTL_SECS))
+                .await
+        {
+            return cached.max(0.0);
+        }
+        let scheduled = NFT_DISCOUNT_REFRESHING
+            .get_or_init(|| Mutex::new(HashSet::new()))
+            .lock()
+            .map(|mut inflight| inflight.insert(cache_key.clone()))
+            .unwrap_or(false);
+        if scheduled {
+            let service = self.clone();
+            let user = user_address.to_string();
+            tokio::spawn(async move {
+                service.refresh_nft_discount(action, &user).await;
+                if let Some(inflight) = NFT_DISCOUNT_REFRESHING.get() {
+                    if let Ok(mut inflight) = inflight.lock() {
+                        inflight.remove(&cache_key);
+                    }
+                }
+            });
+        }
+        self.cached_nft_discount_from_local_state(action, user_address)
+            .await
+    }
+
+    /// Records one NFT discount use after a successful `action` submit: bumps the local
+    /// usage ledger, drops the cached discount, and consumes the on-chain usage.
+    pub async fn record_nft_usage(&self, action: &str, user_address: &str, tx_hash: &str) {
+        let Some(contract) = self.discount_contract_address() else {
+            return;
+        };
+        let period_epoch = self.config.current_nft_period_epoch();
+        match self
+            .db
+            .increment_nft_discount_local_usage(contract, user_address, period_epoch, 1)
+            .await
+        {
+            Ok(updated_usage) => {
+                tracing::debug!(
+                    "Recorded local NFT usage after {} submit user={} period={} local_used={}",
+                    action,
+                    user_address,
+                    period_epoch,
+                    updated_usage
+                );
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed recording local NFT usage after {} submit for user={}: {}",
+                    action,
+                    user_address,
+                    err
+                );
+            }
+        }
+        self.invalidate(user_address).await;
+        if let Err(err) = consume_nft_usage(&self.config, user_address, action).await {
+            tracing::warn!(
+                "Failed to consume NFT discount usage after {} success: user={} tx_hash={} err={}",
+                action,
+                user_address,
+                tx_hash,
+                err
+            );
+        }
+    }
+
+    /// Drops the cached NFT discount for `user_address`.
+    pub async fn invalidate(&self, user_address: &str) {
+        let Some(contract) = self.discount_contract_address() else {
+            return;
+        };
+        let key = nft_discount_cache_key(contract, user_address);
+        nft_discount_cache().write().await.remove(&key);
+    }
+
+    // Internal helper that reads the NFT discount from cache or the local usage ledger.
+    async fn cached_nft_discount_from_local_state(&self, action: &str, user_address: &str) -> f64 {
+        let Some(contract) = self.discount_contract_address() else {
+            return 0.0;
+        };
+        let cache_key = nft_discount_cache_key(contract, user_address);
+        if let Some(cached) =
+            get_cached_nft_discount(&cache_key, Duration::from_secs(NFT_DISCOUNT_CACHE_TTL_SECS))
+                .await
+        {
+            return cached.max(0.0);
+        }
+
+        let period_epoch = self.config.current_nft_period_epoch();
+        match self
+            .db
+            .get_nft_discount_state(contract, user_address, period_epoch)
+            .await
+        {
+            Ok(Some(row)) => {
+                let age_secs = chrono::Utc::now()
+                    .signed_duration_since(row.updated_at)
+                    .num_seconds()
+                    .max(0) as u64;
+                if age_secs > NFT_DISCOUNT_CACHE_STALE_SECS {
+                    return 0.0;
+                }
+                let effective_used = row.local_used_in_period.max(row.chain_used_in_period);
+                let has_remaining_usage = row.max_usage > 0 && effective_used < row.max_usage;
+                let discount = if row.is_active && has_remaining_usage {
+                    row.discount_percent.clamp(0.0, 100.0)
+                } else {
+                    0.0
+                };
+                cache_nft_discount(&cache_key, discount).await;
+                discount
+            }
+            Ok(None) => 0.0,
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to read local NFT discount state in {} for user={}: {}",
+                    action,
+                    user_address,
+                    err
+                );
+                0.0
+            }
+        }
+    }
+
+    // Internal helper that re-validates the NFT discount on-chain at submit time,
+    // falling back to the local ledger when the chain read fails.
+    async fn refresh_nft_discount(&self, action: &str, user_address: &str) -> f64 {
+        let Some(contract) = self.discount_contract_address() else {
+            return 0.0;
+        };
+        let cache_key = nft_discount_cache_key(contract, user_address);
+        let period_epoch = self.config.current_nft_period_epoch();
+
+        let reader = match OnchainReader::from_config(&self.config) {
+            Ok(reader) => reader,
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to initialize on-chain reader for NFT discount submit validation in {}: {}",
+                    action,
+                    err
+                );
+                return self
+                    .cached_nft_discount_from_local_state(action, user_address)
+                    .await;
+            }
+        };
+        let (contract_address, user_felt) = match (parse_felt(contract), parse_felt(user_address)) {
+            (Ok(contract_address), Ok(user_felt)) => (contract_address, user_felt),
+            (Err(err), _) | (_, Err(err)) => {
+                tracing::warn!(
+                    "Invalid address while validating {} fee discount: user={}, err={}",
+                    action,
+                    user_address,
+                    err
+                );
+                return self
+                    .cached_nft_discount_from_local_state(action, user_address)
+                    .await;
+            }
+        };
+        let selector = match get_selector_from_name("has_active_discount") {
+            Ok(value) => value,
+            Err(err) => {
+                tracing::warn!(
+                    "Selector resolution failed for has_active_discount in {} submit validation: {}",
+                    action,
+                    err
+                );
+                return self
+                    .cached_nft_discount_from_local_state(action, user_address)
+                    .await;
+            }
+        };
+
+        let call = FunctionCall {
+            contract_address,
+            entry_point_selector: selector,
+            calldata: vec![user_felt],
+        };
+        let result = match timeout(
+            Duration::from_millis(ONCHAIN_DISCOUNT_TIMEOUT_MS),
+            reader.call(call),
+        )
+        .await
+        {
+            Ok(Ok(value)) if value.len() >= 3 => value,
+            Ok(Ok(_)) => {
+                tracing::warn!(
+                    "NFT discount submit validation in {} returned malformed payload for user={}",
+                    action,
+                    user_address
+                );
+                return self
+                    .cached_nft_discount_from_local_state(action, user_address)
+                    .await;
+            }
+            Ok(Err(err)) => {
+                tracing::warn!(
+                    "Failed on-chain NFT discount submit validation in {} for user={}: {}",
+                    action,
+                    user_address,
+                    err
+                );
+                return self
+                    .cached_nft_discount_from_local_state(action, user_address)
+                    .await;
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "Timeout on-chain NFT discount submit validation in {} for user={}",
+                    action,
+                    user_address
+                );
+                return self
+                    .cached_nft_discount_from_local_state(action, user_address)
+                    .await;
+            }
+        };
+
+        let chain_active = felt_to_u128(&result[0]).unwrap_or(0) > 0;
+        let chain_discount = u256_from_felts(&result[1], &result[2]).unwrap_or(0) as f64;
+
+        let usage_snapshot = match timeout(
+            Duration::from_millis(ONCHAIN_DISCOUNT_TIMEOUT_MS),
+            read_nft_usage_snapshot(&reader, contract_address, user_felt),
+        )
+        .await
+        {
+            Ok(Ok(value)) => value.unwrap_or_default(),
+            Ok(Err(err)) => {
+                tracing::warn!(
+                    "Failed to read NFT usage snapshot in {} submit validation for user={}: {}",
+                    action,
+                    user_address,
+                    err
+                );
+                return self
+                    .cached_nft_discount_from_local_state(action, user_address)
+                    .await;
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "Timeout reading NFT usage snapshot in {} submit validation for user={}",
+                    action,
+                    user_address
+                );
+                return self
+                    .cached_nft_discount_from_local_state(action, user_address)
+                    .await;
+            }
+        };
+
+        let discount_percent = if chain_discount > 0.0 {
+            chain_discount
+        } else {
+            usage_snapshot.discount_percent
+        }
+        .clamp(0.0, 100.0);
+
+        let db_row = self
+            .db
+            .upsert_nft_discount_state_from_chain(NftDiscountStateUpsert {
+                contract_address: contract,
+                user_address,
+                period_epoch,
+                tier: usage_snapshot.tier.max(0),
+                discount_percent,
+                is_active: chain_active,
+                max_usage: u128_to_i64_saturating(usage_snapshot.max_usage),
+                chain_used_in_period: u128_to_i64_saturating(usage_snapshot.used_in_period),
+            })
+            .await;
+
+        let resolved_discount = match db_row {
+            Ok(row) => {
+                let effective_used = row.local_used_in_period.max(row.chain_used_in_period);
+                let has_remaining_usage = row.max_usage > 0 && effective_used < row.max_usage;
+                if row.is_active && has_remaining_usage {
+                    row.discount_percent.clamp(0.0, 100.0)
+                } else {
+                    0.0
+                }
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to persist NFT discount state in {} for user={}: {}",
+                    action,
+                    user_address,
+                    err
+                );
+                let has_remaining_usage = usage_snapshot.max_usage > 0
+                    && usage_snapshot.used_in_period < usage_snapshot.max_usage;
+                if chain_active && has_remaining_usage {
+                    discount_percent
+                } else {
+                    0.0
+                }
+            }
+        };
+
+        cache_nft_discount(&cache_key, resolved_discount).await;
+        resolved_discount
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `discount_profile_caps_fee_and_lists_sources` operations.
+    fn discount_profile_caps_fee_and_lists_sources() {
+        let profile = DiscountProfile::build(40.0, 20.0, 50.0, 3);
+        assert_eq!(profile.fee_discount_percent, 50.0);
+        assert_eq!(profile.ai_points_bonus_percent, 40.0);
+        assert!((profile.points_multiplier - 1.4 * 1.4).abs() < 1e-9);
+        assert_eq!(
+            profile.sources(),
+            vec![
+                DiscountSource {
+                    source: "nft".to_string(),
+                    percent: 40.0,
+                },
+                DiscountSource {
+                    source: "staked_carel".to_string(),
+                    percent: 20.0,
+                },
+            ]
+        );
+
+        let none = DiscountProfile::build(0.0, 0.0, 50.0, 1);
+        assert_eq!(none.fee_discount_percent, 0.0);
+        assert_eq!(none.points_multiplier, 1.0);
+        assert!(none.sources().is_empty());
+    }
+}
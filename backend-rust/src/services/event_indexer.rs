@@ -6,6 +6,7 @@ use crate::{
     indexer::{
         block_processor::BlockProcessor, event_parser::EventParser, starknet_client::StarknetClient,
     },
+    models::OrderStatus,
 };
 use std::sync::Arc;
 use tokio::time::{interval, sleep, Duration};
@@ -393,6 +394,7 @@ impl EventIndexer {
             "Unstake" => self.handle_unstake_event(event, block_number).await?,
             "Claim" => self.handle_claim_event(event, block_number).await?,
             "LimitOrderFilled" => self.handle_order_filled(event, block_number).await?,
+            "ReferralPayout" => self.handle_referral_payout(event, block_number).await?,
             _ => {
                 tracing::debug!("Unknown event type: {}", event.event_type);
             }
@@ -458,17 +460,27 @@ impl EventIndexer {
             .get("user")
             .and_then(|v| v.as_str())
             .unwrap_or("");
+        // Bridge events rarely carry pricing; the API submission row keeps its values via COALESCE.
+        let usd_value = event
+            .data
+            .get("amount_usd")
+            .and_then(|v| v.as_f64())
+            .and_then(rust_decimal::Decimal::from_f64_retain);
 
         let tx = crate::models::Transaction {
             tx_hash: event.tx_hash,
             block_number: block_number as i64,
             user_address: user.to_string(),
             tx_type: "bridge".to_string(),
-            token_in: None,
+            token_in: event
+                .data
+                .get("token_in")
+                .and_then(|v| v.as_str())
+                .map(String::from),
             token_out: None,
             amount_in: None,
             amount_out: None,
-            usd_value: None,
+            usd_value,
             fee_paid: None,
             points_earned: None,
             timestamp: chrono::Utc::now(),
@@ -572,11 +584,55 @@ impl EventIndexer {
             .unwrap_or("");
 
         // Update limit order status
-        self.db.update_order_status(order_id, 2).await?;
+        self.db
+            .update_order_status(order_id, OrderStatus::Filled)
+            .await?;
 
         tracing::info!("Limit order filled: {}", order_id);
         Ok(())
     }
+
+    // Internal helper that records a settled on-chain referral bonus.
+    async fn handle_referral_payout(
+        &self,
+        event: BlockchainEvent,
+        block_number: u64,
+    ) -> Result<()> {
+        let field = |name: &str| event.data.get(name).and_then(|v| v.as_str()).unwrap_or("");
+        let referrer = field("user");
+        let epoch = self.parser.hex_to_decimal(field("epoch"));
+        let amount = u256_to_points(field("amount_low"), field("amount_high"));
+        let (Some(epoch), Some(amount)) = (epoch, amount) else {
+            tracing::warn!(
+                "Skipping malformed referral payout event: tx_hash={}",
+                event.tx_hash
+            );
+            return Ok(());
+        };
+        if referrer.is_empty() {
+            return Ok(());
+        }
+
+        let recorded = self
+            .db
+            .record_referral_payout(
+                &event.tx_hash,
+                referrer,
+                epoch as i64,
+                amount,
+                block_number as i64,
+            )
+            .await?;
+        if recorded {
+            tracing::info!(
+                "Referral payout settled: referrer={}, epoch={}, amount={} points",
+                referrer,
+                epoch,
+                amount
+            );
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -604,6 +660,17 @@ fn normalize_event_data(parser: &EventParser, data: &mut serde_json::Value) {
     }
 }
 
+// Internal helper that converts a raw u256 (low, high) referral points amount.
+// `BonusClaimed.amount` carries the points moved into PointStorage, unscaled.
+fn u256_to_points(low_hex: &str, high_hex: &str) -> Option<rust_decimal::Decimal> {
+    let high = u128::from_str_radix(high_hex.trim_start_matches("0x"), 16).ok()?;
+    if high != 0 {
+        return None;
+    }
+    let low = u128::from_str_radix(low_hex.trim_start_matches("0x"), 16).ok()?;
+    rust_decimal::Decimal::try_from_i128_with_scale(i128::try_from(low).ok()?, 0).ok()
+}
+
 // Internal helper that supports `push_valid_address` operations.
 fn push_valid_address(targets: &mut Vec<String>, address: &str) {
     let trimmed = address.trim();
@@ -626,4 +693,15 @@ mod tests {
         normalize_event_data(&parser, &mut data);
         assert_eq!(data.get("user").and_then(|v| v.as_str()), Some("0xabc"));
     }
+
+    #[test]
+    // Internal helper that parses or transforms values for `u256_to_points_keeps_raw_points`.
+    fn u256_to_points_keeps_raw_points() {
+        assert_eq!(
+            u256_to_points("0x14", "0x0"),
+            Some(rust_decimal::Decimal::from(20))
+        );
+        assert_eq!(u256_to_points("0x1", "0x1"), None);
+        assert_eq!(u256_to_points("", "0x0"), None);
+    }
 }
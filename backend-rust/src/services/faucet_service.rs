@@ -151,7 +151,7 @@ impl FaucetService {
             entry_point_selector: selector,
             calldata: vec![],
         };
-        let result = self.reader.call(call).await;
+        let result = self.reader.call_cached(call).await;
         if let Ok(values) = result {
             if let Some(value) = values.first() {
                 if let Ok(decoded) = u256_from_felts(value, &Felt::from(0_u128)) {
@@ -384,6 +384,62 @@ impl FaucetService {
         }))
     }
 
+    /// Returns when the requester's cluster is next eligible for `token`, if blocked.
+    ///
+    /// # Notes
+    /// * The cluster is the canonical account of `user_address` plus every wallet linked
+    ///   to it; with `FAUCET_CLUSTER_MODE=linked_ip` it also includes claims carrying the
+    ///   same client `fingerprint`.
+    /// * Claims before `FAUCET_POLICY_RESET_AT` never block, and unlimited CAREL is exempt.
+    pub async fn cluster_next_eligible_at(
+        &self,
+        user_address: &str,
+        token: &str,
+        fingerprint: Option<&str>,
+    ) -> Result<Option<DateTime<Utc>>> {
+        let mode = FaucetClusterMode::parse(&self.config.faucet_cluster_mode)
+            .unwrap_or(FaucetClusterMode::Linked);
+        if mode == FaucetClusterMode::Off {
+            return Ok(None);
+        }
+        let token_symbol = normalize_token_symbol(token);
+        if is_carel_token(&token_symbol) && is_faucet_carel_unlimited() {
+            return Ok(None);
+        }
+
+        let canonical = self
+            .db
+            .find_user_by_wallet_address(user_address, Some("starknet"))
+            .await?
+            .unwrap_or_else(|| user_address.to_string());
+        let mut cluster = vec![canonical.to_ascii_lowercase()];
+        for linked in self.db.list_wallet_addresses(&canonical).await? {
+            cluster.push(linked.wallet_address.to_ascii_lowercase());
+        }
+        cluster.sort();
+        cluster.dedup();
+
+        let fingerprint = match mode {
+            FaucetClusterMode::LinkedIp => fingerprint,
+            _ => None,
+        };
+        let cooldown_hours = cooldown_hours_from_config(&self.config);
+        let latest = self
+            .db
+            .latest_faucet_cluster_claim(
+                user_address,
+                &cluster,
+                fingerprint,
+                &token_symbol,
+                cooldown_hours,
+                faucet_policy_reset_at(),
+            )
+            .await?;
+        Ok(latest
+            .map(|claimed_at| claimed_at + Duration::hours(cooldown_hours))
+            .filter(|next| *next > Utc::now()))
+    }
+
     /// Runs `claim_tokens` and handles related side effects.
     ///
     /// # Arguments
@@ -395,7 +451,12 @@ impl FaucetService {
     ///
     /// # Notes
     /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
-    pub async fn claim_tokens(&self, user_address: &str, token: &str) -> Result<String> {
+    pub async fn claim_tokens(
+        &self,
+        user_address: &str,
+        token: &str,
+        fingerprint: Option<&str>,
+    ) -> Result<String> {
         if !self.config.is_testnet() {
             return Err(AppError::BadRequest("Faucet only on testnet".into()));
         }
@@ -419,6 +480,18 @@ impl FaucetService {
         if !self.can_claim(user_address, &token_symbol).await? {
             return Err(AppError::FaucetCooldown);
         }
+        if let Some(next_eligible_at) = self
+            .cluster_next_eligible_at(user_address, &token_symbol, fingerprint)
+            .await?
+        {
+            tracing::warn!(
+                "Faucet claim blocked by cluster cooldown: user={} token={} next_eligible_at={}",
+                user_address,
+                token_symbol,
+                next_eligible_at
+            );
+            return Err(AppError::FaucetClusterCooldown(next_eligible_at));
+        }
 
         let amount = amount_for_token(&token_symbol, &self.config)?;
         let decimals = self.get_token_decimals(token_address).await?;
@@ -435,7 +508,7 @@ impl FaucetService {
             .send_tokens(user_address, token_address, amount_u128)
             .await?;
         self.db
-            .record_faucet_claim(user_address, &token_symbol, amount, &tx_hash)
+            .record_faucet_claim(user_address, &token_symbol, amount, &tx_hash, fingerprint)
             .await?;
 
         let _ = self
@@ -473,47 +546,154 @@ impl FaucetService {
         Ok(tx_hash.to_string())
     }
 
-    /// Fetches data for `get_stats`.
-    ///
-    /// # Arguments
-    /// * Uses function parameters as validated input and runtime context.
+    /// Fetches faucet distribution stats for `window`.
     ///
     /// # Returns
-    /// * `Ok(...)` when processing succeeds.
-    /// * `Err(AppError)` when validation, authorization, or integration checks fail.
+    /// * Per-token claim counts, amounts and unique claimants plus overall totals.
     ///
     /// # Notes
-    /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
-    pub async fn get_stats(&self) -> Result<FaucetStats> {
-        let row = sqlx::query(
-            "SELECT 
-                COUNT(DISTINCT user_address) as total_users,
-                COUNT(*) as total_claims,
-                COALESCE(SUM(CASE WHEN token = 'CAREL' THEN amount ELSE 0 END), 0) as total_carel,
-                COALESCE(SUM(CASE WHEN token = 'USDT' THEN amount ELSE 0 END), 0) as total_usdt,
-                COALESCE(SUM(CASE WHEN token = 'USDC' THEN amount ELSE 0 END), 0) as total_usdc
-             FROM faucet_claims",
-        )
-        .fetch_one(self.db.pool())
-        .await?;
+    /// * One grouped `ROLLUP` query; the rollup row carries the cross-token unique claimant count.
+    pub async fn get_stats(&self, window: FaucetStatsWindow) -> Result<FaucetStats> {
+        const STATS_SELECT: &str = "SELECT
+                token,
+                COUNT(*) as claims,
+                COALESCE(SUM(amount), 0) as amount,
+                COUNT(DISTINCT user_address) as claimants
+             FROM faucet_claims";
+        const STATS_GROUP: &str = "GROUP BY ROLLUP(token) ORDER BY token NULLS FIRST";
+
+        let rows = match window.since(chrono::Utc::now()) {
+            Some(since) => {
+                sqlx::query(&format!(
+                    "{STATS_SELECT} WHERE claimed_at >= $1 {STATS_GROUP}"
+                ))
+                .bind(since)
+                .fetch_all(self.db.pool())
+                .await?
+            }
+            None => {
+                sqlx::query(&format!("{STATS_SELECT} {STATS_GROUP}"))
+                    .fetch_all(self.db.pool())
+                    .await?
+            }
+        };
 
-        Ok(FaucetStats {
-            total_users: row.get::<i64, _>("total_users"),
-            total_claims: row.get::<i64, _>("total_claims"),
-            total_carel_distributed: row.get::<rust_decimal::Decimal, _>("total_carel"),
-            total_usdt_distributed: row.get::<rust_decimal::Decimal, _>("total_usdt"),
-            total_usdc_distributed: row.get::<rust_decimal::Decimal, _>("total_usdc"),
-        })
+        let mut stats = FaucetStats {
+            window: window.as_str().to_string(),
+            total_users: 0,
+            total_claims: 0,
+            total_carel_distributed: rust_decimal::Decimal::ZERO,
+            total_usdt_distributed: rust_decimal::Decimal::ZERO,
+            total_usdc_distributed: rust_decimal::Decimal::ZERO,
+            tokens: Vec::new(),
+        };
+        for row in rows {
+            let claims = row.get::<i64, _>("claims");
+            let amount = row.get::<rust_decimal::Decimal, _>("amount");
+            let claimants = row.get::<i64, _>("claimants");
+            let Some(token) = row.get::<Option<String>, _>("token") else {
+                stats.total_users = claimants;
+                stats.total_claims = claims;
+                continue;
+            };
+            match token.as_str() {
+                "CAREL" => stats.total_carel_distributed = amount,
+                "USDT" => stats.total_usdt_distributed = amount,
+                "USDC" => stats.total_usdc_distributed = amount,
+                _ => {}
+            }
+            stats.tokens.push(FaucetTokenStats {
+                token,
+                claims,
+                amount,
+                unique_claimants: claimants,
+            });
+        }
+        Ok(stats)
+    }
+}
+
+/// Aggregation window for faucet stats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaucetStatsWindow {
+    Day,
+    Week,
+    All,
+}
+
+impl FaucetStatsWindow {
+    /// Parses `24h`, `7d` or `all`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "24h" | "1d" => Some(Self::Day),
+            "7d" | "1w" => Some(Self::Week),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    /// Canonical label echoed back in the response.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Day => "24h",
+            Self::Week => "7d",
+            Self::All => "all",
+        }
+    }
+
+    /// Lower bound on `claimed_at`, or `None` for the unbounded window.
+    pub fn since(
+        self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        match self {
+            Self::Day => Some(now - chrono::Duration::hours(24)),
+            Self::Week => Some(now - chrono::Duration::days(7)),
+            Self::All => None,
+        }
+    }
+}
+
+/// How strictly faucet cooldowns are shared across related requesters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaucetClusterMode {
+    /// Cooldown applies per address only.
+    Off,
+    /// Cooldown is shared by all wallets linked to the same account.
+    Linked,
+    /// As `Linked`, plus claims from the same client fingerprint.
+    LinkedIp,
+}
+
+impl FaucetClusterMode {
+    /// Parses `off`, `linked` or `linked_ip`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" => Some(Self::Off),
+            "linked" => Some(Self::Linked),
+            "linked_ip" => Some(Self::LinkedIp),
+            _ => None,
+        }
     }
 }
 
+#[derive(Debug, serde::Serialize)]
+pub struct FaucetTokenStats {
+    pub token: String,
+    pub claims: i64,
+    pub amount: rust_decimal::Decimal,
+    pub unique_claimants: i64,
+}
+
 #[derive(Debug, serde::Serialize)]
 pub struct FaucetStats {
+    pub window: String,
     pub total_users: i64,
     pub total_claims: i64,
     pub total_carel_distributed: rust_decimal::Decimal,
     pub total_usdt_distributed: rust_decimal::Decimal,
     pub total_usdc_distributed: rust_decimal::Decimal,
+    pub tokens: Vec<FaucetTokenStats>,
 }
 
 #[cfg(test)]
@@ -529,8 +709,13 @@ mod tests {
             database_url: "postgres://localhost".to_string(),
             database_max_connections: 1,
             redis_url: "redis://localhost:6379".to_string(),
+            redis_keepalive_interval_secs: 30,
             point_calculator_batch_size: 100,
             point_calculator_max_batches_per_tick: 1,
+            point_calculator_batches_ceiling: 1,
+            point_calculator_max_attempts: 5,
+            transaction_stuck_after_secs: 900,
+            tx_reconcile_sample_size: 100,
             starknet_rpc_url: "http://localhost:5050".to_string(),
             starknet_chain_id: "SN_MAIN".to_string(),
             ethereum_rpc_url: "http://localhost:8545".to_string(),
@@ -540,8 +725,10 @@ mod tests {
             price_oracle_address: "0x4".to_string(),
             limit_order_book_address: "0x5".to_string(),
             staking_carel_address: None,
+            vesting_manager_address: None,
             discount_soulbound_address: None,
             treasury_address: None,
+            fee_recipient_address: None,
             referral_system_address: None,
             ai_executor_address: "0x6".to_string(),
             ai_signature_verifier_address: None,
@@ -554,8 +741,22 @@ mod tests {
             privacy_auto_garaga_public_inputs_file: None,
             privacy_auto_garaga_prover_cmd: None,
             privacy_auto_garaga_prover_timeout_ms: 45_000,
+            privacy_auto_submit_root_check: "reject".to_string(),
+            garaga_warmup_interval_secs: 0,
+            garaga_warmup_idle_secs: 1800,
+            max_concurrent_proofs: 2,
+            proof_queue_timeout_ms: 30_000,
+            privacy_max_proof_len: 8192,
+            privacy_max_public_inputs_len: 64,
+            garaga_public_input_schema: None,
+            privacy_anonymity_min_set_size: 20,
+            privacy_anonymity_scan_blocks: 100_000,
             private_btc_swap_address: "0x9".to_string(),
             dark_pool_address: "0x10".to_string(),
+            dark_pool_min_notional_usd: 50.0,
+            dark_pool_max_notional_usd: 250_000.0,
+            dark_pool_token_limits: "".to_string(),
+            achievement_definitions: "".to_string(),
             private_payments_address: "0x11".to_string(),
             anonymous_credentials_address: "0x12".to_string(),
             token_strk_address: None,
@@ -566,6 +767,15 @@ mod tests {
             faucet_strk_amount: None,
             faucet_carel_amount: None,
             faucet_cooldown_hours: Some(12),
+            faucet_cluster_mode: "linked".to_string(),
+            faucet_trusted_proxy_hops: 0,
+            faucet_fingerprint_salt: None,
+            deposit_base_token: "USDC".to_string(),
+            rewards_epoch_duration_seconds: 2_592_000,
+            nft_period_duration_seconds: 2_592_000,
+            carel_supply_cache_secs: 300,
+            display_amount_max_decimals: 8,
+            analytics_max_points: 500,
             backend_private_key: "k".to_string(),
             backend_public_key: "p".to_string(),
             backend_account_address: None,
@@ -584,6 +794,10 @@ mod tests {
             ai_llm_rewrite_timeout_ms: 8_000,
             twitter_bearer_token: None,
             telegram_bot_token: None,
+            telegram_api_url: "https://api.telegram.org".to_string(),
+            email_api_url: None,
+            email_api_key: None,
+            email_from: None,
             discord_bot_token: None,
             social_tasks_json: None,
             admin_manual_key: None,
@@ -595,6 +809,11 @@ mod tests {
             atomiq_api_url: "".to_string(),
             garden_api_key: None,
             garden_api_url: "".to_string(),
+            garden_schema_names: "".to_string(),
+            garden_schema_cache_ttl_secs: 0,
+            garden_earnings_snapshot_interval_secs: 0,
+            mempool_api_url: "https://mempool.space/api".to_string(),
+            mempool_testnet_api_url: "https://mempool.space/testnet/api".to_string(),
             sumo_login_api_key: None,
             sumo_login_api_url: "".to_string(),
             xverse_api_key: None,
@@ -602,16 +821,53 @@ mod tests {
             privacy_verifier_routers: "".to_string(),
             stripe_secret_key: None,
             moonpay_api_key: None,
+            object_storage_endpoint: None,
+            object_storage_bucket: None,
+            object_storage_region: "us-east-1".to_string(),
+            object_storage_access_key_id: None,
+            object_storage_secret_access_key: None,
+            snapshot_export_prefix: "reward-snapshots".to_string(),
             rate_limit_public: 1,
             rate_limit_authenticated: 1,
+            api_max_page_limit: 100,
+            api_max_time_range_days: 366,
+            http_body_limit_bytes: 1024 * 1024,
+            http_body_limit_small_bytes: 16 * 1024,
+            http_body_limit_proof_bytes: 4 * 1024 * 1024,
+            http_request_timeout_secs: 15,
+            http_long_request_timeout_secs: 120,
+            webhook_log_retention_days: 30,
+            max_deadline_seconds: 3600,
+            carel_stake_discount_tiers: Vec::new(),
+            max_combined_fee_discount_percent: 50.0,
+            ws_max_connections_per_user: 5,
+            ws_max_messages_per_minute: 60,
+            ws_auth_timeout_secs: 10,
+            points_per_usd_bridge_eth: 15.0,
+            points_per_usd_bridge_btc: 25.0,
+            points_min_usd_bridge_eth: None,
+            points_min_usd_bridge_btc: None,
+            stake_points_accrual_rates: "".to_string(),
+            hide_balance_denominations: "".to_string(),
+            hide_balance_swap_pairs: "".to_string(),
+            onboarding_bonus_points: 0.0,
             ai_rate_limit_window_seconds: 60,
             ai_rate_limit_global_per_window: 40,
             ai_rate_limit_level_1_per_window: 20,
             ai_rate_limit_level_2_per_window: 10,
             ai_rate_limit_level_3_per_window: 8,
+            maintenance_bypass_addresses: String::new(),
+            relayer_allowed_addresses: String::new(),
+            relayer_min_ai_level: 0,
+            relayer_rate_limit_per_hour: 30,
             cors_allowed_origins: "*".to_string(),
             oracle_asset_ids: "".to_string(),
+            oracle_route_dex_ids: crate::constants::DEFAULT_ORACLE_ROUTE_DEX_IDS.to_string(),
+            oracle_route_liquidity_buffer_pct: 0.0,
+            swap_route_cache_ttl_ms: 0,
+            swap_route_execute_max_age_ms: 0,
             bridge_provider_ids: "".to_string(),
+            bridge_quote_sort: "best_output".to_string(),
             price_tokens: "BTC,ETH,STRK,CAREL,USDT,USDC".to_string(),
             coingecko_api_url: "https://api.coingecko.com/api/v3".to_string(),
             coingecko_api_key: None,
@@ -636,4 +892,45 @@ mod tests {
         let amount = amount_for_token("CAREL", &cfg).expect("token valid");
         assert!((amount - 30.0).abs() < f64::EPSILON);
     }
+
+    #[test]
+    // Internal helper that supports `faucet_stats_window_parses_and_bounds` operations.
+    fn faucet_stats_window_parses_and_bounds() {
+        let now = chrono::Utc::now();
+        assert_eq!(
+            FaucetStatsWindow::parse("24H"),
+            Some(FaucetStatsWindow::Day)
+        );
+        assert_eq!(
+            FaucetStatsWindow::parse("7d"),
+            Some(FaucetStatsWindow::Week)
+        );
+        assert_eq!(
+            FaucetStatsWindow::parse("all"),
+            Some(FaucetStatsWindow::All)
+        );
+        assert_eq!(FaucetStatsWindow::parse("30d"), None);
+        assert_eq!(
+            FaucetStatsWindow::Week.since(now),
+            Some(now - chrono::Duration::days(7))
+        );
+        assert_eq!(FaucetStatsWindow::All.since(now), None);
+    }
+    #[test]
+    // Internal helper that supports `faucet_cluster_mode_parses_known_values` operations.
+    fn faucet_cluster_mode_parses_known_values() {
+        assert_eq!(
+            FaucetClusterMode::parse("off"),
+            Some(FaucetClusterMode::Off)
+        );
+        assert_eq!(
+            FaucetClusterMode::parse(" Linked "),
+            Some(FaucetClusterMode::Linked)
+        );
+        assert_eq!(
+            FaucetClusterMode::parse("linked_ip"),
+            Some(FaucetClusterMode::LinkedIp)
+        );
+        assert_eq!(FaucetClusterMode::parse("strict"), None);
+    }
 }
@@ -0,0 +1,227 @@
+use crate::{config::Config, db::Database, error::Result, integrations::bridge::GardenClient};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+// Fields Garden may use for an earnings amount, in order of preference.
+const EARNINGS_AMOUNT_KEYS: [&str; 5] = [
+    "earnings_usd",
+    "total_earnings_usd",
+    "earnings",
+    "total_earnings",
+    "amount",
+];
+const EARNINGS_TOTAL_KEYS: [&str; 4] =
+    ["total_earnings_usd", "total_usd", "total_earnings", "total"];
+
+/// Totals extracted from one Garden app earnings payload.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AppEarningsSummary {
+    pub total: Option<f64>,
+    pub by_chain: BTreeMap<String, f64>,
+}
+
+/// One stored earnings snapshot, with the change since the previous one in the series.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppEarningsPoint {
+    pub captured_at: DateTime<Utc>,
+    pub total_earnings: Option<f64>,
+    pub chains: Value,
+    pub change_from_previous: Option<f64>,
+}
+
+// Snapshot row read back for the history endpoint.
+#[derive(Debug, sqlx::FromRow)]
+struct SnapshotRow {
+    captured_at: DateTime<Utc>,
+    total_earnings: Option<f64>,
+    chains: Value,
+}
+
+// Internal helper that reads a number that Garden may encode as a JSON number or string.
+fn value_as_f64(value: &Value) -> Option<f64> {
+    let amount = match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(raw) => raw.trim().parse().ok(),
+        _ => None,
+    };
+    amount.filter(|amount| amount.is_finite())
+}
+
+// Internal helper that returns the first numeric field among `keys`.
+fn first_amount(object: &serde_json::Map<String, Value>, keys: &[&str]) -> Option<f64> {
+    keys.iter()
+        .find_map(|key| object.get(*key).and_then(value_as_f64))
+}
+
+/// Extracts the total and per-chain breakdown from a Garden app earnings payload.
+///
+/// # Notes
+/// * Accepts the payload with or without the `result` envelope. Per-chain entries are read
+///   from an array (or `earnings`/`chains` array) of objects carrying a `chain` field.
+/// * When Garden reports no total, the per-chain amounts are summed.
+pub fn summarize_app_earnings(payload: &Value) -> AppEarningsSummary {
+    let result = payload.get("result").unwrap_or(payload);
+    let entries = match result {
+        Value::Array(items) => Some(items),
+        Value::Object(object) => ["earnings", "chains"]
+            .iter()
+            .find_map(|key| object.get(*key).and_then(Value::as_array)),
+        _ => None,
+    };
+
+    let mut by_chain = BTreeMap::new();
+    for entry in entries.into_iter().flatten() {
+        let Some(object) = entry.as_object() else {
+            continue;
+        };
+        let Some(chain) = object
+            .get("chain")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|chain| !chain.is_empty())
+        else {
+            continue;
+        };
+        if let Some(amount) = first_amount(object, &EARNINGS_AMOUNT_KEYS) {
+            *by_chain.entry(chain.to_string()).or_insert(0.0) += amount;
+        }
+    }
+
+    let total = result
+        .as_object()
+        .and_then(|object| first_amount(object, &EARNINGS_TOTAL_KEYS))
+        .or_else(|| (!by_chain.is_empty()).then(|| by_chain.values().sum()));
+    AppEarningsSummary { total, by_chain }
+}
+
+pub struct GardenEarningsTracker {
+    db: Database,
+    config: Config,
+}
+
+impl GardenEarningsTracker {
+    pub fn new(db: Database, config: Config) -> Self {
+        Self { db, config }
+    }
+
+    /// Starts the periodic earnings snapshot loop.
+    ///
+    /// # Notes
+    /// * Disabled when `GARDEN_EARNINGS_SNAPSHOT_INTERVAL_SECS=0` or Garden is not configured.
+    /// * A failed Garden call skips that tick; the loop keeps running.
+    pub async fn start(self: Arc<Self>) {
+        let interval_secs = self.config.garden_earnings_snapshot_interval_secs;
+        if interval_secs == 0 || self.config.garden_api_url.trim().is_empty() {
+            tracing::warn!("Garden earnings snapshots disabled");
+            return;
+        }
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+            loop {
+                ticker.tick().await;
+                if let Err(err) = self.snapshot_once().await {
+                    tracing::warn!("Garden earnings snapshot skipped: {}", err);
+                }
+            }
+        });
+    }
+
+    /// Fetches current app earnings from Garden and stores a snapshot.
+    pub async fn snapshot_once(&self) -> Result<()> {
+        let payload = GardenClient::new(
+            self.config.garden_api_key.clone().unwrap_or_default(),
+            self.config.garden_api_url.clone(),
+        )
+        .get_app_earnings()
+        .await?;
+        let summary = summarize_app_earnings(&payload);
+        sqlx::query(
+            "INSERT INTO garden_app_earnings_snapshots (total_earnings, chains, payload)
+             VALUES ($1, $2, $3)",
+        )
+        .bind(summary.total)
+        .bind(serde_json::json!(summary.by_chain))
+        .bind(payload)
+        .execute(self.db.pool())
+        .await?;
+        Ok(())
+    }
+}
+
+/// Lists earnings snapshots in `[from, to]`, oldest first.
+///
+/// # Notes
+/// * `change_from_previous` compares each total with the previous snapshot in the range; a
+///   negative value marks a drop.
+pub async fn list_snapshots(
+    db: &Database,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<AppEarningsPoint>> {
+    let rows = sqlx::query_as::<_, SnapshotRow>(
+        "SELECT captured_at, total_earnings, chains
+         FROM garden_app_earnings_snapshots
+         WHERE captured_at >= $1 AND captured_at <= $2
+         ORDER BY captured_at ASC, id ASC",
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(db.pool())
+    .await?;
+
+    let mut previous: Option<f64> = None;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let change_from_previous = row
+                .total_earnings
+                .zip(previous)
+                .map(|(current, before)| current - before);
+            if row.total_earnings.is_some() {
+                previous = row.total_earnings;
+            }
+            AppEarningsPoint {
+                captured_at: row.captured_at,
+                total_earnings: row.total_earnings,
+                chains: row.chains,
+                change_from_previous,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `summarize_app_earnings_reads_chain_breakdown` operations.
+    fn summarize_app_earnings_reads_chain_breakdown() {
+        let payload = serde_json::json!({
+            "status": "Ok",
+            "result": [
+                { "chain": "bitcoin_testnet", "earnings": "12.5" },
+                { "chain": "starknet_sepolia", "earnings_usd": 7.5 },
+                { "chain": "starknet_sepolia", "earnings": "1" },
+                { "earnings": "99" }
+            ]
+        });
+        let summary = summarize_app_earnings(&payload);
+        assert_eq!(summary.by_chain.get("bitcoin_testnet"), Some(&12.5));
+        assert_eq!(summary.by_chain.get("starknet_sepolia"), Some(&8.5));
+        assert_eq!(summary.total, Some(21.0));
+
+        let totals_only = serde_json::json!({ "result": { "total_earnings": "40" } });
+        let summary = summarize_app_earnings(&totals_only);
+        assert!(summary.by_chain.is_empty());
+        assert_eq!(summary.total, Some(40.0));
+
+        assert_eq!(
+            summarize_app_earnings(&serde_json::json!({ "result": null })),
+            AppEarningsSummary::default()
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use crate::{
+    config::Config,
+    error::ErrorCode,
+    error::{AppError, Result},
+    services::onchain::{felt_to_u128, parse_felt, OnchainError, OnchainReader},
+};
+use starknet_core::types::{Felt, FunctionCall};
+use starknet_core::utils::get_selector_from_name;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use tokio::time::{sleep, Duration};
+
+const DETECTION_REFRESH_SECS: u64 = 600;
+const DETECTION_RETRY_SECS: u64 = 15;
+const NOTE_REGISTRY_CACHE_MAX_ENTRIES: usize = 50_000;
+
+static DETECTED_HIDE_EXECUTOR_KIND: OnceLock<RwLock<Option<HideExecutorKind>>> = OnceLock::new();
+// Registered notes keyed by (executor, commitment); the deposit timestamp is filled in once read.
+// Registration is immutable on-chain, so entries never expire. Misses are never stored.
+type NoteRegistryCache = HashMap<(Felt, Felt), Option<u64>>;
+static NOTE_REGISTRY_CACHE: OnceLock<tokio::sync::RwLock<NoteRegistryCache>> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HideExecutorKind {
+    PrivateActionExecutorV1,
+    ShieldedPoolV2,
+    ShieldedPoolV3,
+}
+
+impl HideExecutorKind {
+    /// Stable label matching the `HIDE_BALANCE_EXECUTOR_KIND` values.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PrivateActionExecutorV1 => "private_action_executor_v1",
+            Self::ShieldedPoolV2 => "shielded_pool_v2",
+            Self::ShieldedPoolV3 => "shielded_pool_v3",
+        }
+    }
+
+    /// Parses an explicit executor-kind label; unknown or empty values yield `None`.
+    pub fn from_label(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "shielded_pool_v3" | "shielded-v3" | "v3" => Some(Self::ShieldedPoolV3),
+            "shielded_pool_v2" | "shielded-v2" | "v2" => Some(Self::ShieldedPoolV2),
+            "private_action_executor_v1" | "private-action-executor-v1" | "v1" => {
+                Some(Self::PrivateActionExecutorV1)
+            }
+            _ => None,
+        }
+    }
+}
+
+// Internal helper that returns the process-wide detection cache.
+fn detected_kind_cache() -> &'static RwLock<Option<HideExecutorKind>> {
+    DETECTED_HIDE_EXECUTOR_KIND.get_or_init(|| RwLock::new(None))
+}
+
+// Internal helper that reads the explicit `HIDE_BALANCE_EXECUTOR_KIND` override, if any.
+fn env_override() -> Option<HideExecutorKind> {
+    std::env::var("HIDE_BALANCE_EXECUTOR_KIND")
+        .ok()
+        .and_then(|raw| HideExecutorKind::from_label(&raw))
+}
+
+/// Returns the active hide-balance executor kind, or `None` while it is still unknown.
+///
+/// `HIDE_BALANCE_EXECUTOR_KIND` wins when set; otherwise the kind detected from the
+/// configured executor's selectors is used. Nothing is assumed before detection succeeds.
+pub fn known_hide_executor_kind() -> Option<HideExecutorKind> {
+    env_override().or_else(|| detected_kind_cache().read().ok().and_then(|guard| *guard))
+}
+
+/// Returns the active hide-balance executor kind for building calldata.
+///
+/// # Errors
+/// Returns `AppError::MaintenanceMode` until the kind is known, so no calldata is
+/// ever built against a guessed executor layout.
+pub fn hide_executor_kind() -> Result<HideExecutorKind> {
+    known_hide_executor_kind().ok_or_else(|| {
+        AppError::MaintenanceMode(
+            "Hide Balance executor is still being detected. Retry shortly.".to_string(),
+        )
+    })
+}
+
+// Internal helper that maps probed selector support to an executor kind.
+fn kind_from_selectors(
+    has_submit_private_swap: bool,
+    has_deposit_fixed_for: bool,
+) -> HideExecutorKind {
+    if has_submit_private_swap {
+        HideExecutorKind::ShieldedPoolV3
+    } else if has_deposit_fixed_for {
+        HideExecutorKind::ShieldedPoolV2
+    } else {
+        HideExecutorKind::PrivateActionExecutorV1
+    }
+}
+
+/// Resolves the configured private executor address (env overrides, then `PRIVACY_ROUTER_ADDRESS`).
+pub fn configured_executor_address(config: &Config) -> Option<String> {
+    configured_executor_addresses(config).into_iter().next()
+}
+
+/// Lists every configured private executor address in precedence order.
+pub fn configured_executor_addresses(config: &Config) -> Vec<String> {
+    [
+        std::env::var("PRIVATE_ACTION_EXECUTOR_ADDRESS").ok(),
+        std::env::var("NEXT_PUBLIC_PRIVATE_ACTION_EXECUTOR_ADDRESS").ok(),
+        config.privacy_router_address.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|raw| raw.trim().to_string())
+    .filter(|raw| !raw.is_empty() && !raw.starts_with("0x0000"))
+    .collect()
+}
+
+// Internal helper that probes whether `contract` exposes `selector_name`.
+// A revert still proves the entrypoint exists; only a missing entrypoint counts as unsupported.
+async fn contract_has_selector(
+    reader: &OnchainReader,
+    contract: Felt,
+    selector_name: &str,
+) -> Result<bool> {
+    let selector = get_selector_from_name(selector_name)
+        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
+    let probe = reader
+        .call_typed(FunctionCall {
+            contract_address: contract,
+            entry_point_selector: selector,
+            calldata: vec![Felt::ONE],
+        })
+        .await;
+    match probe {
+        Ok(_) | Err(OnchainError::ContractRevert(_)) => Ok(true),
+        Err(OnchainError::EntrypointNotFound(_)) => Ok(false),
+        Err(err) => Err(AppError::BlockchainRPC(format!(
+            "Failed to probe {} on {}: {}",
+            selector_name, contract, err
+        ))),
+    }
+}
+
+/// Probes the configured executor and caches the detected kind.
+///
+/// `submit_private_swap` marks a ShieldedPoolV3, `deposit_fixed_for` a ShieldedPoolV2,
+/// and anything else is treated as a PrivateActionExecutorV1.
+pub async fn detect_hide_executor_kind(config: &Config) -> Result<HideExecutorKind> {
+    let address = configured_executor_address(config).ok_or_else(|| {
+        AppError::BadRequest(
+            "PrivateActionExecutor is not configured. Set PRIVATE_ACTION_EXECUTOR_ADDRESS."
+                .to_string(),
+        )
+    })?;
+    let contract = parse_felt(&address)?;
+    let reader = OnchainReader::from_config(config)?;
+    let has_submit_private_swap =
+        contract_has_selector(&reader, contract, "submit_private_swap").await?;
+    let has_deposit_fixed_for = !has_submit_private_swap
+        && contract_has_selector(&reader, contract, "deposit_fixed_for").await?;
+    let kind = kind_from_selectors(has_submit_private_swap, has_deposit_fixed_for);
+
+    if let Ok(mut guard) = detected_kind_cache().write() {
+        if *guard != Some(kind) {
+            tracing::info!(
+                "Detected hide executor kind {} on {}",
+                kind.as_str(),
+                address
+            );
+        }
+        *guard = Some(kind);
+    }
+    if let Some(override_kind) = env_override() {
+        if override_kind != kind {
+            tracing::warn!(
+                "HIDE_BALANCE_EXECUTOR_KIND={} overrides detected kind {} on {}",
+                override_kind.as_str(),
+                kind.as_str(),
+                address
+            );
+        }
+    }
+    Ok(kind)
+}
+
+/// Runs executor-kind detection at startup and refreshes it periodically so executor
+/// upgrades are picked up without touching `HIDE_BALANCE_EXECUTOR_KIND`.
+pub async fn start_hide_executor_detection(config: Config) {
+    if configured_executor_address(&config).is_none() {
+        tracing::info!("Hide executor detection skipped: no private executor configured");
+        return;
+    }
+    loop {
+        // Retry quickly while the kind is still unknown; hide flows are refused until then.
+        let delay = match detect_hide_executor_kind(&config).await {
+            Ok(_) => DETECTION_REFRESH_SECS,
+            Err(err) => {
+                tracing::warn!("Hide executor detection failed: {}", err);
+                if known_hide_executor_kind().is_some() {
+                    DETECTION_REFRESH_SECS
+                } else {
+                    DETECTION_RETRY_SECS
+                }
+            }
+        };
+        sleep(Duration::from_secs(delay)).await;
+    }
+}
+
+// Internal helper that returns the process-wide registered-note cache.
+fn note_registry_cache() -> &'static tokio::sync::RwLock<HashMap<(Felt, Felt), Option<u64>>> {
+    NOTE_REGISTRY_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()))
+}
+
+// Internal helper that records a positive registry lookup, keeping a known timestamp.
+async fn remember_registered_note(
+    executor: Felt,
+    commitment: Felt,
+    deposit_timestamp: Option<u64>,
+) {
+    let mut cache = note_registry_cache().write().await;
+    if cache.len() >= NOTE_REGISTRY_CACHE_MAX_ENTRIES {
+        cache.clear();
+    }
+    let entry = cache.entry((executor, commitment)).or_insert(None);
+    if deposit_timestamp.is_some() {
+        *entry = deposit_timestamp;
+    }
+}
+
+// Internal helper that calls a single-felt view on the shielded pool.
+async fn read_shielded_felt(
+    config: &Config,
+    executor: Felt,
+    selector_name: &str,
+    calldata: Vec<Felt>,
+) -> Result<Felt> {
+    let reader = OnchainReader::from_config(config)?;
+    let selector = get_selector_from_name(selector_name)
+        .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?;
+    let out = reader
+        .call(FunctionCall {
+            contract_address: executor,
+            entry_point_selector: selector,
+            calldata,
+        })
+        .await?;
+    Ok(out.first().copied().unwrap_or(Felt::ZERO))
+}
+
+/// Returns whether `note_commitment` is registered on the shielded pool `executor`.
+///
+/// # Notes
+/// * Positive results are cached for the process lifetime; "not registered" is always re-read.
+pub async fn shielded_note_registered(
+    config: &Config,
+    executor: Felt,
+    note_commitment: Felt,
+) -> Result<bool> {
+    if note_registry_cache()
+        .read()
+        .await
+        .contains_key(&(executor, note_commitment))
+    {
+        return Ok(true);
+    }
+    let flag = read_shielded_felt(
+        config,
+        executor,
+        "is_note_registered",
+        vec![note_commitment],
+    )
+    .await?;
+    if flag == Felt::ZERO {
+        return Ok(false);
+    }
+    remember_registered_note(executor, note_commitment, None).await;
+    Ok(true)
+}
+
+/// Returns the deposit timestamp of `note_commitment`, or `0` while it is not registered.
+///
+/// # Notes
+/// * Nonzero timestamps are cached for the process lifetime; `0` is always re-read.
+pub async fn shielded_note_deposit_timestamp(
+    config: &Config,
+    executor: Felt,
+    note_commitment: Felt,
+) -> Result<u64> {
+    let cached = note_registry_cache()
+        .read()
+        .await
+        .get(&(executor, note_commitment))
+        .copied()
+        .flatten();
+    if let Some(deposit_timestamp) = cached {
+        return Ok(deposit_timestamp);
+    }
+    let raw = read_shielded_felt(
+        config,
+        executor,
+        "get_note_deposit_timestamp",
+        vec![note_commitment],
+    )
+    .await?;
+    let deposit_timestamp = felt_to_u128(&raw).map_err(|_| {
+        AppError::BadRequest("Invalid note timestamp returned by shielded pool".to_string())
+    })? as u64;
+    if deposit_timestamp > 0 {
+        remember_registered_note(executor, note_commitment, Some(deposit_timestamp)).await;
+    }
+    Ok(deposit_timestamp)
+}
+
+/// Returns the current Merkle root of the shielded pool `executor`.
+///
+/// # Notes
+/// * Always read live: every caller either binds a proof to the root or re-checks it right
+///   before submission, so a cached root could pass a check on a root that already moved.
+/// * An uninitialized root (`0`) surfaces `SHIELDED_ROOT_UNINITIALIZED`.
+pub async fn shielded_current_root(config: &Config, executor: Felt) -> Result<Felt> {
+    let root = read_shielded_felt(config, executor, "get_root", vec![]).await?;
+    if root == Felt::ZERO {
+        return Err(ErrorCode::ShieldedRootUninitialized.into());
+    }
+    Ok(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `kind_from_selectors_prefers_v3_then_v2` operations.
+    fn kind_from_selectors_prefers_v3_then_v2() {
+        assert_eq!(
+            kind_from_selectors(true, true),
+            HideExecutorKind::ShieldedPoolV3
+        );
+        assert_eq!(
+            kind_from_selectors(false, true),
+            HideExecutorKind::ShieldedPoolV2
+        );
+        assert_eq!(
+            kind_from_selectors(false, false),
+            HideExecutorKind::PrivateActionExecutorV1
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `from_label_accepts_aliases` operations.
+    fn from_label_accepts_aliases() {
+        assert_eq!(
+            HideExecutorKind::from_label(" Shielded-V3 "),
+            Some(HideExecutorKind::ShieldedPoolV3)
+        );
+        assert_eq!(
+            HideExecutorKind::from_label("v2"),
+            Some(HideExecutorKind::ShieldedPoolV2)
+        );
+        assert_eq!(
+            HideExecutorKind::from_label("v1"),
+            Some(HideExecutorKind::PrivateActionExecutorV1)
+        );
+        assert_eq!(HideExecutorKind::from_label(""), None);
+    }
+
+    #[tokio::test]
+    // Internal helper that supports `remember_registered_note_keeps_known_timestamp` operations.
+    async fn remember_registered_note_keeps_known_timestamp() {
+        let executor = Felt::from(0x705_u64);
+        let commitment = Felt::from(0xabc_u64);
+        remember_registered_note(executor, commitment, Some(1_700_000_000)).await;
+        remember_registered_note(executor, commitment, None).await;
+        let cached = note_registry_cache()
+            .read()
+            .await
+            .get(&(executor, commitment))
+            .copied();
+        assert_eq!(cached, Some(Some(1_700_000_000)));
+    }
+}
@@ -0,0 +1,513 @@
+use crate::error::{AppError, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+
+const MAX_INDICATOR_PERIOD: usize = 500;
+
+/// Technical indicator requested by `charts::get_indicators`, with its period parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndicatorSpec {
+    Sma {
+        period: usize,
+    },
+    Ema {
+        period: usize,
+    },
+    Rsi {
+        period: usize,
+    },
+    Macd {
+        fast: usize,
+        slow: usize,
+        signal: usize,
+    },
+    Bollinger {
+        period: usize,
+        std_dev: f64,
+    },
+}
+
+/// Values of one indicator aligned index-for-index with the input candles.
+///
+/// Leading entries are `None` until enough candles exist to fill the window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndicatorSeries {
+    pub value: Vec<Option<f64>>,
+    pub signal: Option<Vec<Option<f64>>>,
+    pub histogram: Option<Vec<Option<f64>>>,
+    pub upper: Option<Vec<Option<f64>>>,
+    pub lower: Option<Vec<Option<f64>>>,
+}
+
+impl IndicatorSeries {
+    // Internal helper that supports `single` operations.
+    fn single(value: Vec<Option<f64>>) -> Self {
+        Self {
+            value,
+            signal: None,
+            histogram: None,
+            upper: None,
+            lower: None,
+        }
+    }
+}
+
+// Internal helper that parses or transforms values for `parse_period`.
+fn parse_period(raw: Option<&str>, default: usize) -> Result<usize> {
+    let Some(raw) = raw else {
+        return Ok(default);
+    };
+    let period = raw
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| AppError::BadRequest(format!("Invalid indicator period: {}", raw)))?;
+    if period == 0 || period > MAX_INDICATOR_PERIOD {
+        return Err(AppError::BadRequest(format!(
+            "Indicator period must be between 1 and {}",
+            MAX_INDICATOR_PERIOD
+        )));
+    }
+    Ok(period)
+}
+
+impl IndicatorSpec {
+    /// Parses `name[:p1[:p2[:p3]]]`, e.g. `sma:20`, `macd:12:26:9`, `bollinger:20:2`.
+    ///
+    /// # Returns
+    /// * `Err(AppError::BadRequest)` for unknown names or out-of-range parameters.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let mut parts = raw.trim().split(':');
+        let name = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+        let params: Vec<&str> = parts.collect();
+        let spec = match name.as_str() {
+            "sma" => Self::Sma {
+                period: parse_period(params.first().copied(), 20)?,
+            },
+            "ema" => Self::Ema {
+                period: parse_period(params.first().copied(), 20)?,
+            },
+            "rsi" => Self::Rsi {
+                period: parse_period(params.first().copied(), 14)?,
+            },
+            "macd" => {
+                let fast = parse_period(params.first().copied(), 12)?;
+                let slow = parse_period(params.get(1).copied(), 26)?;
+                let signal = parse_period(params.get(2).copied(), 9)?;
+                if fast >= slow {
+                    return Err(AppError::BadRequest(
+                        "MACD fast period must be shorter than slow period".to_string(),
+                    ));
+                }
+                Self::Macd { fast, slow, signal }
+            }
+            "bollinger" | "bb" => {
+                let period = parse_period(params.first().copied(), 20)?;
+                let std_dev = match params.get(1) {
+                    Some(raw) => raw
+                        .trim()
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|value| value.is_finite() && *value > 0.0)
+                        .ok_or_else(|| {
+                            AppError::BadRequest(format!("Invalid Bollinger width: {}", raw))
+                        })?,
+                    None => 2.0,
+                };
+                Self::Bollinger { period, std_dev }
+            }
+            _ => {
+                return Err(AppError::BadRequest(format!(
+                    "Unknown indicator: {} (expected sma, ema, rsi, macd, bollinger)",
+                    name
+                )))
+            }
+        };
+        Ok(spec)
+    }
+
+    /// Parses a comma-separated list; an empty list yields the legacy default (SMA, EMA, RSI).
+    pub fn parse_list(raw: Option<&str>) -> Result<Vec<Self>> {
+        let raw = raw.map(str::trim).unwrap_or_default();
+        if raw.is_empty() {
+            return Ok(vec![
+                Self::Sma { period: 20 },
+                Self::Ema { period: 20 },
+                Self::Rsi { period: 14 },
+            ]);
+        }
+        raw.split(',')
+            .filter(|item| !item.trim().is_empty())
+            .map(Self::parse)
+            .collect()
+    }
+
+    /// Returns the upper-case label used in responses.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Sma { .. } => "SMA",
+            Self::Ema { .. } => "EMA",
+            Self::Rsi { .. } => "RSI",
+            Self::Macd { .. } => "MACD",
+            Self::Bollinger { .. } => "BOLLINGER",
+        }
+    }
+
+    /// Returns the numeric parameters in declaration order.
+    pub fn params(&self) -> Vec<f64> {
+        match *self {
+            Self::Sma { period } | Self::Ema { period } | Self::Rsi { period } => {
+                vec![period as f64]
+            }
+            Self::Macd { fast, slow, signal } => vec![fast as f64, slow as f64, signal as f64],
+            Self::Bollinger { period, std_dev } => vec![period as f64, std_dev],
+        }
+    }
+
+    /// Computes the indicator over close prices.
+    pub fn compute(&self, closes: &[f64]) -> IndicatorSeries {
+        match *self {
+            Self::Sma { period } => IndicatorSeries::single(sma(closes, period)),
+            Self::Ema { period } => IndicatorSeries::single(ema(closes, period)),
+            Self::Rsi { period } => IndicatorSeries::single(rsi(closes, period)),
+            Self::Macd { fast, slow, signal } => {
+                let (line, signal, histogram) = macd(closes, fast, slow, signal);
+                IndicatorSeries {
+                    value: line,
+                    signal: Some(signal),
+                    histogram: Some(histogram),
+                    upper: None,
+                    lower: None,
+                }
+            }
+            Self::Bollinger { period, std_dev } => {
+                let (middle, upper, lower) = bollinger(closes, period, std_dev);
+                IndicatorSeries {
+                    value: middle,
+                    signal: None,
+                    histogram: None,
+                    upper: Some(upper),
+                    lower: Some(lower),
+                }
+            }
+        }
+    }
+}
+
+/// Simple moving average of the trailing `period` values.
+pub fn sma(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut out = vec![None; values.len()];
+    if period == 0 {
+        return out;
+    }
+    let mut sum = 0.0;
+    for (i, value) in values.iter().enumerate() {
+        sum += value;
+        if i >= period {
+            sum -= values[i - period];
+        }
+        if i + 1 >= period {
+            out[i] = Some(sum / period as f64);
+        }
+    }
+    out
+}
+
+/// Exponential moving average seeded with the SMA of the first `period` values.
+pub fn ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut out = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return out;
+    }
+    let multiplier = 2.0 / (period as f64 + 1.0);
+    let mut current = values[..period].iter().sum::<f64>() / period as f64;
+    out[period - 1] = Some(current);
+    for i in period..values.len() {
+        current = (values[i] - current) * multiplier + current;
+        out[i] = Some(current);
+    }
+    out
+}
+
+/// Relative strength index using Wilder's smoothing.
+pub fn rsi(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut out = vec![None; values.len()];
+    if period == 0 || values.len() <= period {
+        return out;
+    }
+    let rsi_from = |avg_gain: f64, avg_loss: f64| {
+        if avg_loss == 0.0 {
+            100.0
+        } else {
+            100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+        }
+    };
+
+    let mut avg_gain = 0.0;
+    let mut avg_loss = 0.0;
+    for i in 1..=period {
+        let diff = values[i] - values[i - 1];
+        if diff > 0.0 {
+            avg_gain += diff;
+        } else {
+            avg_loss -= diff;
+        }
+    }
+    avg_gain /= period as f64;
+    avg_loss /= period as f64;
+    out[period] = Some(rsi_from(avg_gain, avg_loss));
+
+    for i in (period + 1)..values.len() {
+        let diff = values[i] - values[i - 1];
+        let (gain, loss) = if diff > 0.0 {
+            (diff, 0.0)
+        } else {
+            (0.0, -diff)
+        };
+        avg_gain = (avg_gain * (period as f64 - 1.0) + gain) / period as f64;
+        avg_loss = (avg_loss * (period as f64 - 1.0) + loss) / period as f64;
+        out[i] = Some(rsi_from(avg_gain, avg_loss));
+    }
+    out
+}
+
+/// Three aligned series: MACD line/signal/histogram or Bollinger middle/upper/lower.
+pub type SeriesTriple = (Vec<Option<f64>>, Vec<Option<f64>>, Vec<Option<f64>>);
+
+/// Computes the MACD line (fast EMA - slow EMA), its signal EMA, and the histogram.
+///
+/// The signal line starts once `signal` MACD values exist.
+pub fn macd(values: &[f64], fast: usize, slow: usize, signal: usize) -> SeriesTriple {
+    let fast_ema = ema(values, fast);
+    let slow_ema = ema(values, slow);
+    let line: Vec<Option<f64>> = fast_ema
+        .iter()
+        .zip(slow_ema.iter())
+        .map(|(fast, slow)| Some((*fast)? - (*slow)?))
+        .collect();
+
+    let mut signal_line = vec![None; values.len()];
+    if let Some(start) = line.iter().position(Option::is_some) {
+        let defined: Vec<f64> = line[start..].iter().flatten().copied().collect();
+        for (offset, value) in ema(&defined, signal).into_iter().enumerate() {
+            signal_line[start + offset] = value;
+        }
+    }
+    let histogram = line
+        .iter()
+        .zip(signal_line.iter())
+        .map(|(line, signal)| Some((*line)? - (*signal)?))
+        .collect();
+    (line, signal_line, histogram)
+}
+
+/// Bollinger bands: middle SMA and upper/lower bands at `std_dev` population deviations.
+pub fn bollinger(values: &[f64], period: usize, std_dev: f64) -> SeriesTriple {
+    let middle = sma(values, period);
+    let mut upper = vec![None; values.len()];
+    let mut lower = vec![None; values.len()];
+    for (i, mean) in middle.iter().enumerate() {
+        let Some(mean) = *mean else {
+            continue;
+        };
+        let window = &values[i + 1 - period..=i];
+        let variance = window
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / period as f64;
+        let width = variance.sqrt() * std_dev;
+        upper[i] = Some(mean + width);
+        lower[i] = Some(mean - width);
+    }
+    (middle, upper, lower)
+}
+
+/// Session boundary for VWAP accumulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VwapReset {
+    /// Restart at `hour` UTC every day.
+    Daily { hour: u32 },
+    /// Accumulate across the whole series.
+    Never,
+}
+
+// Internal helper that maps a timestamp to its VWAP session key.
+fn vwap_session(timestamp: DateTime<Utc>, reset: VwapReset) -> Option<NaiveDate> {
+    match reset {
+        VwapReset::Daily { hour } => {
+            Some((timestamp - chrono::Duration::hours(i64::from(hour))).date_naive())
+        }
+        VwapReset::Never => None,
+    }
+}
+
+/// Volume-weighted average price over `(timestamp, typical_price, volume)` candles.
+///
+/// Sums reset at each session boundary. Candles with zero (or invalid) volume carry the
+/// prior VWAP of the session forward instead of contributing; a session with no volume yet
+/// yields `None`.
+pub fn vwap(candles: &[(DateTime<Utc>, f64, f64)], reset: VwapReset) -> Vec<Option<f64>> {
+    let mut out = Vec::with_capacity(candles.len());
+    let mut session = None;
+    let mut price_volume = 0.0;
+    let mut volume_sum = 0.0;
+    let mut current = None;
+    for (index, (timestamp, typical, volume)) in candles.iter().enumerate() {
+        let candle_session = vwap_session(*timestamp, reset);
+        if index == 0 || candle_session != session {
+            session = candle_session;
+            price_volume = 0.0;
+            volume_sum = 0.0;
+            current = None;
+        }
+        if volume.is_finite() && *volume > 0.0 && typical.is_finite() {
+            price_volume += typical * volume;
+            volume_sum += volume;
+            current = Some(price_volume / volume_sum);
+        }
+        out.push(current);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Internal helper that supports `assert_close` operations.
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("value present");
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `sma_returns_nulls_for_leading_window` operations.
+    fn sma_returns_nulls_for_leading_window() {
+        let out = sma(&[1.0, 2.0, 3.0, 4.0, 5.0], 3);
+        assert_eq!(out[0], None);
+        assert_eq!(out[1], None);
+        assert_close(out[2], 2.0);
+        assert_close(out[4], 4.0);
+        assert!(sma(&[1.0, 2.0], 5).iter().all(Option::is_none));
+    }
+
+    #[test]
+    // Internal helper that supports `ema_seeds_with_sma` operations.
+    fn ema_seeds_with_sma() {
+        let out = ema(&[2.0, 4.0, 6.0, 8.0], 3);
+        assert_eq!(out[1], None);
+        assert_close(out[2], 4.0);
+        // multiplier 0.5: (8 - 4) * 0.5 + 4
+        assert_close(out[3], 6.0);
+    }
+
+    #[test]
+    // Internal helper that supports `rsi_matches_known_fixture` operations.
+    fn rsi_matches_known_fixture() {
+        // StockCharts sample closes (period 14), Wilder-smoothed.
+        let closes = [
+            44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03,
+            45.61, 46.28, 46.28, 46.00,
+        ];
+        let out = rsi(&closes, 14);
+        assert_eq!(out[13], None);
+        assert!((out[14].unwrap() - 70.464).abs() < 0.01);
+        assert!((out[15].unwrap() - 66.250).abs() < 0.01);
+    }
+
+    #[test]
+    // Internal helper that supports `rsi_is_100_without_losses` operations.
+    fn rsi_is_100_without_losses() {
+        let out = rsi(&[1.0, 2.0, 3.0, 4.0], 2);
+        assert_close(out[2], 100.0);
+        assert_close(out[3], 100.0);
+    }
+
+    #[test]
+    // Internal helper that supports `macd_aligns_signal_after_slow_window` operations.
+    fn macd_aligns_signal_after_slow_window() {
+        let closes: Vec<f64> = (1..=10).map(f64::from).collect();
+        let (line, signal, histogram) = macd(&closes, 2, 4, 3);
+        assert_eq!(line[2], None);
+        // Linear series: EMA lag is (period - 1) / 2, so line = 1.5 - 0.5 = 1.0.
+        assert_close(line[3], 1.0);
+        assert_eq!(signal[4], None);
+        assert_close(signal[5], 1.0);
+        assert_close(histogram[9], 0.0);
+    }
+
+    #[test]
+    // Internal helper that supports `bollinger_uses_population_std_dev` operations.
+    fn bollinger_uses_population_std_dev() {
+        let (middle, upper, lower) = bollinger(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0], 8, 2.0);
+        assert_eq!(middle[6], None);
+        assert_close(middle[7], 5.0);
+        assert_close(upper[7], 9.0);
+        assert_close(lower[7], 1.0);
+    }
+
+    #[test]
+    // Internal helper that supports `vwap_carries_forward_on_zero_volume_and_resets_daily` operations.
+    fn vwap_carries_forward_on_zero_volume_and_resets_daily() {
+        use chrono::TimeZone;
+        let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2024, 1, day, hour, 0, 0).unwrap();
+        let candles = [
+            (at(1, 0), 10.0, 0.0),
+            (at(1, 1), 10.0, 2.0),
+            (at(1, 2), 16.0, 0.0),
+            (at(1, 3), 13.0, 1.0),
+            (at(2, 0), 20.0, 1.0),
+            (at(2, 1), 99.0, 0.0),
+        ];
+        let daily = vwap(&candles, VwapReset::Daily { hour: 0 });
+        assert_eq!(daily[0], None);
+        assert_close(daily[1], 10.0);
+        // Zero volume keeps the prior VWAP instead of pulling towards 16.
+        assert_close(daily[2], 10.0);
+        // (10 * 2 + 13 * 1) / 3
+        assert_close(daily[3], 11.0);
+        // New session starts from the first candle of day 2.
+        assert_close(daily[4], 20.0);
+        assert_close(daily[5], 20.0);
+
+        let cumulative = vwap(&candles, VwapReset::Never);
+        // (20 + 13 + 20) / 4
+        assert_close(cumulative[4], 13.25);
+
+        // With a 02:00 UTC boundary, day 2 00:00 joins the session opened at day 1 02:00.
+        let shifted = vwap(&candles, VwapReset::Daily { hour: 2 });
+        assert_close(shifted[1], 10.0);
+        assert_eq!(shifted[2], None);
+        // (13 * 1 + 20 * 1) / 2
+        assert_close(shifted[4], 16.5);
+    }
+
+    #[test]
+    // Internal helper that supports `parse_list_handles_params_and_defaults` operations.
+    fn parse_list_handles_params_and_defaults() {
+        let specs = IndicatorSpec::parse_list(Some("sma:50, macd, bollinger:20:2.5")).unwrap();
+        assert_eq!(
+            specs,
+            vec![
+                IndicatorSpec::Sma { period: 50 },
+                IndicatorSpec::Macd {
+                    fast: 12,
+                    slow: 26,
+                    signal: 9
+                },
+                IndicatorSpec::Bollinger {
+                    period: 20,
+                    std_dev: 2.5
+                },
+            ]
+        );
+        assert_eq!(IndicatorSpec::parse_list(None).unwrap().len(), 3);
+        assert!(IndicatorSpec::parse("vwap").is_err());
+        assert!(IndicatorSpec::parse("sma:0").is_err());
+        assert!(IndicatorSpec::parse("macd:26:12").is_err());
+    }
+}
@@ -1,15 +1,18 @@
-use crate::services::onchain::{parse_felt, resolve_backend_account, OnchainInvoker};
+use crate::services::onchain::{parse_felt, resolve_backend_account};
 use crate::{
     config::Config,
     constants::{DEX_EKUBO, ORDER_EXECUTOR_INTERVAL_SECS},
     db::Database,
-    error::Result,
-    models::{LimitOrder, Transaction},
+    error::{AppError, Result},
+    models::{LimitOrder, OrderStatus, Transaction},
+    services::liquidity_aggregator::{LiquidityAggregator, SwapRoute},
     services::price_guard::{
         fallback_price_for, first_sane_price, sanitize_usd_notional, symbol_candidates_for,
     },
+    services::relayer::RelayerService,
 };
-use rust_decimal::prelude::ToPrimitive; // Penting untuk f64 conversion
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive}; // Penting untuk f64 conversion
+use rust_decimal::Decimal;
 use sqlx::Row; // Penting untuk .get()
 use starknet_core::types::{Call, Felt};
 use starknet_core::utils::get_selector_from_name;
@@ -23,11 +26,101 @@ fn is_order_expired(
     now > expiry
 }
 
+const STABLE_SYMBOLS: [&str; 3] = ["USDT", "USDC", "DAI"];
+
+/// Direction of a limit order relative to its non-stable token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OrderSide {
+    // Pays a stablecoin for `to_token`; `price` is the max `to_token` price in `from_token`.
+    Buy,
+    // Sells `from_token`; `price` is the min `from_token` price in `to_token`.
+    Sell,
+}
+
+// Internal helper that supports `order_side` operations.
+fn order_side(from_token: &str) -> OrderSide {
+    if STABLE_SYMBOLS.contains(&from_token.trim().to_ascii_uppercase().as_str()) {
+        OrderSide::Buy
+    } else {
+        OrderSide::Sell
+    }
+}
+
+// Internal helper that returns the market price quoted the same way as the order `price`.
+fn quoted_market_price(side: OrderSide, from_price_usd: f64, to_price_usd: f64) -> Option<f64> {
+    let (base, quote) = match side {
+        OrderSide::Buy => (to_price_usd, from_price_usd),
+        OrderSide::Sell => (from_price_usd, to_price_usd),
+    };
+    if !base.is_finite() || !quote.is_finite() || base <= 0.0 || quote <= 0.0 {
+        return None;
+    }
+    Some(base / quote)
+}
+
 // Internal helper that checks conditions for `should_execute_price`.
 fn should_execute_price(current_price: f64, target_price: f64) -> bool {
     current_price <= target_price * 1.005
 }
 
+// Internal helper that checks conditions for `is_price_triggered`.
+fn is_price_triggered(side: OrderSide, market_price: f64, target_price: f64) -> bool {
+    if !market_price.is_finite() || target_price <= 0.0 {
+        return false;
+    }
+    match side {
+        OrderSide::Buy => should_execute_price(market_price, target_price),
+        OrderSide::Sell => market_price >= target_price * 0.995,
+    }
+}
+
+// Internal helper that returns how `order` quotes prices; conditional orders always sell `from_token`.
+fn order_execution_side(order: &LimitOrder) -> OrderSide {
+    if order.order_type.is_conditional() {
+        OrderSide::Sell
+    } else {
+        order_side(&order.from_token)
+    }
+}
+
+// Internal helper that checks conditions for `is_order_triggered`.
+fn is_order_triggered(order: &LimitOrder, side: OrderSide, market_price: f64) -> bool {
+    if !order.order_type.is_conditional() {
+        // Konversi Decimal ke f64 dengan ToPrimitive
+        let target_price_f64 = order.price.to_f64().unwrap_or(0.0);
+        return is_price_triggered(side, market_price, target_price_f64);
+    }
+    // A partially filled conditional order has already fired; keep selling the remainder at market.
+    if order.status == OrderStatus::Partial {
+        return true;
+    }
+    let trigger_price = order
+        .trigger_price
+        .and_then(|value| value.to_f64())
+        .unwrap_or(0.0);
+    order.order_type.is_triggered(market_price, trigger_price)
+}
+
+// Internal helper that converts an input amount into the output amount implied by `price`.
+fn expected_amount_out(side: OrderSide, amount_in: Decimal, price: Decimal) -> Decimal {
+    match side {
+        OrderSide::Buy if price > Decimal::ZERO => amount_in / price,
+        OrderSide::Buy => Decimal::ZERO,
+        OrderSide::Sell => amount_in * price,
+    }
+}
+
+// Internal helper that clamps the remaining order size to the liquidity the routes can absorb.
+fn fillable_amount(remaining: Decimal, routes: &[SwapRoute]) -> Decimal {
+    let routed: f64 = routes
+        .iter()
+        .map(|route| route.amount_in)
+        .filter(|amount| amount.is_finite() && *amount > 0.0)
+        .sum();
+    let routed = Decimal::from_f64(routed).unwrap_or(Decimal::ZERO);
+    remaining.min(routed).max(Decimal::ZERO)
+}
+
 // Internal helper that checks conditions for `is_unauthorized_keeper_error`.
 fn is_unauthorized_keeper_error(message: &str) -> bool {
     message.to_ascii_lowercase().contains("unauthorized keeper")
@@ -42,6 +135,15 @@ fn to_u256_felts(value: f64) -> (Felt, Felt) {
     (Felt::from(scaled), Felt::from(0_u128))
 }
 
+// Internal helper that builds the u256 fill amount passed to `execute_limit_order`.
+// The contract expects output units, so Buy orders divide by the price instead of multiplying.
+fn execution_fill_felts(side: OrderSide, amount_in: Decimal, fill_price: Decimal) -> (Felt, Felt) {
+    let fill_value = expected_amount_out(side, amount_in, fill_price)
+        .to_f64()
+        .unwrap_or(0.0);
+    to_u256_felts(fill_value)
+}
+
 // Internal helper that parses or transforms values for `normalize_usd_volume`.
 fn normalize_usd_volume(usd_in: f64, usd_out: f64) -> f64 {
     let in_valid = usd_in.is_finite() && usd_in > 0.0;
@@ -54,9 +156,19 @@ fn normalize_usd_volume(usd_in: f64, usd_out: f64) -> f64 {
     }
 }
 
+/// Fill prepared for a triggered order on the current tick.
+struct PlannedFill {
+    amount_in: Decimal,
+    market_price: f64,
+    // Limit price for limit orders, market price for conditional (market) executions.
+    fill_price: Decimal,
+    route: String,
+}
+
 pub struct LimitOrderExecutor {
     db: Database,
     config: Config,
+    aggregator: LiquidityAggregator,
 }
 
 impl LimitOrderExecutor {
@@ -72,7 +184,12 @@ impl LimitOrderExecutor {
     /// # Notes
     /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
     pub fn new(db: Database, config: Config) -> Self {
-        Self { db, config }
+        let aggregator = LiquidityAggregator::new(config.clone());
+        Self {
+            db,
+            config,
+            aggregator,
+        }
     }
 
     /// Start limit order monitoring loop
@@ -92,9 +209,9 @@ impl LimitOrderExecutor {
         });
     }
 
-    /// Check all active orders and execute if price matches
+    /// Check all open orders and execute the ones whose trigger price is reached
     async fn check_and_execute_orders(&self) -> Result<()> {
-        let orders = self.get_active_orders().await?;
+        let orders = self.get_open_orders().await?;
 
         for order in orders {
             if is_order_expired(chrono::Utc::now(), order.expiry) {
@@ -102,21 +219,27 @@ impl LimitOrderExecutor {
                 continue;
             }
 
-            if let Ok(should_execute) = self.should_execute_order(&order).await {
-                if should_execute {
-                    match self.execute_order(&order).await {
-                        Ok(_) => {
-                            tracing::info!("Executed limit order: {}", order.order_id);
-                        }
-                        Err(e) => {
-                            tracing::error!("Failed to execute order {}: {}", order.order_id, e);
-                            if is_unauthorized_keeper_error(&e.to_string()) {
-                                tracing::error!(
-                                    "Limit order executor paused for this cycle because backend signer is not a registered keeper."
-                                );
-                                break;
-                            }
-                        }
+            let plan = match self.plan_fill(&order).await {
+                Ok(plan) => plan,
+                Err(e) => {
+                    tracing::warn!("Skipping limit order {} this tick: {}", order.order_id, e);
+                    continue;
+                }
+            };
+            let Some(plan) = plan else {
+                continue;
+            };
+            match self.execute_order(&order, &plan).await {
+                Ok(_) => {
+                    tracing::info!("Executed limit order: {}", order.order_id);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to execute order {}: {}", order.order_id, e);
+                    if is_unauthorized_keeper_error(&e.to_string()) {
+                        tracing::error!(
+                            "Limit order executor paused for this cycle because backend signer is not a registered keeper."
+                        );
+                        break;
                     }
                 }
             }
@@ -137,35 +260,78 @@ impl LimitOrderExecutor {
         Ok(())
     }
 
-    /// Get all active limit orders (Ganti query_as! ke query_as)
-    async fn get_active_orders(&self) -> Result<Vec<LimitOrder>> {
+    /// Get active and partially filled orders, including expired ones so they can be closed
+    async fn get_open_orders(&self) -> Result<Vec<LimitOrder>> {
         let orders = sqlx::query_as::<_, LimitOrder>(
             "SELECT * FROM limit_orders
-             WHERE status = 0 
-             AND expiry > NOW()
+             WHERE status IN ($1, $2)
              ORDER BY created_at ASC",
         )
+        .bind(i16::from(OrderStatus::Active))
+        .bind(i16::from(OrderStatus::Partial))
         .fetch_all(self.db.pool())
         .await?;
 
         Ok(orders)
     }
 
-    // Internal helper that checks conditions for `should_execute_order`.
-    async fn should_execute_order(&self, order: &LimitOrder) -> Result<bool> {
-        let current_price = self
-            .get_current_price(&order.from_token, &order.to_token)
-            .await?;
+    // Internal helper that decides whether `order` triggers now and how much of it can be filled.
+    async fn plan_fill(&self, order: &LimitOrder) -> Result<Option<PlannedFill>> {
+        let remaining = order.amount - order.filled;
+        if remaining <= Decimal::ZERO {
+            return Ok(None);
+        }
 
-        // Konversi Decimal ke f64 dengan ToPrimitive
-        let target_price_f64 = order.price.to_f64().unwrap_or(0.0);
+        let side = order_execution_side(order);
+        let from_price_usd = self.latest_price_usd(&order.from_token).await?;
+        let to_price_usd = self.latest_price_usd(&order.to_token).await?;
+        let Some(market_price) = quoted_market_price(side, from_price_usd, to_price_usd) else {
+            return Ok(None);
+        };
+        if !is_order_triggered(order, side, market_price) {
+            return Ok(None);
+        }
+        let fill_price = if order.order_type.is_conditional() {
+            Decimal::from_f64(market_price).unwrap_or(order.price)
+        } else {
+            order.price
+        };
 
-        Ok(should_execute_price(current_price, target_price_f64))
-    }
+        let routes = self
+            .aggregator
+            .get_split_quote(
+                &order.from_token,
+                &order.to_token,
+                remaining.to_f64().unwrap_or(0.0),
+            )
+            .await?;
+        let amount_in = fillable_amount(remaining, &routes);
+        if amount_in <= Decimal::ZERO {
+            return Err(AppError::InsufficientLiquidity);
+        }
+        if amount_in < remaining {
+            tracing::info!(
+                "Limit order {} partially fillable: {} of {} remaining",
+                order.order_id,
+                amount_in,
+                remaining
+            );
+        }
 
-    // Internal helper that fetches data for `get_current_price`.
-    async fn get_current_price(&self, _from_token: &str, _to_token: &str) -> Result<f64> {
-        Ok(65000.0)
+        let route = routes
+            .iter()
+            .max_by(|a, b| a.amount_in.total_cmp(&b.amount_in))
+            .map(|route| route.dex.clone());
+        let route = match route {
+            Some(route) => route,
+            None => self.get_best_execution_route(order).await?,
+        };
+        Ok(Some(PlannedFill {
+            amount_in,
+            market_price,
+            fill_price,
+            route,
+        }))
     }
 
     // Internal helper that supports `latest_price_usd` operations.
@@ -185,14 +351,17 @@ impl LimitOrderExecutor {
         Ok(fallback_price_for(&symbol))
     }
 
-    /// Execute limit order (Ganti query! ke query)
-    async fn execute_order(&self, order: &LimitOrder) -> Result<()> {
-        let route = self.get_best_execution_route(order).await?;
-        let tx_hash = self.execute_swap_on_chain(order, &route).await?;
+    /// Execute a planned (possibly partial) fill; the remainder is retried on the next tick
+    async fn execute_order(&self, order: &LimitOrder, plan: &PlannedFill) -> Result<()> {
+        let side = order_execution_side(order);
+        let filled_amount = plan.amount_in;
+        let filled_out = expected_amount_out(side, filled_amount, plan.fill_price);
+        let tx_hash = self
+            .execute_swap_on_chain(order, filled_amount, plan.fill_price, &plan.route)
+            .await?;
 
-        let filled_amount = order.amount - order.filled;
         let amount_in = filled_amount.to_f64().unwrap_or(0.0);
-        let amount_out = (filled_amount * order.price).to_f64().unwrap_or(0.0);
+        let amount_out = filled_out.to_f64().unwrap_or(0.0);
         let from_price_usd = self.latest_price_usd(&order.from_token).await?;
         let to_price_usd = self.latest_price_usd(&order.to_token).await?;
         let usd_value = sanitize_usd_notional(normalize_usd_volume(
@@ -209,7 +378,7 @@ impl LimitOrderExecutor {
         .bind(&order.order_id)
         .bind("keeper")
         .bind(filled_amount)
-        .bind(order.price)
+        .bind(Decimal::from_f64(plan.market_price).unwrap_or(plan.fill_price))
         .bind(&tx_hash)
         .execute(self.db.pool())
         .await?;
@@ -222,7 +391,7 @@ impl LimitOrderExecutor {
             token_in: Some(order.from_token.clone()),
             token_out: Some(order.to_token.clone()),
             amount_in: Some(filled_amount),
-            amount_out: Some(filled_amount * order.price),
+            amount_out: Some(filled_out),
             usd_value: Some(rust_decimal::Decimal::from_f64_retain(usd_value).unwrap_or_default()),
             fee_paid: None,
             points_earned: Some(rust_decimal::Decimal::ZERO),
@@ -236,9 +405,9 @@ impl LimitOrderExecutor {
             order.order_id,
             filled_amount,
             order.from_token,
-            filled_amount * order.price,
+            filled_out,
             order.to_token,
-            order.price
+            plan.fill_price
         );
 
         Ok(())
@@ -250,32 +419,33 @@ impl LimitOrderExecutor {
     }
 
     // Internal helper that runs side-effecting logic for `execute_swap_on_chain`.
-    async fn execute_swap_on_chain(&self, order: &LimitOrder, _route: &str) -> Result<String> {
+    async fn execute_swap_on_chain(
+        &self,
+        order: &LimitOrder,
+        amount_in: Decimal,
+        fill_price: Decimal,
+        _route: &str,
+    ) -> Result<String> {
         let contract = self.config.limit_order_book_address.trim();
         if contract.is_empty() || contract.starts_with("0x0000") {
             return Err(crate::error::AppError::BadRequest(
                 "LIMIT_ORDER_BOOK_ADDRESS is not configured".to_string(),
             ));
         }
-        let Some(invoker) = OnchainInvoker::from_config(&self.config).ok().flatten() else {
-            return Err(crate::error::AppError::BadRequest(
-                "Backend on-chain invoker is not configured".to_string(),
-            ));
-        };
+        let relayer = RelayerService::from_config(&self.config)?;
 
         let to = parse_felt(contract)?;
         let selector = get_selector_from_name("execute_limit_order")
             .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
         let order_id = parse_felt(&order.order_id)?;
-        let order_value = (order.amount * order.price).to_f64().unwrap_or(0.0);
-        let amount_u256 = to_u256_felts(order_value);
+        let amount_u256 = execution_fill_felts(order_execution_side(order), amount_in, fill_price);
         let call = Call {
             to,
             selector,
             calldata: vec![order_id, amount_u256.0, amount_u256.1],
         };
-        let tx_hash = match invoker.invoke(call).await {
-            Ok(value) => value,
+        let tx_hash = match relayer.submit_call(call).await {
+            Ok(value) => value.tx_hash,
             Err(error) => {
                 let text = error.to_string();
                 if is_unauthorized_keeper_error(&text) {
@@ -290,19 +460,14 @@ impl LimitOrderExecutor {
                 return Err(error);
             }
         };
-        Ok(tx_hash.to_string())
+        Ok(tx_hash)
     }
 
     // Internal helper that supports `expire_order` operations.
     async fn expire_order(&self, order_id: &str) -> Result<()> {
-        sqlx::query(
-            "UPDATE limit_orders
-             SET status = 4
-             WHERE order_id = $1",
-        )
-        .bind(order_id)
-        .execute(self.db.pool())
-        .await?;
+        self.db
+            .update_order_status(order_id, OrderStatus::Expired)
+            .await?;
 
         tracing::info!("Order {} expired", order_id);
         Ok(())
@@ -312,12 +477,15 @@ impl LimitOrderExecutor {
     pub async fn get_executor_stats(&self) -> Result<ExecutorStats> {
         let row = sqlx::query(
             "SELECT 
-                COUNT(*) FILTER (WHERE status = 0) as active_orders,
-                COUNT(*) FILTER (WHERE status = 2) as filled_orders,
-                COUNT(*) FILTER (WHERE status = 4) as expired_orders,
+                COUNT(*) FILTER (WHERE status = $1) as active_orders,
+                COUNT(*) FILTER (WHERE status = $2) as filled_orders,
+                COUNT(*) FILTER (WHERE status = $3) as expired_orders,
                 COUNT(*) as total_orders
              FROM limit_orders",
         )
+        .bind(i16::from(OrderStatus::Active))
+        .bind(i16::from(OrderStatus::Filled))
+        .bind(i16::from(OrderStatus::Expired))
         .fetch_one(self.db.pool())
         .await?;
 
@@ -341,6 +509,7 @@ pub struct ExecutorStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::OrderType;
     use chrono::{TimeZone, Utc};
 
     #[test]
@@ -360,4 +529,102 @@ mod tests {
         assert!(should_execute_price(100.4, 100.0));
         assert!(!should_execute_price(101.0, 100.0));
     }
+
+    #[test]
+    // Internal helper that checks conditions for `is_price_triggered_respects_order_side`.
+    fn is_price_triggered_respects_order_side() {
+        assert_eq!(order_side("usdc"), OrderSide::Buy);
+        assert_eq!(order_side("ETH"), OrderSide::Sell);
+        // Buy ETH with USDC at <= 2000
+        let buy_market = quoted_market_price(OrderSide::Buy, 1.0, 1990.0).unwrap();
+        assert!(is_price_triggered(OrderSide::Buy, buy_market, 2000.0));
+        assert!(!is_price_triggered(OrderSide::Buy, 2100.0, 2000.0));
+        // Sell ETH for USDC at >= 2000
+        let sell_market = quoted_market_price(OrderSide::Sell, 2010.0, 1.0).unwrap();
+        assert!(is_price_triggered(OrderSide::Sell, sell_market, 2000.0));
+        assert!(!is_price_triggered(OrderSide::Sell, 1900.0, 2000.0));
+        assert!(quoted_market_price(OrderSide::Sell, 0.0, 1.0).is_none());
+    }
+
+    #[test]
+    // Internal helper that checks conditions for `is_order_triggered_handles_conditional_orders`.
+    fn is_order_triggered_handles_conditional_orders() {
+        let now = Utc.timestamp_opt(1_000, 0).unwrap();
+        let mut order = LimitOrder {
+            order_id: "0x1".to_string(),
+            owner: "0xowner".to_string(),
+            from_token: "ETH".to_string(),
+            to_token: "USDC".to_string(),
+            amount: Decimal::from(1),
+            filled: Decimal::ZERO,
+            price: Decimal::from(2000),
+            expiry: now,
+            recipient: None,
+            status: OrderStatus::Active,
+            created_at: now,
+            order_type: OrderType::StopLoss,
+            trigger_price: Some(Decimal::from(1800)),
+        };
+        assert_eq!(order_execution_side(&order), OrderSide::Sell);
+        assert!(is_order_triggered(&order, OrderSide::Sell, 1790.0));
+        assert!(!is_order_triggered(&order, OrderSide::Sell, 1900.0));
+        order.status = OrderStatus::Partial;
+        assert!(is_order_triggered(&order, OrderSide::Sell, 1900.0));
+
+        order.status = OrderStatus::Active;
+        order.order_type = OrderType::TakeProfit;
+        order.trigger_price = Some(Decimal::from(2200));
+        assert!(is_order_triggered(&order, OrderSide::Sell, 2250.0));
+        assert!(!is_order_triggered(&order, OrderSide::Sell, 2100.0));
+    }
+
+    #[test]
+    // Internal helper that supports `fillable_amount_caps_to_routed_liquidity` operations.
+    fn fillable_amount_caps_to_routed_liquidity() {
+        let route = |amount_in: f64| SwapRoute {
+            dex: "dex".to_string(),
+            amount_in,
+            amount_out: amount_in,
+            price_impact: 0.0,
+            fee: 0.0,
+            path: vec![],
+            score: 0.0,
+        };
+        let remaining = Decimal::from(100);
+        assert_eq!(
+            fillable_amount(remaining, &[route(30.0), route(20.0)]),
+            Decimal::from(50)
+        );
+        assert_eq!(fillable_amount(remaining, &[route(500.0)]), remaining);
+        assert_eq!(fillable_amount(remaining, &[]), Decimal::ZERO);
+    }
+
+    #[test]
+    // Internal helper that supports `expected_amount_out_uses_side_quote` operations.
+    fn expected_amount_out_uses_side_quote() {
+        let price = Decimal::from(2000);
+        assert_eq!(
+            expected_amount_out(OrderSide::Sell, Decimal::from(2), price),
+            Decimal::from(4000)
+        );
+        assert_eq!(
+            expected_amount_out(OrderSide::Buy, Decimal::from(4000), price),
+            Decimal::from(2)
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `execution_fill_felts_inverts_price_for_buy_orders` operations.
+    fn execution_fill_felts_inverts_price_for_buy_orders() {
+        let price = Decimal::from(2000);
+        // Buying ETH with 4000 USDC at 2000 fills 2 ETH, not 8,000,000.
+        assert_eq!(
+            execution_fill_felts(OrderSide::Buy, Decimal::from(4000), price),
+            (Felt::from(2_000_000_000_000_000_000_u128), Felt::ZERO)
+        );
+        assert_eq!(
+            execution_fill_felts(OrderSide::Sell, Decimal::from(2), price),
+            (Felt::from(4_000_000_000_000_000_000_000_u128), Felt::ZERO)
+        );
+    }
 }
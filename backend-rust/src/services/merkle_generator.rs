@@ -147,6 +147,44 @@ impl MerkleGenerator {
         if self.config.is_testnet() {
             tracing::debug!("Generating merkle tree in testnet mode");
         }
+        let entries = self
+            .leaf_entries_for_epoch(epoch, total_distribution)
+            .await?;
+
+        // Build merkle tree
+        let tree = self.build_merkle_tree(entries.iter().map(|entry| entry.leaf).collect())?;
+
+        tracing::info!(
+            "Merkle tree generated for epoch {}: {} users, root: {}",
+            epoch,
+            entries.len(),
+            tree.root.to_fixed_hex_string()
+        );
+
+        Ok(tree)
+    }
+
+    /// Returns the epoch's leaf entries together with the tree built from them.
+    pub async fn epoch_leaves_and_tree(
+        &self,
+        epoch: i64,
+    ) -> Result<(Vec<MerkleLeafEntry>, MerkleTree)> {
+        let entries = self
+            .leaf_entries_for_epoch(epoch, self.default_distribution_pool())
+            .await?;
+        let tree = self.build_merkle_tree(entries.iter().map(|entry| entry.leaf).collect())?;
+        Ok((entries, tree))
+    }
+
+    /// Computes the per-user leaves (address, points, reward amount, leaf hash) for an epoch.
+    ///
+    /// # Notes
+    /// * Ordered by address; the tree itself sorts leaf hashes, so order does not affect the root.
+    pub async fn leaf_entries_for_epoch(
+        &self,
+        epoch: i64,
+        total_distribution: Decimal,
+    ) -> Result<Vec<MerkleLeafEntry>> {
         // Menggunakan runtime query untuk menghindari error DATABASE_URL
         let rows = sqlx::query(
             "SELECT user_address, total_points FROM points
@@ -163,12 +201,8 @@ impl MerkleGenerator {
             ));
         }
 
-        // Calculate total points for proportional distribution
-        let mut total_points_dec = rust_decimal::Decimal::ZERO;
-        for row in &rows {
-            let points: rust_decimal::Decimal = row.get("total_points");
-            total_points_dec += points;
-        }
+        // Redeemed points stay in the denominator so spends don't grow other users' shares
+        let total_points_dec = self.db.reward_points_total(epoch).await?;
         if total_points_dec == Decimal::ZERO {
             return Err(crate::error::AppError::NotFound(
                 "Total points is zero".to_string(),
@@ -176,7 +210,7 @@ impl MerkleGenerator {
         }
 
         // Create leaves: poseidon(user, amount_wei, epoch)
-        let mut leaves: Vec<Felt> = Vec::new();
+        let mut entries = Vec::with_capacity(rows.len());
         for row in &rows {
             let address: String = row.get("user_address");
             let points: rust_decimal::Decimal = row.get("total_points");
@@ -188,20 +222,15 @@ impl MerkleGenerator {
             );
 
             let leaf = self.create_leaf(&address, amount_wei, epoch)?;
-            leaves.push(leaf);
+            entries.push(MerkleLeafEntry {
+                address,
+                points,
+                amount_wei,
+                leaf,
+            });
         }
 
-        // Build merkle tree
-        let tree = self.build_merkle_tree(leaves)?;
-
-        tracing::info!(
-            "Merkle tree generated for epoch {}: {} users, root: {}",
-            epoch,
-            rows.len(),
-            tree.root.to_fixed_hex_string()
-        );
-
-        Ok(tree)
+        Ok(entries)
     }
 
     /// Handles `calculate_reward_amount_wei` logic.
@@ -372,6 +401,14 @@ impl MerkleGenerator {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct MerkleLeafEntry {
+    pub address: String,
+    pub points: Decimal,
+    pub amount_wei: u128,
+    pub leaf: Felt,
+}
+
 #[derive(Debug, Clone)]
 pub struct MerkleTree {
     pub root: Felt,
@@ -1,31 +1,43 @@
 // All service modules
+pub mod achievement_service;
 pub mod ai_service;
 pub mod analytics_service;
 pub mod deposit_service;
+pub mod discount_service;
 pub mod event_indexer;
 pub mod faucet_service;
+pub mod garden_earnings;
 pub mod gas_optimizer;
+pub mod hide_executor;
+pub mod indicators;
 pub mod limit_order_executor;
 pub mod liquidity_aggregator;
 pub mod merkle_generator;
 pub mod nft_discount;
 pub mod notification_service;
 pub mod onchain;
+pub mod order_events;
 pub mod point_calculator;
 pub mod price_chart_service;
 pub mod price_guard;
+pub mod privacy_audit;
 pub mod privacy_verifier;
+pub mod public_input_schema;
 pub mod relayer;
 pub mod route_optimizer;
 pub mod snapshot_manager;
 pub mod social_verifier;
+pub mod staking_discount;
 pub mod transaction_history;
+pub mod tx_reconciler;
 pub mod webhook_service;
 
 // Re-export for convenience
+pub use achievement_service::AchievementService;
 pub use analytics_service::AnalyticsService;
 pub use deposit_service::DepositService;
 pub use event_indexer::EventIndexer;
+pub use garden_earnings::GardenEarningsTracker;
 pub use limit_order_executor::LimitOrderExecutor;
 pub use liquidity_aggregator::LiquidityAggregator;
 pub use merkle_generator::MerkleGenerator;
@@ -36,6 +48,7 @@ pub use route_optimizer::RouteOptimizer;
 pub use snapshot_manager::SnapshotManager;
 pub use social_verifier::SocialVerifier;
 pub use transaction_history::TransactionHistoryService;
+pub use tx_reconciler::TransactionReconciler;
 pub use webhook_service::WebhookService;
 
 use crate::{config::Config, db::Database};
@@ -53,16 +66,20 @@ fn is_env_flag_enabled(name: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// Whether the event indexer runs (`ENABLE_EVENT_INDEXER`, on unless explicitly disabled).
+pub fn event_indexer_enabled() -> bool {
+    if std::env::var("ENABLE_EVENT_INDEXER").is_ok() {
+        is_env_flag_enabled("ENABLE_EVENT_INDEXER")
+    } else {
+        true
+    }
+}
+
 /// Start all background services
 pub async fn start_background_services(db: Database, config: Config) {
     tracing::info!("Starting background services...");
 
-    let enable_event_indexer = if std::env::var("ENABLE_EVENT_INDEXER").is_ok() {
-        is_env_flag_enabled("ENABLE_EVENT_INDEXER")
-    } else {
-        true
-    };
-    if enable_event_indexer {
+    if event_indexer_enabled() {
         let event_indexer = Arc::new(EventIndexer::new(db.clone(), config.clone()));
         event_indexer.clone().start().await;
     } else {
@@ -81,10 +98,31 @@ pub async fn start_background_services(db: Database, config: Config) {
     let order_executor = Arc::new(LimitOrderExecutor::new(db.clone(), config.clone()));
     order_executor.clone().start_executor().await;
 
-    // Snapshot manager (optional one-off jobs)
-    let snapshot_manager = SnapshotManager::new(db.clone(), config.clone());
+    // Start notification digest flusher
+    let notification_service = Arc::new(NotificationService::new(db.clone(), config.clone()));
+    notification_service.clone().start_digest_flusher().await;
+
+    // Start fiat deposit auto-swap worker
+    let deposit_service = Arc::new(DepositService::new(db.clone(), config.clone()));
+    deposit_service.clone().start_settlement_worker().await;
+
+    // Start achievement recompute job
+    let achievement_service = Arc::new(AchievementService::new(db.clone(), config.clone()));
+    achievement_service.clone().start().await;
+
+    // Start DB-vs-chain transaction reconciler
+    let tx_reconciler = Arc::new(TransactionReconciler::new(db.clone(), config.clone()));
+    tx_reconciler.clone().start().await;
+
+    // Start Garden app earnings snapshots
+    let earnings_tracker = Arc::new(GardenEarningsTracker::new(db.clone(), config.clone()));
+    earnings_tracker.clone().start().await;
+
+    // Snapshot manager (live leaderboard reconciler + optional one-off jobs)
+    let snapshot_manager = Arc::new(SnapshotManager::new(db.clone(), config.clone()));
     let current_epoch = snapshot_manager.get_current_epoch();
     tracing::info!("Current epoch: {}", current_epoch);
+    snapshot_manager.clone().start_live_leaderboard().await;
 
     if is_env_flag_enabled("RUN_EPOCH_JOBS") {
         tracing::info!("Running epoch finalize job...");
@@ -105,13 +143,7 @@ pub async fn start_background_services(db: Database, config: Config) {
             {
                 let address: String = row.get("user_address");
                 let points: rust_decimal::Decimal = row.get("total_points");
-                if let Ok(total_points) = sqlx::query_scalar::<_, rust_decimal::Decimal>(
-                    "SELECT COALESCE(SUM(total_points), 0) FROM points WHERE epoch = $1",
-                )
-                .bind(finalize_epoch)
-                .fetch_one(db.pool())
-                .await
-                {
+                if let Ok(total_points) = db.reward_points_total(finalize_epoch).await {
                     let amount_wei = merkle.calculate_reward_amount_wei(points, total_points);
                     let _ = merkle
                         .generate_proof(&tree, &address, amount_wei, finalize_epoch)
@@ -1,14 +1,20 @@
 use crate::{
     config::Config,
+    constants::NOTIFICATION_DIGEST_FLUSH_INTERVAL_SECS,
     db::Database,
-    error::Result,
+    error::{AppError, Result},
     models::{Notification, NotificationPreferences},
 };
+use chrono::Timelike;
+use serde::Serialize;
 use sqlx::Row;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use tokio::sync::{broadcast, RwLock}; // Pastikan digunakan di get_unread_count
 
+// Revert reasons can carry long felt dumps; keep notifications readable.
+const SWAP_FAILED_REASON_MAX_LEN: usize = 300;
+
 pub struct NotificationService {
     db: Database,
     config: Config,
@@ -55,14 +61,68 @@ impl NotificationService {
         data: Option<serde_json::Value>,
     ) -> Result<()> {
         let _ = NotificationType::all();
-        self.db
-            .create_notification(
-                user_address,
-                &notif_type.to_string(),
-                &title,
-                &message,
-                data.clone(),
+        let prefs = self.get_user_preferences(user_address).await?;
+        let decision = delivery_decision(
+            &prefs,
+            &notif_type.to_string(),
+            chrono::Utc::now().hour(),
+            muted_notifications_skipped(),
+        );
+        if decision == DeliveryDecision::Skip {
+            tracing::debug!(
+                "Notification {} muted for {}, skipped",
+                notif_type,
+                user_address
+            );
+            return Ok(());
+        }
+
+        if notif_type.is_digestible() && digest_frequency(&prefs).is_some() {
+            sqlx::query(
+                "INSERT INTO notification_digest_items (user_address, notif_type, title, message, data)
+                 VALUES ($1, $2, $3, $4, $5)",
             )
+            .bind(user_address)
+            .bind(notif_type.to_string())
+            .bind(&title)
+            .bind(&message)
+            .bind(&data)
+            .execute(self.db.pool())
+            .await?;
+            tracing::debug!(
+                "Notification {} queued for digest of {}",
+                notif_type,
+                user_address
+            );
+            return Ok(());
+        }
+
+        self.deliver(
+            user_address,
+            &notif_type.to_string(),
+            title,
+            message,
+            data,
+            &prefs,
+            decision,
+        )
+        .await
+    }
+
+    // Internal helper that stores the in-app record and pushes it unless `decision` is `StoreOnly`.
+    #[allow(clippy::too_many_arguments)]
+    async fn deliver(
+        &self,
+        user_address: &str,
+        notif_type: &str,
+        title: String,
+        message: String,
+        data: Option<serde_json::Value>,
+        prefs: &NotificationPreferences,
+        decision: DeliveryDecision,
+    ) -> Result<()> {
+        self.db
+            .create_notification(user_address, notif_type, &title, &message, data.clone())
             .await?;
 
         let notification = Notification {
@@ -76,10 +136,19 @@ impl NotificationService {
             created_at: chrono::Utc::now(),
         };
 
+        if decision == DeliveryDecision::StoreOnly {
+            tracing::debug!(
+                "Notification {} stored without push for {} (muted or quiet hours)",
+                notif_type,
+                user_address
+            );
+            return Ok(());
+        }
+
         self.send_to_websocket(user_address, notification.clone())
             .await;
-        self.send_via_other_channels(user_address, &notification)
-            .await?;
+        self.send_via_other_channels(user_address, &notification, prefs)
+            .await;
 
         tracing::info!(
             "Notification sent to {}: {} - {}",
@@ -90,6 +159,205 @@ impl NotificationService {
         Ok(())
     }
 
+    /// Tells the user a swap failed on-chain, with the revert reason.
+    ///
+    /// # Notes
+    /// * Best effort: delivery errors are logged, never returned to the caller.
+    /// * Sends at most once per `tx_hash`; the send time is recorded in
+    ///   `swap_failure_notifications` and the record is dropped again if delivery fails.
+    pub async fn notify_swap_failed(
+        &self,
+        user_address: &str,
+        token_in: &str,
+        token_out: &str,
+        tx_hash: Option<&str>,
+        reason: &str,
+    ) {
+        let tx_hash = tx_hash.map(|hash| hash.trim().to_ascii_lowercase());
+        if let Some(hash) = tx_hash.as_deref() {
+            match self.claim_swap_failure(hash, user_address).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    tracing::debug!("swap.failed already sent for tx {}", hash);
+                    return;
+                }
+                Err(err) => {
+                    tracing::warn!("Failed to record swap failure notification: {}", err);
+                    return;
+                }
+            }
+        }
+        let reason: String = reason.chars().take(SWAP_FAILED_REASON_MAX_LEN).collect();
+        if let Err(err) = self
+            .send_notification(
+                user_address,
+                NotificationType::SwapFailed,
+                "Swap failed".to_string(),
+                format!("Your {} to {} swap failed: {}", token_in, token_out, reason),
+                Some(serde_json::json!({
+                    "tx_hash": tx_hash,
+                    "from_token": token_in,
+                    "to_token": token_out,
+                    "reason": reason,
+                })),
+            )
+            .await
+        {
+            tracing::warn!("Failed to send swap failure notification: {}", err);
+            if let Some(hash) = tx_hash.as_deref() {
+                if let Err(err) =
+                    sqlx::query("DELETE FROM swap_failure_notifications WHERE tx_hash = $1")
+                        .bind(hash)
+                        .execute(self.db.pool())
+                        .await
+                {
+                    tracing::warn!("Failed to release swap failure notification: {}", err);
+                }
+            }
+        }
+    }
+
+    // Internal helper that records a swap.failed send and reports whether it is the first.
+    async fn claim_swap_failure(&self, tx_hash: &str, user_address: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT INTO swap_failure_notifications (tx_hash, user_address, notified_at)
+             VALUES ($1, $2, NOW())
+             ON CONFLICT (tx_hash) DO NOTHING",
+        )
+        .bind(tx_hash)
+        .bind(user_address)
+        .execute(self.db.pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Starts the background loop that delivers due notification digests.
+    ///
+    /// # Notes
+    /// * Runs every `NOTIFICATION_DIGEST_FLUSH_INTERVAL_SECS`; errors are logged and retried next tick.
+    pub async fn start_digest_flusher(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(
+                NOTIFICATION_DIGEST_FLUSH_INTERVAL_SECS,
+            ));
+            loop {
+                ticker.tick().await;
+                match self.flush_due_digests().await {
+                    Ok(0) => {}
+                    Ok(flushed) => tracing::info!("Delivered {} notification digests", flushed),
+                    Err(err) => tracing::error!("Notification digest flush error: {}", err),
+                }
+            }
+        });
+    }
+
+    /// Delivers a grouped digest for every user whose oldest pending item is due.
+    ///
+    /// # Returns
+    /// * `Ok(count)` with the number of digests delivered.
+    /// * `Err(AppError)` when the pending-items query fails.
+    ///
+    /// # Notes
+    /// * Users who turned digests off get their remaining items flushed immediately.
+    pub async fn flush_due_digests(&self) -> Result<usize> {
+        let rows = sqlx::query(
+            "SELECT d.user_address, p.digest_frequency, MIN(d.created_at) AS oldest
+             FROM notification_digest_items d
+             LEFT JOIN notification_preferences p ON p.user_address = d.user_address
+             GROUP BY d.user_address, p.digest_frequency",
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let now = chrono::Utc::now();
+        let mut flushed = 0;
+        for row in rows {
+            let user_address: String = row.get("user_address");
+            let frequency = row
+                .get::<Option<String>, _>("digest_frequency")
+                .as_deref()
+                .and_then(DigestFrequency::parse);
+            let oldest: chrono::DateTime<chrono::Utc> = row.get("oldest");
+            let due = frequency
+                .map(|frequency| oldest + frequency.interval() <= now)
+                .unwrap_or(true);
+            if !due {
+                continue;
+            }
+            match self.flush_user_digest(&user_address).await {
+                Ok(true) => flushed += 1,
+                Ok(false) => {}
+                Err(err) => {
+                    tracing::warn!("Digest flush failed for {}: {}", user_address, err);
+                }
+            }
+        }
+        Ok(flushed)
+    }
+
+    // Internal helper that drains pending items for one user into a single digest notification.
+    async fn flush_user_digest(&self, user_address: &str) -> Result<bool> {
+        let rows = sqlx::query(
+            "SELECT id, notif_type, title, message, data, created_at
+             FROM notification_digest_items
+             WHERE user_address = $1",
+        )
+        .bind(user_address)
+        .fetch_all(self.db.pool())
+        .await?;
+        let item_ids: Vec<i64> = rows.iter().map(|row| row.get("id")).collect();
+        let mut items: Vec<DigestItem> = rows
+            .iter()
+            .map(|row| DigestItem {
+                notif_type: row.get("notif_type"),
+                title: row.get("title"),
+                message: row.get("message"),
+                data: row.get("data"),
+                created_at: row.get("created_at"),
+            })
+            .collect();
+        if items.is_empty() {
+            return Ok(false);
+        }
+        items.sort_by_key(|item| item.created_at);
+
+        let prefs = self.get_user_preferences(user_address).await?;
+        let digest_type = NotificationType::Digest.to_string();
+        let decision = delivery_decision(
+            &prefs,
+            &digest_type,
+            chrono::Utc::now().hour(),
+            muted_notifications_skipped(),
+        );
+        if decision == DeliveryDecision::Skip {
+            self.delete_digest_items(&item_ids).await?;
+            return Ok(false);
+        }
+        let (title, message, data) = build_digest(&items);
+        // Items are only removed once the digest is stored, so a failed send retries next tick.
+        self.deliver(
+            user_address,
+            &digest_type,
+            title,
+            message,
+            Some(data),
+            &prefs,
+            decision,
+        )
+        .await?;
+        self.delete_digest_items(&item_ids).await?;
+        Ok(true)
+    }
+
+    // Internal helper that runs side-effecting logic for `delete_digest_items`.
+    async fn delete_digest_items(&self, item_ids: &[i64]) -> Result<()> {
+        sqlx::query("DELETE FROM notification_digest_items WHERE id = ANY($1)")
+            .bind(item_ids)
+            .execute(self.db.pool())
+            .await?;
+        Ok(())
+    }
+
     /// Runs `register_connection` and handles related side effects.
     ///
     /// # Arguments
@@ -140,35 +408,43 @@ impl NotificationService {
     }
 
     // Internal helper that runs side-effecting logic for `send_via_other_channels`.
+    // Each channel is best-effort: a failure is logged and never blocks the others.
     async fn send_via_other_channels(
         &self,
         user_address: &str,
         notification: &Notification,
-    ) -> Result<()> {
+        prefs: &NotificationPreferences,
+    ) {
         if self.config.is_testnet() {
             tracing::debug!(
                 "Testnet mode: skip external notifications for {}",
                 user_address
             );
-            return Ok(());
+            return;
         }
-        let prefs = self.get_user_preferences(user_address).await?;
         if prefs.email_enabled {
-            self.send_email(user_address, notification).await?;
+            if let Err(err) = self.send_email(user_address, notification, prefs).await {
+                tracing::warn!("Email notification to {} failed: {}", user_address, err);
+            }
         }
         if prefs.push_enabled {
-            self.send_push(user_address, notification).await?;
+            if let Err(err) = self.send_push(user_address, notification).await {
+                tracing::warn!("Push notification to {} failed: {}", user_address, err);
+            }
         }
         if prefs.telegram_enabled {
-            self.send_telegram(user_address, notification).await?;
+            if let Err(err) = self.send_telegram(user_address, notification).await {
+                tracing::warn!("Telegram notification to {} failed: {}", user_address, err);
+            }
         }
-        Ok(())
     }
 
     // Internal helper that fetches data for `get_user_preferences`.
     async fn get_user_preferences(&self, user_address: &str) -> Result<NotificationPreferences> {
         let prefs = sqlx::query_as::<_, NotificationPreferences>(
-            "SELECT email_enabled, push_enabled, telegram_enabled, discord_enabled
+            "SELECT email_enabled, push_enabled, telegram_enabled, discord_enabled,
+                    muted_all, muted_types, quiet_hours_start, quiet_hours_end, email_address,
+                    digest_frequency
              FROM notification_preferences WHERE user_address = $1",
         )
         .bind(user_address)
@@ -178,12 +454,45 @@ impl NotificationService {
     }
 
     // Internal helper that runs side-effecting logic for `send_email`.
-    async fn send_email(&self, user_address: &str, notification: &Notification) -> Result<()> {
-        tracing::debug!(
-            "Email notification to {}: {}",
-            user_address,
-            notification.title
-        );
+    async fn send_email(
+        &self,
+        user_address: &str,
+        notification: &Notification,
+        prefs: &NotificationPreferences,
+    ) -> Result<()> {
+        let Some(to) = prefs.email_address.as_deref() else {
+            tracing::debug!("No email address linked for {}", user_address);
+            return Ok(());
+        };
+        let (Some(api_url), Some(from)) = (
+            self.config.email_api_url.as_deref(),
+            self.config.email_from.as_deref(),
+        ) else {
+            tracing::debug!(
+                "Email provider not configured; skip email to {}",
+                user_address
+            );
+            return Ok(());
+        };
+
+        let mut request = http_client().post(api_url).json(&serde_json::json!({
+            "from": from,
+            "to": [to],
+            "subject": notification.title,
+            "text": notification.message,
+        }));
+        if let Some(api_key) = self.config.email_api_key.as_deref() {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request.send().await.map_err(|err| {
+            AppError::ExternalAPI(format!("Email provider request failed: {}", err))
+        })?;
+        if !response.status().is_success() {
+            return Err(AppError::ExternalAPI(format!(
+                "Email provider returned {}",
+                response.status()
+            )));
+        }
         Ok(())
     }
 
@@ -199,11 +508,163 @@ impl NotificationService {
 
     // Internal helper that runs side-effecting logic for `send_telegram`.
     async fn send_telegram(&self, user_address: &str, notification: &Notification) -> Result<()> {
-        tracing::debug!(
-            "Telegram notification to {}: {}",
-            user_address,
-            notification.title
-        );
+        let chat_id: Option<String> = sqlx::query_scalar(
+            "SELECT telegram_chat_id FROM notification_preferences WHERE user_address = $1",
+        )
+        .bind(user_address)
+        .fetch_optional(self.db.pool())
+        .await?
+        .flatten();
+        let Some(chat_id) = chat_id else {
+            tracing::debug!("No Telegram chat linked for {}", user_address);
+            return Ok(());
+        };
+        let text = format!("{}\n{}", notification.title, notification.message);
+        self.telegram_send_message(&chat_id, &text).await
+    }
+
+    // Internal helper that builds the Bot API URL for `method`.
+    fn telegram_method_url(&self, method: &str) -> Result<String> {
+        let token = self
+            .config
+            .telegram_bot_token
+            .as_deref()
+            .filter(|token| !token.trim().is_empty())
+            .ok_or_else(|| AppError::BadRequest("Telegram bot is not configured".to_string()))?;
+        Ok(format!(
+            "{}/bot{}/{}",
+            self.config.telegram_api_url.trim_end_matches('/'),
+            token.trim(),
+            method
+        ))
+    }
+
+    // Internal helper that runs side-effecting logic for `telegram_send_message`.
+    async fn telegram_send_message(&self, chat_id: &str, text: &str) -> Result<()> {
+        let url = self.telegram_method_url("sendMessage")?;
+        let response = http_client()
+            .post(url)
+            .json(&serde_json::json!({ "chat_id": chat_id, "text": text }))
+            .send()
+            .await
+            .map_err(|err| AppError::ExternalAPI(format!("Telegram request failed: {}", err)))?;
+        if !response.status().is_success() {
+            return Err(AppError::ExternalAPI(format!(
+                "Telegram sendMessage returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Issues a short-lived code the user sends to the bot to link their Telegram chat.
+    ///
+    /// # Arguments
+    /// * `user_address` - authenticated wallet address.
+    ///
+    /// # Returns
+    /// * `Ok(TelegramLinkCode)` with the code and its expiry.
+    /// * `Err(AppError)` when the bot is not configured or storage fails.
+    pub async fn create_telegram_link_code(&self, user_address: &str) -> Result<TelegramLinkCode> {
+        self.telegram_method_url("getUpdates")?;
+        let code = hex::encode_upper(rand::random::<[u8; 4]>());
+        let expires_at =
+            chrono::Utc::now() + chrono::Duration::seconds(TELEGRAM_LINK_CODE_TTL_SECS);
+        sqlx::query(
+            "INSERT INTO notification_preferences (user_address, telegram_link_code, telegram_link_expires_at)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (user_address) DO UPDATE
+             SET telegram_link_code = $2,
+                 telegram_link_expires_at = $3",
+        )
+        .bind(user_address)
+        .bind(&code)
+        .bind(expires_at)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(TelegramLinkCode {
+            instructions: format!(
+                "Send \"/start {}\" to the CAREL notification bot, then verify.",
+                code
+            ),
+            code,
+            expires_at,
+        })
+    }
+
+    /// Completes Telegram linking by finding the issued code in the bot's recent updates.
+    ///
+    /// # Arguments
+    /// * `user_address` - authenticated wallet address.
+    ///
+    /// # Returns
+    /// * `Ok(())` once the chat id is stored and the Telegram channel is enabled.
+    /// * `Err(AppError)` when no valid code exists or the bot has not received it yet.
+    pub async fn verify_telegram_link(&self, user_address: &str) -> Result<()> {
+        let row = sqlx::query(
+            "SELECT telegram_link_code, telegram_link_expires_at
+             FROM notification_preferences WHERE user_address = $1",
+        )
+        .bind(user_address)
+        .fetch_optional(self.db.pool())
+        .await?;
+        let (code, expires_at) = match row {
+            Some(row) => (
+                row.try_get::<Option<String>, _>("telegram_link_code")?,
+                row.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>(
+                    "telegram_link_expires_at",
+                )?,
+            ),
+            None => (None, None),
+        };
+        let code = match (code, expires_at) {
+            (Some(code), Some(expires_at)) if expires_at > chrono::Utc::now() => code,
+            _ => {
+                return Err(AppError::BadRequest(
+                    "Telegram link code missing or expired; request a new one".to_string(),
+                ))
+            }
+        };
+
+        let url = self.telegram_method_url("getUpdates")?;
+        let updates: serde_json::Value = http_client()
+            .get(url)
+            .send()
+            .await
+            .map_err(|err| AppError::ExternalAPI(format!("Telegram request failed: {}", err)))?
+            .json()
+            .await
+            .map_err(|err| AppError::ExternalAPI(format!("Telegram response invalid: {}", err)))?;
+        let chat_id = find_chat_id_for_code(&updates, &code).ok_or_else(|| {
+            AppError::BadRequest(
+                "Link code not received by the bot yet; send it and retry".to_string(),
+            )
+        })?;
+
+        sqlx::query(
+            "UPDATE notification_preferences
+             SET telegram_chat_id = $2,
+                 telegram_enabled = true,
+                 telegram_link_code = NULL,
+                 telegram_link_expires_at = NULL
+             WHERE user_address = $1",
+        )
+        .bind(user_address)
+        .bind(&chat_id)
+        .execute(self.db.pool())
+        .await?;
+
+        if let Err(err) = self
+            .telegram_send_message(&chat_id, "CAREL notifications are now linked to this chat.")
+            .await
+        {
+            tracing::warn!(
+                "Telegram link confirmation failed for {}: {}",
+                user_address,
+                err
+            );
+        }
         Ok(())
     }
 
@@ -282,10 +743,193 @@ impl NotificationService {
     }
 }
 
+const TELEGRAM_LINK_CODE_TTL_SECS: i64 = 600;
+const NOTIFICATION_CHANNEL_TIMEOUT_SECS: u64 = 10;
+
+static NOTIFICATION_HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+// Internal helper that supports `http_client` operations.
+fn http_client() -> &'static reqwest::Client {
+    NOTIFICATION_HTTP_CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(
+                NOTIFICATION_CHANNEL_TIMEOUT_SECS,
+            ))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// Code issued to link a Telegram chat to a wallet.
+#[derive(Debug, Clone, Serialize)]
+pub struct TelegramLinkCode {
+    pub code: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub instructions: String,
+}
+
+// Internal helper that finds the chat that sent `code` in a Bot API `getUpdates` payload.
+fn find_chat_id_for_code(updates: &serde_json::Value, code: &str) -> Option<String> {
+    updates
+        .get("result")?
+        .as_array()?
+        .iter()
+        .rev()
+        .filter_map(|update| update.get("message"))
+        .find(|message| {
+            message
+                .get("text")
+                .and_then(|text| text.as_str())
+                .map(|text| {
+                    let text = text.trim();
+                    let text = text.strip_prefix("/start").unwrap_or(text).trim();
+                    text.eq_ignore_ascii_case(code)
+                })
+                .unwrap_or(false)
+        })
+        .and_then(|message| message.get("chat")?.get("id")?.as_i64())
+        .map(|chat_id| chat_id.to_string())
+}
+
+/// Schedule for delivering accumulated low-priority notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestFrequency {
+    Hourly,
+    Daily,
+}
+
+impl DigestFrequency {
+    /// Parses a stored or requested frequency (`hourly` / `daily`).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "hourly" => Some(Self::Hourly),
+            "daily" => Some(Self::Daily),
+            _ => None,
+        }
+    }
+
+    /// Returns how long items accumulate before the digest is delivered.
+    pub fn interval(self) -> chrono::Duration {
+        match self {
+            Self::Hourly => chrono::Duration::hours(1),
+            Self::Daily => chrono::Duration::days(1),
+        }
+    }
+}
+
+// Internal helper that supports `digest_frequency` operations.
+fn digest_frequency(prefs: &NotificationPreferences) -> Option<DigestFrequency> {
+    prefs
+        .digest_frequency
+        .as_deref()
+        .and_then(DigestFrequency::parse)
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DigestItem {
+    notif_type: String,
+    title: String,
+    message: String,
+    data: Option<serde_json::Value>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+// Internal helper that builds the grouped title, summary line, and payload for a digest.
+fn build_digest(items: &[DigestItem]) -> (String, String, serde_json::Value) {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for item in items {
+        match counts.iter_mut().find(|(kind, _)| *kind == item.notif_type) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((item.notif_type.clone(), 1)),
+        }
+    }
+    let title = if items.len() == 1 {
+        "1 update".to_string()
+    } else {
+        format!("{} updates", items.len())
+    };
+    let message = counts
+        .iter()
+        .map(|(kind, count)| format!("{} {}", count, kind))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let data = serde_json::json!({
+        "digest": true,
+        "count": items.len(),
+        "items": items,
+    });
+    (title, message, data)
+}
+
+/// Outcome of checking a notification against the user's preferences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryDecision {
+    Deliver,
+    StoreOnly,
+    Skip,
+}
+
+// Internal helper that checks conditions for `muted_notifications_skipped`.
+// `NOTIFICATION_MUTED_MODE=skip` drops muted types entirely; the default stores them without push.
+fn muted_notifications_skipped() -> bool {
+    std::env::var("NOTIFICATION_MUTED_MODE")
+        .map(|value| value.trim().eq_ignore_ascii_case("skip"))
+        .unwrap_or(false)
+}
+
+// Internal helper that checks conditions for `in_quiet_hours`.
+fn in_quiet_hours(start: Option<i16>, end: Option<i16>, hour: u32) -> bool {
+    let (Some(start), Some(end)) = (start, end) else {
+        return false;
+    };
+    let hour = hour as i16;
+    if start == end {
+        return false;
+    }
+    if start < end {
+        hour >= start && hour < end
+    } else {
+        hour >= start || hour < end
+    }
+}
+
+/// Decides how a notification of `notif_type` is delivered for the given preferences.
+///
+/// # Arguments
+/// * `hour_utc` - current UTC hour used for the quiet-hours window.
+/// * `skip_muted` - drop muted types instead of storing them without push.
+///
+/// # Returns
+/// * `Deliver` when nothing is muted, `StoreOnly` during quiet hours or for muted
+///   types (unless `skip_muted`), and `Skip` for muted types when `skip_muted`.
+pub fn delivery_decision(
+    prefs: &NotificationPreferences,
+    notif_type: &str,
+    hour_utc: u32,
+    skip_muted: bool,
+) -> DeliveryDecision {
+    let type_muted = prefs
+        .muted_types
+        .iter()
+        .any(|muted| muted.trim().eq_ignore_ascii_case(notif_type));
+    if prefs.muted_all || type_muted {
+        return if skip_muted {
+            DeliveryDecision::Skip
+        } else {
+            DeliveryDecision::StoreOnly
+        };
+    }
+    if in_quiet_hours(prefs.quiet_hours_start, prefs.quiet_hours_end, hour_utc) {
+        return DeliveryDecision::StoreOnly;
+    }
+    DeliveryDecision::Deliver
+}
+
 #[derive(Debug, Clone)]
 pub enum NotificationType {
     SwapCompleted,
     SwapFailed,
+    SwapRefundDue,
     OrderFilled,
     OrderExpired,
     PointsAwarded,
@@ -293,7 +937,9 @@ pub enum NotificationType {
     NFTExpired,
     RewardClaimable,
     PriceAlert,
+    AchievementEarned,
     System,
+    Digest,
 }
 
 impl std::fmt::Display for NotificationType {
@@ -302,6 +948,7 @@ impl std::fmt::Display for NotificationType {
         let value = match self {
             Self::SwapCompleted => "swap.completed",
             Self::SwapFailed => "swap.failed",
+            Self::SwapRefundDue => "swap.refund_due",
             Self::OrderFilled => "order.filled",
             Self::OrderExpired => "order.expired",
             Self::PointsAwarded => "points.awarded",
@@ -309,7 +956,9 @@ impl std::fmt::Display for NotificationType {
             Self::NFTExpired => "nft.expired",
             Self::RewardClaimable => "reward.claimable",
             Self::PriceAlert => "price.alert",
+            Self::AchievementEarned => "achievement.earned",
             Self::System => "system",
+            Self::Digest => "digest",
         };
         write!(f, "{}", value)
     }
@@ -331,6 +980,7 @@ impl NotificationType {
         vec![
             Self::SwapCompleted,
             Self::SwapFailed,
+            Self::SwapRefundDue,
             Self::OrderFilled,
             Self::OrderExpired,
             Self::PointsAwarded,
@@ -338,9 +988,23 @@ impl NotificationType {
             Self::NFTExpired,
             Self::RewardClaimable,
             Self::PriceAlert,
+            Self::AchievementEarned,
             Self::System,
+            Self::Digest,
         ]
     }
+
+    /// Returns true for low-priority types that are batched when the user enables a digest.
+    pub fn is_digestible(&self) -> bool {
+        matches!(
+            self,
+            Self::OrderFilled
+                | Self::PointsAwarded
+                | Self::StakeRewards
+                | Self::RewardClaimable
+                | Self::PriceAlert
+        )
+    }
 }
 
 #[cfg(test)]
@@ -369,4 +1033,107 @@ mod tests {
         let all = NotificationType::all();
         assert!(all.len() >= 5);
     }
+
+    #[test]
+    // Internal helper that supports `delivery_decision_defaults_to_deliver` operations.
+    fn delivery_decision_defaults_to_deliver() {
+        let prefs = NotificationPreferences::default();
+        assert_eq!(
+            delivery_decision(&prefs, "swap.completed", 12, false),
+            DeliveryDecision::Deliver
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `delivery_decision_honors_type_and_global_mute` operations.
+    fn delivery_decision_honors_type_and_global_mute() {
+        let prefs = NotificationPreferences {
+            muted_types: vec!["swap.completed".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            delivery_decision(&prefs, "swap.completed", 12, false),
+            DeliveryDecision::StoreOnly
+        );
+        assert_eq!(
+            delivery_decision(&prefs, "swap.completed", 12, true),
+            DeliveryDecision::Skip
+        );
+        assert_eq!(
+            delivery_decision(&prefs, "order.filled", 12, true),
+            DeliveryDecision::Deliver
+        );
+
+        let muted_all = NotificationPreferences {
+            muted_all: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            delivery_decision(&muted_all, "system", 12, true),
+            DeliveryDecision::Skip
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `find_chat_id_for_code_matches_start_command` operations.
+    fn find_chat_id_for_code_matches_start_command() {
+        let updates = serde_json::json!({
+            "ok": true,
+            "result": [
+                { "update_id": 1, "message": { "text": "hello", "chat": { "id": 11 } } },
+                { "update_id": 2, "message": { "text": "/start ab12cd34", "chat": { "id": 42 } } }
+            ]
+        });
+        assert_eq!(
+            find_chat_id_for_code(&updates, "AB12CD34"),
+            Some("42".to_string())
+        );
+        assert_eq!(find_chat_id_for_code(&updates, "FFFFFFFF"), None);
+    }
+
+    #[test]
+    // Internal helper that supports `digest_groups_items_by_type` operations.
+    fn digest_groups_items_by_type() {
+        let item = |kind: &str| DigestItem {
+            notif_type: kind.to_string(),
+            title: "t".to_string(),
+            message: "m".to_string(),
+            data: None,
+            created_at: chrono::Utc::now(),
+        };
+        let items = vec![
+            item("price.alert"),
+            item("order.filled"),
+            item("price.alert"),
+        ];
+        let (title, message, data) = build_digest(&items);
+        assert_eq!(title, "3 updates");
+        assert_eq!(message, "2 price.alert, 1 order.filled");
+        assert_eq!(data["count"], 3);
+        assert_eq!(data["items"].as_array().map(|items| items.len()), Some(3));
+    }
+
+    #[test]
+    // Internal helper that supports `digest_only_applies_to_low_priority_types` operations.
+    fn digest_only_applies_to_low_priority_types() {
+        assert!(NotificationType::PriceAlert.is_digestible());
+        assert!(!NotificationType::SwapFailed.is_digestible());
+        assert!(!NotificationType::System.is_digestible());
+        assert_eq!(
+            DigestFrequency::parse("Daily"),
+            Some(DigestFrequency::Daily)
+        );
+        assert_eq!(DigestFrequency::parse("weekly"), None);
+    }
+
+    #[test]
+    // Internal helper that supports `quiet_hours_wrap_past_midnight` operations.
+    fn quiet_hours_wrap_past_midnight() {
+        assert!(in_quiet_hours(Some(22), Some(6), 23));
+        assert!(in_quiet_hours(Some(22), Some(6), 3));
+        assert!(!in_quiet_hours(Some(22), Some(6), 12));
+        assert!(in_quiet_hours(Some(9), Some(17), 9));
+        assert!(!in_quiet_hours(Some(9), Some(17), 17));
+        assert!(!in_quiet_hours(None, Some(6), 3));
+    }
 }
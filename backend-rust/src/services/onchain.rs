@@ -1,12 +1,18 @@
 use crate::{config::Config, error::Result};
 use starknet_accounts::{Account, ExecutionEncoding, SingleOwnerAccount};
+use starknet_core::types::requests::CallRequest;
 use starknet_core::types::{
-    BlockId, BlockTag, Call, ContractClass, Felt, FunctionCall, Transaction,
+    BlockId, BlockTag, Call, ContractClass, Event, ExecutionResult, FeePayment, Felt, FunctionCall,
+    PriceUnit, StarknetError, Transaction, TransactionFinalityStatus, TransactionReceipt,
     TransactionReceiptWithBlockInfo,
 };
-use starknet_providers::jsonrpc::{HttpTransport, JsonRpcClient};
-use starknet_providers::Provider;
+use starknet_core::utils::get_selector_from_name;
+use starknet_providers::jsonrpc::{
+    HttpTransport, HttpTransportError, JsonRpcClient, JsonRpcClientError,
+};
+use starknet_providers::{Provider, ProviderError, ProviderRequestData, ProviderResponseData};
 use starknet_signers::{LocalWallet, SigningKey};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::sync::OnceLock;
@@ -31,6 +37,12 @@ const STARKNET_RPC_BREAKER_BASE_SECS: u64 = 2;
 const STARKNET_RPC_BREAKER_MAX_SECS: u64 = 180;
 const STARKNET_NONCE_RETRY_ATTEMPTS: usize = 2;
 const STARKNET_NONCE_RETRY_DELAY_MS: u64 = 650;
+const STARKNET_RPC_ENDPOINT_FAILURE_THRESHOLD_DEFAULT: u32 = 2;
+const STARKNET_RPC_ENDPOINT_COOLDOWN_SECS_DEFAULT: u64 = 60;
+// Only selectors whose result never changes on-chain belong here; `fixed_amount` is
+// rewritten by `set_asset_rule` and must stay uncached.
+const STARKNET_READ_CACHE_TTLS_DEFAULT: &str = "decimals=86400";
+const STARKNET_READ_CACHE_MAX_ENTRIES: usize = 50_000;
 
 #[derive(Default)]
 struct RpcCircuitBreaker {
@@ -38,9 +50,214 @@ struct RpcCircuitBreaker {
     open_until: Option<Instant>,
 }
 
+// Health of a single RPC endpoint, shared across reader instances by URL.
+#[derive(Debug, Default, Clone, Copy)]
+struct RpcEndpointHealth {
+    consecutive_failures: u32,
+    last_failure_at: Option<Instant>,
+}
+
+impl RpcEndpointHealth {
+    // Internal helper that checks conditions for `is_degraded`.
+    fn is_degraded(&self, threshold: u32, cooldown: Duration, now: Instant) -> bool {
+        if self.consecutive_failures < threshold {
+            return false;
+        }
+        self.last_failure_at
+            .map(|at| now.saturating_duration_since(at) < cooldown)
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Clone)]
+struct CachedRead {
+    fetched_at: Instant,
+    ttl: Duration,
+    values: Vec<Felt>,
+}
+
+/// Typed classification of a failed Starknet RPC read.
+///
+/// Built from the structured JSON-RPC error (`code`/`data`) when the provider returns one,
+/// falling back to message heuristics otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnchainError {
+    /// The target contract does not expose the called selector.
+    EntrypointNotFound(String),
+    /// The selector exists but execution reverted.
+    ContractRevert(String),
+    /// The provider throttled the request.
+    RateLimited(String),
+    /// Transport or availability failure; safe to retry on another endpoint.
+    Network(String),
+    /// Any other failure.
+    Other(String),
+}
+
+const JSON_RPC_CODE_ENTRYPOINT_NOT_FOUND: i64 = 21;
+const JSON_RPC_CODE_CONTRACT_ERROR: i64 = 40;
+const JSON_RPC_CODE_TRANSACTION_EXECUTION_ERROR: i64 = 41;
+const JSON_RPC_CODE_LIMIT_EXCEEDED: i64 = -32005;
+const JSON_RPC_CODE_TOO_MANY_REQUESTS: i64 = 429;
+
+impl OnchainError {
+    /// Returns the underlying provider error message.
+    pub fn message(&self) -> &str {
+        match self {
+            Self::EntrypointNotFound(message)
+            | Self::ContractRevert(message)
+            | Self::RateLimited(message)
+            | Self::Network(message)
+            | Self::Other(message) => message,
+        }
+    }
+
+    /// Checks whether retrying on another endpoint may succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::RateLimited(_) | Self::Network(_))
+    }
+
+    /// Classifies a provider error, preferring structured JSON-RPC data over message text.
+    pub fn from_provider_error(err: &ProviderError) -> Self {
+        let message = err.to_string();
+        match err {
+            ProviderError::RateLimited => Self::RateLimited(message),
+            ProviderError::StarknetError(StarknetError::EntrypointNotFound) => {
+                Self::EntrypointNotFound(message)
+            }
+            ProviderError::StarknetError(StarknetError::ContractError(_))
+            | ProviderError::StarknetError(StarknetError::TransactionExecutionError(_)) => {
+                // Debug output carries the revert data even when Display omits it.
+                Self::from_contract_error(&format!("{:?}", err), message)
+            }
+            ProviderError::Other(inner) => match inner
+                .as_any()
+                .downcast_ref::<JsonRpcClientError<HttpTransportError>>()
+            {
+                Some(JsonRpcClientError::JsonRpcError(rpc_error)) => Self::from_json_rpc_code(
+                    rpc_error.code,
+                    &format!("{} {:?}", rpc_error.message, rpc_error.data),
+                    message,
+                ),
+                Some(JsonRpcClientError::TransportError(_)) => Self::Network(message),
+                _ => Self::from_message(&message),
+            },
+            _ => Self::from_message(&message),
+        }
+    }
+
+    // Internal helper that parses or transforms values for `from_json_rpc_code`.
+    fn from_json_rpc_code(code: i64, detail: &str, message: String) -> Self {
+        match code {
+            JSON_RPC_CODE_ENTRYPOINT_NOT_FOUND => Self::EntrypointNotFound(message),
+            JSON_RPC_CODE_CONTRACT_ERROR | JSON_RPC_CODE_TRANSACTION_EXECUTION_ERROR => {
+                Self::from_contract_error(detail, message)
+            }
+            JSON_RPC_CODE_LIMIT_EXCEEDED | JSON_RPC_CODE_TOO_MANY_REQUESTS => {
+                Self::RateLimited(message)
+            }
+            _ => Self::from_message(&message),
+        }
+    }
+
+    // Internal helper that parses or transforms values for `from_contract_error`.
+    // Cairo 1 contracts report a missing selector as a contract error whose revert reason
+    // names the entry point, so the revert text still decides between the two.
+    fn from_contract_error(detail: &str, message: String) -> Self {
+        if mentions_missing_entrypoint(detail) || mentions_missing_entrypoint(&message) {
+            Self::EntrypointNotFound(message)
+        } else {
+            Self::ContractRevert(message)
+        }
+    }
+
+    // Internal helper that parses or transforms values for `from_preflight_error`.
+    fn from_preflight_error(err: crate::error::AppError) -> Self {
+        match err {
+            crate::error::AppError::BlockchainRPC(message) => Self::Network(message),
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// Classifies a bare error message using string heuristics.
+    ///
+    /// # Notes
+    /// * Only used when the provider returned no structured error data.
+    pub fn from_message(message: &str) -> Self {
+        let lower = message.to_ascii_lowercase();
+        if mentions_missing_entrypoint(&lower) {
+            return Self::EntrypointNotFound(message.to_string());
+        }
+        if lower.contains("contracterror")
+            || lower.contains("contract error")
+            || lower.contains("revert_error")
+            || lower.contains("execution_error")
+            || lower.contains("innercontractexecutionerror")
+            || lower.contains("sender required")
+            || lower.contains("invalid caller")
+            || lower.contains("failed to deserialize param")
+        {
+            return Self::ContractRevert(message.to_string());
+        }
+        if lower.contains("too many requests")
+            || lower.contains("429")
+            || lower.contains("rate limit")
+            || lower.contains("cu limit exceeded")
+            || lower.contains("request too fast")
+        {
+            return Self::RateLimited(message.to_string());
+        }
+        if looks_like_transient_rpc_error(&lower) || lower.contains("network") {
+            return Self::Network(message.to_string());
+        }
+        Self::Other(message.to_string())
+    }
+}
+
+impl std::fmt::Display for OnchainError {
+    // Internal helper that supports `fmt` operations.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl From<OnchainError> for crate::error::AppError {
+    // Internal helper that supports `from` operations.
+    fn from(err: OnchainError) -> Self {
+        match err {
+            OnchainError::EntrypointNotFound(message)
+            | OnchainError::ContractRevert(message)
+            | OnchainError::RateLimited(message)
+            | OnchainError::Network(message)
+            | OnchainError::Other(message) => crate::error::AppError::BlockchainRPC(message),
+        }
+    }
+}
+
+// Internal helper that checks conditions for `mentions_missing_entrypoint`.
+fn mentions_missing_entrypoint(message: &str) -> bool {
+    let lower = message.to_ascii_lowercase();
+    if lower.contains("entry_point_not_found") || lower.contains("entrypoint_not_found") {
+        return true;
+    }
+    let mentions_entrypoint =
+        lower.contains("entrypoint") || lower.contains("entry point") || lower.contains("selector");
+    let mentions_missing = lower.contains("does not exist")
+        || lower.contains("not found")
+        || lower.contains("missing");
+    mentions_entrypoint && mentions_missing
+}
+
 static STARKNET_RPC_SEMAPHORE: OnceLock<Arc<Semaphore>> = OnceLock::new();
 static STARKNET_RPC_BREAKER: OnceLock<tokio::sync::RwLock<RpcCircuitBreaker>> = OnceLock::new();
 static STARKNET_TX_SUBMIT_MUTEX: OnceLock<Arc<Mutex<()>>> = OnceLock::new();
+static STARKNET_RPC_ENDPOINT_HEALTH: OnceLock<
+    std::sync::RwLock<HashMap<String, RpcEndpointHealth>>,
+> = OnceLock::new();
+static STARKNET_RPC_ACTIVE_ENDPOINT: OnceLock<std::sync::Mutex<Option<String>>> = OnceLock::new();
+static STARKNET_READ_CACHE_TTLS: OnceLock<HashMap<Felt, Duration>> = OnceLock::new();
+static STARKNET_READ_CACHE: OnceLock<tokio::sync::RwLock<HashMap<String, CachedRead>>> =
+    OnceLock::new();
 
 // Internal helper that supports `env_non_empty` operations.
 fn env_non_empty(name: &str) -> Option<String> {
@@ -77,8 +294,42 @@ fn dedupe_rpc_urls(urls: Vec<String>) -> Vec<String> {
     out
 }
 
-// Internal helper that supports `resolve_api_rpc_urls` operations.
-fn resolve_api_rpc_urls(config: &Config) -> Vec<String> {
+/// Resolves the ordered Starknet API RPC failover list.
+///
+/// Returns the fee paid by a transaction, whatever its receipt kind.
+pub fn receipt_actual_fee(receipt: &TransactionReceipt) -> &FeePayment {
+    match receipt {
+        TransactionReceipt::Invoke(receipt) => &receipt.actual_fee,
+        TransactionReceipt::L1Handler(receipt) => &receipt.actual_fee,
+        TransactionReceipt::Declare(receipt) => &receipt.actual_fee,
+        TransactionReceipt::Deploy(receipt) => &receipt.actual_fee,
+        TransactionReceipt::DeployAccount(receipt) => &receipt.actual_fee,
+    }
+}
+
+/// Whether an RPC error means the transaction hash is unknown to the node.
+pub fn is_tx_hash_not_found(err: &crate::error::AppError) -> bool {
+    let lower = err.to_string().to_ascii_lowercase();
+    lower.contains("transaction hash not found") || lower.contains("transactionhashnotfound")
+}
+
+/// Label for a fee unit as reported to clients (`WEI` or `FRI`).
+pub fn fee_unit_label(unit: &PriceUnit) -> &'static str {
+    match unit {
+        PriceUnit::Wei => "WEI",
+        PriceUnit::Fri => "FRI",
+    }
+}
+
+/// # Arguments
+/// * `config` - Runtime config; `STARKNET_RPC_URL` may hold a comma-separated list.
+///
+/// # Returns
+/// * Deduplicated RPC URLs in configured priority order.
+///
+/// # Notes
+/// * `STARKNET_API_RPC_POOL` / `STARKNET_API_RPC_URL` / `STARKNET_RPC_POOL` env overrides win.
+pub fn resolve_api_rpc_urls(config: &Config) -> Vec<String> {
     let mut urls = env_rpc_urls("STARKNET_API_RPC_POOL");
     if urls.is_empty() {
         urls.extend(env_rpc_urls("STARKNET_API_RPC_URL"));
@@ -129,6 +380,177 @@ fn rpc_semaphore() -> &'static Arc<Semaphore> {
     STARKNET_RPC_SEMAPHORE.get_or_init(|| Arc::new(Semaphore::new(configured_max_inflight())))
 }
 
+// Internal helper that supports `configured_endpoint_failure_threshold` operations.
+fn configured_endpoint_failure_threshold() -> u32 {
+    std::env::var("STARKNET_RPC_ENDPOINT_FAILURE_THRESHOLD")
+        .ok()
+        .and_then(|value| value.parse::<u32>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(STARKNET_RPC_ENDPOINT_FAILURE_THRESHOLD_DEFAULT)
+}
+
+// Internal helper that supports `configured_endpoint_cooldown` operations.
+fn configured_endpoint_cooldown() -> Duration {
+    let secs = std::env::var("STARKNET_RPC_ENDPOINT_COOLDOWN_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(STARKNET_RPC_ENDPOINT_COOLDOWN_SECS_DEFAULT);
+    Duration::from_secs(secs)
+}
+
+// Internal helper that supports `endpoint_health` operations.
+fn endpoint_health() -> &'static std::sync::RwLock<HashMap<String, RpcEndpointHealth>> {
+    STARKNET_RPC_ENDPOINT_HEALTH.get_or_init(|| std::sync::RwLock::new(HashMap::new()))
+}
+
+// Internal helper that supports `active_endpoint` operations.
+fn active_endpoint() -> &'static std::sync::Mutex<Option<String>> {
+    STARKNET_RPC_ACTIVE_ENDPOINT.get_or_init(|| std::sync::Mutex::new(None))
+}
+
+// Internal helper that supports `record_endpoint_success` operations.
+fn record_endpoint_success(url: &str) {
+    if let Ok(mut guard) = endpoint_health().write() {
+        if let Some(health) = guard.get_mut(url) {
+            if health.consecutive_failures > 0 {
+                tracing::info!(
+                    "Starknet RPC endpoint {} recovered after {} consecutive failures",
+                    url,
+                    health.consecutive_failures
+                );
+            }
+            health.consecutive_failures = 0;
+            health.last_failure_at = None;
+        }
+    }
+    if let Ok(mut guard) = active_endpoint().lock() {
+        if guard.as_deref() != Some(url) {
+            tracing::info!("Starknet RPC active endpoint: {}", url);
+            *guard = Some(url.to_string());
+        }
+    }
+}
+
+// Internal helper that supports `record_endpoint_failure` operations.
+// Only transient failures count against endpoint health; contract errors are endpoint-agnostic.
+fn record_endpoint_failure(url: &str, is_transient: bool) {
+    if !is_transient {
+        return;
+    }
+    let threshold = configured_endpoint_failure_threshold();
+    let Ok(mut guard) = endpoint_health().write() else {
+        return;
+    };
+    let health = guard.entry(url.to_string()).or_default();
+    health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+    health.last_failure_at = Some(Instant::now());
+    if health.consecutive_failures == threshold {
+        tracing::warn!(
+            "Starknet RPC endpoint {} deprioritized after {} consecutive transient failures",
+            url,
+            health.consecutive_failures
+        );
+    }
+}
+
+// Internal helper that supports `rank_providers_by_health` operations.
+// Keeps round-robin order among healthy endpoints and moves degraded ones to the back,
+// least-failing first, so they are still tried when every endpoint is degraded.
+fn rank_providers_by_health(order: Vec<usize>, penalties: &[u32]) -> Vec<usize> {
+    let mut ranked = order;
+    ranked.sort_by_key(|index| penalties.get(*index).copied().unwrap_or(0));
+    ranked
+}
+
+// Internal helper that parses or transforms values for `parse_read_cache_ttls`.
+// Accepts `selector_name=ttl_secs` entries; a TTL of 0 disables caching for that selector.
+fn parse_read_cache_ttls(raw: &str) -> HashMap<Felt, Duration> {
+    let mut out = HashMap::new();
+    for entry in raw.split(',') {
+        let Some((name, secs)) = entry.trim().split_once('=') else {
+            continue;
+        };
+        let Ok(secs) = secs.trim().parse::<u64>() else {
+            tracing::warn!(
+                "Ignoring invalid STARKNET_READ_CACHE_TTLS entry '{}'",
+                entry
+            );
+            continue;
+        };
+        if secs == 0 {
+            continue;
+        }
+        let Ok(selector) = get_selector_from_name(name.trim()) else {
+            tracing::warn!(
+                "Ignoring invalid STARKNET_READ_CACHE_TTLS selector '{}'",
+                name
+            );
+            continue;
+        };
+        out.insert(selector, Duration::from_secs(secs));
+    }
+    out
+}
+
+// Internal helper that supports `read_cache_ttls` operations.
+fn read_cache_ttls() -> &'static HashMap<Felt, Duration> {
+    STARKNET_READ_CACHE_TTLS.get_or_init(|| {
+        let raw = env_non_empty("STARKNET_READ_CACHE_TTLS")
+            .unwrap_or_else(|| STARKNET_READ_CACHE_TTLS_DEFAULT.to_string());
+        parse_read_cache_ttls(&raw)
+    })
+}
+
+// Internal helper that supports `read_cache` operations.
+fn read_cache() -> &'static tokio::sync::RwLock<HashMap<String, CachedRead>> {
+    STARKNET_READ_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()))
+}
+
+// Internal helper that builds inputs for `read_cache_key`.
+fn read_cache_key(call: &FunctionCall) -> String {
+    let calldata = call
+        .calldata
+        .iter()
+        .map(|value| format!("{:#x}", value))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        "{:#x}|{:#x}|{}",
+        call.contract_address, call.entry_point_selector, calldata
+    )
+}
+
+// Internal helper that checks conditions for `is_cacheable_read_result`.
+// Empty or all-zero results usually mean "not registered yet" and must be re-read.
+fn is_cacheable_read_result(values: &[Felt]) -> bool {
+    values.iter().any(|value| *value != Felt::ZERO)
+}
+
+// Internal helper that parses or transforms values for `batch_call_values`.
+// A mismatched length fails the whole batch; an unexpected entry only fails its own call.
+fn batch_call_values(
+    responses: Vec<ProviderResponseData>,
+    expected: usize,
+) -> Result<Vec<Result<Vec<Felt>>>> {
+    if responses.len() != expected {
+        return Err(crate::error::AppError::BlockchainRPC(format!(
+            "starknet_call batch returned {} responses for {} calls",
+            responses.len(),
+            expected
+        )));
+    }
+    Ok(responses
+        .into_iter()
+        .map(|response| match response {
+            ProviderResponseData::Call(values) => Ok(values),
+            _ => Err(crate::error::AppError::BlockchainRPC(
+                "starknet_call batch returned an unexpected response type".to_string(),
+            )),
+        })
+        .collect())
+}
+
 // Internal helper that supports `rpc_breaker` operations.
 fn rpc_breaker() -> &'static tokio::sync::RwLock<RpcCircuitBreaker> {
     STARKNET_RPC_BREAKER.get_or_init(|| tokio::sync::RwLock::new(RpcCircuitBreaker::default()))
@@ -228,8 +650,8 @@ async fn rpc_record_success() {
 }
 
 // Internal helper that supports `rpc_record_failure` operations.
-async fn rpc_record_failure(method: &str, error_text: &str) {
-    if !looks_like_transient_rpc_error(error_text) {
+async fn rpc_record_failure(method: &str, is_transient: bool) {
+    if !is_transient {
         return;
     }
 
@@ -331,7 +753,11 @@ impl OnchainInvoker {
                         sleep(Duration::from_millis(STARKNET_NONCE_RETRY_DELAY_MS)).await;
                         continue;
                     }
-                    rpc_record_failure("starknet_invoke", &err_text).await;
+                    rpc_record_failure(
+                        "starknet_invoke",
+                        looks_like_transient_rpc_error(&err_text),
+                    )
+                    .await;
                     return Err(crate::error::AppError::BlockchainRPC(err_text));
                 }
                 Err(err) => {
@@ -387,7 +813,11 @@ impl OnchainInvoker {
                         sleep(Duration::from_millis(STARKNET_NONCE_RETRY_DELAY_MS)).await;
                         continue;
                     }
-                    rpc_record_failure("starknet_invoke_many", &err_text).await;
+                    rpc_record_failure(
+                        "starknet_invoke_many",
+                        looks_like_transient_rpc_error(&err_text),
+                    )
+                    .await;
                     return Err(crate::error::AppError::BlockchainRPC(err_text));
                 }
                 Err(err) => {
@@ -430,7 +860,34 @@ impl OnchainReader {
             return vec![0];
         }
         let start = self.rr_cursor.fetch_add(1, Ordering::Relaxed) % len;
-        (0..len).map(|offset| (start + offset) % len).collect()
+        let order: Vec<usize> = (0..len).map(|offset| (start + offset) % len).collect();
+
+        let threshold = configured_endpoint_failure_threshold();
+        let cooldown = configured_endpoint_cooldown();
+        let now = Instant::now();
+        let penalties: Vec<u32> = match endpoint_health().read() {
+            Ok(guard) => self
+                .provider_urls
+                .iter()
+                .map(|url| {
+                    guard
+                        .get(url)
+                        .filter(|health| health.is_degraded(threshold, cooldown, now))
+                        .map(|health| health.consecutive_failures)
+                        .unwrap_or(0)
+                })
+                .collect(),
+            Err(_) => return order,
+        };
+        rank_providers_by_health(order, &penalties)
+    }
+
+    // Internal helper that supports `provider_url` operations.
+    fn provider_url(&self, provider_index: usize) -> &str {
+        self.provider_urls
+            .get(provider_index)
+            .map(String::as_str)
+            .unwrap_or("<unknown>")
     }
 
     /// Handles `from_config` logic.
@@ -475,53 +932,198 @@ impl OnchainReader {
     /// # Notes
     /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
     pub async fn call(&self, call: FunctionCall) -> Result<Vec<Felt>> {
-        let _permit = rpc_preflight("starknet_call").await?;
+        self.call_typed(call).await.map_err(Into::into)
+    }
+
+    /// Handles `call_typed` logic.
+    ///
+    /// # Arguments
+    /// * `call` - Read-only call executed against the latest block.
+    ///
+    /// # Returns
+    /// * `Ok(...)` with the returned felts.
+    /// * `Err(OnchainError)` classified from the structured JSON-RPC error when available.
+    ///
+    /// # Notes
+    /// * Use this instead of `call` when the caller needs to branch on the failure kind
+    ///   (e.g. selector probing).
+    pub async fn call_typed(
+        &self,
+        call: FunctionCall,
+    ) -> std::result::Result<Vec<Felt>, OnchainError> {
+        let _permit = rpc_preflight("starknet_call")
+            .await
+            .map_err(OnchainError::from_preflight_error)?;
         let order = self.provider_order();
-        let mut last_error_text: Option<String> = None;
+        let mut last_error: Option<OnchainError> = None;
 
         for (attempt, provider_index) in order.iter().enumerate() {
             let response = self.providers[*provider_index]
                 .call(call.clone(), BlockId::Tag(BlockTag::Latest))
-                .await
-                .map_err(|e| crate::error::AppError::BlockchainRPC(e.to_string()));
+                .await;
             match response {
                 Ok(values) => {
+                    record_endpoint_success(self.provider_url(*provider_index));
                     rpc_record_success().await;
                     return Ok(values);
                 }
-                Err(crate::error::AppError::BlockchainRPC(err_text)) => {
-                    last_error_text = Some(err_text.clone());
-                    let is_transient = looks_like_transient_rpc_error(&err_text);
+                Err(provider_err) => {
+                    let err = OnchainError::from_provider_error(&provider_err);
+                    let is_transient = err.is_transient();
+                    record_endpoint_failure(self.provider_url(*provider_index), is_transient);
                     let has_next = attempt + 1 < order.len();
                     if has_next && is_transient {
                         tracing::warn!(
                             "starknet_call failed on provider {} ({}), trying next RPC: {}",
                             provider_index,
-                            self.provider_urls
-                                .get(*provider_index)
-                                .cloned()
-                                .unwrap_or_else(|| "<unknown>".to_string()),
-                            err_text
+                            self.provider_url(*provider_index),
+                            err
                         );
-                        continue;
                     }
-                    if has_next {
-                        continue;
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        match last_error {
+            Some(err) => {
+                rpc_record_failure("starknet_call", err.is_transient()).await;
+                Err(err)
+            }
+            None => Err(OnchainError::Other(
+                "starknet_call failed without detailed error".to_string(),
+            )),
+        }
+    }
+
+    /// Handles `call_batch` logic.
+    ///
+    /// # Arguments
+    /// * `calls` - Read-only calls to execute against the latest block.
+    ///
+    /// # Returns
+    /// * One result per call, in input order.
+    ///
+    /// # Notes
+    /// * Uses a single JSON-RPC batch round trip; if the batch fails as a whole (e.g. one call
+    ///   reverts), falls back to sequential calls so each entry gets its own result.
+    pub async fn call_batch(&self, calls: &[FunctionCall]) -> Vec<Result<Vec<Felt>>> {
+        if calls.len() <= 1 {
+            let mut out = Vec::with_capacity(calls.len());
+            for call in calls {
+                out.push(self.call(call.clone()).await);
+            }
+            return out;
+        }
+
+        match self.try_call_batch(calls).await {
+            Ok(values) => values,
+            Err(err) => {
+                tracing::debug!(
+                    "starknet_call batch of {} failed, falling back to sequential calls: {}",
+                    calls.len(),
+                    err
+                );
+                let mut out = Vec::with_capacity(calls.len());
+                for call in calls {
+                    out.push(self.call(call.clone()).await);
+                }
+                out
+            }
+        }
+    }
+
+    // Internal helper that supports `try_call_batch` operations.
+    async fn try_call_batch(&self, calls: &[FunctionCall]) -> Result<Vec<Result<Vec<Felt>>>> {
+        let _permit = rpc_preflight("starknet_call_batch").await?;
+        let requests: Vec<ProviderRequestData> = calls
+            .iter()
+            .map(|call| {
+                ProviderRequestData::Call(CallRequest {
+                    request: call.clone(),
+                    block_id: BlockId::Tag(BlockTag::Latest),
+                })
+            })
+            .collect();
+        let order = self.provider_order();
+        let mut last_error: Option<OnchainError> = None;
+
+        for (attempt, provider_index) in order.iter().enumerate() {
+            let response = self.providers[*provider_index]
+                .batch_requests(&requests)
+                .await;
+            match response {
+                Ok(responses) => {
+                    record_endpoint_success(self.provider_url(*provider_index));
+                    rpc_record_success().await;
+                    return batch_call_values(responses, calls.len());
+                }
+                Err(provider_err) => {
+                    let err = OnchainError::from_provider_error(&provider_err);
+                    let is_transient = err.is_transient();
+                    record_endpoint_failure(self.provider_url(*provider_index), is_transient);
+                    last_error = Some(err);
+                    // Non-transient errors (reverts, missing entrypoints) are call-specific;
+                    // retrying the same batch elsewhere will not help.
+                    if !is_transient || attempt + 1 >= order.len() {
+                        break;
                     }
                 }
-                Err(err) => return Err(err),
             }
         }
 
-        if let Some(err_text) = last_error_text {
-            rpc_record_failure("starknet_call", &err_text).await;
-            return Err(crate::error::AppError::BlockchainRPC(err_text));
+        if let Some(err) = last_error {
+            rpc_record_failure("starknet_call_batch", err.is_transient()).await;
+            return Err(err.into());
         }
         Err(crate::error::AppError::BlockchainRPC(
-            "starknet_call failed without detailed error".to_string(),
+            "starknet_call_batch failed without detailed error".to_string(),
         ))
     }
 
+    /// Handles `call_cached` logic.
+    ///
+    /// # Arguments
+    /// * `call` - Read-only call; only selectors listed in `STARKNET_READ_CACHE_TTLS` are cached.
+    ///
+    /// # Returns
+    /// * `Ok(...)` with cached or freshly read values.
+    /// * `Err(AppError)` when the underlying RPC call fails.
+    ///
+    /// # Notes
+    /// * Empty or all-zero results are never cached so pending registrations are re-read.
+    pub async fn call_cached(&self, call: FunctionCall) -> Result<Vec<Felt>> {
+        let Some(ttl) = read_cache_ttls().get(&call.entry_point_selector).copied() else {
+            return self.call(call).await;
+        };
+        let key = read_cache_key(&call);
+        {
+            let guard = read_cache().read().await;
+            if let Some(entry) = guard.get(&key) {
+                if entry.fetched_at.elapsed() <= entry.ttl {
+                    return Ok(entry.values.clone());
+                }
+            }
+        }
+
+        let values = self.call(call).await?;
+        if is_cacheable_read_result(&values) {
+            let mut guard = read_cache().write().await;
+            guard.insert(
+                key,
+                CachedRead {
+                    fetched_at: Instant::now(),
+                    ttl,
+                    values: values.clone(),
+                },
+            );
+            if guard.len() > STARKNET_READ_CACHE_MAX_ENTRIES {
+                guard.retain(|_, entry| entry.fetched_at.elapsed() <= entry.ttl);
+            }
+        }
+        Ok(values)
+    }
+
     /// Fetches data for `get_transaction_receipt`.
     ///
     /// # Arguments
@@ -548,10 +1150,15 @@ impl OnchainReader {
                 .map_err(|e| crate::error::AppError::BlockchainRPC(e.to_string()));
             match response {
                 Ok(receipt) => {
+                    record_endpoint_success(self.provider_url(*provider_index));
                     rpc_record_success().await;
                     return Ok(receipt);
                 }
                 Err(crate::error::AppError::BlockchainRPC(err_text)) => {
+                    record_endpoint_failure(
+                        self.provider_url(*provider_index),
+                        looks_like_transient_rpc_error(&err_text),
+                    );
                     last_error_text = Some(err_text.clone());
                     if attempt + 1 < order.len() && looks_like_transient_rpc_error(&err_text) {
                         continue;
@@ -565,7 +1172,11 @@ impl OnchainReader {
         }
 
         if let Some(err_text) = last_error_text {
-            rpc_record_failure("starknet_getTransactionReceipt", &err_text).await;
+            rpc_record_failure(
+                "starknet_getTransactionReceipt",
+                looks_like_transient_rpc_error(&err_text),
+            )
+            .await;
             return Err(crate::error::AppError::BlockchainRPC(err_text));
         }
         Err(crate::error::AppError::BlockchainRPC(
@@ -596,10 +1207,15 @@ impl OnchainReader {
                 .map_err(|e| crate::error::AppError::BlockchainRPC(e.to_string()));
             match response {
                 Ok(tx) => {
+                    record_endpoint_success(self.provider_url(*provider_index));
                     rpc_record_success().await;
                     return Ok(tx);
                 }
                 Err(crate::error::AppError::BlockchainRPC(err_text)) => {
+                    record_endpoint_failure(
+                        self.provider_url(*provider_index),
+                        looks_like_transient_rpc_error(&err_text),
+                    );
                     last_error_text = Some(err_text.clone());
                     if attempt + 1 < order.len() && looks_like_transient_rpc_error(&err_text) {
                         continue;
@@ -613,7 +1229,11 @@ impl OnchainReader {
         }
 
         if let Some(err_text) = last_error_text {
-            rpc_record_failure("starknet_getTransactionByHash", &err_text).await;
+            rpc_record_failure(
+                "starknet_getTransactionByHash",
+                looks_like_transient_rpc_error(&err_text),
+            )
+            .await;
             return Err(crate::error::AppError::BlockchainRPC(err_text));
         }
         Err(crate::error::AppError::BlockchainRPC(
@@ -644,10 +1264,15 @@ impl OnchainReader {
                 .map_err(|e| crate::error::AppError::BlockchainRPC(e.to_string()));
             match response {
                 Ok(class_data) => {
+                    record_endpoint_success(self.provider_url(*provider_index));
                     rpc_record_success().await;
                     return Ok(class_data);
                 }
                 Err(crate::error::AppError::BlockchainRPC(err_text)) => {
+                    record_endpoint_failure(
+                        self.provider_url(*provider_index),
+                        looks_like_transient_rpc_error(&err_text),
+                    );
                     last_error_text = Some(err_text.clone());
                     if attempt + 1 < order.len() && looks_like_transient_rpc_error(&err_text) {
                         continue;
@@ -661,7 +1286,11 @@ impl OnchainReader {
         }
 
         if let Some(err_text) = last_error_text {
-            rpc_record_failure("starknet_getClassAt", &err_text).await;
+            rpc_record_failure(
+                "starknet_getClassAt",
+                looks_like_transient_rpc_error(&err_text),
+            )
+            .await;
             return Err(crate::error::AppError::BlockchainRPC(err_text));
         }
         Err(crate::error::AppError::BlockchainRPC(
@@ -692,10 +1321,15 @@ impl OnchainReader {
                 .map_err(|e| crate::error::AppError::BlockchainRPC(e.to_string()));
             match response {
                 Ok(class_hash) => {
+                    record_endpoint_success(self.provider_url(*provider_index));
                     rpc_record_success().await;
                     return Ok(class_hash);
                 }
                 Err(crate::error::AppError::BlockchainRPC(err_text)) => {
+                    record_endpoint_failure(
+                        self.provider_url(*provider_index),
+                        looks_like_transient_rpc_error(&err_text),
+                    );
                     last_error_text = Some(err_text.clone());
                     if attempt + 1 < order.len() && looks_like_transient_rpc_error(&err_text) {
                         continue;
@@ -709,7 +1343,11 @@ impl OnchainReader {
         }
 
         if let Some(err_text) = last_error_text {
-            rpc_record_failure("starknet_getClassHashAt", &err_text).await;
+            rpc_record_failure(
+                "starknet_getClassHashAt",
+                looks_like_transient_rpc_error(&err_text),
+            )
+            .await;
             return Err(crate::error::AppError::BlockchainRPC(err_text));
         }
         Err(crate::error::AppError::BlockchainRPC(
@@ -740,10 +1378,15 @@ impl OnchainReader {
                 .map_err(|e| crate::error::AppError::BlockchainRPC(e.to_string()));
             match response {
                 Ok(storage) => {
+                    record_endpoint_success(self.provider_url(*provider_index));
                     rpc_record_success().await;
                     return Ok(storage);
                 }
                 Err(crate::error::AppError::BlockchainRPC(err_text)) => {
+                    record_endpoint_failure(
+                        self.provider_url(*provider_index),
+                        looks_like_transient_rpc_error(&err_text),
+                    );
                     last_error_text = Some(err_text.clone());
                     if attempt + 1 < order.len() && looks_like_transient_rpc_error(&err_text) {
                         continue;
@@ -757,7 +1400,11 @@ impl OnchainReader {
         }
 
         if let Some(err_text) = last_error_text {
-            rpc_record_failure("starknet_getStorageAt", &err_text).await;
+            rpc_record_failure(
+                "starknet_getStorageAt",
+                looks_like_transient_rpc_error(&err_text),
+            )
+            .await;
             return Err(crate::error::AppError::BlockchainRPC(err_text));
         }
         Err(crate::error::AppError::BlockchainRPC(
@@ -914,3 +1561,407 @@ pub fn u256_from_felts(low: &Felt, high: &Felt) -> Result<u128> {
 pub fn u256_to_felts(value: u128) -> (Felt, Felt) {
     (Felt::from(value), Felt::from(0_u128))
 }
+
+const CAREL_BURN_VERIFY_ATTEMPTS: usize = 5;
+const CAREL_BURN_VERIFY_RETRY_MS: u64 = 900;
+
+/// CAREL transfer to the burn address, as confirmed by a transaction receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifiedCarelBurn {
+    pub amount_wei: u128,
+    pub block_number: u64,
+}
+
+/// Address that CAREL burn-gated features pay into.
+///
+/// # Notes
+/// * `AI_LEVEL_BURN_ADDRESS` takes precedence; otherwise `TREASURY_ADDRESS` is used.
+pub fn configured_carel_burn_address(config: &Config) -> Option<String> {
+    [&config.ai_level_burn_address, &config.treasury_address]
+        .into_iter()
+        .filter_map(|value| value.as_deref().map(str::trim))
+        .find(|value| !value.is_empty() && !value.starts_with("0x0000"))
+        .map(str::to_string)
+}
+
+/// Verifies that `tx_hash` burned at least `expected_amount_wei` CAREL from one of `senders`.
+///
+/// # Returns
+/// * The total CAREL transferred to the burn address and the block it was included in.
+///
+/// # Notes
+/// * Amounts come from the CAREL `Transfer` events in the receipt, not from the calldata.
+/// * Retries while the receipt is missing or still pre-confirmed; reverted transactions fail.
+pub async fn verify_carel_burn(
+    config: &Config,
+    tx_hash: &str,
+    expected_amount_wei: u128,
+    senders: &[Felt],
+) -> Result<VerifiedCarelBurn> {
+    let burn_address = configured_carel_burn_address(config).ok_or_else(|| {
+        crate::error::AppError::BadRequest(
+            "AI_LEVEL_BURN_ADDRESS or TREASURY_ADDRESS must be configured to verify CAREL burns"
+                .to_string(),
+        )
+    })?;
+    let carel_token = config.carel_token_address.trim();
+    if carel_token.is_empty() || carel_token.starts_with("0x0000") {
+        return Err(crate::error::AppError::BadRequest(
+            "CAREL_TOKEN_ADDRESS is not configured".to_string(),
+        ));
+    }
+
+    let reader = OnchainReader::from_config(config)?;
+    let tx_hash_felt = parse_felt(tx_hash)?;
+    let carel_token_felt = parse_felt(carel_token)?;
+    let burn_address_felt = parse_felt(&burn_address)?;
+    let mut last_error = String::new();
+
+    for attempt in 0..CAREL_BURN_VERIFY_ATTEMPTS {
+        match reader.get_transaction_receipt(&tx_hash_felt).await {
+            Ok(receipt) => {
+                if let ExecutionResult::Reverted { reason } = receipt.receipt.execution_result() {
+                    return Err(crate::error::AppError::BadRequest(format!(
+                        "onchain_tx_hash reverted: {}",
+                        reason
+                    )));
+                }
+                if matches!(
+                    receipt.receipt.finality_status(),
+                    TransactionFinalityStatus::PreConfirmed
+                ) {
+                    last_error = "transaction still pre-confirmed".to_string();
+                } else {
+                    let amount_wei = carel_burn_amount(
+                        receipt.receipt.events(),
+                        carel_token_felt,
+                        senders,
+                        burn_address_felt,
+                    )?;
+                    if amount_wei < expected_amount_wei {
+                        return Err(crate::error::AppError::BadRequest(format!(
+                            "onchain_tx_hash burns {} CAREL wei from the authenticated user; {} required",
+                            amount_wei, expected_amount_wei
+                        )));
+                    }
+                    return Ok(VerifiedCarelBurn {
+                        amount_wei,
+                        block_number: receipt.block.block_number(),
+                    });
+                }
+            }
+            Err(err) => last_error = err.to_string(),
+        }
+        if attempt + 1 < CAREL_BURN_VERIFY_ATTEMPTS {
+            sleep(Duration::from_millis(CAREL_BURN_VERIFY_RETRY_MS)).await;
+        }
+    }
+
+    Err(crate::error::AppError::BadRequest(format!(
+        "onchain_tx_hash not confirmed on Starknet RPC: {}",
+        last_error
+    )))
+}
+
+// Internal helper that sums CAREL `Transfer` events from `senders` to `burn_address`.
+fn carel_burn_amount(
+    events: &[Event],
+    carel_token: Felt,
+    senders: &[Felt],
+    burn_address: Felt,
+) -> Result<u128> {
+    sum_erc20_transfers(events, carel_token, burn_address, |from| {
+        senders.contains(from)
+    })
+}
+
+/// Sums the `token` received by `recipient` from other accounts in a receipt's events.
+///
+/// # Notes
+/// * Used to settle on what a transaction actually delivered instead of a quoted minimum.
+pub fn erc20_received_amount(events: &[Event], token: Felt, recipient: Felt) -> Result<u128> {
+    sum_erc20_transfers(events, token, recipient, |from| *from != recipient)
+}
+
+// Internal helper that sums `token` `Transfer` events to `recipient` whose sender passes `from_filter`.
+fn sum_erc20_transfers(
+    events: &[Event],
+    token: Felt,
+    recipient: Felt,
+    from_filter: impl Fn(&Felt) -> bool,
+) -> Result<u128> {
+    let transfer_selector = get_selector_from_name("Transfer")
+        .map_err(|e| crate::error::AppError::Internal(format!("Selector error: {}", e)))?;
+    let mut total: u128 = 0;
+    for event in events {
+        if event.from_address != token || event.keys.first() != Some(&transfer_selector) {
+            continue;
+        }
+        // Cairo 1 ERC20s index `from`/`to` as keys; legacy tokens emit everything as data.
+        let (from, to, low, high) = match (event.keys.as_slice(), event.data.as_slice()) {
+            ([_, from, to], [low, high, ..]) => (from, to, low, high),
+            ([_], [from, to, low, high, ..]) => (from, to, low, high),
+            _ => continue,
+        };
+        if !from_filter(from) || *to != recipient {
+            continue;
+        }
+        total = total
+            .checked_add(u256_from_felts(low, high)?)
+            .ok_or_else(|| {
+                crate::error::AppError::BadRequest("Token transfer amount overflows".to_string())
+            })?;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `carel_burn_amount_sums_matching_transfers` operations.
+    fn carel_burn_amount_sums_matching_transfers() {
+        let selector = get_selector_from_name("Transfer").unwrap();
+        let token = Felt::from(0x100_u64);
+        let user = Felt::from(0x200_u64);
+        let burn = Felt::from(0x300_u64);
+        let other = Felt::from(0x400_u64);
+        let events = vec![
+            // Cairo 1 layout: from/to as keys.
+            Event {
+                from_address: token,
+                keys: vec![selector, user, burn],
+                data: vec![Felt::from(7_u64), Felt::ZERO],
+            },
+            // Legacy layout: everything in data.
+            Event {
+                from_address: token,
+                keys: vec![selector],
+                data: vec![user, burn, Felt::from(3_u64), Felt::ZERO],
+            },
+            // Ignored: different token, different sender, different recipient.
+            Event {
+                from_address: other,
+                keys: vec![selector, user, burn],
+                data: vec![Felt::from(100_u64), Felt::ZERO],
+            },
+            Event {
+                from_address: token,
+                keys: vec![selector, other, burn],
+                data: vec![Felt::from(100_u64), Felt::ZERO],
+            },
+            Event {
+                from_address: token,
+                keys: vec![selector, user, other],
+                data: vec![Felt::from(100_u64), Felt::ZERO],
+            },
+        ];
+        assert_eq!(
+            carel_burn_amount(&events, token, &[user], burn).unwrap(),
+            10
+        );
+        assert_eq!(
+            carel_burn_amount(&events, token, &[other], user).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `erc20_received_amount_ignores_self_transfers` operations.
+    fn erc20_received_amount_ignores_self_transfers() {
+        let selector = get_selector_from_name("Transfer").unwrap();
+        let token = Felt::from(0x100_u64);
+        let backend = Felt::from(0x200_u64);
+        let pool = Felt::from(0x300_u64);
+        let events = vec![
+            Event {
+                from_address: token,
+                keys: vec![selector, pool, backend],
+                data: vec![Felt::from(1_250_u64), Felt::ZERO],
+            },
+            Event {
+                from_address: token,
+                keys: vec![selector, backend, backend],
+                data: vec![Felt::from(999_u64), Felt::ZERO],
+            },
+            Event {
+                from_address: token,
+                keys: vec![selector, backend, pool],
+                data: vec![Felt::from(50_u64), Felt::ZERO],
+            },
+        ];
+        assert_eq!(
+            erc20_received_amount(&events, token, backend).unwrap(),
+            1_250
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `rank_providers_by_health_moves_degraded_last` operations.
+    fn rank_providers_by_health_moves_degraded_last() {
+        let ranked = rank_providers_by_health(vec![1, 2, 0], &[0, 3, 0]);
+        assert_eq!(ranked, vec![2, 0, 1]);
+    }
+
+    #[test]
+    // Internal helper that supports `rank_providers_by_health_prefers_least_failing` operations.
+    fn rank_providers_by_health_prefers_least_failing() {
+        let ranked = rank_providers_by_health(vec![0, 1, 2], &[5, 2, 4]);
+        assert_eq!(ranked, vec![1, 2, 0]);
+    }
+
+    #[test]
+    // Internal helper that supports `endpoint_health_recovers_after_cooldown` operations.
+    fn endpoint_health_recovers_after_cooldown() {
+        let now = Instant::now();
+        let health = RpcEndpointHealth {
+            consecutive_failures: 3,
+            last_failure_at: Some(now),
+        };
+        assert!(health.is_degraded(2, Duration::from_secs(60), now));
+        assert!(!health.is_degraded(2, Duration::from_secs(60), now + Duration::from_secs(61)));
+        assert!(!health.is_degraded(4, Duration::from_secs(60), now));
+    }
+
+    #[test]
+    // Internal helper that supports `parse_read_cache_ttls_skips_disabled_and_invalid` operations.
+    fn parse_read_cache_ttls_skips_disabled_and_invalid() {
+        let ttls = parse_read_cache_ttls("decimals=600, symbol=0,get_root=abc,broken");
+        let decimals = get_selector_from_name("decimals").expect("selector");
+        assert_eq!(ttls.len(), 1);
+        assert_eq!(ttls.get(&decimals), Some(&Duration::from_secs(600)));
+
+        let defaults = parse_read_cache_ttls(STARKNET_READ_CACHE_TTLS_DEFAULT);
+        let fixed_amount = get_selector_from_name("fixed_amount").expect("selector");
+        assert!(!defaults.contains_key(&fixed_amount));
+    }
+
+    #[test]
+    // Internal helper that supports `read_cache_skips_zero_results` operations.
+    fn read_cache_skips_zero_results() {
+        assert!(!is_cacheable_read_result(&[]));
+        assert!(!is_cacheable_read_result(&[Felt::ZERO, Felt::ZERO]));
+        assert!(is_cacheable_read_result(&[Felt::from(5_u64), Felt::ZERO]));
+    }
+
+    #[test]
+    // Internal helper that supports `read_cache_key_distinguishes_calldata` operations.
+    fn read_cache_key_distinguishes_calldata() {
+        let selector = get_selector_from_name("decimals").expect("selector");
+        let base = FunctionCall {
+            contract_address: Felt::from(1_u64),
+            entry_point_selector: selector,
+            calldata: vec![Felt::from(2_u64)],
+        };
+        let other = FunctionCall {
+            calldata: vec![Felt::from(3_u64)],
+            ..base.clone()
+        };
+        assert_ne!(read_cache_key(&base), read_cache_key(&other));
+    }
+
+    #[test]
+    // Internal helper that supports `batch_call_values_rejects_length_mismatch` operations.
+    fn batch_call_values_rejects_length_mismatch() {
+        let responses = vec![ProviderResponseData::Call(vec![Felt::from(1_u64)])];
+        assert!(batch_call_values(responses, 2).is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `batch_call_values_preserves_order` operations.
+    fn batch_call_values_preserves_order() {
+        let responses = vec![
+            ProviderResponseData::Call(vec![Felt::from(1_u64)]),
+            ProviderResponseData::Call(vec![Felt::from(2_u64), Felt::ZERO]),
+        ];
+        let values = batch_call_values(responses, 2).expect("batch values");
+        assert_eq!(values[0].as_ref().expect("first"), &vec![Felt::from(1_u64)]);
+        assert_eq!(
+            values[1].as_ref().expect("second"),
+            &vec![Felt::from(2_u64), Felt::ZERO]
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `batch_call_values_isolates_unexpected_entries` operations.
+    fn batch_call_values_isolates_unexpected_entries() {
+        let responses = vec![
+            ProviderResponseData::SpecVersion("0.8.1".to_string()),
+            ProviderResponseData::Call(vec![Felt::from(7_u64), Felt::ZERO]),
+        ];
+        let values = batch_call_values(responses, 2).expect("batch values");
+        assert!(matches!(
+            values[0],
+            Err(crate::error::AppError::BlockchainRPC(_))
+        ));
+        assert_eq!(
+            values[1].as_ref().expect("second"),
+            &vec![Felt::from(7_u64), Felt::ZERO]
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `onchain_error_from_message_classifies_probe_failures` operations.
+    fn onchain_error_from_message_classifies_probe_failures() {
+        assert!(matches!(
+            OnchainError::from_message("ENTRYPOINT_NOT_FOUND"),
+            OnchainError::EntrypointNotFound(_)
+        ));
+        assert!(matches!(
+            OnchainError::from_message("ContractError { revert_error: \"u256_sub Overflow\" }"),
+            OnchainError::ContractRevert(_)
+        ));
+        assert!(matches!(
+            OnchainError::from_message("HTTP 429 Too Many Requests"),
+            OnchainError::RateLimited(_)
+        ));
+        assert!(matches!(
+            OnchainError::from_message("error sending request for url"),
+            OnchainError::Network(_)
+        ));
+        assert!(matches!(
+            OnchainError::from_message("something unexpected"),
+            OnchainError::Other(_)
+        ));
+    }
+
+    #[test]
+    // Internal helper that supports `onchain_error_prefers_structured_codes` operations.
+    fn onchain_error_prefers_structured_codes() {
+        assert!(matches!(
+            OnchainError::from_json_rpc_code(21, "", "opaque".to_string()),
+            OnchainError::EntrypointNotFound(_)
+        ));
+        assert!(matches!(
+            OnchainError::from_json_rpc_code(40, "", "opaque".to_string()),
+            OnchainError::ContractRevert(_)
+        ));
+        assert!(matches!(
+            OnchainError::from_json_rpc_code(
+                40,
+                "Entry point EntryPointSelector(0x1) not found in contract",
+                "Contract error".to_string()
+            ),
+            OnchainError::EntrypointNotFound(_)
+        ));
+        assert!(matches!(
+            OnchainError::from_json_rpc_code(-32005, "", "opaque".to_string()),
+            OnchainError::RateLimited(_)
+        ));
+        assert!(OnchainError::from_provider_error(&ProviderError::RateLimited).is_transient());
+    }
+
+    #[test]
+    // Internal helper that supports `parse_rpc_url_list_accepts_comma_separated` operations.
+    fn parse_rpc_url_list_accepts_comma_separated() {
+        let urls = parse_rpc_url_list("https://a.example, https://b.example,,https://a.example");
+        assert_eq!(
+            dedupe_rpc_urls(urls),
+            vec![
+                "https://a.example".to_string(),
+                "https://b.example".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::models::OrderStatus;
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamMaxlen, StreamReadOptions, StreamReadReply},
+    AsyncCommands,
+};
+use serde::Serialize;
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+use tokio::time::Duration;
+
+const ORDER_EVENT_CHANNEL_CAPACITY: usize = 1024;
+/// Redis stream that carries order events between backend instances.
+pub const ORDER_EVENT_STREAM_KEY: &str = "order_events:stream";
+const ORDER_EVENT_STREAM_MAXLEN: usize = 10_000;
+/// How long a single relay `XREAD` blocks waiting for new entries.
+pub const ORDER_EVENT_READ_BLOCK_MS: usize = 5_000;
+const ORDER_EVENT_READ_COUNT: usize = 256;
+const ORDER_EVENT_RETRY_DELAY_SECS: u64 = 2;
+
+static ORDER_EVENT_BUS: OnceLock<broadcast::Sender<OrderEvent>> = OnceLock::new();
+static ORDER_EVENT_PUBLISHER: OnceLock<ConnectionManager> = OnceLock::new();
+
+/// Limit-order lifecycle change (fill, cancel, expire) emitted after storage is updated.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderEvent {
+    pub order_id: String,
+    pub owner: String,
+    pub status: OrderStatus,
+    pub filled: String,
+    pub timestamp: i64,
+}
+
+impl OrderEvent {
+    /// Returns true when the event belongs to `owner` (address comparison is case-insensitive).
+    pub fn belongs_to(&self, owner: &str) -> bool {
+        self.owner.trim().eq_ignore_ascii_case(owner.trim())
+    }
+
+    // Internal helper that builds the field list written to the Redis stream.
+    fn stream_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("order_id", self.order_id.clone()),
+            ("owner", self.owner.clone()),
+            ("status", i16::from(self.status).to_string()),
+            ("filled", self.filled.clone()),
+            ("timestamp", self.timestamp.to_string()),
+        ]
+    }
+
+    // Internal helper that parses a Redis stream entry back into an event.
+    fn from_stream_entry(entry: &StreamId) -> Option<Self> {
+        Some(Self {
+            order_id: entry.get("order_id")?,
+            owner: entry.get("owner")?,
+            status: OrderStatus::from(entry.get::<i16>("status")?),
+            filled: entry.get("filled").unwrap_or_else(|| "0".to_string()),
+            timestamp: entry.get("timestamp").unwrap_or_default(),
+        })
+    }
+}
+
+// Internal helper that supports `bus` operations.
+fn bus() -> &'static broadcast::Sender<OrderEvent> {
+    ORDER_EVENT_BUS.get_or_init(|| broadcast::channel(ORDER_EVENT_CHANNEL_CAPACITY).0)
+}
+
+/// Subscribes to this instance's order event fan-out.
+///
+/// # Returns
+/// * A receiver that yields every order event relayed after subscription.
+///
+/// # Notes
+/// * Slow receivers observe `RecvError::Lagged` and should resync from storage.
+pub fn subscribe() -> broadcast::Receiver<OrderEvent> {
+    bus().subscribe()
+}
+
+/// Installs the Redis connection used to append events to `ORDER_EVENT_STREAM_KEY`.
+///
+/// # Notes
+/// * Until this is called (tests, tooling) events only reach subscribers in this process.
+pub fn install_stream_publisher(redis: ConnectionManager) {
+    let _ = ORDER_EVENT_PUBLISHER.set(redis);
+}
+
+/// Publishes an order event to every backend instance.
+///
+/// # Notes
+/// * Appends to the Redis stream; each instance's relay fans the entry out to its sockets.
+/// * Falls back to the local bus when no stream publisher is installed or `XADD` fails.
+pub fn publish(event: OrderEvent) {
+    let Some(redis) = ORDER_EVENT_PUBLISHER.get() else {
+        let _ = bus().send(event);
+        return;
+    };
+    let mut conn = redis.clone();
+    tokio::spawn(async move {
+        let written: redis::RedisResult<String> = conn
+            .xadd_maxlen(
+                ORDER_EVENT_STREAM_KEY,
+                StreamMaxlen::Approx(ORDER_EVENT_STREAM_MAXLEN),
+                "*",
+                &event.stream_fields(),
+            )
+            .await;
+        if let Err(err) = written {
+            tracing::warn!(
+                "order event stream append failed for {}: {}",
+                event.order_id,
+                err
+            );
+            let _ = bus().send(event);
+        }
+    });
+}
+
+/// Relays new entries from `ORDER_EVENT_STREAM_KEY` to this instance's subscribers.
+///
+/// # Arguments
+/// * `redis` - Dedicated connection whose response timeout exceeds `ORDER_EVENT_READ_BLOCK_MS`;
+///   blocking reads would otherwise stall commands multiplexed on the shared connection.
+///
+/// # Notes
+/// * Starts at the stream tail and tracks the last delivered id, so entries appended while
+///   Redis was briefly unreachable are still delivered once the read succeeds again.
+pub async fn run_stream_relay(mut redis: ConnectionManager) {
+    let options = StreamReadOptions::default()
+        .block(ORDER_EVENT_READ_BLOCK_MS)
+        .count(ORDER_EVENT_READ_COUNT);
+    let mut last_id = "$".to_string();
+    loop {
+        let reply: redis::RedisResult<Option<StreamReadReply>> = redis
+            .xread_options(&[ORDER_EVENT_STREAM_KEY], &[last_id.as_str()], &options)
+            .await;
+        match reply {
+            Ok(Some(reply)) => {
+                for entry in reply.keys.into_iter().flat_map(|key| key.ids) {
+                    if let Some(event) = OrderEvent::from_stream_entry(&entry) {
+                        let _ = bus().send(event);
+                    }
+                    last_id = entry.id;
+                }
+            }
+            Ok(None) => {}
+            Err(err) => {
+                tracing::warn!("order event stream read failed: {}", err);
+                tokio::time::sleep(Duration::from_secs(ORDER_EVENT_RETRY_DELAY_SECS)).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `belongs_to_matches_owner_case_insensitively` operations.
+    fn belongs_to_matches_owner_case_insensitively() {
+        let event = OrderEvent {
+            order_id: "0x1".to_string(),
+            owner: "0xABC".to_string(),
+            status: OrderStatus::Filled,
+            filled: "1".to_string(),
+            timestamp: 0,
+        };
+        assert!(event.belongs_to("0xabc"));
+        assert!(!event.belongs_to("0xdef"));
+    }
+
+    #[test]
+    // Internal helper that supports `stream_entry_round_trips_event` operations.
+    fn stream_entry_round_trips_event() {
+        let event = OrderEvent {
+            order_id: "0x2".to_string(),
+            owner: "0xabc".to_string(),
+            status: OrderStatus::Unknown(9),
+            filled: "1.5".to_string(),
+            timestamp: 42,
+        };
+        let entry = StreamId {
+            id: "1-0".to_string(),
+            map: event
+                .stream_fields()
+                .into_iter()
+                .map(|(field, value)| (field.to_string(), redis::Value::BulkString(value.into())))
+                .collect(),
+            ..Default::default()
+        };
+        let decoded = OrderEvent::from_stream_entry(&entry).expect("decoded event");
+        assert_eq!(decoded.order_id, "0x2");
+        assert_eq!(decoded.owner, "0xabc");
+        assert_eq!(decoded.status, OrderStatus::Unknown(9));
+        assert_eq!(decoded.filled, "1.5");
+        assert_eq!(decoded.timestamp, 42);
+    }
+
+    #[tokio::test]
+    // Internal helper that supports `published_events_reach_subscribers` operations.
+    async fn published_events_reach_subscribers() {
+        let mut rx = subscribe();
+        publish(OrderEvent {
+            order_id: "0xorder-events-test".to_string(),
+            owner: "0x1".to_string(),
+            status: OrderStatus::Cancelled,
+            filled: "0".to_string(),
+            timestamp: 0,
+        });
+        loop {
+            let event = rx.recv().await.expect("event");
+            if event.order_id == "0xorder-events-test" {
+                assert_eq!(event.status, OrderStatus::Cancelled);
+                break;
+            }
+        }
+    }
+}
@@ -2,45 +2,86 @@ use crate::services::onchain::{parse_felt, OnchainInvoker};
 use crate::{
     config::Config,
     constants::{
-        EPOCH_DURATION_SECONDS, MULTIPLIER_TIER_1, MULTIPLIER_TIER_2, MULTIPLIER_TIER_3,
-        MULTIPLIER_TIER_4, POINTS_BATTLE_HIT, POINTS_BATTLE_LOSS, POINTS_BATTLE_MISS,
-        POINTS_BATTLE_TIMEOUT_WIN, POINTS_BATTLE_WIN, POINTS_MIN_STAKE_BTC,
-        POINTS_MIN_STAKE_BTC_TESTNET, POINTS_MIN_STAKE_CAREL, POINTS_MIN_STAKE_CAREL_TESTNET,
-        POINTS_MIN_STAKE_LP, POINTS_MIN_STAKE_LP_TESTNET, POINTS_MIN_STAKE_STABLECOIN,
+        MULTIPLIER_TIER_1, MULTIPLIER_TIER_2, MULTIPLIER_TIER_3, MULTIPLIER_TIER_4,
+        POINTS_BATTLE_HIT, POINTS_BATTLE_LOSS, POINTS_BATTLE_MISS, POINTS_BATTLE_TIMEOUT_WIN,
+        POINTS_BATTLE_WIN, POINTS_MIN_STAKE_BTC, POINTS_MIN_STAKE_BTC_TESTNET,
+        POINTS_MIN_STAKE_CAREL, POINTS_MIN_STAKE_CAREL_TESTNET, POINTS_MIN_STAKE_LP,
+        POINTS_MIN_STAKE_LP_TESTNET, POINTS_MIN_STAKE_STABLECOIN,
         POINTS_MIN_STAKE_STABLECOIN_TESTNET, POINTS_MIN_STAKE_STRK, POINTS_MIN_STAKE_STRK_TESTNET,
-        POINTS_MIN_USD_BRIDGE_BTC, POINTS_MIN_USD_BRIDGE_BTC_TESTNET, POINTS_MIN_USD_BRIDGE_ETH,
-        POINTS_MIN_USD_BRIDGE_ETH_TESTNET, POINTS_MIN_USD_LIMIT_ORDER,
-        POINTS_MIN_USD_LIMIT_ORDER_TESTNET, POINTS_MIN_USD_SWAP, POINTS_MIN_USD_SWAP_TESTNET,
-        POINTS_MULTIPLIER_STAKE_BTC, POINTS_MULTIPLIER_STAKE_CAREL_TIER_1,
-        POINTS_MULTIPLIER_STAKE_CAREL_TIER_2, POINTS_MULTIPLIER_STAKE_CAREL_TIER_3,
-        POINTS_MULTIPLIER_STAKE_LP, POINTS_MULTIPLIER_STAKE_STABLECOIN, POINTS_PER_USD_BRIDGE_BTC,
-        POINTS_PER_USD_BRIDGE_ETH, POINTS_PER_USD_LIMIT_ORDER, POINTS_PER_USD_STAKE,
+        POINTS_MIN_USD_LIMIT_ORDER, POINTS_MIN_USD_LIMIT_ORDER_TESTNET, POINTS_MIN_USD_SWAP,
+        POINTS_MIN_USD_SWAP_TESTNET, POINTS_MULTIPLIER_STAKE_BTC,
+        POINTS_MULTIPLIER_STAKE_CAREL_TIER_1, POINTS_MULTIPLIER_STAKE_CAREL_TIER_2,
+        POINTS_MULTIPLIER_STAKE_CAREL_TIER_3, POINTS_MULTIPLIER_STAKE_LP,
+        POINTS_MULTIPLIER_STAKE_STABLECOIN, POINTS_PER_USD_LIMIT_ORDER, POINTS_PER_USD_STAKE,
         POINTS_PER_USD_SWAP, POINT_CALCULATOR_INTERVAL_SECS,
     },
     db::Database,
     error::Result,
-    services::price_guard::sanitize_points_usd_base,
+    services::{
+        discount_service::ai_level_points_bonus_percent, price_guard::sanitize_points_usd_base,
+    },
 };
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use sqlx::Row;
 use starknet_core::types::{Call, Felt};
 use starknet_core::utils::get_selector_from_name;
+use std::sync::atomic::{AtomicI64, AtomicU32, Ordering};
 use std::sync::Arc;
 use tokio::time::{interval, Duration};
 
+// Internal helper that converts the configured onboarding bonus into points, if enabled.
+fn onboarding_bonus_amount(configured_points: f64) -> Option<Decimal> {
+    if !configured_points.is_finite() || configured_points <= 0.0 {
+        return None;
+    }
+    Decimal::from_f64_retain(configured_points).filter(|amount| *amount > Decimal::ZERO)
+}
+
+/// Grants the configured one-time onboarding bonus to the user owning `address`.
+///
+/// # Notes
+/// * `address` may be any linked wallet; the bonus lands on the canonical user.
+/// * Idempotent via `Database::grant_onboarding_bonus`, so wallet links and the first
+///   processed transaction can all call it.
+/// * Best effort: failures are logged so callers never fail on the bonus.
+pub async fn grant_onboarding_bonus(db: &Database, config: &Config, address: &str) {
+    let Some(amount) = onboarding_bonus_amount(config.onboarding_bonus_points) else {
+        return;
+    };
+    let canonical = match db.find_user_by_wallet_address(address, None).await {
+        Ok(canonical) => canonical.unwrap_or_else(|| address.to_string()),
+        Err(err) => {
+            tracing::warn!("Onboarding bonus lookup failed for {}: {}", address, err);
+            return;
+        }
+    };
+    let epoch = config.current_rewards_epoch();
+    match db.grant_onboarding_bonus(&canonical, epoch, amount).await {
+        Ok(true) => tracing::info!(
+            "Granted onboarding bonus of {} points to {}",
+            amount,
+            canonical
+        ),
+        Ok(false) => {}
+        Err(err) => tracing::warn!("Onboarding bonus grant failed for {}: {}", canonical, err),
+    }
+}
+
 /// Point Calculator - Calculates trading points with anti-wash trading detection
 pub struct PointCalculator {
     db: Database,
     config: Config,
     onchain: Option<OnchainInvoker>,
+    // Last stuck-transaction count, so the alert only logs when it changes.
+    last_stuck_count: AtomicI64,
+    // Batches per tick, adjusted between the configured baseline and ceiling based on lag.
+    batches_per_tick: AtomicU32,
 }
 
 const REFERRAL_MIN_USD_VOLUME: i64 = 20;
 const REFERRAL_REFERRER_BONUS_BPS: i64 = 1000; // 10%
 const REFERRAL_REFEREE_BONUS_BPS: i64 = 1000; // 10%
-const AI_LEVEL_2_POINTS_BONUS_PERCENT: f64 = 20.0;
-const AI_LEVEL_3_POINTS_BONUS_PERCENT: f64 = 40.0;
 
 impl PointCalculator {
     /// Constructs a new instance via `new`.
@@ -56,10 +97,13 @@ impl PointCalculator {
     /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
     pub fn new(db: Database, config: Config) -> Self {
         let onchain = OnchainInvoker::from_config(&config).ok().flatten();
+        let batches_per_tick = AtomicU32::new(config.point_calculator_max_batches_per_tick.max(1));
         Self {
             db,
             config,
             onchain,
+            last_stuck_count: AtomicI64::new(0),
+            batches_per_tick,
         }
     }
 
@@ -79,6 +123,9 @@ impl PointCalculator {
                 if let Err(e) = self.calculate_pending_points().await {
                     tracing::error!("Point calculator error: {}", e);
                 }
+                if let Err(e) = self.accrue_stake_points().await {
+                    tracing::error!("Stake points accrual error: {}", e);
+                }
             }
         });
     }
@@ -89,17 +136,48 @@ impl PointCalculator {
             tracing::debug!("Point calculator running in testnet mode");
         }
         let batch_size = self.config.point_calculator_batch_size.max(1) as i64;
-        let max_batches = self.config.point_calculator_max_batches_per_tick.max(1);
+        let max_attempts = self.config.point_calculator_max_attempts as i32;
+        let lag = self.db.count_pending_transactions(max_attempts).await?;
+        let floor = self.config.point_calculator_max_batches_per_tick.max(1);
+        let ceiling = self.config.point_calculator_batches_ceiling.max(floor);
+        let previous_batches = self.batches_per_tick.load(Ordering::Relaxed);
+        let max_batches = next_batches_per_tick(previous_batches, lag, batch_size, floor, ceiling);
+        self.batches_per_tick.store(max_batches, Ordering::Relaxed);
+        if lag > 0 {
+            tracing::info!(
+                "Point calculator lag: pending={}, batches_per_tick={} (was {}, range {}..={})",
+                lag,
+                max_batches,
+                previous_batches,
+                floor,
+                ceiling
+            );
+        } else {
+            tracing::debug!(
+                "Point calculator lag: pending=0, batches_per_tick={}",
+                max_batches
+            );
+        }
 
         let mut fetched_total = 0usize;
         let mut processed_total = 0usize;
         let mut failed_total = 0usize;
 
         for _ in 0..max_batches {
+            // Failed rows back off exponentially and stop after the attempt cap until re-queued.
             let transactions = sqlx::query_as::<_, crate::models::Transaction>(
-                "SELECT * FROM transactions WHERE processed = false ORDER BY timestamp ASC LIMIT $1",
+                "SELECT * FROM transactions
+                 WHERE processed = false
+                   AND processing_attempts < $2
+                   AND (
+                       last_processing_attempt_at IS NULL
+                       OR last_processing_attempt_at
+                           < NOW() - make_interval(secs => 30 * POWER(2, LEAST(processing_attempts, 8)))
+                   )
+                 ORDER BY timestamp ASC LIMIT $1",
             )
             .bind(batch_size)
+            .bind(max_attempts)
             .fetch_all(self.db.pool())
             .await?;
 
@@ -122,6 +200,17 @@ impl PointCalculator {
                             tx.tx_type,
                             err
                         );
+                        if let Err(record_err) = self
+                            .db
+                            .record_transaction_processing_failure(&tx.tx_hash, &err.to_string())
+                            .await
+                        {
+                            tracing::warn!(
+                                "Failed to record processing failure for tx {}: {}",
+                                tx.tx_hash,
+                                record_err
+                            );
+                        }
                     }
                 }
             }
@@ -131,6 +220,17 @@ impl PointCalculator {
             }
         }
 
+        let stuck_after = self.config.transaction_stuck_after_secs as i64;
+        let stuck = self.db.count_stuck_transactions(stuck_after).await?;
+        let previous = self.last_stuck_count.swap(stuck, Ordering::Relaxed);
+        if stuck > 0 && stuck != previous {
+            tracing::warn!(
+                "{} transactions unprocessed for more than {}s; see GET /api/v1/admin/transactions/stuck",
+                stuck,
+                stuck_after
+            );
+        }
+
         if fetched_total > 0 {
             tracing::info!(
                 "Point calculator tick complete: fetched={}, processed={}, failed={}, batch_size={}, max_batches={}",
@@ -145,22 +245,128 @@ impl PointCalculator {
         Ok(())
     }
 
+    /// Award time-weighted stake points for open positions in pools with an accrual rate
+    async fn accrue_stake_points(&self) -> Result<()> {
+        let positions = sqlx::query_as::<_, (String, String, f64, f64, f64)>(
+            r#"
+            SELECT
+                user_address,
+                UPPER(token_in) AS pool,
+                COALESCE(SUM(CASE WHEN tx_type = 'stake' THEN amount_in ELSE 0 END), 0)::FLOAT,
+                COALESCE(SUM(CASE WHEN tx_type = 'unstake' THEN amount_in ELSE 0 END), 0)::FLOAT,
+                COALESCE(SUM(CASE WHEN tx_type = 'stake' THEN usd_value ELSE 0 END), 0)::FLOAT
+            FROM transactions
+            WHERE tx_type IN ('stake', 'unstake')
+              AND token_in IS NOT NULL
+            GROUP BY user_address, UPPER(token_in)
+            "#,
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let current_epoch = self.config.current_rewards_epoch();
+        for (user_address, pool, staked, unstaked, staked_usd) in positions {
+            let rate = self.config.stake_points_accrual_rate(&pool);
+            if rate <= 0.0 {
+                continue;
+            }
+            let now = chrono::Utc::now();
+            let inserted = sqlx::query(
+                r#"
+                INSERT INTO stake_points_accruals (user_address, pool, last_accrued_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (user_address, pool) DO NOTHING
+                "#,
+            )
+            .bind(&user_address)
+            .bind(&pool)
+            .bind(now)
+            .execute(self.db.pool())
+            .await?;
+            if inserted.rows_affected() > 0 {
+                // The deposit itself is awarded by `calculate_stake_points`; accrual starts here.
+                continue;
+            }
+
+            let last_accrued_at: chrono::DateTime<chrono::Utc> = sqlx::query_scalar(
+                "SELECT last_accrued_at FROM stake_points_accruals WHERE user_address = $1 AND pool = $2",
+            )
+            .bind(&user_address)
+            .bind(&pool)
+            .fetch_one(self.db.pool())
+            .await?;
+            let elapsed_secs = (now - last_accrued_at).num_seconds();
+            if elapsed_secs <= 0 {
+                continue;
+            }
+
+            // Compare-and-set so overlapping ticks never award the same window twice.
+            let claimed = sqlx::query(
+                r#"
+                UPDATE stake_points_accruals
+                SET last_accrued_at = $3
+                WHERE user_address = $1 AND pool = $2 AND last_accrued_at = $4
+                "#,
+            )
+            .bind(&user_address)
+            .bind(&pool)
+            .bind(now)
+            .bind(last_accrued_at)
+            .execute(self.db.pool())
+            .await?;
+            if claimed.rows_affected() == 0 {
+                continue;
+            }
+
+            let position_usd = open_stake_position_usd(staked, unstaked, staked_usd);
+            let base_points = stake_accrual_points(position_usd, rate, elapsed_secs);
+            if base_points <= 0.0 {
+                continue;
+            }
+            let nft_adjusted = self
+                .apply_nft_discount_bonus(&user_address, base_points)
+                .await?;
+            let points = self
+                .apply_ai_level_points_bonus(&user_address, nft_adjusted)
+                .await;
+            self.db
+                .create_or_update_points(
+                    &user_address,
+                    current_epoch,
+                    Decimal::ZERO,
+                    Decimal::ZERO,
+                    Decimal::from_f64_retain(points).unwrap_or_default(),
+                )
+                .await?;
+            self.apply_multipliers(&user_address, current_epoch).await?;
+            tracing::debug!(
+                "Stake points accrued: user={}, pool={}, position_usd={}, elapsed_secs={}, points={}",
+                user_address,
+                pool,
+                position_usd,
+                elapsed_secs,
+                points
+            );
+        }
+
+        Ok(())
+    }
+
     /// Process a single transaction and calculate points
     async fn process_transaction(&self, tx: &crate::models::Transaction) -> Result<()> {
         // Check for wash trading
         if self.is_wash_trading(&tx.user_address, &tx.tx_hash).await? {
             tracing::warn!("Wash trading detected for user: {}", tx.user_address);
 
-            sqlx::query("UPDATE transactions SET processed = true WHERE tx_hash = $1")
-                .bind(&tx.tx_hash)
-                .execute(self.db.pool())
+            self.db
+                .mark_transaction_processed(&tx.tx_hash, None)
                 .await?;
 
             self.flag_wash_trading(&tx.user_address).await?;
             return Ok(());
         }
 
-        let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+        let current_epoch = self.config.current_rewards_epoch();
         let prev_total: Decimal = sqlx::query_scalar(
             "SELECT COALESCE(total_points, 0) FROM points WHERE user_address = $1 AND epoch = $2",
         )
@@ -224,6 +430,7 @@ impl PointCalculator {
             }
             _ => {}
         }
+        grant_onboarding_bonus(&self.db, &self.config, &tx.user_address).await;
 
         // Apply multipliers after point updates
         self.apply_multipliers(&tx.user_address, current_epoch)
@@ -258,13 +465,9 @@ impl PointCalculator {
                 .await?;
         }
 
-        sqlx::query(
-            "UPDATE transactions SET points_earned = $1, processed = true WHERE tx_hash = $2",
-        )
-        .bind(points_decimal)
-        .bind(&tx.tx_hash)
-        .execute(self.db.pool())
-        .await?;
+        self.db
+            .mark_transaction_processed(&tx.tx_hash, Some(points_decimal))
+            .await?;
 
         tracing::info!(
             "Points calculated: user={}, type={}, points={}",
@@ -320,26 +523,7 @@ impl PointCalculator {
     async fn calculate_bridge_points(&self, tx: &crate::models::Transaction) -> Result<f64> {
         let usd_value =
             sanitize_points_usd_base(tx.usd_value.and_then(|v| v.to_f64()).unwrap_or(0.0));
-        let is_btc_bridge = is_btc_bridge(tx);
-        let (min_threshold, per_usd_rate) = if is_btc_bridge {
-            (
-                if self.config.is_testnet() {
-                    POINTS_MIN_USD_BRIDGE_BTC_TESTNET
-                } else {
-                    POINTS_MIN_USD_BRIDGE_BTC
-                },
-                POINTS_PER_USD_BRIDGE_BTC,
-            )
-        } else {
-            (
-                if self.config.is_testnet() {
-                    POINTS_MIN_USD_BRIDGE_ETH_TESTNET
-                } else {
-                    POINTS_MIN_USD_BRIDGE_ETH
-                },
-                POINTS_PER_USD_BRIDGE_ETH,
-            )
-        };
+        let (min_threshold, per_usd_rate) = self.config.bridge_points_rule(is_btc_bridge(tx));
         if usd_value < min_threshold {
             return Ok(0.0);
         }
@@ -453,7 +637,7 @@ impl PointCalculator {
         if contract.trim().is_empty() || contract.starts_with("0x0000") {
             return Ok(0.0);
         }
-        let period_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+        let period_epoch = self.config.current_nft_period_epoch();
         let Some(state) = self
             .db
             .get_nft_discount_state(contract, user_address, period_epoch)
@@ -488,7 +672,7 @@ impl PointCalculator {
 
     // Internal helper that supports `flag_wash_trading` operations.
     async fn flag_wash_trading(&self, user_address: &str) -> Result<()> {
-        let current_epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+        let current_epoch = self.config.current_rewards_epoch();
 
         sqlx::query(
             "UPDATE points SET wash_trading_flagged = true
@@ -520,20 +704,24 @@ impl PointCalculator {
         let nft_boost = nft_discount > 0.0;
         let nft_factor = nft_factor_for_discount(nft_discount);
 
-        sqlx::query(
+        let new_total: Option<Decimal> = sqlx::query_scalar(
             "UPDATE points 
              SET staking_multiplier = $1,
                  nft_boost = $2,
                  total_points = GREATEST(0, ((swap_points + bridge_points + stake_points + referral_points + social_points) * $1 * $3) - COALESCE(spent_points, 0))
-             WHERE user_address = $4 AND epoch = $5"
+             WHERE user_address = $4 AND epoch = $5
+             RETURNING total_points"
         )
         .bind(rust_decimal::Decimal::from_f64_retain(multiplier).unwrap())
         .bind(nft_boost)
         .bind(rust_decimal::Decimal::from_f64_retain(nft_factor).unwrap())
         .bind(user_address)
         .bind(epoch)
-        .execute(self.db.pool())
+        .fetch_optional(self.db.pool())
         .await?;
+        if let Some(new_total) = new_total {
+            super::snapshot_manager::record_points_total(user_address, epoch, new_total);
+        }
 
         Ok(())
     }
@@ -821,11 +1009,7 @@ fn nft_factor_for_discount(discount_rate: f64) -> f64 {
 
 // Internal helper that supports `ai_level_points_bonus_factor` operations.
 fn ai_level_points_bonus_factor(level: u8) -> f64 {
-    match level {
-        2 => 1.0 + (AI_LEVEL_2_POINTS_BONUS_PERCENT / 100.0),
-        3 => 1.0 + (AI_LEVEL_3_POINTS_BONUS_PERCENT / 100.0),
-        _ => 1.0,
-    }
+    1.0 + (ai_level_points_bonus_percent(level) / 100.0)
 }
 
 // Internal helper that checks conditions for `is_lp_stake_symbol`.
@@ -905,6 +1089,23 @@ fn stake_points_multiplier_for(token: &str, amount: f64, is_testnet: bool) -> f6
     }
 }
 
+// Internal helper that values the still-staked share of a position at its average deposit price.
+fn open_stake_position_usd(staked_amount: f64, unstaked_amount: f64, staked_usd: f64) -> f64 {
+    if staked_amount <= 0.0 {
+        return 0.0;
+    }
+    let remaining_ratio = ((staked_amount - unstaked_amount) / staked_amount).clamp(0.0, 1.0);
+    sanitize_points_usd_base(staked_usd * remaining_ratio)
+}
+
+// Internal helper that supports `stake_accrual_points` operations.
+fn stake_accrual_points(position_usd: f64, rate_per_usd_day: f64, elapsed_secs: i64) -> f64 {
+    if position_usd <= 0.0 || rate_per_usd_day <= 0.0 || elapsed_secs <= 0 {
+        return 0.0;
+    }
+    position_usd * rate_per_usd_day * (elapsed_secs as f64 / 86_400.0)
+}
+
 // Internal helper that supports `staking_multiplier_for` operations.
 fn staking_multiplier_for(stake_amount: f64) -> f64 {
     if stake_amount < POINTS_MIN_STAKE_CAREL {
@@ -918,10 +1119,48 @@ fn staking_multiplier_for(stake_amount: f64) -> f64 {
     }
 }
 
+// Internal helper that supports `next_batches_per_tick` operations.
+// Doubles the batch budget while the backlog exceeds it and halves it once the backlog fits
+// in under half, so spikes drain quickly without hammering the DB when idle.
+fn next_batches_per_tick(current: u32, lag: i64, batch_size: i64, floor: u32, ceiling: u32) -> u32 {
+    let current = current.clamp(floor, ceiling);
+    let batch_size = batch_size.max(1);
+    let needed = (lag.max(0) + batch_size - 1) / batch_size;
+    if needed > current as i64 {
+        current.saturating_mul(2).min(ceiling)
+    } else if needed < (current / 2) as i64 {
+        (current / 2).max(floor)
+    } else {
+        current
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    // Internal helper that supports `onboarding_bonus_amount_skips_disabled_values` operations.
+    fn onboarding_bonus_amount_skips_disabled_values() {
+        assert_eq!(onboarding_bonus_amount(0.0), None);
+        assert_eq!(onboarding_bonus_amount(-5.0), None);
+        assert_eq!(onboarding_bonus_amount(f64::NAN), None);
+        assert_eq!(onboarding_bonus_amount(50.0), Some(Decimal::from(50)));
+    }
+
+    #[test]
+    // Internal helper that supports `next_batches_per_tick_scales_with_lag` operations.
+    fn next_batches_per_tick_scales_with_lag() {
+        // Backlog of 50 batches grows the budget geometrically up to the ceiling.
+        assert_eq!(next_batches_per_tick(4, 5_000, 100, 4, 32), 8);
+        assert_eq!(next_batches_per_tick(32, 5_000, 100, 4, 32), 32);
+        // Backlog within the current budget keeps it steady.
+        assert_eq!(next_batches_per_tick(8, 500, 100, 4, 32), 8);
+        // Caught up shrinks back toward the baseline.
+        assert_eq!(next_batches_per_tick(16, 0, 100, 4, 32), 8);
+        assert_eq!(next_batches_per_tick(4, 0, 100, 4, 32), 4);
+    }
+
     #[test]
     // Internal helper that supports `staking_multiplier_for_tier_boundaries` operations.
     fn staking_multiplier_for_tier_boundaries() {
@@ -991,6 +1230,18 @@ mod tests {
         assert_eq!(nft_factor_for_discount(25.0), 1.25);
     }
 
+    #[test]
+    // Internal helper that supports `stake_accrual_points_scales_with_position_and_time` operations.
+    fn stake_accrual_points_scales_with_position_and_time() {
+        assert_eq!(open_stake_position_usd(100.0, 25.0, 200.0), 150.0);
+        assert_eq!(open_stake_position_usd(100.0, 150.0, 200.0), 0.0);
+        assert_eq!(open_stake_position_usd(0.0, 0.0, 200.0), 0.0);
+        assert_eq!(stake_accrual_points(150.0, 0.5, 86_400), 75.0);
+        assert_eq!(stake_accrual_points(150.0, 0.5, 43_200), 37.5);
+        assert_eq!(stake_accrual_points(150.0, 0.5, 0), 0.0);
+        assert_eq!(stake_accrual_points(150.0, 0.0, 86_400), 0.0);
+    }
+
     #[test]
     // Internal helper that supports `ai_level_points_bonus_factor_matches_expected` operations.
     fn ai_level_points_bonus_factor_matches_expected() {
@@ -5,7 +5,8 @@ use crate::{
     constants::PRICE_UPDATER_INTERVAL_SECS,
     db::{Database, PriceTickUpsert},
     error::{AppError, Result},
-    models::PriceTick,
+    models::{PriceAlert, PriceTick},
+    services::notification_service::{NotificationService, NotificationType},
     services::price_guard::sanitize_price_usd,
 };
 
@@ -38,6 +39,178 @@ fn candle_start_time(time: DateTime<Utc>, interval: &str) -> DateTime<Utc> {
         .and_utc()
 }
 
+// Internal helper that builds the (open, high, low) of a freshly opened candle.
+// The candle opens at the previous price, so high/low must cover both it and the current price.
+fn opening_candle_bounds(
+    last_price: Option<Decimal>,
+    current_price: Decimal,
+) -> (Decimal, Decimal, Decimal) {
+    let open = last_price.unwrap_or(current_price);
+    (open, open.max(current_price), open.min(current_price))
+}
+
+/// Returns true when the price moved across `threshold` in `direction` between two observations.
+///
+/// Comparing against the previous observation (not just the current price) catches gaps where
+/// the price jumps past the threshold between ticks, and keeps recurring alerts from re-firing
+/// until the price has moved back to the other side.
+pub fn alert_crossed(
+    direction: &str,
+    threshold: Decimal,
+    previous: Option<Decimal>,
+    current: Decimal,
+) -> bool {
+    let Some(previous) = previous else {
+        return false;
+    };
+    match direction {
+        "above" => previous < threshold && current >= threshold,
+        "below" => previous > threshold && current <= threshold,
+        _ => false,
+    }
+}
+
+/// Intervals written to `price_history` by the price updater.
+pub const STORED_CANDLE_INTERVALS: [&str; 6] = ["1m", "5m", "15m", "1h", "4h", "1d"];
+
+/// Parses `<n>m`, `<n>h`, or `<n>d` into minutes.
+pub fn interval_minutes(interval: &str) -> Option<i64> {
+    let interval = interval.trim();
+    if interval.len() < 2 {
+        return None;
+    }
+    let (count, unit) = interval.split_at(interval.len() - 1);
+    let count = count.parse::<i64>().ok().filter(|value| *value > 0)?;
+    let unit_minutes = match unit {
+        "m" => 1,
+        "h" => 60,
+        "d" => 1440,
+        _ => return None,
+    };
+    count.checked_mul(unit_minutes)
+}
+
+/// Where candles for a requested interval come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandleSource {
+    Stored,
+    Aggregated {
+        base: &'static str,
+        target_minutes: i64,
+        ratio: i64,
+    },
+}
+
+/// Resolves `interval` to a stored interval or to the largest stored base it is a multiple of.
+///
+/// # Returns
+/// * `Err(AppError::BadRequest)` when the interval is malformed or no stored base divides it.
+pub fn resolve_candle_source(interval: &str) -> Result<CandleSource> {
+    if STORED_CANDLE_INTERVALS.contains(&interval) {
+        return Ok(CandleSource::Stored);
+    }
+    let target_minutes = interval_minutes(interval)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid interval: {}", interval)))?;
+    STORED_CANDLE_INTERVALS
+        .iter()
+        .rev()
+        .filter_map(|base| interval_minutes(base).map(|minutes| (*base, minutes)))
+        .find(|(_, base_minutes)| {
+            *base_minutes < target_minutes && target_minutes % base_minutes == 0
+        })
+        .map(|(base, base_minutes)| CandleSource::Aggregated {
+            base,
+            target_minutes,
+            ratio: target_minutes / base_minutes,
+        })
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Interval {} is not a multiple of a stored interval ({})",
+                interval,
+                STORED_CANDLE_INTERVALS.join(", ")
+            ))
+        })
+}
+
+// Internal helper that returns the UTC-epoch-aligned bucket start for `time`.
+fn bucket_start(time: DateTime<Utc>, bucket_minutes: i64) -> DateTime<Utc> {
+    let bucket_secs = bucket_minutes * 60;
+    let secs = time.timestamp().div_euclid(bucket_secs) * bucket_secs;
+    Utc.timestamp_opt(secs, 0).single().unwrap_or(time)
+}
+
+/// Aggregates ascending lower-interval candles into `target_minutes` buckets.
+///
+/// Each bucket takes the open of its first candle, the close of its last, the max high,
+/// the min low, and the summed volume.
+pub fn aggregate_candles(candles: &[PriceTick], target_minutes: i64) -> Vec<PriceTick> {
+    let mut out: Vec<PriceTick> = Vec::new();
+    for candle in candles {
+        let start = bucket_start(candle.timestamp, target_minutes);
+        match out.last_mut() {
+            Some(bucket) if bucket.timestamp == start => {
+                bucket.high = bucket.high.max(candle.high);
+                bucket.low = bucket.low.min(candle.low);
+                bucket.close = candle.close;
+                bucket.volume += candle.volume;
+            }
+            _ => out.push(PriceTick {
+                token: candle.token.clone(),
+                timestamp: start,
+                open: candle.open,
+                high: candle.high,
+                low: candle.low,
+                close: candle.close,
+                volume: candle.volume,
+            }),
+        }
+    }
+    out
+}
+
+/// Checks that an OHLCV tick is internally consistent before it is persisted.
+///
+/// # Returns
+/// * `Err(reason)` for non-finite values, `low > high`, open/close outside `[low, high]`,
+///   non-positive prices, or negative volume.
+pub fn validate_price_tick(tick: &PriceTickUpsert<'_>) -> std::result::Result<(), String> {
+    let fields = [
+        ("open", tick.open),
+        ("high", tick.high),
+        ("low", tick.low),
+        ("close", tick.close),
+        ("volume", tick.volume),
+    ];
+    if let Some((name, value)) = fields.iter().find(|(_, value)| !value.is_finite()) {
+        return Err(format!("{} is not finite ({})", name, value));
+    }
+    if tick.low <= 0.0 {
+        return Err(format!("low must be positive ({})", tick.low));
+    }
+    if tick.low > tick.high {
+        return Err(format!("low {} exceeds high {}", tick.low, tick.high));
+    }
+    for (name, value) in [("open", tick.open), ("close", tick.close)] {
+        if value < tick.low || value > tick.high {
+            return Err(format!(
+                "{} {} outside [{}, {}]",
+                name, value, tick.low, tick.high
+            ));
+        }
+    }
+    if tick.volume < 0.0 {
+        return Err(format!("volume is negative ({})", tick.volume));
+    }
+    Ok(())
+}
+
+// Internal helper that checks conditions for `price_tick_validation_enabled`.
+fn price_tick_validation_enabled() -> bool {
+    std::env::var("PRICE_TICK_VALIDATION")
+        .map(|value| !value.trim().eq_ignore_ascii_case("off"))
+        .unwrap_or(true)
+}
+
 pub struct PriceChartService {
     db: Database,
     config: Config,
@@ -130,11 +303,77 @@ impl PriceChartService {
             }
 
             self.update_ohlcv_candles(token, price, last_price).await?;
+
+            if let Err(err) = self.evaluate_price_alerts(token, price).await {
+                tracing::warn!("Price alert evaluation failed for {}: {}", token, err);
+            }
         }
 
         Ok(())
     }
 
+    // Internal helper that fires price alerts whose threshold was crossed since the previous tick.
+    async fn evaluate_price_alerts(&self, token: &str, price: Decimal) -> Result<()> {
+        let alerts = sqlx::query_as::<_, PriceAlert>(
+            "SELECT id, user_address, token, direction, threshold, recurring, active,
+                    last_price, triggered_at, created_at
+             FROM price_alerts WHERE token = $1 AND active",
+        )
+        .bind(token)
+        .fetch_all(self.db.pool())
+        .await?;
+        if alerts.is_empty() {
+            return Ok(());
+        }
+
+        let notifier = NotificationService::new(self.db.clone(), self.config.clone());
+        for alert in alerts {
+            if !alert_crossed(&alert.direction, alert.threshold, alert.last_price, price) {
+                continue;
+            }
+            let title = format!("{} {} {}", alert.token, alert.direction, alert.threshold);
+            let message = format!(
+                "{} is now {} (alert: {} {})",
+                alert.token, price, alert.direction, alert.threshold
+            );
+            if let Err(err) = notifier
+                .send_notification(
+                    &alert.user_address,
+                    NotificationType::PriceAlert,
+                    title,
+                    message,
+                    Some(serde_json::json!({
+                        "alert_id": alert.id,
+                        "token": alert.token,
+                        "direction": alert.direction,
+                        "threshold": alert.threshold.to_string(),
+                        "price": price.to_string(),
+                        "recurring": alert.recurring,
+                    })),
+                )
+                .await
+            {
+                tracing::warn!("Price alert {} notification failed: {}", alert.id, err);
+            }
+
+            // One-shot alerts are disarmed; recurring ones stay active and re-arm once
+            // the price moves back across the threshold.
+            sqlx::query(
+                "UPDATE price_alerts SET triggered_at = NOW(), active = recurring WHERE id = $1",
+            )
+            .bind(alert.id)
+            .execute(self.db.pool())
+            .await?;
+        }
+
+        sqlx::query("UPDATE price_alerts SET last_price = $2 WHERE token = $1 AND active")
+            .bind(token)
+            .bind(price)
+            .execute(self.db.pool())
+            .await?;
+        Ok(())
+    }
+
     // Internal helper that fetches data for `fetch_price`.
     async fn fetch_price(&self, token: &str) -> Result<Decimal> {
         if self.config.coingecko_id_for(token).is_some() {
@@ -354,9 +593,8 @@ impl PriceChartService {
         last_price: Option<Decimal>,
     ) -> Result<()> {
         let now = Utc::now();
-        let intervals = ["1m", "5m", "15m", "1h", "4h", "1d"];
 
-        for interval in intervals {
+        for interval in STORED_CANDLE_INTERVALS {
             let candle_start = candle_start_time(now, interval);
 
             let sql = r#"
@@ -389,14 +627,14 @@ impl PriceChartService {
                     .await?;
                 }
                 None => {
-                    let open = last_price.unwrap_or(current_price);
+                    let (open, high, low) = opening_candle_bounds(last_price, current_price);
 
                     self.save_candle(CandleSaveInput {
                         token,
                         timestamp: candle_start,
                         open,
-                        high: current_price,
-                        low: current_price,
+                        high,
+                        low,
                         close: current_price,
                         interval,
                     })
@@ -409,7 +647,7 @@ impl PriceChartService {
     }
 
     // Internal helper that updates state for `save_candle`.
-    async fn save_candle(&self, input: CandleSaveInput<'_>) -> Result<()> {
+    async fn save_candle(&self, input: CandleSaveInput<'_>) -> Result<bool> {
         let close_f64 = input
             .close
             .to_f64()
@@ -421,7 +659,7 @@ impl PriceChartService {
                 input.interval,
                 close_f64
             );
-            return Ok(());
+            return Ok(false);
         };
         let open_sane = input
             .open
@@ -445,20 +683,42 @@ impl PriceChartService {
             .and_then(|v| sanitize_price_usd(input.token, v))
             .unwrap_or(open_sane.min(close_sane));
 
-        self.db
-            .save_price_tick(PriceTickUpsert {
-                token: input.token,
-                timestamp: input.timestamp,
-                open: open_sane,
-                high: high_sane,
-                low: low_sane,
-                close: close_sane,
-                volume: 0.0,
-                interval: input.interval,
-            })
-            .await?;
+        self.save_price_tick(PriceTickUpsert {
+            token: input.token,
+            timestamp: input.timestamp,
+            open: open_sane,
+            high: high_sane,
+            low: low_sane,
+            close: close_sane,
+            volume: 0.0,
+            interval: input.interval,
+        })
+        .await
+    }
 
-        Ok(())
+    /// Persists an OHLCV tick after `validate_price_tick`; every ingestion path should use this
+    /// instead of `Database::save_price_tick`.
+    ///
+    /// # Returns
+    /// * `Ok(false)` when the tick was rejected and only a warning was logged.
+    ///
+    /// # Notes
+    /// * `PRICE_TICK_VALIDATION=off` disables the check.
+    pub async fn save_price_tick(&self, tick: PriceTickUpsert<'_>) -> Result<bool> {
+        if price_tick_validation_enabled() {
+            if let Err(reason) = validate_price_tick(&tick) {
+                tracing::warn!(
+                    "Rejected inconsistent price tick for {} interval {} at {}: {}",
+                    tick.token,
+                    tick.interval,
+                    tick.timestamp,
+                    reason
+                );
+                return Ok(false);
+            }
+        }
+        self.db.save_price_tick(tick).await?;
+        Ok(true)
     }
 
     /// Fetches data for `get_current_price`.
@@ -516,126 +776,74 @@ impl PriceChartService {
         Ok(data.into_iter().rev().collect())
     }
 
-    /// ✅ METHOD YANG SEBELUMNYA HILANG
-    pub async fn get_ohlcv(
-        &self,
-        token: &str,
-        interval: &str,
-        from: DateTime<Utc>,
-        to: DateTime<Utc>,
-    ) -> Result<Vec<PriceTick>> {
-        self.db.get_price_history(token, interval, from, to).await
-    }
-
-    /// Handles `calculate_indicators` logic.
-    ///
-    /// # Arguments
-    /// * Uses function parameters as validated input and runtime context.
+    /// Fetches the latest `count` candles, aggregating from a stored base interval when
+    /// `interval` itself is not stored.
     ///
     /// # Returns
-    /// * `Ok(...)` when processing succeeds.
-    /// * `Err(AppError)` when validation, authorization, or integration checks fail.
-    ///
-    /// # Notes
-    /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
-    pub async fn calculate_indicators(
+    /// * `Err(AppError::BadRequest)` when `interval` is not a multiple of a stored interval.
+    pub async fn get_latest_candles_any_interval(
         &self,
         token: &str,
         interval: &str,
-        indicator: &str,
-    ) -> Result<Vec<(DateTime<Utc>, Decimal)>> {
-        match indicator {
-            "SMA" => self.calculate_sma(token, interval, 20).await,
-            "EMA" => self.calculate_ema(token, interval, 20).await,
-            "RSI" => self.calculate_rsi(token, interval, 14).await,
-            _ => Err(AppError::BadRequest("Invalid indicator".into())),
-        }
-    }
-
-    // Internal helper that supports `calculate_sma` operations.
-    async fn calculate_sma(
-        &self,
-        token: &str,
-        interval: &str,
-        period: i32,
-    ) -> Result<Vec<(DateTime<Utc>, Decimal)>> {
-        let candles = self.get_latest_candles(token, interval, period * 2).await?;
-        let mut out = vec![];
-
-        for i in period as usize..candles.len() {
-            let sum: Decimal = candles[i - period as usize..i]
-                .iter()
-                .map(|c| c.close)
-                .sum();
-            out.push((candles[i].timestamp, sum / Decimal::from(period)));
+        count: i32,
+    ) -> Result<Vec<PriceTick>> {
+        match resolve_candle_source(interval)? {
+            CandleSource::Stored => self.get_latest_candles(token, interval, count).await,
+            CandleSource::Aggregated {
+                base,
+                target_minutes,
+                ratio,
+            } => {
+                // One extra bucket covers a partially filled oldest bucket.
+                let base_count = (i64::from(count.max(1)) + 1)
+                    .saturating_mul(ratio)
+                    .min(i64::from(i32::MAX)) as i32;
+                let candles = self.get_latest_candles(token, base, base_count).await?;
+                let mut aggregated = aggregate_candles(&candles, target_minutes);
+                let keep = count.max(0) as usize;
+                if aggregated.len() > keep {
+                    aggregated.drain(..aggregated.len() - keep);
+                }
+                Ok(aggregated)
+            }
         }
-
-        Ok(out)
     }
 
-    // Internal helper that supports `calculate_ema` operations.
-    async fn calculate_ema(
+    /// Fetches candles in `[from, to]`, aggregating from a stored base interval when
+    /// `interval` itself is not stored.
+    ///
+    /// # Returns
+    /// * `Err(AppError::BadRequest)` when `interval` is not a multiple of a stored interval.
+    pub async fn get_ohlcv_any_interval(
         &self,
         token: &str,
         interval: &str,
-        period: i32,
-    ) -> Result<Vec<(DateTime<Utc>, Decimal)>> {
-        let candles = self.get_latest_candles(token, interval, period * 2).await?;
-        let multiplier = Decimal::from(2) / (Decimal::from(period) + Decimal::ONE);
-
-        let mut ema = candles[0].close;
-        let mut out = vec![(candles[0].timestamp, ema)];
-
-        for c in &candles[1..] {
-            ema = (c.close - ema) * multiplier + ema;
-            out.push((c.timestamp, ema));
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<PriceTick>> {
+        match resolve_candle_source(interval)? {
+            CandleSource::Stored => self.get_ohlcv(token, interval, from, to).await,
+            CandleSource::Aggregated {
+                base,
+                target_minutes,
+                ..
+            } => {
+                let from = bucket_start(from, target_minutes);
+                let candles = self.get_ohlcv(token, base, from, to).await?;
+                Ok(aggregate_candles(&candles, target_minutes))
+            }
         }
-
-        Ok(out)
     }
 
-    // Internal helper that supports `calculate_rsi` operations.
-    async fn calculate_rsi(
+    /// ✅ METHOD YANG SEBELUMNYA HILANG
+    pub async fn get_ohlcv(
         &self,
         token: &str,
         interval: &str,
-        period: i32,
-    ) -> Result<Vec<(DateTime<Utc>, Decimal)>> {
-        let candles = self.get_latest_candles(token, interval, period * 2).await?;
-
-        let mut gains = vec![];
-        let mut losses = vec![];
-
-        for i in 1..candles.len() {
-            let diff = candles[i].close - candles[i - 1].close;
-            if diff > Decimal::ZERO {
-                gains.push(diff);
-                losses.push(Decimal::ZERO);
-            } else {
-                gains.push(Decimal::ZERO);
-                losses.push(diff.abs());
-            }
-        }
-
-        let mut out = vec![];
-
-        for i in period as usize..gains.len() {
-            let avg_gain: Decimal =
-                gains[i - period as usize..i].iter().sum::<Decimal>() / Decimal::from(period);
-            let avg_loss: Decimal =
-                losses[i - period as usize..i].iter().sum::<Decimal>() / Decimal::from(period);
-
-            let rs = if avg_loss == Decimal::ZERO {
-                Decimal::from(100)
-            } else {
-                avg_gain / avg_loss
-            };
-
-            let rsi = Decimal::from(100) - (Decimal::from(100) / (Decimal::ONE + rs));
-            out.push((candles[i].timestamp, rsi));
-        }
-
-        Ok(out)
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<PriceTick>> {
+        self.db.get_price_history(token, interval, from, to).await
     }
 
     // Internal helper that supports `coingecko_id_or_default` operations.
@@ -780,4 +988,143 @@ mod tests {
         assert_eq!(rounded.minute(), 30);
         assert_eq!(rounded.second(), 0);
     }
+
+    #[test]
+    // Internal helper that supports `validate_price_tick_rejects_inconsistent_candles` operations.
+    fn validate_price_tick_rejects_inconsistent_candles() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let tick = |open: f64, high: f64, low: f64, close: f64, volume: f64| PriceTickUpsert {
+            token: "ETH",
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+            interval: "1m",
+        };
+        assert!(validate_price_tick(&tick(10.0, 12.0, 9.0, 11.0, 0.0)).is_ok());
+        assert!(validate_price_tick(&tick(10.0, 9.0, 12.0, 11.0, 0.0)).is_err());
+        assert!(validate_price_tick(&tick(13.0, 12.0, 9.0, 11.0, 0.0)).is_err());
+        assert!(validate_price_tick(&tick(10.0, 12.0, 9.0, 8.0, 0.0)).is_err());
+        assert!(validate_price_tick(&tick(10.0, 12.0, 9.0, 11.0, -1.0)).is_err());
+        assert!(validate_price_tick(&tick(f64::NAN, 12.0, 9.0, 11.0, 0.0)).is_err());
+        assert!(validate_price_tick(&tick(10.0, f64::INFINITY, 9.0, 11.0, 0.0)).is_err());
+        assert!(validate_price_tick(&tick(0.0, 0.0, 0.0, 0.0, 0.0)).is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `opening_candle_bounds_cover_open_and_current` operations.
+    fn opening_candle_bounds_cover_open_and_current() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let (open, high, low) = opening_candle_bounds(Some(Decimal::from(10)), Decimal::from(8));
+        assert_eq!(
+            (open, high, low),
+            (Decimal::from(10), Decimal::from(10), Decimal::from(8))
+        );
+        let tick = PriceTickUpsert {
+            token: "ETH",
+            timestamp,
+            open: open.to_f64().unwrap(),
+            high: high.to_f64().unwrap(),
+            low: low.to_f64().unwrap(),
+            close: 8.0,
+            volume: 0.0,
+            interval: "1m",
+        };
+        assert!(validate_price_tick(&tick).is_ok());
+
+        let (open, high, low) = opening_candle_bounds(None, Decimal::from(5));
+        assert_eq!(
+            (open, high, low),
+            (Decimal::from(5), Decimal::from(5), Decimal::from(5))
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `resolve_candle_source_picks_largest_dividing_base` operations.
+    fn resolve_candle_source_picks_largest_dividing_base() {
+        assert_eq!(resolve_candle_source("1h").unwrap(), CandleSource::Stored);
+        assert_eq!(
+            resolve_candle_source("6h").unwrap(),
+            CandleSource::Aggregated {
+                base: "1h",
+                target_minutes: 360,
+                ratio: 6
+            }
+        );
+        assert_eq!(
+            resolve_candle_source("8h").unwrap(),
+            CandleSource::Aggregated {
+                base: "4h",
+                target_minutes: 480,
+                ratio: 2
+            }
+        );
+        assert!(resolve_candle_source("7x").is_err());
+        assert!(resolve_candle_source("0m").is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `aggregate_candles_merges_ohlcv_per_bucket` operations.
+    fn aggregate_candles_merges_ohlcv_per_bucket() {
+        let candle =
+            |hour: u32, open: i64, high: i64, low: i64, close: i64, volume: i64| PriceTick {
+                token: "ETH".to_string(),
+                timestamp: Utc.with_ymd_and_hms(2024, 1, 1, hour, 0, 0).unwrap(),
+                open: Decimal::from(open),
+                high: Decimal::from(high),
+                low: Decimal::from(low),
+                close: Decimal::from(close),
+                volume: Decimal::from(volume),
+            };
+        let candles = vec![
+            candle(4, 10, 12, 9, 11, 1),
+            candle(5, 11, 15, 10, 14, 2),
+            candle(6, 14, 14, 8, 9, 3),
+        ];
+        let out = aggregate_candles(&candles, 360);
+        assert_eq!(out.len(), 2);
+        assert_eq!(
+            out[0].timestamp,
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(out[0].open, Decimal::from(10));
+        assert_eq!(out[0].high, Decimal::from(15));
+        assert_eq!(out[0].low, Decimal::from(9));
+        assert_eq!(out[0].close, Decimal::from(14));
+        assert_eq!(out[0].volume, Decimal::from(3));
+        assert_eq!(
+            out[1].timestamp,
+            Utc.with_ymd_and_hms(2024, 1, 1, 6, 0, 0).unwrap()
+        );
+        assert_eq!(out[1].open, Decimal::from(14));
+    }
+
+    #[test]
+    // Internal helper that supports `alert_crossed_detects_gaps_and_requires_crossing` operations.
+    fn alert_crossed_detects_gaps_and_requires_crossing() {
+        let threshold = Decimal::from(100);
+        // Gap from 90 straight to 120 still crosses "above 100".
+        assert!(alert_crossed(
+            "above",
+            threshold,
+            Some(Decimal::from(90)),
+            Decimal::from(120)
+        ));
+        // Already above on both observations: no new crossing.
+        assert!(!alert_crossed(
+            "above",
+            threshold,
+            Some(Decimal::from(110)),
+            Decimal::from(120)
+        ));
+        assert!(alert_crossed(
+            "below",
+            threshold,
+            Some(Decimal::from(130)),
+            Decimal::from(80)
+        ));
+        assert!(!alert_crossed("above", threshold, None, Decimal::from(120)));
+    }
 }
@@ -0,0 +1,174 @@
+use crate::{
+    db::Database,
+    error::{AppError, Result},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::time::Instant;
+
+/// Privacy pipeline audit row.
+///
+/// # Notes
+/// * Holds operational metadata only. Proofs, public inputs, nullifiers, commitments, roots,
+///   recipients and the submitting user are never stored, so rows cannot link a note to a user.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct PrivacyAuditEntry {
+    pub id: i64,
+    pub flow: String,
+    pub verifier: String,
+    pub executor_kind: String,
+    pub pool_version: Option<String>,
+    pub tx_hash: Option<String>,
+    pub success: bool,
+    pub error_code: Option<String>,
+    pub duration_ms: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Filters for the admin audit listing.
+#[derive(Debug, Default)]
+pub struct PrivacyAuditFilter<'a> {
+    pub flow: Option<&'a str>,
+    pub success: Option<bool>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// One privacy submission being timed for the audit log.
+pub struct PrivacyAudit {
+    flow: &'static str,
+    verifier: String,
+    executor_kind: &'static str,
+    pool_version: Option<String>,
+    started: Instant,
+}
+
+impl PrivacyAudit {
+    /// Starts timing a submission for `flow` (`submit` or `relayer_execute`).
+    pub fn start(
+        flow: &'static str,
+        verifier: &str,
+        executor_kind: &'static str,
+        pool_version: Option<&str>,
+    ) -> Self {
+        Self {
+            flow,
+            verifier: verifier.to_string(),
+            executor_kind,
+            pool_version: pool_version
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string),
+            started: Instant::now(),
+        }
+    }
+
+    /// Records the outcome in the background; audit failures never affect the request.
+    pub fn finish(self, db: &Database, outcome: &Result<String>) {
+        let duration_ms = i64::try_from(self.started.elapsed().as_millis()).unwrap_or(i64::MAX);
+        let (tx_hash, error_code) = match outcome {
+            Ok(tx_hash) => (Some(tx_hash.clone()), None),
+            Err(err) => (None, Some(audit_error_code(err).to_string())),
+        };
+        let db = db.clone();
+        tokio::spawn(async move {
+            let inserted = sqlx::query(
+                "INSERT INTO privacy_audit_log
+                    (flow, verifier, executor_kind, pool_version, tx_hash, success,
+                     error_code, duration_ms)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            )
+            .bind(self.flow)
+            .bind(&self.verifier)
+            .bind(self.executor_kind)
+            .bind(&self.pool_version)
+            .bind(&tx_hash)
+            .bind(error_code.is_none())
+            .bind(&error_code)
+            .bind(duration_ms)
+            .execute(db.pool())
+            .await;
+            if let Err(err) = inserted {
+                tracing::warn!("Failed to record privacy audit entry: {}", err);
+            }
+        });
+    }
+}
+
+// Internal helper that maps an error to a stable code without copying its message,
+// which may embed payload values.
+fn audit_error_code(err: &AppError) -> &'static str {
+    match err {
+        AppError::Coded(code, _) => code.as_str(),
+        AppError::BadRequest(_) => "BAD_REQUEST",
+        AppError::AuthError(_) => "AUTH_ERROR",
+        AppError::BlockchainRPC(_) => "BLOCKCHAIN_RPC_ERROR",
+        AppError::PrivacyRootAdvanced(_) => "PRIVACY_ROOT_ADVANCED",
+        AppError::ProverBusy => "PROVER_BUSY",
+        AppError::RelayerAccessDenied(_) => "RELAYER_ACCESS_DENIED",
+        AppError::ExternalAPI(_) => "EXTERNAL_API_ERROR",
+        AppError::RateLimitExceeded => "RATE_LIMIT_EXCEEDED",
+        _ => "INTERNAL_ERROR",
+    }
+}
+
+/// Lists audit entries, newest first, with the total matching count.
+pub async fn list_entries(
+    db: &Database,
+    filter: &PrivacyAuditFilter<'_>,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<PrivacyAuditEntry>, i64)> {
+    let filters = "($1::TEXT IS NULL OR flow = $1)
+           AND ($2::BOOLEAN IS NULL OR success = $2)
+           AND ($3::TIMESTAMPTZ IS NULL OR created_at >= $3)
+           AND ($4::TIMESTAMPTZ IS NULL OR created_at <= $4)";
+
+    let items = sqlx::query_as::<_, PrivacyAuditEntry>(&format!(
+        "SELECT id, flow, verifier, executor_kind, pool_version, tx_hash, success,
+                error_code, duration_ms, created_at
+         FROM privacy_audit_log
+         WHERE {}
+         ORDER BY created_at DESC, id DESC
+         LIMIT $5 OFFSET $6",
+        filters
+    ))
+    .bind(filter.flow)
+    .bind(filter.success)
+    .bind(filter.from)
+    .bind(filter.to)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(db.pool())
+    .await?;
+
+    let total: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM privacy_audit_log WHERE {}",
+        filters
+    ))
+    .bind(filter.flow)
+    .bind(filter.success)
+    .bind(filter.from)
+    .bind(filter.to)
+    .fetch_one(db.pool())
+    .await?;
+
+    Ok((items, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    #[test]
+    // Internal helper that supports `audit_error_code_drops_messages` operations.
+    fn audit_error_code_drops_messages() {
+        let err = AppError::BadRequest("nullifier 0xabc already spent".to_string());
+        assert_eq!(audit_error_code(&err), "BAD_REQUEST");
+        let err = AppError::Coded(ErrorCode::HideNoteMixingWindow, "0xabc".to_string());
+        assert_eq!(audit_error_code(&err), "HIDE_NOTE_MIXING_WINDOW");
+        let err = AppError::Internal("proof 0x1 0x2".to_string());
+        assert_eq!(audit_error_code(&err), "INTERNAL_ERROR");
+    }
+}
@@ -8,6 +8,8 @@ pub enum PrivacyVerifierKind {
 }
 
 impl PrivacyVerifierKind {
+    pub const ALL: [Self; 3] = [Self::Garaga, Self::Tongo, Self::Semaphore];
+
     /// Returns the canonical lowercase label used in config and API responses.
     ///
     /// # Returns
@@ -88,6 +90,49 @@ pub fn resolve_privacy_router_for_verifier(
     )))
 }
 
+/// Lists the verifier kinds that resolve to a router on this deployment.
+pub fn configured_privacy_verifiers(config: &Config) -> Vec<PrivacyVerifierKind> {
+    PrivacyVerifierKind::ALL
+        .into_iter()
+        .filter(|kind| resolve_privacy_router_for_verifier(config, *kind).is_ok())
+        .collect()
+}
+
+/// Parses the verifier named in a request and checks that this deployment can route it.
+///
+/// # Arguments
+/// * `config` - Runtime config containing verifier-to-router mappings.
+/// * `raw` - Optional verifier string from request payload.
+///
+/// # Returns
+/// * `Ok(PrivacyVerifierKind)` - Parsed verifier kind (`garaga` by default).
+/// * `Err(AppError)` - Returned for unknown labels, or when an explicitly requested verifier
+///   has no router configured.
+///
+/// # Notes
+/// - Omitting the verifier keeps the default `garaga` behavior without a router check, so
+///   flows that only use the label for payload generation are unaffected.
+pub fn select_privacy_verifier(config: &Config, raw: Option<&str>) -> Result<PrivacyVerifierKind> {
+    let kind = parse_privacy_verifier_kind(raw)?;
+    let explicit = raw.map(str::trim).is_some_and(|value| !value.is_empty());
+    if explicit && resolve_privacy_router_for_verifier(config, kind).is_err() {
+        let configured: Vec<&str> = configured_privacy_verifiers(config)
+            .into_iter()
+            .map(PrivacyVerifierKind::as_str)
+            .collect();
+        return Err(AppError::BadRequest(format!(
+            "Privacy verifier '{}' is not configured on this deployment. Available verifiers: {}.",
+            kind.as_str(),
+            if configured.is_empty() {
+                "none".to_string()
+            } else {
+                configured.join(", ")
+            }
+        )));
+    }
+    Ok(kind)
+}
+
 // Performs lightweight sanity checks for router addresses before using them in chain calls.
 fn is_valid_router_address(address: &str) -> bool {
     !address.is_empty() && address.starts_with("0x") && !address.starts_with("0x0000")
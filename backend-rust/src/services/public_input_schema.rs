@@ -0,0 +1,217 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+static PUBLIC_INPUT_SCHEMA: OnceLock<PublicInputSchema> = OnceLock::new();
+
+/// Index layout of the Garaga public inputs produced by one verifier version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PublicInputLayout {
+    #[serde(default)]
+    pub root: Option<usize>,
+    pub nullifier: usize,
+    #[serde(default)]
+    pub commitment: Option<usize>,
+    #[serde(default)]
+    pub action_hash: Option<usize>,
+}
+
+impl PublicInputLayout {
+    /// Smallest `public_inputs` length that covers every configured index.
+    pub fn required_len(&self) -> usize {
+        [
+            self.root,
+            Some(self.nullifier),
+            self.commitment,
+            self.action_hash,
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(0)
+            + 1
+    }
+
+    // Internal helper that rejects layouts binding two fields to the same index.
+    fn ensure_distinct(&self, version: &str) -> anyhow::Result<()> {
+        let mut seen: HashMap<usize, &str> = HashMap::new();
+        for (field, index) in [
+            ("root", self.root),
+            ("nullifier", Some(self.nullifier)),
+            ("commitment", self.commitment),
+            ("action_hash", self.action_hash),
+        ] {
+            let Some(index) = index else {
+                continue;
+            };
+            if let Some(previous) = seen.insert(index, field) {
+                anyhow::bail!(
+                    "GARAGA_PUBLIC_INPUT_SCHEMA: {} maps both '{}' and '{}' to index {}",
+                    version,
+                    previous,
+                    field,
+                    index
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Public-input layouts keyed by verifier version (`v2`, `v3`, ...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicInputSchema {
+    layouts: HashMap<String, PublicInputLayout>,
+}
+
+impl PublicInputSchema {
+    /// Builds the schema from `GARAGA_PUBLIC_INPUT_SCHEMA` JSON, or from the legacy
+    /// per-index env vars when no schema is configured.
+    ///
+    /// # Notes
+    /// - Versions missing from the JSON keep their legacy layout.
+    /// - `v2` must bind a commitment; `v3` must bind root and action_hash.
+    pub fn parse(raw: Option<&str>) -> anyhow::Result<Self> {
+        let mut layouts = legacy_layouts()?;
+        if let Some(raw) = raw.map(str::trim).filter(|value| !value.is_empty()) {
+            let configured: HashMap<String, PublicInputLayout> = serde_json::from_str(raw)
+                .map_err(|err| anyhow::anyhow!("GARAGA_PUBLIC_INPUT_SCHEMA is invalid: {}", err))?;
+            for (version, layout) in configured {
+                layouts.insert(version.trim().to_ascii_lowercase(), layout);
+            }
+        }
+
+        for (version, layout) in &layouts {
+            layout.ensure_distinct(version)?;
+        }
+        let v2 = &layouts["v2"];
+        if v2.commitment.is_none() {
+            anyhow::bail!("GARAGA_PUBLIC_INPUT_SCHEMA: v2 must define a commitment index");
+        }
+        let v3 = &layouts["v3"];
+        if v3.root.is_none() || v3.action_hash.is_none() {
+            anyhow::bail!(
+                "GARAGA_PUBLIC_INPUT_SCHEMA: v3 must define root and action_hash indexes"
+            );
+        }
+        Ok(Self { layouts })
+    }
+
+    /// Layout used by V1/V2 nullifier/commitment payloads.
+    pub fn v2(&self) -> &PublicInputLayout {
+        &self.layouts["v2"]
+    }
+
+    /// Layout used by V3 root/nullifier/action_hash payloads.
+    pub fn v3(&self) -> &PublicInputLayout {
+        &self.layouts["v3"]
+    }
+}
+
+// Internal helper that reads one legacy index env var.
+fn legacy_index(env_key: &str, default_value: usize) -> anyhow::Result<usize> {
+    let raw = std::env::var(env_key).unwrap_or_else(|_| default_value.to_string());
+    raw.trim()
+        .parse::<usize>()
+        .map_err(|_| anyhow::anyhow!("{} must be a non-negative integer, got '{}'", env_key, raw))
+}
+
+// Internal helper that returns the built-in layouts used when nothing is configured.
+fn builtin_layouts() -> HashMap<String, PublicInputLayout> {
+    HashMap::from([
+        (
+            "v2".to_string(),
+            PublicInputLayout {
+                root: None,
+                nullifier: 0,
+                commitment: Some(1),
+                action_hash: Some(2),
+            },
+        ),
+        (
+            "v3".to_string(),
+            PublicInputLayout {
+                root: Some(0),
+                nullifier: 1,
+                commitment: None,
+                action_hash: Some(2),
+            },
+        ),
+    ])
+}
+
+// Internal helper that builds the default layouts from the legacy per-index env vars.
+fn legacy_layouts() -> anyhow::Result<HashMap<String, PublicInputLayout>> {
+    let action_hash = legacy_index("GARAGA_INTENT_HASH_PUBLIC_INPUT_INDEX", 2)?;
+    let mut layouts = HashMap::new();
+    layouts.insert(
+        "v2".to_string(),
+        PublicInputLayout {
+            root: None,
+            nullifier: legacy_index("GARAGA_NULLIFIER_PUBLIC_INPUT_INDEX", 0)?,
+            commitment: Some(legacy_index("GARAGA_COMMITMENT_PUBLIC_INPUT_INDEX", 1)?),
+            action_hash: Some(action_hash),
+        },
+    );
+    layouts.insert(
+        "v3".to_string(),
+        PublicInputLayout {
+            root: Some(legacy_index("GARAGA_ROOT_PUBLIC_INPUT_INDEX", 0)?),
+            nullifier: legacy_index("GARAGA_NULLIFIER_PUBLIC_INPUT_INDEX_V3", 1)?,
+            commitment: None,
+            action_hash: Some(action_hash),
+        },
+    );
+    Ok(layouts)
+}
+
+/// Returns the process-wide public-input schema.
+///
+/// The schema is loaded once from `GARAGA_PUBLIC_INPUT_SCHEMA`; `Config::validate` parses the
+/// same value at startup, so a malformed schema never reaches this fallback.
+pub fn public_input_schema() -> &'static PublicInputSchema {
+    PUBLIC_INPUT_SCHEMA.get_or_init(|| {
+        let raw = std::env::var("GARAGA_PUBLIC_INPUT_SCHEMA").ok();
+        PublicInputSchema::parse(raw.as_deref()).unwrap_or_else(|err| {
+            tracing::warn!("{}; falling back to default public-input layout", err);
+            PublicInputSchema {
+                layouts: builtin_layouts(),
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `parse_overrides_only_listed_versions` operations.
+    fn parse_overrides_only_listed_versions() {
+        let schema = PublicInputSchema::parse(Some(
+            r#"{"v3": {"root": 1, "nullifier": 0, "action_hash": 3}, "v4": {"nullifier": 0}}"#,
+        ))
+        .expect("valid schema");
+        assert_eq!(schema.v3().root, Some(1));
+        assert_eq!(schema.v3().action_hash, Some(3));
+        assert_eq!(schema.v3().required_len(), 4);
+        assert!(schema.v2().commitment.is_some());
+        assert_eq!(
+            schema.layouts.get("v4").map(|layout| layout.nullifier),
+            Some(0)
+        );
+    }
+
+    #[test]
+    // Internal helper that supports `parse_rejects_invalid_layouts` operations.
+    fn parse_rejects_invalid_layouts() {
+        assert!(PublicInputSchema::parse(Some("not json")).is_err());
+        assert!(PublicInputSchema::parse(Some(
+            r#"{"v3": {"root": 0, "nullifier": 0, "action_hash": 2}}"#
+        ))
+        .is_err());
+        assert!(PublicInputSchema::parse(Some(r#"{"v3": {"nullifier": 1}}"#)).is_err());
+        assert!(PublicInputSchema::parse(Some(r#"{"v2": {"nullifier": 0, "extra": 1}}"#)).is_err());
+    }
+}
@@ -2,13 +2,22 @@ use crate::{
     config::Config,
     error::{AppError, Result},
 };
-use starknet_core::types::{Call, ExecutionResult, Felt, TransactionFinalityStatus};
+use starknet_core::types::{Call, Event, ExecutionResult, Felt, TransactionFinalityStatus};
 use tokio::time::{sleep, Duration};
 
-use super::onchain::{OnchainInvoker, OnchainReader};
+use super::onchain::{fee_unit_label, receipt_actual_fee, OnchainInvoker, OnchainReader};
 
 const DEFAULT_RELAYER_POLL_ATTEMPTS: usize = 20;
 const DEFAULT_RELAYER_POLL_INTERVAL_MS: u64 = 1_500;
+const RELAYER_REVERTED_PREFIX: &str = "Relayer transaction reverted: ";
+
+/// Returns the revert reason when `err` reports a reverted relayer transaction.
+pub fn relayer_revert_reason(err: &AppError) -> Option<&str> {
+    match err {
+        AppError::BadRequest(message) => message.strip_prefix(RELAYER_REVERTED_PREFIX),
+        _ => None,
+    }
+}
 
 pub struct RelayerService {
     invoker: OnchainInvoker,
@@ -18,6 +27,13 @@ pub struct RelayerService {
 #[derive(Debug, Clone)]
 pub struct RelayerSubmitResult {
     pub tx_hash: String,
+    /// Fee paid by the relayer account, in the smallest unit of `fee_unit`.
+    pub actual_fee: String,
+    pub fee_unit: &'static str,
+    /// Block the transaction was included in; receipts are only accepted once out of pre-confirmation.
+    pub block_number: u64,
+    /// Events emitted by the transaction, for callers that settle on transferred amounts.
+    pub events: Vec<Event>,
 }
 
 impl RelayerService {
@@ -41,6 +57,19 @@ impl RelayerService {
         self.wait_for_receipt(tx_hash).await
     }
 
+    /// Sends `calls` without waiting, so callers can persist the hash before confirming.
+    pub async fn send_calls(&self, calls: Vec<Call>) -> Result<String> {
+        let tx_hash = self.invoker.invoke_many(calls).await?;
+        Ok(format!("{:#x}", tx_hash))
+    }
+
+    /// Waits for a previously sent transaction; reverts surface via `relayer_revert_reason`.
+    pub async fn confirm(&self, tx_hash: &str) -> Result<RelayerSubmitResult> {
+        let tx_hash = Felt::from_hex(tx_hash.trim())
+            .map_err(|_| AppError::BadRequest(format!("Invalid relayer tx hash: {}", tx_hash)))?;
+        self.wait_for_receipt(tx_hash).await
+    }
+
     async fn wait_for_receipt(&self, tx_hash: Felt) -> Result<RelayerSubmitResult> {
         let poll_attempts = std::env::var("RELAYER_POLL_ATTEMPTS")
             .ok()
@@ -62,8 +91,8 @@ impl RelayerService {
                     if let ExecutionResult::Reverted { reason } = receipt.receipt.execution_result()
                     {
                         return Err(AppError::BadRequest(format!(
-                            "Relayer transaction reverted: {}",
-                            reason
+                            "{}{}",
+                            RELAYER_REVERTED_PREFIX, reason
                         )));
                     }
                     if matches!(
@@ -78,8 +107,13 @@ impl RelayerService {
                         break;
                     }
 
+                    let fee = receipt_actual_fee(&receipt.receipt);
                     return Ok(RelayerSubmitResult {
                         tx_hash: tx_hash_hex,
+                        actual_fee: fee.amount.to_string(),
+                        fee_unit: fee_unit_label(&fee.unit),
+                        block_number: receipt.block.block_number(),
+                        events: receipt.receipt.events().to_vec(),
                     });
                 }
                 Err(err) => {
@@ -1,9 +1,28 @@
 use crate::{
     config::Config,
     constants::{BRIDGE_ATOMIQ, BRIDGE_GARDEN, BRIDGE_LAYERSWAP, BRIDGE_STARKGATE},
-    error::{AppError, Result},
+    error::{AppError, ErrorCode, Result},
     integrations::bridge::{AtomiqClient, GardenClient, LayerSwapClient},
 };
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+const SUPPORTED_BRIDGE_CACHE_TTL_SECS: u64 = 300;
+
+// (from_chain, to_chain, from_token, to_token) pairs the bridge flow currently accepts.
+const SUPPORTED_BRIDGE_PAIRS: [(&str, &str, &str, &str); 6] = [
+    ("ethereum", "bitcoin", "ETH", "BTC"),
+    ("bitcoin", "ethereum", "BTC", "ETH"),
+    ("bitcoin", "starknet", "BTC", "WBTC"),
+    ("starknet", "bitcoin", "WBTC", "BTC"),
+    ("ethereum", "starknet", "ETH", "WBTC"),
+    ("starknet", "ethereum", "WBTC", "ETH"),
+];
+
+static SUPPORTED_BRIDGE_CACHE: OnceLock<tokio::sync::RwLock<Option<(Instant, SupportedBridges)>>> =
+    OnceLock::new();
 
 // Internal helper that parses or transforms values for `normalize_chain`.
 fn normalize_chain(value: &str) -> String {
@@ -321,24 +340,127 @@ fn bridge_pair_supported_for_current_routes(
     from_token: &str,
     requested_to_token: Option<&str>,
 ) -> bool {
+    let (from, to) =
+        resolve_bridge_pair_tokens(from_chain, to_chain, from_token, requested_to_token);
+    SUPPORTED_BRIDGE_PAIRS.contains(&(from_chain, to_chain, from.as_str(), to.as_str()))
+}
+
+// Internal helper that resolves the normalized (source, destination) tokens of a bridge request.
+fn resolve_bridge_pair_tokens(
+    from_chain: &str,
+    to_chain: &str,
+    from_token: &str,
+    requested_to_token: Option<&str>,
+) -> (String, String) {
     let from = normalize_bridge_token_for_chain(from_chain, from_token);
     let resolved_to = requested_to_token
         .map(normalize_token_symbol)
         .filter(|value| !value.is_empty())
         .unwrap_or_else(|| garden_destination_token(to_chain, from_token, requested_to_token));
-    let to = normalize_bridge_token_for_chain(to_chain, &resolved_to);
+    (
+        from,
+        normalize_bridge_token_for_chain(to_chain, &resolved_to),
+    )
+}
 
-    matches!(
-        (from_chain, to_chain, from.as_str(), to.as_str()),
-        ("ethereum", "bitcoin", "ETH", "BTC")
-            | ("bitcoin", "ethereum", "BTC", "ETH")
-            | ("bitcoin", "starknet", "BTC", "WBTC")
-            | ("starknet", "bitcoin", "WBTC", "BTC")
-            | ("ethereum", "starknet", "ETH", "WBTC")
-            | ("starknet", "ethereum", "WBTC", "ETH")
+// Internal helper that builds the error for an amount below every provider's route minimum.
+fn below_minimum_error(amount: f64, token: &str, below: &[(String, f64)]) -> AppError {
+    let minimums = below
+        .iter()
+        .map(|(provider, min)| format!("{} {} ({})", min, token, provider))
+        .collect::<Vec<_>>()
+        .join(", ");
+    AppError::Coded(
+        ErrorCode::BridgeAmountBelowMinimum,
+        format!(
+            "Amount {} {} is below the minimum for this route: {}.",
+            amount, token, minimums
+        ),
     )
 }
 
+// Internal helper that records Garden's minimum order amount on the routes it serves.
+fn apply_garden_min_amounts(
+    routes: &mut [SupportedBridgeRoute],
+    min_amounts: &std::collections::HashMap<String, u128>,
+) {
+    for route in routes.iter_mut() {
+        if !route
+            .providers
+            .iter()
+            .any(|provider| provider == BRIDGE_GARDEN)
+        {
+            continue;
+        }
+        let asset_id = GardenClient::asset_id(&route.from_chain, &route.from_token);
+        if let Some(units) = min_amounts.get(&asset_id) {
+            let scale = 10f64.powi(bridge_token_decimals(&route.from_token) as i32);
+            route
+                .min_amounts
+                .insert(BRIDGE_GARDEN.to_string(), *units as f64 / scale);
+        }
+    }
+}
+
+// Internal helper that splits `providers` into those accepting `amount` and those whose
+// route minimum it falls below.
+fn split_providers_by_minimum(
+    providers: Vec<String>,
+    min_amounts: &BTreeMap<String, f64>,
+    amount: f64,
+) -> (Vec<String>, Vec<(String, f64)>) {
+    let mut eligible = Vec::new();
+    let mut below = Vec::new();
+    for provider in providers {
+        match min_amounts.get(&provider) {
+            Some(min) if amount < *min => below.push((provider, *min)),
+            _ => eligible.push(provider),
+        }
+    }
+    (eligible, below)
+}
+
+// Internal helper that removes Garden from routes whose assets Garden no longer lists.
+fn drop_unlisted_garden_routes(
+    routes: &mut Vec<SupportedBridgeRoute>,
+    asset_ids: &HashSet<String>,
+) {
+    for route in routes.iter_mut() {
+        let listed = asset_ids.contains(&GardenClient::asset_id(
+            &route.from_chain,
+            &route.from_token,
+        )) && asset_ids
+            .contains(&GardenClient::asset_id(&route.to_chain, &route.to_token));
+        if !listed {
+            route.providers.retain(|provider| provider != BRIDGE_GARDEN);
+        }
+    }
+    routes.retain(|route| !route.providers.is_empty());
+}
+
+// Internal helper that builds the per-chain token and provider summary for `routes`.
+fn summarize_bridge_chains(routes: &[SupportedBridgeRoute]) -> Vec<SupportedBridgeChain> {
+    let mut chains: BTreeMap<&str, (BTreeSet<&str>, BTreeSet<&str>)> = BTreeMap::new();
+    for route in routes {
+        for (chain, token) in [
+            (route.from_chain.as_str(), route.from_token.as_str()),
+            (route.to_chain.as_str(), route.to_token.as_str()),
+        ] {
+            let (tokens, providers) = chains.entry(chain).or_default();
+            tokens.insert(token);
+            providers.extend(route.providers.iter().map(String::as_str));
+        }
+    }
+    chains
+        .into_iter()
+        .map(|(chain, (tokens, providers))| SupportedBridgeChain {
+            chain: chain.to_string(),
+            tokens: tokens.into_iter().map(str::to_string).collect(),
+            providers: providers.into_iter().map(str::to_string).collect(),
+        })
+        .collect()
+}
+
 /// Route Optimizer - Selects best bridge/swap routes
 pub struct RouteOptimizer {
     config: Config,
@@ -369,6 +491,27 @@ impl RouteOptimizer {
         to_token: Option<&str>,
         amount: f64,
     ) -> Result<BridgeRoute> {
+        let routes = self
+            .quote_bridge_routes(from_chain, to_chain, token, to_token, amount)
+            .await?;
+        routes.into_iter().next().ok_or_else(|| {
+            crate::error::AppError::NotFound("No bridge route available".to_string())
+        })
+    }
+
+    /// Quotes every eligible provider for a bridge route.
+    ///
+    /// # Returns
+    /// * Successful quotes ordered by routing score, so the first entry is the route
+    ///   `find_best_bridge_route` picks.
+    pub async fn quote_bridge_routes(
+        &self,
+        from_chain: &str,
+        to_chain: &str,
+        token: &str,
+        to_token: Option<&str>,
+        amount: f64,
+    ) -> Result<Vec<BridgeRoute>> {
         let from_chain_normalized = normalize_chain(from_chain);
         let to_chain_normalized = normalize_chain(to_chain);
         let expected_providers = bridge_providers_for(&from_chain_normalized, &to_chain_normalized);
@@ -424,8 +567,25 @@ impl RouteOptimizer {
             )));
         }
 
-        let mut best_route: Option<BridgeRoute> = None;
-        let mut best_score = 0.0;
+        let min_amounts = self
+            .route_min_amounts(
+                &from_chain_normalized,
+                &to_chain_normalized,
+                &normalized_from_token,
+                normalized_to_token.as_deref(),
+            )
+            .await;
+        let (providers, below_minimum) =
+            split_providers_by_minimum(providers, &min_amounts, amount);
+        if providers.is_empty() {
+            return Err(below_minimum_error(
+                amount,
+                &normalized_from_token,
+                &below_minimum,
+            ));
+        }
+
+        let mut scored_routes: Vec<(f64, BridgeRoute)> = Vec::new();
         let mut provider_errors: Vec<String> = Vec::new();
 
         for provider in providers {
@@ -442,11 +602,7 @@ impl RouteOptimizer {
             {
                 Ok(route) => {
                     let score = self.calculate_bridge_score(&route);
-
-                    if best_route.is_none() || score > best_score {
-                        best_route = Some(route);
-                        best_score = score;
-                    }
+                    scored_routes.push((score, route));
                 }
                 Err(err) => {
                     tracing::warn!(
@@ -470,8 +626,15 @@ impl RouteOptimizer {
             }
         }
 
-        if let Some(route) = best_route {
-            return Ok(route);
+        if !scored_routes.is_empty() {
+            // Stable sort keeps provider order on ties, matching the previous first-best pick.
+            scored_routes.sort_by(|left, right| {
+                right
+                    .0
+                    .partial_cmp(&left.0)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            return Ok(scored_routes.into_iter().map(|(_, route)| route).collect());
         }
 
         if provider_errors.is_empty() {
@@ -486,6 +649,92 @@ impl RouteOptimizer {
         )))
     }
 
+    /// Lists the bridge routes executable with the configured providers, merged across providers.
+    ///
+    /// # Notes
+    /// * Each route lists every configured provider that may execute it; cross-token routes are
+    ///   Garden-only, as in `find_best_bridge_route`.
+    /// * Garden routes are checked against Garden's live asset list, which also supplies their
+    ///   minimum amounts; when that lookup fails the configured routes are kept without
+    ///   minimums. Results are cached for 5 minutes.
+    pub async fn supported_bridge_routes(&self) -> SupportedBridges {
+        let cache = SUPPORTED_BRIDGE_CACHE.get_or_init(|| tokio::sync::RwLock::new(None));
+        if let Some((fetched_at, cached)) = cache.read().await.as_ref() {
+            if fetched_at.elapsed().as_secs() < SUPPORTED_BRIDGE_CACHE_TTL_SECS {
+                return cached.clone();
+            }
+        }
+
+        let force_garden = bridge_force_garden_enabled();
+        let mut routes: Vec<SupportedBridgeRoute> = SUPPORTED_BRIDGE_PAIRS
+            .iter()
+            .filter_map(|&(from_chain, to_chain, from_token, to_token)| {
+                let mut providers = apply_bridge_provider_mode(
+                    self.get_bridge_providers(from_chain, to_chain),
+                    force_garden,
+                );
+                if from_token != to_token {
+                    providers.retain(|provider| provider == BRIDGE_GARDEN);
+                }
+                providers.dedup();
+                (!providers.is_empty()).then(|| SupportedBridgeRoute {
+                    from_chain: from_chain.to_string(),
+                    to_chain: to_chain.to_string(),
+                    from_token: from_token.to_string(),
+                    to_token: to_token.to_string(),
+                    providers,
+                    min_amounts: BTreeMap::new(),
+                })
+            })
+            .collect();
+
+        if self.provider_is_configured(BRIDGE_GARDEN) {
+            let client = GardenClient::new(
+                self.config.garden_api_key.clone().unwrap_or_default(),
+                self.config.garden_api_url.clone(),
+            );
+            match client.get_asset_catalog().await {
+                Ok(catalog) => {
+                    if !catalog.ids.is_empty() {
+                        drop_unlisted_garden_routes(&mut routes, &catalog.ids);
+                    }
+                    apply_garden_min_amounts(&mut routes, &catalog.min_amounts);
+                }
+                Err(err) => tracing::warn!("Garden supported asset lookup failed: {}", err),
+            }
+        }
+
+        let supported = SupportedBridges {
+            chains: summarize_bridge_chains(&routes),
+            routes,
+        };
+        *cache.write().await = Some((Instant::now(), supported.clone()));
+        supported
+    }
+
+    // Internal helper that looks up the cached per-provider minimums for a bridge route.
+    async fn route_min_amounts(
+        &self,
+        from_chain: &str,
+        to_chain: &str,
+        from_token: &str,
+        to_token: Option<&str>,
+    ) -> BTreeMap<String, f64> {
+        let (from, to) = resolve_bridge_pair_tokens(from_chain, to_chain, from_token, to_token);
+        self.supported_bridge_routes()
+            .await
+            .routes
+            .into_iter()
+            .find(|route| {
+                route.from_chain == from_chain
+                    && route.to_chain == to_chain
+                    && route.from_token == from
+                    && route.to_token == to
+            })
+            .map(|route| route.min_amounts)
+            .unwrap_or_default()
+    }
+
     // Internal helper that fetches data for `get_bridge_providers`.
     fn get_bridge_providers(&self, from: &str, to: &str) -> Vec<String> {
         bridge_providers_for(from, to)
@@ -543,6 +792,7 @@ impl RouteOptimizer {
                 BridgeRoute {
                     provider: provider.to_string(),
                     token: token.to_string(),
+                    to_token: token.to_string(),
                     amount_in: quote.amount_in,
                     amount_out: quote.amount_out,
                     fee: quote.fee,
@@ -560,6 +810,7 @@ impl RouteOptimizer {
                 BridgeRoute {
                     provider: provider.to_string(),
                     token: token.to_string(),
+                    to_token: token.to_string(),
                     amount_in: quote.amount_in,
                     amount_out: quote.amount_out,
                     fee: quote.fee,
@@ -583,6 +834,7 @@ impl RouteOptimizer {
                 BridgeRoute {
                     provider: provider.to_string(),
                     token: token.to_string(),
+                    to_token: token.to_string(),
                     amount_in: amount,
                     amount_out: amount * (1.0 - fee_percent / 100.0),
                     fee: amount * (fee_percent / 100.0),
@@ -613,6 +865,7 @@ impl RouteOptimizer {
                 BridgeRoute {
                     provider: provider.to_string(),
                     token: token.to_string(),
+                    to_token: quote.to_token,
                     amount_in: quote.amount_in,
                     amount_out: quote.amount_out,
                     fee: quote.fee,
@@ -624,6 +877,7 @@ impl RouteOptimizer {
                 BridgeRoute {
                     provider: provider.to_string(),
                     token: token.to_string(),
+                    to_token: token.to_string(),
                     amount_in: amount,
                     amount_out: amount * (1.0 - fee_percent / 100.0),
                     fee: amount * (fee_percent / 100.0),
@@ -658,10 +912,41 @@ impl RouteOptimizer {
     }
 }
 
+/// Bridge route and the configured providers able to execute it.
+#[derive(Debug, Clone, Serialize)]
+pub struct SupportedBridgeRoute {
+    pub from_chain: String,
+    pub to_chain: String,
+    pub from_token: String,
+    pub to_token: String,
+    pub providers: Vec<String>,
+    /// Minimum amount in `from_token` per provider, where the provider publishes one.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub min_amounts: BTreeMap<String, f64>,
+}
+
+/// Chain reachable through at least one supported bridge route.
+#[derive(Debug, Clone, Serialize)]
+pub struct SupportedBridgeChain {
+    pub chain: String,
+    pub tokens: Vec<String>,
+    pub providers: Vec<String>,
+}
+
+/// Merged bridge support across every configured provider.
+#[derive(Debug, Clone, Serialize)]
+pub struct SupportedBridges {
+    pub chains: Vec<SupportedBridgeChain>,
+    pub routes: Vec<SupportedBridgeRoute>,
+}
+
 #[derive(Debug, Clone)]
 pub struct BridgeRoute {
     pub provider: String,
+    /// Source token; `amount_in` and `fee` are denominated in it.
     pub token: String,
+    /// Destination token `amount_out` is denominated in.
+    pub to_token: String,
     pub amount_in: f64,
     pub amount_out: f64,
     pub fee: f64,
@@ -776,6 +1061,7 @@ mod tests {
         let route = BridgeRoute {
             provider: BRIDGE_STARKGATE.to_string(),
             token: "ETH".to_string(),
+            to_token: "ETH".to_string(),
             amount_in: 100.0,
             amount_out: 99.0,
             fee: 1.0,
@@ -932,4 +1218,58 @@ mod tests {
             Some("STRK")
         ));
     }
+
+    #[test]
+    // Internal helper that supports `supported_routes_drop_unlisted_garden_assets` operations.
+    fn supported_routes_drop_unlisted_garden_assets() {
+        let route = |from_chain: &str, to_chain: &str, from_token: &str, to_token: &str| {
+            SupportedBridgeRoute {
+                from_chain: from_chain.to_string(),
+                to_chain: to_chain.to_string(),
+                from_token: from_token.to_string(),
+                to_token: to_token.to_string(),
+                providers: vec![BRIDGE_GARDEN.to_string()],
+                min_amounts: BTreeMap::new(),
+            }
+        };
+        let mut routes = vec![
+            route("bitcoin", "starknet", "BTC", "WBTC"),
+            route("ethereum", "starknet", "ETH", "WBTC"),
+        ];
+        let asset_ids: HashSet<String> = ["bitcoin_testnet:btc", "starknet_sepolia:wbtc"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        drop_unlisted_garden_routes(&mut routes, &asset_ids);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].from_chain, "bitcoin");
+
+        let chains = summarize_bridge_chains(&routes);
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[1].chain, "starknet");
+        assert_eq!(chains[1].tokens, vec!["WBTC".to_string()]);
+        assert_eq!(chains[1].providers, vec![BRIDGE_GARDEN.to_string()]);
+    }
+
+    #[test]
+    // Internal helper that supports `split_providers_by_minimum_reports_below_minimum` operations.
+    fn split_providers_by_minimum_reports_below_minimum() {
+        let providers = vec![BRIDGE_GARDEN.to_string(), BRIDGE_LAYERSWAP.to_string()];
+        let min_amounts = BTreeMap::from([(BRIDGE_GARDEN.to_string(), 0.0005)]);
+
+        let (eligible, below) = split_providers_by_minimum(providers.clone(), &min_amounts, 0.0001);
+        assert_eq!(eligible, vec![BRIDGE_LAYERSWAP.to_string()]);
+        assert_eq!(below, vec![(BRIDGE_GARDEN.to_string(), 0.0005)]);
+
+        let (eligible, below) = split_providers_by_minimum(providers, &min_amounts, 0.0005);
+        assert_eq!(eligible.len(), 2);
+        assert!(below.is_empty());
+
+        let err = below_minimum_error(0.0001, "BTC", &[(BRIDGE_GARDEN.to_string(), 0.0005)]);
+        assert!(matches!(
+            &err,
+            AppError::Coded(ErrorCode::BridgeAmountBelowMinimum, message)
+                if message.contains("0.0005 BTC (Garden)")
+        ));
+    }
 }
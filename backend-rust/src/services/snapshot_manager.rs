@@ -1,9 +1,245 @@
-use crate::{config::Config, constants::EPOCH_DURATION_SECONDS, db::Database, error::Result};
+use crate::{
+    config::Config,
+    constants::LIVE_LEADERBOARD_RECONCILE_INTERVAL_SECS,
+    db::Database,
+    error::{AppError, Result},
+    integrations::object_storage::ObjectStorageClient,
+    services::merkle_generator::{MerkleGenerator, MerkleLeafEntry},
+};
+use rust_decimal::prelude::ToPrimitive;
+use serde::Serialize;
 use sqlx::Row;
+use starknet_crypto::Felt;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::{Arc, OnceLock, RwLock},
+};
+use tokio::time::{interval, Duration};
+
+// Points are ranked at 0.01 resolution so float noise never splits a tie.
+const LIVE_LEADERBOARD_SCORE_SCALE: f64 = 100.0;
+
+static LIVE_LEADERBOARD: OnceLock<RwLock<LiveLeaderboard>> = OnceLock::new();
 
 // Internal helper that supports `epoch_from_timestamp` operations.
-fn epoch_from_timestamp(timestamp: i64) -> i64 {
-    timestamp / EPOCH_DURATION_SECONDS
+fn epoch_from_timestamp(timestamp: i64, epoch_seconds: i64) -> i64 {
+    timestamp / epoch_seconds.max(1)
+}
+
+// Internal helper that supports `score_bucket` operations.
+fn score_bucket(value: f64) -> i64 {
+    (value * LIVE_LEADERBOARD_SCORE_SCALE).round() as i64
+}
+
+/// One ranked identity (canonical user address, linked wallets folded in).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedIdentity {
+    pub rank: i64,
+    pub identity: String,
+    pub value: f64,
+}
+
+/// Points leaderboard for the current epoch, updated in place as point rows change.
+///
+/// Ranks use `RANK()` semantics: one plus the number of identities with a strictly
+/// higher score. Per-score counts keep rank lookups proportional to distinct scores,
+/// and the ordered index serves top-N without sorting the whole user base.
+#[derive(Debug, Default)]
+pub struct LiveLeaderboard {
+    epoch: Option<i64>,
+    address_totals: HashMap<String, f64>,
+    identity_of: HashMap<String, String>,
+    identity_totals: HashMap<String, f64>,
+    score_counts: BTreeMap<i64, u64>,
+    ordered: BTreeSet<(Reverse<i64>, String)>,
+}
+
+impl LiveLeaderboard {
+    /// Builds a leaderboard from `(address, identity, total)` point rows plus every known identity.
+    pub fn rebuild(epoch: i64, rows: Vec<(String, String, f64)>, identities: Vec<String>) -> Self {
+        let mut board = Self {
+            epoch: Some(epoch),
+            ..Self::default()
+        };
+        for identity in identities {
+            board.adjust_identity(&identity.to_ascii_lowercase(), 0.0);
+        }
+        for (address, identity, total) in rows {
+            let address = address.to_ascii_lowercase();
+            let identity = identity.to_ascii_lowercase();
+            if address != identity {
+                board.identity_of.insert(address.clone(), identity);
+            }
+            board.record_address_total(epoch, &address, total);
+        }
+        board
+    }
+
+    /// Epoch the leaderboard currently tracks, `None` until the first reconciliation.
+    pub fn epoch(&self) -> Option<i64> {
+        self.epoch
+    }
+
+    /// Applies a new absolute `total_points` for one `points` row; other epochs are ignored.
+    pub fn record_address_total(&mut self, epoch: i64, address: &str, total: f64) -> bool {
+        if self.epoch != Some(epoch) {
+            return false;
+        }
+        let address = address.trim().to_ascii_lowercase();
+        let previous = self
+            .address_totals
+            .insert(address.clone(), total)
+            .unwrap_or(0.0);
+        let identity = self.identity_of.get(&address).cloned().unwrap_or(address);
+        self.adjust_identity(&identity, total - previous);
+        true
+    }
+
+    // Internal helper that moves `identity` between score buckets by `delta`.
+    fn adjust_identity(&mut self, identity: &str, delta: f64) {
+        let previous = self.identity_totals.get(identity).copied();
+        if let Some(previous) = previous {
+            let bucket = score_bucket(previous);
+            if let Some(count) = self.score_counts.get_mut(&bucket) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    self.score_counts.remove(&bucket);
+                }
+            }
+            self.ordered
+                .remove(&(Reverse(bucket), identity.to_string()));
+        }
+        let next = previous.unwrap_or(0.0) + delta;
+        let bucket = score_bucket(next);
+        self.identity_totals.insert(identity.to_string(), next);
+        *self.score_counts.entry(bucket).or_insert(0) += 1;
+        self.ordered.insert((Reverse(bucket), identity.to_string()));
+    }
+
+    /// Number of ranked identities.
+    pub fn total_identities(&self) -> i64 {
+        self.identity_totals.len() as i64
+    }
+
+    /// Rank and score of `address`, resolving linked wallets to their identity.
+    pub fn rank_of(&self, address: &str) -> RankedIdentity {
+        let address = address.trim().to_ascii_lowercase();
+        let identity = self.identity_of.get(&address).cloned().unwrap_or(address);
+        let value = self.identity_totals.get(&identity).copied().unwrap_or(0.0);
+        let higher: u64 = self
+            .score_counts
+            .range(score_bucket(value) + 1..)
+            .map(|(_, count)| *count)
+            .sum();
+        RankedIdentity {
+            rank: higher as i64 + 1,
+            identity,
+            value,
+        }
+    }
+
+    /// Highest `limit` identities ordered by score, then identity.
+    pub fn top(&self, limit: usize) -> Vec<RankedIdentity> {
+        let mut entries = Vec::with_capacity(limit.min(self.ordered.len()));
+        let mut rank = 0_i64;
+        let mut previous_bucket = None;
+        for (position, (Reverse(bucket), identity)) in self.ordered.iter().take(limit).enumerate() {
+            if previous_bucket != Some(*bucket) {
+                rank = position as i64 + 1;
+                previous_bucket = Some(*bucket);
+            }
+            entries.push(RankedIdentity {
+                rank,
+                identity: identity.clone(),
+                value: self.identity_totals.get(identity).copied().unwrap_or(0.0),
+            });
+        }
+        entries
+    }
+
+    /// Counts identities whose score bucket differs from `other`, i.e. drift corrected by a reconcile.
+    pub fn drift_against(&self, other: &LiveLeaderboard) -> usize {
+        let mut drifted = self
+            .identity_totals
+            .iter()
+            .filter(|(identity, value)| {
+                other
+                    .identity_totals
+                    .get(*identity)
+                    .map(|other_value| score_bucket(*other_value) != score_bucket(**value))
+                    .unwrap_or(true)
+            })
+            .count();
+        drifted += other
+            .identity_totals
+            .keys()
+            .filter(|identity| !self.identity_totals.contains_key(*identity))
+            .count();
+        drifted
+    }
+}
+
+/// Process-wide live points leaderboard.
+pub fn live_leaderboard() -> &'static RwLock<LiveLeaderboard> {
+    LIVE_LEADERBOARD.get_or_init(|| RwLock::new(LiveLeaderboard::default()))
+}
+
+/// Records a `points` row's new `total_points` in the live leaderboard.
+pub fn record_points_total(address: &str, epoch: i64, total: rust_decimal::Decimal) {
+    let total = total.to_f64().unwrap_or(0.0);
+    if let Ok(mut board) = live_leaderboard().write() {
+        board.record_address_total(epoch, address, total);
+    }
+}
+
+/// Public, content-addressed export of a finalized epoch's merkle data.
+#[derive(Debug, Serialize)]
+pub struct EpochSnapshotExport {
+    pub epoch: i64,
+    pub root: String,
+    pub leaf_count: usize,
+    pub leaves: Vec<EpochSnapshotLeaf>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EpochSnapshotLeaf {
+    pub address: String,
+    pub points: String,
+    pub amount_wei: String,
+    pub leaf: String,
+}
+
+// Internal helper that builds inputs for `epoch_snapshot_export`.
+fn epoch_snapshot_export(
+    epoch: i64,
+    root: Felt,
+    entries: &[MerkleLeafEntry],
+) -> EpochSnapshotExport {
+    EpochSnapshotExport {
+        epoch,
+        root: root.to_fixed_hex_string(),
+        leaf_count: entries.len(),
+        leaves: entries
+            .iter()
+            .map(|entry| EpochSnapshotLeaf {
+                address: entry.address.clone(),
+                points: entry.points.to_string(),
+                amount_wei: entry.amount_wei.to_string(),
+                leaf: entry.leaf.to_fixed_hex_string(),
+            })
+            .collect(),
+    }
+}
+
+// Internal helper that builds inputs for `snapshot_object_key`.
+fn snapshot_object_key(prefix: &str, epoch: i64, root: &str) -> String {
+    let prefix = prefix.trim_matches('/');
+    if prefix.is_empty() {
+        format!("epoch-{}/{}.json", epoch, root)
+    } else {
+        format!("{}/epoch-{}/{}.json", prefix, epoch, root)
+    }
 }
 
 /// Snapshot Manager - Finalizes epochs and prepares for distribution
@@ -67,9 +303,50 @@ impl SnapshotManager {
             total_points
         );
 
+        // 4. Export merkle data off-site (best effort, never blocks finalization)
+        if let Err(err) = self.export_epoch_snapshot(epoch).await {
+            tracing::warn!("Epoch {} snapshot export failed: {}", epoch, err);
+        }
+
         Ok(())
     }
 
+    /// Uploads a finalized epoch's merkle root and leaf set to object storage.
+    ///
+    /// # Returns
+    /// * `Ok(Some(key))` with the object key once the export exists remotely.
+    /// * `Ok(None)` when object storage is not configured.
+    ///
+    /// # Notes
+    /// * The key embeds the root, so re-running for the same epoch is a no-op.
+    pub async fn export_epoch_snapshot(&self, epoch: i64) -> Result<Option<String>> {
+        let Some(storage) = ObjectStorageClient::from_config(&self.config)? else {
+            tracing::debug!("Object storage not configured; skipping epoch snapshot export");
+            return Ok(None);
+        };
+
+        let merkle = MerkleGenerator::new(self.db.clone(), self.config.clone());
+        let (entries, tree) = merkle.epoch_leaves_and_tree(epoch).await?;
+        let export = epoch_snapshot_export(epoch, tree.root, &entries);
+        let key = snapshot_object_key(&self.config.snapshot_export_prefix, epoch, &export.root);
+
+        if storage.object_exists(&key).await? {
+            tracing::info!("Epoch {} snapshot already exported at {}", epoch, key);
+            return Ok(Some(key));
+        }
+
+        let body =
+            serde_json::to_vec(&export).map_err(|err| AppError::Internal(err.to_string()))?;
+        storage.put_object(&key, body, "application/json").await?;
+        tracing::info!(
+            "Epoch {} snapshot exported: {} leaves at {}",
+            epoch,
+            export.leaf_count,
+            key
+        );
+        Ok(Some(key))
+    }
+
     /// Start new epoch
     pub async fn start_new_epoch(&self, epoch: i64) -> Result<()> {
         tracing::info!("Starting new epoch {}...", epoch);
@@ -85,19 +362,199 @@ impl SnapshotManager {
 
     /// Get current epoch
     pub fn get_current_epoch(&self) -> i64 {
-        epoch_from_timestamp(chrono::Utc::now().timestamp())
+        epoch_from_timestamp(
+            chrono::Utc::now().timestamp(),
+            self.config.rewards_epoch_duration_seconds,
+        )
+    }
+
+    /// Starts the periodic full reconciliation that seeds and corrects the live leaderboard.
+    pub async fn start_live_leaderboard(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut ticker = interval(Duration::from_secs(
+                LIVE_LEADERBOARD_RECONCILE_INTERVAL_SECS,
+            ));
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.reconcile_live_leaderboard().await {
+                    tracing::error!("Live leaderboard reconcile error: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Recomputes the current epoch's leaderboard from `points` and swaps it in.
+    ///
+    /// Also rolls the live leaderboard over to a new epoch. Updates landing between the
+    /// read and the swap are picked up by the next reconciliation.
+    pub async fn reconcile_live_leaderboard(&self) -> Result<usize> {
+        let epoch = self.config.current_rewards_epoch();
+        let rows: Vec<(String, String, f64)> = sqlx::query_as(
+            "SELECT
+                 p.user_address,
+                 COALESCE(uw.user_address, p.user_address) as identity,
+                 COALESCE(SUM(p.total_points), 0)::FLOAT as total_points
+             FROM points p
+             LEFT JOIN user_wallet_addresses uw
+               ON LOWER(uw.wallet_address) = LOWER(p.user_address)
+             WHERE p.epoch = $1
+             GROUP BY p.user_address, COALESCE(uw.user_address, p.user_address)",
+        )
+        .bind(epoch)
+        .fetch_all(self.db.pool())
+        .await?;
+        let identities: Vec<String> = sqlx::query_scalar("SELECT address FROM users")
+            .fetch_all(self.db.pool())
+            .await?;
+
+        let rebuilt = LiveLeaderboard::rebuild(epoch, rows, identities);
+        let mut board = live_leaderboard()
+            .write()
+            .map_err(|_| crate::error::AppError::Internal("Live leaderboard poisoned".into()))?;
+        let drift = if board.epoch() == Some(epoch) {
+            board.drift_against(&rebuilt)
+        } else {
+            0
+        };
+        if drift > 0 {
+            tracing::warn!(
+                "Live leaderboard drift corrected for {} identities in epoch {}",
+                drift,
+                epoch
+            );
+        }
+        *board = rebuilt;
+        Ok(drift)
+    }
+
+    /// Ranked identities for a finalized epoch, or `None` when no snapshot exists yet.
+    pub async fn finalized_rankings(
+        &self,
+        epoch: i64,
+        limit: i64,
+    ) -> Result<Option<Vec<RankedIdentity>>> {
+        let finalized: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM epoch_snapshots WHERE epoch = $1)")
+                .bind(epoch)
+                .fetch_one(self.db.pool())
+                .await?;
+        if !finalized {
+            return Ok(None);
+        }
+
+        let rows: Vec<(i64, String, f64)> = sqlx::query_as(
+            "WITH identity_points AS (
+                 SELECT
+                     COALESCE(uw.user_address, p.user_address) as identity,
+                     COALESCE(SUM(p.total_points), 0) as total_points
+                 FROM points p
+                 LEFT JOIN user_wallet_addresses uw
+                   ON LOWER(uw.wallet_address) = LOWER(p.user_address)
+                 WHERE p.epoch = $1 AND p.finalized = true
+                 GROUP BY COALESCE(uw.user_address, p.user_address)
+             )
+             SELECT
+                 RANK() OVER (ORDER BY ip.total_points DESC) as rank,
+                 ip.identity,
+                 CAST(ip.total_points AS FLOAT) as value
+             FROM identity_points ip
+             ORDER BY ip.total_points DESC, ip.identity ASC
+             LIMIT $2",
+        )
+        .bind(epoch)
+        .bind(limit)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(Some(
+            rows.into_iter()
+                .map(|(rank, identity, value)| RankedIdentity {
+                    rank,
+                    identity,
+                    value,
+                })
+                .collect(),
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::EPOCH_DURATION_SECONDS;
+
+    #[test]
+    // Internal helper that builds inputs for `snapshot_export_is_content_addressed`.
+    fn snapshot_export_is_content_addressed() {
+        let entries = vec![MerkleLeafEntry {
+            address: "0x1".to_string(),
+            points: rust_decimal::Decimal::new(1250, 2),
+            amount_wei: 1_000_000_000_000_000_000,
+            leaf: Felt::from(7_u64),
+        }];
+        let export = epoch_snapshot_export(3, Felt::from(42_u64), &entries);
+        assert_eq!(export.leaf_count, 1);
+        assert_eq!(export.leaves[0].amount_wei, "1000000000000000000");
+        assert_eq!(
+            snapshot_object_key("/reward-snapshots/", 3, &export.root),
+            format!("reward-snapshots/epoch-3/{}.json", export.root)
+        );
+        assert_eq!(snapshot_object_key("", 3, "0x2a"), "epoch-3/0x2a.json");
+    }
 
     #[test]
     // Internal helper that supports `epoch_from_timestamp_calculates_epoch` operations.
     fn epoch_from_timestamp_calculates_epoch() {
         // Memastikan epoch dihitung dari timestamp
         let timestamp = EPOCH_DURATION_SECONDS * 2 + 10;
-        assert_eq!(epoch_from_timestamp(timestamp), 2);
+        assert_eq!(epoch_from_timestamp(timestamp, EPOCH_DURATION_SECONDS), 2);
+        assert_eq!(epoch_from_timestamp(7_200 * 3 + 1, 7_200), 3);
+    }
+
+    #[test]
+    // Internal helper that supports `live_leaderboard_updates_ranks_incrementally` operations.
+    fn live_leaderboard_updates_ranks_incrementally() {
+        let mut board = LiveLeaderboard::rebuild(
+            7,
+            vec![
+                ("0xa".to_string(), "0xa".to_string(), 50.0),
+                ("0xb".to_string(), "0xb".to_string(), 20.0),
+                ("0xb2".to_string(), "0xB".to_string(), 5.0),
+            ],
+            vec!["0xa".to_string(), "0xb".to_string(), "0xc".to_string()],
+        );
+        assert_eq!(board.total_identities(), 3);
+        assert_eq!(board.rank_of("0xb2").identity, "0xb");
+        assert_eq!(board.rank_of("0xb").value, 25.0);
+        assert_eq!(board.rank_of("0xc").rank, 3);
+
+        assert!(board.record_address_total(7, "0xB2", 40.0));
+        assert_eq!(board.rank_of("0xb").rank, 1);
+        assert_eq!(board.rank_of("0xa").rank, 2);
+        assert!(!board.record_address_total(8, "0xa", 100.0));
+
+        assert!(board.record_address_total(7, "0xa", 60.0));
+        let top = board.top(2);
+        assert_eq!(top[0].rank, 1);
+        assert_eq!(top[1].rank, 1);
+        assert_eq!(top[0].identity, "0xa");
+        assert_eq!(board.rank_of("0xc").rank, 3);
+    }
+
+    #[test]
+    // Internal helper that supports `live_leaderboard_drift_matches_full_rebuild` operations.
+    fn live_leaderboard_drift_matches_full_rebuild() {
+        let rows = vec![
+            ("0xa".to_string(), "0xa".to_string(), 10.0),
+            ("0xb".to_string(), "0xb".to_string(), 30.0),
+        ];
+        let mut board = LiveLeaderboard::rebuild(1, rows.clone(), Vec::new());
+        let full = LiveLeaderboard::rebuild(1, rows, Vec::new());
+        assert_eq!(board.drift_against(&full), 0);
+
+        board.record_address_total(1, "0xa", 12.0);
+        board.record_address_total(1, "0xd", 1.0);
+        assert_eq!(board.drift_against(&full), 2);
+        assert_eq!(board.top(10).len(), 3);
     }
 }
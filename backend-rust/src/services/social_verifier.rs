@@ -1,5 +1,5 @@
 use crate::services::onchain::{parse_felt, OnchainInvoker};
-use crate::{config::Config, constants::EPOCH_DURATION_SECONDS, db::Database, error::Result};
+use crate::{config::Config, db::Database, error::Result};
 use rust_decimal::prelude::ToPrimitive;
 use starknet_core::types::Call;
 use starknet_core::utils::get_selector_from_name;
@@ -100,7 +100,7 @@ impl SocialVerifier {
 
     /// Award social points
     pub async fn award_points(&self, user_address: &str, points: f64) -> Result<()> {
-        let epoch = chrono::Utc::now().timestamp() / EPOCH_DURATION_SECONDS;
+        let epoch = self.config.current_rewards_epoch();
 
         // Gunakan from_f64_retain atau unwrap_or_default untuk keamanan
         let points_decimal =
@@ -108,7 +108,7 @@ impl SocialVerifier {
 
         // Upsert agar social points tidak hilang saat row points epoch belum ada.
         // total_points dijaga konsisten dengan formula berbasis multiplier.
-        sqlx::query(
+        let new_total: rust_decimal::Decimal = sqlx::query_scalar(
             r#"
             INSERT INTO points (user_address, epoch, social_points, total_points)
             VALUES ($1, $2, $3, $3)
@@ -116,13 +116,15 @@ impl SocialVerifier {
             SET social_points = points.social_points + EXCLUDED.social_points,
                 total_points = points.total_points + (EXCLUDED.social_points * points.staking_multiplier),
                 updated_at = NOW()
+            RETURNING total_points
             "#,
         )
         .bind(user_address)
         .bind(epoch)
         .bind(points_decimal)
-        .execute(self.db.pool())
+        .fetch_one(self.db.pool())
         .await?;
+        super::snapshot_manager::record_points_total(user_address, epoch, new_total);
 
         self.sync_points_onchain(epoch as u64, user_address, points_decimal)
             .await?;
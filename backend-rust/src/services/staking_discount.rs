@@ -0,0 +1,219 @@
+use crate::{
+    config::Config,
+    error::{AppError, Result},
+    services::onchain::{felt_to_u128, parse_felt, u256_from_felts, OnchainReader},
+};
+use serde::Deserialize;
+use starknet_core::types::FunctionCall;
+use starknet_core::utils::get_selector_from_name;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Instant;
+use tokio::time::{timeout, Duration};
+
+const CAREL_DECIMALS: f64 = 1_000_000_000_000_000_000.0;
+const STAKE_READ_TIMEOUT_MS: u64 = 2_500;
+const STAKE_DISCOUNT_CACHE_TTL_SECS: u64 = 300;
+const STAKE_DISCOUNT_CACHE_STALE_SECS: u64 = 1_800;
+const STAKE_DISCOUNT_CACHE_MAX_ENTRIES: usize = 100_000;
+
+static STAKED_CAREL_CACHE: OnceLock<tokio::sync::RwLock<HashMap<String, (Instant, f64)>>> =
+    OnceLock::new();
+
+/// One `CAREL_STAKE_DISCOUNT_TIERS` entry: stakes of at least `min_staked` CAREL earn
+/// `discount_percent` off swap fees.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct StakeDiscountTier {
+    pub min_staked: f64,
+    pub discount_percent: f64,
+}
+
+/// Parses `min_carel:percent` pairs separated by commas (e.g. `1000:5,10000:10`).
+///
+/// An empty value disables the staking discount. Tiers are returned sorted by threshold.
+pub fn parse_stake_discount_tiers(raw: &str) -> anyhow::Result<Vec<StakeDiscountTier>> {
+    let mut tiers = Vec::new();
+    for entry in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (min_raw, percent_raw) = entry.split_once(':').ok_or_else(|| {
+            anyhow::anyhow!(
+                "CAREL_STAKE_DISCOUNT_TIERS entry '{}' must be min_carel:percent",
+                entry
+            )
+        })?;
+        let min_staked: f64 = min_raw.trim().parse().map_err(|_| {
+            anyhow::anyhow!(
+                "CAREL_STAKE_DISCOUNT_TIERS has invalid amount '{}'",
+                min_raw
+            )
+        })?;
+        let discount_percent: f64 = percent_raw.trim().parse().map_err(|_| {
+            anyhow::anyhow!(
+                "CAREL_STAKE_DISCOUNT_TIERS has invalid percent '{}'",
+                percent_raw
+            )
+        })?;
+        if !min_staked.is_finite() || min_staked <= 0.0 {
+            anyhow::bail!("CAREL_STAKE_DISCOUNT_TIERS amounts must be > 0");
+        }
+        if !(0.0..=100.0).contains(&discount_percent) {
+            anyhow::bail!("CAREL_STAKE_DISCOUNT_TIERS percents must be within 0..=100");
+        }
+        tiers.push(StakeDiscountTier {
+            min_staked,
+            discount_percent,
+        });
+    }
+    tiers.sort_by(|a, b| a.min_staked.total_cmp(&b.min_staked));
+    Ok(tiers)
+}
+
+// Internal helper that picks the highest tier reached by `staked`.
+fn discount_for_stake(tiers: &[StakeDiscountTier], staked: f64) -> f64 {
+    tiers
+        .iter()
+        .filter(|tier| staked >= tier.min_staked)
+        .map(|tier| tier.discount_percent)
+        .fold(0.0, f64::max)
+}
+
+/// Adds the NFT and staking discounts and caps the result at `max_percent`.
+pub fn combine_fee_discounts(nft_percent: f64, stake_percent: f64, max_percent: f64) -> f64 {
+    (nft_percent.max(0.0) + stake_percent.max(0.0)).clamp(0.0, max_percent.clamp(0.0, 100.0))
+}
+
+// Internal helper that supports `staked_carel_cache` operations.
+fn staked_carel_cache() -> &'static tokio::sync::RwLock<HashMap<String, (Instant, f64)>> {
+    STAKED_CAREL_CACHE.get_or_init(|| tokio::sync::RwLock::new(HashMap::new()))
+}
+
+// Internal helper that supports `staking_contract` operations.
+fn staking_contract(config: &Config) -> Option<&str> {
+    config
+        .staking_carel_address
+        .as_deref()
+        .filter(|addr| !addr.trim().is_empty() && !addr.starts_with("0x0000"))
+}
+
+/// Decoded `get_stake_info` result for a CAREL staker.
+pub struct CarelStakeInfo {
+    pub amount: u128,
+    pub start_time: u64,
+}
+
+/// Reads `get_stake_info(user)` from the CAREL staking contract.
+///
+/// Returns `None` when the contract answers with fewer felts than the `Stake` struct.
+pub async fn fetch_carel_stake_info(
+    reader: &OnchainReader,
+    contract: &str,
+    user_address: &str,
+) -> Result<Option<CarelStakeInfo>> {
+    let call = FunctionCall {
+        contract_address: parse_felt(contract)?,
+        entry_point_selector: get_selector_from_name("get_stake_info")
+            .map_err(|e| AppError::Internal(format!("Selector error: {}", e)))?,
+        calldata: vec![parse_felt(user_address)?],
+    };
+
+    let result = reader.call(call).await?;
+    if result.len() < 7 {
+        return Ok(None);
+    }
+
+    let amount = u256_from_felts(&result[0], &result[1])?;
+    let start_time = felt_to_u128(&result[3])? as u64;
+
+    Ok(Some(CarelStakeInfo { amount, start_time }))
+}
+
+// Internal helper that reads the user's staked CAREL (whole tokens).
+async fn read_staked_carel(config: &Config, contract: &str, user_address: &str) -> Result<f64> {
+    let reader = OnchainReader::from_config(config)?;
+    let info = timeout(
+        Duration::from_millis(STAKE_READ_TIMEOUT_MS),
+        fetch_carel_stake_info(&reader, contract, user_address),
+    )
+    .await
+    .map_err(|_| AppError::BlockchainRPC("CAREL stake read timeout".to_string()))??;
+    Ok(info.map_or(0.0, |info| info.amount as f64 / CAREL_DECIMALS))
+}
+
+/// Returns the staking fee discount (percent) earned by `user_address`.
+///
+/// The staked balance is cached for five minutes; on read failures a cached value up to
+/// 30 minutes old is reused, otherwise no staking discount is applied.
+pub async fn staked_carel_discount_percent(config: &Config, user_address: &str) -> f64 {
+    let tiers = config.carel_stake_discount_tiers.as_slice();
+    if tiers.is_empty() {
+        return 0.0;
+    }
+    let Some(contract) = staking_contract(config) else {
+        return 0.0;
+    };
+    let cache_key = format!(
+        "{}|{}",
+        contract.trim().to_ascii_lowercase(),
+        user_address.trim().to_ascii_lowercase()
+    );
+    let cached = staked_carel_cache().read().await.get(&cache_key).copied();
+    if let Some((fetched_at, staked)) = cached {
+        if fetched_at.elapsed() <= Duration::from_secs(STAKE_DISCOUNT_CACHE_TTL_SECS) {
+            return discount_for_stake(tiers, staked);
+        }
+    }
+
+    match read_staked_carel(config, contract, user_address).await {
+        Ok(staked) => {
+            let mut guard = staked_carel_cache().write().await;
+            guard.insert(cache_key, (Instant::now(), staked));
+            if guard.len() > STAKE_DISCOUNT_CACHE_MAX_ENTRIES {
+                let stale_after = Duration::from_secs(STAKE_DISCOUNT_CACHE_STALE_SECS);
+                guard.retain(|_, (fetched_at, _)| fetched_at.elapsed() <= stale_after);
+            }
+            discount_for_stake(tiers, staked)
+        }
+        Err(err) => {
+            tracing::warn!(
+                "Failed to read staked CAREL for fee discount (user={}): {}",
+                user_address,
+                err
+            );
+            cached
+                .filter(|(fetched_at, _)| {
+                    fetched_at.elapsed() <= Duration::from_secs(STAKE_DISCOUNT_CACHE_STALE_SECS)
+                })
+                .map(|(_, staked)| discount_for_stake(tiers, staked))
+                .unwrap_or(0.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `parse_stake_discount_tiers_sorts_and_validates` operations.
+    fn parse_stake_discount_tiers_sorts_and_validates() {
+        let tiers = parse_stake_discount_tiers("10000:10, 1000:5").expect("valid tiers");
+        assert_eq!(tiers[0].min_staked, 1000.0);
+        assert_eq!(discount_for_stake(&tiers, 999.0), 0.0);
+        assert_eq!(discount_for_stake(&tiers, 1000.0), 5.0);
+        assert_eq!(discount_for_stake(&tiers, 50_000.0), 10.0);
+        assert!(parse_stake_discount_tiers("").expect("empty").is_empty());
+        assert!(parse_stake_discount_tiers("1000").is_err());
+        assert!(parse_stake_discount_tiers("1000:150").is_err());
+    }
+
+    #[test]
+    // Internal helper that supports `combine_fee_discounts_respects_cap` operations.
+    fn combine_fee_discounts_respects_cap() {
+        assert_eq!(combine_fee_discounts(25.0, 10.0, 50.0), 35.0);
+        assert_eq!(combine_fee_discounts(45.0, 15.0, 50.0), 50.0);
+        assert_eq!(combine_fee_discounts(-5.0, 0.0, 50.0), 0.0);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     db::Database,
     error::{AppError, Result},
-    models::{PaginatedResponse, Transaction},
+    models::{PaginatedResponse, Transaction, TransactionHistoryItem},
 };
 use chrono::{DateTime, Utc};
 use sqlx::Row; // PENTING: Import ini untuk memperbaiki error try_get
@@ -46,6 +46,8 @@ fn normalize_scope_addresses(user_addresses: &[String]) -> Vec<String> {
 
 pub struct TransactionHistoryService {
     db: Database,
+    // Attempts after which an unprocessed row is reported as `failed`.
+    max_processing_attempts: i32,
 }
 
 impl TransactionHistoryService {
@@ -60,8 +62,11 @@ impl TransactionHistoryService {
     ///
     /// # Notes
     /// * May update state, query storage, or invoke relayer/on-chain paths depending on flow.
-    pub fn new(db: Database) -> Self {
-        Self { db }
+    pub fn new(db: Database, max_processing_attempts: u32) -> Self {
+        Self {
+            db,
+            max_processing_attempts: i32::try_from(max_processing_attempts).unwrap_or(i32::MAX),
+        }
     }
 
     /// Get user transaction history with pagination and filters
@@ -73,7 +78,7 @@ impl TransactionHistoryService {
         to_date: Option<DateTime<Utc>>,
         page: i32,
         limit: i32,
-    ) -> Result<PaginatedResponse<Transaction>> {
+    ) -> Result<PaginatedResponse<TransactionHistoryItem>> {
         let normalized_addresses = normalize_scope_addresses(user_addresses);
         if normalized_addresses.is_empty() {
             return Err(AppError::BadRequest(
@@ -103,11 +108,26 @@ impl TransactionHistoryService {
                 CASE
                     WHEN block_number > 0 THEN true
                     ELSE processed
-                END AS processed
+                END AS processed,
+                CASE
+                    WHEN processed THEN 'processed'
+                    WHEN processing_attempts >= $2 THEN 'failed'
+                    WHEN processing_attempts > 0 THEN 'retrying'
+                    ELSE 'pending'
+                END AS points_status,
+                processing_attempts,
+                CASE
+                    WHEN processed THEN NULL
+                    WHEN processing_attempts >= $2
+                        THEN 'Points could not be awarded for this transaction. Contact support if this persists.'
+                    WHEN processing_attempts > 0
+                        THEN 'Points processing failed and will be retried automatically.'
+                    ELSE NULL
+                END AS processing_error
              FROM transactions
              WHERE LOWER(user_address) = ANY($1)",
         );
-        let mut param_count = 2;
+        let mut param_count = 3;
 
         if tx_type.is_some() {
             query.push_str(&format!(" AND tx_type = ${}", param_count));
@@ -130,8 +150,9 @@ impl TransactionHistoryService {
         ));
 
         // Gunakan sqlx::query_as (Runtime) bukan macro query_as!
-        let mut query_builder = sqlx::query_as::<_, Transaction>(&query);
+        let mut query_builder = sqlx::query_as::<_, TransactionHistoryItem>(&query);
         query_builder = query_builder.bind(normalized_addresses.clone());
+        query_builder = query_builder.bind(self.max_processing_attempts);
 
         if let Some(ref t) = tx_type {
             query_builder = query_builder.bind(t);
@@ -151,12 +172,7 @@ impl TransactionHistoryService {
             .get_total_count(&normalized_addresses, tx_type, from_date, to_date)
             .await?;
 
-        Ok(PaginatedResponse {
-            items: transactions,
-            page,
-            limit,
-            total,
-        })
+        Ok(PaginatedResponse::new(transactions, page, limit, total))
     }
 
     // Internal helper that fetches data for `get_total_count`.
@@ -354,8 +370,8 @@ impl TransactionHistoryService {
 
         let mut csv = String::from(csv_header());
 
-        for tx in transactions.items {
-            csv.push_str(&format_csv_row(&tx));
+        for item in transactions.items {
+            csv.push_str(&format_csv_row(&item.transaction));
         }
 
         Ok(csv)
@@ -0,0 +1,387 @@
+use crate::{
+    config::Config,
+    constants::TX_RECONCILE_INTERVAL_SECS,
+    db::Database,
+    error::Result,
+    services::notification_service::NotificationService,
+    services::onchain::{is_tx_hash_not_found, parse_felt, OnchainReader},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use starknet_core::types::{ExecutionResult, Felt, TransactionFinalityStatus};
+use std::sync::Arc;
+
+// Only recent rows are sampled; older ones were settled or flagged by earlier runs.
+const TX_RECONCILE_LOOKBACK_SECS: f64 = 86_400.0;
+// Hashes unknown to the node this long after saving are reported as missing.
+const TX_RECONCILE_NOT_FOUND_GRACE_SECS: i64 = 900;
+const MAX_ISSUE_DETAIL_LEN: usize = 500;
+// Types whose `tx_hash` is always a Starknet hash. Bridge rows may carry the source
+// chain's hash and battle rows use off-chain ids, and neither records its chain.
+const STARKNET_TX_TYPES: &[&str] = &[
+    "swap",
+    "stake",
+    "unstake",
+    "claim",
+    "limit_order",
+    "ai_level_upgrade",
+    "rename_fee",
+];
+
+/// On-chain state of a saved transaction as seen by the reconciler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainTxState {
+    Succeeded { block_number: u64 },
+    Reverted { block_number: u64, reason: String },
+    Pending,
+    NotFound,
+}
+
+/// Corrections and report for one sampled transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReconcilePlan {
+    pub chain_status: Option<&'static str>,
+    pub block_number: Option<i64>,
+    pub issue: Option<&'static str>,
+    pub detail: Option<String>,
+}
+
+/// Discrepancy recorded between the `transactions` table and the chain.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ReconciliationIssue {
+    pub id: i64,
+    pub tx_hash: String,
+    pub issue: String,
+    pub db_block_number: i64,
+    pub chain_block_number: Option<i64>,
+    pub detail: Option<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+// Saved transaction picked for a reconciliation run.
+#[derive(Debug, sqlx::FromRow)]
+struct SampledTransaction {
+    tx_hash: String,
+    block_number: i64,
+    timestamp: DateTime<Utc>,
+    user_address: String,
+    tx_type: String,
+    token_in: Option<String>,
+    token_out: Option<String>,
+}
+
+/// Decides what to correct and report for a saved transaction.
+///
+/// # Notes
+/// * API-saved rows carry `block_number = 0`; a confirmed receipt backfills it without
+///   counting as a discrepancy. A different non-zero block number is corrected and reported.
+pub fn plan_reconciliation(
+    db_block_number: i64,
+    age_secs: i64,
+    state: &ChainTxState,
+) -> ReconcilePlan {
+    match state {
+        ChainTxState::Succeeded { block_number } => {
+            let chain_block = i64::try_from(*block_number).unwrap_or(i64::MAX);
+            let mismatch = db_block_number != 0 && db_block_number != chain_block;
+            ReconcilePlan {
+                chain_status: Some("succeeded"),
+                block_number: (db_block_number != chain_block).then_some(chain_block),
+                issue: mismatch.then_some("block_number_mismatch"),
+                detail: None,
+            }
+        }
+        ChainTxState::Reverted {
+            block_number,
+            reason,
+        } => {
+            let chain_block = i64::try_from(*block_number).unwrap_or(i64::MAX);
+            ReconcilePlan {
+                chain_status: Some("reverted"),
+                block_number: (db_block_number != chain_block).then_some(chain_block),
+                issue: Some("reverted_on_chain"),
+                detail: Some(reason.chars().take(MAX_ISSUE_DETAIL_LEN).collect()),
+            }
+        }
+        ChainTxState::Pending => ReconcilePlan::default(),
+        ChainTxState::NotFound if age_secs >= TX_RECONCILE_NOT_FOUND_GRACE_SECS => ReconcilePlan {
+            chain_status: Some("not_found"),
+            block_number: None,
+            issue: Some("missing_on_chain"),
+            detail: None,
+        },
+        ChainTxState::NotFound => ReconcilePlan::default(),
+    }
+}
+
+pub struct TransactionReconciler {
+    db: Database,
+    config: Config,
+}
+
+impl TransactionReconciler {
+    pub fn new(db: Database, config: Config) -> Self {
+        Self { db, config }
+    }
+
+    /// Starts the periodic reconciliation loop.
+    ///
+    /// # Notes
+    /// * Disabled when `TX_RECONCILE_SAMPLE_SIZE=0`.
+    pub async fn start(self: Arc<Self>) {
+        if self.config.tx_reconcile_sample_size == 0 {
+            tracing::warn!("Transaction reconciler disabled via TX_RECONCILE_SAMPLE_SIZE=0");
+            return;
+        }
+        tokio::spawn(async move {
+            let mut ticker =
+                tokio::time::interval(std::time::Duration::from_secs(TX_RECONCILE_INTERVAL_SECS));
+            loop {
+                ticker.tick().await;
+                match self.reconcile_once().await {
+                    Ok(0) => {}
+                    Ok(issues) => tracing::warn!(
+                        "Transaction reconciler found {} discrepancies; see GET /api/v1/admin/transactions/reconciliation",
+                        issues
+                    ),
+                    Err(err) => tracing::error!("Transaction reconciliation error: {}", err),
+                }
+            }
+        });
+    }
+
+    /// Checks a sample of recent unreconciled transactions against the chain.
+    ///
+    /// # Returns
+    /// * `Ok(count)` with the number of discrepancies recorded in this run.
+    ///
+    /// # Notes
+    /// * Only rows whose type always settles on Starknet are sampled, so bridge and battle
+    ///   rows are never selected. Hashes that do not parse as a felt are marked `skipped`.
+    /// * Pending, recently saved unknown hashes and RPC failures are retried next run.
+    pub async fn reconcile_once(&self) -> Result<usize> {
+        let reader = OnchainReader::from_config(&self.config)?;
+        let rows = sqlx::query_as::<_, SampledTransaction>(
+            "SELECT tx_hash, block_number, timestamp, user_address, tx_type, token_in, token_out
+             FROM transactions
+             WHERE chain_status IS NULL
+               AND timestamp > NOW() - make_interval(secs => $1)
+               AND tx_type = ANY($3)
+               AND tx_hash ~ '^0x[0-9a-fA-F]{1,64}$'
+             ORDER BY timestamp ASC
+             LIMIT $2",
+        )
+        .bind(TX_RECONCILE_LOOKBACK_SECS)
+        .bind(i64::from(self.config.tx_reconcile_sample_size))
+        .bind(STARKNET_TX_TYPES)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let now = Utc::now();
+        let mut issues = 0;
+        for row in rows {
+            let Ok(tx_hash_felt) = parse_felt(&row.tx_hash) else {
+                self.mark_skipped(&row.tx_hash).await?;
+                continue;
+            };
+            let state = match self.chain_state(&reader, &tx_hash_felt).await {
+                Ok(state) => state,
+                Err(err) => {
+                    tracing::debug!("Reconciler skipped tx {}: {}", row.tx_hash, err);
+                    continue;
+                }
+            };
+            let age_secs = (now - row.timestamp).num_seconds();
+            let plan = plan_reconciliation(row.block_number, age_secs, &state);
+            if self.apply(&row.tx_hash, row.block_number, &plan).await? {
+                issues += 1;
+            }
+            if let ChainTxState::Reverted { reason, .. } = &state {
+                if row.tx_type == "swap" {
+                    NotificationService::new(self.db.clone(), self.config.clone())
+                        .notify_swap_failed(
+                            &row.user_address,
+                            row.token_in.as_deref().unwrap_or("?"),
+                            row.token_out.as_deref().unwrap_or("?"),
+                            Some(&row.tx_hash),
+                            reason,
+                        )
+                        .await;
+                }
+            }
+        }
+        Ok(issues)
+    }
+
+    // Internal helper that fetches data for `chain_state`.
+    async fn chain_state(
+        &self,
+        reader: &OnchainReader,
+        tx_hash_felt: &Felt,
+    ) -> Result<ChainTxState> {
+        let receipt = match reader.get_transaction_receipt(tx_hash_felt).await {
+            Ok(receipt) => receipt,
+            Err(err) if is_tx_hash_not_found(&err) => return Ok(ChainTxState::NotFound),
+            Err(err) => return Err(err),
+        };
+        if matches!(
+            receipt.receipt.finality_status(),
+            TransactionFinalityStatus::PreConfirmed
+        ) {
+            return Ok(ChainTxState::Pending);
+        }
+        let block_number = receipt.block.block_number();
+        Ok(match receipt.receipt.execution_result() {
+            ExecutionResult::Succeeded => ChainTxState::Succeeded { block_number },
+            ExecutionResult::Reverted { reason } => ChainTxState::Reverted {
+                block_number,
+                reason: reason.clone(),
+            },
+        })
+    }
+
+    // Internal helper that marks a row whose hash cannot be checked so it is not resampled.
+    async fn mark_skipped(&self, tx_hash: &str) -> Result<()> {
+        tracing::debug!("Reconciler skipped unparseable tx hash {}", tx_hash);
+        sqlx::query(
+            "UPDATE transactions
+             SET chain_status = 'skipped', reconciled_at = NOW()
+             WHERE tx_hash = $1",
+        )
+        .bind(tx_hash)
+        .execute(self.db.pool())
+        .await?;
+        Ok(())
+    }
+
+    // Internal helper that applies `plan` and reports whether a discrepancy was recorded.
+    async fn apply(
+        &self,
+        tx_hash: &str,
+        db_block_number: i64,
+        plan: &ReconcilePlan,
+    ) -> Result<bool> {
+        let Some(chain_status) = plan.chain_status else {
+            return Ok(false);
+        };
+        sqlx::query(
+            "UPDATE transactions
+             SET chain_status = $2,
+                 block_number = COALESCE($3, block_number),
+                 reconciled_at = NOW()
+             WHERE tx_hash = $1",
+        )
+        .bind(tx_hash)
+        .bind(chain_status)
+        .bind(plan.block_number)
+        .execute(self.db.pool())
+        .await?;
+
+        let Some(issue) = plan.issue else {
+            return Ok(false);
+        };
+        tracing::warn!("Transaction {} reconciliation issue: {}", tx_hash, issue);
+        sqlx::query(
+            "INSERT INTO transaction_reconciliation_issues
+                (tx_hash, issue, db_block_number, chain_block_number, detail)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(tx_hash)
+        .bind(issue)
+        .bind(db_block_number)
+        .bind(plan.block_number)
+        .bind(&plan.detail)
+        .execute(self.db.pool())
+        .await?;
+        Ok(true)
+    }
+}
+
+/// Lists recorded discrepancies, newest first, with the total matching count.
+pub async fn list_issues(
+    db: &Database,
+    issue: Option<&str>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<ReconciliationIssue>, i64)> {
+    let filters = "($1::TEXT IS NULL OR issue = $1)
+           AND ($2::TIMESTAMPTZ IS NULL OR detected_at >= $2)
+           AND ($3::TIMESTAMPTZ IS NULL OR detected_at <= $3)";
+
+    let items = sqlx::query_as::<_, ReconciliationIssue>(&format!(
+        "SELECT id, tx_hash, issue, db_block_number, chain_block_number, detail, detected_at
+         FROM transaction_reconciliation_issues
+         WHERE {}
+         ORDER BY detected_at DESC, id DESC
+         LIMIT $4 OFFSET $5",
+        filters
+    ))
+    .bind(issue)
+    .bind(from)
+    .bind(to)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(db.pool())
+    .await?;
+
+    let total: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM transaction_reconciliation_issues WHERE {}",
+        filters
+    ))
+    .bind(issue)
+    .bind(from)
+    .bind(to)
+    .fetch_one(db.pool())
+    .await?;
+
+    Ok((items, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `plan_reconciliation_backfills_and_flags` operations.
+    fn plan_reconciliation_backfills_and_flags() {
+        let backfill = plan_reconciliation(0, 60, &ChainTxState::Succeeded { block_number: 42 });
+        assert_eq!(backfill.chain_status, Some("succeeded"));
+        assert_eq!(backfill.block_number, Some(42));
+        assert_eq!(backfill.issue, None);
+
+        let mismatch = plan_reconciliation(41, 60, &ChainTxState::Succeeded { block_number: 42 });
+        assert_eq!(mismatch.issue, Some("block_number_mismatch"));
+
+        let reverted = plan_reconciliation(
+            42,
+            60,
+            &ChainTxState::Reverted {
+                block_number: 42,
+                reason: "u256_sub Overflow".to_string(),
+            },
+        );
+        assert_eq!(reverted.chain_status, Some("reverted"));
+        assert_eq!(reverted.block_number, None);
+        assert_eq!(reverted.issue, Some("reverted_on_chain"));
+
+        assert_eq!(
+            plan_reconciliation(0, 60, &ChainTxState::NotFound),
+            ReconcilePlan::default()
+        );
+        assert_eq!(
+            plan_reconciliation(
+                0,
+                TX_RECONCILE_NOT_FOUND_GRACE_SECS,
+                &ChainTxState::NotFound
+            )
+            .issue,
+            Some("missing_on_chain")
+        );
+        assert_eq!(
+            plan_reconciliation(0, 86_000, &ChainTxState::Pending),
+            ReconcilePlan::default()
+        );
+    }
+}
@@ -150,3 +150,66 @@ pub fn claim_fee_multiplier() -> Decimal {
 pub fn bps_to_percent(bps: i64) -> f64 {
     (bps as f64) / 100.0
 }
+
+/// One projected epoch of reward-pool emission.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmissionProjection {
+    pub epoch_offset: i64,
+    pub pool_emission: Decimal,
+    pub claim_fees: Decimal,
+    pub distributed_to_users: Decimal,
+    pub cumulative_emission: Decimal,
+    pub remaining_supply: Decimal,
+}
+
+/// Projects reward-pool emission for the next `epochs` epochs.
+///
+/// # Notes
+/// * Pure simulation: starts from `remaining_supply` and caps each epoch's pool at what is left.
+/// * `distributed_to_users` is the pool net of the claim fee.
+pub fn project_emissions(
+    mode: RewardsDistributionMode,
+    remaining_supply: Decimal,
+    epochs: u32,
+) -> Vec<EmissionProjection> {
+    let pool = rewards_distribution_pool_carel(mode);
+    let net_multiplier = claim_fee_multiplier();
+    let mut remaining = remaining_supply.max(Decimal::ZERO);
+    let mut cumulative = Decimal::ZERO;
+    (0..epochs)
+        .map(|offset| {
+            let emission = pool.min(remaining);
+            let distributed = emission * net_multiplier;
+            remaining -= emission;
+            cumulative += emission;
+            EmissionProjection {
+                epoch_offset: i64::from(offset),
+                pool_emission: emission,
+                claim_fees: emission - distributed,
+                distributed_to_users: distributed,
+                cumulative_emission: cumulative,
+                remaining_supply: remaining,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `project_emissions_caps_at_remaining_supply` operations.
+    fn project_emissions_caps_at_remaining_supply() {
+        let pool = rewards_distribution_pool_carel(RewardsDistributionMode::MainnetMonthly);
+        let start = pool * Decimal::from(2) + Decimal::from(1_000);
+        let out = project_emissions(RewardsDistributionMode::MainnetMonthly, start, 4);
+        assert_eq!(out.len(), 4);
+        assert_eq!(out[0].pool_emission, pool);
+        assert_eq!(out[0].distributed_to_users, pool * claim_fee_multiplier());
+        assert_eq!(out[2].pool_emission, Decimal::from(1_000));
+        assert_eq!(out[3].pool_emission, Decimal::ZERO);
+        assert_eq!(out[3].remaining_supply, Decimal::ZERO);
+        assert_eq!(out[3].cumulative_emission, start);
+    }
+}
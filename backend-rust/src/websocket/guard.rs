@@ -0,0 +1,330 @@
+use axum::{
+    extract::ws::{CloseFrame, Message, WebSocket},
+    http::{header::AUTHORIZATION, HeaderMap},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use tokio::task::JoinHandle;
+use tokio::time::{timeout, Duration, Instant};
+
+use crate::{
+    api::{auth::extract_user_from_token, AppState},
+    config::Config,
+    error::AppError,
+};
+
+/// Close code sent when the socket could not be authenticated.
+pub const WS_CLOSE_UNAUTHORIZED: u16 = 4401;
+/// Close code sent when the user already holds the maximum number of sockets.
+pub const WS_CLOSE_CONNECTION_LIMIT: u16 = 4409;
+/// Close code sent when the client exceeds the inbound message rate.
+pub const WS_CLOSE_RATE_LIMITED: u16 = 4429;
+
+static WS_CONNECTIONS: OnceLock<Mutex<HashMap<String, usize>>> = OnceLock::new();
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct WsAuthQuery {
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WsAuthMessage {
+    #[serde(rename = "type")]
+    msg_type: String,
+    token: String,
+}
+
+// Internal helper that supports `connections` operations.
+fn connections() -> &'static Mutex<HashMap<String, usize>> {
+    WS_CONNECTIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+// Internal helper that supports `token_from_headers` operations.
+fn token_from_headers(headers: &HeaderMap) -> Option<String> {
+    let header_value = headers.get(AUTHORIZATION)?.to_str().ok()?;
+    header_value
+        .strip_prefix("Bearer ")
+        .map(|token| token.to_string())
+}
+
+// Internal helper that parses or transforms values for `parse_auth_message`.
+fn parse_auth_message(text: &str) -> Option<String> {
+    let message = serde_json::from_str::<WsAuthMessage>(text).ok()?;
+    let token = message.token.trim();
+    if message.msg_type != "auth" || token.is_empty() {
+        return None;
+    }
+    Some(token.to_string())
+}
+
+/// Builds a close frame with one of the `WS_CLOSE_*` codes.
+pub fn close_frame(code: u16, reason: &str) -> Message {
+    Message::Close(Some(CloseFrame {
+        code,
+        reason: reason.to_string().into(),
+    }))
+}
+
+/// Holds one slot of the per-user connection cap; the slot is released on drop.
+#[derive(Debug)]
+pub struct ConnectionPermit {
+    user_address: String,
+}
+
+impl ConnectionPermit {
+    /// Attempts to reserve a connection slot for `user_address`.
+    ///
+    /// # Returns
+    /// * `None` when the user already holds `limit` (`WS_MAX_CONNECTIONS_PER_USER`) sockets.
+    pub fn acquire(user_address: &str, limit: usize) -> Option<Self> {
+        let key = user_address.trim().to_ascii_lowercase();
+        let mut guard = connections().lock().ok()?;
+        let count = guard.entry(key.clone()).or_insert(0);
+        if *count >= limit {
+            return None;
+        }
+        *count += 1;
+        Some(Self { user_address: key })
+    }
+}
+
+impl Drop for ConnectionPermit {
+    // Internal helper that supports `drop` operations.
+    fn drop(&mut self) {
+        if let Ok(mut guard) = connections().lock() {
+            if let Some(count) = guard.get_mut(&self.user_address) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    guard.remove(&self.user_address);
+                }
+            }
+        }
+    }
+}
+
+/// Fixed-window limiter for inbound client messages on a single socket.
+#[derive(Debug)]
+pub struct MessageRateLimiter {
+    limit: u32,
+    window: Duration,
+    window_start: Instant,
+    count: u32,
+}
+
+impl MessageRateLimiter {
+    /// Constructs a limiter using `WS_MAX_MESSAGES_PER_MINUTE`.
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.ws_max_messages_per_minute, Duration::from_secs(60))
+    }
+
+    // Internal helper that supports `new` operations.
+    fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            window_start: Instant::now(),
+            count: 0,
+        }
+    }
+
+    /// Records one inbound message and returns false once the window budget is exceeded.
+    pub fn allow(&mut self) -> bool {
+        let now = Instant::now();
+        if now.duration_since(self.window_start) >= self.window {
+            self.window_start = now;
+            self.count = 0;
+        }
+        self.count = self.count.saturating_add(1);
+        self.count <= self.limit
+    }
+}
+
+/// Authenticated WebSocket session shared by all `/ws/*` handlers.
+#[derive(Debug)]
+pub struct WsSession {
+    pub user_address: String,
+    pub limiter: MessageRateLimiter,
+    _permit: ConnectionPermit,
+}
+
+impl WsSession {
+    /// Splits the session into the user address, its rate limiter, and the connection permit.
+    ///
+    /// # Notes
+    /// * The permit must be held for the lifetime of the socket.
+    pub fn into_parts(self) -> (String, MessageRateLimiter, ConnectionPermit) {
+        (self.user_address, self.limiter, self._permit)
+    }
+}
+
+/// Lets the send task flush a pending close frame (when `wait_for_close`) before aborting it.
+pub async fn drain_close<T>(send_task: &mut JoinHandle<T>, wait_for_close: bool) {
+    if wait_for_close {
+        let _ = timeout(Duration::from_secs(1), &mut *send_task).await;
+    }
+    send_task.abort();
+}
+
+/// Validates the JWT supplied before the upgrade (Bearer header or `?token=`).
+///
+/// # Returns
+/// * `Ok(Some(user))` when a valid token was supplied.
+/// * `Ok(None)` when no token was supplied; the socket must then authenticate with its first message.
+/// * `Err(Response)` when a token was supplied but is invalid.
+pub async fn authenticate_upgrade(
+    state: &AppState,
+    headers: &HeaderMap,
+    query: WsAuthQuery,
+) -> std::result::Result<Option<String>, Response> {
+    let Some(token) = token_from_headers(headers).or(query.token) else {
+        return Ok(None);
+    };
+    extract_user_from_token(&token, &state.config.jwt_secret)
+        .await
+        .map(Some)
+        .map_err(|err| err.into_response())
+}
+
+/// Completes authentication on an upgraded socket and reserves a connection slot.
+///
+/// # Arguments
+/// * `pre_authenticated` - user resolved by `authenticate_upgrade`, if any.
+///
+/// # Returns
+/// * `Some(session)` when the socket may proceed.
+/// * `None` after the socket was closed with `WS_CLOSE_UNAUTHORIZED` or `WS_CLOSE_CONNECTION_LIMIT`.
+///
+/// # Notes
+/// * Without a pre-upgrade token the first client frame must be `{"type":"auth","token":"..."}`
+///   and arrive within `WS_AUTH_TIMEOUT_SECS`.
+pub async fn establish_session(
+    socket: &mut WebSocket,
+    state: &AppState,
+    pre_authenticated: Option<String>,
+    channel: &str,
+) -> Option<WsSession> {
+    let user_address = match pre_authenticated {
+        Some(user_address) => user_address,
+        None => match authenticate_first_message(socket, state).await {
+            Ok(user_address) => user_address,
+            Err(err) => {
+                tracing::info!("{} websocket rejected: {}", channel, err);
+                let _ = socket
+                    .send(close_frame(WS_CLOSE_UNAUTHORIZED, "unauthorized"))
+                    .await;
+                return None;
+            }
+        },
+    };
+
+    let Some(permit) =
+        ConnectionPermit::acquire(&user_address, state.config.ws_max_connections_per_user)
+    else {
+        tracing::warn!(
+            "{} websocket connection limit reached for {}",
+            channel,
+            user_address
+        );
+        let _ = socket
+            .send(close_frame(
+                WS_CLOSE_CONNECTION_LIMIT,
+                "too many connections",
+            ))
+            .await;
+        return None;
+    };
+
+    Some(WsSession {
+        user_address,
+        limiter: MessageRateLimiter::from_config(&state.config),
+        _permit: permit,
+    })
+}
+
+// Internal helper that supports `authenticate_first_message` operations.
+async fn authenticate_first_message(
+    socket: &mut WebSocket,
+    state: &AppState,
+) -> std::result::Result<String, AppError> {
+    let auth_timeout = Duration::from_secs(state.config.ws_auth_timeout_secs);
+    let first = timeout(auth_timeout, socket.recv())
+        .await
+        .map_err(|_| AppError::AuthError("WebSocket auth timed out".to_string()))?;
+    let token = match first {
+        Some(Ok(Message::Text(text))) => parse_auth_message(&text),
+        _ => None,
+    }
+    .ok_or_else(|| AppError::AuthError("Missing WebSocket token".to_string()))?;
+    extract_user_from_token(&token, &state.config.jwt_secret).await
+}
+
+/// Updates `last_seen` for the connected user without delaying the socket.
+pub fn spawn_touch_user(state: &AppState, user_address: &str, channel: &'static str) {
+    let db = state.db.clone();
+    let user_address = user_address.to_string();
+    tokio::spawn(async move {
+        match timeout(Duration::from_millis(2500), db.touch_user(&user_address)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                tracing::warn!(
+                    "{} websocket touch_user failed for {}: {}",
+                    channel,
+                    user_address,
+                    err
+                );
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "{} websocket touch_user timed out for {}",
+                    channel,
+                    user_address
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Internal helper that supports `parse_auth_message_requires_auth_type` operations.
+    fn parse_auth_message_requires_auth_type() {
+        assert_eq!(
+            parse_auth_message(r#"{"type":"auth","token":"abc"}"#),
+            Some("abc".to_string())
+        );
+        assert_eq!(
+            parse_auth_message(r#"{"type":"subscribe","token":"abc"}"#),
+            None
+        );
+        assert_eq!(parse_auth_message(r#"{"type":"auth","token":" "}"#), None);
+        assert_eq!(parse_auth_message("not json"), None);
+    }
+
+    #[test]
+    // Internal helper that supports `connection_permit_enforces_cap_and_releases_on_drop` operations.
+    fn connection_permit_enforces_cap_and_releases_on_drop() {
+        let user = "0xWS-PERMIT-TEST";
+        let first = ConnectionPermit::acquire(user, 2).expect("first");
+        let second = ConnectionPermit::acquire(user, 2).expect("second");
+        assert!(ConnectionPermit::acquire(user, 2).is_none());
+        drop(first);
+        let third = ConnectionPermit::acquire(user, 2);
+        assert!(third.is_some());
+        drop(second);
+        drop(third);
+    }
+
+    #[test]
+    // Internal helper that supports `message_rate_limiter_blocks_after_budget` operations.
+    fn message_rate_limiter_blocks_after_budget() {
+        let mut limiter = MessageRateLimiter::new(2, Duration::from_secs(60));
+        assert!(limiter.allow());
+        assert!(limiter.allow());
+        assert!(!limiter.allow());
+    }
+}
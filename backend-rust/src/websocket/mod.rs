@@ -1,3 +1,4 @@
+pub mod guard;
 pub mod notifications;
 pub mod orders;
 pub mod prices;
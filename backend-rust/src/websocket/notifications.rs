@@ -3,31 +3,19 @@ use axum::{
         ws::{Message, WebSocket, WebSocketUpgrade},
         Query, State,
     },
-    http::{header::AUTHORIZATION, HeaderMap},
-    response::{IntoResponse, Response},
+    http::HeaderMap,
+    response::Response,
 };
 use futures_util::{SinkExt, StreamExt};
-use serde::Deserialize;
+use tokio::sync::oneshot;
 use tokio::time::{interval, timeout, Duration};
 
 use crate::{
-    api::{auth::extract_user_from_token, AppState},
+    api::AppState,
     constants::{WS_CLIENT_TIMEOUT_SECS, WS_HEARTBEAT_INTERVAL_SECS},
-    error::AppError,
 };
 
-#[derive(Debug, Deserialize)]
-pub(crate) struct WsAuthQuery {
-    token: Option<String>,
-}
-
-// Internal helper that supports `token_from_headers` operations.
-fn token_from_headers(headers: &HeaderMap) -> Option<String> {
-    let header_value = headers.get(AUTHORIZATION)?.to_str().ok()?;
-    header_value
-        .strip_prefix("Bearer ")
-        .map(|token| token.to_string())
-}
+use super::guard::{self, WsAuthQuery, WsSession};
 
 // Internal helper that supports `connected_payload` operations.
 fn connected_payload() -> String {
@@ -45,49 +33,27 @@ pub async fn handler(
     headers: HeaderMap,
     Query(query): Query<WsAuthQuery>,
 ) -> Response {
-    let token = token_from_headers(&headers).or(query.token);
-    let token = match token {
-        Some(token) => token,
-        None => return AppError::AuthError("Missing WebSocket token".to_string()).into_response(),
-    };
-
-    let user_address = match extract_user_from_token(&token, &state.config.jwt_secret).await {
-        Ok(address) => address,
-        Err(err) => return err.into_response(),
+    let pre_authenticated = match guard::authenticate_upgrade(&state, &headers, query).await {
+        Ok(user_address) => user_address,
+        Err(response) => return response,
     };
 
-    let db = state.db.clone();
-    let user_address_for_touch = user_address.clone();
-    tokio::spawn(async move {
-        match timeout(
-            Duration::from_millis(2500),
-            db.touch_user(&user_address_for_touch),
-        )
-        .await
-        {
-            Ok(Ok(())) => {}
-            Ok(Err(err)) => {
-                tracing::warn!(
-                    "notifications websocket touch_user failed for {}: {}",
-                    user_address_for_touch,
-                    err
-                );
-            }
-            Err(_) => {
-                tracing::warn!(
-                    "notifications websocket touch_user timed out for {}",
-                    user_address_for_touch
-                );
-            }
-        }
-    });
-
-    ws.on_upgrade(|socket| handle_socket(socket, state, user_address))
+    ws.on_upgrade(move |mut socket| async move {
+        let Some(session) =
+            guard::establish_session(&mut socket, &state, pre_authenticated, "notifications").await
+        else {
+            return;
+        };
+        guard::spawn_touch_user(&state, &session.user_address, "notifications");
+        handle_socket(socket, state, session).await
+    })
 }
 
 // Internal helper that supports `handle_socket` operations.
-async fn handle_socket(socket: WebSocket, state: AppState, user_address: String) {
+async fn handle_socket(socket: WebSocket, state: AppState, session: WsSession) {
+    let (user_address, mut limiter, _permit) = session.into_parts();
     let (mut sender, mut receiver) = socket.split();
+    let (close_tx, mut close_rx) = oneshot::channel::<Message>();
 
     // Subscribe to notifications
     let notification_service =
@@ -106,6 +72,12 @@ async fn handle_socket(socket: WebSocket, state: AppState, user_address: String)
 
         loop {
             tokio::select! {
+                frame = &mut close_rx => {
+                    if let Ok(frame) = frame {
+                        let _ = sender.send(frame).await;
+                    }
+                    break;
+                }
                 _ = heartbeat.tick() => {
                     if sender.send(Message::Ping(Vec::new().into())).await.is_err() {
                         break;
@@ -140,6 +112,15 @@ async fn handle_socket(socket: WebSocket, state: AppState, user_address: String)
                 }
             };
 
+            if matches!(msg, Message::Text(_) | Message::Binary(_)) && !limiter.allow() {
+                tracing::warn!("Notification client exceeded message rate limit");
+                let _ = close_tx.send(guard::close_frame(
+                    guard::WS_CLOSE_RATE_LIMITED,
+                    "message rate limit exceeded",
+                ));
+                return true;
+            }
+
             match msg {
                 Message::Text(text) => {
                     tracing::debug!("Received: {}", text);
@@ -154,14 +135,15 @@ async fn handle_socket(socket: WebSocket, state: AppState, user_address: String)
                 _ => {}
             }
         }
+        false
     });
 
     tokio::select! {
         _ = &mut send_task => {
             recv_task.abort();
         }
-        _ = &mut recv_task => {
-            send_task.abort();
+        rate_limited = &mut recv_task => {
+            guard::drain_close(&mut send_task, matches!(rate_limited, Ok(true))).await;
         }
     }
 
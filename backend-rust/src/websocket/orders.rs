@@ -3,30 +3,22 @@ use axum::{
         ws::{Message, WebSocket, WebSocketUpgrade},
         Query, State,
     },
-    http::{header::AUTHORIZATION, HeaderMap},
-    response::{IntoResponse, Response},
+    http::HeaderMap,
+    response::Response,
 };
 use futures_util::{SinkExt, StreamExt};
 use serde::Serialize;
-use tokio::time::{timeout, Duration};
+use tokio::sync::{broadcast, oneshot};
+use tokio::time::{interval, timeout, Duration};
 
 use crate::{
-    api::{auth::extract_user_from_token, AppState},
-    error::AppError,
+    api::AppState,
+    constants::{WS_CLIENT_TIMEOUT_SECS, WS_HEARTBEAT_INTERVAL_SECS},
+    models::LimitOrder,
+    services::order_events::{self, OrderEvent},
 };
 
-#[derive(Debug, serde::Deserialize)]
-pub(crate) struct WsAuthQuery {
-    token: Option<String>,
-}
-
-// Internal helper that supports `token_from_headers` operations.
-fn token_from_headers(headers: &HeaderMap) -> Option<String> {
-    let header_value = headers.get(AUTHORIZATION)?.to_str().ok()?;
-    header_value
-        .strip_prefix("Bearer ")
-        .map(|token| token.to_string())
-}
+use super::guard::{self, WsAuthQuery, WsSession};
 
 #[derive(Debug, Serialize)]
 struct OrderUpdate {
@@ -47,18 +39,6 @@ fn connected_payload() -> String {
     .to_string()
 }
 
-// Internal helper that supports `status_label` operations.
-fn status_label(status: i16) -> &'static str {
-    match status {
-        0 => "active",
-        1 => "partially_filled",
-        2 => "filled",
-        3 => "cancelled",
-        4 => "expired",
-        _ => "unknown",
-    }
-}
-
 /// WebSocket handler for limit order updates
 /// GET /ws/orders
 pub async fn handler(
@@ -67,85 +47,124 @@ pub async fn handler(
     headers: HeaderMap,
     Query(query): Query<WsAuthQuery>,
 ) -> Response {
-    let token = token_from_headers(&headers).or(query.token);
-    let token = match token {
-        Some(token) => token,
-        None => return AppError::AuthError("Missing WebSocket token".to_string()).into_response(),
+    let pre_authenticated = match guard::authenticate_upgrade(&state, &headers, query).await {
+        Ok(user_address) => user_address,
+        Err(response) => return response,
     };
 
-    let user_address = match extract_user_from_token(&token, &state.config.jwt_secret).await {
-        Ok(address) => address,
-        Err(err) => return err.into_response(),
+    ws.on_upgrade(move |mut socket| async move {
+        let Some(session) =
+            guard::establish_session(&mut socket, &state, pre_authenticated, "orders").await
+        else {
+            return;
+        };
+        guard::spawn_touch_user(&state, &session.user_address, "orders");
+        handle_socket(socket, state, session).await
+    })
+}
+
+// Internal helper that builds the `order_update` payload for an order event.
+fn order_update_payload(event: &OrderEvent) -> String {
+    let update = OrderUpdate {
+        msg_type: "order_update".to_string(),
+        order_id: event.order_id.clone(),
+        status: event.status.as_str().to_string(),
+        filled: event.filled.clone(),
+        timestamp: event.timestamp,
     };
+    serde_json::to_string(&update).unwrap_or_default()
+}
 
-    let db = state.db.clone();
-    let user_address_for_touch = user_address.clone();
-    tokio::spawn(async move {
-        match timeout(
-            Duration::from_millis(2500),
-            db.touch_user(&user_address_for_touch),
-        )
-        .await
-        {
-            Ok(Ok(())) => {}
-            Ok(Err(err)) => {
-                tracing::warn!(
-                    "orders websocket touch_user failed for {}: {}",
-                    user_address_for_touch,
-                    err
-                );
-            }
-            Err(_) => {
-                tracing::warn!(
-                    "orders websocket touch_user timed out for {}",
-                    user_address_for_touch
-                );
-            }
-        }
-    });
+// Internal helper that builds the active-order snapshot sent on (re)connect or after lag.
+fn order_snapshot_payload(orders: Vec<LimitOrder>) -> String {
+    let timestamp = chrono::Utc::now().timestamp();
+    let orders: Vec<OrderUpdate> = orders
+        .into_iter()
+        .map(|order| OrderUpdate {
+            msg_type: "order_update".to_string(),
+            order_id: order.order_id,
+            status: order.status.as_str().to_string(),
+            filled: order.filled.to_string(),
+            timestamp,
+        })
+        .collect();
+    serde_json::json!({
+        "type": "order_snapshot",
+        "orders": orders,
+        "timestamp": timestamp,
+    })
+    .to_string()
+}
 
-    ws.on_upgrade(|socket| handle_socket(socket, state, user_address))
+// Internal helper that loads the active-order snapshot for `owner`.
+async fn load_snapshot(state: &AppState, owner: &str) -> Option<String> {
+    match state.db.get_active_orders_for_owner(owner).await {
+        Ok(orders) => Some(order_snapshot_payload(orders)),
+        Err(err) => {
+            tracing::warn!("orders websocket snapshot failed for {}: {}", owner, err);
+            None
+        }
+    }
 }
 
 // Internal helper that supports `handle_socket` operations.
-async fn handle_socket(socket: WebSocket, state: AppState, user_address: String) {
+async fn handle_socket(socket: WebSocket, state: AppState, session: WsSession) {
+    let (user_address, mut limiter, _permit) = session.into_parts();
     let (mut sender, mut receiver) = socket.split();
+    let (close_tx, mut close_rx) = oneshot::channel::<Message>();
+
+    // Subscribe before reading the snapshot so no event between the two is lost.
+    let mut events = order_events::subscribe();
 
     // Perbaikan: Tambahkan .into() untuk menyambut koneksi
     let _ = sender.send(Message::Text(connected_payload().into())).await;
 
-    // Spawn task to send order updates
+    // Spawn task to send the snapshot followed by live order events
     let state_clone = state.clone();
     let owner_address = user_address.clone();
     let mut send_task = tokio::spawn(async move {
-        loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
-
-            // Get user's active orders
-            let orders = match state_clone
-                .db
-                .get_active_orders_for_owner(&owner_address)
-                .await
-            {
-                Ok(orders) => orders,
-                Err(_) => continue,
-            };
+        if let Some(snapshot) = load_snapshot(&state_clone, &owner_address).await {
+            if sender.send(Message::Text(snapshot.into())).await.is_err() {
+                return;
+            }
+        }
 
-            // Send updates for each order
-            for order in orders {
-                let update = OrderUpdate {
-                    msg_type: "order_update".to_string(),
-                    order_id: order.order_id,
-                    status: status_label(order.status).to_string(),
-                    filled: order.filled.to_string(),
-                    timestamp: chrono::Utc::now().timestamp(),
-                };
-
-                let json = serde_json::to_string(&update).unwrap_or_default();
-
-                // Perbaikan: Tambahkan .into() di sini juga
-                if sender.send(Message::Text(json.into())).await.is_err() {
-                    return;
+        let mut heartbeat = interval(Duration::from_secs(WS_HEARTBEAT_INTERVAL_SECS));
+        loop {
+            tokio::select! {
+                frame = &mut close_rx => {
+                    if let Ok(frame) = frame {
+                        let _ = sender.send(frame).await;
+                    }
+                    break;
+                }
+                _ = heartbeat.tick() => {
+                    if sender.send(Message::Ping(Vec::new().into())).await.is_err() {
+                        break;
+                    }
+                }
+                result = events.recv() => {
+                    let payload = match result {
+                        Ok(event) if event.belongs_to(&owner_address) => {
+                            order_update_payload(&event)
+                        }
+                        Ok(_) => continue,
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!(
+                                "orders websocket lagged {} events for {}, resending snapshot",
+                                skipped,
+                                owner_address
+                            );
+                            match load_snapshot(&state_clone, &owner_address).await {
+                                Some(snapshot) => snapshot,
+                                None => continue,
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    };
+                    if sender.send(Message::Text(payload.into())).await.is_err() {
+                        break;
+                    }
                 }
             }
         }
@@ -153,7 +172,25 @@ async fn handle_socket(socket: WebSocket, state: AppState, user_address: String)
 
     // Handle incoming messages (ping/pong)
     let mut recv_task = tokio::spawn(async move {
-        while let Some(Ok(msg)) = receiver.next().await {
+        loop {
+            let next_msg =
+                timeout(Duration::from_secs(WS_CLIENT_TIMEOUT_SECS), receiver.next()).await;
+            let msg = match next_msg {
+                Ok(Some(Ok(msg))) => msg,
+                Ok(Some(Err(_))) | Ok(None) => break,
+                Err(_) => {
+                    tracing::info!("Order stream client timeout");
+                    break;
+                }
+            };
+            if matches!(msg, Message::Text(_) | Message::Binary(_)) && !limiter.allow() {
+                tracing::warn!("Order stream client exceeded message rate limit");
+                let _ = close_tx.send(guard::close_frame(
+                    guard::WS_CLOSE_RATE_LIMITED,
+                    "message rate limit exceeded",
+                ));
+                return true;
+            }
             match msg {
                 Message::Close(_) => {
                     tracing::info!("Order stream client disconnected");
@@ -165,6 +202,7 @@ async fn handle_socket(socket: WebSocket, state: AppState, user_address: String)
                 _ => {}
             }
         }
+        false
     });
 
     // Wait for either task to finish
@@ -172,23 +210,50 @@ async fn handle_socket(socket: WebSocket, state: AppState, user_address: String)
         _ = &mut send_task => {
             recv_task.abort();
         }
-        _ = &mut recv_task => {
-            send_task.abort();
+        rate_limited = &mut recv_task => {
+            guard::drain_close(&mut send_task, matches!(rate_limited, Ok(true))).await;
         }
     }
 
-    tracing::info!("Order WebSocket connection closed");
+    tracing::info!(
+        "Order WebSocket connection closed for user: {}",
+        user_address
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::OrderStatus;
 
     #[test]
     // Internal helper that supports `status_label_maps_known_values` operations.
     fn status_label_maps_known_values() {
         // Memastikan status order terjemah sesuai kode
-        assert_eq!(status_label(2), "filled");
-        assert_eq!(status_label(9), "unknown");
+        assert_eq!(OrderStatus::from(2).as_str(), "filled");
+        assert_eq!(OrderStatus::from(9).as_str(), "unknown");
+    }
+
+    #[test]
+    // Internal helper that supports `order_update_payload_uses_status_label` operations.
+    fn order_update_payload_uses_status_label() {
+        let event = OrderEvent {
+            order_id: "0x1".to_string(),
+            owner: "0xabc".to_string(),
+            status: OrderStatus::Cancelled,
+            filled: "0".to_string(),
+            timestamp: 42,
+        };
+        let payload = order_update_payload(&event);
+        assert!(payload.contains("\"type\":\"order_update\""));
+        assert!(payload.contains("\"status\":\"cancelled\""));
+    }
+
+    #[test]
+    // Internal helper that supports `order_snapshot_payload_wraps_orders` operations.
+    fn order_snapshot_payload_wraps_orders() {
+        let payload = order_snapshot_payload(Vec::new());
+        assert!(payload.contains("\"type\":\"order_snapshot\""));
+        assert!(payload.contains("\"orders\":[]"));
     }
 }
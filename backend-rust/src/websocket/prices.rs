@@ -1,17 +1,20 @@
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        Query, State,
     },
+    http::HeaderMap,
     response::Response,
 };
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{oneshot, RwLock};
 
 use crate::api::AppState;
 
+use super::guard::{self, WsAuthQuery, WsSession};
+
 // Internal helper that supports `connected_payload` operations.
 fn connected_payload() -> String {
     serde_json::json!({
@@ -39,13 +42,32 @@ struct PriceUpdate {
 }
 
 /// WebSocket handler for real-time price updates
-pub async fn handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
-    ws.on_upgrade(|socket| handle_socket(socket, state))
+pub async fn handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<WsAuthQuery>,
+) -> Response {
+    let pre_authenticated = match guard::authenticate_upgrade(&state, &headers, query).await {
+        Ok(user_address) => user_address,
+        Err(response) => return response,
+    };
+
+    ws.on_upgrade(move |mut socket| async move {
+        let Some(session) =
+            guard::establish_session(&mut socket, &state, pre_authenticated, "prices").await
+        else {
+            return;
+        };
+        handle_socket(socket, state, session).await
+    })
 }
 
 // Internal helper that supports `handle_socket` operations.
-async fn handle_socket(socket: WebSocket, state: AppState) {
+async fn handle_socket(socket: WebSocket, state: AppState, session: WsSession) {
+    let (user_address, mut limiter, _permit) = session.into_parts();
     let (mut sender, mut receiver) = socket.split();
+    let (close_tx, mut close_rx) = oneshot::channel::<Message>();
 
     // Track subscribed tokens
     let subscribed_tokens: Arc<RwLock<Vec<String>>> = Arc::new(RwLock::new(Vec::new()));
@@ -58,7 +80,15 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     let state_clone = state.clone();
     let mut send_task = tokio::spawn(async move {
         loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+            tokio::select! {
+                frame = &mut close_rx => {
+                    if let Ok(frame) = frame {
+                        let _ = sender.send(frame).await;
+                    }
+                    return;
+                }
+                _ = tokio::time::sleep(tokio::time::Duration::from_secs(5)) => {}
+            }
 
             let tokens = subscribed_clone.read().await.clone();
 
@@ -90,6 +120,14 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
     // Handle incoming messages
     let mut recv_task = tokio::spawn(async move {
         while let Some(Ok(msg)) = receiver.next().await {
+            if matches!(msg, Message::Text(_) | Message::Binary(_)) && !limiter.allow() {
+                tracing::warn!("Price stream client exceeded message rate limit");
+                let _ = close_tx.send(guard::close_frame(
+                    guard::WS_CLOSE_RATE_LIMITED,
+                    "message rate limit exceeded",
+                ));
+                return true;
+            }
             match msg {
                 Message::Text(text) => {
                     // text di sini sudah bertipe Utf8Bytes, bisa langsung digunakan atau di-convert
@@ -112,18 +150,22 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
                 _ => {}
             }
         }
+        false
     });
 
     tokio::select! {
         _ = &mut send_task => {
             recv_task.abort();
         }
-        _ = &mut recv_task => {
-            send_task.abort();
+        rate_limited = &mut recv_task => {
+            guard::drain_close(&mut send_task, matches!(rate_limited, Ok(true))).await;
         }
     }
 
-    tracing::info!("Price WebSocket connection closed");
+    tracing::info!(
+        "Price WebSocket connection closed for user: {}",
+        user_address
+    );
 }
 
 // Internal helper that supports `fallback_price_for` operations.
@@ -557,16 +557,16 @@ function formatExecutionFailureMessage(rawMessage: string, command: string): str
   if (/pool belum didukung untuk on-chain staking/i.test(lowerRaw)) {
     return "This staking pool is not supported for on-chain staking."
   }
-  if (/hide balance v3 note belum terdaftar/i.test(lowerRaw)) {
+  if (/hide balance v3 note belum terdaftar|note is not registered yet/i.test(lowerRaw)) {
     return "Hide Balance V3 note is not registered yet. Deposit the note first."
   }
-  if (/shieldedpoolv3 root belum diinisialisasi/i.test(lowerRaw)) {
+  if (/shieldedpoolv3 root belum diinisialisasi|root is not initialized yet/i.test(lowerRaw)) {
     return "ShieldedPoolV3 root is not initialized yet (get_root=0)."
   }
-  if (/swap real token belum aktif/i.test(lowerRaw)) {
+  if (/swap real token belum aktif|swap contract only emits events/i.test(lowerRaw)) {
     return "Real-token swap is not active yet. The configured swap contract is still event-only. Activate an on-chain swap router that moves real tokens, then retry."
   }
-  if (/deposit note baru ke v2 diblok|gunakan v3 untuk note baru/i.test(lowerRaw)) {
+  if (/deposit note baru ke v2 diblok|gunakan v3 untuk note baru|new note deposits to v2 are blocked/i.test(lowerRaw)) {
     return "Hide Balance V2 is redeem-only. New note deposits to V2 are blocked; use V3 for new notes."
   }
   if (
@@ -1081,9 +1081,9 @@ export function LimitOrder() {
           price: String(order.price),
           expiry: order.expiry,
           status:
-            order.status === 2
+            order.status === "filled"
               ? "filled"
-              : order.status === 3 || order.status === 4
+              : order.status === "cancelled" || order.status === "expired"
               ? "cancelled"
               : "active",
           createdAt: formatDateTime(order.created_at),
@@ -1113,9 +1113,9 @@ export function LimitOrder() {
             price: String(order.price),
             expiry: order.expiry,
             status:
-              order.status === 2
+              order.status === "filled"
                 ? "filled"
-                : order.status === 3 || order.status === 4
+                : order.status === "cancelled" || order.status === "expired"
                 ? "cancelled"
                 : "active",
             createdAt: formatDateTime(order.created_at),
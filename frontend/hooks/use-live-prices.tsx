@@ -2,6 +2,7 @@
 
 import * as React from "react"
 import { usePriceStream } from "@/hooks/use-price-stream"
+import { useWallet } from "@/hooks/use-wallet"
 import {
   DEFAULT_COINGECKO_IDS,
   DEFAULT_FALLBACK_PRICES,
@@ -182,8 +183,14 @@ export function useLivePrices(tokens: string[], options: UseLivePricesOptions =
 
   const lastWsUpdateRef = React.useRef<Record<string, number>>({})
 
+  const wallet = useWallet()
+  const sessionToken = typeof window !== "undefined"
+    ? wallet.token || window.localStorage.getItem("auth_token")
+    : wallet.token
+
   const { prices: wsPrices, changes: wsChanges, status: wsStatus } = usePriceStream(uniqueTokens, {
     enabled,
+    token: sessionToken,
   })
 
   React.useEffect(() => {
@@ -53,6 +53,10 @@ export function useOrderUpdates(token?: string | null, options: UseOrderUpdatesO
         const payload = JSON.parse(event.data)
         if (payload?.type === "order_update") {
           onUpdateRef.current?.(payload as OrderUpdate)
+        } else if (payload?.type === "order_snapshot" && Array.isArray(payload.orders)) {
+          for (const order of payload.orders as OrderUpdate[]) {
+            onUpdateRef.current?.(order)
+          }
         }
       } catch {
         // ignore invalid payloads
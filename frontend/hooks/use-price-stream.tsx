@@ -14,6 +14,7 @@ export type PriceUpdate = {
 
 type UsePriceStreamOptions = {
   enabled?: boolean
+  token?: string | null
 }
 
 /**
@@ -34,13 +35,13 @@ export function usePriceStream(tokens: string[], options: UsePriceStreamOptions
     [tokens]
   )
 
-  const wsUrl = uniqueTokens.length
-    ? `${WS_BASE_URL.replace(/\/$/, "")}/ws/prices`
+  const wsUrl = uniqueTokens.length && options.token
+    ? `${WS_BASE_URL.replace(/\/$/, "")}/ws/prices?token=${encodeURIComponent(options.token)}`
     : null
 
   const { status, send } = useWebSocket({
     url: wsUrl,
-    enabled: options.enabled !== false && uniqueTokens.length > 0,
+    enabled: options.enabled !== false && uniqueTokens.length > 0 && Boolean(options.token),
     onOpen: () => {
       emitEvent("ws:status", { channel: "prices", status: "connected" })
       send(
@@ -43,7 +43,9 @@ export interface PaginatedResponse<T> {
   items: T[]
   page: number
   limit: number
+  offset: number
   total: number
+  has_more: boolean
 }
 
 export interface BackendNotification {
@@ -80,6 +82,8 @@ export interface AnalyticsResponse {
       percentage: number
       value_usd: NumericLike
     }>
+    downsampled?: boolean
+    original_count?: number
   }
   trading: {
     total_trades: number
@@ -111,6 +115,13 @@ export interface LeaderboardResponse {
   total_users: number
 }
 
+export interface PointsRankingsResponse {
+  view: "live" | "finalized"
+  epoch: number
+  entries: LeaderboardEntry[]
+  total_users: number
+}
+
 export interface SwapQuoteResponse {
   from_amount: string
   to_amount: string
@@ -122,6 +133,7 @@ export interface SwapQuoteResponse {
   estimated_gas: string
   estimated_time: string
   onchain_calls?: StarknetWalletCall[]
+  estimated_points_earned: string
 }
 
 export interface StarknetWalletCall {
@@ -130,6 +142,11 @@ export interface StarknetWalletCall {
   calldata: string[]
 }
 
+export interface DiscountSource {
+  source: "nft" | "staked_carel" | string
+  percent: number
+}
+
 export interface ExecuteSwapResponse {
   tx_hash: string
   status: string
@@ -140,6 +157,9 @@ export interface ExecuteSwapResponse {
   fee_before_discount: string
   fee_discount_saved: string
   nft_discount_percent: string
+  stake_discount_percent?: string
+  fee_discount_percent?: string
+  discount_sources?: DiscountSource[]
   estimated_points_earned: string
   points_pending: boolean
   privacy_tx_hash?: string
@@ -167,6 +187,7 @@ export interface ExecuteBridgeResponse {
   // Optional for backward compatibility when frontend talks to older backend payloads.
   fee_discount_saved?: string
   nft_discount_percent?: string
+  fee_discount_percent?: string
   estimated_points_earned?: string
   points_pending?: boolean
   ai_level_points_bonus_percent?: string
@@ -216,6 +237,17 @@ export interface GardenStringMetricResponse {
   error?: string | null
 }
 
+export interface GardenInstantRefundResponse {
+  result: string | null
+  order_id: string
+  refund_status: "completed" | "refunded" | "not_initiated" | "locked" | "claimable" | "unknown"
+  refund_eligible: boolean | null
+  source_chain: string | null
+  current_block: number | null
+  claimable_at_block: number | null
+  claimable_at: number | null
+}
+
 export interface GardenListResponse<T = unknown> {
   status: "Ok" | "Error"
   result: T[]
@@ -301,6 +333,15 @@ export interface ReferralHistoryItem {
   timestamp: string
 }
 
+export interface ReferralPayout {
+  tx_hash: string
+  epoch: number
+  amount_points: number
+  offchain_referral_points: number
+  block_number: number
+  settled_at: string
+}
+
 export interface LimitOrderResponse {
   order_id: string
   status: string
@@ -331,8 +372,10 @@ export interface LimitOrderItem {
   price: NumericLike
   expiry: string
   recipient: string | null
-  status: number
+  status: "active" | "partially_filled" | "filled" | "cancelled" | "expired" | "unknown"
   created_at: string
+  order_type: "limit" | "stop_loss" | "take_profit"
+  trigger_price: NumericLike | null
 }
 
 export interface BattleshipCell {
@@ -476,6 +519,26 @@ export interface AiUpgradeLevelResponse {
   block_number: number
 }
 
+export interface AiLevelBenefits {
+  level: number
+  total_cost_carel: string
+  points_bonus_percent: number
+  rate_limit_per_window: number
+  features: string[]
+}
+
+export interface AiLevelsResponse {
+  rate_limit_window_seconds: number
+  global_rate_limit_per_window: number
+  levels: AiLevelBenefits[]
+}
+
+export interface AiDowngradeLevelResponse {
+  previous_level: number
+  current_level: number
+  refund_carel: string
+}
+
 export interface PrivacySubmitResponse {
   tx_hash: string
 }
@@ -616,6 +679,8 @@ export interface PortfolioHistoryResponse {
   total_value: PortfolioHistoryPoint[]
   pnl: number
   pnl_percentage: number
+  downsampled?: boolean
+  original_count?: number
 }
 
 export interface PortfolioOHLCVPoint {
@@ -646,6 +711,9 @@ export interface Transaction {
   points_earned?: string | number | null
   timestamp: string
   processed: boolean
+  points_status?: "pending" | "retrying" | "failed" | "processed"
+  processing_attempts?: number
+  processing_error?: string | null
 }
 
 export interface LeaderboardUserRank {
@@ -663,8 +731,20 @@ export interface LeaderboardUserCategory {
   value: number
 }
 
+export interface UserAchievementProgress {
+  key: string
+  name: string
+  metric: string
+  threshold: number
+  progress: number
+  progress_pct: number
+  earned: boolean
+  earned_at?: string | null
+}
+
 export interface LeaderboardUserCategoriesResponse {
   categories: LeaderboardUserCategory[]
+  achievements?: UserAchievementProgress[]
 }
 
 export interface OrderBookLevel {
@@ -804,6 +884,8 @@ let stakePoolsInFlight: Promise<StakingPool[]> | null = null
 let stakePoolsCache: TimedCacheEntry<StakingPool[]> | null = null
 const limitOrdersInFlight = new Map<string, Promise<PaginatedResponse<LimitOrderItem>>>()
 const limitOrdersCache = new Map<string, TimedCacheEntry<PaginatedResponse<LimitOrderItem>>>()
+const OWNED_NFTS_PAGE_LIMIT = 100
+const OWNED_NFTS_MAX_PAGES = 50
 let ownedNftsInFlight: Promise<NFTItem[]> | null = null
 let ownedNftsCache: TimedCacheEntry<NFTItem[]> | null = null
 const onchainBalancesInFlight = new Map<string, Promise<OnchainBalancesResponse>>()
@@ -1197,6 +1279,43 @@ export async function getHealth() {
   return apiFetch<{ status: string; version: string; database: string; redis: string }>("/health")
 }
 
+export interface FeatureConfig {
+  network: "testnet" | "mainnet"
+  features: {
+    event_indexer: boolean
+    btc_bridge_watcher: boolean
+    htlc_refund_monitor: boolean
+    hide_balance: boolean
+    hide_balance_relayer_pool: boolean
+    hide_balance_strict_privacy: boolean
+    faucet: boolean
+    snapshot_export: boolean
+  }
+  params: {
+    supported_tokens: string[]
+    min_swap_usd_for_points: number
+    max_deadline_seconds: number
+    display_amount_max_decimals: number
+    hide_balance_executor: string
+    hide_balance_min_note_age_secs: number
+    hide_balance_max_uses_per_day: number
+    faucet_tokens: string[]
+    deposit_base_token: string
+    dark_pool_min_notional_usd: number
+    dark_pool_max_notional_usd: number
+  }
+}
+
+/**
+ * Runs `getFeatureConfig` as part of the frontend API client workflow.
+ *
+ * @returns Result used by UI state, request lifecycle, or callback chaining.
+ * @remarks May trigger Hide Mode payload handling, network calls, or local state updates.
+ */
+export async function getFeatureConfig() {
+  return apiFetch<FeatureConfig>("/api/v1/config/features")
+}
+
 /**
  * Runs `connectWallet` as part of the frontend API client workflow.
  *
@@ -1451,6 +1570,34 @@ export async function getLeaderboard(
   return request
 }
 
+/**
+ * Runs `getLivePointsRankings` as part of the frontend API client workflow.
+ *
+ * @returns Result used by UI state, request lifecycle, or callback chaining.
+ * @remarks May trigger Hide Mode payload handling, network calls, or local state updates.
+ */
+export async function getLivePointsRankings() {
+  return apiFetch<PointsRankingsResponse>("/api/v1/leaderboard/points/live", {
+    timeoutMs: SLOW_READ_TIMEOUT_MS,
+    suppressErrorNotification: true,
+  })
+}
+
+/**
+ * Runs `getFinalizedPointsRankings` as part of the frontend API client workflow.
+ *
+ * @param epoch - Input used to compute or dispatch the `getFinalizedPointsRankings` operation.
+ *
+ * @returns Result used by UI state, request lifecycle, or callback chaining.
+ * @remarks May trigger Hide Mode payload handling, network calls, or local state updates.
+ */
+export async function getFinalizedPointsRankings(epoch: number) {
+  return apiFetch<PointsRankingsResponse>(`/api/v1/leaderboard/points/finalized/${epoch}`, {
+    timeoutMs: SLOW_READ_TIMEOUT_MS,
+    suppressErrorNotification: true,
+  })
+}
+
 /**
  * Runs `getLeaderboardGlobalMetrics` as part of the frontend API client workflow.
  *
@@ -1618,7 +1765,7 @@ export async function getReferralStats() {
  * @remarks May trigger Hide Mode payload handling, network calls, or local state updates.
  */
 export async function getReferralHistory(page = 1, limit = 10) {
-  return apiFetch<PaginatedResponse<ReferralHistoryItem>>(
+  return apiFetch<PaginatedResponse<ReferralHistoryItem> & { payouts?: ReferralPayout[] }>(
     `/api/v1/referral/history?page=${page}&limit=${limit}`
   )
 }
@@ -1751,10 +1898,14 @@ export async function executeBridge(payload: {
   mode?: string
   hide_balance?: boolean
   privacy?: PrivacyVerificationPayload
+  deadline?: number
 }) {
   return apiFetch<ExecuteBridgeResponse>("/api/v1/bridge/execute", {
     method: "POST",
-    body: JSON.stringify(payload),
+    body: JSON.stringify({
+      ...payload,
+      deadline: payload.deadline ?? Math.floor(Date.now() / 1000) + 60 * 20,
+    }),
     context: "Bridge execute",
     suppressErrorNotification: true,
     timeoutMs: 120000,
@@ -1913,7 +2064,7 @@ export async function getGardenOrderById(orderId: string) {
  * @remarks May trigger Hide Mode payload handling, network calls, or local state updates.
  */
 export async function getGardenOrderInstantRefundHash(orderId: string) {
-  return apiFetch<GardenStringMetricResponse>(
+  return apiFetch<GardenInstantRefundResponse>(
     `/api/v1/garden/orders/${encodeURIComponent(orderId)}/instant-refund-hash`
   )
 }
@@ -1998,6 +2149,8 @@ export async function createLimitOrder(payload: {
   price: string
   expiry: string
   recipient?: string | null
+  order_type?: "limit" | "stop_loss" | "take_profit"
+  trigger_price?: string
   client_order_id?: string
   onchain_tx_hash?: string
   hide_balance?: boolean
@@ -2175,9 +2328,18 @@ export async function getOwnedNfts(options?: { force?: boolean }) {
   if (!force && ownedNftsInFlight) {
     return ownedNftsInFlight
   }
-  ownedNftsInFlight = apiFetch<NFTItem[]>("/api/v1/nft/owned", {
-    timeoutMs: 25000,
-  })
+  ownedNftsInFlight = (async () => {
+    const data: NFTItem[] = []
+    for (let page = 1; page <= OWNED_NFTS_MAX_PAGES; page += 1) {
+      const result = await apiFetch<PaginatedResponse<NFTItem>>(
+        `/api/v1/nft/owned?page=${page}&limit=${OWNED_NFTS_PAGE_LIMIT}`,
+        { timeoutMs: 25000 }
+      )
+      data.push(...result.items)
+      if (!result.has_more) break
+    }
+    return data
+  })()
     .then((data) => {
       ownedNftsCache = {
         data,
@@ -2417,6 +2579,19 @@ export async function getAiLevel() {
   })
 }
 
+/**
+ * Runs `getAiLevels` as part of the frontend API client workflow.
+ *
+ * @returns Result used by UI state, request lifecycle, or callback chaining.
+ * @remarks May trigger Hide Mode payload handling, network calls, or local state updates.
+ */
+export async function getAiLevels() {
+  return apiFetch<AiLevelsResponse>("/api/v1/ai/levels", {
+    context: "AI levels",
+    suppressErrorNotification: true,
+  })
+}
+
 /**
  * Runs `upgradeAiLevel` as part of the frontend API client workflow.
  *
@@ -2435,6 +2610,23 @@ export async function upgradeAiLevel(payload: { target_level: number; onchain_tx
   })
 }
 
+/**
+ * Runs `downgradeAiLevel` as part of the frontend API client workflow.
+ *
+ * @param payload - Input used to compute or dispatch the `downgradeAiLevel` operation.
+ *
+ * @returns Result used by UI state, request lifecycle, or callback chaining.
+ * @remarks Upgrade payments are burned on-chain, so downgrades never refund CAREL.
+ */
+export async function downgradeAiLevel(payload: { target_level: number }) {
+  return apiFetch<AiDowngradeLevelResponse>("/api/v1/ai/downgrade", {
+    method: "POST",
+    body: JSON.stringify(payload),
+    context: "AI level downgrade",
+    suppressErrorNotification: true,
+  })
+}
+
 /**
  * Runs `prepareAiAction` as part of the frontend API client workflow.
  *